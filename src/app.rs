@@ -1,8 +1,8 @@
 use std::{
     cmp::{max, min},
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     path::PathBuf,
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use unicode_segmentation::GraphemeCursor;
@@ -10,31 +10,52 @@ use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use typed_builder::*;
 
+use alerts::Alert;
 use data_farmer::*;
 use data_harvester::temperature;
+use derived_metrics::DerivedMetrics;
+use hooks::{Hook, HookContext, HookKey};
+use inspector::{get_process_command, inspect_process};
+use keybindings::*;
+use layout_editor::*;
 use layout_manager::*;
 pub use states::*;
+#[cfg(target_os = "linux")]
+use threads::ThreadReader;
 
 use crate::{
-    components::text_table::SortState,
+    components::text_table::{SortState, TableComponentHeader, TableComponentState},
     constants,
-    data_conversion::ConvertedData,
+    data_conversion::{self, ConvertedData, CpuEntry, Labels},
+    options::layout_options::Row,
     options::Config,
     options::ConfigFlags,
     options::WidgetIdEnabled,
+    remote::{HostStatus, RemoteHost},
     units::data_units::DataUnit,
     utils::error::{BottomError, Result},
     Pid,
 };
 
-use self::widgets::{ProcWidget, ProcWidgetMode};
+use self::widgets::{ProcWidget, ProcWidgetMode, TableFilter};
 
+pub mod affinity;
+pub mod alerts;
+pub mod clipboard;
 pub mod data_farmer;
 pub mod data_harvester;
+pub mod derived_metrics;
+pub mod hooks;
+pub mod inspector;
+pub mod keybindings;
+pub mod layout_editor;
 pub mod layout_manager;
 mod process_killer;
+pub mod process_priority;
 pub mod query;
 pub mod states;
+pub mod systemd;
+pub mod threads;
 pub mod widgets;
 
 const MAX_SEARCH_LENGTH: usize = 200;
@@ -47,29 +68,68 @@ pub enum AxisScaling {
 
 /// AppConfigFields is meant to cover basic fields that would normally be set
 /// by config files or launch options.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct AppConfigFields {
     pub update_rate_in_milliseconds: u64,
+    pub harvest_intervals: data_harvester::HarvestIntervals,
     pub temperature_type: temperature::TemperatureType,
+    pub temp_thresholds: temperature::TempThresholds,
+    pub labels: Labels,
     pub use_dot: bool,
+    pub no_unicode: bool,
+    pub screen_reader: bool,
     pub left_legend: bool,
     pub show_average_cpu: bool,
     pub use_current_cpu_total: bool,
     pub use_basic_mode: bool,
     pub default_time_value: u64,
     pub time_interval: u64,
+    /// How long data is kept for graphs, in milliseconds.
+    pub retention: u64,
     pub hide_time: bool,
     pub autohide_time: bool,
     pub use_old_network_legend: bool,
     pub table_gap: u16,
     pub disable_click: bool,
+    /// Whether the Termux profile is used, which skips what Android doesn't let Termux read, and
+    /// makes small click targets easier to tap.
+    pub use_termux_profile: bool,
     pub no_write: bool,
+    pub save_layout: bool,
     pub show_table_scroll_position: bool,
+    pub show_mini_widgets: bool,
+    /// The smallest height of a layout row, in lines, or 0 to always fit the layout on the screen.
+    pub min_row_height: u16,
     pub is_advanced_kill: bool,
+    pub show_process_memory_details: bool,
+    pub precise_process_memory: bool,
+    pub show_process_gpu: bool,
+    pub show_disk_smart: bool,
+    pub show_disk_activity: bool,
+    /// Disks estimated to fill up within this many milliseconds are highlighted.
+    pub disk_full_warning: u64,
+    pub show_swap_activity: bool,
+    pub show_peaks: bool,
+    /// How far back the comparison overlay on graphs is from, in milliseconds, if set.
+    pub compare_offset: Option<u64>,
+    /// Whether to shade gaps in graphs, like from the system sleeping.
+    pub shade_gaps: bool,
     // TODO: Remove these, move network details state-side.
     pub network_unit_type: DataUnit,
     pub network_scale_type: AxisScaling,
     pub network_use_binary_prefix: bool,
+    pub show_network_errors: bool,
+    pub network_split: bool,
+    pub show_network_stats: bool,
+    pub stacked_cpu: bool,
+    pub show_cpu_breakdown: bool,
+    pub show_cpu_top_process: bool,
+    pub show_graph_stats: bool,
+    pub show_network_addresses: bool,
+    pub show_public_ip: bool,
+    pub latency_hosts: Vec<data_harvester::latency::LatencyHost>,
+    pub plugins: Vec<data_harvester::plugins::PluginConfig>,
+    pub derived_metrics: Vec<derived_metrics::DerivedMetricConfig>,
 }
 
 /// For filtering out information
@@ -101,9 +161,43 @@ pub struct App {
     #[builder(default, setter(skip))]
     to_delete_process_list: Option<(String, Vec<Pid>)>,
 
+    /// The error from the last time the config file was reloaded, which is shown until closed.
+    #[builder(default, setter(skip))]
+    pub config_error: Option<String>,
+
+    /// Mistakes in the config file that didn't stop it from being used, which are shown until
+    /// closed.
+    #[builder(default, setter(skip))]
+    pub config_warnings: Vec<String>,
+
+    /// Whether the diagnostics dialog, which lists what isn't being collected, is shown.
+    #[builder(default, setter(skip))]
+    pub is_showing_diagnostics: bool,
+
+    /// Whether time graphs are overlaid with their values from `compare_offset` ago.
+    #[builder(default)]
+    pub is_comparing: bool,
+
+    /// A short message shown at the bottom of the screen, and when it was set.
+    #[builder(default, setter(skip))]
+    pub status_message: Option<(String, Instant)>,
+
     #[builder(default = false, setter(skip))]
     pub is_frozen: bool,
 
+    /// Whether data collection is paused. Unlike freezing, nothing is harvested at all.
+    #[builder(default = false, setter(skip))]
+    pub is_paused: bool,
+
+    /// Whether the overlay with bottom's own usage and how long collecting and drawing take is
+    /// shown.
+    #[builder(default, setter(skip))]
+    pub is_showing_debug_overlay: bool,
+
+    /// How long the last frame took to draw.
+    #[builder(default, setter(skip))]
+    pub last_render_duration: Duration,
+
     #[builder(default = Instant::now(), setter(skip))]
     last_key_press: Instant,
 
@@ -119,6 +213,36 @@ pub struct App {
     #[builder(default, setter(skip))]
     pub help_dialog_state: AppHelpDialogState,
 
+    #[builder(default, setter(skip))]
+    pub layout_edit_state: AppLayoutEditState,
+
+    #[builder(default, setter(skip))]
+    pub process_graph_state: AppProcessGraphState,
+
+    #[builder(default, setter(skip))]
+    pub process_inspector_state: AppProcessInspectorState,
+
+    #[builder(default, setter(skip))]
+    pub process_command_state: AppProcessCommandState,
+
+    #[builder(default, setter(skip))]
+    pub process_threads_state: AppProcessThreadsState,
+
+    #[builder(default, setter(skip))]
+    pub process_priority_state: AppProcessPriorityState,
+
+    #[builder(default, setter(skip))]
+    pub process_affinity_state: AppProcessAffinityState,
+
+    #[builder(default, setter(skip))]
+    pub kill_queue_state: AppKillQueueState,
+
+    #[builder(default, setter(skip))]
+    pub unit_control_state: AppUnitControlState,
+
+    #[builder(default, setter(skip))]
+    pub options_menu_state: AppOptionsMenuState,
+
     #[builder(default = false, setter(skip))]
     pub is_expanded: bool,
 
@@ -131,10 +255,107 @@ pub struct App {
     #[builder(default = false, setter(skip))]
     pub basic_mode_use_percent: bool,
 
-    #[cfg(target_family = "unix")]
     #[builder(default, setter(skip))]
     pub user_table: data_harvester::processes::UserTable,
 
+    #[builder(default, setter(skip))]
+    pub layout_splits: Vec<LayoutSplit>,
+
+    /// How many lines the layout is scrolled down by, if it's taller than the screen.
+    #[builder(default, setter(skip))]
+    pub layout_scroll: u16,
+
+    #[builder(default, setter(skip))]
+    layout_drag: Option<LayoutSplit>,
+
+    #[builder(default, setter(skip))]
+    pub pending_layout_resize: Option<(LayoutSplit, u16)>,
+
+    #[builder(default, setter(skip))]
+    pub pending_layout_edit: Option<Vec<Row>>,
+
+    /// The index of the layout being shown, in [`Config::layout_names`].
+    #[builder(default, setter(skip))]
+    pub current_layout: usize,
+
+    /// The index of a layout to switch to, which is done by the main loop.
+    #[builder(default, setter(skip))]
+    pub pending_layout_switch: Option<usize>,
+
+    /// The widget states of the layouts that were switched away from, by name.
+    #[builder(default, setter(skip))]
+    pub saved_layout_states: HashMap<String, SavedLayoutState>,
+
+    /// The widget shown in each tabbed column, by the ID of the column's first widget.
+    #[builder(default, setter(skip))]
+    pub selected_tabs: HashMap<u64, u64>,
+
+    /// The tab headers of the tabbed columns when drawn, for mouse click detection, as the top
+    /// left corner, bottom right corner, and widget ID of each.
+    #[builder(default, setter(skip))]
+    pub layout_tab_click_locs: Vec<((u16, u16), (u16, u16), u64)>,
+
+    /// Whether to switch to the next colour scheme, which is done by the painter.
+    #[builder(default = false, setter(skip))]
+    pub pending_colour_scheme_cycle: bool,
+
+    /// Whether to save the selected graph to a file, which is done by the painter.
+    #[builder(default = false, setter(skip))]
+    pub pending_graph_export: bool,
+
+    /// Whether [`AppConfigFields`] was changed in a way the collection thread needs to know about.
+    #[builder(default = false, setter(skip))]
+    pub pending_config_update: bool,
+
+    /// Whether to save the options from the options menu to the config file.
+    #[builder(default = false, setter(skip))]
+    pub pending_options_save: bool,
+
+    #[builder(default)]
+    pub key_bindings: KeyBindings,
+
+    #[builder(default)]
+    pub hooks: Vec<Hook>,
+
+    #[builder(default)]
+    pub alerts: Vec<Alert>,
+
+    #[builder(default)]
+    pub derived_metrics: DerivedMetrics,
+
+    /// The command of a hook to run, and whether to pause after it. This is done by the main
+    /// loop, as the terminal has to be handed over to the command.
+    #[builder(default, setter(skip))]
+    pub pending_hook: Option<(String, bool)>,
+
+    /// Text to copy to the clipboard, which is done by the main loop as it's partly written to the
+    /// terminal.
+    #[builder(default, setter(skip))]
+    pub pending_clipboard: Option<String>,
+
+    /// The agents the data comes from, which is empty if it's collected locally.
+    #[builder(default)]
+    pub remote_hosts: Vec<RemoteHost>,
+
+    /// The index of the remote host being shown.
+    #[builder(default, setter(skip))]
+    pub selected_host: usize,
+
+    /// Whether the collected data has to be converted again, even though none arrived, which is
+    /// done by the main loop.
+    #[builder(default = false, setter(skip))]
+    pub pending_data_conversion: bool,
+
+    /// Whether the network filter changed, so the collection thread needs to know about it, which
+    /// is done by the main loop.
+    #[builder(default = false, setter(skip))]
+    pub pending_net_query_update: bool,
+
+    /// Whether data collection was paused or resumed, so the collection thread needs to know about
+    /// it, which is done by the main loop.
+    #[builder(default = false, setter(skip))]
+    pub pending_pause_update: bool,
+
     pub cpu_state: CpuState,
     pub mem_state: MemState,
     pub net_state: NetState,
@@ -142,6 +363,15 @@ pub struct App {
     pub temp_state: TempState,
     pub disk_state: DiskState,
     pub battery_state: BatteryState,
+    pub power_state: PowerState,
+    pub pressure_state: PressureState,
+    pub load_avg_state: LoadAvgState,
+    pub latency_state: LatencyState,
+    pub plugin_state: PluginState,
+    pub frequency_state: FrequencyState,
+    pub cgroup_state: CgroupState,
+    pub event_state: EventState,
+    pub user_state: UserState,
     pub basic_table_widget_state: Option<BasicTableWidgetState>,
     pub app_config_fields: AppConfigFields,
     pub widget_map: HashMap<u64, BottomWidget>,
@@ -162,6 +392,33 @@ const MAX_SIGNAL: usize = 31;
 #[cfg(target_os = "freebsd")]
 const MAX_SIGNAL: usize = 33;
 
+/// How many cells outside of a small click target, like a button or a tab, still hit it with the
+/// Termux profile, as taps on a touch screen are less precise than mouse clicks.
+const TOUCH_HIT_PADDING: u16 = 1;
+
+/// Returns the index of the target hit at (x, y), given the top-left and bottom-right corners of
+/// each target, which are both inclusive. If no target is hit, the closest one that's within
+/// `padding` cells is hit instead.
+fn find_hit_target(
+    x: u16, y: u16, targets: &[((u16, u16), (u16, u16))], padding: u16,
+) -> Option<usize> {
+    targets
+        .iter()
+        .enumerate()
+        .filter_map(|(index, ((tlc_x, tlc_y), (brc_x, brc_y)))| {
+            let distance_x = tlc_x.saturating_sub(x).max(x.saturating_sub(*brc_x));
+            let distance_y = tlc_y.saturating_sub(y).max(y.saturating_sub(*brc_y));
+            let distance = distance_x.max(distance_y);
+            if distance <= padding {
+                Some((distance, index))
+            } else {
+                None
+            }
+        })
+        .min()
+        .map(|(_, index)| index)
+}
+
 impl App {
     pub fn reset(&mut self) {
         // Reset multi
@@ -170,6 +427,21 @@ impl App {
         // Reset dialog state
         self.help_dialog_state.is_showing_help = false;
         self.delete_dialog_state.is_showing_dd = false;
+        self.layout_edit_state.close();
+        if self.process_graph_state.is_showing_graph {
+            self.unpin_process();
+        }
+        self.process_inspector_state.is_showing_inspector = false;
+        self.process_command_state.is_showing_command = false;
+        self.process_threads_state.is_showing_threads = false;
+        self.process_priority_state.is_showing_priority = false;
+        self.process_affinity_state.is_showing_affinity = false;
+        self.kill_queue_state.is_showing_queue = false;
+        self.unit_control_state = AppUnitControlState::default();
+        self.options_menu_state.is_showing_options = false;
+        self.config_error = None;
+        self.config_warnings.clear();
+        self.is_showing_diagnostics = false;
 
         // Close all searches and reset it
         self.proc_state
@@ -181,6 +453,8 @@ impl App {
 
         // Clear current delete list
         self.to_delete_process_list = None;
+        self.delete_dialog_state.descendant_pids.clear();
+        self.delete_dialog_state.kill_descendants = false;
         self.dd_err = None;
 
         // Unfreeze.
@@ -203,6 +477,9 @@ impl App {
         self.delete_dialog_state.is_showing_dd = false;
         self.delete_dialog_state.selected_signal = KillSignal::default();
         self.delete_dialog_state.scroll_pos = 0;
+        self.delete_dialog_state.descendant_pids.clear();
+        self.delete_dialog_state.kill_descendants = false;
+        self.delete_dialog_state.is_killing_marked = false;
         self.to_delete_process_list = None;
         self.dd_err = None;
     }
@@ -210,9 +487,35 @@ impl App {
     pub fn on_esc(&mut self) {
         self.reset_multi_tap_keys();
         if self.is_in_dialog() {
-            if self.help_dialog_state.is_showing_help {
+            if self.config_error.is_some() {
+                self.config_error = None;
+            } else if !self.config_warnings.is_empty() {
+                self.config_warnings.clear();
+            } else if self.is_showing_diagnostics {
+                self.is_showing_diagnostics = false;
+            } else if self.help_dialog_state.is_showing_help {
                 self.help_dialog_state.is_showing_help = false;
                 self.help_dialog_state.scroll_state.current_scroll_index = 0;
+            } else if self.layout_edit_state.is_editing {
+                self.layout_edit_state.close();
+            } else if self.process_graph_state.is_showing_graph {
+                self.unpin_process();
+            } else if self.process_inspector_state.is_showing_inspector {
+                self.close_process_inspector();
+            } else if self.process_command_state.is_showing_command {
+                self.close_process_command();
+            } else if self.process_threads_state.is_showing_threads {
+                self.close_process_threads();
+            } else if self.process_priority_state.is_showing_priority {
+                self.close_priority_dialog();
+            } else if self.process_affinity_state.is_showing_affinity {
+                self.close_affinity_dialog();
+            } else if self.kill_queue_state.is_showing_queue {
+                self.close_kill_queue();
+            } else if self.unit_control_state.pending.is_some() {
+                self.close_unit_control();
+            } else if self.options_menu_state.is_showing_options {
+                self.close_options_menu();
             } else {
                 self.close_dd();
             }
@@ -231,6 +534,11 @@ impl App {
                             self.is_force_redraw = true;
                             return;
                         }
+
+                        if pws.clear_marks() {
+                            pws.force_data_update();
+                            return;
+                        }
                     }
                 }
                 BottomWidgetType::ProcSearch => {
@@ -259,6 +567,20 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Disk | BottomWidgetType::Temp | BottomWidgetType::Net => {
+                    // Close the filter first, and clear it if it's already closed.
+                    if let Some(filter) = self.get_mut_filter() {
+                        if filter.is_open() {
+                            filter.set_open(false);
+                            self.is_force_redraw = true;
+                            return;
+                        } else if !filter.query().is_empty() {
+                            self.change_filter(TableFilter::clear);
+                            self.is_force_redraw = true;
+                            return;
+                        }
+                    }
+                }
                 _ => {}
             }
 
@@ -270,19 +592,102 @@ impl App {
     }
 
     pub fn is_in_search_widget(&self) -> bool {
-        matches!(
-            self.current_widget.widget_type,
-            BottomWidgetType::ProcSearch
-        )
+        match self.current_widget.widget_type {
+            BottomWidgetType::ProcSearch => true,
+            _ => self.is_filter_open(),
+        }
+    }
+
+    /// Returns the filter of the current widget, if it has one.
+    fn get_mut_filter(&mut self) -> Option<&mut TableFilter> {
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Disk => self
+                .disk_state
+                .get_mut_widget_state(widget_id)
+                .map(|disk_widget_state| &mut disk_widget_state.filter),
+            BottomWidgetType::Temp => self
+                .temp_state
+                .get_mut_widget_state(widget_id)
+                .map(|temp_widget_state| &mut temp_widget_state.filter),
+            BottomWidgetType::Net => Some(&mut self.net_state.filter),
+            _ => None,
+        }
+    }
+
+    /// Whether the filter of the current widget is being typed in.
+    fn is_filter_open(&self) -> bool {
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Disk => matches!(
+                self.disk_state.get_widget_state(widget_id),
+                Some(disk_widget_state) if disk_widget_state.filter.is_open()
+            ),
+            BottomWidgetType::Temp => matches!(
+                self.temp_state.get_widget_state(widget_id),
+                Some(temp_widget_state) if temp_widget_state.filter.is_open()
+            ),
+            BottomWidgetType::Net => self.net_state.filter.is_open(),
+            _ => false,
+        }
+    }
+
+    /// Changes the filter of the current widget, and updates what's shown to match. The network
+    /// filter is passed on to the collection thread by the main loop.
+    fn change_filter(&mut self, change: impl FnOnce(&mut TableFilter)) {
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Disk => {
+                if let Some(disk_widget_state) = self.disk_state.get_mut_widget_state(widget_id) {
+                    disk_widget_state.change_filter(change);
+                }
+            }
+            BottomWidgetType::Temp => {
+                if let Some(temp_widget_state) = self.temp_state.get_mut_widget_state(widget_id) {
+                    temp_widget_state.change_filter(change);
+                }
+            }
+            BottomWidgetType::Net => {
+                change(&mut self.net_state.filter);
+                self.pending_net_query_update = true;
+                self.pending_data_conversion = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// Changes the filter of the current widget if it's being typed in. Returns whether it was.
+    fn change_open_filter(&mut self, change: impl FnOnce(&mut TableFilter)) -> bool {
+        if self.is_filter_open() {
+            self.change_filter(change);
+            true
+        } else {
+            false
+        }
     }
 
     fn reset_multi_tap_keys(&mut self) {
         self.awaiting_second_char = false;
         self.second_char = None;
+        self.key_bindings.reset();
     }
 
     fn is_in_dialog(&self) -> bool {
-        self.help_dialog_state.is_showing_help || self.delete_dialog_state.is_showing_dd
+        self.config_error.is_some()
+            || !self.config_warnings.is_empty()
+            || self.is_showing_diagnostics
+            || self.help_dialog_state.is_showing_help
+            || self.delete_dialog_state.is_showing_dd
+            || self.layout_edit_state.is_editing
+            || self.process_graph_state.is_showing_graph
+            || self.process_inspector_state.is_showing_inspector
+            || self.process_command_state.is_showing_command
+            || self.process_threads_state.is_showing_threads
+            || self.process_priority_state.is_showing_priority
+            || self.process_affinity_state.is_showing_affinity
+            || self.kill_queue_state.is_showing_queue
+            || self.unit_control_state.pending.is_some()
+            || self.options_menu_state.is_showing_options
     }
 
     fn ignore_normal_keybinds(&self) -> bool {
@@ -311,6 +716,14 @@ impl App {
                         proc_widget_state.toggle_tab();
                     }
                 }
+                BottomWidgetType::Battery => {
+                    if let Some(battery_widget_state) = self
+                        .battery_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        battery_widget_state.is_graph_mode = !battery_widget_state.is_graph_mode;
+                    }
+                }
                 _ => {}
             }
         }
@@ -333,12 +746,44 @@ impl App {
                         self.is_force_redraw = true;
                     }
                 }
+                BottomWidgetType::Disk | BottomWidgetType::Temp | BottomWidgetType::Net => {
+                    if let Some(filter) = self.get_mut_filter() {
+                        filter.set_open(true);
+                        self.is_force_redraw = true;
+                    }
+                }
                 _ => {}
             }
         }
     }
 
     pub fn toggle_sort(&mut self) {
+        // The disk and temperature tables don't have a sort menu, so this just sorts by the next
+        // column instead.
+        match &self.current_widget.widget_type {
+            BottomWidgetType::Disk => {
+                if let Some(disk_widget_state) = self
+                    .disk_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    disk_widget_state.table_state.cycle_sort_column();
+                    disk_widget_state.force_data_update();
+                }
+                return;
+            }
+            BottomWidgetType::Temp => {
+                if let Some(temp_widget_state) = self
+                    .temp_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    temp_widget_state.table_state.cycle_sort_column();
+                    temp_widget_state.force_data_update();
+                }
+                return;
+            }
+            _ => {}
+        }
+
         let widget_id = self.current_widget.widget_id
             - match &self.current_widget.widget_type {
                 BottomWidgetType::Proc => 0,
@@ -385,6 +830,32 @@ impl App {
                     }
                 }
             }
+            BottomWidgetType::Disk => {
+                if let Some(disk_widget_state) = self
+                    .disk_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    if let SortState::Sortable(state) =
+                        &mut disk_widget_state.table_state.sort_state
+                    {
+                        state.toggle_order();
+                        disk_widget_state.force_data_update();
+                    }
+                }
+            }
+            BottomWidgetType::Temp => {
+                if let Some(temp_widget_state) = self
+                    .temp_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    if let SortState::Sortable(state) =
+                        &mut temp_widget_state.table_state.sort_state
+                    {
+                        state.toggle_order();
+                        temp_widget_state.force_data_update();
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -408,7 +879,30 @@ impl App {
         }
     }
 
+    pub fn toggle_network_totals(&mut self) {
+        if let BottomWidgetType::Net = self.current_widget.widget_type {
+            if let Some(net_widget_state) = self
+                .net_state
+                .widget_states
+                .get_mut(&self.current_widget.widget_id)
+            {
+                // If errors are shown, cycle through rates, totals, and errors.
+                let (show_totals, show_errors) =
+                    match (net_widget_state.show_totals, net_widget_state.show_errors) {
+                        (false, false) => (true, false),
+                        (true, _) if self.app_config_fields.show_network_errors => (false, true),
+                        _ => (false, false),
+                    };
+                net_widget_state.show_totals = show_totals;
+                net_widget_state.show_errors = show_errors;
+            }
+        }
+    }
+
     pub fn toggle_ignore_case(&mut self) {
+        if self.change_open_filter(TableFilter::toggle_ignore_case) {
+            return;
+        }
         let is_in_search_widget = self.is_in_search_widget();
         let mut is_case_sensitive: Option<bool> = None;
         if let Some(proc_widget_state) = self
@@ -459,6 +953,9 @@ impl App {
     }
 
     pub fn toggle_search_whole_word(&mut self) {
+        if self.change_open_filter(TableFilter::toggle_whole_word) {
+            return;
+        }
         let is_in_search_widget = self.is_in_search_widget();
         let mut is_searching_whole_word: Option<bool> = None;
         if let Some(proc_widget_state) = self
@@ -513,6 +1010,9 @@ impl App {
     }
 
     pub fn toggle_search_regex(&mut self) {
+        if self.change_open_filter(TableFilter::toggle_regex) {
+            return;
+        }
         let is_in_search_widget = self.is_in_search_widget();
         let mut is_searching_with_regex: Option<bool> = None;
         if let Some(proc_widget_state) = self
@@ -573,7 +1073,7 @@ impl App {
                     proc_widget_state.mode = ProcWidgetMode::Normal;
                     proc_widget_state.force_rerender_and_update();
                 }
-                ProcWidgetMode::Normal => {
+                ProcWidgetMode::Normal | ProcWidgetMode::User { .. } => {
                     proc_widget_state.mode = ProcWidgetMode::Tree {
                         collapsed_pids: Default::default(),
                     };
@@ -584,9 +1084,41 @@ impl App {
         }
     }
 
+    /// Toggles grouping processes by user.
+    pub fn toggle_user_grouping(&mut self) {
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .widget_states
+            .get_mut(&(self.current_widget.widget_id))
+        {
+            match proc_widget_state.mode {
+                ProcWidgetMode::User { .. } => {
+                    proc_widget_state.mode = ProcWidgetMode::Normal;
+                    proc_widget_state.force_rerender_and_update();
+                }
+                ProcWidgetMode::Normal | ProcWidgetMode::Tree { .. } => {
+                    proc_widget_state.mode = ProcWidgetMode::User {
+                        expanded_users: Default::default(),
+                    };
+                    proc_widget_state.force_rerender_and_update();
+                }
+                ProcWidgetMode::Grouped => {}
+            }
+        }
+    }
+
     /// One of two functions allowed to run while in a dialog...
     pub fn on_enter(&mut self) {
-        if self.delete_dialog_state.is_showing_dd {
+        if self.config_error.is_some() {
+            self.config_error = None;
+            self.is_force_redraw = true;
+        } else if !self.config_warnings.is_empty() {
+            self.config_warnings.clear();
+            self.is_force_redraw = true;
+        } else if self.is_showing_diagnostics {
+            self.is_showing_diagnostics = false;
+            self.is_force_redraw = true;
+        } else if self.delete_dialog_state.is_showing_dd {
             if self.dd_err.is_some() {
                 self.close_dd();
             } else if self.delete_dialog_state.selected_signal != KillSignal::Cancel {
@@ -610,23 +1142,134 @@ impl App {
                 self.delete_dialog_state.is_showing_dd = false;
             }
             self.is_force_redraw = true;
+        } else if self.process_priority_state.is_showing_priority {
+            #[cfg(target_family = "unix")]
+            self.apply_process_priority();
+        } else if self.process_affinity_state.is_showing_affinity {
+            #[cfg(target_os = "linux")]
+            self.apply_process_affinity();
+        } else if self.kill_queue_state.is_showing_queue {
+            self.kill_queued_processes();
+        } else if self.unit_control_state.pending.is_some() {
+            #[cfg(target_os = "linux")]
+            self.apply_unit_control();
+        } else if self.options_menu_state.is_showing_options {
+            self.change_selected_option(true);
+        } else if self.layout_edit_state.is_editing {
+            if self.layout_edit_state.has_widgets() {
+                self.layout_edit_state.error = None;
+                self.pending_layout_edit = Some(self.layout_edit_state.rows.clone());
+            } else {
+                self.layout_edit_state.error =
+                    Some("The layout must have at least one non-empty widget.".to_string());
+            }
+        } else if self.is_filter_open() {
+            if let Some(filter) = self.get_mut_filter() {
+                filter.set_open(false);
+            }
+            self.is_force_redraw = true;
         } else if !self.is_in_dialog() {
-            if let BottomWidgetType::ProcSort = self.current_widget.widget_type {
-                if let Some(proc_widget_state) = self
-                    .proc_state
-                    .widget_states
-                    .get_mut(&(self.current_widget.widget_id - 2))
+            match self.current_widget.widget_type {
+                BottomWidgetType::ProcSort => {
+                    if let Some(proc_widget_state) = self
+                        .proc_state
+                        .widget_states
+                        .get_mut(&(self.current_widget.widget_id - 2))
+                    {
+                        proc_widget_state.use_sort_table_value();
+                        self.move_widget_selection(&WidgetDirection::Right);
+                        self.is_force_redraw = true;
+                    }
+                }
+                BottomWidgetType::Users => self.show_user_processes(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Forgets the peak values seen so far, so they start again from the current values.
+    fn reset_peaks(&mut self) {
+        if !self.app_config_fields.show_peaks {
+            return;
+        }
+
+        self.data_collection.peaks = PeakData::default();
+        self.converted_data.peak_display = Default::default();
+        self.pending_data_conversion = true;
+        self.set_status_message("Reset the peak values".to_string());
+    }
+
+    /// Turns the comparison overlay on graphs on or off.
+    fn toggle_compare(&mut self) {
+        let compare_offset = match self.app_config_fields.compare_offset {
+            Some(compare_offset) => compare_offset,
+            None => {
+                self.set_status_message(
+                    "Set --compare to how far back to compare with, like 5m".to_string(),
+                );
+                return;
+            }
+        };
+
+        self.is_comparing = !self.is_comparing;
+        self.set_status_message(if self.is_comparing {
+            format!(
+                "Comparing with {} ago",
+                data_conversion::duration_string(compare_offset / 1000)
+            )
+        } else {
+            "Stopped comparing".to_string()
+        });
+    }
+
+    /// Selects a process widget, searching for the processes of the user selected in the users
+    /// widget.
+    fn show_user_processes(&mut self) {
+        if self.data_collection.user_session_harvest.is_empty() {
+            return;
+        }
+
+        let user = match self
+            .user_state
+            .get_widget_state(self.current_widget.widget_id)
+            .and_then(|state| {
+                self.converted_data
+                    .user_data
+                    .data
+                    .get(state.table_state.current_scroll_position)
+            }) {
+            Some(row) => row.row()[0].main_text().to_string(),
+            None => return,
+        };
+
+        let proc_widget_id = self
+            .widget_map
+            .values()
+            .filter(|widget| widget.widget_type == BottomWidgetType::Proc)
+            .map(|widget| widget.widget_id)
+            .min();
+
+        match proc_widget_id {
+            Some(proc_widget_id) => {
+                if let Some(proc_widget_state) =
+                    self.proc_state.get_mut_widget_state(proc_widget_id)
                 {
-                    proc_widget_state.use_sort_table_value();
-                    self.move_widget_selection(&WidgetDirection::Right);
-                    self.is_force_redraw = true;
+                    proc_widget_state
+                        .set_search_query(format!("user ~ \"^{}$\"", regex::escape(&user)));
                 }
+                self.is_expanded = false;
+                self.select_tab(proc_widget_id);
             }
+            None => self.set_status_message(
+                "A process widget is needed to show a user's processes".to_string(),
+            ),
         }
     }
 
     pub fn on_delete(&mut self) {
-        if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+        if self.layout_edit_state.is_editing {
+            self.layout_edit_state.remove_selected();
+        } else if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             let is_in_search_widget = self.is_in_search_widget();
             if let Some(proc_widget_state) = self
                 .proc_state
@@ -674,6 +1317,9 @@ impl App {
     }
 
     pub fn on_backspace(&mut self) {
+        if self.change_open_filter(TableFilter::pop_char) {
+            return;
+        }
         if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             let is_in_search_widget = self.is_in_search_widget();
             if let Some(proc_widget_state) = self
@@ -768,6 +1414,23 @@ impl App {
             self.decrement_position_count();
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
+        } else if self.process_inspector_state.is_showing_inspector {
+            self.inspector_scroll_up();
+        } else if self.process_command_state.is_showing_command {
+            self.command_scroll_up();
+        } else if self.process_threads_state.is_showing_threads {
+            self.threads_scroll_up();
+        } else if self.process_priority_state.is_showing_priority {
+            self.process_priority_state.select_previous();
+        } else if self.options_menu_state.is_showing_options {
+            self.options_menu_state.select_previous();
+        } else if self.process_affinity_state.is_showing_affinity {
+            self.process_affinity_state
+                .select_previous(AppProcessAffinityState::CPUS_PER_ROW);
+        } else if self.kill_queue_state.is_showing_queue {
+            self.kill_queue_state.select_previous();
+        } else if self.layout_edit_state.is_editing {
+            self.layout_edit_state.select_previous();
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_os = "windows")]
             self.on_right_key();
@@ -789,6 +1452,23 @@ impl App {
             self.increment_position_count();
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
+        } else if self.process_inspector_state.is_showing_inspector {
+            self.inspector_scroll_down();
+        } else if self.process_command_state.is_showing_command {
+            self.command_scroll_down();
+        } else if self.process_threads_state.is_showing_threads {
+            self.threads_scroll_down();
+        } else if self.process_priority_state.is_showing_priority {
+            self.process_priority_state.select_next();
+        } else if self.options_menu_state.is_showing_options {
+            self.options_menu_state.select_next();
+        } else if self.process_affinity_state.is_showing_affinity {
+            self.process_affinity_state
+                .select_next(AppProcessAffinityState::CPUS_PER_ROW);
+        } else if self.kill_queue_state.is_showing_queue {
+            self.kill_queue_state.select_next();
+        } else if self.layout_edit_state.is_editing {
+            self.layout_edit_state.select_next();
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_os = "windows")]
             self.on_left_key();
@@ -846,8 +1526,22 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Proc
+                | BottomWidgetType::Disk
+                | BottomWidgetType::Temp
+                | BottomWidgetType::Cgroup
+                | BottomWidgetType::Events
+                | BottomWidgetType::Users => self.handle_scroll_left(),
                 _ => {}
             }
+        } else if self.process_priority_state.is_showing_priority {
+            self.process_priority_state.change_selected(false);
+        } else if self.options_menu_state.is_showing_options {
+            self.change_selected_option(false);
+        } else if self.process_affinity_state.is_showing_affinity {
+            self.process_affinity_state.select_previous(1);
+        } else if self.layout_edit_state.is_editing {
+            self.layout_edit_state.cycle_selected_type(false);
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
@@ -919,8 +1613,22 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::Proc
+                | BottomWidgetType::Disk
+                | BottomWidgetType::Temp
+                | BottomWidgetType::Cgroup
+                | BottomWidgetType::Events
+                | BottomWidgetType::Users => self.handle_scroll_right(),
                 _ => {}
             }
+        } else if self.process_priority_state.is_showing_priority {
+            self.process_priority_state.change_selected(true);
+        } else if self.options_menu_state.is_showing_options {
+            self.change_selected_option(true);
+        } else if self.process_affinity_state.is_showing_affinity {
+            self.process_affinity_state.select_next(1);
+        } else if self.layout_edit_state.is_editing {
+            self.layout_edit_state.cycle_selected_type(true);
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
@@ -1071,6 +1779,9 @@ impl App {
     }
 
     pub fn clear_search(&mut self) {
+        if self.change_open_filter(TableFilter::clear) {
+            return;
+        }
         if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             if let Some(proc_widget_state) = self
                 .proc_state
@@ -1147,12 +1858,46 @@ impl App {
 
     pub fn start_killing_process(&mut self) {
         self.reset_multi_tap_keys();
+        if !self.check_is_local() {
+            return;
+        }
 
         if let Some(pws) = self
             .proc_state
             .widget_states
             .get(&self.current_widget.widget_id)
         {
+            let marked_pids = pws.get_marked_pids();
+            if !marked_pids.is_empty() {
+                const MAX_SHOWN_PROCESSES: usize = 8;
+
+                let process_data = &self.data_collection.process_data;
+                let mut shown_processes = marked_pids
+                    .iter()
+                    .take(MAX_SHOWN_PROCESSES)
+                    .map(|pid| match process_data.process_harvest.get(pid) {
+                        Some(process) => format!("{} ({})", process.name, pid),
+                        None => pid.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if marked_pids.len() > MAX_SHOWN_PROCESSES {
+                    shown_processes.push_str(&format!(
+                        ", and {} more",
+                        marked_pids.len() - MAX_SHOWN_PROCESSES
+                    ));
+                }
+
+                self.delete_dialog_state.descendant_pids =
+                    process_data.get_descendant_pids(&marked_pids);
+                self.delete_dialog_state.kill_descendants = false;
+                self.delete_dialog_state.is_killing_marked = true;
+                self.to_delete_process_list = Some((shown_processes, marked_pids));
+                self.delete_dialog_state.is_showing_dd = true;
+                self.is_determining_widget_boundary = true;
+                return;
+            }
+
             if let Some(table_row) = pws
                 .table_data
                 .data
@@ -1160,18 +1905,18 @@ impl App {
             {
                 if let Some(col_value) = table_row.row().get(ProcWidget::PROC_NAME_OR_CMD) {
                     let val = col_value.main_text().to_string();
-                    if pws.is_using_command() {
-                        if let Some(pids) = self.data_collection.process_data.cmd_pid_map.get(&val)
-                        {
-                            let current_process = (val, pids.clone());
+                    let pid_map = if pws.is_using_command() {
+                        &self.data_collection.process_data.cmd_pid_map
+                    } else {
+                        &self.data_collection.process_data.name_pid_map
+                    };
+
+                    if let Some(pids) = pid_map.get(&val) {
+                        self.delete_dialog_state.descendant_pids =
+                            self.data_collection.process_data.get_descendant_pids(pids);
+                        self.delete_dialog_state.kill_descendants = false;
+                        self.delete_dialog_state.is_killing_marked = false;
 
-                            self.to_delete_process_list = Some(current_process);
-                            self.delete_dialog_state.is_showing_dd = true;
-                            self.is_determining_widget_boundary = true;
-                        }
-                    } else if let Some(pids) =
-                        self.data_collection.process_data.name_pid_map.get(&val)
-                    {
                         let current_process = (val, pids.clone());
 
                         self.to_delete_process_list = Some(current_process);
@@ -1250,7 +1995,16 @@ impl App {
                         return;
                     }
                 }
-            }
+            } else if self.is_filter_open() {
+                let has_room = matches!(
+                    self.get_mut_filter(),
+                    Some(filter) if UnicodeWidthStr::width(filter.query()) <= MAX_SEARCH_LENGTH
+                );
+                if has_room {
+                    self.change_filter(|filter| filter.push_char(caught_char));
+                }
+                return;
+            }
             self.handle_char(caught_char);
         } else if self.help_dialog_state.is_showing_help {
             match caught_char {
@@ -1265,7 +2019,98 @@ impl App {
                         }
                     }
                 }
-                'j' | 'k' | 'g' | 'G' => self.handle_char(caught_char),
+                _ => {
+                    // Only allow movement bindings while the help menu is open.
+                    if let KeyResult::Action(
+                        action @ (KeyAction::MoveUp
+                        | KeyAction::MoveDown
+                        | KeyAction::SkipToFirst
+                        | KeyAction::SkipToLast),
+                        _,
+                    ) = self.key_bindings.on_char(caught_char)
+                    {
+                        self.perform_key_action(action);
+                    }
+                }
+            }
+        } else if self.layout_edit_state.is_editing {
+            self.handle_layout_edit_char(caught_char);
+        } else if self.process_priority_state.is_showing_priority {
+            match caught_char {
+                'h' => self.on_left_key(),
+                'j' => self.on_down_key(),
+                'k' => self.on_up_key(),
+                'l' => self.on_right_key(),
+                _ => {}
+            }
+        } else if self.process_affinity_state.is_showing_affinity {
+            match caught_char {
+                'h' => self.on_left_key(),
+                'j' => self.on_down_key(),
+                'k' => self.on_up_key(),
+                'l' => self.on_right_key(),
+                ' ' => self.process_affinity_state.toggle_selected(),
+                'a' => self.process_affinity_state.allow_all(),
+                _ => {}
+            }
+        } else if self.kill_queue_state.is_showing_queue {
+            match caught_char {
+                'j' => self.on_down_key(),
+                'k' => self.on_up_key(),
+                'd' | 'x' => self.kill_queue_state.remove_selected(),
+                'c' => self.kill_queue_state.clear(),
+                _ => {}
+            }
+        } else if self.options_menu_state.is_showing_options {
+            match caught_char {
+                'h' => self.on_left_key(),
+                'j' => self.on_down_key(),
+                'k' => self.on_up_key(),
+                'l' | ' ' => self.on_right_key(),
+                's' => self.pending_options_save = true,
+                _ => {}
+            }
+        } else if self.process_graph_state.is_showing_graph {
+            if caught_char == 'v' {
+                self.unpin_process();
+            }
+        } else if self.process_inspector_state.is_showing_inspector {
+            // Only allow movement bindings and closing the inspector while it is open.
+            match self.key_bindings.on_char(caught_char) {
+                KeyResult::Action(
+                    action @ (KeyAction::MoveUp
+                    | KeyAction::MoveDown
+                    | KeyAction::SkipToFirst
+                    | KeyAction::SkipToLast),
+                    _,
+                ) => self.perform_key_action(action),
+                KeyResult::Action(KeyAction::InspectProcess, _) => self.close_process_inspector(),
+                _ => {}
+            }
+        } else if self.process_command_state.is_showing_command {
+            // Only allow movement bindings and closing the dialog while it is open.
+            match self.key_bindings.on_char(caught_char) {
+                KeyResult::Action(
+                    action @ (KeyAction::MoveUp
+                    | KeyAction::MoveDown
+                    | KeyAction::SkipToFirst
+                    | KeyAction::SkipToLast),
+                    _,
+                ) => self.perform_key_action(action),
+                KeyResult::Action(KeyAction::ShowCommand, _) => self.close_process_command(),
+                _ => {}
+            }
+        } else if self.process_threads_state.is_showing_threads {
+            // Only allow movement bindings and closing the dialog while it is open.
+            match self.key_bindings.on_char(caught_char) {
+                KeyResult::Action(
+                    action @ (KeyAction::MoveUp
+                    | KeyAction::MoveDown
+                    | KeyAction::SkipToFirst
+                    | KeyAction::SkipToLast),
+                    _,
+                ) => self.perform_key_action(action),
+                KeyResult::Action(KeyAction::ShowThreads, _) => self.close_process_threads(),
                 _ => {}
             }
         } else if self.delete_dialog_state.is_showing_dd {
@@ -1289,61 +2134,795 @@ impl App {
                         }
                     }
 
-                    if is_first_g {
-                        self.awaiting_second_char = true;
-                        self.second_char = Some('g');
-                    }
-                }
-                'G' => self.skip_to_last(),
-                _ => {}
-            }
+                    if is_first_g {
+                        self.awaiting_second_char = true;
+                        self.second_char = Some('g');
+                    }
+                }
+                'G' => self.skip_to_last(),
+                't' if !self.delete_dialog_state.descendant_pids.is_empty() => {
+                    self.delete_dialog_state.kill_descendants =
+                        !self.delete_dialog_state.kill_descendants;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Starts storing the usage of the selected process, and shows it in a graph.
+    fn pin_selected_process(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_widget_state(self.current_widget.widget_id)
+            {
+                if let Some(pid) = proc_widget_state.get_selected_pid() {
+                    if let Some(process) =
+                        self.data_collection.process_data.process_harvest.get(&pid)
+                    {
+                        self.process_graph_state.name = if proc_widget_state.is_using_command() {
+                            process.command.clone()
+                        } else {
+                            process.name.clone()
+                        };
+                        self.process_graph_state.pid = pid;
+                        self.process_graph_state.is_showing_graph = true;
+                        self.data_collection.pin_process(pid);
+                        self.update_pinned_process_data();
+                        self.is_force_redraw = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Stops storing the usage of the process being graphed, and closes the graph.
+    fn unpin_process(&mut self) {
+        self.data_collection
+            .unpin_process(self.process_graph_state.pid);
+        self.process_graph_state.is_showing_graph = false;
+        self.is_force_redraw = true;
+    }
+
+    /// Reads the details of the selected process, and shows them in a dialog.
+    fn inspect_selected_process(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_widget_state(self.current_widget.widget_id)
+            {
+                if let Some(pid) = proc_widget_state.get_selected_pid() {
+                    if let Some(process) =
+                        self.data_collection.process_data.process_harvest.get(&pid)
+                    {
+                        self.process_inspector_state.name = process.name.clone();
+                        self.process_inspector_state.pid = pid;
+                        self.process_inspector_state.details = Some(inspect_process(pid));
+                        self.process_inspector_state
+                            .scroll_state
+                            .current_scroll_index = 0;
+                        self.process_inspector_state.is_showing_inspector = true;
+                        self.is_force_redraw = true;
+                    }
+                }
+            }
+        }
+    }
+
+    fn close_process_inspector(&mut self) {
+        self.process_inspector_state.is_showing_inspector = false;
+        self.process_inspector_state.details = None;
+        self.is_force_redraw = true;
+    }
+
+    /// Shows the full command line of the selected process in a dialog.
+    fn show_selected_process_command(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_widget_state(self.current_widget.widget_id)
+            {
+                if let Some(pid) = proc_widget_state.get_selected_pid() {
+                    if let Some(process) =
+                        self.data_collection.process_data.process_harvest.get(&pid)
+                    {
+                        self.process_command_state.name = process.name.clone();
+                        self.process_command_state.pid = pid;
+                        self.process_command_state.command =
+                            Some(get_process_command(pid, &process.command));
+                        self.process_command_state.scroll_state.current_scroll_index = 0;
+                        self.process_command_state.is_showing_command = true;
+                        self.is_force_redraw = true;
+                    }
+                }
+            }
+        }
+    }
+
+    fn close_process_command(&mut self) {
+        self.process_command_state.is_showing_command = false;
+        self.process_command_state.command = None;
+        self.is_force_redraw = true;
+    }
+
+    /// Shows the threads of the selected process in a dialog, which is updated with the rest of
+    /// the data.
+    #[cfg(target_os = "linux")]
+    fn show_selected_process_threads(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_widget_state(self.current_widget.widget_id)
+            {
+                if let Some(pid) = proc_widget_state.get_selected_pid() {
+                    if let Some(process) =
+                        self.data_collection.process_data.process_harvest.get(&pid)
+                    {
+                        self.process_threads_state.name = process.name.clone();
+                        self.process_threads_state.pid = pid;
+                        self.process_threads_state.reader = ThreadReader::new(pid);
+                        self.process_threads_state.scroll_state.current_scroll_index = 0;
+                        self.process_threads_state.is_showing_threads = true;
+                        self.update_process_threads();
+                        self.is_force_redraw = true;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn update_process_threads(&mut self) {
+        let threads_state = &mut self.process_threads_state;
+        match threads_state.reader.read_threads() {
+            Ok(threads) => {
+                threads_state.threads = threads;
+                threads_state.error = None;
+            }
+            Err(err) => {
+                threads_state.threads.clear();
+                threads_state.error = Some(err);
+            }
+        }
+    }
+
+    fn close_process_threads(&mut self) {
+        self.process_threads_state.is_showing_threads = false;
+        self.process_threads_state.threads.clear();
+        self.is_force_redraw = true;
+    }
+
+    /// Reads the priority of the selected process, or the first marked process if any are marked,
+    /// and shows a dialog for changing the priority of them.
+    #[cfg(target_family = "unix")]
+    fn open_priority_dialog(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            let mut target_pids = self.get_target_pids();
+            if !target_pids.is_empty() {
+                let pid = target_pids.remove(0);
+                if let Some(process) = self.data_collection.process_data.process_harvest.get(&pid) {
+                    let priority_state = &mut self.process_priority_state;
+                    priority_state.name = process.name.clone();
+                    priority_state.pid = pid;
+                    priority_state.other_pids = target_pids;
+                    priority_state.selected_field = 0;
+                    priority_state.error = None;
+
+                    match process_priority::get_nice(pid) {
+                        Ok(nice) => priority_state.nice = nice,
+                        Err(err) => {
+                            priority_state.nice = 0;
+                            priority_state.error = Some(err.to_string());
+                        }
+                    }
+                    priority_state.initial_nice = priority_state.nice;
+
+                    #[cfg(target_os = "linux")]
+                    match process_priority::get_io_priority(pid) {
+                        Ok((io_class, io_level)) => {
+                            priority_state.io_class = io_class;
+                            priority_state.io_level = io_level;
+                        }
+                        Err(err) => {
+                            priority_state.io_class = process_priority::IoPriorityClass::None;
+                            priority_state.io_level = 0;
+                            priority_state.error.get_or_insert(err.to_string());
+                        }
+                    }
+                    priority_state.initial_io_priority =
+                        (priority_state.io_class, priority_state.io_level);
+
+                    priority_state.is_showing_priority = true;
+                    self.is_force_redraw = true;
+                }
+            }
+        }
+    }
+
+    /// Applies any changes made in the priority dialog to each of its processes, closing it if
+    /// they succeed.
+    #[cfg(target_family = "unix")]
+    fn apply_process_priority(&mut self) {
+        let priority_state = &mut self.process_priority_state;
+        let pids = std::iter::once(priority_state.pid)
+            .chain(priority_state.other_pids.iter().copied())
+            .collect::<Vec<_>>();
+
+        let result = if priority_state.nice != priority_state.initial_nice {
+            pids.iter()
+                .try_for_each(|pid| process_priority::set_nice(*pid, priority_state.nice))
+        } else {
+            Ok(())
+        };
+
+        #[cfg(target_os = "linux")]
+        let result = result.and_then(|_| {
+            if (priority_state.io_class, priority_state.io_level)
+                != priority_state.initial_io_priority
+            {
+                pids.iter().try_for_each(|pid| {
+                    process_priority::set_io_priority(
+                        *pid,
+                        priority_state.io_class,
+                        priority_state.io_level,
+                    )
+                })
+            } else {
+                Ok(())
+            }
+        });
+
+        match result {
+            Ok(()) => self.close_priority_dialog(),
+            Err(err) => priority_state.error = Some(err.to_string()),
+        }
+    }
+
+    fn close_priority_dialog(&mut self) {
+        self.process_priority_state.is_showing_priority = false;
+        self.process_priority_state.error = None;
+        self.is_force_redraw = true;
+    }
+
+    /// Reads the CPU affinity of the selected process, and shows a dialog for changing it.
+    #[cfg(target_os = "linux")]
+    fn open_affinity_dialog(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_widget_state(self.current_widget.widget_id)
+            {
+                if let Some(pid) = proc_widget_state.get_selected_pid() {
+                    if let Some(process) =
+                        self.data_collection.process_data.process_harvest.get(&pid)
+                    {
+                        let affinity_state = &mut self.process_affinity_state;
+                        affinity_state.name = process.name.clone();
+                        affinity_state.pid = pid;
+                        affinity_state.selected_cpu = 0;
+
+                        match affinity::get_affinity(pid) {
+                            Ok(cpus) => {
+                                affinity_state.cpus = cpus;
+                                affinity_state.error = None;
+                            }
+                            Err(err) => {
+                                affinity_state.cpus.clear();
+                                affinity_state.error = Some(err.to_string());
+                            }
+                        }
+                        affinity_state.initial_cpus = affinity_state.cpus.clone();
+
+                        affinity_state.is_showing_affinity = true;
+                        self.is_force_redraw = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Applies the affinity chosen in the affinity dialog if it was changed, closing it if this
+    /// succeeds.
+    #[cfg(target_os = "linux")]
+    fn apply_process_affinity(&mut self) {
+        let affinity_state = &mut self.process_affinity_state;
+
+        let result = if affinity_state.cpus != affinity_state.initial_cpus {
+            affinity::set_affinity(affinity_state.pid, &affinity_state.cpus)
+        } else {
+            Ok(())
+        };
+
+        match result {
+            Ok(()) => self.close_affinity_dialog(),
+            Err(err) => affinity_state.error = Some(err.to_string()),
+        }
+    }
+
+    fn close_affinity_dialog(&mut self) {
+        self.process_affinity_state.is_showing_affinity = false;
+        self.process_affinity_state.error = None;
+        self.is_force_redraw = true;
+    }
+
+    /// Runs the hook bound to a function key, if there is one. Returns whether there was.
+    pub fn on_function_key(&mut self, number: u8) -> bool {
+        if self.is_in_dialog() || self.is_in_search_widget() {
+            return false;
+        }
+
+        match self
+            .hooks
+            .iter()
+            .position(|hook| hook.key == HookKey::Function(number))
+        {
+            Some(index) => {
+                self.run_hook(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Fills in the placeholders of a hook's command, and leaves it for the main loop to run.
+    fn run_hook(&mut self, index: usize) {
+        if !self.check_is_local() {
+            return;
+        }
+        if let Some(hook) = self.hooks.get(index) {
+            match hooks::expand_command(&hook.command, &self.get_hook_context()) {
+                Ok(command) => self.pending_hook = Some((command, hook.pause)),
+                Err(err) => self.set_status_message(err),
+            }
+        }
+    }
+
+    /// Returns the process or disk selected in the current widget, for filling in hooks.
+    fn get_hook_context(&self) -> HookContext {
+        let mut context = HookContext::default();
+        match self.current_widget.widget_type {
+            BottomWidgetType::Proc => {
+                if let Some(proc_widget_state) = self
+                    .proc_state
+                    .get_widget_state(self.current_widget.widget_id)
+                {
+                    if let Some(pid) = proc_widget_state.get_selected_pid() {
+                        context.pid = Some(pid);
+                        if let Some(process) =
+                            self.data_collection.process_data.process_harvest.get(&pid)
+                        {
+                            context.name = Some(process.name.clone());
+                            context.user = Some(process.user.to_string());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Disk => {
+                if let Some(disk_widget_state) = self
+                    .disk_state
+                    .get_widget_state(self.current_widget.widget_id)
+                {
+                    context.mount = disk_widget_state.get_selected_mount().map(str::to_string);
+                }
+            }
+            _ => {}
+        }
+
+        context
+    }
+
+    /// Adds the marked processes, or the selected process if none are marked, to the kill queue
+    /// without killing them yet.
+    fn queue_selected_processes(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            let process_harvest = &self.data_collection.process_data.process_harvest;
+            let mut num_added = 0;
+            for pid in self.get_target_pids() {
+                let (name, start_time) = process_harvest
+                    .get(&pid)
+                    .map(|process| (process.name.clone(), process.start_time))
+                    .unwrap_or_default();
+                if self.kill_queue_state.add(pid, name, start_time) {
+                    num_added += 1;
+                }
+            }
+
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                if proc_widget_state.clear_marks() {
+                    proc_widget_state.force_data_update();
+                }
+            }
+
+            let num_queued = self.kill_queue_state.queue.len();
+            self.set_status_message(format!(
+                "Queued {} process{} to kill, {} in the queue",
+                num_added,
+                if num_added == 1 { "" } else { "es" },
+                num_queued
+            ));
+        }
+    }
+
+    fn open_kill_queue(&mut self) {
+        self.kill_queue_state.selected_entry = 0;
+        self.kill_queue_state.error = None;
+        self.kill_queue_state.is_showing_queue = true;
+        self.is_force_redraw = true;
+    }
+
+    fn close_kill_queue(&mut self) {
+        self.kill_queue_state.is_showing_queue = false;
+        self.kill_queue_state.error = None;
+        self.is_force_redraw = true;
+    }
+
+    /// Kills every process in the kill queue that's still running, closing the queue if this
+    /// succeeds. Processes that couldn't be killed are left in the queue.
+    fn kill_queued_processes(&mut self) {
+        let process_harvest = &self.data_collection.process_data.process_harvest;
+        let mut num_killed = 0;
+        let mut errors = Vec::new();
+        let mut replaced = Vec::new();
+
+        self.kill_queue_state.queue.retain(|queued| {
+            // Processes that have already exited have nothing left to kill, and their PID may have
+            // been given to an unrelated process since they were queued.
+            match process_harvest.get(&queued.pid) {
+                None => return false,
+                Some(process) if !queued.is_same_process(process) => {
+                    replaced.push(format!("{} ({})", queued.name, queued.pid));
+                    return false;
+                }
+                Some(_) => {}
+            }
+
+            #[cfg(target_family = "unix")]
+            let result = process_killer::kill_process_given_pid(queued.pid, libc::SIGTERM as usize);
+            #[cfg(target_os = "windows")]
+            let result = process_killer::kill_process_given_pid(queued.pid);
+
+            match result {
+                Ok(()) => {
+                    num_killed += 1;
+                    false
+                }
+                Err(err) => {
+                    errors.push(format!("{} ({}): {}", queued.name, queued.pid, err));
+                    true
+                }
+            }
+        });
+        self.kill_queue_state.selected_entry = 0;
+
+        let skipped = if replaced.is_empty() {
+            String::new()
+        } else {
+            format!(
+                ", skipped {} as {} exited and the PID is now used by another process",
+                replaced.join(", "),
+                if replaced.len() == 1 { "it" } else { "they" }
+            )
+        };
+        if errors.is_empty() {
+            self.close_kill_queue();
+            self.set_status_message(format!(
+                "Killed {} queued process{}{}",
+                num_killed,
+                if num_killed == 1 { "" } else { "es" },
+                skipped
+            ));
+        } else {
+            self.kill_queue_state.error = Some(errors.join("; ") + &skipped);
+        }
+    }
+
+    fn open_options_menu(&mut self) {
+        self.options_menu_state.is_showing_options = true;
+        self.options_menu_state.save_result = None;
+        self.is_force_redraw = true;
+    }
+
+    fn close_options_menu(&mut self) {
+        self.options_menu_state.is_showing_options = false;
+        self.options_menu_state.save_result = None;
+        self.is_force_redraw = true;
+    }
+
+    /// Whether there are process widgets, and all of them are in the given mode.
+    pub fn are_all_processes_in_mode(&self, is_in_mode: impl Fn(&ProcWidgetMode) -> bool) -> bool {
+        !self.proc_state.widget_states.is_empty()
+            && self
+                .proc_state
+                .widget_states
+                .values()
+                .all(|proc_widget_state| is_in_mode(&proc_widget_state.mode))
+    }
+
+    /// Returns the current value of an option in the options menu, as shown in the menu.
+    pub fn get_option_value(&self, entry: OptionsMenuEntry) -> &'static str {
+        let on_or_off = |is_on: bool| if is_on { "On" } else { "Off" };
+
+        match entry {
+            OptionsMenuEntry::TemperatureUnit => match self.app_config_fields.temperature_type {
+                temperature::TemperatureType::Celsius => "Celsius",
+                temperature::TemperatureType::Fahrenheit => "Fahrenheit",
+                temperature::TemperatureType::Kelvin => "Kelvin",
+            },
+            OptionsMenuEntry::NetworkUnit => match self.app_config_fields.network_unit_type {
+                DataUnit::Bit => "Bits",
+                DataUnit::Byte => "Bytes",
+            },
+            OptionsMenuEntry::NetworkBinaryPrefix => {
+                on_or_off(self.app_config_fields.network_use_binary_prefix)
+            }
+            OptionsMenuEntry::DotMarker => {
+                if self.app_config_fields.use_dot {
+                    "Dots"
+                } else {
+                    "Braille"
+                }
+            }
+            OptionsMenuEntry::TreeMode => on_or_off(
+                self.are_all_processes_in_mode(|mode| matches!(mode, ProcWidgetMode::Tree { .. })),
+            ),
+            OptionsMenuEntry::Grouping => on_or_off(
+                self.are_all_processes_in_mode(|mode| matches!(mode, ProcWidgetMode::Grouped)),
+            ),
+        }
+    }
+
+    /// Changes the option selected in the options menu to its next or previous value. Tree mode
+    /// and grouping apply to every process widget.
+    fn change_selected_option(&mut self, forwards: bool) {
+        let app_config_fields = &mut self.app_config_fields;
+        match self.options_menu_state.get_selected_entry() {
+            OptionsMenuEntry::TemperatureUnit => {
+                use temperature::TemperatureType::*;
+
+                app_config_fields.temperature_type =
+                    match (&app_config_fields.temperature_type, forwards) {
+                        (Celsius, true) | (Kelvin, false) => Fahrenheit,
+                        (Fahrenheit, true) | (Celsius, false) => Kelvin,
+                        (Kelvin, true) | (Fahrenheit, false) => Celsius,
+                    };
+                self.pending_config_update = true;
+            }
+            OptionsMenuEntry::NetworkUnit => {
+                app_config_fields.network_unit_type = match app_config_fields.network_unit_type {
+                    DataUnit::Bit => DataUnit::Byte,
+                    DataUnit::Byte => DataUnit::Bit,
+                };
+                self.converted_data.clear_network_graphs();
+                self.pending_data_conversion = true;
+            }
+            OptionsMenuEntry::NetworkBinaryPrefix => {
+                app_config_fields.network_use_binary_prefix =
+                    !app_config_fields.network_use_binary_prefix;
+                self.converted_data.clear_network_graphs();
+                self.pending_data_conversion = true;
+            }
+            OptionsMenuEntry::DotMarker => {
+                app_config_fields.use_dot = !app_config_fields.use_dot;
+            }
+            OptionsMenuEntry::TreeMode => {
+                let is_tree = !self
+                    .are_all_processes_in_mode(|mode| matches!(mode, ProcWidgetMode::Tree { .. }));
+                for proc_widget_state in self.proc_state.widget_states.values_mut() {
+                    proc_widget_state.set_tree_mode(is_tree);
+                }
+            }
+            OptionsMenuEntry::Grouping => {
+                let is_grouped =
+                    !self.are_all_processes_in_mode(|mode| matches!(mode, ProcWidgetMode::Grouped));
+                for proc_widget_state in self.proc_state.widget_states.values_mut() {
+                    proc_widget_state.set_grouped(is_grouped);
+                }
+            }
+        }
+
+        self.options_menu_state.save_result = None;
+        self.is_force_redraw = true;
+    }
+
+    /// Keeps the recent CPU usage of every process only if a process widget shows it, as it's
+    /// costly to keep for every process otherwise.
+    pub fn update_process_cpu_history(&mut self) {
+        let keep_process_cpu_history = self
+            .proc_state
+            .widget_states
+            .values()
+            .any(|proc_widget_state| proc_widget_state.show_cpu_history);
+
+        self.data_collection.keep_process_cpu_history = keep_process_cpu_history;
+        for remote_host in &mut self.remote_hosts {
+            remote_host.data_collection.keep_process_cpu_history = keep_process_cpu_history;
+        }
+    }
+
+    /// Updates the converted data of the pinned process being graphed.
+    pub fn update_pinned_process_data(&mut self) {
+        let (cpu_data, mem_data) = data_conversion::convert_pinned_process_data_points(
+            &self.data_collection,
+            self.process_graph_state.pid,
+        );
+        self.converted_data.pinned_process_cpu_data = cpu_data;
+        self.converted_data.pinned_process_mem_data = mem_data;
+    }
+
+    fn handle_layout_edit_char(&mut self, caught_char: char) {
+        match caught_char {
+            'k' => self.layout_edit_state.select_previous(),
+            'j' => self.layout_edit_state.select_next(),
+            'h' => self.layout_edit_state.cycle_selected_type(false),
+            'l' => self.layout_edit_state.cycle_selected_type(true),
+            'K' => self.layout_edit_state.move_selected(true),
+            'J' => self.layout_edit_state.move_selected(false),
+            'a' => self.layout_edit_state.add_widget(),
+            'c' => self.layout_edit_state.add_widget_below(),
+            't' => self.layout_edit_state.toggle_selected_tabs(),
+            'r' => self.layout_edit_state.add_row(),
+            'x' => self.layout_edit_state.remove_selected(),
+            '+' => self.layout_edit_state.change_selected_ratio(true),
+            '-' => self.layout_edit_state.change_selected_ratio(false),
+            'g' => {
+                let mut is_first_g = true;
+                if let Some(second_char) = self.second_char {
+                    if self.awaiting_second_char && second_char == 'g' {
+                        is_first_g = false;
+                        self.awaiting_second_char = false;
+                        self.second_char = None;
+                        self.layout_edit_state.select_first();
+                    }
+                }
+
+                if is_first_g {
+                    self.awaiting_second_char = true;
+                    self.second_char = Some('g');
+                }
+            }
+            'G' => self.layout_edit_state.select_last(),
+            _ => {}
+        }
+    }
+
+    fn open_layout_editor(&mut self) {
+        if !self.app_config_fields.use_basic_mode {
+            let rows = match self.config.layout_rows(self.current_layout) {
+                Some(Some(rows)) => rows.clone(),
+                _ => toml::from_str::<Config>(if self.used_widgets.use_battery {
+                    constants::DEFAULT_BATTERY_LAYOUT
+                } else {
+                    constants::DEFAULT_LAYOUT
+                })
+                .ok()
+                .and_then(|config| config.row)
+                .unwrap_or_default(),
+            };
+
+            self.layout_edit_state.open(rows);
+            self.is_expanded = false;
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Queues a switch to the next or previous layout in the config file, wrapping around.
+    fn cycle_layout(&mut self, is_forward: bool) {
+        if self.app_config_fields.use_basic_mode {
+            self.set_status_message("Layouts can't be switched in basic mode".to_string());
+            return;
+        }
+
+        let num_layouts = self.config.layout_names().len();
+        if num_layouts <= 1 {
+            self.set_status_message(
+                "There are no other layouts, add them under [[layouts]] in the config file"
+                    .to_string(),
+            );
+            return;
+        }
+
+        let layout_index = self.pending_layout_switch.unwrap_or(self.current_layout);
+        self.pending_layout_switch = Some(if is_forward {
+            (layout_index + 1) % num_layouts
+        } else {
+            (layout_index + num_layouts - 1) % num_layouts
+        });
+    }
+
+    /// Scrolls a layout that's taller than the screen up or down by the smallest height of a row.
+    /// The painter keeps this within the layout.
+    fn scroll_layout(&mut self, is_down: bool) {
+        let step = self.app_config_fields.min_row_height;
+        if step == 0 || self.app_config_fields.use_basic_mode || self.is_expanded {
+            return;
         }
+
+        self.layout_scroll = if is_down {
+            self.layout_scroll.saturating_add(step)
+        } else {
+            self.layout_scroll.saturating_sub(step)
+        };
+        self.is_force_redraw = true;
     }
 
-    fn handle_char(&mut self, caught_char: char) {
-        match caught_char {
-            '/' => {
-                self.on_slash();
+    /// Returns the ID of the widget shown in a tabbed column.
+    pub fn get_selected_tab(&self, container_id: u64) -> u64 {
+        self.selected_tabs
+            .get(&container_id)
+            .copied()
+            .unwrap_or(container_id)
+    }
+
+    /// Selects the widget of a tab, which also shows that tab.
+    fn select_tab(&mut self, tab_widget_id: u64) {
+        if let Some(widget) = self.widget_map.get(&tab_widget_id) {
+            if let Some(tab) = widget.tab {
+                self.selected_tabs
+                    .insert(tab.container_id, tab.tab_widget_id);
             }
-            'd' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    let mut is_first_d = true;
-                    if let Some(second_char) = self.second_char {
-                        if self.awaiting_second_char && second_char == 'd' {
-                            is_first_d = false;
-                            self.awaiting_second_char = false;
-                            self.second_char = None;
+            self.current_widget = widget.clone();
+            self.is_force_redraw = true;
+        }
+    }
 
-                            self.start_killing_process();
-                        }
-                    }
+    /// Selects the next or previous tab of the tabbed column the selected widget is in, wrapping
+    /// around.
+    fn cycle_tab(&mut self, is_forward: bool) {
+        let current_tab = match self.current_widget.tab {
+            Some(tab) if !self.is_expanded => tab,
+            _ => return,
+        };
+
+        let tab_widget_ids: Vec<u64> = self
+            .widget_map
+            .values()
+            .filter_map(|widget| widget.tab)
+            .filter(|tab| tab.container_id == current_tab.container_id)
+            .map(|tab| tab.tab_widget_id)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        if let Some(index) = tab_widget_ids
+            .iter()
+            .position(|id| *id == current_tab.tab_widget_id)
+        {
+            let num_tabs = tab_widget_ids.len();
+            self.select_tab(if is_forward {
+                tab_widget_ids[(index + 1) % num_tabs]
+            } else {
+                tab_widget_ids[(index + num_tabs - 1) % num_tabs]
+            });
+        }
+    }
 
-                    if is_first_d {
-                        self.awaiting_second_char = true;
-                        self.second_char = Some('d');
-                    }
-                }
+    fn handle_char(&mut self, caught_char: char) {
+        if let KeyResult::Action(action, count) = self.key_bindings.on_char(caught_char) {
+            let count = if action.is_repeatable() { count } else { 1 };
+            for _ in 0..count {
+                self.perform_key_action(action);
             }
-            'g' => {
-                let mut is_first_g = true;
-                if let Some(second_char) = self.second_char {
-                    if self.awaiting_second_char && second_char == 'g' {
-                        is_first_g = false;
-                        self.awaiting_second_char = false;
-                        self.second_char = None;
-                        self.skip_to_first();
-                    }
-                }
+        }
+    }
 
-                if is_first_g {
-                    self.awaiting_second_char = true;
-                    self.second_char = Some('g');
+    fn perform_key_action(&mut self, action: KeyAction) {
+        if action.is_local_only() && !self.check_is_local() {
+            return;
+        }
+
+        match action {
+            KeyAction::Search => self.on_slash(),
+            KeyAction::KillProcess => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.start_killing_process();
                 }
             }
-            'G' => self.skip_to_last(),
-            'k' => self.on_up_key(),
-            'j' => self.on_down_key(),
-            'f' => {
+            KeyAction::SkipToFirst => self.skip_to_first(),
+            KeyAction::SkipToLast => self.skip_to_last(),
+            KeyAction::MoveUp => self.on_up_key(),
+            KeyAction::MoveDown => self.on_down_key(),
+            KeyAction::Freeze => {
                 self.is_frozen = !self.is_frozen;
                 if self.is_frozen {
                     self.data_collection.freeze();
@@ -1351,78 +2930,358 @@ impl App {
                     self.data_collection.thaw();
                 }
             }
-            'c' => {
+            KeyAction::SortByCpu => self.select_process_column(ProcWidget::CPU),
+            KeyAction::SortByMem => self.select_process_column(ProcWidget::MEM),
+            KeyAction::SortByPid => self.select_process_column(ProcWidget::PID_OR_COUNT),
+            KeyAction::SortByName => self.select_process_column(ProcWidget::PROC_NAME_OR_CMD),
+            KeyAction::ToggleCommand => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     if let Some(proc_widget_state) = self
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        proc_widget_state.select_column(ProcWidget::CPU);
+                        proc_widget_state.toggle_command();
                     }
                 }
             }
-            'm' => {
+            KeyAction::Help => {
+                self.help_dialog_state.is_showing_help = true;
+                self.is_force_redraw = true;
+            }
+            KeyAction::WidgetLeft => self.move_widget_selection(&WidgetDirection::Left),
+            KeyAction::WidgetRight => self.move_widget_selection(&WidgetDirection::Right),
+            KeyAction::WidgetUp => self.move_widget_selection(&WidgetDirection::Up),
+            KeyAction::WidgetDown => self.move_widget_selection(&WidgetDirection::Down),
+            KeyAction::ToggleTree => self.toggle_tree_mode(),
+            KeyAction::ZoomIn => self.on_plus(),
+            KeyAction::ZoomOut => self.on_minus(),
+            KeyAction::ResetZoom => self.reset_zoom(),
+            KeyAction::ExpandWidget => self.toggle_expand_widget(),
+            KeyAction::EditLayout => self.open_layout_editor(),
+            KeyAction::PinProcess => self.pin_selected_process(),
+            KeyAction::ToggleSort => self.toggle_sort(),
+            KeyAction::InvertSort => self.invert_sort(),
+            KeyAction::TogglePercentages => self.toggle_percentages(),
+            KeyAction::CycleFilter => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     if let Some(proc_widget_state) = self
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        proc_widget_state.select_column(ProcWidget::MEM);
+                        proc_widget_state.cycle_saved_filter();
                     }
                 }
             }
-            'p' => {
+            KeyAction::ToggleFollow => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     if let Some(proc_widget_state) = self
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        proc_widget_state.select_column(ProcWidget::PID_OR_COUNT);
+                        proc_widget_state.toggle_following();
                     }
                 }
             }
-            'P' => {
+            KeyAction::ToggleMark => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     if let Some(proc_widget_state) = self
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        proc_widget_state.toggle_command();
+                        proc_widget_state.toggle_mark();
+                        proc_widget_state.force_data_update();
                     }
                 }
             }
-            'n' => {
+            KeyAction::MarkRange => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     if let Some(proc_widget_state) = self
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        proc_widget_state.select_column(ProcWidget::PROC_NAME_OR_CMD);
+                        proc_widget_state.toggle_range_marking();
+                        proc_widget_state.force_data_update();
                     }
                 }
             }
-            '?' => {
-                self.help_dialog_state.is_showing_help = true;
+            KeyAction::ToggleNetworkTotals => self.toggle_network_totals(),
+            KeyAction::InspectProcess => self.inspect_selected_process(),
+            KeyAction::ShowCommand => self.show_selected_process_command(),
+            KeyAction::ShowThreads => {
+                #[cfg(target_os = "linux")]
+                self.show_selected_process_threads();
+            }
+            KeyAction::ShowOptions => self.open_options_menu(),
+            KeyAction::ShowDiagnostics => {
+                self.is_showing_diagnostics = true;
                 self.is_force_redraw = true;
             }
-            'H' | 'A' => self.move_widget_selection(&WidgetDirection::Left),
-            'L' | 'D' => self.move_widget_selection(&WidgetDirection::Right),
-            'K' | 'W' => self.move_widget_selection(&WidgetDirection::Up),
-            'J' | 'S' => self.move_widget_selection(&WidgetDirection::Down),
-            't' => self.toggle_tree_mode(),
-            '+' => self.on_plus(),
-            '-' => self.on_minus(),
-            '=' => self.reset_zoom(),
-            'e' => self.toggle_expand_widget(),
-            's' => self.toggle_sort(),
-            'I' => self.invert_sort(),
-            '%' => self.toggle_percentages(),
-            _ => {}
+            KeyAction::QueueKill => self.queue_selected_processes(),
+            KeyAction::ShowKillQueue => self.open_kill_queue(),
+            KeyAction::ResetPeaks => self.reset_peaks(),
+            KeyAction::TogglePause => {
+                self.is_paused = !self.is_paused;
+                self.pending_pause_update = true;
+            }
+            KeyAction::ToggleDebugOverlay => {
+                self.is_showing_debug_overlay = !self.is_showing_debug_overlay;
+            }
+            KeyAction::ToggleCompare => self.toggle_compare(),
+            KeyAction::NextLayout => self.cycle_layout(true),
+            KeyAction::PreviousLayout => self.cycle_layout(false),
+            KeyAction::NextTab => self.cycle_tab(true),
+            KeyAction::PreviousTab => self.cycle_tab(false),
+            KeyAction::ScrollLayoutUp => self.scroll_layout(false),
+            KeyAction::ScrollLayoutDown => self.scroll_layout(true),
+            KeyAction::RunHook(index) => self.run_hook(index),
+            KeyAction::ChangePriority => {
+                #[cfg(target_family = "unix")]
+                self.open_priority_dialog();
+            }
+            KeyAction::ChangeAffinity => {
+                #[cfg(target_os = "linux")]
+                self.open_affinity_dialog();
+            }
+            KeyAction::TogglePseudoFilesystems => {
+                if let BottomWidgetType::Disk = self.current_widget.widget_type {
+                    if let Some(disk_widget_state) = self
+                        .disk_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        disk_widget_state.toggle_pseudo_filesystems();
+                    }
+                }
+            }
+            KeyAction::SuspendProcess => {
+                #[cfg(target_family = "unix")]
+                self.signal_selected_processes(libc::SIGSTOP);
+            }
+            KeyAction::ResumeProcess => {
+                #[cfg(target_family = "unix")]
+                self.signal_selected_processes(libc::SIGCONT);
+            }
+            KeyAction::RestartUnit => {
+                #[cfg(target_os = "linux")]
+                self.control_selected_unit(systemd::UnitAction::Restart);
+            }
+            KeyAction::StopUnit => {
+                #[cfg(target_os = "linux")]
+                self.control_selected_unit(systemd::UnitAction::Stop);
+            }
+            KeyAction::ToggleUserGrouping => self.toggle_user_grouping(),
+            KeyAction::ScrollLeft => self.handle_scroll_left(),
+            KeyAction::ScrollRight => self.handle_scroll_right(),
+            KeyAction::CycleColourScheme => {
+                self.pending_colour_scheme_cycle = true;
+                self.is_force_redraw = true;
+            }
+            KeyAction::ExportGraph => {
+                self.pending_graph_export = true;
+                // Redraw everything, so the selected widget's bounds are up to date.
+                self.is_force_redraw = true;
+            }
+            KeyAction::CopyRow => self.copy_table_rows(false),
+            KeyAction::CopyTable => self.copy_table_rows(true),
+            KeyAction::NextHost => self.cycle_host(true),
+            KeyAction::PreviousHost => self.cycle_host(false),
+        }
+    }
+
+    /// Whether the data shown is from this machine, so its processes can be acted on. If it's from
+    /// a remote host, this says so in the status bar.
+    fn check_is_local(&mut self) -> bool {
+        match self.remote_hosts.get(self.selected_host) {
+            Some(host) => {
+                let message = format!("Processes on {} can't be controlled remotely", host.name());
+                self.set_status_message(message);
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Shows the next or previous remote host.
+    fn cycle_host(&mut self, is_forward: bool) {
+        let num_hosts = self.remote_hosts.len();
+        if num_hosts < 2 {
+            self.set_status_message(
+                "There are no other hosts, add them with more --connect flags".to_string(),
+            );
+            return;
+        }
+
+        let host = if is_forward {
+            (self.selected_host + 1) % num_hosts
+        } else {
+            (self.selected_host + num_hosts - 1) % num_hosts
+        };
+
+        // The data of the shown host is kept in `data_collection`, so it's swapped back into its
+        // host before the new host's is swapped out.
+        let old_host = self.selected_host;
+        std::mem::swap(
+            &mut self.data_collection,
+            &mut self.remote_hosts[old_host].data_collection,
+        );
+        std::mem::swap(
+            &mut self.data_collection,
+            &mut self.remote_hosts[host].data_collection,
+        );
+        if self.is_frozen {
+            self.remote_hosts[old_host].data_collection.thaw();
+            self.data_collection.freeze();
         }
 
-        if let Some(second_char) = self.second_char {
-            if self.awaiting_second_char && caught_char != second_char {
-                self.awaiting_second_char = false;
+        // The graphs are converted incrementally, so they're redone from scratch for the new host.
+        self.converted_data = ConvertedData::default();
+        self.selected_host = host;
+        self.pending_data_conversion = true;
+        self.is_force_redraw = true;
+    }
+
+    /// Says in the status bar when something that was being collected for the shown host starts
+    /// failing, which is called before its data is eaten.
+    pub fn notice_harvest_errors(&mut self, harvest_errors: &[data_harvester::HarvestError]) {
+        let new_error = harvest_errors.iter().find(|harvest_error| {
+            !self
+                .data_collection
+                .harvest_errors
+                .iter()
+                .any(|old_error| old_error.collector == harvest_error.collector)
+        });
+
+        if let Some(harvest_error) = new_error {
+            self.set_status_message(format!(
+                "{} couldn't be collected, see the diagnostics for why",
+                harvest_error.collector
+            ));
+        }
+    }
+
+    /// Stores data from a remote host that isn't shown. Data from the shown host is eaten like
+    /// local data instead.
+    pub fn eat_host_data(&mut self, host: usize, data: Box<data_harvester::Data>) {
+        if let Some(remote_host) = self.remote_hosts.get_mut(host) {
+            remote_host.data_collection.eat_data(data);
+            remote_host
+                .data_collection
+                .clean_data(self.app_config_fields.retention);
+        }
+    }
+
+    /// Updates whether a remote host is connected, and says so in the status bar.
+    pub fn set_host_status(&mut self, host: usize, status: HostStatus) {
+        let remote_host = match self.remote_hosts.get_mut(host) {
+            Some(remote_host) => remote_host,
+            None => return,
+        };
+
+        let message = match status {
+            HostStatus::Connected(hostname) => {
+                remote_host.is_connected = true;
+                remote_host.hostname = Some(hostname);
+                format!(
+                    "Connected to {} at {}",
+                    remote_host.name(),
+                    remote_host.address
+                )
+            }
+            HostStatus::Disconnected(err) => {
+                remote_host.is_connected = false;
+                format!("Lost connection to {}, {}", remote_host.name(), err)
+            }
+        };
+        self.set_status_message(message);
+    }
+
+    /// Copies the selected row of the selected table, or the whole table with a line of column
+    /// names, as tab-separated text.
+    fn copy_table_rows(&mut self, whole_table: bool) {
+        let widget_id = self.current_widget.widget_id;
+        let tsv = match self.current_widget.widget_type {
+            BottomWidgetType::Proc => {
+                self.proc_state
+                    .widget_states
+                    .get(&widget_id)
+                    .and_then(|state| {
+                        clipboard::table_to_tsv(&state.table_state, &state.table_data, whole_table)
+                    })
+            }
+            BottomWidgetType::Temp => {
+                self.temp_state
+                    .widget_states
+                    .get(&widget_id)
+                    .and_then(|state| {
+                        clipboard::table_to_tsv(&state.table_state, &state.table_data, whole_table)
+                    })
+            }
+            BottomWidgetType::Disk => {
+                self.disk_state
+                    .widget_states
+                    .get(&widget_id)
+                    .and_then(|state| {
+                        clipboard::table_to_tsv(&state.table_state, &state.table_data, whole_table)
+                    })
+            }
+            BottomWidgetType::Cgroup => {
+                self.cgroup_state
+                    .widget_states
+                    .get(&widget_id)
+                    .and_then(|state| {
+                        clipboard::table_to_tsv(
+                            &state.table_state,
+                            &self.converted_data.cgroup_data,
+                            whole_table,
+                        )
+                    })
+            }
+            BottomWidgetType::Events => {
+                self.event_state
+                    .widget_states
+                    .get(&widget_id)
+                    .and_then(|state| {
+                        clipboard::table_to_tsv(
+                            &state.table_state,
+                            &self.converted_data.event_data,
+                            whole_table,
+                        )
+                    })
+            }
+            BottomWidgetType::Users => {
+                self.user_state
+                    .widget_states
+                    .get(&widget_id)
+                    .and_then(|state| {
+                        clipboard::table_to_tsv(
+                            &state.table_state,
+                            &self.converted_data.user_data,
+                            whole_table,
+                        )
+                    })
+            }
+            _ => None,
+        };
+
+        match tsv {
+            Some((text, num_rows)) => {
+                self.pending_clipboard = Some(text);
+                self.set_status_message(if num_rows == 1 {
+                    "Copied 1 row".to_string()
+                } else {
+                    format!("Copied {} rows", num_rows)
+                });
+            }
+            None => self.set_status_message("There's no table row to copy".to_string()),
+        }
+    }
+
+    fn select_process_column(&mut self, index: usize) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                proc_widget_state.select_column(index);
             }
         }
     }
@@ -1435,7 +3294,19 @@ impl App {
                     KillSignal::Kill(sig) => sig,
                     KillSignal::Cancel => 15, // should never happen, so just TERM
                 };
-                for pid in &current_selected_processes.1 {
+                // Descendants are killed from the bottom up, so they can't be reparented to one
+                // another while this is running.
+                let descendant_pids = if self.delete_dialog_state.kill_descendants {
+                    self.delete_dialog_state.descendant_pids.as_slice()
+                } else {
+                    &[]
+                };
+
+                for pid in descendant_pids
+                    .iter()
+                    .rev()
+                    .chain(&current_selected_processes.1)
+                {
                     #[cfg(target_family = "unix")]
                     {
                         process_killer::kill_process_given_pid(*pid, signal)?;
@@ -1445,6 +3316,15 @@ impl App {
                         process_killer::kill_process_given_pid(*pid)?;
                     }
                 }
+
+                if self.delete_dialog_state.is_killing_marked {
+                    if let Some(pws) = self
+                        .proc_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        pws.clear_marks();
+                    }
+                }
             }
             self.to_delete_process_list = None;
             Ok(())
@@ -1456,6 +3336,86 @@ impl App {
         }
     }
 
+    /// Sends a signal to the marked processes, or the selected process if none are marked,
+    /// without confirmation. This is used to suspend and resume them. If this fails, the error is
+    /// shown in the kill dialog.
+    #[cfg(target_family = "unix")]
+    fn signal_selected_processes(&mut self, signal: libc::c_int) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            let result = self
+                .get_target_pids()
+                .into_iter()
+                .try_for_each(|pid| process_killer::kill_process_given_pid(pid, signal as usize));
+
+            if let Err(err) = result {
+                self.dd_err = Some(err.to_string());
+                self.delete_dialog_state.is_showing_dd = true;
+                self.is_force_redraw = true;
+            }
+        }
+    }
+
+    /// Returns the processes that actions in the current process widget apply to, which are the
+    /// marked processes, or the selected process if none are marked.
+    fn get_target_pids(&self) -> Vec<Pid> {
+        match self
+            .proc_state
+            .get_widget_state(self.current_widget.widget_id)
+        {
+            Some(proc_widget_state) => {
+                let marked_pids = proc_widget_state.get_marked_pids();
+                if marked_pids.is_empty() {
+                    proc_widget_state.get_selected_pid().into_iter().collect()
+                } else {
+                    marked_pids
+                }
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Opens the dialog asking whether to restart or stop the selected process' systemd unit.
+    #[cfg(target_os = "linux")]
+    fn control_selected_unit(&mut self, action: systemd::UnitAction) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(unit) = self
+                .proc_state
+                .get_widget_state(self.current_widget.widget_id)
+                .and_then(|proc_widget_state| proc_widget_state.get_selected_pid())
+                .and_then(|pid| self.data_collection.process_data.process_harvest.get(&pid))
+                .and_then(|process| process.systemd_unit.clone())
+            {
+                self.unit_control_state.pending = Some((unit, action));
+                self.unit_control_state.error = None;
+                self.is_force_redraw = true;
+            }
+        }
+    }
+
+    /// Restarts or stops the unit in the open dialog, closing it if this succeeds. If it fails,
+    /// the dialog is left open with the error.
+    #[cfg(target_os = "linux")]
+    fn apply_unit_control(&mut self) {
+        if let Some((unit, action)) = &self.unit_control_state.pending {
+            match systemd::control_unit(unit, *action) {
+                Ok(()) => {
+                    let message = format!("Asked systemd to {} {}", action.verb(), unit.name);
+                    self.close_unit_control();
+                    self.set_status_message(message);
+                }
+                Err(err) => {
+                    self.unit_control_state.error = Some(err.to_string());
+                    self.is_force_redraw = true;
+                }
+            }
+        }
+    }
+
+    fn close_unit_control(&mut self) {
+        self.unit_control_state = AppUnitControlState::default();
+        self.is_force_redraw = true;
+    }
+
     pub fn get_to_delete_processes(&self) -> Option<(String, Vec<Pid>)> {
         self.to_delete_process_list.clone()
     }
@@ -1470,12 +3430,19 @@ impl App {
     }
 
     fn expand_widget(&mut self) {
-        // TODO: [BASIC] Expansion in basic mode.
-        if !self.ignore_normal_keybinds() && !self.app_config_fields.use_basic_mode {
-            // Pop-out mode.  We ignore if in process search.
+        if !self.ignore_normal_keybinds() {
+            // Pop-out mode.  We ignore if in process search.  In basic mode, only the summary
+            // rows can be expanded, into their full graphs.
 
             match self.current_widget.widget_type {
                 BottomWidgetType::ProcSearch => {}
+                BottomWidgetType::BasicCpu
+                | BottomWidgetType::BasicMem
+                | BottomWidgetType::BasicNet => {
+                    self.is_expanded = true;
+                    self.is_force_redraw = true;
+                }
+                _ if self.app_config_fields.use_basic_mode => {}
                 _ => {
                     self.is_expanded = true;
                     self.is_force_redraw = true;
@@ -1487,8 +3454,21 @@ impl App {
     pub fn move_widget_selection(&mut self, direction: &WidgetDirection) {
         // Since we only want to call reset once, we do it like this to avoid
         // redundant calls on recursion.
+        let previous_tab = self.current_widget.tab;
         self.move_widget_selection_logic(direction);
         self.reset_multi_tap_keys();
+
+        // Moving into a tabbed column lands on the tab that's shown, rather than whichever tab
+        // happens to be next to the last widget.
+        if let Some(tab) = self.current_widget.tab {
+            if previous_tab.map(|previous_tab| previous_tab.container_id) != Some(tab.container_id)
+            {
+                let selected_tab = self.get_selected_tab(tab.container_id);
+                if selected_tab != tab.tab_widget_id {
+                    self.select_tab(selected_tab);
+                }
+            }
+        }
     }
 
     fn move_widget_selection_logic(&mut self, direction: &WidgetDirection) {
@@ -1972,6 +3952,33 @@ impl App {
                         disk_widget_state.table_state.scroll_direction = ScrollDirection::Up;
                     }
                 }
+                BottomWidgetType::Cgroup => {
+                    if let Some(cgroup_widget_state) = self
+                        .cgroup_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        cgroup_widget_state.table_state.current_scroll_position = 0;
+                        cgroup_widget_state.table_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Events => {
+                    if let Some(event_widget_state) = self
+                        .event_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        event_widget_state.table_state.current_scroll_position = 0;
+                        event_widget_state.table_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
+                BottomWidgetType::Users => {
+                    if let Some(user_widget_state) = self
+                        .user_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        user_widget_state.table_state.current_scroll_position = 0;
+                        user_widget_state.table_state.scroll_direction = ScrollDirection::Up;
+                    }
+                }
                 BottomWidgetType::CpuLegend => {
                     if let Some(cpu_widget_state) = self
                         .cpu_state
@@ -1987,6 +3994,16 @@ impl App {
             self.reset_multi_tap_keys();
         } else if self.help_dialog_state.is_showing_help {
             self.help_dialog_state.scroll_state.current_scroll_index = 0;
+        } else if self.process_inspector_state.is_showing_inspector {
+            self.process_inspector_state
+                .scroll_state
+                .current_scroll_index = 0;
+        } else if self.process_command_state.is_showing_command {
+            self.process_command_state.scroll_state.current_scroll_index = 0;
+        } else if self.process_threads_state.is_showing_threads {
+            self.process_threads_state.scroll_state.current_scroll_index = 0;
+        } else if self.layout_edit_state.is_editing {
+            self.layout_edit_state.select_first();
         } else if self.delete_dialog_state.is_showing_dd {
             self.delete_dialog_state.selected_signal = KillSignal::Cancel;
         }
@@ -2020,9 +4037,9 @@ impl App {
                         .temp_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        if !self.converted_data.temp_sensor_data.data.is_empty() {
+                        if !temp_widget_state.table_data.data.is_empty() {
                             temp_widget_state.table_state.current_scroll_position =
-                                self.converted_data.temp_sensor_data.data.len() - 1;
+                                temp_widget_state.table_data.data.len() - 1;
                             temp_widget_state.table_state.scroll_direction = ScrollDirection::Down;
                         }
                     }
@@ -2032,13 +4049,50 @@ impl App {
                         .disk_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        if !self.converted_data.disk_data.data.is_empty() {
+                        if !disk_widget_state.table_data.data.is_empty() {
                             disk_widget_state.table_state.current_scroll_position =
-                                self.converted_data.disk_data.data.len() - 1;
+                                disk_widget_state.table_data.data.len() - 1;
                             disk_widget_state.table_state.scroll_direction = ScrollDirection::Down;
                         }
                     }
                 }
+                BottomWidgetType::Cgroup => {
+                    if let Some(cgroup_widget_state) = self
+                        .cgroup_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.converted_data.cgroup_data.data.is_empty() {
+                            cgroup_widget_state.table_state.current_scroll_position =
+                                self.converted_data.cgroup_data.data.len() - 1;
+                            cgroup_widget_state.table_state.scroll_direction =
+                                ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Events => {
+                    if let Some(event_widget_state) = self
+                        .event_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.converted_data.event_data.data.is_empty() {
+                            event_widget_state.table_state.current_scroll_position =
+                                self.converted_data.event_data.data.len() - 1;
+                            event_widget_state.table_state.scroll_direction = ScrollDirection::Down;
+                        }
+                    }
+                }
+                BottomWidgetType::Users => {
+                    if let Some(user_widget_state) = self
+                        .user_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.converted_data.user_data.data.is_empty() {
+                            user_widget_state.table_state.current_scroll_position =
+                                self.converted_data.user_data.data.len() - 1;
+                            user_widget_state.table_state.scroll_direction = ScrollDirection::Down;
+                        }
+                    }
+                }
                 BottomWidgetType::CpuLegend => {
                     if let Some(cpu_widget_state) = self
                         .cpu_state
@@ -2060,6 +4114,28 @@ impl App {
                 .scroll_state
                 .max_scroll_index
                 .saturating_sub(1);
+        } else if self.process_inspector_state.is_showing_inspector {
+            self.process_inspector_state
+                .scroll_state
+                .current_scroll_index = self
+                .process_inspector_state
+                .scroll_state
+                .max_scroll_index
+                .saturating_sub(1);
+        } else if self.process_command_state.is_showing_command {
+            self.process_command_state.scroll_state.current_scroll_index = self
+                .process_command_state
+                .scroll_state
+                .max_scroll_index
+                .saturating_sub(1);
+        } else if self.process_threads_state.is_showing_threads {
+            self.process_threads_state.scroll_state.current_scroll_index = self
+                .process_threads_state
+                .scroll_state
+                .max_scroll_index
+                .saturating_sub(1);
+        } else if self.layout_edit_state.is_editing {
+            self.layout_edit_state.select_last();
         } else if self.delete_dialog_state.is_showing_dd {
             self.delete_dialog_state.selected_signal = KillSignal::Kill(MAX_SIGNAL);
         }
@@ -2082,6 +4158,9 @@ impl App {
                 BottomWidgetType::ProcSort => self.change_process_sort_position(amount),
                 BottomWidgetType::Temp => self.change_temp_position(amount),
                 BottomWidgetType::Disk => self.change_disk_position(amount),
+                BottomWidgetType::Cgroup => self.change_cgroup_position(amount),
+                BottomWidgetType::Events => self.change_event_position(amount),
+                BottomWidgetType::Users => self.change_user_position(amount),
                 BottomWidgetType::CpuLegend => self.change_cpu_legend_position(amount),
                 _ => {}
             }
@@ -2132,10 +4211,9 @@ impl App {
             .widget_states
             .get_mut(&self.current_widget.widget_id)
         {
-            temp_widget_state.table_state.update_position(
-                num_to_change_by,
-                self.converted_data.temp_sensor_data.data.len(),
-            );
+            temp_widget_state
+                .table_state
+                .update_position(num_to_change_by, temp_widget_state.table_data.data.len());
         }
     }
 
@@ -2147,7 +4225,43 @@ impl App {
         {
             disk_widget_state
                 .table_state
-                .update_position(num_to_change_by, self.converted_data.disk_data.data.len());
+                .update_position(num_to_change_by, disk_widget_state.table_data.data.len());
+        }
+    }
+
+    fn change_cgroup_position(&mut self, num_to_change_by: i64) {
+        if let Some(cgroup_widget_state) = self
+            .cgroup_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            cgroup_widget_state
+                .table_state
+                .update_position(num_to_change_by, self.converted_data.cgroup_data.data.len());
+        }
+    }
+
+    fn change_event_position(&mut self, num_to_change_by: i64) {
+        if let Some(event_widget_state) = self
+            .event_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            event_widget_state
+                .table_state
+                .update_position(num_to_change_by, self.converted_data.event_data.data.len());
+        }
+    }
+
+    fn change_user_position(&mut self, num_to_change_by: i64) {
+        if let Some(user_widget_state) = self
+            .user_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            user_widget_state
+                .table_state
+                .update_position(num_to_change_by, self.converted_data.user_data.data.len());
         }
     }
 
@@ -2165,6 +4279,42 @@ impl App {
         }
     }
 
+    fn inspector_scroll_up(&mut self) {
+        let scroll_state = &mut self.process_inspector_state.scroll_state;
+        scroll_state.current_scroll_index = scroll_state.current_scroll_index.saturating_sub(1);
+    }
+
+    fn inspector_scroll_down(&mut self) {
+        let scroll_state = &mut self.process_inspector_state.scroll_state;
+        if scroll_state.current_scroll_index + 1 < scroll_state.max_scroll_index {
+            scroll_state.current_scroll_index += 1;
+        }
+    }
+
+    fn command_scroll_up(&mut self) {
+        let scroll_state = &mut self.process_command_state.scroll_state;
+        scroll_state.current_scroll_index = scroll_state.current_scroll_index.saturating_sub(1);
+    }
+
+    fn command_scroll_down(&mut self) {
+        let scroll_state = &mut self.process_command_state.scroll_state;
+        if scroll_state.current_scroll_index + 1 < scroll_state.max_scroll_index {
+            scroll_state.current_scroll_index += 1;
+        }
+    }
+
+    fn threads_scroll_up(&mut self) {
+        let scroll_state = &mut self.process_threads_state.scroll_state;
+        scroll_state.current_scroll_index = scroll_state.current_scroll_index.saturating_sub(1);
+    }
+
+    fn threads_scroll_down(&mut self) {
+        let scroll_state = &mut self.process_threads_state.scroll_state;
+        if scroll_state.current_scroll_index + 1 < scroll_state.max_scroll_index {
+            scroll_state.current_scroll_index += 1;
+        }
+    }
+
     fn help_scroll_to_or_max(&mut self, new_position: u16) {
         if new_position < self.help_dialog_state.scroll_state.max_scroll_index {
             self.help_dialog_state.scroll_state.current_scroll_index = new_position;
@@ -2184,6 +4334,12 @@ impl App {
         }
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
+        } else if self.process_inspector_state.is_showing_inspector {
+            self.inspector_scroll_up();
+        } else if self.process_command_state.is_showing_command {
+            self.command_scroll_up();
+        } else if self.process_threads_state.is_showing_threads {
+            self.threads_scroll_up();
         } else if self.current_widget.widget_type.is_widget_graph() {
             self.zoom_in();
         } else if self.current_widget.widget_type.is_widget_table() {
@@ -2201,6 +4357,12 @@ impl App {
         }
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
+        } else if self.process_inspector_state.is_showing_inspector {
+            self.inspector_scroll_down();
+        } else if self.process_command_state.is_showing_command {
+            self.command_scroll_down();
+        } else if self.process_threads_state.is_showing_threads {
+            self.threads_scroll_down();
         } else if self.current_widget.widget_type.is_widget_graph() {
             self.zoom_out();
         } else if self.current_widget.widget_type.is_widget_table() {
@@ -2208,10 +4370,72 @@ impl App {
         }
     }
 
+    /// Scrolls the current table one column to the left.
+    pub fn handle_scroll_left(&mut self) {
+        self.scroll_table_horizontally(false);
+    }
+
+    /// Scrolls the current table one column to the right, if anything is cut off.
+    pub fn handle_scroll_right(&mut self) {
+        self.scroll_table_horizontally(true);
+    }
+
+    fn scroll_table_horizontally(&mut self, is_right: bool) {
+        fn scroll<H: TableComponentHeader>(
+            table_state: &mut TableComponentState<H>, is_right: bool,
+        ) {
+            if is_right {
+                table_state.scroll_right();
+            } else {
+                table_state.scroll_left();
+            }
+        }
+
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Proc => {
+                if let Some(proc_widget_state) = self.proc_state.get_mut_widget_state(widget_id) {
+                    scroll(&mut proc_widget_state.table_state, is_right);
+                }
+            }
+            BottomWidgetType::Disk => {
+                if let Some(disk_widget_state) = self.disk_state.get_mut_widget_state(widget_id) {
+                    scroll(&mut disk_widget_state.table_state, is_right);
+                }
+            }
+            BottomWidgetType::Temp => {
+                if let Some(temp_widget_state) = self.temp_state.get_mut_widget_state(widget_id) {
+                    scroll(&mut temp_widget_state.table_state, is_right);
+                }
+            }
+            BottomWidgetType::Cgroup => {
+                if let Some(cgroup_widget_state) =
+                    self.cgroup_state.widget_states.get_mut(&widget_id)
+                {
+                    scroll(&mut cgroup_widget_state.table_state, is_right);
+                }
+            }
+            BottomWidgetType::Events => {
+                if let Some(event_widget_state) = self.event_state.widget_states.get_mut(&widget_id)
+                {
+                    scroll(&mut event_widget_state.table_state, is_right);
+                }
+            }
+            BottomWidgetType::Users => {
+                if let Some(user_widget_state) = self.user_state.widget_states.get_mut(&widget_id) {
+                    scroll(&mut user_widget_state.table_state, is_right);
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn on_plus(&mut self) {
         if let BottomWidgetType::Proc = self.current_widget.widget_type {
             // Toggle collapsing if tree
             self.toggle_collapsing_process_branch();
+        } else if let BottomWidgetType::CpuLegend = self.current_widget.widget_type {
+            self.toggle_collapsing_cpu_node();
         } else {
             self.zoom_in();
         }
@@ -2221,6 +4445,8 @@ impl App {
         if let BottomWidgetType::Proc = self.current_widget.widget_type {
             // Toggle collapsing if tree
             self.toggle_collapsing_process_branch();
+        } else if let BottomWidgetType::CpuLegend = self.current_widget.widget_type {
+            self.toggle_collapsing_cpu_node();
         } else {
             self.zoom_out();
         }
@@ -2233,12 +4459,31 @@ impl App {
             .get_mut(&self.current_widget.widget_id)
         {
             pws.toggle_tree_branch();
+            pws.toggle_user_group();
+        }
+    }
+
+    fn toggle_collapsing_cpu_node(&mut self) {
+        let widget_id = self.current_widget.widget_id - 1;
+        if let Some(cpu_widget_state) = self.cpu_state.widget_states.get(&widget_id) {
+            let selected_entry = self
+                .converted_data
+                .cpu_data
+                .get(cpu_widget_state.table_state.current_scroll_position)
+                .map(|cpu| cpu.entry);
+            if let Some(CpuEntry::Node { node, .. }) = selected_entry {
+                if let Some(cpu_node) = self.cpu_state.nodes.get_mut(node) {
+                    cpu_node.is_collapsed = !cpu_node.is_collapsed;
+                    self.cpu_state.force_update = Some(widget_id);
+                }
+            }
         }
     }
 
     fn zoom_out(&mut self) {
+        let retention = self.app_config_fields.retention;
         match self.current_widget.widget_type {
-            BottomWidgetType::Cpu => {
+            BottomWidgetType::Cpu | BottomWidgetType::BasicCpu => {
                 if let Some(cpu_widget_state) = self
                     .cpu_state
                     .widget_states
@@ -2246,16 +4491,250 @@ impl App {
                 {
                     let new_time = cpu_widget_state.current_display_time
                         + self.app_config_fields.time_interval;
-                    if new_time <= constants::STALE_MAX_MILLISECONDS {
+                    if new_time <= retention {
+                        cpu_widget_state.current_display_time = new_time;
+                        self.cpu_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            cpu_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if cpu_widget_state.current_display_time != retention {
+                        cpu_widget_state.current_display_time = retention;
+                        self.cpu_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            cpu_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Mem | BottomWidgetType::BasicMem => {
+                if let Some(mem_widget_state) = self
+                    .mem_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = mem_widget_state.current_display_time
+                        + self.app_config_fields.time_interval;
+                    if new_time <= retention {
+                        mem_widget_state.current_display_time = new_time;
+                        self.mem_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            mem_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if mem_widget_state.current_display_time != retention {
+                        mem_widget_state.current_display_time = retention;
+                        self.mem_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            mem_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Net | BottomWidgetType::BasicNet => {
+                if let Some(net_widget_state) = self
+                    .net_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = net_widget_state.current_display_time
+                        + self.app_config_fields.time_interval;
+                    if new_time <= retention {
+                        net_widget_state.current_display_time = new_time;
+                        self.net_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            net_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if net_widget_state.current_display_time != retention {
+                        net_widget_state.current_display_time = retention;
+                        self.net_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            net_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Battery => {
+                if let Some(battery_widget_state) = self
+                    .battery_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = battery_widget_state.current_display_time
+                        + self.app_config_fields.time_interval;
+                    if new_time <= retention {
+                        battery_widget_state.current_display_time = new_time;
+                        if self.app_config_fields.autohide_time {
+                            battery_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if battery_widget_state.current_display_time != retention {
+                        battery_widget_state.current_display_time = retention;
+                        if self.app_config_fields.autohide_time {
+                            battery_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Power => {
+                if let Some(power_widget_state) = self
+                    .power_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = power_widget_state.current_display_time
+                        + self.app_config_fields.time_interval;
+                    if new_time <= retention {
+                        power_widget_state.current_display_time = new_time;
+                        self.power_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            power_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if power_widget_state.current_display_time != retention {
+                        power_widget_state.current_display_time = retention;
+                        self.power_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            power_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Pressure => {
+                if let Some(pressure_widget_state) = self
+                    .pressure_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = pressure_widget_state.current_display_time
+                        + self.app_config_fields.time_interval;
+                    if new_time <= retention {
+                        pressure_widget_state.current_display_time = new_time;
+                        self.pressure_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            pressure_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if pressure_widget_state.current_display_time != retention {
+                        pressure_widget_state.current_display_time = retention;
+                        self.pressure_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            pressure_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::LoadAvg => {
+                if let Some(load_avg_widget_state) = self
+                    .load_avg_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = load_avg_widget_state.current_display_time
+                        + self.app_config_fields.time_interval;
+                    if new_time <= retention {
+                        load_avg_widget_state.current_display_time = new_time;
+                        self.load_avg_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            load_avg_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if load_avg_widget_state.current_display_time != retention {
+                        load_avg_widget_state.current_display_time = retention;
+                        self.load_avg_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            load_avg_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Latency => {
+                if let Some(latency_widget_state) = self
+                    .latency_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = latency_widget_state.current_display_time
+                        + self.app_config_fields.time_interval;
+                    if new_time <= retention {
+                        latency_widget_state.current_display_time = new_time;
+                        self.latency_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            latency_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if latency_widget_state.current_display_time != retention {
+                        latency_widget_state.current_display_time = retention;
+                        self.latency_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            latency_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Plugin => {
+                if let Some(plugin_widget_state) = self
+                    .plugin_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = plugin_widget_state.current_display_time
+                        + self.app_config_fields.time_interval;
+                    if new_time <= retention {
+                        plugin_widget_state.current_display_time = new_time;
+                        self.plugin_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            plugin_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if plugin_widget_state.current_display_time != retention {
+                        plugin_widget_state.current_display_time = retention;
+                        self.plugin_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            plugin_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Frequency => {
+                if let Some(frequency_widget_state) = self
+                    .frequency_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = frequency_widget_state.current_display_time
+                        + self.app_config_fields.time_interval;
+                    if new_time <= retention {
+                        frequency_widget_state.current_display_time = new_time;
+                        self.frequency_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            frequency_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if frequency_widget_state.current_display_time != retention {
+                        frequency_widget_state.current_display_time = retention;
+                        self.frequency_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            frequency_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn zoom_in(&mut self) {
+        match self.current_widget.widget_type {
+            BottomWidgetType::Cpu | BottomWidgetType::BasicCpu => {
+                if let Some(cpu_widget_state) = self
+                    .cpu_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = cpu_widget_state.current_display_time
+                        - self.app_config_fields.time_interval;
+                    if new_time >= constants::STALE_MIN_MILLISECONDS {
                         cpu_widget_state.current_display_time = new_time;
                         self.cpu_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
                             cpu_widget_state.autohide_timer = Some(Instant::now());
                         }
                     } else if cpu_widget_state.current_display_time
-                        != constants::STALE_MAX_MILLISECONDS
+                        != constants::STALE_MIN_MILLISECONDS
                     {
-                        cpu_widget_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
+                        cpu_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
                         self.cpu_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
                             cpu_widget_state.autohide_timer = Some(Instant::now());
@@ -2263,133 +4742,231 @@ impl App {
                     }
                 }
             }
-            BottomWidgetType::Mem => {
+            BottomWidgetType::Mem | BottomWidgetType::BasicMem => {
                 if let Some(mem_widget_state) = self
                     .mem_state
                     .widget_states
                     .get_mut(&self.current_widget.widget_id)
                 {
                     let new_time = mem_widget_state.current_display_time
-                        + self.app_config_fields.time_interval;
-                    if new_time <= constants::STALE_MAX_MILLISECONDS {
+                        - self.app_config_fields.time_interval;
+                    if new_time >= constants::STALE_MIN_MILLISECONDS {
                         mem_widget_state.current_display_time = new_time;
                         self.mem_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
                             mem_widget_state.autohide_timer = Some(Instant::now());
                         }
-                    } else if mem_widget_state.current_display_time
-                        != constants::STALE_MAX_MILLISECONDS
+                    } else if mem_widget_state.current_display_time
+                        != constants::STALE_MIN_MILLISECONDS
+                    {
+                        mem_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
+                        self.mem_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            mem_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Net | BottomWidgetType::BasicNet => {
+                if let Some(net_widget_state) = self
+                    .net_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = net_widget_state.current_display_time
+                        - self.app_config_fields.time_interval;
+                    if new_time >= constants::STALE_MIN_MILLISECONDS {
+                        net_widget_state.current_display_time = new_time;
+                        self.net_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            net_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if net_widget_state.current_display_time
+                        != constants::STALE_MIN_MILLISECONDS
+                    {
+                        net_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
+                        self.net_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            net_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Battery => {
+                if let Some(battery_widget_state) = self
+                    .battery_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = battery_widget_state.current_display_time
+                        - self.app_config_fields.time_interval;
+                    if new_time >= constants::STALE_MIN_MILLISECONDS {
+                        battery_widget_state.current_display_time = new_time;
+                        if self.app_config_fields.autohide_time {
+                            battery_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if battery_widget_state.current_display_time
+                        != constants::STALE_MIN_MILLISECONDS
+                    {
+                        battery_widget_state.current_display_time =
+                            constants::STALE_MIN_MILLISECONDS;
+                        if self.app_config_fields.autohide_time {
+                            battery_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Power => {
+                if let Some(power_widget_state) = self
+                    .power_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = power_widget_state.current_display_time
+                        - self.app_config_fields.time_interval;
+                    if new_time >= constants::STALE_MIN_MILLISECONDS {
+                        power_widget_state.current_display_time = new_time;
+                        self.power_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            power_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if power_widget_state.current_display_time
+                        != constants::STALE_MIN_MILLISECONDS
+                    {
+                        power_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
+                        self.power_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            power_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Pressure => {
+                if let Some(pressure_widget_state) = self
+                    .pressure_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = pressure_widget_state.current_display_time
+                        - self.app_config_fields.time_interval;
+                    if new_time >= constants::STALE_MIN_MILLISECONDS {
+                        pressure_widget_state.current_display_time = new_time;
+                        self.pressure_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            pressure_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if pressure_widget_state.current_display_time
+                        != constants::STALE_MIN_MILLISECONDS
                     {
-                        mem_widget_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
-                        self.mem_state.force_update = Some(self.current_widget.widget_id);
+                        pressure_widget_state.current_display_time =
+                            constants::STALE_MIN_MILLISECONDS;
+                        self.pressure_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
-                            mem_widget_state.autohide_timer = Some(Instant::now());
+                            pressure_widget_state.autohide_timer = Some(Instant::now());
                         }
                     }
                 }
             }
-            BottomWidgetType::Net => {
-                if let Some(net_widget_state) = self
-                    .net_state
+            BottomWidgetType::LoadAvg => {
+                if let Some(load_avg_widget_state) = self
+                    .load_avg_state
                     .widget_states
                     .get_mut(&self.current_widget.widget_id)
                 {
-                    let new_time = net_widget_state.current_display_time
-                        + self.app_config_fields.time_interval;
-                    if new_time <= constants::STALE_MAX_MILLISECONDS {
-                        net_widget_state.current_display_time = new_time;
-                        self.net_state.force_update = Some(self.current_widget.widget_id);
+                    let new_time = load_avg_widget_state.current_display_time
+                        - self.app_config_fields.time_interval;
+                    if new_time >= constants::STALE_MIN_MILLISECONDS {
+                        load_avg_widget_state.current_display_time = new_time;
+                        self.load_avg_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
-                            net_widget_state.autohide_timer = Some(Instant::now());
+                            load_avg_widget_state.autohide_timer = Some(Instant::now());
                         }
-                    } else if net_widget_state.current_display_time
-                        != constants::STALE_MAX_MILLISECONDS
+                    } else if load_avg_widget_state.current_display_time
+                        != constants::STALE_MIN_MILLISECONDS
                     {
-                        net_widget_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
-                        self.net_state.force_update = Some(self.current_widget.widget_id);
+                        load_avg_widget_state.current_display_time =
+                            constants::STALE_MIN_MILLISECONDS;
+                        self.load_avg_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
-                            net_widget_state.autohide_timer = Some(Instant::now());
+                            load_avg_widget_state.autohide_timer = Some(Instant::now());
                         }
                     }
                 }
             }
-            _ => {}
-        }
-    }
-
-    fn zoom_in(&mut self) {
-        match self.current_widget.widget_type {
-            BottomWidgetType::Cpu => {
-                if let Some(cpu_widget_state) = self
-                    .cpu_state
+            BottomWidgetType::Latency => {
+                if let Some(latency_widget_state) = self
+                    .latency_state
                     .widget_states
                     .get_mut(&self.current_widget.widget_id)
                 {
-                    let new_time = cpu_widget_state.current_display_time
+                    let new_time = latency_widget_state.current_display_time
                         - self.app_config_fields.time_interval;
                     if new_time >= constants::STALE_MIN_MILLISECONDS {
-                        cpu_widget_state.current_display_time = new_time;
-                        self.cpu_state.force_update = Some(self.current_widget.widget_id);
+                        latency_widget_state.current_display_time = new_time;
+                        self.latency_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
-                            cpu_widget_state.autohide_timer = Some(Instant::now());
+                            latency_widget_state.autohide_timer = Some(Instant::now());
                         }
-                    } else if cpu_widget_state.current_display_time
+                    } else if latency_widget_state.current_display_time
                         != constants::STALE_MIN_MILLISECONDS
                     {
-                        cpu_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
-                        self.cpu_state.force_update = Some(self.current_widget.widget_id);
+                        latency_widget_state.current_display_time =
+                            constants::STALE_MIN_MILLISECONDS;
+                        self.latency_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
-                            cpu_widget_state.autohide_timer = Some(Instant::now());
+                            latency_widget_state.autohide_timer = Some(Instant::now());
                         }
                     }
                 }
             }
-            BottomWidgetType::Mem => {
-                if let Some(mem_widget_state) = self
-                    .mem_state
+            BottomWidgetType::Plugin => {
+                if let Some(plugin_widget_state) = self
+                    .plugin_state
                     .widget_states
                     .get_mut(&self.current_widget.widget_id)
                 {
-                    let new_time = mem_widget_state.current_display_time
+                    let new_time = plugin_widget_state.current_display_time
                         - self.app_config_fields.time_interval;
                     if new_time >= constants::STALE_MIN_MILLISECONDS {
-                        mem_widget_state.current_display_time = new_time;
-                        self.mem_state.force_update = Some(self.current_widget.widget_id);
+                        plugin_widget_state.current_display_time = new_time;
+                        self.plugin_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
-                            mem_widget_state.autohide_timer = Some(Instant::now());
+                            plugin_widget_state.autohide_timer = Some(Instant::now());
                         }
-                    } else if mem_widget_state.current_display_time
+                    } else if plugin_widget_state.current_display_time
                         != constants::STALE_MIN_MILLISECONDS
                     {
-                        mem_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
-                        self.mem_state.force_update = Some(self.current_widget.widget_id);
+                        plugin_widget_state.current_display_time =
+                            constants::STALE_MIN_MILLISECONDS;
+                        self.plugin_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
-                            mem_widget_state.autohide_timer = Some(Instant::now());
+                            plugin_widget_state.autohide_timer = Some(Instant::now());
                         }
                     }
                 }
             }
-            BottomWidgetType::Net => {
-                if let Some(net_widget_state) = self
-                    .net_state
+            BottomWidgetType::Frequency => {
+                if let Some(frequency_widget_state) = self
+                    .frequency_state
                     .widget_states
                     .get_mut(&self.current_widget.widget_id)
                 {
-                    let new_time = net_widget_state.current_display_time
+                    let new_time = frequency_widget_state.current_display_time
                         - self.app_config_fields.time_interval;
                     if new_time >= constants::STALE_MIN_MILLISECONDS {
-                        net_widget_state.current_display_time = new_time;
-                        self.net_state.force_update = Some(self.current_widget.widget_id);
+                        frequency_widget_state.current_display_time = new_time;
+                        self.frequency_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
-                            net_widget_state.autohide_timer = Some(Instant::now());
+                            frequency_widget_state.autohide_timer = Some(Instant::now());
                         }
-                    } else if net_widget_state.current_display_time
+                    } else if frequency_widget_state.current_display_time
                         != constants::STALE_MIN_MILLISECONDS
                     {
-                        net_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
-                        self.net_state.force_update = Some(self.current_widget.widget_id);
+                        frequency_widget_state.current_display_time =
+                            constants::STALE_MIN_MILLISECONDS;
+                        self.frequency_state.force_update = Some(self.current_widget.widget_id);
                         if self.app_config_fields.autohide_time {
-                            net_widget_state.autohide_timer = Some(Instant::now());
+                            frequency_widget_state.autohide_timer = Some(Instant::now());
                         }
                     }
                 }
@@ -2398,6 +4975,235 @@ impl App {
         }
     }
 
+    /// Returns the title of a widget, which is the one set for it in the layout if there is one,
+    /// or `default_title` if not.
+    pub fn get_widget_title(&self, widget_id: u64, default_title: &str) -> String {
+        self.widget_map
+            .get(&widget_id)
+            .and_then(|widget| widget.title.clone())
+            .unwrap_or_else(|| default_title.to_string())
+    }
+
+    /// Works out the derived metrics from the latest data.
+    pub fn update_derived_metrics(&mut self) {
+        let derived_harvest = self.derived_metrics.evaluate(&self.data_collection);
+        self.data_collection.eat_derived(derived_harvest);
+    }
+
+    /// Checks the alerts against the latest data, and shows and sends any that went over their
+    /// thresholds.
+    pub fn check_alerts(&mut self) {
+        let mut messages = Vec::new();
+        for alert in &mut self.alerts {
+            let value = alert
+                .metric
+                .get_value(&self.data_collection, &self.app_config_fields);
+            if let (true, Some(value)) = (alert.update(value), value) {
+                messages.push(format!("Alert: {}", alert.get_message(value)));
+                alert.send(value);
+            }
+        }
+
+        if !messages.is_empty() {
+            self.set_status_message(messages.join(", "));
+        }
+    }
+
+    /// Shows a message at the bottom of the screen for a few seconds.
+    pub fn set_status_message(&mut self, message: String) {
+        self.status_message = Some((message, Instant::now()));
+        self.is_force_redraw = true;
+    }
+
+    /// Takes the widget states of this app's layout, for when it's switched away from.
+    pub fn into_layout_state(self) -> SavedLayoutState {
+        SavedLayoutState {
+            widget_types: self.get_widget_types(),
+            current_widget: self.current_widget,
+            cpu_state: self.cpu_state,
+            mem_state: self.mem_state,
+            net_state: self.net_state,
+            proc_state: self.proc_state,
+            temp_state: self.temp_state,
+            disk_state: self.disk_state,
+            battery_state: self.battery_state,
+            power_state: self.power_state,
+            pressure_state: self.pressure_state,
+            load_avg_state: self.load_avg_state,
+            latency_state: self.latency_state,
+            plugin_state: self.plugin_state,
+            frequency_state: self.frequency_state,
+            cgroup_state: self.cgroup_state,
+            event_state: self.event_state,
+            user_state: self.user_state,
+            basic_table_widget_state: self.basic_table_widget_state,
+        }
+    }
+
+    /// Restores widget states taken with [`App::into_layout_state`]. Nothing is restored if the
+    /// layout's widgets have changed since, like if the config file was edited.
+    pub fn restore_layout_state(&mut self, state: SavedLayoutState) {
+        if state.widget_types != self.get_widget_types() {
+            return;
+        }
+
+        self.current_widget = state.current_widget;
+        self.cpu_state = state.cpu_state;
+        self.mem_state = state.mem_state;
+        self.net_state = state.net_state;
+        self.proc_state = state.proc_state;
+        self.temp_state = state.temp_state;
+        self.disk_state = state.disk_state;
+        self.battery_state = state.battery_state;
+        self.power_state = state.power_state;
+        self.pressure_state = state.pressure_state;
+        self.load_avg_state = state.load_avg_state;
+        self.latency_state = state.latency_state;
+        self.plugin_state = state.plugin_state;
+        self.frequency_state = state.frequency_state;
+        self.cgroup_state = state.cgroup_state;
+        self.event_state = state.event_state;
+        self.user_state = state.user_state;
+        self.basic_table_widget_state = state.basic_table_widget_state;
+    }
+
+    /// Restores widget states taken with [`App::into_layout_state`] after the layout was edited.
+    /// Widgets are matched up by type, in order, so a widget that's kept keeps its state, like
+    /// its sorting or search, even if it was moved.
+    pub fn restore_edited_layout_state(&mut self, mut state: SavedLayoutState) {
+        let mut previous_widgets = state.widget_types.clone();
+        let id_map = self
+            .get_widget_types()
+            .into_iter()
+            .filter_map(|(widget_id, widget_type)| {
+                let index = previous_widgets
+                    .iter()
+                    .position(|(_, previous_type)| *previous_type == widget_type)?;
+                Some((previous_widgets.remove(index).0, widget_id))
+            })
+            .collect::<Vec<_>>();
+
+        // Each type of widget keeps its states in its own map, keyed by widget ID.
+        macro_rules! move_widget_states {
+            ($($state:ident),*) => {
+                $(
+                    for (previous_id, widget_id) in &id_map {
+                        if let Some(widget_state) = state.$state.widget_states.remove(previous_id) {
+                            self.$state.widget_states.insert(*widget_id, widget_state);
+                        }
+                    }
+                )*
+            };
+        }
+        move_widget_states!(
+            cpu_state,
+            mem_state,
+            net_state,
+            proc_state,
+            temp_state,
+            disk_state,
+            battery_state,
+            power_state,
+            pressure_state,
+            load_avg_state,
+            latency_state,
+            plugin_state,
+            frequency_state,
+            cgroup_state,
+            event_state,
+            user_state
+        );
+
+        if let Some(widget) = id_map
+            .iter()
+            .find(|(previous_id, _)| *previous_id == state.current_widget.widget_id)
+            .and_then(|(_, widget_id)| self.widget_map.get(widget_id))
+        {
+            self.current_widget = widget.clone();
+        }
+        self.net_state.filter = state.net_state.filter;
+    }
+
+    /// Returns the ID and type of each widget, in order of their IDs.
+    fn get_widget_types(&self) -> Vec<(u64, BottomWidgetType)> {
+        let mut widget_types = self
+            .widget_map
+            .iter()
+            .map(|(widget_id, widget)| (*widget_id, widget.widget_type.clone()))
+            .collect::<Vec<_>>();
+        widget_types.sort_by_key(|(widget_id, _)| *widget_id);
+
+        widget_types
+    }
+
+    /// Moves graphs that are still showing the previous default time range to the current one,
+    /// for when the default is changed while running.
+    pub fn update_default_time_value(&mut self, previous_default_time_value: u64) {
+        let default_time_value = self.app_config_fields.default_time_value;
+        if default_time_value == previous_default_time_value {
+            return;
+        }
+
+        let display_times = self
+            .cpu_state
+            .widget_states
+            .values_mut()
+            .map(|state| &mut state.current_display_time)
+            .chain(
+                self.mem_state
+                    .widget_states
+                    .values_mut()
+                    .map(|state| &mut state.current_display_time),
+            )
+            .chain(
+                self.net_state
+                    .widget_states
+                    .values_mut()
+                    .map(|state| &mut state.current_display_time),
+            )
+            .chain(
+                self.battery_state
+                    .widget_states
+                    .values_mut()
+                    .map(|state| &mut state.current_display_time),
+            )
+            .chain(
+                self.power_state
+                    .widget_states
+                    .values_mut()
+                    .map(|state| &mut state.current_display_time),
+            )
+            .chain(
+                self.pressure_state
+                    .widget_states
+                    .values_mut()
+                    .map(|state| &mut state.current_display_time),
+            )
+            .chain(
+                self.load_avg_state
+                    .widget_states
+                    .values_mut()
+                    .map(|state| &mut state.current_display_time),
+            )
+            .chain(
+                self.latency_state
+                    .widget_states
+                    .values_mut()
+                    .map(|state| &mut state.current_display_time),
+            )
+            .chain(
+                self.plugin_state
+                    .widget_states
+                    .values_mut()
+                    .map(|state| &mut state.current_display_time),
+            );
+        for display_time in display_times {
+            if *display_time == previous_default_time_value {
+                *display_time = default_time_value;
+            }
+        }
+    }
+
     fn reset_cpu_zoom(&mut self) {
         if let Some(cpu_widget_state) = self
             .cpu_state
@@ -2440,15 +5246,153 @@ impl App {
         }
     }
 
+    fn reset_battery_zoom(&mut self) {
+        if let Some(battery_widget_state) = self
+            .battery_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            battery_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            if self.app_config_fields.autohide_time {
+                battery_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
+    fn reset_power_zoom(&mut self) {
+        if let Some(power_widget_state) = self
+            .power_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            power_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            self.power_state.force_update = Some(self.current_widget.widget_id);
+            if self.app_config_fields.autohide_time {
+                power_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
+    fn reset_pressure_zoom(&mut self) {
+        if let Some(pressure_widget_state) = self
+            .pressure_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            pressure_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            self.pressure_state.force_update = Some(self.current_widget.widget_id);
+            if self.app_config_fields.autohide_time {
+                pressure_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
+    fn reset_load_avg_zoom(&mut self) {
+        if let Some(load_avg_widget_state) = self
+            .load_avg_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            load_avg_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            self.load_avg_state.force_update = Some(self.current_widget.widget_id);
+            if self.app_config_fields.autohide_time {
+                load_avg_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
+    fn reset_latency_zoom(&mut self) {
+        if let Some(latency_widget_state) = self
+            .latency_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            latency_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            self.latency_state.force_update = Some(self.current_widget.widget_id);
+            if self.app_config_fields.autohide_time {
+                latency_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
+    fn reset_plugin_zoom(&mut self) {
+        if let Some(plugin_widget_state) = self
+            .plugin_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            plugin_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            self.plugin_state.force_update = Some(self.current_widget.widget_id);
+            if self.app_config_fields.autohide_time {
+                plugin_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
+    fn reset_frequency_zoom(&mut self) {
+        if let Some(frequency_widget_state) = self
+            .frequency_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            frequency_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            self.frequency_state.force_update = Some(self.current_widget.widget_id);
+            if self.app_config_fields.autohide_time {
+                frequency_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
     fn reset_zoom(&mut self) {
         match self.current_widget.widget_type {
-            BottomWidgetType::Cpu => self.reset_cpu_zoom(),
-            BottomWidgetType::Mem => self.reset_mem_zoom(),
-            BottomWidgetType::Net => self.reset_net_zoom(),
+            BottomWidgetType::Cpu | BottomWidgetType::BasicCpu => self.reset_cpu_zoom(),
+            BottomWidgetType::Mem | BottomWidgetType::BasicMem => self.reset_mem_zoom(),
+            BottomWidgetType::Net | BottomWidgetType::BasicNet => self.reset_net_zoom(),
+            BottomWidgetType::Battery => self.reset_battery_zoom(),
+            BottomWidgetType::Power => self.reset_power_zoom(),
+            BottomWidgetType::Pressure => self.reset_pressure_zoom(),
+            BottomWidgetType::LoadAvg => self.reset_load_avg_zoom(),
+            BottomWidgetType::Latency => self.reset_latency_zoom(),
+            BottomWidgetType::Plugin => self.reset_plugin_zoom(),
+            BottomWidgetType::Frequency => self.reset_frequency_zoom(),
             _ => {}
         }
     }
 
+    /// Returns how many cells outside of a small click target still hit it.
+    fn get_hit_padding(&self) -> u16 {
+        if self.app_config_fields.use_termux_profile {
+            TOUCH_HIT_PADDING
+        } else {
+            0
+        }
+    }
+
+    /// Starts dragging the layout split at the clicked location, if there is one.
+    pub fn on_left_mouse_down(&mut self, x: u16, y: u16) {
+        let hit_padding = self.get_hit_padding();
+        self.layout_drag = if self.is_in_dialog() || self.is_expanded {
+            None
+        } else {
+            self.layout_splits
+                .iter()
+                .find(|split| split.is_hit(x, y, hit_padding))
+                .cloned()
+        };
+    }
+
+    /// Resizes the layout components around the split being dragged, if any.
+    pub fn on_left_mouse_drag(&mut self, x: u16, y: u16) {
+        if let Some(split) = &self.layout_drag {
+            let position = if split.is_vertical { y } else { x };
+            self.pending_layout_resize = Some((split.clone(), position));
+        }
+    }
+
+    pub fn on_left_mouse_release(&mut self) {
+        self.layout_drag = None;
+    }
+
     /// Moves the mouse to the widget that was clicked on, then propagates the click down to be
     /// handled by the widget specifically.
     pub fn on_left_mouse_up(&mut self, x: u16, y: u16) {
@@ -2464,6 +5408,7 @@ impl App {
         // Short circuit if we're in basic table... we might have to handle the basic table arrow
         // case here...
 
+        let hit_padding = self.get_hit_padding();
         if let Some(bt) = &mut self.basic_table_widget_state {
             if let (
                 Some((left_tlc_x, left_tlc_y)),
@@ -2472,7 +5417,22 @@ impl App {
                 Some((right_brc_x, right_brc_y)),
             ) = (bt.left_tlc, bt.left_brc, bt.right_tlc, bt.right_brc)
             {
-                if (x >= left_tlc_x && y >= left_tlc_y) && (x < left_brc_x && y < left_brc_y) {
+                let arrow_hit = find_hit_target(
+                    x,
+                    y,
+                    &[
+                        (
+                            (left_tlc_x, left_tlc_y),
+                            (left_brc_x.saturating_sub(1), left_brc_y.saturating_sub(1)),
+                        ),
+                        (
+                            (right_tlc_x, right_tlc_y),
+                            (right_brc_x.saturating_sub(1), right_brc_y.saturating_sub(1)),
+                        ),
+                    ],
+                    hit_padding,
+                );
+                if arrow_hit == Some(0) {
                     // Case for the left "button" in the simple arrow.
                     if let Some(new_widget) =
                         self.widget_map.get(&(bt.currently_displayed_widget_id))
@@ -2492,9 +5452,7 @@ impl App {
                         self.move_widget_selection(&WidgetDirection::Left);
                         return;
                     }
-                } else if (x >= right_tlc_x && y >= right_tlc_y)
-                    && (x < right_brc_x && y < right_brc_y)
-                {
+                } else if arrow_hit == Some(1) {
                     // Case for the right "button" in the simple arrow.
                     if let Some(new_widget) =
                         self.widget_map.get(&(bt.currently_displayed_widget_id))
@@ -2525,11 +5483,15 @@ impl App {
         // Second short circuit --- are we in the dd dialog state?  If so, only check yes/no/signals
         // and bail after.
         if self.is_in_dialog() {
-            match self.delete_dialog_state.button_positions.iter().find(
-                |(tl_x, tl_y, br_x, br_y, _idx)| {
-                    (x >= *tl_x && y >= *tl_y) && (x <= *br_x && y <= *br_y)
-                },
-            ) {
+            let button_bounds = self
+                .delete_dialog_state
+                .button_positions
+                .iter()
+                .map(|(tl_x, tl_y, br_x, br_y, _idx)| ((*tl_x, *tl_y), (*br_x, *br_y)))
+                .collect::<Vec<_>>();
+            match find_hit_target(x, y, &button_bounds, self.get_hit_padding())
+                .and_then(|index| self.delete_dialog_state.button_positions.get(index))
+            {
                 Some((_, _, _, _, 0)) => {
                     self.delete_dialog_state.selected_signal = KillSignal::Cancel
                 }
@@ -2545,6 +5507,25 @@ impl App {
             return;
         }
 
+        // Check if a tab header of a tabbed column was clicked.
+        if !self.is_expanded {
+            let tab_bounds = self
+                .layout_tab_click_locs
+                .iter()
+                .map(|((tlc_x, tlc_y), (brc_x, brc_y), _)| {
+                    (
+                        (*tlc_x, *tlc_y),
+                        (brc_x.saturating_sub(1), brc_y.saturating_sub(1)),
+                    )
+                })
+                .collect::<Vec<_>>();
+            if let Some(index) = find_hit_target(x, y, &tab_bounds, self.get_hit_padding()) {
+                let (_, _, tab_widget_id) = self.layout_tab_click_locs[index];
+                self.select_tab(tab_widget_id);
+                return;
+            }
+        }
+
         let mut failed_to_get = true;
         for (new_widget_id, widget) in &self.widget_map {
             if let (Some((tlc_x, tlc_y)), Some((brc_x, brc_y))) =
@@ -2582,6 +5563,11 @@ impl App {
             return;
         }
 
+        // Clicking on a table's column header sorts by that column.
+        if self.try_select_table_header(x, y) {
+            return;
+        }
+
         // Now handle click propagation down to widget.
         if let (Some((_tlc_x, tlc_y)), Some((_brc_x, brc_y))) = (
             &self.current_widget.top_left_corner,
@@ -2596,7 +5582,10 @@ impl App {
                     | BottomWidgetType::ProcSort
                     | BottomWidgetType::CpuLegend
                     | BottomWidgetType::Temp
-                    | BottomWidgetType::Disk => {
+                    | BottomWidgetType::Disk
+                    | BottomWidgetType::Cgroup
+                    | BottomWidgetType::Events
+                    | BottomWidgetType::Users => {
                         // Get our index...
                         let clicked_entry = y - *tlc_y;
                         let header_offset = self.header_offset(&self.current_widget);
@@ -2612,13 +5601,14 @@ impl App {
                                         if let Some(visual_index) =
                                             proc_widget_state.table_state.table_state.selected()
                                         {
-                                            // If in tree mode, also check to see if this click is on
+                                            // If in tree or user mode, also check to see if this click is on
                                             // the same entry as the already selected one - if it is,
                                             // then we minimize.
 
                                             let is_tree_mode = matches!(
                                                 proc_widget_state.mode,
                                                 ProcWidgetMode::Tree { .. }
+                                                    | ProcWidgetMode::User { .. }
                                             );
 
                                             let previous_scroll_position = proc_widget_state
@@ -2698,44 +5688,64 @@ impl App {
                                         }
                                     }
                                 }
-                                _ => {}
-                            }
-                        } else {
-                            // We might have clicked on a header!  Check if we only exceeded the table + border offset, and
-                            // it's implied we exceeded the gap offset.
-                            if clicked_entry == border_offset {
-                                if let BottomWidgetType::Proc = &self.current_widget.widget_type {
-                                    if let Some(proc_widget_state) = self
-                                        .proc_state
-                                        .get_mut_widget_state(self.current_widget.widget_id)
+                                BottomWidgetType::Cgroup => {
+                                    if let Some(cgroup_widget_state) = self
+                                        .cgroup_state
+                                        .get_widget_state(self.current_widget.widget_id)
                                     {
-                                        if let SortState::Sortable(st) =
-                                            &mut proc_widget_state.table_state.sort_state
+                                        if let Some(visual_index) =
+                                            cgroup_widget_state.table_state.table_state.selected()
                                         {
-                                            if st.try_select_location(x, y).is_some() {
-                                                proc_widget_state.force_data_update();
-                                            }
+                                            self.change_cgroup_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Events => {
+                                    if let Some(event_widget_state) = self
+                                        .event_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            event_widget_state.table_state.table_state.selected()
+                                        {
+                                            self.change_event_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Users => {
+                                    if let Some(user_widget_state) = self
+                                        .user_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            user_widget_state.table_state.table_state.selected()
+                                        {
+                                            self.change_user_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
                                         }
                                     }
                                 }
+                                _ => {}
                             }
                         }
                     }
                     BottomWidgetType::Battery => {
+                        let hit_padding = self.get_hit_padding();
                         if let Some(battery_widget_state) = self
                             .battery_state
                             .get_mut_widget_state(self.current_widget.widget_id)
                         {
-                            if let Some(tab_spacing) = &battery_widget_state.tab_click_locs {
-                                for (itx, ((tlc_x, tlc_y), (brc_x, brc_y))) in
-                                    tab_spacing.iter().enumerate()
-                                {
-                                    if (x >= *tlc_x && y >= *tlc_y) && (x <= *brc_x && y <= *brc_y)
-                                    {
-                                        battery_widget_state.currently_selected_battery_index = itx;
-                                        break;
-                                    }
-                                }
+                            if let Some(itx) =
+                                battery_widget_state.tab_click_locs.as_ref().and_then(
+                                    |tab_spacing| find_hit_target(x, y, tab_spacing, hit_padding),
+                                )
+                            {
+                                battery_widget_state.currently_selected_battery_index = itx;
                             }
                         }
                     }
@@ -2745,6 +5755,42 @@ impl App {
         }
     }
 
+    /// Sorts the current table widget by the column whose header is at the given location, or
+    /// toggles the sort order if it is already sorted by that column. Returns whether a header
+    /// was clicked.
+    fn try_select_table_header(&mut self, x: u16, y: u16) -> bool {
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Proc => {
+                if let Some(proc_widget_state) = self.proc_state.get_mut_widget_state(widget_id) {
+                    if proc_widget_state.table_state.try_select_header(x, y) {
+                        proc_widget_state.force_data_update();
+                        return true;
+                    }
+                }
+            }
+            BottomWidgetType::Disk => {
+                if let Some(disk_widget_state) = self.disk_state.get_mut_widget_state(widget_id) {
+                    if disk_widget_state.table_state.try_select_header(x, y) {
+                        disk_widget_state.force_data_update();
+                        return true;
+                    }
+                }
+            }
+            BottomWidgetType::Temp => {
+                if let Some(temp_widget_state) = self.temp_state.get_mut_widget_state(widget_id) {
+                    if temp_widget_state.table_state.try_select_header(x, y) {
+                        temp_widget_state.force_data_update();
+                        return true;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        false
+    }
+
     fn is_drawing_border(&self) -> bool {
         self.is_expanded || !self.app_config_fields.use_basic_mode
     }
@@ -2769,3 +5815,115 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::data_harvester::processes::SystemdUnit;
+
+    #[test]
+    fn test_find_hit_target() {
+        let targets = [((2, 0), (4, 0)), ((6, 0), (8, 0))];
+
+        assert_eq!(find_hit_target(3, 0, &targets, 0), Some(0));
+        assert_eq!(find_hit_target(8, 0, &targets, 0), Some(1));
+        assert_eq!(find_hit_target(5, 0, &targets, 0), None);
+        assert_eq!(find_hit_target(4, 1, &targets, 0), None);
+
+        // Misses hit the closest target within the padding, and the first one on a tie.
+        assert_eq!(find_hit_target(5, 0, &targets, 1), Some(0));
+        assert_eq!(find_hit_target(9, 1, &targets, 1), Some(1));
+        assert_eq!(find_hit_target(0, 0, &targets, 1), None);
+    }
+
+    fn new_app() -> App {
+        let matches = crate::clap::build_app().get_matches_from(["btm"]);
+        let mut config = Config::default();
+        let (widget_layout, default_widget_id, default_widget_type_option) =
+            crate::options::get_widget_layout(&matches, &config).unwrap();
+        crate::options::build_app(
+            &matches,
+            &mut config,
+            &widget_layout,
+            default_widget_id,
+            &default_widget_type_option,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cycle_host_converts_new_host() {
+        let mut app = new_app();
+
+        // Both hosts have samples at the same times, so only their values tell them apart.
+        let start = Instant::now();
+        let get_data = |value: f64| {
+            let mut data = DataCollection::default();
+            for millis in [0, 1000, 2000] {
+                let time = start + Duration::from_millis(millis);
+                data.timed_data.push_back((
+                    time,
+                    TimedData {
+                        mem_data: Some(value),
+                        ..Default::default()
+                    },
+                ));
+                data.current_instant = time;
+            }
+            data
+        };
+        app.remote_hosts = vec![
+            RemoteHost::new("first:7777".to_string()),
+            RemoteHost::new("second:7777".to_string()),
+        ];
+        app.data_collection = get_data(1.0);
+        app.remote_hosts[1].data_collection = get_data(2.0);
+
+        crate::convert_collected_data(&mut app);
+        assert!(app
+            .converted_data
+            .mem_data
+            .points()
+            .iter()
+            .all(|(_, value)| *value == 1.0));
+
+        app.cycle_host(true);
+        assert_eq!(app.selected_host, 1);
+        crate::convert_collected_data(&mut app);
+        let points = app.converted_data.mem_data.points();
+        assert_eq!(points.len(), 3);
+        assert!(points.iter().all(|(_, value)| *value == 2.0));
+    }
+
+    #[test]
+    fn test_unit_control_can_be_cancelled() {
+        let mut app = new_app();
+        app.unit_control_state.pending = Some((
+            SystemdUnit {
+                name: "sshd.service".to_string(),
+                is_user: false,
+            },
+            systemd::UnitAction::Stop,
+        ));
+        assert!(app.is_in_dialog());
+
+        app.on_esc();
+        assert!(app.unit_control_state.pending.is_none());
+        assert!(!app.is_in_dialog());
+    }
+
+    #[test]
+    fn test_closing_process_graph_unpins() {
+        let mut app = new_app();
+        for close in [App::on_esc, App::reset] {
+            app.process_graph_state.pid = 42;
+            app.process_graph_state.is_showing_graph = true;
+            app.data_collection.pin_process(42);
+
+            close(&mut app);
+            assert!(!app.process_graph_state.is_showing_graph);
+            assert!(app.data_collection.pinned_pids.is_empty());
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Getting and setting the CPU affinity of processes, similar to `taskset`. This is only
+//! supported on Linux.
+
+#[cfg(target_os = "linux")]
+use crate::{
+    utils::error::{BottomError, Result},
+    Pid,
+};
+
+/// Converts the last OS error from reading or changing a process' affinity into a readable error.
+#[cfg(target_os = "linux")]
+fn get_affinity_error(action: &str) -> BottomError {
+    let err = std::io::Error::last_os_error();
+    let reason = match err.raw_os_error() {
+        Some(libc::ESRCH) => "the target process did not exist.",
+        Some(libc::EPERM) => {
+            "permission denied. Changing the affinity of another user's process requires root."
+        }
+        Some(libc::EINVAL) => "none of the selected CPUs are available.",
+        _ => "Unknown error occurred.",
+    };
+
+    BottomError::GenericError(format!(
+        "Failed to {} - error code {} - {}",
+        action,
+        err.raw_os_error()
+            .map(|code| code.to_string())
+            .unwrap_or_else(|| "???".to_string()),
+        reason
+    ))
+}
+
+/// Returns the number of CPUs that an affinity mask can refer to.
+#[cfg(target_os = "linux")]
+fn get_num_cpus() -> usize {
+    let num_cpus = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) };
+    if num_cpus > 0 {
+        (num_cpus as usize).min(libc::CPU_SETSIZE as usize)
+    } else {
+        1
+    }
+}
+
+/// Returns whether the process is allowed to run on each CPU.
+#[cfg(target_os = "linux")]
+pub fn get_affinity(pid: Pid) -> Result<Vec<bool>> {
+    let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    let result = unsafe {
+        libc::sched_getaffinity(pid, std::mem::size_of::<libc::cpu_set_t>(), &mut cpu_set)
+    };
+
+    if result != 0 {
+        Err(get_affinity_error("get the CPU affinity"))
+    } else {
+        Ok((0..get_num_cpus())
+            .map(|cpu| unsafe { libc::CPU_ISSET(cpu, &cpu_set) })
+            .collect())
+    }
+}
+
+/// Restricts the process to run on only the given CPUs.
+#[cfg(target_os = "linux")]
+pub fn set_affinity(pid: Pid, cpus: &[bool]) -> Result<()> {
+    if !cpus.contains(&true) {
+        return Err(BottomError::GenericError(
+            "At least one CPU must be selected.".to_string(),
+        ));
+    }
+
+    let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for (cpu, _) in cpus.iter().enumerate().filter(|(_, allowed)| **allowed) {
+        unsafe { libc::CPU_SET(cpu, &mut cpu_set) };
+    }
+
+    let result =
+        unsafe { libc::sched_setaffinity(pid, std::mem::size_of::<libc::cpu_set_t>(), &cpu_set) };
+    if result != 0 {
+        Err(get_affinity_error("set the CPU affinity"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Formats the allowed CPUs as a list of ranges, in the same style as `taskset -c`; for
+/// example, `0-3,6`.
+pub fn format_cpu_list(cpus: &[bool]) -> String {
+    let mut ranges = Vec::new();
+    let mut range_start = None;
+    for (cpu, allowed) in cpus.iter().chain(std::iter::once(&false)).enumerate() {
+        match (*allowed, range_start) {
+            (true, None) => range_start = Some(cpu),
+            (false, Some(start)) => {
+                ranges.push(if cpu - 1 == start {
+                    start.to_string()
+                } else {
+                    format!("{}-{}", start, cpu - 1)
+                });
+                range_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if ranges.is_empty() {
+        "none".to_string()
+    } else {
+        ranges.join(",")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_cpu_list() {
+        assert_eq!(format_cpu_list(&[true, true, true, true]), "0-3");
+        assert_eq!(
+            format_cpu_list(&[true, true, false, true, false, true, true]),
+            "0-1,3,5-6"
+        );
+        assert_eq!(format_cpu_list(&[false, true]), "1");
+        assert_eq!(format_cpu_list(&[false, false]), "none");
+        assert_eq!(format_cpu_list(&[]), "none");
+    }
+}
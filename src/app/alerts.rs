@@ -0,0 +1,403 @@
+//! Alerts, which are thresholds from the `[[alerts]]` section of the config file that are checked
+//! after every harvest. When a metric goes over its threshold, a message is shown at the bottom of
+//! the screen, and a desktop notification or a webhook can also be sent. An alert only triggers
+//! again once the metric has gone back under the threshold.
+
+use std::{
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    process::Command,
+    time::Duration,
+};
+
+use super::{data_farmer::DataCollection, data_harvester::cpu, AppConfigFields};
+
+/// How long to wait for a webhook to connect and respond.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AlertMetric {
+    /// The average CPU usage, as a percentage.
+    Cpu,
+    /// The memory usage, as a percentage.
+    Memory,
+    /// The swap usage, as a percentage.
+    Swap,
+    /// The hottest temperature sensor, in the configured unit.
+    Temperature,
+    /// The fullest disk, as a percentage.
+    Disk,
+    /// The derived metric with this name.
+    Derived(String),
+    /// How many temperature sensors are over their critical temperature, from the
+    /// `[temp_thresholds]` section. This is only used if `alert_on_critical` is set there.
+    CriticalTemperature,
+}
+
+impl AlertMetric {
+    pub fn parse(metric: &str) -> Option<Self> {
+        match metric.to_lowercase().as_str() {
+            "cpu" => Some(AlertMetric::Cpu),
+            "mem" | "memory" => Some(AlertMetric::Memory),
+            "swap" => Some(AlertMetric::Swap),
+            "temp" | "temperature" => Some(AlertMetric::Temperature),
+            "disk" => Some(AlertMetric::Disk),
+            _ => None,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            AlertMetric::Cpu => "cpu",
+            AlertMetric::Memory => "memory",
+            AlertMetric::Swap => "swap",
+            AlertMetric::Temperature => "temperature",
+            AlertMetric::Disk => "disk",
+            AlertMetric::Derived(name) => name,
+            AlertMetric::CriticalTemperature => "critical_temperature",
+        }
+    }
+
+    /// Returns the current value of the metric, if it's being collected.
+    pub fn get_value(&self, data: &DataCollection, config: &AppConfigFields) -> Option<f64> {
+        match self {
+            AlertMetric::Cpu => cpu::get_average(&data.cpu_harvest).map(|cpu| cpu.cpu_usage),
+            AlertMetric::Memory => data.memory_harvest.use_percent,
+            AlertMetric::Swap => data.swap_harvest.use_percent,
+            AlertMetric::Temperature => data
+                .temp_harvest
+                .iter()
+                .map(|sensor| f64::from(sensor.temperature))
+                .reduce(f64::max),
+            AlertMetric::Disk => data
+                .disk_harvest
+                .iter()
+                .filter_map(|disk| match (disk.used_space, disk.total_space) {
+                    (Some(used), Some(total)) if total > 0 => {
+                        Some(used as f64 / total as f64 * 100.0)
+                    }
+                    _ => None,
+                })
+                .reduce(f64::max),
+            AlertMetric::Derived(name) => data
+                .derived_harvest
+                .iter()
+                .find(|derived_metric| derived_metric.name == *name)
+                .and_then(|derived_metric| derived_metric.series.first())
+                .map(|series| series.value),
+            AlertMetric::CriticalTemperature => {
+                // Temperatures are harvested in the shown unit, while thresholds are in Celsius.
+                let num_critical = data
+                    .temp_harvest
+                    .iter()
+                    .filter(|sensor| {
+                        matches!(
+                            config.temp_thresholds.get_threshold(&sensor.name),
+                            Some(threshold) if sensor.temperature
+                                > config.temperature_type.convert_from_celsius(threshold.critical)
+                        )
+                    })
+                    .count();
+                Some(num_critical as f64)
+            }
+        }
+    }
+
+    fn get_unit(&self) -> &'static str {
+        match self {
+            AlertMetric::Temperature => "°",
+            AlertMetric::Derived(_) | AlertMetric::CriticalTemperature => "",
+            _ => "%",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Alert {
+    pub metric: AlertMetric,
+    pub threshold: f64,
+    /// Whether to send a desktop notification when the alert triggers.
+    pub notify: bool,
+    /// An `http://` URL to send a JSON payload to when the alert triggers.
+    pub webhook: Option<String>,
+    is_triggered: bool,
+}
+
+impl Alert {
+    pub fn new(metric: AlertMetric, threshold: f64, notify: bool, webhook: Option<String>) -> Self {
+        Alert {
+            metric,
+            threshold,
+            notify,
+            webhook,
+            is_triggered: false,
+        }
+    }
+
+    /// Updates the alert with the current value of its metric, and returns whether it just went
+    /// over the threshold.
+    pub fn update(&mut self, value: Option<f64>) -> bool {
+        let is_over = matches!(value, Some(value) if value > self.threshold);
+        let has_triggered = is_over && !self.is_triggered;
+        self.is_triggered = is_over;
+
+        has_triggered
+    }
+
+    /// Returns the message shown for the alert, like `cpu is at 95.2% (over 90%)`.
+    pub fn get_message(&self, value: f64) -> String {
+        if let AlertMetric::CriticalTemperature = self.metric {
+            return if value as u64 == 1 {
+                "1 temperature sensor is over its critical temperature".to_string()
+            } else {
+                format!(
+                    "{} temperature sensors are over their critical temperature",
+                    value
+                )
+            };
+        }
+
+        let unit = self.metric.get_unit();
+        format!(
+            "{} is at {:.1}{} (over {}{})",
+            self.metric.get_name(),
+            value,
+            unit,
+            self.threshold,
+            unit
+        )
+    }
+
+    /// Sends the notification and webhook of an alert that has triggered. This is done on another
+    /// thread, as either can take a while.
+    pub fn send(&self, value: f64) {
+        if !self.notify && self.webhook.is_none() {
+            return;
+        }
+
+        let message = self.get_message(value);
+        let notify = self.notify;
+        let webhook = self.webhook.clone();
+        let payload = serde_json::json!({
+            "metric": self.metric.get_name(),
+            "value": value,
+            "threshold": self.threshold,
+            "message": message,
+        })
+        .to_string();
+
+        std::thread::spawn(move || {
+            if notify {
+                send_notification(&message);
+            }
+            if let Some(webhook) = webhook {
+                let _ = post_webhook(&webhook, &payload);
+            }
+        });
+    }
+}
+
+/// Shows a desktop notification with the system's own tools, so nothing has to be linked in for
+/// it. Nothing happens if they aren't installed.
+fn send_notification(message: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification \"{}\" with title \"bottom\"",
+            message.replace('"', "\\\"")
+        ));
+        command
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-Command"]).arg(format!(
+            "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null; \
+            $template = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+            $text = $template.GetElementsByTagName('text'); \
+            $text.Item(0).AppendChild($template.CreateTextNode('bottom')) | Out-Null; \
+            $text.Item(1).AppendChild($template.CreateTextNode('{}')) | Out-Null; \
+            [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('bottom').Show([Windows.UI.Notifications.ToastNotification]::new($template))",
+            message.replace('\'', "''")
+        ));
+        command
+    } else {
+        let mut command = Command::new("notify-send");
+        command.arg("bottom").arg(message);
+        command
+    };
+
+    let _ = command.output();
+}
+
+/// Splits an `http://` URL into its host, port, and path.
+pub fn parse_webhook_url(url: &str) -> Result<(&str, u16, &str), String> {
+    if url.starts_with("https://") {
+        return Err(format!(
+            "the webhook '{}' uses HTTPS, which isn't supported; send it through a local relay with an http:// URL instead.",
+            url
+        ));
+    }
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("the webhook '{}' must be an http:// URL.", url))?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (
+            host,
+            port.parse()
+                .map_err(|_| format!("the webhook '{}' has an invalid port.", url))?,
+        ),
+        None => (authority, 80),
+    };
+
+    if host.is_empty() {
+        Err(format!("the webhook '{}' has no host.", url))
+    } else {
+        Ok((host, port, path))
+    }
+}
+
+/// Sends a JSON payload to a webhook with a POST request.
+fn post_webhook(url: &str, payload: &str) -> Result<(), String> {
+    let (host, port, path) = parse_webhook_url(url)?;
+    let address = (host, port)
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addresses| addresses.next())
+        .ok_or_else(|| format!("couldn't resolve {}", host))?;
+    let mut stream =
+        TcpStream::connect_timeout(&address, WEBHOOK_TIMEOUT).map_err(|err| err.to_string())?;
+    stream
+        .set_read_timeout(Some(WEBHOOK_TIMEOUT))
+        .map_err(|err| err.to_string())?;
+    stream
+        .write_all(
+            format!(
+                "POST {} HTTP/1.0\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                path,
+                host,
+                payload.len(),
+                payload
+            )
+            .as_bytes(),
+        )
+        .map_err(|err| err.to_string())?;
+
+    // The response isn't used, but is read so the request isn't cut off.
+    let mut response = String::new();
+    let _ = stream.read_to_string(&mut response);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_alert_update() {
+        let mut alert = Alert::new(AlertMetric::Cpu, 90.0, false, None);
+
+        assert!(!alert.update(Some(50.0)));
+        assert!(alert.update(Some(95.0)));
+        assert!(!alert.update(Some(97.0)));
+        assert!(!alert.update(None));
+        assert!(alert.update(Some(91.0)));
+    }
+
+    #[test]
+    fn test_parse_webhook_url() {
+        assert_eq!(
+            parse_webhook_url("http://localhost:8080/alerts"),
+            Ok(("localhost", 8080, "/alerts"))
+        );
+        assert_eq!(
+            parse_webhook_url("http://example.com"),
+            Ok(("example.com", 80, "/"))
+        );
+        assert!(parse_webhook_url("https://example.com").is_err());
+        assert!(parse_webhook_url("http://example.com:port/").is_err());
+        assert!(parse_webhook_url("http:///alerts").is_err());
+    }
+
+    fn get_config_fields(config: &str) -> AppConfigFields {
+        let matches = crate::clap::build_app().get_matches_from(["btm"]);
+        let mut config: crate::options::Config = toml::from_str(config).unwrap();
+        let (widget_layout, default_widget_id, default_widget_type_option) =
+            crate::options::get_widget_layout(&matches, &config).unwrap();
+        crate::options::build_app(
+            &matches,
+            &mut config,
+            &widget_layout,
+            default_widget_id,
+            &default_widget_type_option,
+            None,
+        )
+        .unwrap()
+        .app_config_fields
+    }
+
+    #[test]
+    fn test_cpu_without_average() {
+        use crate::app::data_harvester::cpu::CpuData;
+
+        let config = get_config_fields("");
+
+        let core = |usage| CpuData {
+            cpu_prefix: "CPU".to_string(),
+            cpu_count: None,
+            cpu_usage: usage,
+            breakdown: None,
+        };
+        let mut data = DataCollection {
+            cpu_harvest: vec![core(20.0), core(60.0)],
+            ..DataCollection::default()
+        };
+        assert_eq!(AlertMetric::Cpu.get_value(&data, &config), Some(40.0));
+
+        data.cpu_harvest.insert(
+            0,
+            CpuData {
+                cpu_prefix: "AVG".to_string(),
+                ..core(45.0)
+            },
+        );
+        assert_eq!(AlertMetric::Cpu.get_value(&data, &config), Some(45.0));
+
+        data.cpu_harvest.clear();
+        assert_eq!(AlertMetric::Cpu.get_value(&data, &config), None);
+    }
+
+    #[test]
+    fn test_critical_temperature() {
+        use crate::app::data_harvester::temperature::TempHarvest;
+
+        let config = get_config_fields(
+            "[flags]\ntemperature_type = \"f\"\n[temp_thresholds]\nwarning = 70.0\ncritical = 90.0\n\
+             sensors = { nvme = { warning = 50.0, critical = 60.0 } }\nalert_on_critical = true\n",
+        );
+        let sensor = |name: &str, temperature: f32| TempHarvest {
+            name: name.to_string(),
+            temperature,
+        };
+
+        // 150°F is under the default critical temperature, but over the NVMe's.
+        let mut data = DataCollection {
+            temp_harvest: vec![sensor("cpu", 150.0), sensor("nvme", 150.0)],
+            ..DataCollection::default()
+        };
+        let metric = AlertMetric::CriticalTemperature;
+        assert_eq!(metric.get_value(&data, &config), Some(1.0));
+
+        data.temp_harvest[0].temperature = 200.0;
+        assert_eq!(metric.get_value(&data, &config), Some(2.0));
+
+        let alert = Alert::new(metric, 0.0, false, None);
+        assert_eq!(
+            alert.get_message(2.0),
+            "2 temperature sensors are over their critical temperature"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! Copying table rows to the clipboard. Text is sent to the terminal as an OSC 52 sequence, which
+//! also works over SSH in terminals that support it, and is handed to the first of the usual
+//! clipboard tools that's installed.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use itertools::Itertools;
+
+use crate::{
+    components::text_table::{strip_sort_shortcut, TableComponentHeader, TableComponentState},
+    data_conversion::TableData,
+    utils::gen_util::base64_encode,
+};
+
+/// Returns the shown columns of a table as tab-separated text, and how many rows it has. This is
+/// either just the selected row, or every row after a line of column names.
+pub fn table_to_tsv<H: TableComponentHeader>(
+    table_state: &TableComponentState<H>, table_data: &TableData, whole_table: bool,
+) -> Option<(String, usize)> {
+    let shown_columns = table_state
+        .columns
+        .iter()
+        .map(|column| !column.is_hidden)
+        .collect::<Vec<_>>();
+    let to_line = |cells: Vec<String>| {
+        cells
+            .iter()
+            .zip(&shown_columns)
+            .filter(|(_, is_shown)| **is_shown)
+            .map(|(cell, _)| cell.trim().replace(['\t', '\n'], " "))
+            .join("\t")
+    };
+    let row_to_line = |index: usize| {
+        table_data.data.get(index).map(|row| {
+            to_line(
+                row.row()
+                    .iter()
+                    .map(|cell| cell.main_text().to_string())
+                    .collect(),
+            )
+        })
+    };
+
+    if whole_table {
+        if table_data.data.is_empty() {
+            return None;
+        }
+        let header = to_line(
+            table_state
+                .columns
+                .iter()
+                .map(|column| {
+                    strip_sort_shortcut(column.header.header_text().main_text().trim()).to_string()
+                })
+                .collect(),
+        );
+        let lines = (0..table_data.data.len()).filter_map(row_to_line);
+
+        Some((
+            std::iter::once(header).chain(lines).join("\n"),
+            table_data.data.len(),
+        ))
+    } else {
+        row_to_line(table_state.current_scroll_position).map(|line| (line, 1))
+    }
+}
+
+/// Copies text to the clipboard. The OSC 52 sequence is always written to `out`, as a native tool
+/// being installed doesn't mean it can reach the clipboard the user is looking at.
+pub fn copy_to_clipboard<W: Write>(out: &mut W, text: &str) -> std::io::Result<()> {
+    copy_with_native_tool(text);
+
+    out.write_all(osc52_sequence(text).as_bytes())?;
+    out.flush()
+}
+
+/// Returns the escape sequence that asks the terminal to put text on the clipboard.
+fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+/// Tries each clipboard tool for this platform until one works. Returns whether any did.
+fn copy_with_native_tool(text: &str) -> bool {
+    native_tools().into_iter().any(|(program, args)| {
+        let child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        match child {
+            Ok(mut child) => {
+                let is_written = matches!(
+                    child
+                        .stdin
+                        .take()
+                        .map(|mut stdin| stdin.write_all(text.as_bytes())),
+                    Some(Ok(()))
+                );
+                // Dropping stdin above closes it, so the tool knows the text is complete.
+                matches!(child.wait(), Ok(status) if status.success()) && is_written
+            }
+            Err(_) => false,
+        }
+    })
+}
+
+/// The clipboard tools to try, as a program and its arguments.
+fn native_tools() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        vec![("pbcopy", &[])]
+    } else if cfg!(target_os = "windows") {
+        vec![("clip", &[])]
+    } else {
+        let mut tools: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            tools.push(("wl-copy", &[]));
+        }
+        if std::env::var_os("DISPLAY").is_some() {
+            tools.push(("xclip", &["-selection", "clipboard"]));
+            tools.push(("xsel", &["--clipboard", "--input"]));
+        }
+        tools.push(("termux-clipboard-set", &[]));
+        tools
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        components::text_table::{CellContent, TableComponentColumn},
+        data_conversion::TableRow,
+    };
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("1\tbash"), "\x1b]52;c;MQliYXNo\x07");
+    }
+
+    #[test]
+    fn test_table_to_tsv() {
+        let mut table_state = TableComponentState::new(vec![
+            TableComponentColumn::new(CellContent::new("PID(p)", None)),
+            TableComponentColumn::new(CellContent::new("Name(n)", None)),
+            TableComponentColumn::new(CellContent::new("CPU%", None)),
+        ]);
+        table_state.columns[2].is_hidden = true;
+        table_state.current_scroll_position = 1;
+        let table_data = TableData {
+            data: vec![
+                TableRow::Raw(vec!["1".into(), "init".into(), "0.0%".into()]),
+                TableRow::Raw(vec!["42".into(), " my\tapp ".into(), "1.5%".into()]),
+            ],
+            col_widths: vec![],
+        };
+
+        assert_eq!(
+            table_to_tsv(&table_state, &table_data, false),
+            Some(("42\tmy app".to_string(), 1))
+        );
+        assert_eq!(
+            table_to_tsv(&table_state, &table_data, true),
+            Some(("PID\tName\n1\tinit\n42\tmy app".to_string(), 2))
+        );
+        assert_eq!(
+            table_to_tsv(
+                &table_state,
+                &TableData {
+                    data: vec![],
+                    col_widths: vec![]
+                },
+                true
+            ),
+            None
+        );
+    }
+}
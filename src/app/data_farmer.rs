@@ -15,16 +15,30 @@
 
 use once_cell::sync::Lazy;
 
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 use itertools::Itertools;
 
-use std::{time::Instant, vec::Vec};
+use std::{
+    collections::VecDeque,
+    net::IpAddr,
+    time::{Duration, Instant, SystemTime},
+    vec::Vec,
+};
 
 #[cfg(feature = "battery")]
 use crate::data_harvester::batteries;
 
 use crate::{
-    data_harvester::{cpu, disks, memory, network, processes::ProcessHarvest, temperature, Data},
+    constants::{
+        DISK_TREND_MIN_MILLISECONDS, GAP_INTERVAL_MULTIPLIER, GAP_MIN_MILLISECONDS,
+        PROCESS_CPU_HISTORY_LENGTH,
+    },
+    data_conversion::get_average_cpu_usage,
+    data_harvester::{
+        addresses, cgroups, cpu, disks, events, frequency, latency, memory, network, plugins,
+        power, pressure, processes::ProcessHarvest, self_usage, system_info, temperature, users,
+        wireless, Data, HarvestError, UnavailableHarvest,
+    },
     utils::gen_util::{get_decimal_bytes, GIGA_LIMIT},
     Pid,
 };
@@ -37,14 +51,57 @@ pub type Value = f64;
 pub struct TimedData {
     pub rx_data: Value,
     pub tx_data: Value,
-    pub cpu_data: Vec<Value>,
-    pub load_avg_data: [f32; 3],
+    /// Total bits received and transmitted so far.
+    pub total_rx_data: Value,
+    pub total_tx_data: Value,
+    /// Network errors and dropped packets per second.
+    pub error_data: Value,
+    pub drop_data: Value,
+    /// Stored as `f32` in a boxed slice, as there's one of these per sample for every core, and
+    /// with a long retention period, this adds up.
+    pub cpu_data: Box<[f32]>,
+    /// How the usage of each core breaks down by state, which is empty unless that's turned on.
+    pub cpu_breakdown_data: Box<[cpu::CpuBreakdown]>,
+    pub load_avg_data: Option<[f32; 3]>,
     pub mem_data: Option<Value>,
     pub swap_data: Option<Value>,
+    /// Pages swapped in and out per second.
+    pub swap_activity_data: Option<(Value, Value)>,
+    pub power_data: Option<power::PowerHarvest>,
+    pub pressure_data: Option<pressure::PressureHarvest>,
+    /// The round-trip time in milliseconds for each latency host, if it replied. This is empty if
+    /// latency wasn't harvested.
+    pub latency_data: Vec<Option<Value>>,
+    pub frequency_data: Option<frequency::FrequencyHarvest>,
+    /// The value of each plugin and derived metric series, in the same order as
+    /// [`DataCollection::plugin_series`].
+    /// Series that weren't reported are `None`, and ones seen after this was harvested are left
+    /// off the end.
+    pub plugin_data: Vec<Option<Value>>,
+    /// Charge percentage and power draw in watts, for each battery.
+    #[cfg(feature = "battery")]
+    pub battery_data: Vec<(Value, Value)>,
+    /// CPU and memory usage percentages for each pinned process.
+    pub pinned_process_data: FxHashMap<Pid, (Value, Value)>,
 }
 
 pub type StringPidMap = FxHashMap<String, Vec<Pid>>;
 
+/// The highest values seen since bottom started or they were last reset. Unlike the data for
+/// graphs, these are kept past the retention period.
+#[derive(Clone, Debug, Default)]
+pub struct PeakData {
+    /// The peak average CPU usage, as a percentage.
+    pub cpu_usage: Option<f64>,
+    /// The memory harvest with the most memory used.
+    pub memory: Option<memory::MemHarvest>,
+    /// The peak RX and TX rates, in bits per second.
+    pub rx: u64,
+    pub tx: u64,
+    /// The peak temperature of each sensor, keyed by name.
+    pub temperatures: FxHashMap<String, f32>,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct ProcessData {
     /// A PID to process data map.
@@ -61,10 +118,17 @@ pub struct ProcessData {
 
     /// PIDs corresponding to processes that have no parents.
     pub orphan_pids: Vec<Pid>,
+
+    /// A mapping between a process PID and its most recent CPU usage samples, oldest first. This
+    /// is only kept if a process widget shows it.
+    pub cpu_history: FxHashMap<Pid, VecDeque<f64>>,
+
+    /// The average of each process' samples in `cpu_history`, for sorting by it.
+    pub cpu_history_averages: FxHashMap<Pid, f64>,
 }
 
 impl ProcessData {
-    fn ingest(&mut self, list_of_processes: Vec<ProcessHarvest>) {
+    fn ingest(&mut self, list_of_processes: Vec<ProcessHarvest>, keep_cpu_history: bool) {
         // TODO: [Optimization] Probably more efficient to all of this in the data collection step, but it's fine for now.
         self.name_pid_map.clear();
         self.cmd_pid_map.clear();
@@ -102,6 +166,33 @@ impl ProcessData {
         self.cmd_pid_map.shrink_to_fit();
         self.process_parent_mapping.shrink_to_fit();
 
+        // Keep a fixed number of CPU samples per process, dropping any processes that are gone.
+        if keep_cpu_history {
+            self.cpu_history = list_of_processes
+                .iter()
+                .map(|process| {
+                    let mut history = self
+                        .cpu_history
+                        .remove(&process.pid)
+                        .unwrap_or_else(|| VecDeque::with_capacity(PROCESS_CPU_HISTORY_LENGTH));
+                    if history.len() >= PROCESS_CPU_HISTORY_LENGTH {
+                        history.pop_front();
+                    }
+                    history.push_back(process.cpu_usage_percent);
+
+                    (process.pid, history)
+                })
+                .collect();
+            self.cpu_history_averages = self
+                .cpu_history
+                .iter()
+                .map(|(pid, history)| (*pid, history.iter().sum::<f64>() / history.len() as f64))
+                .collect();
+        } else if !self.cpu_history.is_empty() {
+            self.cpu_history = FxHashMap::default();
+            self.cpu_history_averages = FxHashMap::default();
+        }
+
         let process_pid_map = list_of_processes
             .into_iter()
             .map(|process| (process.pid, process))
@@ -132,6 +223,26 @@ impl ProcessData {
             res
         }
     }
+
+    /// Returns the PIDs of every descendant of the given processes, with parents before their
+    /// children. PIDs in `pids` are never included.
+    pub fn get_descendant_pids(&self, pids: &[Pid]) -> Vec<Pid> {
+        let mut visited: FxHashSet<Pid> = pids.iter().copied().collect();
+        let mut descendants = Vec::new();
+        let mut to_visit: VecDeque<Pid> = pids.iter().copied().collect();
+        while let Some(pid) = to_visit.pop_front() {
+            if let Some(children) = self.process_parent_mapping.get(&pid) {
+                for child in children {
+                    if visited.insert(*child) {
+                        descendants.push(*child);
+                        to_visit.push_back(*child);
+                    }
+                }
+            }
+        }
+
+        descendants
+    }
 }
 
 /// AppCollection represents the pooled data stored within the main app
@@ -147,10 +258,25 @@ impl ProcessData {
 pub struct DataCollection {
     pub current_instant: Instant,
     pub frozen_instant: Option<Instant>,
-    pub timed_data_vec: Vec<(Instant, TimedData)>,
+    /// The data kept for graphs, oldest first. Entries past the retention period are dropped from
+    /// the front as new ones are added to the back.
+    pub timed_data: VecDeque<(Instant, TimedData)>,
+    /// Gaps in `timed_data`, as the times of the samples on either side, oldest first. Graphs
+    /// don't draw a line across these.
+    pub gaps: VecDeque<(Instant, Instant)>,
+    /// The wall clock time of the last sample. Unlike an [`Instant`], this also moves forward while
+    /// the system is asleep, so it's used to notice that it was.
+    pub last_sample_time: Option<SystemTime>,
     pub network_harvest: network::NetworkHarvest,
+    pub wireless_harvest: Vec<wireless::WirelessHarvest>,
+    pub address_harvest: Vec<addresses::InterfaceAddressHarvest>,
+    /// The last public IP that was successfully looked up.
+    pub public_ip: Option<IpAddr>,
     pub memory_harvest: memory::MemHarvest,
     pub swap_harvest: memory::MemHarvest,
+    /// The last swap counters and when they were harvested, to work out how many pages are being
+    /// swapped per second.
+    pub prev_swap_activity: Option<(Instant, memory::SwapActivityHarvest)>,
     pub cpu_harvest: cpu::CpuHarvest,
     pub load_avg_harvest: cpu::LoadAvgHarvest,
     pub process_data: ProcessData,
@@ -158,9 +284,45 @@ pub struct DataCollection {
     pub io_harvest: disks::IoHarvest,
     pub io_labels_and_prev: Vec<((u64, u64), (u64, u64))>,
     pub io_labels: Vec<(String, String)>,
+    /// When disk I/O was last harvested, which may differ from the last harvest of other data.
+    pub last_io_instant: Instant,
+    /// The used space of each disk over time, oldest first, keyed by mount point. This is kept
+    /// for as long as graph data is, and is used to estimate when a disk will be full.
+    pub disk_usage_history: FxHashMap<String, VecDeque<(Instant, u64)>>,
     pub temp_harvest: Vec<temperature::TempHarvest>,
+    pub power_harvest: Option<power::PowerHarvest>,
+    pub pressure_harvest: Option<pressure::PressureHarvest>,
+    pub cgroup_harvest: Vec<cgroups::CgroupHarvest>,
+    pub latency_harvest: Vec<latency::LatencyHarvest>,
+    pub frequency_harvest: Option<frequency::FrequencyHarvest>,
+    pub plugin_harvest: Vec<plugins::PluginHarvest>,
+    /// The values of the derived metrics, stored like plugins with a single series each.
+    pub derived_harvest: Vec<plugins::PluginHarvest>,
+    /// The plugin and series name of every plugin and derived metric series seen so far. Series
+    /// are only ever added to the end, so they can be looked up by index in
+    /// [`TimedData::plugin_data`].
+    pub plugin_series: Vec<(String, String)>,
+    /// The kernel events of the last harvest, oldest first.
+    pub kernel_event_harvest: Vec<events::KernelEventHarvest>,
+    pub system_info_harvest: Option<system_info::SystemInfoHarvest>,
+    pub user_session_harvest: Vec<users::UserSession>,
+    pub peaks: PeakData,
     #[cfg(feature = "battery")]
     pub battery_harvest: Vec<batteries::BatteryHarvest>,
+    /// PIDs of processes whose usage is stored over time.
+    pub pinned_pids: FxHashSet<Pid>,
+    /// Whether to keep the recent CPU usage of every process, which is only done if a process
+    /// widget shows it.
+    pub keep_process_cpu_history: bool,
+    /// What was failing to be collected as of the last harvest.
+    pub harvest_errors: Vec<HarvestError>,
+    /// What's skipped, as it can't be read on this system.
+    pub unavailable_harvests: Vec<UnavailableHarvest>,
+    pub self_usage: Option<self_usage::SelfUsageHarvest>,
+    /// How long each collector took the last time it ran.
+    pub harvest_timings: Vec<self_usage::HarvestTiming>,
+    /// How long the last harvest took.
+    pub harvest_duration: Duration,
 }
 
 impl Default for DataCollection {
@@ -168,10 +330,16 @@ impl Default for DataCollection {
         DataCollection {
             current_instant: Instant::now(),
             frozen_instant: None,
-            timed_data_vec: Vec::default(),
+            timed_data: VecDeque::default(),
+            gaps: VecDeque::default(),
+            last_sample_time: None,
             network_harvest: network::NetworkHarvest::default(),
+            wireless_harvest: Vec::default(),
+            address_harvest: Vec::default(),
+            public_ip: None,
             memory_harvest: memory::MemHarvest::default(),
             swap_harvest: memory::MemHarvest::default(),
+            prev_swap_activity: None,
             cpu_harvest: cpu::CpuHarvest::default(),
             load_avg_harvest: cpu::LoadAvgHarvest::default(),
             process_data: Default::default(),
@@ -179,31 +347,85 @@ impl Default for DataCollection {
             io_harvest: disks::IoHarvest::default(),
             io_labels_and_prev: Vec::default(),
             io_labels: Vec::default(),
+            last_io_instant: Instant::now(),
+            disk_usage_history: FxHashMap::default(),
             temp_harvest: Vec::default(),
+            power_harvest: None,
+            pressure_harvest: None,
+            cgroup_harvest: Vec::default(),
+            latency_harvest: Vec::default(),
+            frequency_harvest: None,
+            plugin_harvest: Vec::default(),
+            derived_harvest: Vec::default(),
+            plugin_series: Vec::default(),
+            kernel_event_harvest: Vec::default(),
+            system_info_harvest: None,
+            user_session_harvest: Vec::default(),
+            peaks: PeakData::default(),
             #[cfg(feature = "battery")]
             battery_harvest: Vec::default(),
+            pinned_pids: FxHashSet::default(),
+            keep_process_cpu_history: false,
+            harvest_errors: Vec::default(),
+            unavailable_harvests: Vec::default(),
+            self_usage: None,
+            harvest_timings: Vec::default(),
+            harvest_duration: Duration::ZERO,
         }
     }
 }
 
 impl DataCollection {
     pub fn reset(&mut self) {
-        self.timed_data_vec = Vec::default();
+        self.timed_data = VecDeque::default();
+        self.gaps = VecDeque::default();
+        self.last_sample_time = None;
         self.network_harvest = network::NetworkHarvest::default();
+        self.wireless_harvest = Vec::default();
+        self.address_harvest = Vec::default();
         self.memory_harvest = memory::MemHarvest::default();
         self.swap_harvest = memory::MemHarvest::default();
+        self.prev_swap_activity = None;
         self.cpu_harvest = cpu::CpuHarvest::default();
         self.process_data = Default::default();
         self.disk_harvest = Vec::default();
         self.io_harvest = disks::IoHarvest::default();
         self.io_labels_and_prev = Vec::default();
+        self.disk_usage_history = FxHashMap::default();
         self.temp_harvest = Vec::default();
+        self.power_harvest = None;
+        self.pressure_harvest = None;
+        self.cgroup_harvest = Vec::default();
+        self.latency_harvest = Vec::default();
+        self.frequency_harvest = None;
+        self.plugin_harvest = Vec::default();
+        self.derived_harvest = Vec::default();
+        self.plugin_series = Vec::default();
+        self.kernel_event_harvest = Vec::default();
+        self.system_info_harvest = None;
+        self.user_session_harvest = Vec::default();
+        self.peaks = PeakData::default();
+        self.harvest_errors = Vec::default();
+        self.unavailable_harvests = Vec::default();
+        self.self_usage = None;
+        self.harvest_timings = Vec::default();
+        self.harvest_duration = Duration::ZERO;
         #[cfg(feature = "battery")]
         {
             self.battery_harvest = Vec::default();
         }
     }
 
+    /// Starts storing the CPU and memory usage of the process with the given PID over time.
+    pub fn pin_process(&mut self, pid: Pid) {
+        self.pinned_pids.insert(pid);
+    }
+
+    /// Stops storing the usage of the process with the given PID.
+    pub fn unpin_process(&mut self, pid: Pid) {
+        self.pinned_pids.remove(&pid);
+    }
+
     pub fn freeze(&mut self) {
         self.frozen_instant = Some(self.current_instant);
     }
@@ -212,23 +434,71 @@ impl DataCollection {
         self.frozen_instant = None;
     }
 
+    /// Drops any data older than `max_time_millis`. As data is stored oldest first, this only
+    /// needs to look at the front.
     pub fn clean_data(&mut self, max_time_millis: u64) {
         let current_time = Instant::now();
 
-        let remove_index = match self
-            .timed_data_vec
-            .binary_search_by(|(instant, _timed_data)| {
-                current_time
-                    .duration_since(*instant)
-                    .as_millis()
-                    .cmp(&(max_time_millis.into()))
-                    .reverse()
-            }) {
-            Ok(index) => index,
-            Err(index) => index,
-        };
+        while let Some((instant, _timed_data)) = self.timed_data.front() {
+            if current_time.duration_since(*instant).as_millis() > max_time_millis.into() {
+                self.timed_data.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        // A gap is kept until the sample after it is dropped.
+        while let Some((_gap_start, gap_end)) = self.gaps.front() {
+            if current_time.duration_since(*gap_end).as_millis() > max_time_millis.into() {
+                self.gaps.pop_front();
+            } else {
+                break;
+            }
+        }
 
-        self.timed_data_vec.drain(0..remove_index);
+        self.disk_usage_history.retain(|_mount_point, history| {
+            while let Some((instant, _used_space)) = history.front() {
+                if current_time.duration_since(*instant).as_millis() > max_time_millis.into() {
+                    history.pop_front();
+                } else {
+                    break;
+                }
+            }
+            !history.is_empty()
+        });
+    }
+
+    /// Estimates how long until the disk mounted at `mount_point` is full, by fitting a line to
+    /// its used space over time. This is [`None`] if the disk isn't filling up, or if there isn't
+    /// at least [`DISK_TREND_MIN_MILLISECONDS`] of history yet.
+    pub fn get_disk_time_until_full(&self, mount_point: &str) -> Option<Duration> {
+        let disk = self
+            .disk_harvest
+            .iter()
+            .find(|disk| disk.mount_point == mount_point)?;
+        let history = self.disk_usage_history.get(mount_point)?;
+        let (first_instant, _) = history.front()?;
+        let (last_instant, _) = history.back()?;
+        if last_instant.duration_since(*first_instant).as_millis()
+            < DISK_TREND_MIN_MILLISECONDS.into()
+        {
+            return None;
+        }
+
+        let bytes_per_second = get_linear_trend(
+            history
+                .iter()
+                .map(|(instant, used)| (instant.duration_since(*first_instant), *used as f64)),
+        )?;
+        if bytes_per_second > 0.0 {
+            let free_space = disk.free_space? as f64;
+            // Casting saturates, so a disk that's barely filling up doesn't overflow this.
+            Some(Duration::from_secs(
+                (free_space / bytes_per_second).round() as u64
+            ))
+        } else {
+            None
+        }
     }
 
     pub fn eat_data(&mut self, harvested_data: Box<Data>) {
@@ -236,10 +506,49 @@ impl DataCollection {
         // trace!("Harvested time: {:?}", harvested_time);
         // trace!("New current instant: {:?}", self.current_instant);
         let mut new_entry = TimedData::default();
+        self.harvest_errors = harvested_data.harvest_errors;
+        self.unavailable_harvests = harvested_data.unavailable_harvests;
+        self.self_usage = harvested_data.self_usage;
+        self.harvest_duration = harvested_data.harvest_duration;
+
+        // Not every collector runs in every harvest, so keep the last timing of the ones that
+        // didn't run this time.
+        for timing in harvested_data.harvest_timings {
+            match self
+                .harvest_timings
+                .iter_mut()
+                .find(|existing| existing.collector == timing.collector)
+            {
+                Some(existing) => existing.duration = timing.duration,
+                None => self.harvest_timings.push(timing),
+            }
+        }
 
         // Network
         if let Some(network) = harvested_data.network {
             self.eat_network(network, &mut new_entry);
+        } else if let Some((_, last_entry)) = self.timed_data.back() {
+            // Network data may be harvested less often than other data, so carry the last values
+            // forward rather than dropping to zero.
+            new_entry.rx_data = last_entry.rx_data;
+            new_entry.tx_data = last_entry.tx_data;
+            new_entry.total_rx_data = last_entry.total_rx_data;
+            new_entry.total_tx_data = last_entry.total_tx_data;
+            new_entry.error_data = last_entry.error_data;
+            new_entry.drop_data = last_entry.drop_data;
+        }
+
+        // Wireless
+        if let Some(wireless) = harvested_data.wireless {
+            self.wireless_harvest = wireless;
+        }
+
+        // Addresses
+        if let Some(addresses) = harvested_data.addresses {
+            self.address_harvest = addresses;
+        }
+        if let Some(public_ip) = harvested_data.public_ip {
+            self.public_ip = Some(public_ip);
         }
 
         // Memory and Swap
@@ -247,6 +556,11 @@ impl DataCollection {
             self.eat_memory_and_swap(memory, swap, &mut new_entry);
         }
 
+        // Swap activity
+        if let Some(swap_activity) = harvested_data.swap_activity {
+            self.eat_swap_activity(swap_activity, harvested_time, &mut new_entry);
+        }
+
         // CPU
         if let Some(cpu) = harvested_data.cpu {
             self.eat_cpu(cpu, &mut new_entry);
@@ -262,6 +576,51 @@ impl DataCollection {
             self.eat_temp(temperature_sensors);
         }
 
+        // Power
+        if let Some(power) = harvested_data.power {
+            self.eat_power(power, &mut new_entry);
+        }
+
+        // Pressure
+        if let Some(pressure) = harvested_data.pressure {
+            self.eat_pressure(pressure, &mut new_entry);
+        }
+
+        // Cgroups
+        if let Some(cgroups) = harvested_data.cgroups {
+            self.eat_cgroups(cgroups);
+        }
+
+        // Kernel events
+        if let Some(kernel_events) = harvested_data.kernel_events {
+            self.eat_kernel_events(kernel_events);
+        }
+
+        // System info
+        if let Some(system_info) = harvested_data.system_info {
+            self.eat_system_info(system_info);
+        }
+
+        // Users
+        if let Some(user_sessions) = harvested_data.user_sessions {
+            self.eat_user_sessions(user_sessions);
+        }
+
+        // Latency
+        if let Some(latency) = harvested_data.latency {
+            self.eat_latency(latency, &mut new_entry);
+        }
+
+        // Frequency
+        if let Some(frequency) = harvested_data.frequency {
+            self.eat_frequency(frequency, &mut new_entry);
+        }
+
+        // Plugins
+        if let Some(plugins) = harvested_data.plugins {
+            self.eat_plugins(plugins, &mut new_entry);
+        }
+
         // Disks
         if let Some(disks) = harvested_data.disks {
             if let Some(io) = harvested_data.io {
@@ -271,20 +630,45 @@ impl DataCollection {
 
         // Processes
         if let Some(list_of_processes) = harvested_data.list_of_processes {
-            self.eat_proc(list_of_processes);
+            self.eat_proc(list_of_processes, &mut new_entry);
         }
 
         #[cfg(feature = "battery")]
         {
             // Battery
             if let Some(list_of_batteries) = harvested_data.list_of_batteries {
-                self.eat_battery(list_of_batteries);
+                self.eat_battery(list_of_batteries, &mut new_entry);
             }
         }
 
         // And we're done eating.  Update time and push the new entry!
+        let sample_time = SystemTime::now();
+        if let Some((last_time, _)) = self.timed_data.back() {
+            let wall_elapsed = self
+                .last_sample_time
+                .and_then(|last_sample_time| sample_time.duration_since(last_sample_time).ok())
+                .unwrap_or_default();
+            let elapsed = harvested_time
+                .saturating_duration_since(*last_time)
+                .max(wall_elapsed);
+            if is_gap(self.get_last_interval(), elapsed) {
+                self.gaps.push_back((*last_time, harvested_time));
+            }
+        }
+        self.last_sample_time = Some(sample_time);
         self.current_instant = harvested_time;
-        self.timed_data_vec.push((harvested_time, new_entry));
+        self.timed_data.push_back((harvested_time, new_entry));
+    }
+
+    /// Returns the time between the last two samples, if there are two.
+    fn get_last_interval(&self) -> Option<Duration> {
+        let mut samples = self.timed_data.iter().rev();
+        match (samples.next(), samples.next()) {
+            (Some((newer_time, _)), Some((older_time, _))) => {
+                Some(newer_time.saturating_duration_since(*older_time))
+            }
+            _ => None,
+        }
     }
 
     fn eat_memory_and_swap(
@@ -296,11 +680,35 @@ impl DataCollection {
         // Swap
         new_entry.swap_data = swap.use_percent;
 
+        if !matches!(&self.peaks.memory, Some(peak) if peak.mem_used_in_kib >= memory.mem_used_in_kib)
+        {
+            self.peaks.memory = Some(memory.clone());
+        }
+
         // In addition copy over latest data for easy reference
         self.memory_harvest = memory;
         self.swap_harvest = swap;
     }
 
+    fn eat_swap_activity(
+        &mut self, swap_activity: memory::SwapActivityHarvest, harvested_time: Instant,
+        new_entry: &mut TimedData,
+    ) {
+        if let Some((prev_time, prev_activity)) = self.prev_swap_activity {
+            let elapsed = harvested_time.duration_since(prev_time).as_secs_f64();
+            if elapsed > 0.0 {
+                let get_rate =
+                    |current: u64, prev: u64| current.saturating_sub(prev) as f64 / elapsed;
+                new_entry.swap_activity_data = Some((
+                    get_rate(swap_activity.pages_in, prev_activity.pages_in),
+                    get_rate(swap_activity.pages_out, prev_activity.pages_out),
+                ));
+            }
+        }
+
+        self.prev_swap_activity = Some((harvested_time, swap_activity));
+    }
+
     fn eat_network(&mut self, network: network::NetworkHarvest, new_entry: &mut TimedData) {
         // RX
         if network.rx > 0 {
@@ -312,6 +720,17 @@ impl DataCollection {
             new_entry.tx_data = network.tx as f64;
         }
 
+        // Totals
+        new_entry.total_rx_data = network.total_rx as f64;
+        new_entry.total_tx_data = network.total_tx as f64;
+
+        // Errors and drops
+        new_entry.error_data = network.errors_per_sec;
+        new_entry.drop_data = network.drops_per_sec;
+
+        self.peaks.rx = self.peaks.rx.max(network.rx);
+        self.peaks.tx = self.peaks.tx.max(network.tx);
+
         // In addition copy over latest data for easy reference
         self.network_harvest = network;
     }
@@ -320,31 +739,107 @@ impl DataCollection {
         // Note this only pre-calculates the data points - the names will be
         // within the local copy of cpu_harvest.  Since it's all sequential
         // it probably doesn't matter anyways.
-        cpu.iter()
-            .for_each(|cpu| new_entry.cpu_data.push(cpu.cpu_usage));
+        new_entry.cpu_data = cpu.iter().map(|cpu| cpu.cpu_usage as f32).collect();
+        if cpu.iter().all(|cpu| cpu.breakdown.is_some()) {
+            new_entry.cpu_breakdown_data = cpu.iter().filter_map(|cpu| cpu.breakdown).collect();
+        }
+
+        if let Some(usage) = get_average_cpu_usage(&cpu) {
+            self.peaks.cpu_usage = Some(self.peaks.cpu_usage.map_or(usage, |peak| peak.max(usage)));
+        }
 
         self.cpu_harvest = cpu.to_vec();
     }
 
     fn eat_load_avg(&mut self, load_avg: cpu::LoadAvgHarvest, new_entry: &mut TimedData) {
-        new_entry.load_avg_data = load_avg;
+        new_entry.load_avg_data = Some(load_avg);
 
         self.load_avg_harvest = load_avg;
     }
 
     fn eat_temp(&mut self, temperature_sensors: Vec<temperature::TempHarvest>) {
+        for sensor in &temperature_sensors {
+            let peak = self
+                .peaks
+                .temperatures
+                .entry(sensor.name.clone())
+                .or_insert(sensor.temperature);
+            *peak = peak.max(sensor.temperature);
+        }
+
         // TODO: [PO] To implement
         self.temp_harvest = temperature_sensors.to_vec();
     }
 
+    fn eat_power(&mut self, power: power::PowerHarvest, new_entry: &mut TimedData) {
+        new_entry.power_data = Some(power);
+
+        self.power_harvest = Some(power);
+    }
+
+    fn eat_pressure(&mut self, pressure: pressure::PressureHarvest, new_entry: &mut TimedData) {
+        new_entry.pressure_data = Some(pressure);
+
+        self.pressure_harvest = Some(pressure);
+    }
+
+    fn eat_cgroups(&mut self, cgroups: Vec<cgroups::CgroupHarvest>) {
+        self.cgroup_harvest = cgroups;
+    }
+
+    fn eat_kernel_events(&mut self, kernel_events: Vec<events::KernelEventHarvest>) {
+        self.kernel_event_harvest = kernel_events;
+    }
+
+    fn eat_system_info(&mut self, system_info: system_info::SystemInfoHarvest) {
+        self.system_info_harvest = Some(system_info);
+    }
+
+    fn eat_user_sessions(&mut self, user_sessions: Vec<users::UserSession>) {
+        self.user_session_harvest = user_sessions;
+    }
+
+    fn eat_latency(&mut self, latency: Vec<latency::LatencyHarvest>, new_entry: &mut TimedData) {
+        new_entry.latency_data = latency.iter().map(|host| host.rtt_ms).collect();
+
+        self.latency_harvest = latency;
+    }
+
+    fn eat_frequency(&mut self, frequency: frequency::FrequencyHarvest, new_entry: &mut TimedData) {
+        new_entry.frequency_data = Some(frequency.clone());
+
+        self.frequency_harvest = Some(frequency);
+    }
+
+    fn eat_plugins(&mut self, plugins: Vec<plugins::PluginHarvest>, new_entry: &mut TimedData) {
+        for plugin in &plugins {
+            record_plugin_series(&mut self.plugin_series, plugin, new_entry);
+        }
+
+        self.plugin_harvest = plugins;
+    }
+
+    /// Stores the values of the derived metrics, which are worked out from the latest entry after
+    /// it's been eaten, in that entry.
+    pub fn eat_derived(&mut self, derived: Vec<plugins::PluginHarvest>) {
+        if let Some((_, entry)) = self.timed_data.back_mut() {
+            for metric in &derived {
+                record_plugin_series(&mut self.plugin_series, metric, entry);
+            }
+        }
+
+        self.derived_harvest = derived;
+    }
+
     fn eat_disks(
         &mut self, disks: Vec<disks::DiskHarvest>, io: disks::IoHarvest, harvested_time: Instant,
     ) {
         // TODO: [PO] To implement
 
         let time_since_last_harvest = harvested_time
-            .duration_since(self.current_instant)
+            .duration_since(self.last_io_instant)
             .as_secs_f64();
+        self.last_io_instant = harvested_time;
 
         for (itx, device) in disks.iter().enumerate() {
             if let Some(trim) = device.name.split('/').last() {
@@ -415,16 +910,300 @@ impl DataCollection {
             }
         }
 
+        for disk in &disks {
+            if let Some(used_space) = disk.used_space {
+                self.disk_usage_history
+                    .entry(disk.mount_point.clone())
+                    .or_default()
+                    .push_back((harvested_time, used_space));
+            }
+        }
+
         self.disk_harvest = disks;
         self.io_harvest = io;
     }
 
-    fn eat_proc(&mut self, list_of_processes: Vec<ProcessHarvest>) {
-        self.process_data.ingest(list_of_processes);
+    fn eat_proc(&mut self, list_of_processes: Vec<ProcessHarvest>, new_entry: &mut TimedData) {
+        if !self.pinned_pids.is_empty() {
+            new_entry.pinned_process_data = list_of_processes
+                .iter()
+                .filter(|process| self.pinned_pids.contains(&process.pid))
+                .map(|process| {
+                    (
+                        process.pid,
+                        (process.cpu_usage_percent, process.mem_usage_percent),
+                    )
+                })
+                .collect();
+        }
+
+        self.process_data
+            .ingest(list_of_processes, self.keep_process_cpu_history);
     }
 
     #[cfg(feature = "battery")]
-    fn eat_battery(&mut self, list_of_batteries: Vec<batteries::BatteryHarvest>) {
+    fn eat_battery(
+        &mut self, list_of_batteries: Vec<batteries::BatteryHarvest>, new_entry: &mut TimedData,
+    ) {
+        new_entry.battery_data = list_of_batteries
+            .iter()
+            .map(|battery| (battery.charge_percent, battery.power_consumption_rate_watts))
+            .collect();
+
         self.battery_harvest = list_of_batteries;
     }
 }
+
+/// Stores the values of a plugin's series in `entry`, adding any series not seen before to
+/// `plugin_series`.
+fn record_plugin_series(
+    plugin_series: &mut Vec<(String, String)>, plugin: &plugins::PluginHarvest,
+    entry: &mut TimedData,
+) {
+    for series in &plugin.series {
+        let index = match plugin_series
+            .iter()
+            .position(|(name, series_name)| *name == plugin.name && *series_name == series.name)
+        {
+            Some(index) => index,
+            None => {
+                plugin_series.push((plugin.name.clone(), series.name.clone()));
+                plugin_series.len() - 1
+            }
+        };
+        if entry.plugin_data.len() <= index {
+            entry.plugin_data.resize(index + 1, None);
+        }
+        entry.plugin_data[index] = Some(series.value);
+    }
+}
+
+/// Returns whether `elapsed` since the last sample is long enough to be a gap in the data, given
+/// the interval between the two samples before it. Without an interval to go by, nothing is.
+fn is_gap(last_interval: Option<Duration>, elapsed: Duration) -> bool {
+    match last_interval {
+        Some(last_interval) => {
+            elapsed > last_interval * GAP_INTERVAL_MULTIPLIER
+                && elapsed > Duration::from_millis(GAP_MIN_MILLISECONDS)
+        }
+        None => false,
+    }
+}
+
+/// Returns the slope of the least-squares line through `points`, in units per second. This is
+/// [`None`] if there are fewer than two points, or they were all taken at the same time.
+fn get_linear_trend(points: impl Iterator<Item = (Duration, f64)>) -> Option<f64> {
+    let points = points
+        .map(|(offset, value)| (offset.as_secs_f64(), value))
+        .collect::<Vec<_>>();
+    if points.len() < 2 {
+        return None;
+    }
+
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let (covariance, variance) =
+        points
+            .iter()
+            .fold((0.0, 0.0), |(covariance, variance), (x, y)| {
+                (
+                    covariance + (x - mean_x) * (y - mean_y),
+                    variance + (x - mean_x) * (x - mean_x),
+                )
+            });
+
+    if variance > 0.0 {
+        Some(covariance / variance)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_linear_trend() {
+        let secs = Duration::from_secs;
+        assert_eq!(
+            get_linear_trend(
+                vec![(secs(0), 100.0), (secs(10), 200.0), (secs(20), 300.0)].into_iter()
+            ),
+            Some(10.0)
+        );
+        assert_eq!(
+            get_linear_trend(
+                vec![(secs(0), 300.0), (secs(10), 100.0), (secs(20), 200.0)].into_iter()
+            ),
+            Some(-5.0)
+        );
+        assert_eq!(get_linear_trend(vec![(secs(0), 100.0)].into_iter()), None);
+        assert_eq!(
+            get_linear_trend(vec![(secs(5), 100.0), (secs(5), 200.0)].into_iter()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_gap() {
+        let secs = Duration::from_secs;
+        assert!(!is_gap(None, secs(60)));
+        assert!(!is_gap(Some(secs(1)), secs(2)));
+        assert!(!is_gap(Some(secs(1)), secs(4)));
+        assert!(is_gap(Some(secs(1)), secs(6)));
+        assert!(!is_gap(Some(secs(10)), secs(20)));
+        assert!(is_gap(Some(secs(10)), secs(31)));
+    }
+
+    #[test]
+    fn test_get_disk_time_until_full() {
+        let mut data = DataCollection::default();
+        data.disk_harvest.push(disks::DiskHarvest {
+            name: "/dev/sda1".to_string(),
+            mount_point: "/".to_string(),
+            free_space: Some(6000),
+            ..Default::default()
+        });
+        assert_eq!(data.get_disk_time_until_full("/"), None);
+
+        let start = Instant::now();
+        let add_usage = |data: &mut DataCollection, secs: u64, used_space: u64| {
+            data.disk_usage_history
+                .entry("/".to_string())
+                .or_default()
+                .push_back((start + Duration::from_secs(secs), used_space));
+        };
+        add_usage(&mut data, 0, 1000);
+        add_usage(&mut data, 10, 1100);
+        assert_eq!(data.get_disk_time_until_full("/"), None);
+
+        add_usage(&mut data, 60, 1600);
+        assert_eq!(
+            data.get_disk_time_until_full("/"),
+            Some(Duration::from_secs(600))
+        );
+
+        add_usage(&mut data, 70, 0);
+        assert_eq!(data.get_disk_time_until_full("/"), None);
+        assert_eq!(data.get_disk_time_until_full("/home"), None);
+    }
+
+    #[test]
+    fn test_get_descendant_pids() {
+        let mut process_data = ProcessData::default();
+        process_data.process_parent_mapping.insert(1, vec![2, 3]);
+        process_data.process_parent_mapping.insert(2, vec![4]);
+        process_data.process_parent_mapping.insert(4, vec![5]);
+        process_data.process_parent_mapping.insert(6, vec![7]);
+
+        assert_eq!(process_data.get_descendant_pids(&[1]), vec![2, 3, 4, 5]);
+        assert_eq!(process_data.get_descendant_pids(&[2, 6]), vec![4, 7, 5]);
+        assert_eq!(process_data.get_descendant_pids(&[1, 4]), vec![2, 3, 5]);
+        assert!(process_data.get_descendant_pids(&[5]).is_empty());
+    }
+
+    #[test]
+    fn test_process_cpu_history() {
+        let process = |cpu_usage_percent: f64| ProcessHarvest {
+            pid: 1,
+            cpu_usage_percent,
+            ..ProcessHarvest::default()
+        };
+        let mut process_data = ProcessData::default();
+
+        process_data.ingest(vec![process(10.0)], true);
+        process_data.ingest(vec![process(30.0)], true);
+        assert_eq!(
+            process_data.cpu_history[&1],
+            VecDeque::from(vec![10.0, 30.0])
+        );
+        assert_eq!(process_data.cpu_history_averages[&1], 20.0);
+
+        process_data.ingest(vec![process(50.0)], false);
+        assert!(process_data.cpu_history.is_empty());
+        assert!(process_data.cpu_history_averages.is_empty());
+    }
+    #[test]
+    fn test_temperature_peaks() {
+        let sensor = |name: &str, temperature: f32| temperature::TempHarvest {
+            name: name.to_string(),
+            temperature,
+        };
+
+        let mut data = DataCollection::default();
+        data.eat_temp(vec![sensor("cpu", 50.0), sensor("gpu", 40.0)]);
+        data.eat_temp(vec![sensor("cpu", 70.0), sensor("gpu", 30.0)]);
+        data.eat_temp(vec![sensor("cpu", 60.0)]);
+
+        assert_eq!(data.peaks.temperatures.get("cpu"), Some(&70.0));
+        assert_eq!(data.peaks.temperatures.get("gpu"), Some(&40.0));
+
+        data.reset();
+        assert!(data.peaks.temperatures.is_empty());
+    }
+
+    #[test]
+    fn test_harvest_timings() {
+        let timing = |collector: &str, millis: u64| self_usage::HarvestTiming {
+            collector: collector.to_string(),
+            duration: Duration::from_millis(millis),
+        };
+        let harvest = |harvest_timings| {
+            Box::new(Data {
+                harvest_timings,
+                ..Data::default()
+            })
+        };
+
+        let mut data = DataCollection::default();
+        data.eat_data(harvest(vec![timing("CPU", 1), timing("Processes", 10)]));
+        data.eat_data(harvest(vec![timing("CPU", 2)]));
+
+        assert_eq!(
+            data.harvest_timings,
+            vec![timing("CPU", 2), timing("Processes", 10)]
+        );
+    }
+    #[test]
+    fn test_eat_plugins() {
+        let plugin = |name: &str, series: &[(&str, f64)]| plugins::PluginHarvest {
+            name: name.to_string(),
+            series: series
+                .iter()
+                .map(|(name, value)| plugins::PluginSeries {
+                    name: name.to_string(),
+                    value: *value,
+                })
+                .collect(),
+            error: None,
+        };
+
+        let mut data = DataCollection::default();
+        let mut first_entry = TimedData::default();
+        data.eat_plugins(
+            vec![
+                plugin("redis", &[("hits", 1.0)]),
+                plugin("jvm", &[("heap", 2.0)]),
+            ],
+            &mut first_entry,
+        );
+        let mut second_entry = TimedData::default();
+        data.eat_plugins(
+            vec![plugin("redis", &[("misses", 3.0), ("hits", 4.0)])],
+            &mut second_entry,
+        );
+
+        assert_eq!(
+            data.plugin_series,
+            vec![
+                ("redis".to_string(), "hits".to_string()),
+                ("jvm".to_string(), "heap".to_string()),
+                ("redis".to_string(), "misses".to_string()),
+            ]
+        );
+        assert_eq!(first_entry.plugin_data, vec![Some(1.0), Some(2.0)]);
+        assert_eq!(second_entry.plugin_data, vec![Some(4.0), None, Some(3.0)]);
+    }
+}
@@ -1,9 +1,13 @@
 //! This is the main file to house data collection functions.
 
-use std::time::Instant;
+use std::{
+    collections::BTreeMap,
+    net::IpAddr,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
-#[cfg(target_os = "linux")]
 use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
 
 #[cfg(not(target_os = "linux"))]
 use sysinfo::{System, SystemExt};
@@ -17,29 +21,174 @@ use futures::join;
 
 use super::DataFilters;
 
+pub mod addresses;
 #[cfg(feature = "battery")]
 pub mod batteries;
+pub mod cgroups;
 pub mod cpu;
 pub mod disks;
+pub mod events;
+pub mod frequency;
+pub mod latency;
 pub mod memory;
 pub mod network;
+#[cfg(target_os = "windows")]
+pub mod performance_counters;
+pub mod plugins;
+pub mod power;
+pub mod pressure;
 pub mod processes;
+pub mod self_usage;
+pub mod system_info;
 pub mod temperature;
+pub mod termux;
+pub mod users;
+pub mod wireless;
 
-#[derive(Clone, Debug)]
+/// The types of data that are harvested on their own schedules.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HarvestType {
+    Cpu,
+    Memory,
+    Network,
+    Processes,
+    Temperature,
+    Disk,
+    Battery,
+    Power,
+    Pressure,
+    Cgroup,
+    Latency,
+    Frequency,
+    Events,
+    SystemInfo,
+    Users,
+    Plugins,
+}
+
+impl HarvestType {
+    const ALL: [HarvestType; 16] = [
+        HarvestType::Cpu,
+        HarvestType::Memory,
+        HarvestType::Network,
+        HarvestType::Processes,
+        HarvestType::Temperature,
+        HarvestType::Disk,
+        HarvestType::Battery,
+        HarvestType::Power,
+        HarvestType::Pressure,
+        HarvestType::Cgroup,
+        HarvestType::Latency,
+        HarvestType::Frequency,
+        HarvestType::Events,
+        HarvestType::SystemInfo,
+        HarvestType::Users,
+        HarvestType::Plugins,
+    ];
+}
+
+/// How often each type of data is harvested, in milliseconds.
+#[derive(Clone, Debug, Default)]
+pub struct HarvestIntervals {
+    default_interval: u64,
+    intervals: FxHashMap<HarvestType, u64>,
+}
+
+impl HarvestIntervals {
+    /// Creates intervals where every type of data is harvested every `default_interval`
+    /// milliseconds.
+    pub fn new(default_interval: u64) -> Self {
+        HarvestIntervals {
+            default_interval,
+            intervals: FxHashMap::default(),
+        }
+    }
+
+    pub fn set_interval(&mut self, harvest_type: HarvestType, interval: u64) {
+        self.intervals.insert(harvest_type, interval);
+    }
+
+    pub fn get_interval(&self, harvest_type: HarvestType) -> u64 {
+        self.intervals
+            .get(&harvest_type)
+            .copied()
+            .unwrap_or(self.default_interval)
+    }
+
+    /// Returns the shortest interval of any type, which is how often the collection thread
+    /// needs to wake up.
+    pub fn get_shortest_interval(&self) -> u64 {
+        HarvestType::ALL
+            .iter()
+            .map(|harvest_type| self.get_interval(*harvest_type))
+            .min()
+            .unwrap_or(self.default_interval)
+    }
+}
+
+/// A type of data that couldn't be collected the last time it was harvested.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HarvestError {
+    /// What couldn't be collected, like `Temperature`.
+    pub collector: String,
+    /// Why it couldn't be collected the last time.
+    pub message: String,
+    /// How many harvests in a row have failed.
+    pub failures: u64,
+    /// When the harvests started failing, in seconds since the Unix epoch.
+    pub since: u64,
+}
+
+/// A type of data that isn't collected at all, as it can't be read on this system.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UnavailableHarvest {
+    /// What isn't collected, like `Temperature`.
+    pub collector: String,
+    /// Why it can't be collected.
+    pub reason: String,
+}
+
+/// One harvest of every type of data. This can be sent over the network by an agent, in which
+/// case anything the receiving platform doesn't have is left out.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Data {
+    #[serde(skip, default = "Instant::now")]
     pub last_collection_time: Instant,
     pub cpu: Option<cpu::CpuHarvest>,
     pub load_avg: Option<cpu::LoadAvgHarvest>,
     pub memory: Option<memory::MemHarvest>,
     pub swap: Option<memory::MemHarvest>,
+    pub swap_activity: Option<memory::SwapActivityHarvest>,
     pub temperature_sensors: Option<Vec<temperature::TempHarvest>>,
     pub network: Option<network::NetworkHarvest>,
+    pub wireless: Option<Vec<wireless::WirelessHarvest>>,
+    pub addresses: Option<Vec<addresses::InterfaceAddressHarvest>>,
+    pub public_ip: Option<IpAddr>,
     pub list_of_processes: Option<Vec<processes::ProcessHarvest>>,
     pub disks: Option<Vec<disks::DiskHarvest>>,
     pub io: Option<disks::IoHarvest>,
+    pub power: Option<power::PowerHarvest>,
+    pub pressure: Option<pressure::PressureHarvest>,
+    pub cgroups: Option<Vec<cgroups::CgroupHarvest>>,
+    pub latency: Option<Vec<latency::LatencyHarvest>>,
+    pub frequency: Option<frequency::FrequencyHarvest>,
+    pub kernel_events: Option<Vec<events::KernelEventHarvest>>,
+    pub system_info: Option<system_info::SystemInfoHarvest>,
+    pub user_sessions: Option<Vec<users::UserSession>>,
+    pub plugins: Option<Vec<plugins::PluginHarvest>>,
     #[cfg(feature = "battery")]
     pub list_of_batteries: Option<Vec<batteries::BatteryHarvest>>,
+    /// Everything that's currently failing to be collected, which is sent with every harvest, as
+    /// not everything is harvested every time.
+    pub harvest_errors: Vec<HarvestError>,
+    /// Everything that's skipped, as it can't be read on this system.
+    pub unavailable_harvests: Vec<UnavailableHarvest>,
+    pub self_usage: Option<self_usage::SelfUsageHarvest>,
+    /// How long each collector that ran in this harvest took.
+    pub harvest_timings: Vec<self_usage::HarvestTiming>,
+    /// How long the whole harvest took.
+    pub harvest_duration: Duration,
 }
 
 impl Default for Data {
@@ -50,11 +199,29 @@ impl Default for Data {
             load_avg: None,
             memory: None,
             swap: None,
+            swap_activity: None,
             temperature_sensors: None,
             list_of_processes: None,
             disks: None,
             io: None,
             network: None,
+            wireless: None,
+            addresses: None,
+            public_ip: None,
+            power: None,
+            pressure: None,
+            cgroups: None,
+            latency: None,
+            frequency: None,
+            kernel_events: None,
+            system_info: None,
+            user_sessions: None,
+            plugins: None,
+            harvest_errors: Vec::new(),
+            unavailable_harvests: Vec::new(),
+            self_usage: None,
+            harvest_timings: Vec::new(),
+            harvest_duration: Duration::ZERO,
             #[cfg(feature = "battery")]
             list_of_batteries: None,
         }
@@ -69,8 +236,23 @@ impl Data {
         self.disks = None;
         self.memory = None;
         self.swap = None;
+        self.swap_activity = None;
         self.cpu = None;
         self.load_avg = None;
+        self.power = None;
+        self.pressure = None;
+        self.cgroups = None;
+        self.wireless = None;
+        self.addresses = None;
+        self.public_ip = None;
+        self.latency = None;
+        self.frequency = None;
+        self.kernel_events = None;
+        self.system_info = None;
+        self.user_sessions = None;
+        self.plugins = None;
+        self.self_usage = None;
+        self.harvest_timings.clear();
 
         if let Some(network) = &mut self.network {
             network.first_run_cleanup();
@@ -83,8 +265,8 @@ pub struct DataCollector {
     pub data: Data,
     #[cfg(not(target_os = "linux"))]
     sys: System,
-    previous_cpu_times: Vec<(cpu::PastCpuWork, cpu::PastCpuTotal)>,
-    previous_average_cpu_time: Option<(cpu::PastCpuWork, cpu::PastCpuTotal)>,
+    previous_cpu_times: Vec<cpu::CpuTimes>,
+    previous_average_cpu_time: Option<cpu::CpuTimes>,
     #[cfg(target_os = "linux")]
     pid_mapping: FxHashMap<crate::Pid, processes::PrevProcDetails>,
     #[cfg(target_os = "linux")]
@@ -94,18 +276,58 @@ pub struct DataCollector {
     mem_total_kb: u64,
     temperature_type: temperature::TemperatureType,
     use_current_cpu_total: bool,
+    /// Only used on Linux, as other platforms only collect the virtual size, which is always read.
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    process_memory_details: processes::MemoryDetails,
+    /// Only used on Windows and with the nvidia feature, as other GPUs don't report per-process
+    /// usage elsewhere.
+    #[cfg_attr(not(any(feature = "nvidia", target_os = "windows")), allow(dead_code))]
+    process_gpu: bool,
+    #[cfg(all(feature = "nvidia", not(target_os = "windows")))]
+    gpu_process_reader: processes::nvidia::GpuProcessReader,
     last_collection_time: Instant,
     total_rx: u64,
     total_tx: u64,
+    prev_net_error_counters: FxHashMap<String, network::NetworkErrorCounters>,
+    last_wireless_harvest: Option<Instant>,
+    show_addresses: bool,
+    last_address_harvest: Option<Instant>,
+    show_public_ip: bool,
+    public_ip_checker: addresses::PublicIpChecker,
+    prev_energy_readings: FxHashMap<String, power::EnergyReading>,
+    prev_cgroup_counters: FxHashMap<String, cgroups::CgroupCounters>,
+    #[cfg(target_os = "linux")]
+    prev_c_state_times: frequency::CStateTimes,
+    latency_checker: latency::LatencyChecker,
+    plugin_runner: plugins::PluginRunner,
     show_average_cpu: bool,
+    show_cpu_breakdown: bool,
+    enable_smart: bool,
+    /// Only used on Windows, where disk activity is read from performance counters.
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    enable_disk_activity: bool,
+    #[cfg(target_os = "windows")]
+    performance_counters: performance_counters::PerformanceCounters,
+    #[cfg(target_os = "linux")]
+    smart_cache: FxHashMap<String, (Instant, Option<disks::SmartHarvest>)>,
     widgets_to_harvest: UsedWidgets,
+    harvest_intervals: HarvestIntervals,
+    last_harvest_times: FxHashMap<HarvestType, Instant>,
     #[cfg(feature = "battery")]
     battery_manager: Option<Manager>,
     #[cfg(feature = "battery")]
     battery_list: Option<Vec<Battery>>,
     filters: DataFilters,
-
-    #[cfg(target_family = "unix")]
+    /// Only interfaces matching this are collected, on top of the network filter in the config.
+    net_query: Option<regex::Regex>,
+    /// Whether the interfaces that are collected changed since the last harvest, so the rates
+    /// can't be worked out from the last totals.
+    is_net_filter_changed: bool,
+    /// What's currently failing to be collected, by collector.
+    harvest_errors: BTreeMap<&'static str, HarvestError>,
+    /// What's skipped, as it can't be read on this system.
+    unavailable_harvests: Vec<UnavailableHarvest>,
+    self_usage_checker: self_usage::SelfUsageChecker,
     user_table: self::processes::UserTable,
 }
 
@@ -126,17 +348,46 @@ impl DataCollector {
             mem_total_kb: 0,
             temperature_type: temperature::TemperatureType::Celsius,
             use_current_cpu_total: false,
+            process_memory_details: processes::MemoryDetails::None,
+            process_gpu: false,
+            #[cfg(all(feature = "nvidia", not(target_os = "windows")))]
+            gpu_process_reader: Default::default(),
             last_collection_time: Instant::now(),
             total_rx: 0,
             total_tx: 0,
+            prev_net_error_counters: FxHashMap::default(),
+            last_wireless_harvest: None,
+            show_addresses: false,
+            last_address_harvest: None,
+            show_public_ip: false,
+            public_ip_checker: addresses::PublicIpChecker::default(),
+            prev_energy_readings: FxHashMap::default(),
+            prev_cgroup_counters: FxHashMap::default(),
+            #[cfg(target_os = "linux")]
+            prev_c_state_times: Vec::new(),
+            latency_checker: latency::LatencyChecker::default(),
+            plugin_runner: plugins::PluginRunner::default(),
             show_average_cpu: false,
+            show_cpu_breakdown: false,
+            enable_smart: false,
+            enable_disk_activity: false,
+            #[cfg(target_os = "windows")]
+            performance_counters: Default::default(),
+            #[cfg(target_os = "linux")]
+            smart_cache: FxHashMap::default(),
             widgets_to_harvest: UsedWidgets::default(),
+            harvest_intervals: HarvestIntervals::default(),
+            last_harvest_times: FxHashMap::default(),
             #[cfg(feature = "battery")]
             battery_manager: None,
             #[cfg(feature = "battery")]
             battery_list: None,
             filters,
-            #[cfg(target_family = "unix")]
+            net_query: None,
+            is_net_filter_changed: false,
+            harvest_errors: BTreeMap::new(),
+            unavailable_harvests: Vec::new(),
+            self_usage_checker: self_usage::SelfUsageChecker::default(),
             user_table: Default::default(),
         }
     }
@@ -153,7 +404,7 @@ impl DataCollector {
 
             // TODO: Would be good to get this and network list running on a timer instead...?
             // Refresh components list once...
-            if self.widgets_to_harvest.use_temp {
+            if cfg!(not(target_os = "freebsd")) && self.widgets_to_harvest.use_temp {
                 self.sys.refresh_components_list();
             }
 
@@ -193,6 +444,9 @@ impl DataCollector {
 
         self.data.cleanup();
 
+        // Make sure everything is harvested again on the first real update, regardless of schedule.
+        self.last_harvest_times.clear();
+
         // trace!("Enabled widgets to harvest: {:#?}", self.widgets_to_harvest);
     }
 
@@ -217,85 +471,493 @@ impl DataCollector {
         self.use_current_cpu_total = use_current_cpu_total;
     }
 
+    pub fn set_process_memory_details(
+        &mut self, process_memory_details: bool, precise_process_memory: bool,
+    ) {
+        self.process_memory_details = if !process_memory_details {
+            processes::MemoryDetails::None
+        } else if precise_process_memory {
+            processes::MemoryDetails::Precise
+        } else {
+            processes::MemoryDetails::Basic
+        };
+    }
+
+    pub fn set_process_gpu(&mut self, process_gpu: bool) {
+        self.process_gpu = process_gpu;
+        #[cfg(target_os = "windows")]
+        self.performance_counters
+            .set_counters(self.enable_disk_activity, self.process_gpu);
+    }
+
     pub fn set_show_average_cpu(&mut self, show_average_cpu: bool) {
         self.show_average_cpu = show_average_cpu;
     }
 
+    pub fn set_show_cpu_breakdown(&mut self, show_cpu_breakdown: bool) {
+        self.show_cpu_breakdown = show_cpu_breakdown;
+    }
+
+    pub fn set_enable_smart(&mut self, enable_smart: bool) {
+        self.enable_smart = enable_smart;
+    }
+
+    pub fn set_enable_disk_activity(&mut self, enable_disk_activity: bool) {
+        self.enable_disk_activity = enable_disk_activity;
+        #[cfg(target_os = "windows")]
+        self.performance_counters
+            .set_counters(self.enable_disk_activity, self.process_gpu);
+    }
+
+    /// Uses the Termux profile, which skips the collectors that Android doesn't let Termux read.
+    pub fn set_termux_profile(&mut self, use_termux_profile: bool) {
+        self.unavailable_harvests = if use_termux_profile {
+            termux::get_unavailable_harvests()
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// Returns whether a collector can be used, and isn't skipped as it can't be read.
+    fn is_available(&self, collector: &str) -> bool {
+        !self
+            .unavailable_harvests
+            .iter()
+            .any(|unavailable| unavailable.collector == collector)
+    }
+
+    pub fn set_show_addresses(&mut self, show_addresses: bool) {
+        self.show_addresses = show_addresses;
+    }
+
+    pub fn set_show_public_ip(&mut self, show_public_ip: bool) {
+        self.show_public_ip = show_public_ip;
+    }
+
+    pub fn set_latency_hosts(&mut self, latency_hosts: Vec<latency::LatencyHost>) {
+        self.latency_checker.set_hosts(latency_hosts);
+    }
+
+    pub fn set_plugins(&mut self, plugins: Vec<plugins::PluginConfig>) {
+        self.plugin_runner.set_plugins(plugins);
+    }
+
+    pub fn set_harvest_intervals(&mut self, harvest_intervals: HarvestIntervals) {
+        self.harvest_intervals = harvest_intervals;
+    }
+
+    pub fn set_filters(&mut self, filters: DataFilters) {
+        self.filters = filters;
+        self.is_net_filter_changed = true;
+    }
+
+    pub fn set_net_query(&mut self, net_query: Option<regex::Regex>) {
+        self.net_query = net_query;
+        self.is_net_filter_changed = true;
+    }
+
+    /// Returns whether a type of data is used and is due to be harvested again.
+    fn should_harvest(&self, harvest_type: HarvestType, is_used: bool, instant: Instant) -> bool {
+        if !is_used {
+            return false;
+        }
+
+        match self.last_harvest_times.get(&harvest_type) {
+            Some(last_harvest_time) => {
+                // Allow some leeway, as the collection thread doesn't wake up at exact intervals.
+                let leeway = self.harvest_intervals.get_shortest_interval() / 2;
+                instant.duration_since(*last_harvest_time).as_millis() as u64 + leeway
+                    >= self.harvest_intervals.get_interval(harvest_type)
+            }
+            None => true,
+        }
+    }
+
+    /// Returns what a collector harvested, or records why it failed and returns `None`. Failures
+    /// are logged when a collector starts failing, when the reason changes, and when it recovers.
+    fn check_harvest<T, E: std::fmt::Display>(
+        &mut self, collector: &'static str, result: Result<T, E>,
+    ) -> Option<T> {
+        match result {
+            Ok(value) => {
+                if self.harvest_errors.remove(collector).is_some() {
+                    #[cfg(feature = "log")]
+                    info!("{} is being collected again", collector);
+                }
+                Some(value)
+            }
+            Err(err) => {
+                let message = err.to_string();
+                match self.harvest_errors.get_mut(collector) {
+                    Some(harvest_error) => {
+                        harvest_error.failures += 1;
+                        if harvest_error.message != message {
+                            #[cfg(feature = "log")]
+                            warn!("{} still couldn't be collected: {}", collector, message);
+                            harvest_error.message = message;
+                        } else {
+                            #[cfg(feature = "log")]
+                            debug!("{} still couldn't be collected: {}", collector, message);
+                        }
+                    }
+                    None => {
+                        #[cfg(feature = "log")]
+                        warn!("{} couldn't be collected: {}", collector, message);
+                        self.harvest_errors.insert(
+                            collector,
+                            HarvestError {
+                                collector: collector.to_string(),
+                                message,
+                                failures: 1,
+                                since: SystemTime::now()
+                                    .duration_since(UNIX_EPOCH)
+                                    .map(|duration| duration.as_secs())
+                                    .unwrap_or(0),
+                            },
+                        );
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    /// Records how long a collector took, given when it started.
+    fn record_timing(&mut self, collector: &str, start: Instant) {
+        self.record_duration(collector, start.elapsed());
+    }
+
+    fn record_duration(&mut self, collector: &str, duration: Duration) {
+        self.data.harvest_timings.push(self_usage::HarvestTiming {
+            collector: collector.to_string(),
+            duration,
+        });
+    }
+
+    fn get_last_harvest_time(&self, harvest_type: HarvestType) -> Instant {
+        self.last_harvest_times
+            .get(&harvest_type)
+            .copied()
+            .unwrap_or(self.last_collection_time)
+    }
+
     pub async fn update_data(&mut self) {
+        let harvest_instant = Instant::now();
+        self.data.harvest_timings.clear();
+        let harvest_cpu = self.should_harvest(
+            HarvestType::Cpu,
+            self.widgets_to_harvest.use_cpu,
+            harvest_instant,
+        );
+        // The load average is shown in the CPU widget, but can also be graphed on its own.
+        let harvest_load_avg = self.should_harvest(
+            HarvestType::Cpu,
+            self.widgets_to_harvest.use_cpu || self.widgets_to_harvest.use_load_avg,
+            harvest_instant,
+        );
+        let harvest_mem = self.should_harvest(
+            HarvestType::Memory,
+            self.widgets_to_harvest.use_mem,
+            harvest_instant,
+        );
+        let harvest_net = self.should_harvest(
+            HarvestType::Network,
+            self.widgets_to_harvest.use_net,
+            harvest_instant,
+        );
+        // The top processes widget needs processes even if there's no process widget.
+        let harvest_proc = self.should_harvest(
+            HarvestType::Processes,
+            self.widgets_to_harvest.use_proc || self.widgets_to_harvest.use_top_processes,
+            harvest_instant,
+        );
+        let harvest_temp = self.should_harvest(
+            HarvestType::Temperature,
+            self.widgets_to_harvest.use_temp,
+            harvest_instant,
+        ) && self.is_available("Temperature");
+        let harvest_disk = self.should_harvest(
+            HarvestType::Disk,
+            self.widgets_to_harvest.use_disk,
+            harvest_instant,
+        );
+        let harvest_disk_io = harvest_disk && self.is_available("Disk I/O");
+        let harvest_power = self.should_harvest(
+            HarvestType::Power,
+            self.widgets_to_harvest.use_power,
+            harvest_instant,
+        );
+        let harvest_pressure = self.should_harvest(
+            HarvestType::Pressure,
+            self.widgets_to_harvest.use_pressure,
+            harvest_instant,
+        );
+        let harvest_cgroup = self.should_harvest(
+            HarvestType::Cgroup,
+            self.widgets_to_harvest.use_cgroup,
+            harvest_instant,
+        );
+        let harvest_latency = self.should_harvest(
+            HarvestType::Latency,
+            self.widgets_to_harvest.use_latency,
+            harvest_instant,
+        );
+        let harvest_frequency = self.should_harvest(
+            HarvestType::Frequency,
+            self.widgets_to_harvest.use_frequency,
+            harvest_instant,
+        );
+        let harvest_events = self.should_harvest(
+            HarvestType::Events,
+            self.widgets_to_harvest.use_events,
+            harvest_instant,
+        );
+        let harvest_system_info = self.should_harvest(
+            HarvestType::SystemInfo,
+            self.widgets_to_harvest.use_system_info,
+            harvest_instant,
+        );
+        let harvest_users = self.should_harvest(
+            HarvestType::Users,
+            self.widgets_to_harvest.use_users,
+            harvest_instant,
+        );
+        let harvest_plugins = self.should_harvest(
+            HarvestType::Plugins,
+            self.widgets_to_harvest.use_plugins,
+            harvest_instant,
+        );
+        #[cfg(feature = "battery")]
+        let harvest_battery = self.should_harvest(
+            HarvestType::Battery,
+            self.widgets_to_harvest.use_battery,
+            harvest_instant,
+        );
+
         #[cfg(not(target_os = "linux"))]
         {
-            if self.widgets_to_harvest.use_proc || self.widgets_to_harvest.use_cpu {
+            let start = Instant::now();
+            if harvest_proc || harvest_cpu || harvest_frequency {
                 self.sys.refresh_cpu();
             }
-            if self.widgets_to_harvest.use_proc {
+            if harvest_proc {
                 self.sys.refresh_processes();
             }
-            if self.widgets_to_harvest.use_temp {
+            if cfg!(not(target_os = "freebsd")) && harvest_temp {
                 self.sys.refresh_components();
             }
-            if cfg!(target_os = "windows") && self.widgets_to_harvest.use_net {
+            if cfg!(target_os = "windows") && harvest_net {
                 self.sys.refresh_networks();
             }
-            if cfg!(target_os = "freebsd") && self.widgets_to_harvest.use_disk {
+            if cfg!(target_os = "freebsd") && harvest_disk {
                 self.sys.refresh_disks();
             }
-            if cfg!(target_os = "freebsd") && self.widgets_to_harvest.use_mem {
+            if cfg!(target_os = "freebsd") && harvest_mem {
                 self.sys.refresh_memory();
             }
+            self.record_timing("System refresh", start);
         }
 
         let current_instant = std::time::Instant::now();
 
         // CPU
-        if self.widgets_to_harvest.use_cpu {
+        if harvest_cpu && self.is_available("CPU") {
+            let start = Instant::now();
             #[cfg(not(target_os = "freebsd"))]
             {
-                if let Ok(cpu_data) = cpu::get_cpu_data_list(
+                let cpu_res = cpu::get_cpu_data_list(
                     self.show_average_cpu,
+                    self.show_cpu_breakdown,
                     &mut self.previous_cpu_times,
                     &mut self.previous_average_cpu_time,
                 )
-                .await
-                {
+                .await;
+                if let Some(cpu_data) = self.check_harvest("CPU", cpu_res) {
                     self.data.cpu = Some(cpu_data);
                 }
             }
             #[cfg(target_os = "freebsd")]
             {
-                if let Ok(cpu_data) = cpu::get_cpu_data_list(
+                let cpu_res = cpu::get_cpu_data_list(
                     &self.sys,
                     self.show_average_cpu,
+                    self.show_cpu_breakdown,
                     &mut self.previous_cpu_times,
                     &mut self.previous_average_cpu_time,
                 )
-                .await
-                {
+                .await;
+                if let Some(cpu_data) = self.check_harvest("CPU", cpu_res) {
                     self.data.cpu = Some(cpu_data);
                 }
             }
+            self.record_timing("CPU", start);
+        }
 
-            #[cfg(target_family = "unix")]
-            {
-                // Load Average
-                if let Ok(load_avg_data) = cpu::get_load_avg().await {
+        // Load Average
+        #[cfg(target_family = "unix")]
+        {
+            if harvest_load_avg && self.is_available("Load average") {
+                let start = Instant::now();
+                let load_avg_res = cpu::get_load_avg().await;
+                if let Some(load_avg_data) = self.check_harvest("Load average", load_avg_res) {
                     self.data.load_avg = Some(load_avg_data);
                 }
+                self.record_timing("Load average", start);
             }
         }
 
         // Batteries
         #[cfg(feature = "battery")]
         {
-            if let Some(battery_manager) = &self.battery_manager {
-                if let Some(battery_list) = &mut self.battery_list {
-                    self.data.list_of_batteries =
-                        Some(batteries::refresh_batteries(battery_manager, battery_list));
+            if harvest_battery {
+                let start = Instant::now();
+                if let Some(battery_manager) = &self.battery_manager {
+                    if let Some(battery_list) = &mut self.battery_list {
+                        self.data.list_of_batteries =
+                            Some(batteries::refresh_batteries(battery_manager, battery_list));
+                    }
+                }
+                #[cfg(target_os = "freebsd")]
+                {
+                    if self.battery_manager.is_none() {
+                        self.data.list_of_batteries =
+                            Some(batteries::freebsd::get_sysctl_batteries());
+                    }
                 }
+                self.record_timing("Batteries", start);
+            }
+        }
+
+        // Power
+        if harvest_power {
+            let start = Instant::now();
+            let last_power_harvest_time = self.get_last_harvest_time(HarvestType::Power);
+            self.data.power = power::get_power_data(
+                &mut self.prev_energy_readings,
+                current_instant
+                    .duration_since(last_power_harvest_time)
+                    .as_secs_f64(),
+            );
+            self.record_timing("Power", start);
+        }
+
+        // Swap activity
+        if harvest_mem {
+            let start = Instant::now();
+            self.data.swap_activity = memory::get_swap_activity();
+            self.record_timing("Swap activity", start);
+        }
+
+        // Pressure
+        if harvest_pressure && self.is_available("Pressure") {
+            let start = Instant::now();
+            self.data.pressure = pressure::get_pressure_data();
+            self.record_timing("Pressure", start);
+        }
+
+        // Cgroups
+        if harvest_cgroup {
+            let start = Instant::now();
+            let last_cgroup_harvest_time = self.get_last_harvest_time(HarvestType::Cgroup);
+            self.data.cgroups = cgroups::get_cgroup_data(
+                &mut self.prev_cgroup_counters,
+                current_instant
+                    .duration_since(last_cgroup_harvest_time)
+                    .as_secs_f64(),
+            );
+            self.record_timing("Cgroups", start);
+        }
+
+        // Latency
+        if harvest_latency {
+            let start = Instant::now();
+            self.data.latency = self.latency_checker.get_latency_data();
+            self.record_timing("Latency", start);
+        }
+
+        // Frequency
+        if harvest_frequency {
+            let start = Instant::now();
+            #[cfg(target_os = "linux")]
+            {
+                let last_frequency_harvest_time =
+                    self.get_last_harvest_time(HarvestType::Frequency);
+                self.data.frequency = frequency::get_frequency_data(
+                    &mut self.prev_c_state_times,
+                    current_instant
+                        .duration_since(last_frequency_harvest_time)
+                        .as_secs_f64(),
+                );
+            }
+            #[cfg(not(target_os = "linux"))]
+            {
+                self.data.frequency = frequency::get_frequency_data(&self.sys);
+            }
+            self.record_timing("Frequency", start);
+        }
+
+        // Kernel events
+        if harvest_events {
+            let start = Instant::now();
+            let events_res = events::get_kernel_events();
+            if let Some(kernel_events) = self.check_harvest("Kernel events", events_res) {
+                self.data.kernel_events = kernel_events;
             }
+            self.record_timing("Kernel events", start);
+        }
+
+        // System info
+        if harvest_system_info {
+            let start = Instant::now();
+            self.data.system_info = Some(system_info::get_system_info());
+            self.record_timing("System info", start);
         }
 
-        if self.widgets_to_harvest.use_proc {
-            if let Ok(process_list) = {
+        // Users
+        if harvest_users {
+            let start = Instant::now();
+            self.data.user_sessions = users::get_user_sessions();
+            self.record_timing("Users", start);
+        }
+
+        // Plugins
+        if harvest_plugins {
+            let start = Instant::now();
+            if let Some(plugins) = self.plugin_runner.get_plugin_data() {
+                let errors = plugins
+                    .iter()
+                    .filter_map(|plugin| {
+                        plugin
+                            .error
+                            .as_ref()
+                            .map(|error| format!("{} {}", plugin.name, error))
+                    })
+                    .collect::<Vec<_>>();
+                let plugins_res = if errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(errors.join("; "))
+                };
+                self.check_harvest("Plugins", plugins_res);
+                self.data.plugins = Some(plugins);
+            }
+            self.record_timing("Plugins", start);
+        }
+
+        // Disk activity and per-process GPU usage are read together on Windows, as they come from
+        // the same performance counters.
+        #[cfg(target_os = "windows")]
+        let has_performance_counters = (harvest_disk || harvest_proc) && {
+            let start = Instant::now();
+            let has_performance_counters = self.performance_counters.collect();
+            self.record_timing("Performance counters", start);
+            has_performance_counters
+        };
+
+        if harvest_proc {
+            let start = Instant::now();
+            let last_proc_harvest_time = self.get_last_harvest_time(HarvestType::Processes);
+            let process_res = {
                 #[cfg(target_os = "linux")]
                 {
                     processes::get_process_data(
@@ -304,139 +966,313 @@ impl DataCollector {
                         &mut self.pid_mapping,
                         self.use_current_cpu_total,
                         current_instant
-                            .duration_since(self.last_collection_time)
+                            .duration_since(last_proc_harvest_time)
                             .as_secs(),
                         self.mem_total_kb,
+                        self.process_memory_details,
                         &mut self.user_table,
                     )
                 }
                 #[cfg(not(target_os = "linux"))]
                 {
-                    #[cfg(target_family = "unix")]
-                    {
-                        processes::get_process_data(
-                            &self.sys,
-                            self.use_current_cpu_total,
-                            self.mem_total_kb,
-                            &mut self.user_table,
-                        )
+                    processes::get_process_data(
+                        &self.sys,
+                        self.use_current_cpu_total,
+                        self.mem_total_kb,
+                        &mut self.user_table,
+                    )
+                }
+            };
+            if let Some(process_list) = self.check_harvest("Processes", process_res) {
+                #[cfg(all(feature = "nvidia", not(target_os = "windows")))]
+                let process_list = {
+                    let mut process_list = process_list;
+                    if self.process_gpu {
+                        self.gpu_process_reader.add_gpu_data(&mut process_list);
                     }
-                    #[cfg(not(target_family = "unix"))]
-                    {
-                        processes::get_process_data(
-                            &self.sys,
-                            self.use_current_cpu_total,
-                            self.mem_total_kb,
-                        )
+                    process_list
+                };
+                #[cfg(target_os = "windows")]
+                let process_list = {
+                    let mut process_list = process_list;
+                    if self.process_gpu && has_performance_counters {
+                        self.performance_counters.add_gpu_data(&mut process_list);
                     }
-                }
-            } {
+                    process_list
+                };
+
                 self.data.list_of_processes = Some(process_list);
             }
+            self.record_timing("Processes", start);
         }
 
+        let last_net_harvest_time = self.get_last_harvest_time(HarvestType::Network);
         let network_data_fut = {
             #[cfg(any(target_os = "windows", target_os = "freebsd"))]
             {
                 network::get_network_data(
                     &self.sys,
-                    self.last_collection_time,
+                    last_net_harvest_time,
                     &mut self.total_rx,
                     &mut self.total_tx,
+                    &mut self.prev_net_error_counters,
                     current_instant,
-                    self.widgets_to_harvest.use_net,
+                    harvest_net,
                     &self.filters.net_filter,
+                    self.net_query.as_ref(),
                 )
             }
             #[cfg(not(any(target_os = "windows", target_os = "freebsd")))]
             {
                 network::get_network_data(
-                    self.last_collection_time,
+                    last_net_harvest_time,
                     &mut self.total_rx,
                     &mut self.total_tx,
+                    &mut self.prev_net_error_counters,
                     current_instant,
-                    self.widgets_to_harvest.use_net,
+                    harvest_net,
                     &self.filters.net_filter,
+                    self.net_query.as_ref(),
                 )
             }
         };
         let mem_data_fut = {
             #[cfg(not(target_os = "freebsd"))]
             {
-                memory::get_mem_data(self.widgets_to_harvest.use_mem)
+                memory::get_mem_data(harvest_mem)
             }
             #[cfg(target_os = "freebsd")]
             {
-                memory::get_mem_data(&self.sys, self.widgets_to_harvest.use_mem)
+                memory::get_mem_data(&self.sys, harvest_mem)
             }
         };
         let disk_data_fut = disks::get_disk_usage(
-            self.widgets_to_harvest.use_disk,
+            harvest_disk,
             &self.filters.disk_filter,
             &self.filters.mount_filter,
         );
-        let disk_io_usage_fut = disks::get_io_usage(self.widgets_to_harvest.use_disk);
+        let disk_io_usage_fut = disks::get_io_usage(harvest_disk_io);
         let temp_data_fut = {
-            #[cfg(not(target_os = "linux"))]
+            #[cfg(any(target_os = "macos", target_os = "windows"))]
             {
                 temperature::get_temperature_data(
                     &self.sys,
                     &self.temperature_type,
-                    self.widgets_to_harvest.use_temp,
+                    harvest_temp,
                     &self.filters.temp_filter,
                 )
             }
 
-            #[cfg(target_os = "linux")]
+            #[cfg(not(any(target_os = "macos", target_os = "windows")))]
             {
                 temperature::get_temperature_data(
                     &self.temperature_type,
-                    self.widgets_to_harvest.use_temp,
+                    harvest_temp,
                     &self.filters.temp_filter,
                 )
             }
         };
 
-        let (net_data, mem_res, disk_res, io_res, temp_res) = join!(
-            network_data_fut,
-            mem_data_fut,
-            disk_data_fut,
-            disk_io_usage_fut,
-            temp_data_fut
+        let (
+            (net_data, net_duration),
+            (mem_res, mem_duration),
+            (disk_res, disk_duration),
+            (io_res, io_duration),
+            (temp_res, temp_duration),
+        ) = join!(
+            self_usage::timed(network_data_fut),
+            self_usage::timed(mem_data_fut),
+            self_usage::timed(disk_data_fut),
+            self_usage::timed(disk_io_usage_fut),
+            self_usage::timed(temp_data_fut)
         );
+        for (collector, was_harvested, duration) in [
+            ("Network", harvest_net, net_duration),
+            ("Memory", harvest_mem, mem_duration),
+            ("Disks", harvest_disk, disk_duration),
+            ("Disk I/O", harvest_disk_io, io_duration),
+            ("Temperature", harvest_temp, temp_duration),
+        ] {
+            if was_harvested {
+                self.record_duration(collector, duration);
+            }
+        }
 
-        if let Ok(net_data) = net_data {
-            if let Some(net_data) = &net_data {
+        if let Some(mut net_data) = self.check_harvest("Network", net_data) {
+            if let Some(net_data) = &mut net_data {
                 self.total_rx = net_data.total_rx;
                 self.total_tx = net_data.total_tx;
+                if std::mem::take(&mut self.is_net_filter_changed) {
+                    net_data.first_run_cleanup();
+                }
             }
             self.data.network = net_data;
         }
 
-        if let Ok(memory) = mem_res.0 {
+        // Wireless
+        let is_wireless_stale = match self.last_wireless_harvest {
+            Some(last_harvest) => {
+                current_instant.duration_since(last_harvest) >= wireless::WIRELESS_REFRESH_INTERVAL
+            }
+            None => true,
+        };
+        if harvest_net && is_wireless_stale {
+            let start = Instant::now();
+            self.data.wireless = Some(wireless::get_wireless_data(&self.filters.net_filter));
+            self.last_wireless_harvest = Some(current_instant);
+            self.record_timing("Wireless", start);
+        }
+
+        // Addresses
+        let is_address_stale = match self.last_address_harvest {
+            Some(last_harvest) => {
+                current_instant.duration_since(last_harvest) >= addresses::ADDRESS_REFRESH_INTERVAL
+            }
+            None => true,
+        };
+        if harvest_net && self.show_addresses && is_address_stale {
+            let start = Instant::now();
+            self.data.addresses =
+                Some(addresses::get_interface_addresses(&self.filters.net_filter).await);
+            self.last_address_harvest = Some(current_instant);
+            self.record_timing("Addresses", start);
+        }
+        if harvest_net && self.show_public_ip {
+            self.data.public_ip = self.public_ip_checker.get_public_ip(current_instant);
+        }
+
+        if let Some(memory) = self.check_harvest("Memory", mem_res.0) {
             self.data.memory = memory;
         }
 
-        if let Ok(swap) = mem_res.1 {
+        if let Some(swap) = self.check_harvest("Swap", mem_res.1) {
             self.data.swap = swap;
         }
 
-        if let Ok(disks) = disk_res {
+        if let Some(disks) = self.check_harvest("Disks", disk_res) {
             self.data.disks = disks;
+
+            if self.enable_smart {
+                #[cfg(target_os = "linux")]
+                {
+                    let start = Instant::now();
+                    self.update_smart_data(current_instant);
+                    self.record_timing("SMART", start);
+                }
+            }
+
+            #[cfg(target_os = "windows")]
+            {
+                if self.enable_disk_activity && has_performance_counters {
+                    self.update_disk_activity();
+                }
+            }
         }
 
-        if let Ok(io) = io_res {
+        if let Some(io) = self.check_harvest("Disk I/O", io_res) {
             self.data.io = io;
         }
 
-        if let Ok(temp) = temp_res {
+        if let Some(temp) = self.check_harvest("Temperature", temp_res) {
             self.data.temperature_sensors = temp;
         }
 
+        self.data.harvest_errors = self.harvest_errors.values().cloned().collect();
+        self.data.unavailable_harvests = self.unavailable_harvests.clone();
+
+        for (harvest_type, was_harvested) in &[
+            (HarvestType::Cpu, harvest_cpu || harvest_load_avg),
+            (HarvestType::Memory, harvest_mem),
+            (HarvestType::Network, harvest_net),
+            (HarvestType::Processes, harvest_proc),
+            (HarvestType::Temperature, harvest_temp),
+            (HarvestType::Disk, harvest_disk),
+            (HarvestType::Power, harvest_power),
+            (HarvestType::Pressure, harvest_pressure),
+            (HarvestType::Cgroup, harvest_cgroup),
+            (HarvestType::Latency, harvest_latency),
+            (HarvestType::Frequency, harvest_frequency),
+            (HarvestType::Events, harvest_events),
+            (HarvestType::SystemInfo, harvest_system_info),
+            (HarvestType::Users, harvest_users),
+            (HarvestType::Plugins, harvest_plugins),
+            #[cfg(feature = "battery")]
+            (HarvestType::Battery, harvest_battery),
+        ] {
+            if *was_harvested {
+                self.last_harvest_times
+                    .insert(*harvest_type, current_instant);
+            }
+        }
+
+        self.data.self_usage = {
+            #[cfg(target_os = "linux")]
+            {
+                self.self_usage_checker.get_self_usage()
+            }
+            #[cfg(not(target_os = "linux"))]
+            {
+                self.self_usage_checker.get_self_usage(&mut self.sys)
+            }
+        };
+        self.data.harvest_duration = harvest_instant.elapsed();
+
         // Update time
         self.data.last_collection_time = current_instant;
         self.last_collection_time = current_instant;
     }
+
+    /// Attaches SMART data to each harvested disk. As this is fairly expensive and doesn't
+    /// change often, results are cached per device for [`disks::smart::SMART_REFRESH_INTERVAL`].
+    #[cfg(target_os = "linux")]
+    fn update_smart_data(&mut self, current_instant: Instant) {
+        let temperature_type = &self.temperature_type;
+
+        if let Some(disks) = &mut self.data.disks {
+            for disk in disks.iter_mut() {
+                if let Some(device) = disks::smart::get_parent_device(&disk.name) {
+                    let smart = match self.smart_cache.get(&device) {
+                        Some((last_checked, smart))
+                            if current_instant.duration_since(*last_checked)
+                                < disks::smart::SMART_REFRESH_INTERVAL =>
+                        {
+                            smart.clone()
+                        }
+                        _ => {
+                            let smart = disks::smart::get_smart_data(&device);
+                            self.smart_cache
+                                .insert(device, (current_instant, smart.clone()));
+                            smart
+                        }
+                    };
+
+                    disk.smart = smart.map(|mut smart| {
+                        smart.temperature = smart
+                            .temperature
+                            .map(|celsius| temperature_type.convert_from_celsius(celsius));
+                        smart
+                    });
+                }
+            }
+        }
+    }
+
+    /// Attaches the activity of the physical disk backing each harvested disk, matched by the
+    /// drive letter of its mount point, like `C:` for `C:\`.
+    #[cfg(target_os = "windows")]
+    fn update_disk_activity(&mut self) {
+        let disk_activity = self.performance_counters.get_disk_activity();
+
+        if let Some(disks) = &mut self.data.disks {
+            for disk in disks.iter_mut() {
+                disk.activity = disk
+                    .mount_point
+                    .get(..2)
+                    .and_then(|drive| disk_activity.get(&drive.to_uppercase()))
+                    .copied();
+            }
+        }
+    }
 }
 
 #[cfg(target_os = "freebsd")]
@@ -452,3 +1288,72 @@ where
         .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "key not found"))
         .and_then(|val| serde_json::from_value(val).map_err(|err| err.into()))
 }
+
+#[cfg(target_os = "freebsd")]
+/// Reads an integer [sysctl](https://www.freebsd.org/cgi/man.cgi?query=sysctl) value by name, like
+/// `hw.acpi.battery.life`. Returns [`None`] if it doesn't exist.
+fn get_sysctl_int(name: &str) -> Option<i32> {
+    let name = std::ffi::CString::new(name).ok()?;
+    let mut value: libc::c_int = 0;
+    let mut size = std::mem::size_of::<libc::c_int>();
+
+    // SAFETY: The name is null-terminated, and the size given is that of the value written to.
+    let result = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr(),
+            (&mut value as *mut libc::c_int).cast(),
+            &mut size,
+            std::ptr::null(),
+            0,
+        )
+    };
+
+    if result == 0 && size == std::mem::size_of::<libc::c_int>() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check_harvest() {
+        let mut data_collector = DataCollector::new(DataFilters {
+            disk_filter: None,
+            mount_filter: None,
+            temp_filter: None,
+            net_filter: None,
+        });
+
+        assert_eq!(
+            data_collector.check_harvest("CPU", Ok::<_, String>(1)),
+            Some(1)
+        );
+        assert!(data_collector.harvest_errors.is_empty());
+
+        let failed: Result<u32, _> = Err("no sensors");
+        assert_eq!(data_collector.check_harvest("Temperature", failed), None);
+        assert_eq!(data_collector.check_harvest("Temperature", failed), None);
+        let harvest_error = &data_collector.harvest_errors["Temperature"];
+        assert_eq!(harvest_error.message, "no sensors");
+        assert_eq!(harvest_error.failures, 2);
+
+        assert_eq!(
+            data_collector.check_harvest("Temperature", Err::<u32, _>("permission denied")),
+            None
+        );
+        assert_eq!(
+            data_collector.harvest_errors["Temperature"].message,
+            "permission denied"
+        );
+
+        assert_eq!(
+            data_collector.check_harvest("Temperature", Ok::<_, String>(2)),
+            Some(2)
+        );
+        assert!(data_collector.harvest_errors.is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+//! Data collection for the IP addresses of network interfaces and the public IP address.
+//!
+//! Interface addresses are only supported on Linux and macOS, where they are read through heim.
+//! The public IP is looked up by asking [`PUBLIC_IP_HOST`] over plain HTTP on its own thread,
+//! and is only done if enabled, as some users won't want bottom making network requests.
+
+use std::{
+    io::{Read, Write},
+    net::{IpAddr, TcpStream, ToSocketAddrs},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// How long interface addresses are kept before they are read again.
+pub const ADDRESS_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How long the public IP is kept before it is looked up again.
+pub const PUBLIC_IP_REFRESH_INTERVAL: Duration = Duration::from_secs(600);
+
+/// The service used to look up the public IP, which replies with just the address.
+pub const PUBLIC_IP_HOST: &str = "api.ipify.org";
+
+const PUBLIC_IP_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct InterfaceAddressHarvest {
+    pub interface: String,
+    pub addresses: Vec<IpAddr>,
+}
+
+/// Whether an address is worth showing. Link-local IPv6 addresses are on every interface with
+/// IPv6 enabled, so they are skipped.
+#[cfg(any(target_os = "linux", target_os = "macos", test))]
+fn is_shown(address: &IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => !address.is_loopback(),
+        IpAddr::V6(address) => !address.is_loopback() && (address.segments()[0] & 0xffc0) != 0xfe80,
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub async fn get_interface_addresses(
+    filter: &Option<crate::app::Filter>,
+) -> Vec<InterfaceAddressHarvest> {
+    use futures::StreamExt;
+    use heim::net::Address;
+
+    let mut harvest: Vec<InterfaceAddressHarvest> = Vec::new();
+    let nics = match heim::net::nic().await {
+        Ok(nics) => nics,
+        Err(_) => return harvest,
+    };
+    futures::pin_mut!(nics);
+
+    while let Some(nic) = nics.next().await {
+        if let Ok(nic) = nic {
+            let address = match nic.address() {
+                Address::Inet(address) => IpAddr::V4(*address.ip()),
+                Address::Inet6(address) => IpAddr::V6(*address.ip()),
+                _ => continue,
+            };
+            if !is_shown(&address) || !super::network::is_interface_kept(nic.name(), filter) {
+                continue;
+            }
+
+            match harvest
+                .iter_mut()
+                .find(|interface| interface.interface == nic.name())
+            {
+                Some(interface) => interface.addresses.push(address),
+                None => harvest.push(InterfaceAddressHarvest {
+                    interface: nic.name().to_string(),
+                    addresses: vec![address],
+                }),
+            }
+        }
+    }
+
+    harvest
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub async fn get_interface_addresses(
+    _filter: &Option<crate::app::Filter>,
+) -> Vec<InterfaceAddressHarvest> {
+    Vec::new()
+}
+
+/// Keeps track of the public IP lookup, which is done on its own thread so a slow connection
+/// doesn't hold up the rest of the harvest.
+#[derive(Debug, Default)]
+pub struct PublicIpChecker {
+    in_flight: Option<Receiver<Option<IpAddr>>>,
+    last_lookup: Option<Instant>,
+}
+
+impl PublicIpChecker {
+    /// Returns the result of a finished lookup, if there is one, and starts a new lookup if the
+    /// last one is older than [`PUBLIC_IP_REFRESH_INTERVAL`].
+    pub fn get_public_ip(&mut self, current_instant: Instant) -> Option<IpAddr> {
+        let mut public_ip = None;
+        if let Some(receiver) = &self.in_flight {
+            match receiver.try_recv() {
+                Ok(result) => {
+                    public_ip = result;
+                    self.in_flight = None;
+                }
+                Err(TryRecvError::Disconnected) => self.in_flight = None,
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+
+        let is_stale = match self.last_lookup {
+            Some(last_lookup) => {
+                current_instant.duration_since(last_lookup) >= PUBLIC_IP_REFRESH_INTERVAL
+            }
+            None => true,
+        };
+        if self.in_flight.is_none() && is_stale {
+            let (sender, receiver) = mpsc::channel();
+            std::thread::spawn(move || {
+                let _ = sender.send(lookup_public_ip());
+            });
+            self.in_flight = Some(receiver);
+            self.last_lookup = Some(current_instant);
+        }
+
+        public_ip
+    }
+}
+
+fn lookup_public_ip() -> Option<IpAddr> {
+    let address = (PUBLIC_IP_HOST, 80).to_socket_addrs().ok()?.next()?;
+    let mut stream = TcpStream::connect_timeout(&address, PUBLIC_IP_TIMEOUT).ok()?;
+    stream.set_read_timeout(Some(PUBLIC_IP_TIMEOUT)).ok()?;
+    stream
+        .write_all(
+            format!(
+                "GET / HTTP/1.0\r\nHost: {}\r\nConnection: close\r\n\r\n",
+                PUBLIC_IP_HOST
+            )
+            .as_bytes(),
+        )
+        .ok()?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).ok()?;
+    parse_public_ip_response(&response)
+}
+
+/// Returns the address from the body of a successful HTTP response.
+fn parse_public_ip_response(response: &str) -> Option<IpAddr> {
+    let (head, body) = response.split_once("\r\n\r\n")?;
+    let status = head.lines().next()?.split_whitespace().nth(1)?;
+    if status == "200" {
+        body.trim().parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_shown() {
+        assert!(is_shown(&"192.168.1.2".parse().unwrap()));
+        assert!(is_shown(&"2001:db8::1".parse().unwrap()));
+        assert!(!is_shown(&"127.0.0.1".parse().unwrap()));
+        assert!(!is_shown(&"::1".parse().unwrap()));
+        assert!(!is_shown(&"fe80::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_parse_public_ip_response() {
+        let response =
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 11\r\n\r\n203.0.113.7";
+        assert_eq!(
+            parse_public_ip_response(response),
+            Some("203.0.113.7".parse().unwrap())
+        );
+        assert_eq!(
+            parse_public_ip_response("HTTP/1.1 503 Service Unavailable\r\n\r\n203.0.113.7"),
+            None
+        );
+        assert_eq!(
+            parse_public_ip_response("HTTP/1.1 200 OK\r\n\r\nnot an ip"),
+            None
+        );
+    }
+}
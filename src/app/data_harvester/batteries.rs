@@ -1,6 +1,7 @@
 //! Data collection for batteries.
 //!
 //! For Linux, macOS, Windows, FreeBSD, Dragonfly, and iOS, this is handled by the battery crate.
+//! On FreeBSD, batteries that the battery crate can't read are read from sysctl instead.
 
 cfg_if::cfg_if! {
     if #[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux", target_os = "freebsd", target_os = "dragonfly", target_os = "ios"))] {
@@ -8,3 +9,6 @@ cfg_if::cfg_if! {
         pub use self::battery::*;
     }
 }
+
+#[cfg(target_os = "freebsd")]
+pub mod freebsd;
@@ -9,18 +9,20 @@
 //!
 //! For more information, refer to the [starship_battery](https://github.com/starship/rust-battery) repo/docs.
 
+use serde::{Deserialize, Serialize};
 use starship_battery::{
     units::{power::watt, ratio::percent, time::second},
     Battery, Manager,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct BatteryHarvest {
     pub charge_percent: f64,
     pub secs_until_full: Option<i64>,
     pub secs_until_empty: Option<i64>,
     pub power_consumption_rate_watts: f64,
-    pub health_percent: f64,
+    /// The health of the battery, if it's known.
+    pub health_percent: Option<f64>,
 }
 
 pub fn refresh_batteries(manager: &Manager, batteries: &mut [Battery]) -> Vec<BatteryHarvest> {
@@ -39,7 +41,7 @@ pub fn refresh_batteries(manager: &Manager, batteries: &mut [Battery]) -> Vec<Ba
                     },
                     charge_percent: f64::from(battery.state_of_charge().get::<percent>()),
                     power_consumption_rate_watts: f64::from(battery.energy_rate().get::<watt>()),
-                    health_percent: f64::from(battery.state_of_health().get::<percent>()),
+                    health_percent: Some(f64::from(battery.state_of_health().get::<percent>())),
                 })
             } else {
                 None
@@ -0,0 +1,52 @@
+//! Reads batteries through the `hw.acpi.battery` sysctls on FreeBSD. Unlike `/dev/acpi`, which the
+//! battery crate uses, these can be read without any permissions, but they only give the combined
+//! state of every battery, and not their health.
+
+use super::BatteryHarvest;
+use crate::app::data_harvester::get_sysctl_int;
+
+/// Set in `hw.acpi.battery.state` while discharging.
+const ACPI_BATT_STAT_DISCHARG: i32 = 0x0001;
+
+/// Returns the combined state of the batteries, or nothing if there aren't any.
+pub fn get_sysctl_batteries() -> Vec<BatteryHarvest> {
+    match get_sysctl_int("hw.acpi.battery.units") {
+        Some(units) if units > 0 => {}
+        _ => return Vec::new(),
+    }
+
+    let life = get_sysctl_int("hw.acpi.battery.life");
+    let state = get_sysctl_int("hw.acpi.battery.state");
+    let minutes = get_sysctl_int("hw.acpi.battery.time");
+    let rate = get_sysctl_int("hw.acpi.battery.rate");
+
+    match (life, state) {
+        (Some(life), Some(state)) if life >= 0 => vec![get_battery_harvest(
+            life,
+            state,
+            minutes.unwrap_or(-1),
+            rate.unwrap_or(-1),
+        )],
+        _ => Vec::new(),
+    }
+}
+
+/// Builds a battery from the sysctl values, where unknown values are -1. The time left is in
+/// minutes and is only known while discharging, and the rate is in milliwatts.
+fn get_battery_harvest(life: i32, state: i32, minutes: i32, rate: i32) -> BatteryHarvest {
+    BatteryHarvest {
+        charge_percent: f64::from(life),
+        secs_until_full: None,
+        secs_until_empty: if state & ACPI_BATT_STAT_DISCHARG != 0 && minutes >= 0 {
+            Some(i64::from(minutes) * 60)
+        } else {
+            None
+        },
+        power_consumption_rate_watts: if rate >= 0 {
+            f64::from(rate) / 1000.0
+        } else {
+            0.0
+        },
+        health_percent: None,
+    }
+}
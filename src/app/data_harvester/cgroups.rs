@@ -0,0 +1,199 @@
+//! Data collection for cgroups.
+//!
+//! This walks the cgroup v2 hierarchy mounted at `/sys/fs/cgroup`, which on systemd systems has a
+//! cgroup for each slice, service, and scope. CPU usage is read from each cgroup's `cpu.stat`,
+//! memory usage from `memory.current`, and I/O from `io.stat`. The I/O and memory files only
+//! exist if their controller is enabled for the cgroup. The older cgroup v1 hierarchy is not
+//! supported.
+
+use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CgroupHarvest {
+    /// The path of the cgroup relative to the root of the hierarchy, like
+    /// `system.slice/sshd.service`.
+    pub name: String,
+    /// Where 100% is one core being fully used.
+    pub cpu_usage_percent: f64,
+    pub mem_usage_bytes: Option<u64>,
+    pub read_bytes_per_sec: Option<u64>,
+    pub write_bytes_per_sec: Option<u64>,
+}
+
+/// The cumulative counters of a cgroup, which are needed to calculate usage between harvests.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CgroupCounters {
+    cpu_usage_usec: u64,
+    /// Total bytes read and written.
+    io_bytes: Option<(u64, u64)>,
+}
+
+#[cfg(target_os = "linux")]
+pub fn get_cgroup_data(
+    prev_counters: &mut FxHashMap<String, CgroupCounters>, elapsed_secs: f64,
+) -> Option<Vec<CgroupHarvest>> {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+    // Only the root of a cgroup v2 hierarchy has this file.
+    if !Path::new(CGROUP_ROOT).join("cgroup.controllers").exists() {
+        return None;
+    }
+
+    let get_rate = |curr: u64, prev: u64| {
+        if elapsed_secs > 0.0 {
+            (curr.saturating_sub(prev) as f64 / elapsed_secs).round() as u64
+        } else {
+            0
+        }
+    };
+
+    let mut counters = FxHashMap::default();
+    let mut harvest = Vec::new();
+    let mut to_visit = vec![PathBuf::from(CGROUP_ROOT)];
+    while let Some(dir) = to_visit.pop() {
+        if let Ok(entries) = fs::read_dir(&dir) {
+            to_visit.extend(
+                entries
+                    .flatten()
+                    .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                    .map(|entry| entry.path()),
+            );
+        }
+
+        // The root cgroup covers the whole system, so it isn't worth showing.
+        let name = match dir.strip_prefix(CGROUP_ROOT) {
+            Ok(name) if !name.as_os_str().is_empty() => name.to_string_lossy().to_string(),
+            _ => continue,
+        };
+
+        let cpu_usage_usec = match fs::read_to_string(dir.join("cpu.stat"))
+            .ok()
+            .and_then(|contents| parse_cpu_stat(&contents))
+        {
+            Some(cpu_usage_usec) => cpu_usage_usec,
+            None => continue,
+        };
+        let mem_usage_bytes = fs::read_to_string(dir.join("memory.current"))
+            .ok()
+            .and_then(|contents| contents.trim().parse().ok());
+        let io_bytes = fs::read_to_string(dir.join("io.stat"))
+            .ok()
+            .map(|contents| parse_io_stat(&contents));
+
+        let prev = prev_counters.get(&name);
+        let cpu_usage_percent = if let Some(prev) = prev {
+            get_rate(cpu_usage_usec, prev.cpu_usage_usec) as f64 / 10_000.0
+        } else {
+            0.0
+        };
+        let (read_bytes_per_sec, write_bytes_per_sec) = if let Some((read, write)) = io_bytes {
+            let (prev_read, prev_write) =
+                prev.and_then(|prev| prev.io_bytes).unwrap_or((read, write));
+            (
+                Some(get_rate(read, prev_read)),
+                Some(get_rate(write, prev_write)),
+            )
+        } else {
+            (None, None)
+        };
+
+        counters.insert(
+            name.clone(),
+            CgroupCounters {
+                cpu_usage_usec,
+                io_bytes,
+            },
+        );
+        harvest.push(CgroupHarvest {
+            name,
+            cpu_usage_percent,
+            mem_usage_bytes,
+            read_bytes_per_sec,
+            write_bytes_per_sec,
+        });
+    }
+
+    *prev_counters = counters;
+
+    Some(harvest)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_cgroup_data(
+    _prev_counters: &mut FxHashMap<String, CgroupCounters>, _elapsed_secs: f64,
+) -> Option<Vec<CgroupHarvest>> {
+    None
+}
+
+/// Returns the total CPU time used in microseconds from a `cpu.stat` file, which looks like:
+///
+/// ```text
+/// usage_usec 123456
+/// user_usec 100000
+/// system_usec 23456
+/// ```
+#[cfg(target_os = "linux")]
+fn parse_cpu_stat(contents: &str) -> Option<u64> {
+    contents.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        match (fields.next(), fields.next()) {
+            (Some("usage_usec"), Some(value)) => value.parse().ok(),
+            _ => None,
+        }
+    })
+}
+
+/// Returns the total bytes read and written across all devices from an `io.stat` file, which
+/// looks like:
+///
+/// ```text
+/// 8:0 rbytes=1024 wbytes=2048 rios=1 wios=2 dbytes=0 dios=0
+/// 259:0 rbytes=4096 wbytes=0 rios=1 wios=0 dbytes=0 dios=0
+/// ```
+#[cfg(target_os = "linux")]
+fn parse_io_stat(contents: &str) -> (u64, u64) {
+    let mut read_bytes = 0;
+    let mut write_bytes = 0;
+    for field in contents.split_whitespace() {
+        match field.split_once('=') {
+            Some(("rbytes", value)) => read_bytes += value.parse().unwrap_or(0),
+            Some(("wbytes", value)) => write_bytes += value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    (read_bytes, write_bytes)
+}
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu_stat() {
+        let contents = "\
+usage_usec 123456
+user_usec 100000
+system_usec 23456
+";
+        assert_eq!(parse_cpu_stat(contents), Some(123456));
+        assert_eq!(parse_cpu_stat(""), None);
+    }
+
+    #[test]
+    fn test_parse_io_stat() {
+        let contents = "\
+8:0 rbytes=1024 wbytes=2048 rios=1 wios=2 dbytes=0 dios=0
+259:0 rbytes=4096 wbytes=0 rios=1 wios=0 dbytes=0 dios=0
+";
+        assert_eq!(parse_io_stat(contents), (5120, 2048));
+        assert_eq!(parse_io_stat(""), (0, 0));
+    }
+}
@@ -3,6 +3,13 @@
 //! For CPU usage, Linux, macOS, and Windows are handled by Heim, FreeBSD by sysinfo.
 //!
 //! For load average, macOS and Linux are supported through Heim, FreeBSD by sysinfo.
+//!
+//! Grouping CPUs by NUMA node or socket is only supported on Linux, and by kind of core on Apple
+//! Silicon.
+
+pub mod topology;
+
+use serde::{Deserialize, Serialize};
 
 cfg_if::cfg_if! {
     if #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))] {
@@ -16,14 +23,103 @@ cfg_if::cfg_if! {
 
 pub type LoadAvgHarvest = [f32; 3];
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Deserialize, Serialize)]
 pub struct CpuData {
     pub cpu_prefix: String,
     pub cpu_count: Option<usize>,
     pub cpu_usage: f64,
+    /// How the usage breaks down into [`CPU_STATES`], if that's turned on and supported.
+    pub breakdown: Option<CpuBreakdown>,
 }
 
 pub type CpuHarvest = Vec<CpuData>;
 
-pub type PastCpuWork = f64;
-pub type PastCpuTotal = f64;
+/// Returns the average CPU in `harvest`. This is the `AVG` entry if it's there, which it isn't if
+/// the average CPU is hidden, in which case it's worked out from the other entries.
+pub fn get_average(harvest: &[CpuData]) -> Option<CpuData> {
+    if let Some(average) = harvest.iter().find(|cpu| cpu.cpu_prefix == "AVG") {
+        return Some(average.clone());
+    }
+
+    let cores = harvest
+        .iter()
+        .filter(|cpu| cpu.cpu_prefix == "CPU")
+        .collect::<Vec<_>>();
+    if cores.is_empty() {
+        return None;
+    }
+
+    let count = cores.len();
+    let breakdown = cores
+        .iter()
+        .map(|cpu| cpu.breakdown)
+        .collect::<Option<Vec<_>>>()
+        .map(|breakdowns| {
+            let mut average = CpuBreakdown::default();
+            for breakdown in breakdowns {
+                for (total, usage) in average.iter_mut().zip(breakdown.iter()) {
+                    *total += usage / count as f32;
+                }
+            }
+            average
+        });
+
+    Some(CpuData {
+        cpu_prefix: "AVG".to_string(),
+        cpu_count: None,
+        cpu_usage: cores.iter().map(|cpu| cpu.cpu_usage).sum::<f64>() / count as f64,
+        breakdown,
+    })
+}
+
+/// The states that CPU time is broken down into. Nice time is counted as user time, and soft
+/// IRQs as IRQs. I/O wait is counted as idle time, so unlike the others, it isn't part of the
+/// usage. Only user and system time are reported on macOS and Windows.
+pub const CPU_STATES: [&str; 5] = ["User", "System", "I/O wait", "IRQ", "Steal"];
+
+/// The share of time a CPU spent in each of [`CPU_STATES`], as percentages.
+pub type CpuBreakdown = [f32; 5];
+
+/// The time a CPU has spent working, in total, and in each of [`CPU_STATES`], in seconds. Usage
+/// is worked out from the difference between two of these.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct CpuTimes {
+    pub work: f64,
+    pub total: f64,
+    pub states: [f64; 5],
+}
+
+impl CpuTimes {
+    /// Returns the percentage of time spent working since `previous`.
+    pub fn usage_since(&self, previous: &CpuTimes) -> f64 {
+        ((if self.work > previous.work {
+            self.work - previous.work
+        } else {
+            0.0
+        }) * 100.0)
+            / (if self.total > previous.total {
+                self.total - previous.total
+            } else {
+                1.0
+            })
+    }
+
+    /// Returns how the time since `previous` breaks down into [`CPU_STATES`].
+    pub fn breakdown_since(&self, previous: &CpuTimes) -> CpuBreakdown {
+        let total = if self.total > previous.total {
+            self.total - previous.total
+        } else {
+            1.0
+        };
+        let mut breakdown = CpuBreakdown::default();
+        for ((state, previous), current) in
+            breakdown.iter_mut().zip(&previous.states).zip(&self.states)
+        {
+            if current > previous {
+                *state = ((current - previous) * 100.0 / total) as f32;
+            }
+        }
+
+        breakdown
+    }
+}
@@ -18,28 +18,26 @@ cfg_if::cfg_if! {
     }
 }
 
-use crate::data_harvester::cpu::{CpuData, CpuHarvest, PastCpuTotal, PastCpuWork};
+use crate::data_harvester::cpu::{CpuBreakdown, CpuData, CpuHarvest, CpuTimes};
 use futures::StreamExt;
 use std::collections::VecDeque;
 
 pub async fn get_cpu_data_list(
-    show_average_cpu: bool, previous_cpu_times: &mut Vec<(PastCpuWork, PastCpuTotal)>,
-    previous_average_cpu_time: &mut Option<(PastCpuWork, PastCpuTotal)>,
+    show_average_cpu: bool, show_cpu_breakdown: bool, previous_cpu_times: &mut Vec<CpuTimes>,
+    previous_average_cpu_time: &mut Option<CpuTimes>,
 ) -> crate::error::Result<CpuHarvest> {
-    fn calculate_cpu_usage_percentage(
-        (previous_working_time, previous_total_time): (f64, f64),
-        (current_working_time, current_total_time): (f64, f64),
-    ) -> f64 {
-        ((if current_working_time > previous_working_time {
-            current_working_time - previous_working_time
+    fn calculate_cpu_usage_percentage(previous: &CpuTimes, current: &CpuTimes) -> f64 {
+        current.usage_since(previous)
+    }
+
+    fn calculate_cpu_breakdown(
+        show_cpu_breakdown: bool, previous: &CpuTimes, current: &CpuTimes,
+    ) -> Option<CpuBreakdown> {
+        if show_cpu_breakdown {
+            Some(current.breakdown_since(previous))
         } else {
-            0.0
-        }) * 100.0)
-            / (if current_total_time > previous_total_time {
-                current_total_time - previous_total_time
-            } else {
-                1.0
-            })
+            None
+        }
     }
 
     // Get all CPU times...
@@ -53,28 +51,32 @@ pub async fn get_cpu_data_list(
         let second_cpu_times = heim::cpu::times().await?;
         futures::pin_mut!(second_cpu_times);
 
-        let mut new_cpu_times: Vec<(PastCpuWork, PastCpuTotal)> = Vec::new();
+        let mut new_cpu_times: Vec<CpuTimes> = Vec::new();
         let mut cpu_deque: VecDeque<CpuData> = VecDeque::new();
         let mut collected_zip = cpu_times.zip(second_cpu_times).enumerate(); // Gotta move it here, can't on while line.
 
         while let Some((itx, (past, present))) = collected_zip.next().await {
             if let (Ok(past), Ok(present)) = (past, present) {
+                let past_times = convert_cpu_times(&past);
                 let present_times = convert_cpu_times(&present);
                 new_cpu_times.push(present_times);
                 cpu_deque.push_back(CpuData {
                     cpu_prefix: "CPU".to_string(),
                     cpu_count: Some(itx),
-                    cpu_usage: calculate_cpu_usage_percentage(
-                        convert_cpu_times(&past),
-                        present_times,
+                    cpu_usage: calculate_cpu_usage_percentage(&past_times, &present_times),
+                    breakdown: calculate_cpu_breakdown(
+                        show_cpu_breakdown,
+                        &past_times,
+                        &present_times,
                     ),
                 });
             } else {
-                new_cpu_times.push((0.0, 0.0));
+                new_cpu_times.push(CpuTimes::default());
                 cpu_deque.push_back(CpuData {
                     cpu_prefix: "CPU".to_string(),
                     cpu_count: Some(itx),
                     cpu_usage: 0.0,
+                    breakdown: None,
                 });
             }
         }
@@ -82,40 +84,42 @@ pub async fn get_cpu_data_list(
         *previous_cpu_times = new_cpu_times;
         cpu_deque
     } else {
-        let (new_cpu_times, cpu_deque): (Vec<(PastCpuWork, PastCpuTotal)>, VecDeque<CpuData>) =
-            cpu_times
-                .collect::<Vec<_>>()
-                .await
-                .iter()
-                .zip(&*previous_cpu_times)
-                .enumerate()
-                .map(|(itx, (current_cpu, (past_cpu_work, past_cpu_total)))| {
-                    if let Ok(cpu_time) = current_cpu {
-                        let present_times = convert_cpu_times(cpu_time);
-
-                        (
-                            present_times,
-                            CpuData {
-                                cpu_prefix: "CPU".to_string(),
-                                cpu_count: Some(itx),
-                                cpu_usage: calculate_cpu_usage_percentage(
-                                    (*past_cpu_work, *past_cpu_total),
-                                    present_times,
-                                ),
-                            },
-                        )
-                    } else {
-                        (
-                            (*past_cpu_work, *past_cpu_total),
-                            CpuData {
-                                cpu_prefix: "CPU".to_string(),
-                                cpu_count: Some(itx),
-                                cpu_usage: 0.0,
-                            },
-                        )
-                    }
-                })
-                .unzip();
+        let (new_cpu_times, cpu_deque): (Vec<CpuTimes>, VecDeque<CpuData>) = cpu_times
+            .collect::<Vec<_>>()
+            .await
+            .iter()
+            .zip(&*previous_cpu_times)
+            .enumerate()
+            .map(|(itx, (current_cpu, past_times))| {
+                if let Ok(cpu_time) = current_cpu {
+                    let present_times = convert_cpu_times(cpu_time);
+
+                    (
+                        present_times,
+                        CpuData {
+                            cpu_prefix: "CPU".to_string(),
+                            cpu_count: Some(itx),
+                            cpu_usage: calculate_cpu_usage_percentage(past_times, &present_times),
+                            breakdown: calculate_cpu_breakdown(
+                                show_cpu_breakdown,
+                                past_times,
+                                &present_times,
+                            ),
+                        },
+                    )
+                } else {
+                    (
+                        *past_times,
+                        CpuData {
+                            cpu_prefix: "CPU".to_string(),
+                            cpu_count: Some(itx),
+                            cpu_usage: 0.0,
+                            breakdown: None,
+                        },
+                    )
+                }
+            })
+            .unzip();
 
         *previous_cpu_times = new_cpu_times;
         cpu_deque
@@ -125,31 +129,25 @@ pub async fn get_cpu_data_list(
     if show_average_cpu {
         let cpu_time = heim::cpu::time().await?;
 
-        let (cpu_usage, new_average_cpu_time) = if let Some((past_cpu_work, past_cpu_total)) =
-            previous_average_cpu_time
-        {
-            let present_times = convert_cpu_times(&cpu_time);
-            (
-                calculate_cpu_usage_percentage((*past_cpu_work, *past_cpu_total), present_times),
-                present_times,
-            )
+        let (past_times, present_times) = if let Some(past_times) = previous_average_cpu_time {
+            (*past_times, convert_cpu_times(&cpu_time))
         } else {
             // Again, we need to do a quick timeout...
             futures_timer::Delay::new(std::time::Duration::from_millis(100)).await;
             let second_cpu_time = heim::cpu::time().await?;
 
-            let present_times = convert_cpu_times(&second_cpu_time);
             (
-                calculate_cpu_usage_percentage(convert_cpu_times(&cpu_time), present_times),
-                present_times,
+                convert_cpu_times(&cpu_time),
+                convert_cpu_times(&second_cpu_time),
             )
         };
 
-        *previous_average_cpu_time = Some(new_average_cpu_time);
+        *previous_average_cpu_time = Some(present_times);
         cpu_deque.push_front(CpuData {
             cpu_prefix: "AVG".to_string(),
             cpu_count: None,
-            cpu_usage,
+            cpu_usage: calculate_cpu_usage_percentage(&past_times, &present_times),
+            breakdown: calculate_cpu_breakdown(show_cpu_breakdown, &past_times, &present_times),
         })
     }
 
@@ -1,16 +1,21 @@
 //! Linux-specific functions regarding CPU usage.
 
+use crate::data_harvester::cpu::CpuTimes;
 use heim::cpu::os::linux::CpuTimeExt;
-pub fn convert_cpu_times(cpu_time: &heim::cpu::CpuTime) -> (f64, f64) {
-    let working_time: f64 = (cpu_time.user()
-        + cpu_time.nice()
-        + cpu_time.system()
-        + cpu_time.irq()
-        + cpu_time.soft_irq()
-        + cpu_time.steal())
-    .get::<heim::units::time::second>();
-    (
-        working_time,
-        working_time + (cpu_time.idle() + cpu_time.io_wait()).get::<heim::units::time::second>(),
-    )
+
+pub fn convert_cpu_times(cpu_time: &heim::cpu::CpuTime) -> CpuTimes {
+    let seconds = |time: heim::units::Time| time.get::<heim::units::time::second>();
+
+    let user = seconds(cpu_time.user() + cpu_time.nice());
+    let system = seconds(cpu_time.system());
+    let io_wait = seconds(cpu_time.io_wait());
+    let irq = seconds(cpu_time.irq() + cpu_time.soft_irq());
+    let steal = seconds(cpu_time.steal());
+
+    let working_time = user + system + irq + steal;
+    CpuTimes {
+        work: working_time,
+        total: working_time + seconds(cpu_time.idle()) + io_wait,
+        states: [user, system, io_wait, irq, steal],
+    }
 }
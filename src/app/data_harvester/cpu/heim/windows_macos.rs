@@ -1,10 +1,17 @@
 //! Windows and macOS-specific functions regarding CPU usage.
 
-pub fn convert_cpu_times(cpu_time: &heim::cpu::CpuTime) -> (f64, f64) {
-    let working_time: f64 =
-        (cpu_time.user() + cpu_time.system()).get::<heim::units::time::second>();
-    (
-        working_time,
-        working_time + cpu_time.idle().get::<heim::units::time::second>(),
-    )
+use crate::data_harvester::cpu::CpuTimes;
+
+pub fn convert_cpu_times(cpu_time: &heim::cpu::CpuTime) -> CpuTimes {
+    let seconds = |time: heim::units::Time| time.get::<heim::units::time::second>();
+
+    let user = seconds(cpu_time.user());
+    let system = seconds(cpu_time.system());
+
+    let working_time = user + system;
+    CpuTimes {
+        work: working_time,
+        total: working_time + seconds(cpu_time.idle()),
+        states: [user, system, 0.0, 0.0, 0.0],
+    }
 }
@@ -5,13 +5,12 @@ use std::collections::VecDeque;
 
 use sysinfo::{LoadAvg, ProcessorExt, System, SystemExt};
 
-use super::{CpuData, CpuHarvest, PastCpuTotal, PastCpuWork};
+use super::{CpuData, CpuHarvest, CpuTimes};
 use crate::app::data_harvester::cpu::LoadAvgHarvest;
 
 pub async fn get_cpu_data_list(
-    sys: &sysinfo::System, show_average_cpu: bool,
-    _previous_cpu_times: &mut Vec<(PastCpuWork, PastCpuTotal)>,
-    _previous_average_cpu_time: &mut Option<(PastCpuWork, PastCpuTotal)>,
+    sys: &sysinfo::System, show_average_cpu: bool, _show_cpu_breakdown: bool,
+    _previous_cpu_times: &mut Vec<CpuTimes>, _previous_average_cpu_time: &mut Option<CpuTimes>,
 ) -> crate::error::Result<CpuHarvest> {
     let mut cpu_deque: VecDeque<_> = sys
         .processors()
@@ -21,6 +20,7 @@ pub async fn get_cpu_data_list(
             cpu_prefix: "CPU".to_string(),
             cpu_count: Some(i),
             cpu_usage: cpu.cpu_usage() as f64,
+            breakdown: None,
         })
         .collect();
 
@@ -31,6 +31,7 @@ pub async fn get_cpu_data_list(
             cpu_prefix: "AVG".to_string(),
             cpu_count: None,
             cpu_usage: cpu.cpu_usage() as f64,
+            breakdown: None,
         })
     }
 
@@ -0,0 +1,249 @@
+//! Detecting how CPUs are grouped, into NUMA nodes or sockets on Linux through sysfs, or into
+//! efficiency and performance cores on Apple Silicon through sysctl. This is read once at startup.
+
+/// A group of CPUs, like a NUMA node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CpuGroup {
+    /// The name of the group in the CPU widget's legend, like `Node0` or `P-cores`.
+    pub name: String,
+    /// The name of the group when the legend is narrow, like `N0` or `P`.
+    pub short_name: String,
+    /// The numbers of the CPUs in the group.
+    pub cpus: Vec<usize>,
+}
+
+/// Names groups that are just numbered, like NUMA nodes.
+#[cfg(target_os = "linux")]
+fn get_numbered_groups(groups: Vec<Vec<usize>>) -> Vec<CpuGroup> {
+    groups
+        .into_iter()
+        .enumerate()
+        .map(|(node, cpus)| CpuGroup {
+            name: format!("Node{}", node),
+            short_name: format!("N{}", node),
+            cpus,
+        })
+        .collect()
+}
+
+/// Returns the CPUs in each NUMA node, or in each socket if there's only one NUMA node. This is
+/// empty if there's only one of either, as there's nothing to group then.
+#[cfg(target_os = "linux")]
+pub fn get_cpu_nodes() -> Vec<CpuGroup> {
+    let nodes = read_numbered_dirs("/sys/devices/system/node", "node", |path| {
+        std::fs::read_to_string(path.join("cpulist"))
+            .ok()
+            .map(|cpu_list| parse_cpu_list(&cpu_list))
+    })
+    .into_iter()
+    .map(|(_node, cpus)| cpus)
+    .filter(|cpus| !cpus.is_empty())
+    .collect::<Vec<_>>();
+    if nodes.len() > 1 {
+        return get_numbered_groups(nodes);
+    }
+
+    // Each CPU lists its socket rather than the other way around, so group them here.
+    let mut sockets: std::collections::BTreeMap<u64, Vec<usize>> = Default::default();
+    for (cpu, socket) in read_numbered_dirs("/sys/devices/system/cpu", "cpu", |path| {
+        std::fs::read_to_string(path.join("topology/physical_package_id"))
+            .ok()
+            .and_then(|socket| socket.trim().parse::<u64>().ok())
+    }) {
+        sockets.entry(socket).or_default().push(cpu);
+    }
+    if sockets.len() > 1 {
+        get_numbered_groups(sockets.into_values().collect())
+    } else {
+        Vec::new()
+    }
+}
+
+/// Returns the CPUs of each kind of core on Apple Silicon, like efficiency and performance cores.
+/// This is empty on Intel Macs, which only have one kind.
+#[cfg(target_os = "macos")]
+pub fn get_cpu_nodes() -> Vec<CpuGroup> {
+    let num_levels = read_sysctl_u32("hw.nperflevels").unwrap_or(0);
+    let levels = (0..num_levels)
+        .map(|level| {
+            let name = read_sysctl_string(&format!("hw.perflevel{}.name", level))
+                .unwrap_or_else(|| format!("Level{}", level));
+            let num_cpus = read_sysctl_u32(&format!("hw.perflevel{}.logicalcpu", level))?;
+            Some((name, num_cpus as usize))
+        })
+        .collect::<Option<Vec<_>>>()
+        .unwrap_or_default();
+
+    get_perf_level_groups(&levels)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn get_cpu_nodes() -> Vec<CpuGroup> {
+    Vec::new()
+}
+
+/// Groups CPUs by the performance level of their cores, given the name and number of CPUs of each
+/// level from the fastest, as macOS lists them. The cores of the slowest level are numbered first.
+#[cfg(any(target_os = "macos", test))]
+fn get_perf_level_groups(levels: &[(String, usize)]) -> Vec<CpuGroup> {
+    if levels.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut first_cpu = 0;
+    levels
+        .iter()
+        .rev()
+        .map(|(name, num_cpus)| {
+            let (name, short_name) = match name.as_str() {
+                "Efficiency" => ("E-cores".to_string(), "E".to_string()),
+                "Performance" => ("P-cores".to_string(), "P".to_string()),
+                name => (
+                    name.to_string(),
+                    name.chars().next().map(String::from).unwrap_or_default(),
+                ),
+            };
+            let cpus = (first_cpu..first_cpu + num_cpus).collect();
+            first_cpu += num_cpus;
+
+            CpuGroup {
+                name,
+                short_name,
+                cpus,
+            }
+        })
+        .collect()
+}
+
+/// Reads a sysctl value that's a 32-bit integer, like `hw.nperflevels`.
+#[cfg(target_os = "macos")]
+fn read_sysctl_u32(name: &str) -> Option<u32> {
+    let name = std::ffi::CString::new(name).ok()?;
+    let mut value: u32 = 0;
+    let mut size = std::mem::size_of::<u32>();
+
+    // SAFETY: the name is null-terminated, and the size passed is that of the value.
+    let result = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr(),
+            (&mut value as *mut u32).cast(),
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if result == 0 && size == std::mem::size_of::<u32>() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Reads a sysctl value that's a string, like `hw.perflevel0.name`.
+#[cfg(target_os = "macos")]
+fn read_sysctl_string(name: &str) -> Option<String> {
+    let name = std::ffi::CString::new(name).ok()?;
+    let mut buffer = [0_u8; 64];
+    let mut size = buffer.len();
+
+    // SAFETY: the name is null-terminated, and the size passed is that of the buffer.
+    let result = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr(),
+            buffer.as_mut_ptr().cast(),
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if result != 0 {
+        return None;
+    }
+
+    let value = &buffer[..size.min(buffer.len())];
+    let value = value.split(|byte| *byte == 0).next().unwrap_or_default();
+    Some(String::from_utf8_lossy(value).into_owned())
+}
+
+/// Reads each directory in `dir` named `prefix` followed by a number with `read`, in order of that
+/// number.
+#[cfg(target_os = "linux")]
+fn read_numbered_dirs<T>(
+    dir: &str, prefix: &str, read: impl Fn(&std::path::Path) -> Option<T>,
+) -> Vec<(usize, T)> {
+    let mut values = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let number = entry
+                        .file_name()
+                        .to_str()?
+                        .strip_prefix(prefix)?
+                        .parse::<usize>()
+                        .ok()?;
+                    Some((number, read(&entry.path())?))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    values.sort_by_key(|(number, _value)| *number);
+
+    values
+}
+
+/// Parses a list of CPUs in the kernel's format, like `0-3,8,10-11`.
+#[cfg(target_os = "linux")]
+fn parse_cpu_list(cpu_list: &str) -> Vec<usize> {
+    cpu_list
+        .trim()
+        .split(',')
+        .filter_map(|range| match range.split_once('-') {
+            Some((start, end)) => Some(start.parse::<usize>().ok()?..=end.parse::<usize>().ok()?),
+            None => {
+                let cpu = range.parse::<usize>().ok()?;
+                Some(cpu..=cpu)
+            }
+        })
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpu_list("5"), vec![5]);
+        assert!(parse_cpu_list("\n").is_empty());
+    }
+
+    #[test]
+    fn test_get_perf_level_groups() {
+        let groups = get_perf_level_groups(&[
+            ("Performance".to_string(), 8),
+            ("Efficiency".to_string(), 2),
+        ]);
+        assert_eq!(
+            groups,
+            vec![
+                CpuGroup {
+                    name: "E-cores".to_string(),
+                    short_name: "E".to_string(),
+                    cpus: vec![0, 1],
+                },
+                CpuGroup {
+                    name: "P-cores".to_string(),
+                    short_name: "P".to_string(),
+                    cpus: (2..10).collect(),
+                },
+            ]
+        );
+
+        // Intel Macs only have one level.
+        assert!(get_perf_level_groups(&[("Performance".to_string(), 8)]).is_empty());
+    }
+}
@@ -13,16 +13,72 @@ cfg_if::cfg_if! {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[cfg(target_os = "linux")]
+pub mod smart;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct DiskHarvest {
     pub name: String,
     pub mount_point: String,
+    /// The filesystem type, like `ext4`, if it is known.
+    pub fs_type: Option<String>,
     pub free_space: Option<u64>,
     pub used_space: Option<u64>,
     pub total_space: Option<u64>,
+    pub total_inodes: Option<u64>,
+    pub free_inodes: Option<u64>,
+    pub smart: Option<SmartHarvest>,
+    pub activity: Option<DiskActivity>,
+}
+
+/// SMART health data of the device backing a disk.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SmartHarvest {
+    pub temperature: Option<f32>,
+    /// The percentage of the device's rated life that has been used.
+    pub wear_level: Option<u8>,
+    pub reallocated_sectors: Option<u64>,
+    pub power_on_hours: Option<u64>,
+    pub is_failing: bool,
+}
+
+/// How busy the device backing a disk is. This is only collected on Windows.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+pub struct DiskActivity {
+    /// The percentage of time the device was busy with reads or writes.
+    pub active_percent: f64,
+    /// The average number of reads and writes waiting on the device.
+    pub queue_length: f64,
+}
+
+/// Returns the total and free inode counts of the filesystem mounted at `mount_point`. Both are
+/// [`None`] if they can't be read, or if the filesystem doesn't have a fixed number of inodes.
+#[cfg(target_family = "unix")]
+#[allow(clippy::unnecessary_cast)] // `fsfilcnt_t` is not a `u64` on every platform.
+pub fn get_inode_usage(mount_point: &str) -> (Option<u64>, Option<u64>) {
+    let path = match std::ffi::CString::new(mount_point) {
+        Ok(path) => path,
+        Err(_) => return (None, None),
+    };
+
+    let mut stats = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    let result = unsafe { libc::statvfs(path.as_ptr(), stats.as_mut_ptr()) };
+    if result != 0 {
+        return (None, None);
+    }
+
+    // SAFETY: statvfs succeeded, so the struct has been filled in.
+    let stats = unsafe { stats.assume_init() };
+    if stats.f_files == 0 {
+        (None, None)
+    } else {
+        (Some(stats.f_files as u64), Some(stats.f_ffree as u64))
+    }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct IoData {
     pub read_bytes: u64,
     pub write_bytes: u64,
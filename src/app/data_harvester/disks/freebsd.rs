@@ -65,12 +65,19 @@ pub async fn get_disk_usage(
                 if matches_allow_list(filter_check_map.as_slice())
                     || !matches_ignore_list(filter_check_map.as_slice())
                 {
+                    let (total_inodes, free_inodes) = super::get_inode_usage(&disk.mounted_on);
+
                     Some(DiskHarvest {
                         free_space: Some(disk.available_blocks * 1024),
                         used_space: Some(disk.used_blocks * 1024),
                         total_space: Some(disk.total_blocks * 1024),
+                        total_inodes,
+                        free_inodes,
                         mount_point: disk.mounted_on,
+                        fs_type: None,
                         name: disk.name,
+                        smart: None,
+                        activity: None,
                     })
                 } else {
                     None
@@ -111,6 +111,17 @@ pub async fn get_disk_usage(
             };
 
             if to_keep {
+                let (total_inodes, free_inodes) = {
+                    #[cfg(target_family = "unix")]
+                    {
+                        crate::data_harvester::disks::get_inode_usage(&mount_point)
+                    }
+                    #[cfg(not(target_family = "unix"))]
+                    {
+                        (None, None)
+                    }
+                };
+
                 // The usage line can fail in some cases (for example, if you use Void Linux + LUKS,
                 // see https://github.com/ClementTsang/bottom/issues/419 for details).  As such, check
                 // it like this instead.
@@ -119,16 +130,26 @@ pub async fn get_disk_usage(
                         free_space: Some(usage.free().get::<heim::units::information::byte>()),
                         used_space: Some(usage.used().get::<heim::units::information::byte>()),
                         total_space: Some(usage.total().get::<heim::units::information::byte>()),
+                        total_inodes,
+                        free_inodes,
                         mount_point,
+                        fs_type: Some(partition.file_system().as_str().to_string()),
                         name,
+                        smart: None,
+                        activity: None,
                     });
                 } else {
                     vec_disks.push(DiskHarvest {
                         free_space: None,
                         used_space: None,
                         total_space: None,
+                        total_inodes,
+                        free_inodes,
                         mount_point,
+                        fs_type: Some(partition.file_system().as_str().to_string()),
                         name,
+                        smart: None,
+                        activity: None,
                     });
                 }
             }
@@ -0,0 +1,235 @@
+//! Linux-specific SMART data collection.
+//!
+//! This reads SMART data directly from the device through ioctls, so it generally requires
+//! root (or `CAP_SYS_ADMIN`/`CAP_SYS_RAWIO`) to work. Only NVMe and ATA devices are supported.
+
+use std::{fs::File, os::unix::io::AsRawFd, path::Path, time::Duration};
+
+use crate::data_harvester::disks::SmartHarvest;
+
+/// How long SMART data is cached before the device is queried again.
+pub const SMART_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// `_IOWR('N', 0x41, struct nvme_admin_cmd)`
+const NVME_IOCTL_ADMIN_CMD: libc::c_ulong = 0xC048_4E41;
+const NVME_ADMIN_GET_LOG_PAGE: u8 = 0x02;
+const NVME_LOG_SMART: u32 = 0x02;
+
+const HDIO_DRIVE_CMD: libc::c_ulong = 0x031F;
+const ATA_SMART_CMD: u8 = 0xB0;
+const ATA_SMART_READ_VALUES: u8 = 0xD0;
+
+const SMART_DATA_LENGTH: usize = 512;
+
+const ATA_ATTRIBUTE_COUNT: usize = 30;
+const ATA_ATTRIBUTE_LENGTH: usize = 12;
+const ATA_REALLOCATED_SECTORS: u8 = 5;
+const ATA_POWER_ON_HOURS: u8 = 9;
+const ATA_TEMPERATURE: u8 = 194;
+const ATA_WEAR_ATTRIBUTES: [u8; 3] = [177, 231, 233];
+
+/// Mirrors `struct nvme_admin_cmd` from `linux/nvme_ioctl.h`.
+#[repr(C)]
+#[derive(Default)]
+struct NvmeAdminCmd {
+    opcode: u8,
+    flags: u8,
+    rsvd1: u16,
+    nsid: u32,
+    cdw2: u32,
+    cdw3: u32,
+    metadata: u64,
+    addr: u64,
+    metadata_len: u32,
+    data_len: u32,
+    cdw10: u32,
+    cdw11: u32,
+    cdw12: u32,
+    cdw13: u32,
+    cdw14: u32,
+    cdw15: u32,
+    timeout_ms: u32,
+    result: u32,
+}
+
+/// Returns the path of the whole device backing a partition (for example, `/dev/sda` for
+/// `/dev/sda1`), or [`None`] if SMART data can't be read from it.
+pub fn get_parent_device(name: &str) -> Option<String> {
+    let device = Path::new(name).file_name()?.to_str()?;
+    let sys_path = Path::new("/sys/class/block").join(device);
+
+    let parent = if sys_path.join("partition").exists() {
+        std::fs::canonicalize(&sys_path)
+            .ok()?
+            .parent()?
+            .file_name()?
+            .to_str()?
+            .to_string()
+    } else {
+        device.to_string()
+    };
+
+    if parent.starts_with("nvme") || parent.starts_with("sd") || parent.starts_with("hd") {
+        Some(format!("/dev/{}", parent))
+    } else {
+        None
+    }
+}
+
+/// Reads SMART data from a whole device, as returned by [`get_parent_device`]. Temperatures
+/// are returned in Celsius.
+pub fn get_smart_data(device: &str) -> Option<SmartHarvest> {
+    let file = File::open(device).ok()?;
+
+    if device.starts_with("/dev/nvme") {
+        read_nvme_smart(&file)
+    } else {
+        read_ata_smart(&file)
+    }
+}
+
+fn read_nvme_smart(file: &File) -> Option<SmartHarvest> {
+    let mut log = [0_u8; SMART_DATA_LENGTH];
+    let num_dwords = (SMART_DATA_LENGTH / 4) as u32;
+    let mut cmd = NvmeAdminCmd {
+        opcode: NVME_ADMIN_GET_LOG_PAGE,
+        nsid: 0xFFFF_FFFF,
+        addr: log.as_mut_ptr() as u64,
+        data_len: SMART_DATA_LENGTH as u32,
+        cdw10: NVME_LOG_SMART | ((num_dwords - 1) << 16),
+        ..Default::default()
+    };
+
+    let result = unsafe { libc::ioctl(file.as_raw_fd(), NVME_IOCTL_ADMIN_CMD as _, &mut cmd) };
+    if result != 0 {
+        return None;
+    }
+
+    Some(parse_nvme_smart_log(&log))
+}
+
+fn parse_nvme_smart_log(log: &[u8; SMART_DATA_LENGTH]) -> SmartHarvest {
+    let critical_warning = log[0];
+    let temperature_kelvin = u16::from_le_bytes([log[1], log[2]]);
+    let percentage_used = log[5];
+
+    // These are 128-bit counters; the lower 64 bits are plenty.
+    let read_u64 = |offset: usize| {
+        let mut bytes = [0_u8; 8];
+        bytes.copy_from_slice(&log[offset..offset + 8]);
+        u64::from_le_bytes(bytes)
+    };
+    let power_on_hours = read_u64(128);
+    let media_errors = read_u64(160);
+
+    SmartHarvest {
+        temperature: if temperature_kelvin > 0 {
+            Some(f32::from(temperature_kelvin) - 273.15)
+        } else {
+            None
+        },
+        wear_level: Some(percentage_used),
+        reallocated_sectors: Some(media_errors),
+        power_on_hours: Some(power_on_hours),
+        is_failing: critical_warning != 0 || media_errors > 0 || percentage_used >= 100,
+    }
+}
+
+fn read_ata_smart(file: &File) -> Option<SmartHarvest> {
+    // The first four bytes are the command, and the rest is filled with the returned data.
+    let mut buffer = [0_u8; 4 + SMART_DATA_LENGTH];
+    buffer[0] = ATA_SMART_CMD;
+    buffer[2] = ATA_SMART_READ_VALUES;
+    buffer[3] = 1;
+
+    let result = unsafe { libc::ioctl(file.as_raw_fd(), HDIO_DRIVE_CMD as _, buffer.as_mut_ptr()) };
+    if result != 0 {
+        return None;
+    }
+
+    Some(parse_ata_smart_values(&buffer[4..]))
+}
+
+fn parse_ata_smart_values(data: &[u8]) -> SmartHarvest {
+    let mut smart = SmartHarvest::default();
+
+    // Skip the two byte revision number at the start of the table.
+    for attribute in data[2..]
+        .chunks_exact(ATA_ATTRIBUTE_LENGTH)
+        .take(ATA_ATTRIBUTE_COUNT)
+    {
+        let id = attribute[0];
+        let normalized = attribute[3];
+        let raw = attribute[5..11]
+            .iter()
+            .rev()
+            .fold(0_u64, |acc, byte| (acc << 8) | u64::from(*byte));
+
+        match id {
+            ATA_REALLOCATED_SECTORS => {
+                smart.reallocated_sectors = Some(raw & 0xFFFF_FFFF);
+            }
+            ATA_POWER_ON_HOURS => {
+                smart.power_on_hours = Some(raw & 0xFFFF_FFFF);
+            }
+            ATA_TEMPERATURE => {
+                smart.temperature = Some(f32::from(attribute[5]));
+            }
+            id if ATA_WEAR_ATTRIBUTES.contains(&id) && smart.wear_level.is_none() => {
+                // The normalized value counts down from 100 as the drive wears out.
+                smart.wear_level = Some(100_u8.saturating_sub(normalized));
+            }
+            _ => {}
+        }
+    }
+
+    smart.is_failing = matches!(smart.reallocated_sectors, Some(sectors) if sectors > 0)
+        || matches!(smart.wear_level, Some(wear) if wear >= 100);
+
+    smart
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_nvme_smart_log() {
+        let mut log = [0_u8; SMART_DATA_LENGTH];
+        log[1..3].copy_from_slice(&313_u16.to_le_bytes());
+        log[5] = 3;
+        log[128..136].copy_from_slice(&1234_u64.to_le_bytes());
+
+        let smart = parse_nvme_smart_log(&log);
+        assert_eq!(smart.temperature.map(|t| t.round() as u64), Some(40));
+        assert_eq!(smart.wear_level, Some(3));
+        assert_eq!(smart.power_on_hours, Some(1234));
+        assert_eq!(smart.reallocated_sectors, Some(0));
+        assert!(!smart.is_failing);
+
+        log[0] = 1;
+        assert!(parse_nvme_smart_log(&log).is_failing);
+    }
+
+    #[test]
+    fn test_parse_ata_smart_values() {
+        let mut data = [0_u8; SMART_DATA_LENGTH];
+        let mut set_attribute = |index: usize, id: u8, normalized: u8, raw: u64| {
+            let offset = 2 + index * ATA_ATTRIBUTE_LENGTH;
+            data[offset] = id;
+            data[offset + 3] = normalized;
+            data[offset + 5..offset + 11].copy_from_slice(&raw.to_le_bytes()[..6]);
+        };
+        set_attribute(0, ATA_REALLOCATED_SECTORS, 100, 8);
+        set_attribute(1, ATA_POWER_ON_HOURS, 99, 5000);
+        set_attribute(2, ATA_TEMPERATURE, 60, 0x0032_0014_0025);
+        set_attribute(3, 177, 95, 42);
+
+        let smart = parse_ata_smart_values(&data);
+        assert_eq!(smart.reallocated_sectors, Some(8));
+        assert_eq!(smart.power_on_hours, Some(5000));
+        assert_eq!(smart.temperature.map(|t| t as u64), Some(37));
+        assert_eq!(smart.wear_level, Some(5));
+        assert!(smart.is_failing);
+    }
+}
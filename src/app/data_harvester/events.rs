@@ -0,0 +1,188 @@
+//! Data collection for kernel events.
+//!
+//! This is only available on Linux. The kernel log is read with `dmesg`, or from journald with
+//! `journalctl` if the kernel log can't be read directly, which is the case for normal users on
+//! distributions that set `kernel.dmesg_restrict`. Only messages about OOM kills, thermal
+//! throttling, and disk errors are kept, as the rest of the log is mostly noise for finding out
+//! why a system is slow or why a process vanished.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum KernelEventType {
+    /// A process was killed by the OOM killer, either system-wide or for a cgroup's memory limit.
+    OomKill,
+    /// A CPU was throttled for running too hot.
+    Thermal,
+    /// A disk or file system reported an error.
+    DiskError,
+}
+
+impl KernelEventType {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            KernelEventType::OomKill => "OOM",
+            KernelEventType::Thermal => "Thermal",
+            KernelEventType::DiskError => "Disk",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct KernelEventHarvest {
+    /// When the event was logged, like `2022-05-01 12:34:56`.
+    pub time: String,
+    pub event_type: KernelEventType,
+    pub message: String,
+}
+
+/// Returns the events in the kernel log, oldest first. An error is only returned if neither
+/// `dmesg` nor `journalctl` could be run.
+#[cfg(target_os = "linux")]
+pub fn get_kernel_events() -> Result<Option<Vec<KernelEventHarvest>>, String> {
+    use std::process::Command;
+
+    let run = |command: &mut Command| {
+        command
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    if let Some(output) = run(Command::new("dmesg").arg("--time-format=iso")) {
+        return Ok(Some(output.lines().filter_map(parse_dmesg_line).collect()));
+    }
+
+    if let Some(output) = run(Command::new("journalctl").args([
+        "--dmesg",
+        "--boot",
+        "--output=short-iso",
+        "--no-pager",
+        "--quiet",
+    ])) {
+        return Ok(Some(
+            output.lines().filter_map(parse_journal_line).collect(),
+        ));
+    }
+
+    Err("the kernel log couldn't be read with dmesg or journalctl".to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_kernel_events() -> Result<Option<Vec<KernelEventHarvest>>, String> {
+    Ok(None)
+}
+
+/// Parses a line from `dmesg --time-format=iso`, which looks like:
+///
+/// ```text
+/// 2022-05-01T12:34:56,123456+00:00 Out of memory: Killed process 1234 (firefox) ...
+/// ```
+#[cfg(target_os = "linux")]
+fn parse_dmesg_line(line: &str) -> Option<KernelEventHarvest> {
+    let (time, message) = line.split_once(' ')?;
+    get_event(time, message)
+}
+
+/// Parses a line from `journalctl --dmesg --output=short-iso`, which looks like:
+///
+/// ```text
+/// 2022-05-01T12:34:56+0000 hostname kernel: Out of memory: Killed process 1234 (firefox) ...
+/// ```
+#[cfg(target_os = "linux")]
+fn parse_journal_line(line: &str) -> Option<KernelEventHarvest> {
+    let (time, rest) = line.split_once(' ')?;
+    let (_, message) = rest.split_once("kernel: ")?;
+    get_event(time, message)
+}
+
+#[cfg(target_os = "linux")]
+fn get_event(time: &str, message: &str) -> Option<KernelEventHarvest> {
+    let message = message.trim();
+    let event_type = get_event_type(message)?;
+
+    // Only the date and time to the second are worth showing.
+    let time = time.get(..19).unwrap_or(time).replacen('T', " ", 1);
+
+    Some(KernelEventHarvest {
+        time,
+        event_type,
+        message: message.to_string(),
+    })
+}
+
+/// Returns the type of event a kernel log message is about, if it's one that's kept. Only the
+/// one line summing up each OOM kill is kept, as the kernel also logs a few for the state of
+/// memory and the candidate processes at the time.
+#[cfg(target_os = "linux")]
+fn get_event_type(message: &str) -> Option<KernelEventType> {
+    if message.contains("out of memory: Killed process")
+        || message.contains("Out of memory: Killed process")
+    {
+        Some(KernelEventType::OomKill)
+    } else if message.contains("temperature above threshold")
+        || message.contains("critical temperature reached")
+    {
+        Some(KernelEventType::Thermal)
+    } else if message.contains("I/O error")
+        || message.contains("EXT4-fs error")
+        || message.contains("BTRFS error")
+        || message.contains("Medium Error")
+    {
+        Some(KernelEventType::DiskError)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_dmesg_line() {
+        assert_eq!(
+            parse_dmesg_line(
+                "2022-05-01T12:34:56,123456+00:00 Out of memory: Killed process 1234 (firefox)"
+            ),
+            Some(KernelEventHarvest {
+                time: "2022-05-01 12:34:56".to_string(),
+                event_type: KernelEventType::OomKill,
+                message: "Out of memory: Killed process 1234 (firefox)".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_dmesg_line(
+                "2022-05-01T12:34:56,123456+00:00 oom-kill:constraint=CONSTRAINT_NONE"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_journal_line() {
+        assert_eq!(
+            parse_journal_line(
+                "2022-05-01T12:34:56+0000 host kernel: CPU3: Core temperature above threshold, cpu clock throttled"
+            ),
+            Some(KernelEventHarvest {
+                time: "2022-05-01 12:34:56".to_string(),
+                event_type: KernelEventType::Thermal,
+                message: "CPU3: Core temperature above threshold, cpu clock throttled".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_journal_line(
+                "2022-05-01T12:34:56+0000 host kernel: blk_update_request: I/O error, dev sda, sector 2048"
+            )
+            .map(|event| event.event_type),
+            Some(KernelEventType::DiskError)
+        );
+        assert_eq!(
+            parse_journal_line("2022-05-01T12:34:56+0000 host kernel: usb 1-1: new device"),
+            None
+        );
+    }
+}
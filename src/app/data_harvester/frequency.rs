@@ -0,0 +1,198 @@
+//! Data collection for CPU clock speeds and C-state residency.
+//!
+//! On Linux, clock speeds are read from `cpufreq` in sysfs, falling back to `/proc/cpuinfo`, and
+//! C-state residency from `cpuidle`. Other platforms get clock speeds through sysinfo, and don't
+//! report C-states.
+
+#[cfg(not(target_os = "linux"))]
+use sysinfo::{ProcessorExt, System, SystemExt};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct FrequencyHarvest {
+    /// The clock speed of each CPU, in MHz.
+    pub frequencies: Vec<f64>,
+    /// The highest clock speed any CPU can reach, including turbo, in MHz, if known.
+    pub max_frequency: Option<f64>,
+    /// The share of time spent in each C-state since the last harvest, averaged over all CPUs, as
+    /// percentages. This is empty on the first harvest, and on platforms that don't report it.
+    pub c_states: Vec<(String, f64)>,
+}
+
+/// The time spent in each C-state, summed over all CPUs, in microseconds.
+pub type CStateTimes = Vec<(String, u64)>;
+
+#[cfg(target_os = "linux")]
+pub fn get_frequency_data(
+    prev_c_state_times: &mut CStateTimes, elapsed_secs: f64,
+) -> Option<FrequencyHarvest> {
+    use std::fs::read_to_string;
+
+    let cpu_dirs = read_cpu_dirs();
+
+    let mut frequencies = cpu_dirs
+        .iter()
+        .filter_map(|dir| read_khz(&dir.join("cpufreq/scaling_cur_freq")))
+        .collect::<Vec<_>>();
+    if frequencies.is_empty() {
+        frequencies = read_to_string("/proc/cpuinfo")
+            .map(|cpuinfo| parse_cpuinfo_frequencies(&cpuinfo))
+            .unwrap_or_default();
+    }
+    if frequencies.is_empty() {
+        return None;
+    }
+
+    let max_frequency = cpu_dirs
+        .iter()
+        .filter_map(|dir| read_khz(&dir.join("cpufreq/cpuinfo_max_freq")))
+        .reduce(f64::max);
+
+    // Each CPU lists the total time it's spent in each of its states, so sum them up by name.
+    let mut c_state_times: CStateTimes = Vec::new();
+    let mut num_cpus_with_c_states = 0;
+    for dir in &cpu_dirs {
+        let states = std::fs::read_dir(dir.join("cpuidle"))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|entry| entry.file_name().to_string_lossy().starts_with("state"))
+                    .filter_map(|entry| {
+                        let path = entry.path();
+                        let name = read_to_string(path.join("name")).ok()?;
+                        let time = read_to_string(path.join("time")).ok()?;
+                        Some((name.trim().to_string(), time.trim().parse::<u64>().ok()?))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if states.is_empty() {
+            continue;
+        }
+
+        num_cpus_with_c_states += 1;
+        for (name, time) in states {
+            match c_state_times.iter_mut().find(|(other, _)| *other == name) {
+                Some((_, total_time)) => *total_time += time,
+                None => c_state_times.push((name, time)),
+            }
+        }
+    }
+    c_state_times.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let elapsed_micros = elapsed_secs * 1_000_000.0 * num_cpus_with_c_states as f64;
+    let c_states = if elapsed_micros > 0.0 {
+        c_state_times
+            .iter()
+            .filter_map(|(name, time)| {
+                let (_, prev_time) = prev_c_state_times
+                    .iter()
+                    .find(|(prev_name, _)| prev_name == name)?;
+                let residency = time.saturating_sub(*prev_time) as f64 / elapsed_micros * 100.0;
+                Some((name.clone(), residency.min(100.0)))
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    *prev_c_state_times = c_state_times;
+
+    Some(FrequencyHarvest {
+        frequencies,
+        max_frequency,
+        c_states,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_frequency_data(sys: &System) -> Option<FrequencyHarvest> {
+    let frequencies = sys
+        .processors()
+        .iter()
+        .map(|processor| processor.frequency() as f64)
+        .filter(|frequency| *frequency > 0.0)
+        .collect::<Vec<_>>();
+
+    if frequencies.is_empty() {
+        None
+    } else {
+        Some(FrequencyHarvest {
+            frequencies,
+            max_frequency: None,
+            c_states: Vec::new(),
+        })
+    }
+}
+
+/// Returns the sysfs directory of each CPU, in order of their numbers.
+#[cfg(target_os = "linux")]
+fn read_cpu_dirs() -> Vec<std::path::PathBuf> {
+    let mut cpu_dirs = std::fs::read_dir("/sys/devices/system/cpu")
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let cpu = entry
+                        .file_name()
+                        .to_str()?
+                        .strip_prefix("cpu")?
+                        .parse::<usize>()
+                        .ok()?;
+                    Some((cpu, entry.path()))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    cpu_dirs.sort_by_key(|(cpu, _path)| *cpu);
+
+    cpu_dirs.into_iter().map(|(_cpu, path)| path).collect()
+}
+
+/// Reads a frequency in kHz from a sysfs file, returning it in MHz.
+#[cfg(target_os = "linux")]
+fn read_khz(path: &std::path::Path) -> Option<f64> {
+    let khz = std::fs::read_to_string(path)
+        .ok()?
+        .trim()
+        .parse::<f64>()
+        .ok()?;
+    Some(khz / 1000.0)
+}
+
+/// Parses the `cpu MHz` line of each CPU in `/proc/cpuinfo`.
+#[cfg(target_os = "linux")]
+fn parse_cpuinfo_frequencies(cpuinfo: &str) -> Vec<f64> {
+    cpuinfo
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            if key.trim() == "cpu MHz" {
+                value.trim().parse::<f64>().ok()
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpuinfo_frequencies() {
+        let cpuinfo = "\
+processor\t: 0
+model name\t: Some CPU
+cpu MHz\t\t: 2400.000
+
+processor\t: 1
+model name\t: Some CPU
+cpu MHz\t\t: 3612.512
+";
+        assert_eq!(parse_cpuinfo_frequencies(cpuinfo), vec![2400.0, 3612.512]);
+        assert!(parse_cpuinfo_frequencies("").is_empty());
+    }
+}
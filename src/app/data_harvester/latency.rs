@@ -0,0 +1,207 @@
+//! Data collection for the latency of user-configured hosts.
+//!
+//! Hosts with a port are timed by opening a TCP connection to them, and hosts without one are
+//! pinged with the system's `ping` command, as sending ICMP packets directly usually requires
+//! root. Each check runs on its own thread so a slow host doesn't hold up the rest of the
+//! harvest; results are picked up by the next harvest after the check finishes.
+
+use std::{
+    net::{TcpStream, ToSocketAddrs},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// How long to wait for a reply before a host is considered unreachable.
+pub const LATENCY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A host to check the latency of, as set in the config file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LatencyHost {
+    pub host: String,
+    /// If set, the time taken to open a TCP connection to this port is used instead of a ping.
+    pub port: Option<u16>,
+    /// The name shown in the legend. Defaults to the host.
+    pub name: Option<String>,
+}
+
+impl LatencyHost {
+    pub fn get_name(&self) -> String {
+        match (&self.name, self.port) {
+            (Some(name), _) => name.clone(),
+            (None, Some(port)) => format!("{}:{}", self.host, port),
+            (None, None) => self.host.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct LatencyHarvest {
+    pub name: String,
+    /// The round-trip time of the last check in milliseconds, or `None` if the host didn't
+    /// reply in time or couldn't be reached.
+    pub rtt_ms: Option<f64>,
+    /// Whether the first check of the host hasn't finished yet.
+    pub is_pending: bool,
+}
+
+#[derive(Debug, Default)]
+struct HostState {
+    in_flight: Option<Receiver<Option<f64>>>,
+    last_rtt_ms: Option<Option<f64>>,
+}
+
+/// Keeps track of the checks in flight for each configured host.
+#[derive(Debug, Default)]
+pub struct LatencyChecker {
+    hosts: Vec<LatencyHost>,
+    states: Vec<HostState>,
+}
+
+impl LatencyChecker {
+    pub fn set_hosts(&mut self, hosts: Vec<LatencyHost>) {
+        if hosts != self.hosts {
+            self.states = hosts.iter().map(|_| HostState::default()).collect();
+            self.hosts = hosts;
+        }
+    }
+
+    /// Collects the results of any finished checks and starts new checks for hosts that don't
+    /// have one in flight. Returns `None` if no hosts are configured.
+    pub fn get_latency_data(&mut self) -> Option<Vec<LatencyHarvest>> {
+        if self.hosts.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.hosts
+                .iter()
+                .zip(self.states.iter_mut())
+                .map(|(host, state)| {
+                    if let Some(receiver) = &state.in_flight {
+                        match receiver.try_recv() {
+                            Ok(rtt_ms) => {
+                                state.last_rtt_ms = Some(rtt_ms);
+                                state.in_flight = None;
+                            }
+                            Err(TryRecvError::Disconnected) => {
+                                state.last_rtt_ms = Some(None);
+                                state.in_flight = None;
+                            }
+                            Err(TryRecvError::Empty) => {}
+                        }
+                    }
+
+                    if state.in_flight.is_none() {
+                        let (sender, receiver) = mpsc::channel();
+                        let host = host.clone();
+                        std::thread::spawn(move || {
+                            let rtt_ms = match host.port {
+                                Some(port) => get_tcp_connect_time(&host.host, port),
+                                None => get_ping_time(&host.host),
+                            };
+                            let _ = sender.send(rtt_ms);
+                        });
+                        state.in_flight = Some(receiver);
+                    }
+
+                    LatencyHarvest {
+                        name: host.get_name(),
+                        rtt_ms: state.last_rtt_ms.flatten(),
+                        is_pending: state.last_rtt_ms.is_none(),
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Returns how long it took to open a TCP connection to the host in milliseconds.
+fn get_tcp_connect_time(host: &str, port: u16) -> Option<f64> {
+    let address = (host, port).to_socket_addrs().ok()?.next()?;
+    let start = Instant::now();
+    TcpStream::connect_timeout(&address, LATENCY_TIMEOUT).ok()?;
+
+    Some(start.elapsed().as_secs_f64() * 1000.0)
+}
+
+/// Returns the round-trip time reported by pinging the host once in milliseconds.
+fn get_ping_time(host: &str) -> Option<f64> {
+    let mut command = std::process::Command::new("ping");
+    if cfg!(target_os = "windows") {
+        command.args(["-n", "1", "-w", &LATENCY_TIMEOUT.as_millis().to_string()]);
+    } else if cfg!(any(target_os = "linux", target_os = "android")) {
+        command.args(["-c", "1", "-W", &LATENCY_TIMEOUT.as_secs().to_string()]);
+    } else {
+        command.args(["-c", "1", "-t", &LATENCY_TIMEOUT.as_secs().to_string()]);
+    }
+
+    let output = command.arg(host).output().ok()?;
+    if output.status.success() {
+        parse_ping_time(&String::from_utf8_lossy(&output.stdout))
+    } else {
+        None
+    }
+}
+
+/// Returns the round-trip time from the output of `ping`, which contains a line like
+/// `64 bytes from 1.1.1.1: icmp_seq=1 ttl=57 time=12.3 ms` on Unix, or
+/// `Reply from 1.1.1.1: bytes=32 time=12ms TTL=57` on Windows, which reports times under a
+/// millisecond as `time<1ms`.
+fn parse_ping_time(output: &str) -> Option<f64> {
+    output.lines().find_map(|line| {
+        let index = line.find("time=").or_else(|| line.find("time<"))?;
+        let time = &line[index + "time=".len()..];
+        let end = time
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(time.len());
+        time[..end].parse().ok()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_ping_time() {
+        let linux_output = "\
+PING 1.1.1.1 (1.1.1.1) 56(84) bytes of data.
+64 bytes from 1.1.1.1: icmp_seq=1 ttl=57 time=12.3 ms
+
+--- 1.1.1.1 ping statistics ---
+1 packets transmitted, 1 received, 0% packet loss, time 0ms
+rtt min/avg/max/mdev = 12.345/12.345/12.345/0.000 ms
+";
+        assert_eq!(parse_ping_time(linux_output), Some(12.3));
+
+        let windows_output = "
+Pinging 1.1.1.1 with 32 bytes of data:
+Reply from 1.1.1.1: bytes=32 time=15ms TTL=57
+";
+        assert_eq!(parse_ping_time(windows_output), Some(15.0));
+        assert_eq!(
+            parse_ping_time("Reply from 192.168.1.1: bytes=32 time<1ms TTL=64"),
+            Some(1.0)
+        );
+        assert_eq!(
+            parse_ping_time("1 packets transmitted, 0 received, 100% packet loss, time 0ms"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_latency_host_name() {
+        let mut host = LatencyHost {
+            host: "example.com".to_string(),
+            port: None,
+            name: None,
+        };
+        assert_eq!(host.get_name(), "example.com");
+        host.port = Some(443);
+        assert_eq!(host.get_name(), "example.com:443");
+        host.name = Some("Example".to_string());
+        assert_eq!(host.get_name(), "Example");
+    }
+}
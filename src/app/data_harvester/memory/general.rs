@@ -8,9 +8,79 @@ cfg_if::cfg_if! {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct MemHarvest {
     pub mem_total_in_kib: u64,
     pub mem_used_in_kib: u64,
     pub use_percent: Option<f64>,
 }
+
+/// The total number of pages swapped in from and out to disk since boot.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SwapActivityHarvest {
+    pub pages_in: u64,
+    pub pages_out: u64,
+}
+
+/// Returns how many pages have been swapped in and out, which is only available on Linux, through
+/// `/proc/vmstat`.
+#[cfg(target_os = "linux")]
+pub fn get_swap_activity() -> Option<SwapActivityHarvest> {
+    std::fs::read_to_string("/proc/vmstat")
+        .ok()
+        .and_then(|contents| parse_vmstat(contents.lines()))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_swap_activity() -> Option<SwapActivityHarvest> {
+    None
+}
+
+/// Parses the swap counters out of `/proc/vmstat`, which has one counter per line, like:
+///
+/// ```text
+/// pswpin 1234
+/// pswpout 5678
+/// ```
+pub fn parse_vmstat<'a>(lines: impl IntoIterator<Item = &'a str>) -> Option<SwapActivityHarvest> {
+    let mut pages_in = None;
+    let mut pages_out = None;
+    for line in lines {
+        match line.split_once(' ') {
+            Some(("pswpin", value)) => pages_in = value.trim().parse().ok(),
+            Some(("pswpout", value)) => pages_out = value.trim().parse().ok(),
+            _ => {}
+        }
+    }
+
+    Some(SwapActivityHarvest {
+        pages_in: pages_in?,
+        pages_out: pages_out?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_vmstat() {
+        let contents = "\
+nr_free_pages 123456
+pswpin 42
+pswpout 1337
+pgfault 99999
+";
+        assert_eq!(
+            parse_vmstat(contents.lines()),
+            Some(SwapActivityHarvest {
+                pages_in: 42,
+                pages_out: 1337,
+            })
+        );
+
+        assert_eq!(parse_vmstat(["nr_free_pages 123456"]), None);
+    }
+}
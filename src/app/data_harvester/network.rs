@@ -3,6 +3,9 @@
 //! For Linux and macOS, this is handled by Heim.
 //! For Windows, this is handled by sysinfo.
 
+use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
 cfg_if::cfg_if! {
     if #[cfg(any(target_os = "linux", target_os = "macos"))] {
         pub mod heim;
@@ -13,18 +16,169 @@ cfg_if::cfg_if! {
     }
 }
 
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
 /// All units in bits.
 pub struct NetworkHarvest {
     pub rx: u64,
     pub tx: u64,
     pub total_rx: u64,
     pub total_tx: u64,
+    /// Receive and transmit errors per second, across all interfaces.
+    pub errors_per_sec: f64,
+    /// Dropped packets per second, across all interfaces.
+    pub drops_per_sec: f64,
+    pub total_errors: u64,
+    pub total_drops: u64,
+    /// The interfaces that had errors or dropped packets since the last harvest.
+    pub lossy_interfaces: Vec<InterfaceErrorHarvest>,
 }
 
 impl NetworkHarvest {
     pub fn first_run_cleanup(&mut self) {
         self.rx = 0;
         self.tx = 0;
+        self.errors_per_sec = 0.0;
+        self.drops_per_sec = 0.0;
+        self.lossy_interfaces.clear();
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct InterfaceErrorHarvest {
+    pub name: String,
+    pub errors_per_sec: f64,
+    pub drops_per_sec: f64,
+}
+
+/// The cumulative error and drop counters of a network interface. Drops on transmit are only
+/// reported on Linux, and drops are not reported at all on Windows and FreeBSD.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NetworkErrorCounters {
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_drops: u64,
+    pub tx_drops: u64,
+}
+
+impl NetworkErrorCounters {
+    fn errors(&self) -> u64 {
+        self.rx_errors + self.tx_errors
+    }
+
+    fn drops(&self) -> u64 {
+        self.rx_drops + self.tx_drops
+    }
+}
+
+/// Whether an interface should be kept according to the network filter.
+pub fn is_interface_kept(interface: &str, filter: &Option<crate::app::Filter>) -> bool {
+    if let Some(filter) = filter {
+        let is_matched = filter.list.iter().any(|r| r.is_match(interface));
+        is_matched != filter.is_list_ignored
+    } else {
+        true
+    }
+}
+
+/// The error and drop counts and rates calculated from the counters of each interface.
+pub struct NetworkErrorData {
+    pub errors_per_sec: f64,
+    pub drops_per_sec: f64,
+    pub total_errors: u64,
+    pub total_drops: u64,
+    pub lossy_interfaces: Vec<InterfaceErrorHarvest>,
+}
+
+/// Calculates the error and drop rates of each interface from their counters, and replaces
+/// `prev_counters` with the new counters for the next harvest. Interfaces that weren't seen in the
+/// last harvest are treated as having no errors or drops yet.
+pub fn get_error_data(
+    counters: FxHashMap<String, NetworkErrorCounters>,
+    prev_counters: &mut FxHashMap<String, NetworkErrorCounters>, elapsed_secs: f64,
+) -> NetworkErrorData {
+    let get_rate = |curr: u64, prev: u64| {
+        if elapsed_secs > 0.0 {
+            curr.saturating_sub(prev) as f64 / elapsed_secs
+        } else {
+            0.0
+        }
+    };
+
+    let mut data = NetworkErrorData {
+        errors_per_sec: 0.0,
+        drops_per_sec: 0.0,
+        total_errors: 0,
+        total_drops: 0,
+        lossy_interfaces: Vec::new(),
+    };
+
+    for (name, counter) in &counters {
+        data.total_errors += counter.errors();
+        data.total_drops += counter.drops();
+
+        if let Some(prev) = prev_counters.get(name) {
+            let errors_per_sec = get_rate(counter.errors(), prev.errors());
+            let drops_per_sec = get_rate(counter.drops(), prev.drops());
+            data.errors_per_sec += errors_per_sec;
+            data.drops_per_sec += drops_per_sec;
+
+            if errors_per_sec > 0.0 || drops_per_sec > 0.0 {
+                data.lossy_interfaces.push(InterfaceErrorHarvest {
+                    name: name.clone(),
+                    errors_per_sec,
+                    drops_per_sec,
+                });
+            }
+        }
+    }
+
+    data.lossy_interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    #[cfg(feature = "log")]
+    for name in prev_counters.keys() {
+        if !counters.contains_key(name) {
+            info!("Network interface {} disappeared", name);
+        }
+    }
+    *prev_counters = counters;
+
+    data
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_error_data() {
+        let counters = |rx_errors, rx_drops| NetworkErrorCounters {
+            rx_errors,
+            tx_errors: 0,
+            rx_drops,
+            tx_drops: 1,
+        };
+
+        let mut prev_counters = FxHashMap::default();
+        prev_counters.insert("eth0".to_string(), counters(10, 0));
+        prev_counters.insert("lo".to_string(), counters(0, 0));
+
+        let mut curr_counters = FxHashMap::default();
+        curr_counters.insert("eth0".to_string(), counters(14, 2));
+        curr_counters.insert("lo".to_string(), counters(0, 0));
+        curr_counters.insert("wlan0".to_string(), counters(5, 5));
+
+        let data = get_error_data(curr_counters.clone(), &mut prev_counters, 2.0);
+        assert_eq!(data.errors_per_sec, 2.0);
+        assert_eq!(data.drops_per_sec, 1.0);
+        assert_eq!(data.total_errors, 19);
+        assert_eq!(data.total_drops, 10);
+        assert_eq!(
+            data.lossy_interfaces,
+            vec![InterfaceErrorHarvest {
+                name: "eth0".to_string(),
+                errors_per_sec: 2.0,
+                drops_per_sec: 1.0,
+            }]
+        );
+        assert_eq!(prev_counters, curr_counters);
     }
 }
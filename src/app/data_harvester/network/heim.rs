@@ -1,14 +1,19 @@
 //! Gets network data via heim.
 
-use super::NetworkHarvest;
+use super::{get_error_data, NetworkErrorCounters, NetworkHarvest};
+use fxhash::FxHashMap;
 use std::time::Instant;
 
 // TODO: Eventually make it so that this thing also takes individual usage into account, so we can show per-interface!
+#[allow(clippy::too_many_arguments)]
 pub async fn get_network_data(
     prev_net_access_time: Instant, prev_net_rx: &mut u64, prev_net_tx: &mut u64,
-    curr_time: Instant, actually_get: bool, filter: &Option<crate::app::Filter>,
+    prev_error_counters: &mut FxHashMap<String, NetworkErrorCounters>, curr_time: Instant,
+    actually_get: bool, filter: &Option<crate::app::Filter>, query: Option<&regex::Regex>,
 ) -> crate::utils::error::Result<Option<NetworkHarvest>> {
     use futures::StreamExt;
+    #[cfg(target_os = "linux")]
+    use heim::net::os::linux::IoCountersExt;
 
     if !actually_get {
         return Ok(None);
@@ -18,6 +23,7 @@ pub async fn get_network_data(
     futures::pin_mut!(io_data);
     let mut total_rx: u64 = 0;
     let mut total_tx: u64 = 0;
+    let mut error_counters = FxHashMap::default();
 
     while let Some(io) = io_data.next().await {
         if let Ok(io) = io {
@@ -38,13 +44,26 @@ pub async fn get_network_data(
                 true
             };
 
-            if to_keep {
+            if to_keep && query.iter().all(|query| query.is_match(io.interface())) {
                 // TODO: Use bytes as the default instead, perhaps?
                 // Since you might have to do a double conversion (bytes -> bits -> bytes) in some cases;
                 // but if you stick to bytes, then in the bytes, case, you do no conversion, and in the bits case,
                 // you only do one conversion...
                 total_rx += io.bytes_recv().get::<heim::units::information::bit>();
                 total_tx += io.bytes_sent().get::<heim::units::information::bit>();
+
+                error_counters.insert(
+                    io.interface().to_string(),
+                    NetworkErrorCounters {
+                        rx_errors: io.errors_recv(),
+                        tx_errors: io.errors_sent(),
+                        rx_drops: io.drop_recv(),
+                        #[cfg(target_os = "linux")]
+                        tx_drops: io.drop_sent(),
+                        #[cfg(not(target_os = "linux"))]
+                        tx_drops: 0,
+                    },
+                );
             }
         }
     }
@@ -60,6 +79,8 @@ pub async fn get_network_data(
         )
     };
 
+    let error_data = get_error_data(error_counters, prev_error_counters, elapsed_time);
+
     *prev_net_rx = total_rx;
     *prev_net_tx = total_tx;
     Ok(Some(NetworkHarvest {
@@ -67,5 +88,10 @@ pub async fn get_network_data(
         tx,
         total_rx,
         total_tx,
+        errors_per_sec: error_data.errors_per_sec,
+        drops_per_sec: error_data.drops_per_sec,
+        total_errors: error_data.total_errors,
+        total_drops: error_data.total_drops,
+        lossy_interfaces: error_data.lossy_interfaces,
     }))
 }
@@ -1,12 +1,15 @@
 //! Gets network data via sysinfo.
 
-use super::NetworkHarvest;
+use super::{get_error_data, NetworkErrorCounters, NetworkHarvest};
+use fxhash::FxHashMap;
 use std::time::Instant;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn get_network_data(
     sys: &sysinfo::System, prev_net_access_time: Instant, prev_net_rx: &mut u64,
-    prev_net_tx: &mut u64, curr_time: Instant, actually_get: bool,
-    filter: &Option<crate::app::Filter>,
+    prev_net_tx: &mut u64, prev_error_counters: &mut FxHashMap<String, NetworkErrorCounters>,
+    curr_time: Instant, actually_get: bool, filter: &Option<crate::app::Filter>,
+    query: Option<&regex::Regex>,
 ) -> crate::utils::error::Result<Option<NetworkHarvest>> {
     use sysinfo::{NetworkExt, SystemExt};
 
@@ -16,6 +19,7 @@ pub async fn get_network_data(
 
     let mut total_rx: u64 = 0;
     let mut total_tx: u64 = 0;
+    let mut error_counters = FxHashMap::default();
 
     let networks = sys.networks();
     for (name, network) in networks {
@@ -32,9 +36,20 @@ pub async fn get_network_data(
             true
         };
 
-        if to_keep {
+        if to_keep && query.iter().all(|query| query.is_match(name)) {
             total_rx += network.total_received() * 8;
             total_tx += network.total_transmitted() * 8;
+
+            // sysinfo doesn't report dropped packets.
+            error_counters.insert(
+                name.to_string(),
+                NetworkErrorCounters {
+                    rx_errors: network.total_errors_on_received(),
+                    tx_errors: network.total_errors_on_transmitted(),
+                    rx_drops: 0,
+                    tx_drops: 0,
+                },
+            );
         }
     }
 
@@ -49,6 +64,8 @@ pub async fn get_network_data(
         )
     };
 
+    let error_data = get_error_data(error_counters, prev_error_counters, elapsed_time);
+
     *prev_net_rx = total_rx;
     *prev_net_tx = total_tx;
     Ok(Some(NetworkHarvest {
@@ -56,5 +73,10 @@ pub async fn get_network_data(
         tx,
         total_rx,
         total_tx,
+        errors_per_sec: error_data.errors_per_sec,
+        drops_per_sec: error_data.drops_per_sec,
+        total_errors: error_data.total_errors,
+        total_drops: error_data.total_drops,
+        lossy_interfaces: error_data.lossy_interfaces,
     }))
 }
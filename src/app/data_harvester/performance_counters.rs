@@ -0,0 +1,345 @@
+//! Disk activity and per-process GPU usage on Windows, read from the same performance counters
+//! that Task Manager uses through PDH. Counters are added by their English names, so they're
+//! found on systems in any language.
+
+use std::{convert::TryFrom, ptr, slice};
+
+use fxhash::FxHashMap;
+use winapi::{
+    shared::{minwindef::DWORD, winerror::ERROR_SUCCESS},
+    um::pdh::{
+        PdhAddEnglishCounterW, PdhCloseQuery, PdhCollectQueryData, PdhGetFormattedCounterArrayW,
+        PdhOpenQueryW, PDH_FMT_COUNTERVALUE_ITEM_W, PDH_FMT_DOUBLE, PDH_FMT_NOCAP100, PDH_HCOUNTER,
+        PDH_HQUERY, PDH_STATUS,
+    },
+};
+
+use super::{disks::DiskActivity, processes::ProcessHarvest};
+
+/// Returned when the buffer passed is too small, from `pdhmsg.h`.
+const PDH_MORE_DATA: PDH_STATUS = 0x8000_07D2_u32 as PDH_STATUS;
+/// The statuses of a counter value that can be used, from `pdhmsg.h`.
+const PDH_CSTATUS_VALID_DATA: DWORD = 0x0000_0000;
+const PDH_CSTATUS_NEW_DATA: DWORD = 0x0000_0001;
+
+const DISK_IDLE_TIME: &str = r"\PhysicalDisk(*)\% Idle Time";
+const DISK_QUEUE_LENGTH: &str = r"\PhysicalDisk(*)\Avg. Disk Queue Length";
+const GPU_ENGINE_UTILIZATION: &str = r"\GPU Engine(*)\Utilization Percentage";
+const GPU_DEDICATED_MEMORY: &str = r"\GPU Process Memory(*)\Dedicated Usage";
+
+/// An open PDH query, and the counters in it.
+#[derive(Debug)]
+struct Query {
+    handle: PDH_HQUERY,
+    disk_idle_time: Option<PDH_HCOUNTER>,
+    disk_queue_length: Option<PDH_HCOUNTER>,
+    gpu_engine_utilization: Option<PDH_HCOUNTER>,
+    gpu_dedicated_memory: Option<PDH_HCOUNTER>,
+}
+
+impl Query {
+    fn open(read_disks: bool, read_gpu: bool) -> Option<Self> {
+        let mut handle = ptr::null_mut();
+        // SAFETY: the handle is only used if the query was opened.
+        if unsafe { PdhOpenQueryW(ptr::null(), 0, &mut handle) } != ERROR_SUCCESS as PDH_STATUS {
+            return None;
+        }
+
+        let mut query = Query {
+            handle,
+            disk_idle_time: None,
+            disk_queue_length: None,
+            gpu_engine_utilization: None,
+            gpu_dedicated_memory: None,
+        };
+        if read_disks {
+            query.disk_idle_time = query.add_counter(DISK_IDLE_TIME);
+            query.disk_queue_length = query.add_counter(DISK_QUEUE_LENGTH);
+        }
+        if read_gpu {
+            query.gpu_engine_utilization = query.add_counter(GPU_ENGINE_UTILIZATION);
+            query.gpu_dedicated_memory = query.add_counter(GPU_DEDICATED_MEMORY);
+        }
+
+        Some(query)
+    }
+
+    /// Adds a counter, or returns [`None`] if it doesn't exist, like the GPU counters on older
+    /// versions of Windows.
+    fn add_counter(&self, path: &str) -> Option<PDH_HCOUNTER> {
+        let path = path.encode_utf16().chain(Some(0)).collect::<Vec<_>>();
+        let mut counter = ptr::null_mut();
+        // SAFETY: the path is null-terminated, and the counter is only used if it was added.
+        let status = unsafe { PdhAddEnglishCounterW(self.handle, path.as_ptr(), 0, &mut counter) };
+
+        if status == ERROR_SUCCESS as PDH_STATUS {
+            Some(counter)
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for Query {
+    fn drop(&mut self) {
+        // SAFETY: the query was opened successfully, and closing it also removes its counters.
+        unsafe { PdhCloseQuery(self.handle) };
+    }
+}
+
+/// Keeps the PDH query open between harvests, as counters like disk time are rates worked out
+/// from the difference between two reads.
+#[derive(Debug, Default)]
+pub struct PerformanceCounters {
+    query: Option<Query>,
+    /// Whether opening the query has been tried, so it isn't retried on every harvest if it fails.
+    has_tried_init: bool,
+    read_disks: bool,
+    read_gpu: bool,
+}
+
+impl PerformanceCounters {
+    /// Sets which counters are read. The query is opened again with the new counters on the next
+    /// read if they changed.
+    pub fn set_counters(&mut self, read_disks: bool, read_gpu: bool) {
+        if read_disks != self.read_disks || read_gpu != self.read_gpu {
+            *self = PerformanceCounters {
+                read_disks,
+                read_gpu,
+                ..PerformanceCounters::default()
+            };
+        }
+    }
+
+    /// Reads new values of every counter. Returns whether there are values to get, which isn't
+    /// the case on the first read, as it only sets the starting point of the rates.
+    pub fn collect(&mut self) -> bool {
+        if !self.read_disks && !self.read_gpu {
+            return false;
+        }
+
+        let is_first_read = !self.has_tried_init;
+        if is_first_read {
+            self.has_tried_init = true;
+            self.query = Query::open(self.read_disks, self.read_gpu);
+        }
+
+        match &self.query {
+            Some(query) => {
+                // SAFETY: the query is open.
+                let status = unsafe { PdhCollectQueryData(query.handle) };
+                status == ERROR_SUCCESS as PDH_STATUS && !is_first_read
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the activity of each physical disk, keyed by each drive on it, like `C:`.
+    pub fn get_disk_activity(&self) -> FxHashMap<String, DiskActivity> {
+        let mut disk_activity: FxHashMap<String, DiskActivity> = FxHashMap::default();
+        let query = match &self.query {
+            Some(query) => query,
+            None => return disk_activity,
+        };
+
+        if let Some(counter) = query.disk_idle_time {
+            for (instance, idle_percent) in get_counter_values(counter, PDH_FMT_DOUBLE) {
+                for drive in get_disk_drives(&instance) {
+                    disk_activity
+                        .entry(drive.to_string())
+                        .or_default()
+                        .active_percent = (100.0 - idle_percent).clamp(0.0, 100.0);
+                }
+            }
+        }
+        if let Some(counter) = query.disk_queue_length {
+            for (instance, queue_length) in
+                get_counter_values(counter, PDH_FMT_DOUBLE | PDH_FMT_NOCAP100)
+            {
+                for drive in get_disk_drives(&instance) {
+                    disk_activity
+                        .entry(drive.to_string())
+                        .or_default()
+                        .queue_length = queue_length;
+                }
+            }
+        }
+
+        disk_activity
+    }
+
+    /// Adds the GPU utilization and dedicated GPU memory of each process. Like in Task Manager,
+    /// the utilization is that of the busiest type of engine the process uses, like 3D or video
+    /// decoding, on any GPU.
+    pub fn add_gpu_data(&self, processes: &mut [ProcessHarvest]) {
+        let query = match &self.query {
+            Some(query) => query,
+            None => return,
+        };
+
+        let engine_values = query
+            .gpu_engine_utilization
+            .map(|counter| get_counter_values(counter, PDH_FMT_DOUBLE | PDH_FMT_NOCAP100))
+            .unwrap_or_default();
+
+        // The utilization of each type of engine on each GPU, for each PID.
+        let mut engine_usage: FxHashMap<(u32, &str, &str), f64> = FxHashMap::default();
+        for (instance, utilization) in &engine_values {
+            if let Some(engine) = parse_gpu_engine_instance(instance) {
+                *engine_usage.entry(engine).or_default() += utilization;
+            }
+        }
+
+        // The utilization and memory of each PID.
+        let mut gpu_usage: FxHashMap<u32, (f64, u64)> = FxHashMap::default();
+        for ((pid, ..), utilization) in engine_usage {
+            let usage = &mut gpu_usage.entry(pid).or_default().0;
+            *usage = usage.max(utilization.min(100.0));
+        }
+        if let Some(counter) = query.gpu_dedicated_memory {
+            for (instance, bytes) in get_counter_values(counter, PDH_FMT_DOUBLE | PDH_FMT_NOCAP100)
+            {
+                if let Some(pid) = parse_gpu_pid(&instance) {
+                    gpu_usage.entry(pid).or_default().1 += bytes as u64;
+                }
+            }
+        }
+
+        for process in processes {
+            if let Some((gpu_usage_percent, gpu_mem_bytes)) = u32::try_from(process.pid)
+                .ok()
+                .and_then(|pid| gpu_usage.get(&pid))
+            {
+                process.gpu_usage_percent = *gpu_usage_percent;
+                process.gpu_mem_bytes = *gpu_mem_bytes;
+            }
+        }
+    }
+}
+
+/// Returns the value of each instance of a counter, like each disk, along with its name. Values
+/// that aren't valid, like rates on the first read, are left out.
+fn get_counter_values(counter: PDH_HCOUNTER, format: DWORD) -> Vec<(String, f64)> {
+    // The first call fails, but gives the size needed for the values and their names.
+    let mut buffer_size: DWORD = 0;
+    let mut item_count: DWORD = 0;
+    // SAFETY: no buffer is passed, so nothing is written but the size and count.
+    let status = unsafe {
+        PdhGetFormattedCounterArrayW(
+            counter,
+            format,
+            &mut buffer_size,
+            &mut item_count,
+            ptr::null_mut(),
+        )
+    };
+    if status != PDH_MORE_DATA || buffer_size == 0 {
+        return Vec::new();
+    }
+
+    // The buffer is made of u64s so the items at its start are aligned.
+    let mut buffer = vec![0_u64; (buffer_size as usize + 7) / 8];
+    // SAFETY: the buffer holds at least `buffer_size` bytes.
+    let status = unsafe {
+        PdhGetFormattedCounterArrayW(
+            counter,
+            format,
+            &mut buffer_size,
+            &mut item_count,
+            buffer.as_mut_ptr().cast(),
+        )
+    };
+    if status != ERROR_SUCCESS as PDH_STATUS {
+        return Vec::new();
+    }
+
+    // SAFETY: the buffer now starts with `item_count` items, whose names point into the buffer.
+    let items = unsafe {
+        slice::from_raw_parts(
+            buffer.as_ptr().cast::<PDH_FMT_COUNTERVALUE_ITEM_W>(),
+            item_count as usize,
+        )
+    };
+    items
+        .iter()
+        .filter(|item| {
+            matches!(
+                item.FmtValue.CStatus,
+                PDH_CSTATUS_VALID_DATA | PDH_CSTATUS_NEW_DATA
+            )
+        })
+        .map(|item| {
+            // SAFETY: the name is null-terminated, and the value was formatted as a double.
+            let name = unsafe {
+                let length = (0..).take_while(|&i| *item.szName.add(i) != 0).count();
+                String::from_utf16_lossy(slice::from_raw_parts(item.szName, length))
+            };
+            let value = unsafe { *item.FmtValue.u.doubleValue() };
+
+            (name, value)
+        })
+        .collect()
+}
+
+/// Returns the drives on a physical disk from its instance name, like `C:` and `D:` from
+/// `1 C: D:`. The `_Total` instance has none.
+fn get_disk_drives(instance: &str) -> impl Iterator<Item = &str> {
+    instance
+        .split_whitespace()
+        .skip(1)
+        .filter(|part| part.len() == 2 && part.ends_with(':'))
+}
+
+/// Returns the PID from the instance name of a GPU counter, like
+/// `pid_1234_luid_0x00000000_0x0000D1A3_phys_0_eng_0_engtype_3D`.
+fn parse_gpu_pid(instance: &str) -> Option<u32> {
+    instance
+        .strip_prefix("pid_")?
+        .split('_')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Returns the PID, GPU, and type of engine from the instance name of a GPU engine.
+fn parse_gpu_engine_instance(instance: &str) -> Option<(u32, &str, &str)> {
+    let pid = parse_gpu_pid(instance)?;
+    let (engine, engine_type) = instance.split_once("_engtype_")?;
+    let (gpu, _) = engine.split_once("_eng_")?;
+
+    Some((pid, gpu, engine_type))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_disk_drives() {
+        assert_eq!(get_disk_drives("0 C:").collect::<Vec<_>>(), vec!["C:"]);
+        assert_eq!(
+            get_disk_drives("1 D: E:").collect::<Vec<_>>(),
+            vec!["D:", "E:"]
+        );
+        assert_eq!(get_disk_drives("_Total").count(), 0);
+    }
+
+    #[test]
+    fn test_parse_gpu_engine_instance() {
+        assert_eq!(
+            parse_gpu_engine_instance(
+                "pid_1234_luid_0x00000000_0x0000D1A3_phys_0_eng_3_engtype_VideoDecode"
+            ),
+            Some((
+                1234,
+                "pid_1234_luid_0x00000000_0x0000D1A3_phys_0",
+                "VideoDecode"
+            ))
+        );
+        assert_eq!(
+            parse_gpu_pid("pid_88_luid_0x00000000_0x0000D1A3_phys_0"),
+            Some(88)
+        );
+        assert_eq!(parse_gpu_engine_instance("_Total"), None);
+    }
+}
@@ -0,0 +1,274 @@
+//! Data collection from plugins, which are external commands from the `[[plugins]]` section of
+//! the config file that report values of their own, like the stats of a database or the heap of a
+//! JVM. These are shown in plugin widgets.
+//!
+//! A plugin is started once and kept running. Each time plugins are harvested, a line is written
+//! to the plugin's stdin, and the plugin replies with a line of JSON on its stdout, like
+//! `{"series": [{"name": "hits", "value": 12.5}]}`. Replies are read on their own thread so a slow
+//! plugin doesn't hold up the rest of the harvest; they're picked up by the next harvest after
+//! they arrive. A plugin that exits is started again on the harvest after.
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver, TryRecvError},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// A plugin, as set in the config file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PluginConfig {
+    /// The name used to pick the plugin in a widget, which is also its default title.
+    pub name: String,
+    /// The command to run the plugin, which is run through the shell.
+    pub command: String,
+    /// The unit of the plugin's values, shown on the graph's axis.
+    pub unit: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct PluginSeries {
+    pub name: String,
+    pub value: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct PluginHarvest {
+    pub name: String,
+    /// The values from the plugin's last reply.
+    pub series: Vec<PluginSeries>,
+    /// Why the plugin isn't replying, if it isn't.
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PluginReply {
+    series: Vec<PluginSeries>,
+}
+
+/// Parses a line of a plugin's stdout.
+pub fn parse_reply(line: &str) -> Result<Vec<PluginSeries>, String> {
+    serde_json::from_str::<PluginReply>(line)
+        .map(|reply| reply.series)
+        .map_err(|err| format!("sent an invalid reply: {}", err))
+}
+
+#[derive(Debug)]
+struct RunningPlugin {
+    child: Child,
+    stdin: ChildStdin,
+    replies: Receiver<String>,
+}
+
+impl Drop for RunningPlugin {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn start_plugin(command: &str) -> Result<RunningPlugin, String> {
+    let mut shell = if cfg!(target_family = "unix") {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    } else {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    };
+
+    let mut child = shell
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| format!("couldn't be started: {}", err))?;
+    let (stdin, stdout) = match (child.stdin.take(), child.stdout.take()) {
+        (Some(stdin), Some(stdout)) => (stdin, stdout),
+        _ => {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err("couldn't be started: no stdin or stdout".to_string());
+        }
+    };
+
+    let (sender, replies) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            match line {
+                Ok(line) if line.trim().is_empty() => {}
+                Ok(line) => {
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+    });
+
+    Ok(RunningPlugin {
+        child,
+        stdin,
+        replies,
+    })
+}
+
+#[derive(Debug, Default)]
+struct PluginState {
+    running: Option<RunningPlugin>,
+    last_series: Vec<PluginSeries>,
+    error: Option<String>,
+}
+
+impl PluginState {
+    /// Picks up any replies since the last harvest, and asks for the next one.
+    fn update(&mut self, plugin: &PluginConfig) {
+        if let Some(running) = &mut self.running {
+            loop {
+                match running.replies.try_recv() {
+                    Ok(line) => match parse_reply(&line) {
+                        Ok(series) => {
+                            self.last_series = series;
+                            self.error = None;
+                        }
+                        Err(err) => self.error = Some(err),
+                    },
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        self.error = Some(match running.child.wait() {
+                            Ok(status) => format!("exited with {}", status),
+                            Err(err) => format!("stopped: {}", err),
+                        });
+                        self.last_series.clear();
+                        self.running = None;
+                        return;
+                    }
+                }
+            }
+        } else {
+            match start_plugin(&plugin.command) {
+                Ok(running) => self.running = Some(running),
+                Err(err) => {
+                    self.error = Some(err);
+                    return;
+                }
+            }
+        }
+
+        if let Some(running) = &mut self.running {
+            if running
+                .stdin
+                .write_all(b"{}\n")
+                .and_then(|_| running.stdin.flush())
+                .is_err()
+            {
+                // The plugin has exited, which is picked up from its stdout next time.
+                self.error = Some("stopped reading its stdin".to_string());
+            }
+        }
+    }
+}
+
+/// Keeps each configured plugin running.
+#[derive(Debug, Default)]
+pub struct PluginRunner {
+    plugins: Vec<PluginConfig>,
+    states: Vec<PluginState>,
+}
+
+impl PluginRunner {
+    pub fn set_plugins(&mut self, plugins: Vec<PluginConfig>) {
+        if plugins != self.plugins {
+            self.states = plugins.iter().map(|_| PluginState::default()).collect();
+            self.plugins = plugins;
+        }
+    }
+
+    /// Returns the last values of each plugin, and asks each for new ones. Returns `None` if no
+    /// plugins are configured.
+    pub fn get_plugin_data(&mut self) -> Option<Vec<PluginHarvest>> {
+        if self.plugins.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.plugins
+                .iter()
+                .zip(self.states.iter_mut())
+                .map(|(plugin, state)| {
+                    state.update(plugin);
+
+                    PluginHarvest {
+                        name: plugin.name.clone(),
+                        series: state.last_series.clone(),
+                        error: state.error.clone(),
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_reply() {
+        assert_eq!(
+            parse_reply(
+                r#"{"series": [{"name": "hits", "value": 12.5}, {"name": "misses", "value": 3}]}"#
+            ),
+            Ok(vec![
+                PluginSeries {
+                    name: "hits".to_string(),
+                    value: 12.5
+                },
+                PluginSeries {
+                    name: "misses".to_string(),
+                    value: 3.0
+                },
+            ])
+        );
+        assert_eq!(parse_reply(r#"{"series": []}"#), Ok(vec![]));
+        assert!(parse_reply(r#"{"series": [{"name": "hits"}]}"#).is_err());
+        assert!(parse_reply("12.5").is_err());
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_plugin_runner() {
+        let mut runner = PluginRunner::default();
+        runner.set_plugins(vec![PluginConfig {
+            name: "test".to_string(),
+            command:
+                r#"while read -r line; do echo '{"series": [{"name": "a", "value": 1}]}'; done"#
+                    .to_string(),
+            unit: None,
+        }]);
+
+        // The first reply is only picked up once it's arrived, so wait for it.
+        let mut harvest = runner.get_plugin_data().unwrap();
+        for _ in 0..50 {
+            if !harvest[0].series.is_empty() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            harvest = runner.get_plugin_data().unwrap();
+        }
+
+        assert_eq!(harvest[0].name, "test");
+        assert_eq!(
+            harvest[0].series,
+            vec![PluginSeries {
+                name: "a".to_string(),
+                value: 1.0
+            }]
+        );
+        assert_eq!(harvest[0].error, None);
+    }
+}
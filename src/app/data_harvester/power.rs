@@ -0,0 +1,132 @@
+//! Data collection for power consumption.
+//!
+//! On Linux, this reads the RAPL energy counters exposed through the powercap interface in
+//! `/sys/class/powercap`. Both Intel and (on newer kernels) AMD processors expose their counters
+//! here. Other platforms are not supported yet; macOS only exposes this through `powermetrics`,
+//! which requires root.
+
+use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+/// Power draw in watts, summed across all packages.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct PowerHarvest {
+    pub package: Option<f64>,
+    pub core: Option<f64>,
+    pub dram: Option<f64>,
+}
+
+/// The energy counter of a single RAPL zone, in microjoules.
+#[derive(Clone, Copy, Debug)]
+pub struct EnergyReading {
+    energy_uj: u64,
+    max_energy_range_uj: u64,
+}
+
+#[cfg(target_os = "linux")]
+pub fn get_power_data(
+    prev_readings: &mut FxHashMap<String, EnergyReading>, elapsed_secs: f64,
+) -> Option<PowerHarvest> {
+    use std::{fs, path::Path};
+
+    let read_u64 =
+        |path: &Path| -> Option<u64> { fs::read_to_string(path).ok()?.trim().parse().ok() };
+
+    let mut readings = FxHashMap::default();
+    for entry in fs::read_dir("/sys/class/powercap").ok()?.flatten() {
+        let zone = entry.file_name().to_string_lossy().to_string();
+
+        // Only look at RAPL zones, and skip the duplicate MMIO interface some systems expose.
+        if !zone.starts_with("intel-rapl:") {
+            continue;
+        }
+
+        let path = entry.path();
+        if let (Ok(name), Some(energy_uj), Some(max_energy_range_uj)) = (
+            fs::read_to_string(path.join("name")),
+            read_u64(&path.join("energy_uj")),
+            read_u64(&path.join("max_energy_range_uj")),
+        ) {
+            readings.insert(
+                zone,
+                (
+                    name.trim().to_string(),
+                    EnergyReading {
+                        energy_uj,
+                        max_energy_range_uj,
+                    },
+                ),
+            );
+        }
+    }
+
+    if readings.is_empty() {
+        return None;
+    }
+
+    let mut harvest = PowerHarvest::default();
+    for (zone, (name, reading)) in &readings {
+        if let Some(prev_reading) = prev_readings.get(zone) {
+            if elapsed_secs > 0.0 {
+                let watts =
+                    get_energy_delta(prev_reading, reading) as f64 / 1_000_000.0 / elapsed_secs;
+                let domain = if name.starts_with("package") {
+                    &mut harvest.package
+                } else if name == "core" {
+                    &mut harvest.core
+                } else if name == "dram" {
+                    &mut harvest.dram
+                } else {
+                    continue;
+                };
+                *domain = Some(domain.unwrap_or(0.0) + watts);
+            }
+        }
+    }
+
+    *prev_readings = readings
+        .into_iter()
+        .map(|(zone, (_name, reading))| (zone, reading))
+        .collect();
+
+    Some(harvest)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_power_data(
+    _prev_readings: &mut FxHashMap<String, EnergyReading>, _elapsed_secs: f64,
+) -> Option<PowerHarvest> {
+    None
+}
+
+/// Returns how much energy was used between two readings, accounting for the counter wrapping
+/// around.
+#[cfg(target_os = "linux")]
+fn get_energy_delta(prev_reading: &EnergyReading, reading: &EnergyReading) -> u64 {
+    if reading.energy_uj >= prev_reading.energy_uj {
+        reading.energy_uj - prev_reading.energy_uj
+    } else {
+        reading
+            .max_energy_range_uj
+            .saturating_sub(prev_reading.energy_uj)
+            + reading.energy_uj
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_energy_delta() {
+        let reading = |energy_uj| EnergyReading {
+            energy_uj,
+            max_energy_range_uj: 1_000_000,
+        };
+
+        assert_eq!(get_energy_delta(&reading(100), &reading(350)), 250);
+        assert_eq!(get_energy_delta(&reading(999_900), &reading(50)), 150);
+        assert_eq!(get_energy_delta(&reading(100), &reading(100)), 0);
+    }
+}
@@ -0,0 +1,134 @@
+//! Data collection for Pressure Stall Information (PSI).
+//!
+//! This is only available on Linux 4.20 and newer, through the files in `/proc/pressure`. Each
+//! value is the percentage of time in which at least one task (`some`) or all non-idle tasks
+//! (`full`) were stalled waiting on a resource.
+
+use serde::{Deserialize, Serialize};
+
+/// The average stall percentages over the last 10 and 60 seconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct PressureValues {
+    pub avg10: f64,
+    pub avg60: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct ResourcePressure {
+    pub some: PressureValues,
+    /// Not reported by older kernels for the CPU.
+    pub full: Option<PressureValues>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct PressureHarvest {
+    pub cpu: Option<ResourcePressure>,
+    pub memory: Option<ResourcePressure>,
+    pub io: Option<ResourcePressure>,
+}
+
+#[cfg(target_os = "linux")]
+pub fn get_pressure_data() -> Option<PressureHarvest> {
+    let read_pressure = |resource: &str| {
+        std::fs::read_to_string(format!("/proc/pressure/{}", resource))
+            .ok()
+            .and_then(|contents| parse_pressure(&contents))
+    };
+
+    let harvest = PressureHarvest {
+        cpu: read_pressure("cpu"),
+        memory: read_pressure("memory"),
+        io: read_pressure("io"),
+    };
+
+    if harvest.cpu.is_none() && harvest.memory.is_none() && harvest.io.is_none() {
+        None
+    } else {
+        Some(harvest)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_pressure_data() -> Option<PressureHarvest> {
+    None
+}
+
+/// Parses a file from `/proc/pressure`, which looks like:
+///
+/// ```text
+/// some avg10=0.12 avg60=0.05 avg300=0.01 total=123456
+/// full avg10=0.00 avg60=0.00 avg300=0.00 total=7890
+/// ```
+#[cfg(target_os = "linux")]
+fn parse_pressure(contents: &str) -> Option<ResourcePressure> {
+    let parse_values = |fields: std::str::SplitWhitespace<'_>| {
+        let mut avg10 = None;
+        let mut avg60 = None;
+        for field in fields {
+            match field.split_once('=') {
+                Some(("avg10", value)) => avg10 = value.parse().ok(),
+                Some(("avg60", value)) => avg60 = value.parse().ok(),
+                _ => {}
+            }
+        }
+
+        Some(PressureValues {
+            avg10: avg10?,
+            avg60: avg60?,
+        })
+    };
+
+    let mut some = None;
+    let mut full = None;
+    for line in contents.lines() {
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("some") => some = parse_values(fields),
+            Some("full") => full = parse_values(fields),
+            _ => {}
+        }
+    }
+
+    Some(ResourcePressure { some: some?, full })
+}
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_pressure() {
+        let contents = "\
+some avg10=1.50 avg60=0.75 avg300=0.10 total=123456
+full avg10=0.25 avg60=0.05 avg300=0.00 total=7890
+";
+        assert_eq!(
+            parse_pressure(contents),
+            Some(ResourcePressure {
+                some: PressureValues {
+                    avg10: 1.5,
+                    avg60: 0.75
+                },
+                full: Some(PressureValues {
+                    avg10: 0.25,
+                    avg60: 0.05
+                }),
+            })
+        );
+
+        let contents = "some avg10=2.00 avg60=1.00 avg300=0.50 total=42\n";
+        assert_eq!(
+            parse_pressure(contents),
+            Some(ResourcePressure {
+                some: PressureValues {
+                    avg10: 2.0,
+                    avg60: 1.0
+                },
+                full: None,
+            })
+        );
+
+        assert_eq!(parse_pressure(""), None);
+    }
+}
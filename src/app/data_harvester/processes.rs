@@ -1,7 +1,8 @@
 //! Data collection for processes.
 //!
 //! For Linux, this is handled by a custom set of functions.
-//! For Windows and macOS, this is handled by sysinfo.
+//! For Windows and macOS, this is handled by sysinfo. On Windows, the user and elevation of each
+//! process are read through the Windows API.
 
 cfg_if::cfg_if! {
     if #[cfg(target_os = "linux")] {
@@ -28,9 +29,41 @@ cfg_if::cfg_if! {
     }
 }
 
+// On Windows, GPU usage is read from performance counters instead, which cover every GPU.
+#[cfg(all(feature = "nvidia", not(target_os = "windows")))]
+pub mod nvidia;
+
+use serde::{Deserialize, Serialize};
+
 use crate::Pid;
 
-#[derive(Debug, Clone, Default)]
+/// How much detail is collected about the memory usage of each process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryDetails {
+    /// Only the resident and virtual sizes are collected.
+    None,
+    /// Shared and swapped memory are also collected. On Linux, these are read from `statm` and
+    /// `status`.
+    Basic,
+    /// Like [`MemoryDetails::Basic`], but on Linux, shared and swapped memory are read from
+    /// `smaps_rollup`. This only counts pages that really are shared with another process, but is
+    /// slower to read.
+    Precise,
+}
+
+/// The systemd unit that a process belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SystemdUnit {
+    /// The name of the unit, like `sshd.service`.
+    pub name: String,
+
+    /// Whether the unit is run by a user's service manager, rather than the system's.
+    pub is_user: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+// Some fields only exist on some platforms, so they may be missing when sent from another.
+#[serde(default)]
 pub struct ProcessHarvest {
     /// The pid of the process.
     pub pid: Pid,
@@ -47,6 +80,17 @@ pub struct ProcessHarvest {
     /// Memory usage as bytes.
     pub mem_usage_bytes: u64,
 
+    /// The virtual memory size of the process in bytes.
+    pub virtual_bytes: u64,
+
+    /// Resident memory shared with other processes, in bytes. This is only collected on Linux
+    /// if memory details are enabled, and is 0 otherwise.
+    pub shared_bytes: u64,
+
+    /// Memory swapped out to disk, in bytes. This is only collected on Linux if memory details
+    /// are enabled, and is 0 otherwise.
+    pub swap_bytes: u64,
+
     /// The name of the process.
     pub name: String,
 
@@ -68,16 +112,36 @@ pub struct ProcessHarvest {
     /// The current state of the process (e.g. zombie, asleep)
     pub process_state: (String, char),
 
+    /// The number of threads in the process. This is only collected on Linux, and is 0 elsewhere.
+    pub num_threads: u64,
+
+    /// The CPU the process last ran on. This is only collected on Linux.
+    pub last_cpu: Option<usize>,
+
+    /// When the process started, in seconds since the Unix epoch, if known.
+    pub start_time: Option<u64>,
+
+    /// The systemd unit the process belongs to. This is only collected on Linux.
+    pub systemd_unit: Option<SystemdUnit>,
+
+    /// GPU utilization as a percentage, summed over all GPUs, or of the busiest engine on
+    /// Windows. This is only collected on Windows and for NVIDIA GPUs if GPU columns are enabled,
+    /// and is 0 otherwise.
+    pub gpu_usage_percent: f64,
+
+    /// GPU memory usage in bytes, summed over all GPUs. This is only collected on Windows and for
+    /// NVIDIA GPUs if GPU columns are enabled, and is 0 otherwise.
+    pub gpu_mem_bytes: u64,
+
     /// This is the *effective* user ID of the process. This is only used on Unix platforms.
     #[cfg(target_family = "unix")]
     pub uid: libc::uid_t,
 
-    /// This is the process' user. This is only used on Unix platforms.
-    #[cfg(target_family = "unix")]
+    /// This is the process' user.
     pub user: std::borrow::Cow<'static, str>,
-    // TODO: Additional fields
-    // pub rss_kb: u64,
-    // pub virt_kb: u64,
+
+    /// Whether the process runs elevated, as an administrator. This is only collected on Windows.
+    pub is_elevated: bool,
 }
 
 impl ProcessHarvest {
@@ -85,9 +149,25 @@ impl ProcessHarvest {
         self.cpu_usage_percent += rhs.cpu_usage_percent;
         self.mem_usage_bytes += rhs.mem_usage_bytes;
         self.mem_usage_percent += rhs.mem_usage_percent;
+        self.virtual_bytes += rhs.virtual_bytes;
+        self.shared_bytes += rhs.shared_bytes;
+        self.swap_bytes += rhs.swap_bytes;
         self.read_bytes_per_sec += rhs.read_bytes_per_sec;
         self.write_bytes_per_sec += rhs.write_bytes_per_sec;
         self.total_read_bytes += rhs.total_read_bytes;
         self.total_write_bytes += rhs.total_write_bytes;
+        self.num_threads += rhs.num_threads;
+        self.gpu_usage_percent += rhs.gpu_usage_percent;
+        self.gpu_mem_bytes += rhs.gpu_mem_bytes;
+        // A group of processes started when its earliest process did.
+        self.start_time = match (self.start_time, rhs.start_time) {
+            (Some(start_time), Some(rhs_start_time)) => Some(start_time.min(rhs_start_time)),
+            (start_time, rhs_start_time) => start_time.or(rhs_start_time),
+        };
+    }
+
+    /// Whether the process has been stopped, such as by `SIGSTOP`.
+    pub fn is_stopped(&self) -> bool {
+        matches!(self.process_state.1, 'T' | 't')
     }
 }
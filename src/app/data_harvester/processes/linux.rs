@@ -2,10 +2,10 @@
 
 use std::collections::hash_map::Entry;
 
-use crate::utils::error::{self, BottomError};
+use crate::utils::error;
 use crate::Pid;
 
-use super::{ProcessHarvest, UserTable};
+use super::{MemoryDetails, ProcessHarvest, SystemdUnit, UserTable};
 
 use sysinfo::ProcessStatus;
 
@@ -23,6 +23,8 @@ pub struct PrevProcDetails {
     pub total_write_bytes: u64,
     pub cpu_time: u64,
     pub process: Process,
+    /// Processes are very rarely moved between units, so this is only read once.
+    pub systemd_unit: Option<SystemdUnit>,
 }
 
 impl PrevProcDetails {
@@ -32,10 +34,47 @@ impl PrevProcDetails {
             total_write_bytes: 0,
             cpu_time: 0,
             process: Process::new(pid)?,
+            systemd_unit: std::fs::read_to_string(format!("/proc/{}/cgroup", pid))
+                .ok()
+                .and_then(|cgroup| parse_systemd_unit(&cgroup)),
         })
     }
 }
 
+/// Returns the systemd unit of a process from the contents of its `cgroup` file. systemd puts
+/// each unit in a cgroup named after it, so this is the first service or scope in the process'
+/// cgroup path, unless that's a user's service manager, in which case it's the next one.
+fn parse_systemd_unit(cgroup: &str) -> Option<SystemdUnit> {
+    // Use the unified hierarchy if there is one, or systemd's own hierarchy on cgroup v1.
+    let path = cgroup.lines().find_map(|line| {
+        let mut split = line.splitn(3, ':');
+        let (_id, controllers, path) = (split.next()?, split.next()?, split.next()?);
+        if controllers.is_empty() || controllers == "name=systemd" {
+            Some(path)
+        } else {
+            None
+        }
+    })?;
+
+    let mut units = path
+        .split('/')
+        .filter(|component| component.ends_with(".service") || component.ends_with(".scope"));
+    let system_unit = units.next()?;
+    if system_unit.starts_with("user@") {
+        if let Some(user_unit) = units.next() {
+            return Some(SystemdUnit {
+                name: user_unit.to_string(),
+                is_user: true,
+            });
+        }
+    }
+
+    Some(SystemdUnit {
+        name: system_unit.to_string(),
+        is_user: false,
+    })
+}
+
 fn calculate_idle_values(line: String) -> (f64, f64) {
     /// Converts a `Option<&str>` value to an f64. If it fails to parse or is `None`, then it will return `0_f64`.
     fn str_to_f64(val: Option<&str>) -> f64 {
@@ -116,11 +155,84 @@ fn get_linux_cpu_usage(
     }
 }
 
+/// Returns the shared and swapped memory of a process in bytes, read from `statm` and `status`.
+/// Note that the shared memory here counts every resident page backed by a file, even if no
+/// other process maps it.
+fn read_basic_memory_details(process: &Process) -> (u64, u64) {
+    use std::convert::TryFrom;
+
+    let shared_bytes = match (process.statm(), procfs::page_size()) {
+        (Ok(statm), Ok(page_size)) => statm.shared * u64::try_from(page_size).unwrap_or(0),
+        _ => 0,
+    };
+    let swap_bytes = process
+        .status()
+        .ok()
+        .and_then(|status| status.vmswap)
+        .map(|swap_kb| swap_kb * 1024)
+        .unwrap_or(0);
+
+    (shared_bytes, swap_bytes)
+}
+
+/// Returns the shared and swapped memory of a process in bytes from the contents of its
+/// `smaps_rollup` file, or `None` if neither is present.
+fn parse_smaps_rollup(smaps_rollup: &str) -> Option<(u64, u64)> {
+    let mut shared_kb = None;
+    let mut swap_kb = None;
+
+    for line in smaps_rollup.lines() {
+        let mut split = line.split_whitespace();
+        if let (Some(key), Some(Ok(value))) = (split.next(), split.next().map(str::parse::<u64>)) {
+            match key {
+                "Shared_Clean:" | "Shared_Dirty:" => {
+                    shared_kb = Some(shared_kb.unwrap_or(0) + value);
+                }
+                "Swap:" => swap_kb = Some(value),
+                _ => {}
+            }
+        }
+    }
+
+    if shared_kb.is_none() && swap_kb.is_none() {
+        None
+    } else {
+        Some((shared_kb.unwrap_or(0) * 1024, swap_kb.unwrap_or(0) * 1024))
+    }
+}
+
+fn read_memory_details(process: &Process, memory_details: MemoryDetails) -> (u64, u64) {
+    match memory_details {
+        MemoryDetails::None => (0, 0),
+        MemoryDetails::Basic => read_basic_memory_details(process),
+        MemoryDetails::Precise => {
+            // smaps_rollup needs Linux 4.14 or newer, so fall back if it can't be read.
+            std::fs::read_to_string(format!("/proc/{}/smaps_rollup", process.pid))
+                .ok()
+                .and_then(|smaps_rollup| parse_smaps_rollup(&smaps_rollup))
+                .unwrap_or_else(|| read_basic_memory_details(process))
+        }
+    }
+}
+
+/// Returns when a process started in seconds since the Unix epoch. The kernel only gives this in
+/// clock ticks since boot, so the boot time is added on.
+fn get_start_time(stat: &Stat) -> Option<u64> {
+    use std::convert::TryFrom;
+
+    let ticks_per_second = u64::try_from(procfs::ticks_per_second().ok()?).ok()?;
+    if ticks_per_second == 0 {
+        return None;
+    }
+
+    Some(procfs::boot_time_secs().ok()? + stat.starttime / ticks_per_second)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn read_proc(
     prev_proc: &PrevProcDetails, stat: &Stat, cpu_usage: f64, cpu_fraction: f64,
     use_current_cpu_total: bool, time_difference_in_secs: u64, mem_total_kb: u64,
-    user_table: &mut UserTable,
+    memory_details: MemoryDetails, user_table: &mut UserTable,
 ) -> error::Result<(ProcessHarvest, u64)> {
     use std::convert::TryFrom;
 
@@ -172,6 +284,8 @@ fn read_proc(
     let mem_usage_bytes = u64::try_from(stat.rss_bytes()?).unwrap_or(0);
     let mem_usage_kb = mem_usage_bytes / 1024;
     let mem_usage_percent = mem_usage_kb as f64 / mem_total_kb as f64 * 100.0;
+    let virtual_bytes = stat.vsize;
+    let (shared_bytes, swap_bytes) = read_memory_details(process, memory_details);
 
     // This can fail if permission is denied!
 
@@ -204,6 +318,11 @@ fn read_proc(
         };
 
     let uid = process.owner;
+    let num_threads = u64::try_from(stat.num_threads).unwrap_or(0);
+    let last_cpu = stat
+        .processor
+        .and_then(|processor| usize::try_from(processor).ok());
+    let start_time = get_start_time(stat);
 
     Ok((
         ProcessHarvest {
@@ -212,6 +331,9 @@ fn read_proc(
             cpu_usage_percent,
             mem_usage_percent,
             mem_usage_bytes,
+            virtual_bytes,
+            shared_bytes,
+            swap_bytes,
             name,
             command,
             read_bytes_per_sec,
@@ -219,91 +341,98 @@ fn read_proc(
             total_read_bytes,
             total_write_bytes,
             process_state,
+            num_threads,
+            last_cpu,
+            start_time,
+            systemd_unit: prev_proc.systemd_unit.clone(),
+            gpu_usage_percent: 0.0,
+            gpu_mem_bytes: 0,
             uid,
             user: user_table
                 .get_uid_to_username_mapping(uid)
                 .map(Into::into)
                 .unwrap_or_else(|_| "N/A".into()),
+            is_elevated: false,
         },
         new_process_times,
     ))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn get_process_data(
     prev_idle: &mut f64, prev_non_idle: &mut f64,
     pid_mapping: &mut FxHashMap<Pid, PrevProcDetails>, use_current_cpu_total: bool,
-    time_difference_in_secs: u64, mem_total_kb: u64, user_table: &mut UserTable,
+    time_difference_in_secs: u64, mem_total_kb: u64, memory_details: MemoryDetails,
+    user_table: &mut UserTable,
 ) -> crate::utils::error::Result<Vec<ProcessHarvest>> {
     // TODO: [PROC THREADS] Add threads
 
-    if let Ok((cpu_usage, cpu_fraction)) = cpu_usage_calculation(prev_idle, prev_non_idle) {
-        let mut pids_to_clear: FxHashSet<Pid> = pid_mapping.keys().cloned().collect();
-
-        let process_vector: Vec<ProcessHarvest> = std::fs::read_dir("/proc")?
-            .filter_map(|dir| {
-                if let Ok(dir) = dir {
-                    if let Ok(pid) = dir.file_name().to_string_lossy().trim().parse::<Pid>() {
-                        let mut fresh = false;
-                        if let Entry::Vacant(entry) = pid_mapping.entry(pid) {
-                            if let Ok(ppd) = PrevProcDetails::new(pid) {
-                                entry.insert(ppd);
-                                fresh = true;
-                            } else {
-                                // Bail early.
-                                return None;
-                            }
-                        };
-
-                        if let Some(prev_proc_details) = pid_mapping.get_mut(&pid) {
-                            let stat;
-                            let stat_live;
-                            if fresh {
-                                stat = &prev_proc_details.process.stat;
-                            } else if let Ok(s) = prev_proc_details.process.stat() {
-                                stat_live = s;
-                                stat = &stat_live;
-                            } else {
-                                // Bail early.
-                                return None;
-                            }
-
-                            if let Ok((process_harvest, new_process_times)) = read_proc(
-                                prev_proc_details,
-                                stat,
-                                cpu_usage,
-                                cpu_fraction,
-                                use_current_cpu_total,
-                                time_difference_in_secs,
-                                mem_total_kb,
-                                user_table,
-                            ) {
-                                prev_proc_details.cpu_time = new_process_times;
-                                prev_proc_details.total_read_bytes =
-                                    process_harvest.total_read_bytes;
-                                prev_proc_details.total_write_bytes =
-                                    process_harvest.total_write_bytes;
-
-                                pids_to_clear.remove(&pid);
-                                return Some(process_harvest);
-                            }
+    // Processes are still listed without their CPU usage if /proc/stat can't be read, which is
+    // the case on Android.
+    let (cpu_usage, cpu_fraction) =
+        cpu_usage_calculation(prev_idle, prev_non_idle).unwrap_or((0.0, 0.0));
+
+    let mut pids_to_clear: FxHashSet<Pid> = pid_mapping.keys().cloned().collect();
+
+    let process_vector: Vec<ProcessHarvest> = std::fs::read_dir("/proc")?
+        .filter_map(|dir| {
+            if let Ok(dir) = dir {
+                if let Ok(pid) = dir.file_name().to_string_lossy().trim().parse::<Pid>() {
+                    let mut fresh = false;
+                    if let Entry::Vacant(entry) = pid_mapping.entry(pid) {
+                        if let Ok(ppd) = PrevProcDetails::new(pid) {
+                            entry.insert(ppd);
+                            fresh = true;
+                        } else {
+                            // Bail early.
+                            return None;
+                        }
+                    };
+
+                    if let Some(prev_proc_details) = pid_mapping.get_mut(&pid) {
+                        let stat;
+                        let stat_live;
+                        if fresh {
+                            stat = &prev_proc_details.process.stat;
+                        } else if let Ok(s) = prev_proc_details.process.stat() {
+                            stat_live = s;
+                            stat = &stat_live;
+                        } else {
+                            // Bail early.
+                            return None;
+                        }
+
+                        if let Ok((process_harvest, new_process_times)) = read_proc(
+                            prev_proc_details,
+                            stat,
+                            cpu_usage,
+                            cpu_fraction,
+                            use_current_cpu_total,
+                            time_difference_in_secs,
+                            mem_total_kb,
+                            memory_details,
+                            user_table,
+                        ) {
+                            prev_proc_details.cpu_time = new_process_times;
+                            prev_proc_details.total_read_bytes = process_harvest.total_read_bytes;
+                            prev_proc_details.total_write_bytes = process_harvest.total_write_bytes;
+
+                            pids_to_clear.remove(&pid);
+                            return Some(process_harvest);
                         }
                     }
                 }
+            }
 
-                None
-            })
-            .collect();
+            None
+        })
+        .collect();
 
-        pids_to_clear.iter().for_each(|pid| {
-            pid_mapping.remove(pid);
-        });
+    pids_to_clear.iter().for_each(|pid| {
+        pid_mapping.remove(pid);
+    });
 
-        Ok(process_vector)
-    } else {
-        Err(BottomError::GenericError(
-            "Could not calculate CPU usage.".to_string(),
-        ))
-    }
+    Ok(process_vector)
 }
 
 #[cfg(test)]
@@ -348,4 +477,54 @@ mod tests {
             "Failed to properly calculate idle/non-idle for /proc/stat CPU with 10 values"
         );
     }
+
+    #[test]
+    fn test_parse_systemd_unit() {
+        assert_eq!(
+            parse_systemd_unit("0::/system.slice/sshd.service\n"),
+            Some(SystemdUnit {
+                name: "sshd.service".to_string(),
+                is_user: false,
+            })
+        );
+        assert_eq!(
+            parse_systemd_unit(
+                "0::/user.slice/user-1000.slice/user@1000.service/app.slice/pipewire.service\n"
+            ),
+            Some(SystemdUnit {
+                name: "pipewire.service".to_string(),
+                is_user: true,
+            })
+        );
+        assert_eq!(
+            parse_systemd_unit(
+                "12:cpu,cpuacct:/\n1:name=systemd:/user.slice/user-1000.slice/session-2.scope\n"
+            ),
+            Some(SystemdUnit {
+                name: "session-2.scope".to_string(),
+                is_user: false,
+            })
+        );
+        assert_eq!(parse_systemd_unit("0::/\n"), None);
+    }
+
+    #[test]
+    fn test_parse_smaps_rollup() {
+        let smaps_rollup = "\
+55d4c7a5e000-7ffd3b3f1000 ---p 00000000 00:00 0                          [rollup]
+Rss:                4512 kB
+Pss:                1268 kB
+Shared_Clean:       3208 kB
+Shared_Dirty:         96 kB
+Private_Clean:       412 kB
+Private_Dirty:       796 kB
+Swap:                128 kB
+SwapPss:              64 kB
+";
+        assert_eq!(
+            parse_smaps_rollup(smaps_rollup),
+            Some((3304 * 1024, 128 * 1024))
+        );
+        assert_eq!(parse_smaps_rollup(""), None);
+    }
 }
@@ -77,17 +77,27 @@ pub fn get_process_data(
                 0.0
             },
             mem_usage_bytes: process_val.memory() * 1024,
+            virtual_bytes: process_val.virtual_memory() * 1024,
+            shared_bytes: 0,
+            swap_bytes: 0,
             cpu_usage_percent: process_cpu_usage,
             read_bytes_per_sec: disk_usage.read_bytes,
             write_bytes_per_sec: disk_usage.written_bytes,
             total_read_bytes: disk_usage.total_read_bytes,
             total_write_bytes: disk_usage.total_written_bytes,
             process_state,
+            num_threads: 0,
+            last_cpu: None,
+            start_time: Some(process_val.start_time()),
+            systemd_unit: None,
+            gpu_usage_percent: 0.0,
+            gpu_mem_bytes: 0,
             uid,
             user: user_table
                 .get_uid_to_username_mapping(uid)
                 .map(Into::into)
                 .unwrap_or_else(|_| "N/A".into()),
+            is_elevated: false,
         });
     }
 
@@ -117,6 +127,7 @@ fn convert_process_status_to_char(status: ProcessStatus) -> char {
         ProcessStatus::Sleep => 'S',
         ProcessStatus::Idle => 'D',
         ProcessStatus::Zombie => 'Z',
+        ProcessStatus::Stop => 'T',
         _ => '?',
     }
 }
@@ -0,0 +1,87 @@
+//! Per-process GPU usage for NVIDIA GPUs, through NVML.
+
+use std::convert::TryFrom;
+
+use fxhash::FxHashMap;
+use nvml_wrapper::{enums::device::UsedGpuMemory, NVML};
+
+use super::ProcessHarvest;
+
+/// Keeps NVML loaded between harvests, along with the newest utilization sample seen on each GPU,
+/// so each harvest only averages the samples taken since the last one.
+#[derive(Debug, Default)]
+pub struct GpuProcessReader {
+    nvml: Option<NVML>,
+    /// Whether loading NVML has been tried, so it isn't retried on every harvest if it's missing.
+    has_tried_init: bool,
+    last_seen_timestamps: Vec<u64>,
+}
+
+impl GpuProcessReader {
+    /// Adds the GPU utilization and memory of each process, summed over all GPUs.
+    pub fn add_gpu_data(&mut self, processes: &mut [ProcessHarvest]) {
+        if !self.has_tried_init {
+            self.has_tried_init = true;
+            self.nvml = NVML::init().ok();
+        }
+        let nvml = match &self.nvml {
+            Some(nvml) => nvml,
+            None => return,
+        };
+
+        let num_gpus = nvml.device_count().unwrap_or(0);
+        self.last_seen_timestamps.resize(num_gpus as usize, 0);
+
+        // The GPU utilization and memory of each PID.
+        let mut gpu_usage: FxHashMap<u32, (f64, u64)> = FxHashMap::default();
+        for (index, last_seen_timestamp) in self.last_seen_timestamps.iter_mut().enumerate() {
+            let device = match nvml.device_by_index(index as u32) {
+                Ok(device) => device,
+                Err(_) => continue,
+            };
+
+            // A process can be both a compute and a graphics process, in which case it's listed
+            // twice with the same memory.
+            let mut gpu_mem: FxHashMap<u32, u64> = FxHashMap::default();
+            for process_info in device
+                .running_compute_processes()
+                .unwrap_or_default()
+                .into_iter()
+                .chain(device.running_graphics_processes().unwrap_or_default())
+            {
+                if let UsedGpuMemory::Used(bytes) = process_info.used_gpu_memory {
+                    let mem = gpu_mem.entry(process_info.pid).or_default();
+                    *mem = (*mem).max(bytes);
+                }
+            }
+            for (pid, bytes) in gpu_mem {
+                gpu_usage.entry(pid).or_default().1 += bytes;
+            }
+
+            // The driver keeps a buffer of samples, so average the ones since the last harvest.
+            let mut utilization: FxHashMap<u32, (u64, u64)> = FxHashMap::default();
+            for sample in device
+                .process_utilization_stats(*last_seen_timestamp)
+                .unwrap_or_default()
+            {
+                *last_seen_timestamp = (*last_seen_timestamp).max(sample.timestamp);
+                let (total, count) = utilization.entry(sample.pid).or_default();
+                *total += u64::from(sample.sm_util);
+                *count += 1;
+            }
+            for (pid, (total, count)) in utilization {
+                gpu_usage.entry(pid).or_default().0 += total as f64 / count as f64;
+            }
+        }
+
+        for process in processes {
+            if let Some((gpu_usage_percent, gpu_mem_bytes)) = u32::try_from(process.pid)
+                .ok()
+                .and_then(|pid| gpu_usage.get(&pid))
+            {
+                process.gpu_usage_percent = *gpu_usage_percent;
+                process.gpu_mem_bytes = *gpu_mem_bytes;
+            }
+        }
+    }
+}
@@ -1,10 +1,158 @@
-//! Process data collection for Windows.  Uses sysinfo.
+//! Process data collection for Windows.  Uses sysinfo, along with the Windows API for the user and
+//! elevation of each process, which sysinfo doesn't collect.
 
-use super::ProcessHarvest;
+use std::{mem, ptr};
+
+use fxhash::FxHashMap;
 use sysinfo::{PidExt, ProcessExt, ProcessorExt, System, SystemExt};
+use winapi::{
+    shared::{
+        minwindef::{DWORD, FALSE},
+        ntdef::HANDLE,
+    },
+    um::{
+        handleapi::CloseHandle,
+        processthreadsapi::{OpenProcess, OpenProcessToken},
+        securitybaseapi::{GetLengthSid, GetTokenInformation},
+        winbase::LookupAccountSidW,
+        winnt::{
+            TokenElevation, TokenUser, PROCESS_QUERY_LIMITED_INFORMATION, PSID, SID_NAME_USE,
+            TOKEN_ELEVATION, TOKEN_QUERY, TOKEN_USER,
+        },
+    },
+};
+
+use super::ProcessHarvest;
+
+/// A handle that is closed when dropped.
+struct Handle(HANDLE);
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        // SAFETY: the handle was opened successfully, and is only closed here.
+        unsafe { CloseHandle(self.0) };
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct UserTable {
+    /// The account name of each SID, keyed by the bytes of the SID.
+    pub sid_user_mapping: FxHashMap<Vec<u8>, String>,
+}
+
+impl UserTable {
+    /// Returns the account that a process runs as, and whether it runs elevated. Both are unknown
+    /// for processes that can't be opened, like protected system processes, and for those
+    /// `(None, false)` is returned.
+    pub fn get_process_user(&mut self, pid: DWORD) -> (Option<String>, bool) {
+        // SAFETY: OpenProcess returns a null handle if the process can't be opened.
+        let process = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, pid) };
+        if process.is_null() {
+            return (None, false);
+        }
+        let process = Handle(process);
+
+        let mut token = ptr::null_mut();
+        // SAFETY: the token is only used if it was opened.
+        if unsafe { OpenProcessToken(process.0, TOKEN_QUERY, &mut token) } == 0 {
+            return (None, false);
+        }
+        let token = Handle(token);
+
+        (self.get_token_user(&token), is_token_elevated(&token))
+    }
+
+    fn get_token_user(&mut self, token: &Handle) -> Option<String> {
+        // The first call fails, but gives the size needed for the user and its SID.
+        let mut length: DWORD = 0;
+        // SAFETY: no buffer is passed, so nothing is written but the length.
+        unsafe { GetTokenInformation(token.0, TokenUser, ptr::null_mut(), 0, &mut length) };
+        if length == 0 {
+            return None;
+        }
+
+        // The buffer is made of u64s so the TOKEN_USER at its start is aligned.
+        let mut buffer = vec![0_u64; (length as usize + 7) / 8];
+        // SAFETY: the buffer holds at least `length` bytes.
+        if unsafe {
+            GetTokenInformation(
+                token.0,
+                TokenUser,
+                buffer.as_mut_ptr().cast(),
+                length,
+                &mut length,
+            )
+        } == 0
+        {
+            return None;
+        }
+
+        // SAFETY: the buffer now starts with a TOKEN_USER, whose SID points into the buffer.
+        let sid = unsafe { (*buffer.as_ptr().cast::<TOKEN_USER>()).User.Sid };
+        let sid_bytes =
+            unsafe { std::slice::from_raw_parts(sid.cast::<u8>(), GetLengthSid(sid) as usize) }
+                .to_vec();
+
+        if let Some(user) = self.sid_user_mapping.get(&sid_bytes) {
+            Some(user.clone())
+        } else {
+            let user = get_account_name(sid)?;
+            self.sid_user_mapping.insert(sid_bytes, user.clone());
+
+            Some(user)
+        }
+    }
+}
+
+/// Returns the name of the account with the given SID, without its domain.
+fn get_account_name(sid: PSID) -> Option<String> {
+    let mut name = [0_u16; 256];
+    let mut name_length = name.len() as DWORD;
+    let mut domain = [0_u16; 256];
+    let mut domain_length = domain.len() as DWORD;
+    let mut sid_type: SID_NAME_USE = 0;
+
+    // SAFETY: the lengths passed are those of the buffers, and on success are set to the number
+    // of characters written, without the null terminator.
+    if unsafe {
+        LookupAccountSidW(
+            ptr::null(),
+            sid,
+            name.as_mut_ptr(),
+            &mut name_length,
+            domain.as_mut_ptr(),
+            &mut domain_length,
+            &mut sid_type,
+        )
+    } == 0
+    {
+        return None;
+    }
+
+    Some(String::from_utf16_lossy(&name[..name_length as usize]))
+}
+
+/// Whether the token is elevated, which is the case for processes run as an administrator.
+fn is_token_elevated(token: &Handle) -> bool {
+    let mut elevation = TOKEN_ELEVATION { TokenIsElevated: 0 };
+    let mut length: DWORD = 0;
+
+    // SAFETY: the size passed is that of the TOKEN_ELEVATION written to.
+    let is_read = unsafe {
+        GetTokenInformation(
+            token.0,
+            TokenElevation,
+            (&mut elevation as *mut TOKEN_ELEVATION).cast(),
+            mem::size_of::<TOKEN_ELEVATION>() as DWORD,
+            &mut length,
+        )
+    } != 0;
+
+    is_read && elevation.TokenIsElevated != 0
+}
 
 pub fn get_process_data(
-    sys: &System, use_current_cpu_total: bool, mem_total_kb: u64,
+    sys: &System, use_current_cpu_total: bool, mem_total_kb: u64, user_table: &mut UserTable,
 ) -> crate::utils::error::Result<Vec<ProcessHarvest>> {
     let mut process_vector: Vec<ProcessHarvest> = Vec::new();
     let process_hashmap = sys.processes();
@@ -56,6 +204,7 @@ pub fn get_process_data(
 
         let disk_usage = process_val.disk_usage();
         let process_state = (process_val.status().to_string(), 'R');
+        let (user, is_elevated) = user_table.get_process_user(process_val.pid().as_u32());
         process_vector.push(ProcessHarvest {
             pid: process_val.pid().as_u32() as _,
             parent_pid: process_val.parent().map(|p| p.as_u32() as _),
@@ -67,12 +216,23 @@ pub fn get_process_data(
                 0.0
             },
             mem_usage_bytes: process_val.memory() * 1024,
+            virtual_bytes: process_val.virtual_memory() * 1024,
+            shared_bytes: 0,
+            swap_bytes: 0,
             cpu_usage_percent: process_cpu_usage,
             read_bytes_per_sec: disk_usage.read_bytes,
             write_bytes_per_sec: disk_usage.written_bytes,
             total_read_bytes: disk_usage.total_read_bytes,
             total_write_bytes: disk_usage.total_written_bytes,
             process_state,
+            num_threads: 0,
+            last_cpu: None,
+            start_time: Some(process_val.start_time()),
+            systemd_unit: None,
+            gpu_usage_percent: 0.0,
+            gpu_mem_bytes: 0,
+            user: user.map(Into::into).unwrap_or_else(|| "N/A".into()),
+            is_elevated,
         });
     }
 
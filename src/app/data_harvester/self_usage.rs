@@ -0,0 +1,83 @@
+//! Data collection for bottom's own resource usage and how long each collector takes, which is
+//! shown in the debug overlay to help with tuning refresh rates.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct SelfUsageHarvest {
+    /// The CPU usage of bottom itself, where 100% is one core.
+    pub cpu_usage: f64,
+    pub rss_in_kib: u64,
+}
+
+/// How long a collector took the last time it ran.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HarvestTiming {
+    pub collector: String,
+    pub duration: Duration,
+}
+
+/// Keeps what's needed to work out bottom's own CPU usage between harvests.
+#[derive(Debug, Default)]
+pub struct SelfUsageChecker {
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    prev_cpu_ticks: Option<(u64, Instant)>,
+}
+
+impl SelfUsageChecker {
+    #[cfg(target_os = "linux")]
+    pub fn get_self_usage(&mut self) -> Option<SelfUsageHarvest> {
+        use std::convert::TryFrom;
+
+        let stat = procfs::process::Process::myself().ok()?.stat;
+        let page_size = u64::try_from(procfs::page_size().ok()?).ok()?;
+        let ticks_per_second = u64::try_from(procfs::ticks_per_second().ok()?).ok()?;
+
+        let now = Instant::now();
+        let cpu_ticks = stat.utime + stat.stime;
+        let cpu_usage = match self.prev_cpu_ticks.replace((cpu_ticks, now)) {
+            Some((prev_cpu_ticks, prev_instant)) if ticks_per_second > 0 => {
+                let elapsed_secs = now.duration_since(prev_instant).as_secs_f64();
+                if elapsed_secs > 0.0 {
+                    cpu_ticks.saturating_sub(prev_cpu_ticks) as f64
+                        / ticks_per_second as f64
+                        / elapsed_secs
+                        * 100.0
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.0,
+        };
+
+        Some(SelfUsageHarvest {
+            cpu_usage,
+            rss_in_kib: u64::try_from(stat.rss).unwrap_or(0) * page_size / 1024,
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn get_self_usage(&mut self, sys: &mut sysinfo::System) -> Option<SelfUsageHarvest> {
+        use sysinfo::{ProcessExt, SystemExt};
+
+        let pid = sysinfo::get_current_pid().ok()?;
+        if !sys.refresh_process(pid) {
+            return None;
+        }
+        let process = sys.process(pid)?;
+
+        Some(SelfUsageHarvest {
+            cpu_usage: f64::from(process.cpu_usage()),
+            rss_in_kib: process.memory(),
+        })
+    }
+}
+
+/// Times how long a collector's future takes to finish.
+pub async fn timed<F: std::future::Future>(future: F) -> (F::Output, Duration) {
+    let start = Instant::now();
+    let output = future.await;
+    (output, start.elapsed())
+}
@@ -0,0 +1,34 @@
+//! Data collection for general information about the system, like its host name and uptime.
+
+use serde::{Deserialize, Serialize};
+
+use super::users;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SystemInfoHarvest {
+    pub hostname: Option<String>,
+    /// The name and version of the OS, like `Ubuntu 22.04`.
+    pub os_name: Option<String>,
+    pub kernel_version: Option<String>,
+    pub uptime_secs: u64,
+    /// How many sessions are logged in, which isn't known on Windows.
+    pub num_users: Option<usize>,
+}
+
+pub fn get_system_info() -> SystemInfoHarvest {
+    use sysinfo::{System, SystemExt};
+
+    let sys = System::new();
+    let os_name = match (sys.name(), sys.os_version()) {
+        (Some(name), Some(version)) => Some(format!("{} {}", name, version)),
+        (name, _) => name,
+    };
+
+    SystemInfoHarvest {
+        hostname: sys.host_name(),
+        os_name,
+        kernel_version: sys.kernel_version(),
+        uptime_secs: sys.uptime(),
+        num_users: users::get_user_sessions().map(|sessions| sessions.len()),
+    }
+}
@@ -1,13 +1,17 @@
 //! Data collection for temperature metrics.
 //!
-//! For Linux and macOS, this is handled by Heim.
-//! For Windows, this is handled by sysinfo.
+//! For Linux, this is handled by Heim.
+//! For macOS and Windows, this is handled by sysinfo.
+//! For FreeBSD, this is read from sysctl.
 
 cfg_if::cfg_if! {
     if #[cfg(target_os = "linux")] {
         pub mod heim;
         pub use self::heim::*;
-    } else if #[cfg(any(target_os = "freebsd", target_os = "macos", target_os = "windows"))] {
+    } else if #[cfg(target_os = "freebsd")] {
+        pub mod freebsd;
+        pub use self::freebsd::*;
+    } else if #[cfg(any(target_os = "macos", target_os = "windows"))] {
         pub mod sysinfo;
         pub use self::sysinfo::*;
     }
@@ -16,11 +20,13 @@ cfg_if::cfg_if! {
 #[cfg(feature = "nvidia")]
 pub mod nvidia;
 
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::HashMap};
+
+use serde::{Deserialize, Serialize};
 
 use crate::app::Filter;
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Deserialize, Serialize)]
 pub struct TempHarvest {
     pub name: String,
     pub temperature: f32,
@@ -39,6 +45,39 @@ impl Default for TemperatureType {
     }
 }
 
+impl TemperatureType {
+    /// Converts a temperature in Celsius to this unit.
+    pub fn convert_from_celsius(&self, celsius: f32) -> f32 {
+        match self {
+            TemperatureType::Celsius => celsius,
+            TemperatureType::Kelvin => celsius + 273.15,
+            TemperatureType::Fahrenheit => (celsius * (9.0 / 5.0)) + 32.0,
+        }
+    }
+}
+
+/// The temperatures at which a sensor is considered to be running warm or critically hot,
+/// in Celsius.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TempThreshold {
+    pub warning: f32,
+    pub critical: f32,
+}
+
+/// Temperature thresholds for each sensor, falling back to a default for any sensors that
+/// are not given one.
+#[derive(Clone, Debug, Default)]
+pub struct TempThresholds {
+    pub default: Option<TempThreshold>,
+    pub sensors: HashMap<String, TempThreshold>,
+}
+
+impl TempThresholds {
+    pub fn get_threshold(&self, sensor_name: &str) -> Option<TempThreshold> {
+        self.sensors.get(sensor_name).copied().or(self.default)
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(any(feature = "nvidia", target_os = "macos", target_os = "windows"))] {
         fn convert_celsius_to_kelvin(celsius: f32) -> f32 {
@@ -51,7 +90,7 @@ cfg_if::cfg_if! {
     }
 }
 
-fn is_temp_filtered(filter: &Option<Filter>, text: &str) -> bool {
+pub fn is_temp_filtered(filter: &Option<Filter>, text: &str) -> bool {
     if let Some(filter) = filter {
         if filter.is_list_ignored {
             let mut ret = true;
@@ -0,0 +1,47 @@
+//! Gets temperature data via sysctl on FreeBSD, from the CPU sensors of the `coretemp` and
+//! `amdtemp` drivers and from the ACPI thermal zones.
+
+use super::{is_temp_filtered, temp_vec_sort, TempHarvest, TemperatureType};
+use crate::app::{data_harvester::get_sysctl_int, Filter};
+
+/// Converts a sysctl temperature, which is in tenths of a Kelvin, to Celsius.
+fn convert_decikelvin_to_celsius(decikelvin: i32) -> f32 {
+    (decikelvin - 2732) as f32 / 10.0
+}
+
+pub async fn get_temperature_data(
+    temp_type: &TemperatureType, actually_get: bool, filter: &Option<Filter>,
+) -> crate::utils::error::Result<Option<Vec<TempHarvest>>> {
+    if !actually_get {
+        return Ok(None);
+    }
+
+    let mut temperature_vec: Vec<TempHarvest> = Vec::new();
+
+    // Both are numbered from 0, so stop at the first one that's missing.
+    for (prefix, label) in [("dev.cpu.", "CPU"), ("hw.acpi.thermal.tz", "Thermal zone")] {
+        for (index, decikelvin) in (0..)
+            .map(|index| get_sysctl_int(&format!("{}{}.temperature", prefix, index)))
+            .take_while(Option::is_some)
+            .flatten()
+            .enumerate()
+        {
+            let name = format!("{} {}", label, index);
+            if is_temp_filtered(filter, &name) {
+                temperature_vec.push(TempHarvest {
+                    name,
+                    temperature: temp_type
+                        .convert_from_celsius(convert_decikelvin_to_celsius(decikelvin)),
+                });
+            }
+        }
+    }
+
+    #[cfg(feature = "nvidia")]
+    {
+        super::nvidia::add_nvidia_data(&mut temperature_vec, temp_type, filter)?;
+    }
+
+    temp_vec_sort(&mut temperature_vec);
+    Ok(Some(temperature_vec))
+}
@@ -17,35 +17,40 @@ pub async fn get_temperature_data(
 
     let mut sensor_data = heim::sensors::temperatures().boxed_local();
     while let Some(sensor) = sensor_data.next().await {
-        if let Ok(sensor) = sensor {
-            let component_name = Some(sensor.unit().to_string());
-            let component_label = sensor.label().map(|label| label.to_string());
-
-            let name = match (component_name, component_label) {
-                (Some(name), Some(label)) => format!("{}: {}", name, label),
-                (None, Some(label)) => label.to_string(),
-                (Some(name), None) => name.to_string(),
-                (None, None) => String::default(),
-            };
-
-            if is_temp_filtered(filter, &name) {
-                temperature_vec.push(TempHarvest {
-                    name,
-                    temperature: match temp_type {
-                        TemperatureType::Celsius => sensor
-                            .current()
-                            .get::<thermodynamic_temperature::degree_celsius>(
-                        ),
-                        TemperatureType::Kelvin => {
-                            sensor.current().get::<thermodynamic_temperature::kelvin>()
-                        }
-                        TemperatureType::Fahrenheit => sensor
-                            .current()
-                            .get::<thermodynamic_temperature::degree_fahrenheit>(
-                        ),
-                    },
-                });
+        let sensor = match sensor {
+            Ok(sensor) => sensor,
+            Err(_err) => {
+                #[cfg(feature = "log")]
+                debug!("A temperature sensor couldn't be read: {}", _err);
+                continue;
             }
+        };
+        let component_name = Some(sensor.unit().to_string());
+        let component_label = sensor.label().map(|label| label.to_string());
+
+        let name = match (component_name, component_label) {
+            (Some(name), Some(label)) => format!("{}: {}", name, label),
+            (None, Some(label)) => label.to_string(),
+            (Some(name), None) => name.to_string(),
+            (None, None) => String::default(),
+        };
+
+        if is_temp_filtered(filter, &name) {
+            temperature_vec.push(TempHarvest {
+                name,
+                temperature: match temp_type {
+                    TemperatureType::Celsius => sensor
+                        .current()
+                        .get::<thermodynamic_temperature::degree_celsius>(),
+                    TemperatureType::Kelvin => {
+                        sensor.current().get::<thermodynamic_temperature::kelvin>()
+                    }
+                    TemperatureType::Fahrenheit => sensor
+                        .current()
+                        .get::<thermodynamic_temperature::degree_fahrenheit>(
+                    ),
+                },
+            });
         }
     }
 
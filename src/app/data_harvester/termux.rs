@@ -0,0 +1,67 @@
+//! The Termux profile, for running bottom in Termux on Android. Android keeps apps from reading
+//! much of `/proc` and `/sys`, so the collectors that need these are checked once, and the ones
+//! that can't read them are skipped rather than failing on every harvest.
+
+use std::{fs, path::Path};
+
+use super::UnavailableHarvest;
+
+/// The collectors that read from places Android may not allow, and what they read.
+const RESTRICTED_SOURCES: &[(&str, &str)] = &[
+    ("CPU", "/proc/stat"),
+    ("Load average", "/proc/loadavg"),
+    ("Disk I/O", "/proc/diskstats"),
+    ("Temperature", "/sys/class/thermal"),
+    ("Pressure", "/proc/pressure"),
+];
+
+/// Returns whether a file can be opened, or a directory listed.
+fn is_readable(path: &str) -> bool {
+    let path = Path::new(path);
+    if path.is_dir() {
+        fs::read_dir(path).is_ok()
+    } else {
+        fs::File::open(path).is_ok()
+    }
+}
+
+/// Returns the collectors whose sources `is_readable` says can't be read, and why.
+fn get_unreadable_sources(is_readable: impl Fn(&str) -> bool) -> Vec<UnavailableHarvest> {
+    RESTRICTED_SOURCES
+        .iter()
+        .filter(|(_, source)| !is_readable(source))
+        .map(|(collector, source)| UnavailableHarvest {
+            collector: collector.to_string(),
+            reason: format!("{} can't be read", source),
+        })
+        .collect()
+}
+
+/// Returns the collectors that can't be used here. Only Linux reads from `/proc` and `/sys`, so
+/// everything is available elsewhere.
+pub fn get_unavailable_harvests() -> Vec<UnavailableHarvest> {
+    if cfg!(target_os = "linux") {
+        get_unreadable_sources(is_readable)
+    } else {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unreadable_sources() {
+        let unavailable = get_unreadable_sources(|source| source.starts_with("/proc/"));
+        assert_eq!(
+            unavailable,
+            vec![UnavailableHarvest {
+                collector: "Temperature".to_string(),
+                reason: "/sys/class/thermal can't be read".to_string(),
+            }]
+        );
+
+        assert!(get_unreadable_sources(|_| true).is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+//! Data collection for the users logged in to the system.
+//!
+//! Sessions are read from the utmp database, like `who` does. On Linux, `/run/utmp` is read
+//! directly, as musl doesn't implement the functions for reading it. On macOS and FreeBSD it's
+//! read with `getutxent`. This isn't supported on Windows.
+//!
+//! How long a session has been idle is worked out from when its terminal was last read from, as
+//! `who -u` does.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UserSession {
+    pub user: String,
+    /// The terminal of the session, like `pts/0`.
+    pub tty: String,
+    /// The host the session is from, if it's remote.
+    pub host: Option<String>,
+    /// When the session started, as a Unix timestamp in seconds.
+    pub login_time: u64,
+    /// How long it's been since there was any input on the session's terminal, in seconds.
+    pub idle_secs: Option<u64>,
+}
+
+#[cfg(target_os = "linux")]
+pub fn get_user_sessions() -> Option<Vec<UserSession>> {
+    std::fs::read("/run/utmp")
+        .or_else(|_| std::fs::read("/var/run/utmp"))
+        .ok()
+        .map(|contents| {
+            let mut sessions = parse_utmp(&contents);
+            for session in &mut sessions {
+                session.idle_secs = get_idle_secs(&session.tty);
+            }
+            sessions
+        })
+}
+
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+pub fn get_user_sessions() -> Option<Vec<UserSession>> {
+    let mut sessions = Vec::new();
+
+    // SAFETY: Each entry returned by `getutxent` is only read before the next call, and the
+    // database is closed once all entries are read.
+    unsafe {
+        libc::setutxent();
+        loop {
+            let entry = libc::getutxent();
+            if entry.is_null() {
+                break;
+            }
+
+            let entry = &*entry;
+            if entry.ut_type == libc::USER_PROCESS {
+                let host = c_chars_to_string(&entry.ut_host);
+                let tty = c_chars_to_string(&entry.ut_line);
+                sessions.push(UserSession {
+                    user: c_chars_to_string(&entry.ut_user),
+                    idle_secs: get_idle_secs(&tty),
+                    tty,
+                    host: if host.is_empty() { None } else { Some(host) },
+                    login_time: entry.ut_tv.tv_sec as u64,
+                });
+            }
+        }
+        libc::endutxent();
+    }
+
+    Some(sessions)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")))]
+pub fn get_user_sessions() -> Option<Vec<UserSession>> {
+    None
+}
+
+/// Returns how long it's been since the terminal was last read from, which is when there was last
+/// input on it.
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+fn get_idle_secs(tty: &str) -> Option<u64> {
+    let accessed = std::fs::metadata(format!("/dev/{tty}"))
+        .and_then(|metadata| metadata.accessed())
+        .ok()?;
+
+    Some(
+        std::time::SystemTime::now()
+            .duration_since(accessed)
+            .map(|idle| idle.as_secs())
+            .unwrap_or(0),
+    )
+}
+
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+fn c_chars_to_string(chars: &[libc::c_char]) -> String {
+    let bytes = chars
+        .iter()
+        .take_while(|c| **c != 0)
+        .map(|c| *c as u8)
+        .collect::<Vec<_>>();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Parses the records of a Linux utmp file, keeping only the ones for logged in users. Each record
+/// is a `struct utmp`, which has the same 384 byte layout with glibc and musl on all of the
+/// architectures bottom is built for. The idle time of each session is left unset.
+#[cfg(target_os = "linux")]
+fn parse_utmp(contents: &[u8]) -> Vec<UserSession> {
+    const RECORD_SIZE: usize = 384;
+    const USER_PROCESS: i16 = 7;
+
+    let get_string = |field: &[u8]| {
+        let end = field.iter().position(|b| *b == 0).unwrap_or(field.len());
+        String::from_utf8_lossy(&field[..end]).into_owned()
+    };
+
+    contents
+        .chunks_exact(RECORD_SIZE)
+        .filter(|record| i16::from_ne_bytes([record[0], record[1]]) == USER_PROCESS)
+        .map(|record| {
+            let host = get_string(&record[76..332]);
+            UserSession {
+                user: get_string(&record[44..76]),
+                tty: get_string(&record[8..40]),
+                host: if host.is_empty() { None } else { Some(host) },
+                login_time: u64::from(u32::from_ne_bytes([
+                    record[340],
+                    record[341],
+                    record[342],
+                    record[343],
+                ])),
+                idle_secs: None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod test {
+    use super::*;
+
+    fn utmp_record(ut_type: i16, line: &str, user: &str, host: &str, time: u32) -> Vec<u8> {
+        let mut record = vec![0; 384];
+        record[0..2].copy_from_slice(&ut_type.to_ne_bytes());
+        record[8..8 + line.len()].copy_from_slice(line.as_bytes());
+        record[44..44 + user.len()].copy_from_slice(user.as_bytes());
+        record[76..76 + host.len()].copy_from_slice(host.as_bytes());
+        record[340..344].copy_from_slice(&time.to_ne_bytes());
+        record
+    }
+
+    #[test]
+    fn test_parse_utmp() {
+        let contents = [
+            // A boot time record, which isn't a session.
+            utmp_record(2, "~", "reboot", "5.15.0", 1651400000),
+            utmp_record(7, "tty1", "alice", "", 1651400100),
+            utmp_record(7, "pts/0", "bob", "192.168.1.2", 1651400200),
+        ]
+        .concat();
+
+        assert_eq!(
+            parse_utmp(&contents),
+            vec![
+                UserSession {
+                    user: "alice".to_string(),
+                    tty: "tty1".to_string(),
+                    host: None,
+                    login_time: 1651400100,
+                    idle_secs: None,
+                },
+                UserSession {
+                    user: "bob".to_string(),
+                    tty: "pts/0".to_string(),
+                    host: Some("192.168.1.2".to_string()),
+                    login_time: 1651400200,
+                    idle_secs: None,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! Data collection for wireless interfaces.
+//!
+//! On Linux, the signal strength and link quality are read from `/proc/net/wireless`, and the
+//! link speed from the output of `iw`, if it is installed. On macOS, this uses the output of
+//! `airport -I`, and on Windows, the output of `netsh wlan show interfaces`. As these can be
+//! fairly expensive, results are only refreshed every [`WIRELESS_REFRESH_INTERVAL`].
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+use super::network::is_interface_kept;
+
+/// How long wireless data is kept before it is read again.
+pub const WIRELESS_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct WirelessHarvest {
+    pub interface: String,
+    /// The signal strength in dBm.
+    pub signal_dbm: Option<f64>,
+    /// The link quality as a percentage.
+    pub link_quality: Option<f64>,
+    /// The link speed in megabits per second.
+    pub link_speed_mbps: Option<f64>,
+}
+
+#[cfg(target_os = "linux")]
+pub fn get_wireless_data(filter: &Option<crate::app::Filter>) -> Vec<WirelessHarvest> {
+    let contents = match std::fs::read_to_string("/proc/net/wireless") {
+        Ok(contents) => contents,
+        Err(_) => return Vec::new(),
+    };
+
+    parse_proc_net_wireless(&contents)
+        .into_iter()
+        .filter(|harvest| is_interface_kept(&harvest.interface, filter))
+        .map(|mut harvest| {
+            harvest.link_speed_mbps = std::process::Command::new("iw")
+                .args(["dev", &harvest.interface, "link"])
+                .output()
+                .ok()
+                .and_then(|output| parse_iw_link(&String::from_utf8_lossy(&output.stdout)));
+            harvest
+        })
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+pub fn get_wireless_data(filter: &Option<crate::app::Filter>) -> Vec<WirelessHarvest> {
+    const AIRPORT_PATH: &str =
+        "/System/Library/PrivateFrameworks/Apple80211.framework/Versions/Current/Resources/airport";
+
+    std::process::Command::new(AIRPORT_PATH)
+        .arg("-I")
+        .output()
+        .ok()
+        .and_then(|output| parse_airport(&String::from_utf8_lossy(&output.stdout)))
+        .filter(|harvest| is_interface_kept(&harvest.interface, filter))
+        .into_iter()
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+pub fn get_wireless_data(filter: &Option<crate::app::Filter>) -> Vec<WirelessHarvest> {
+    std::process::Command::new("netsh")
+        .args(["wlan", "show", "interfaces"])
+        .output()
+        .map(|output| parse_netsh(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|harvest| is_interface_kept(&harvest.interface, filter))
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub fn get_wireless_data(_filter: &Option<crate::app::Filter>) -> Vec<WirelessHarvest> {
+    Vec::new()
+}
+
+/// Converts a signal strength in dBm into a rough percentage, where -100 dBm or lower is 0% and
+/// -50 dBm or higher is 100%.
+#[cfg(any(target_os = "macos", test))]
+fn signal_to_quality(signal_dbm: f64) -> f64 {
+    (2.0 * (signal_dbm + 100.0)).clamp(0.0, 100.0)
+}
+
+/// Parses `/proc/net/wireless`, which looks like:
+///
+/// ```text
+/// Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+///  face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+///  wlan0: 0000   54.  -56.  -256        0      0      0      0    140        0
+/// ```
+///
+/// The link quality is out of 70 for most drivers.
+#[cfg(target_os = "linux")]
+fn parse_proc_net_wireless(contents: &str) -> Vec<WirelessHarvest> {
+    const MAX_LINK_QUALITY: f64 = 70.0;
+
+    contents
+        .lines()
+        .skip(2)
+        .filter_map(|line| {
+            let (interface, values) = line.split_once(':')?;
+            let mut fields = values
+                .split_whitespace()
+                .skip(1)
+                .map(|field| field.trim_end_matches('.').parse::<f64>().ok());
+            let link = fields.next().flatten();
+            let level = fields.next().flatten();
+
+            Some(WirelessHarvest {
+                interface: interface.trim().to_string(),
+                // Some drivers report the level as an unsigned value if it isn't in dBm.
+                signal_dbm: level.filter(|level| *level < 0.0),
+                link_quality: link.map(|link| (link / MAX_LINK_QUALITY * 100.0).min(100.0)),
+                link_speed_mbps: None,
+            })
+        })
+        .collect()
+}
+
+/// Returns the transmit bitrate from the output of `iw dev <interface> link`, which contains a
+/// line like `tx bitrate: 866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2`.
+#[cfg(target_os = "linux")]
+fn parse_iw_link(output: &str) -> Option<f64> {
+    output.lines().find_map(|line| {
+        let rate = line.trim().strip_prefix("tx bitrate:")?;
+        rate.split_whitespace().next()?.parse().ok()
+    })
+}
+
+/// Parses the output of `airport -I`, which looks like:
+///
+/// ```text
+///      agrCtlRSSI: -55
+///     agrCtlNoise: -90
+///           state: running
+///      lastTxRate: 867
+///            SSID: network
+/// ```
+///
+/// The interface name isn't reported, so the SSID is used instead.
+#[cfg(any(target_os = "macos", test))]
+fn parse_airport(output: &str) -> Option<WirelessHarvest> {
+    let mut ssid = None;
+    let mut signal_dbm = None;
+    let mut link_speed_mbps = None;
+    for line in output.lines() {
+        match line.split_once(':').map(|(k, v)| (k.trim(), v.trim())) {
+            Some(("SSID", value)) => ssid = Some(value.to_string()),
+            Some(("agrCtlRSSI", value)) => signal_dbm = value.parse::<f64>().ok(),
+            Some(("lastTxRate", value)) => link_speed_mbps = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    Some(WirelessHarvest {
+        interface: ssid?,
+        signal_dbm,
+        link_quality: signal_dbm.map(signal_to_quality),
+        link_speed_mbps,
+    })
+}
+
+/// Parses the output of `netsh wlan show interfaces`, which has a block like this for each
+/// interface:
+///
+/// ```text
+///     Name                   : Wi-Fi
+///     State                  : connected
+///     Receive rate (Mbps)    : 866.7
+///     Transmit rate (Mbps)   : 866.7
+///     Signal                 : 92%
+/// ```
+///
+/// Windows only reports the signal as a percentage, so the signal strength is estimated from it.
+/// This only works if Windows is in English.
+#[cfg(any(target_os = "windows", test))]
+fn parse_netsh(output: &str) -> Vec<WirelessHarvest> {
+    let mut harvest: Vec<WirelessHarvest> = Vec::new();
+    for line in output.lines() {
+        match line.split_once(':').map(|(k, v)| (k.trim(), v.trim())) {
+            Some(("Name", value)) => harvest.push(WirelessHarvest {
+                interface: value.to_string(),
+                ..Default::default()
+            }),
+            Some(("Signal", value)) => {
+                if let Some(last) = harvest.last_mut() {
+                    last.link_quality = value.trim_end_matches('%').parse::<f64>().ok();
+                    last.signal_dbm = last.link_quality.map(|quality| quality / 2.0 - 100.0);
+                }
+            }
+            Some(("Transmit rate (Mbps)", value)) => {
+                if let Some(last) = harvest.last_mut() {
+                    last.link_speed_mbps = value.parse().ok();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Disconnected interfaces don't report a signal.
+    harvest
+        .into_iter()
+        .filter(|harvest| harvest.link_quality.is_some())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_proc_net_wireless() {
+        let contents = "\
+Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+ face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+ wlan0: 0000   56.  -54.  -256        0      0      0      0    140        0
+";
+        assert_eq!(
+            parse_proc_net_wireless(contents),
+            vec![WirelessHarvest {
+                interface: "wlan0".to_string(),
+                signal_dbm: Some(-54.0),
+                link_quality: Some(80.0),
+                link_speed_mbps: None,
+            }]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_iw_link() {
+        let output = "\
+Connected to 00:11:22:33:44:55 (on wlan0)
+	SSID: network
+	signal: -54 dBm
+	rx bitrate: 585.1 MBit/s VHT-MCS 7 80MHz VHT-NSS 2
+	tx bitrate: 866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2
+";
+        assert_eq!(parse_iw_link(output), Some(866.7));
+        assert_eq!(parse_iw_link("Not connected."), None);
+    }
+
+    #[test]
+    fn test_parse_airport() {
+        let output = "
+     agrCtlRSSI: -60
+     agrExtRSSI: 0
+    agrCtlNoise: -90
+          state: running
+     lastTxRate: 400
+        maxRate: 867
+           SSID: network
+";
+        assert_eq!(
+            parse_airport(output),
+            Some(WirelessHarvest {
+                interface: "network".to_string(),
+                signal_dbm: Some(-60.0),
+                link_quality: Some(80.0),
+                link_speed_mbps: Some(400.0),
+            })
+        );
+        assert_eq!(parse_airport("AirPort: Off"), None);
+    }
+
+    #[test]
+    fn test_parse_netsh() {
+        let output = "
+There are 2 interfaces on the system:
+
+    Name                   : Wi-Fi
+    State                  : connected
+    Receive rate (Mbps)    : 780
+    Transmit rate (Mbps)   : 866.7
+    Signal                 : 90%
+
+    Name                   : Wi-Fi 2
+    State                  : disconnected
+";
+        assert_eq!(
+            parse_netsh(output),
+            vec![WirelessHarvest {
+                interface: "Wi-Fi".to_string(),
+                signal_dbm: Some(-55.0),
+                link_quality: Some(90.0),
+                link_speed_mbps: Some(866.7),
+            }]
+        );
+    }
+}
@@ -0,0 +1,380 @@
+//! Derived metrics, which are Lua expressions from the `[[derived_metrics]]` section of the config
+//! file that are worked out from the other metrics after every harvest, like
+//! `cpu.total - cpu.steal`. They're stored like a plugin's values, so they can be shown in plugin
+//! widgets, and they can also be alerted on.
+//!
+//! Running them needs Lua, which is built from source, so it's behind the `derived_metrics`
+//! feature. Without it, a config file with derived metrics is rejected.
+
+#[cfg(feature = "derived_metrics")]
+use mlua::{Function, HookTriggers, Lua, LuaOptions, RegistryKey, StdLib, Table, Value};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "derived_metrics")]
+use super::data_harvester::{cpu, plugins::PluginSeries};
+use super::{data_farmer::DataCollection, data_harvester::plugins::PluginHarvest};
+
+/// How many Lua instructions an expression can run before it's stopped, so one that loops forever
+/// can't freeze bottom. Expressions are run on the UI thread, so this is kept small.
+#[cfg(feature = "derived_metrics")]
+const INSTRUCTION_BUDGET: u32 = 1_000_000;
+
+/// How much memory every expression together can use, so one that builds huge strings or tables
+/// fails rather than using up all the memory.
+#[cfg(feature = "derived_metrics")]
+const MEMORY_LIMIT: usize = 16 * 1024 * 1024;
+
+/// A derived metric, as set in the config file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DerivedMetricConfig {
+    /// The name used to pick the metric in a widget or an alert.
+    pub name: String,
+    /// The Lua expression that works out the metric's value.
+    pub expression: String,
+    /// The unit of the metric's values, shown on the graph's axis.
+    pub unit: Option<String>,
+}
+
+/// The compiled expressions of every derived metric, and the Lua state they run in.
+#[cfg(feature = "derived_metrics")]
+#[derive(Debug)]
+pub struct DerivedMetrics {
+    lua: Lua,
+    metrics: Vec<(String, RegistryKey)>,
+}
+
+#[cfg(feature = "derived_metrics")]
+impl Default for DerivedMetrics {
+    fn default() -> Self {
+        DerivedMetrics::new(&[]).expect("no expressions to compile")
+    }
+}
+
+#[cfg(feature = "derived_metrics")]
+impl DerivedMetrics {
+    /// Compiles the expression of each metric. Expressions only have access to the math, string,
+    /// and table libraries, so they can't touch files or run commands.
+    pub fn new(configs: &[DerivedMetricConfig]) -> Result<Self, String> {
+        let lua = Lua::new_with(
+            StdLib::MATH | StdLib::STRING | StdLib::TABLE,
+            LuaOptions::default(),
+        )
+        .map_err(|err| err.to_string())?;
+        lua.set_memory_limit(MEMORY_LIMIT)
+            .map_err(|err| err.to_string())?;
+
+        let mut metrics = Vec::with_capacity(configs.len());
+        for config in configs {
+            // An expression can also be a chunk of statements that returns the value.
+            let function = lua
+                .load(format!("return {}", config.expression))
+                .set_name(&config.name)
+                .into_function()
+                .or_else(|_| {
+                    lua.load(&config.expression)
+                        .set_name(&config.name)
+                        .into_function()
+                })
+                .map_err(|err| {
+                    format!(
+                        "the expression of the derived metric \"{}\" is invalid: {}",
+                        config.name, err
+                    )
+                })?;
+            let key = lua
+                .create_registry_value(function)
+                .map_err(|err| err.to_string())?;
+            metrics.push((config.name.clone(), key));
+        }
+
+        Ok(DerivedMetrics { lua, metrics })
+    }
+
+    /// Works out the value of each metric from the latest data. Metrics can use the values of
+    /// ones listed before them through `derived`.
+    pub fn evaluate(&self, data: &DataCollection) -> Vec<PluginHarvest> {
+        if self.metrics.is_empty() {
+            return Vec::new();
+        }
+
+        let derived = match self.set_globals(data) {
+            Ok(derived) => derived,
+            Err(err) => {
+                return self
+                    .metrics
+                    .iter()
+                    .map(|(name, _)| PluginHarvest {
+                        name: name.clone(),
+                        series: Vec::new(),
+                        error: Some(err.to_string()),
+                    })
+                    .collect();
+            }
+        };
+
+        self.metrics
+            .iter()
+            .map(|(name, key)| {
+                // Setting the hook again starts the count over, so each expression gets the whole
+                // budget.
+                self.lua.set_hook(
+                    HookTriggers::new().every_nth_instruction(INSTRUCTION_BUDGET),
+                    |_lua, _debug| {
+                        Err(mlua::Error::RuntimeError(format!(
+                            "stopped after {} instructions",
+                            INSTRUCTION_BUDGET
+                        )))
+                    },
+                );
+                let value = self
+                    .lua
+                    .registry_value::<Function<'_>>(key)
+                    .and_then(|function| function.call::<_, Value<'_>>(()))
+                    .map_err(get_error_message)
+                    .and_then(|value| match value {
+                        Value::Nil => Ok(None),
+                        Value::Integer(value) => Ok(Some(value as f64)),
+                        Value::Number(value) => Ok(Some(value)),
+                        other => Err(format!("returned a {}, not a number", other.type_name())),
+                    });
+
+                match value {
+                    Ok(value) => {
+                        let _ = derived.set(name.as_str(), value);
+                        PluginHarvest {
+                            name: name.clone(),
+                            series: value
+                                .map(|value| {
+                                    vec![PluginSeries {
+                                        name: name.clone(),
+                                        value,
+                                    }]
+                                })
+                                .unwrap_or_default(),
+                            error: None,
+                        }
+                    }
+                    Err(err) => PluginHarvest {
+                        name: name.clone(),
+                        series: Vec::new(),
+                        error: Some(err),
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// Sets the tables that expressions read metrics from. Metrics that aren't being collected
+    /// are left as `nil`. Returns the `derived` table, which is filled in as metrics are worked
+    /// out.
+    fn set_globals(&self, data: &DataCollection) -> mlua::Result<Table<'_>> {
+        let lua = &self.lua;
+        let globals = lua.globals();
+
+        let cpu = lua.create_table()?;
+        let cores = lua.create_table()?;
+        if let Some(average) = cpu::get_average(&data.cpu_harvest) {
+            cpu.set("total", average.cpu_usage)?;
+            if let Some(breakdown) = &average.breakdown {
+                for (state, usage) in ["user", "system", "iowait", "irq", "steal"]
+                    .iter()
+                    .zip(breakdown.iter())
+                {
+                    cpu.set(*state, f64::from(*usage))?;
+                }
+            }
+        }
+        for cpu_data in &data.cpu_harvest {
+            if cpu_data.cpu_prefix != "AVG" {
+                cores.push(cpu_data.cpu_usage)?;
+            }
+        }
+        cpu.set("cores", cores)?;
+        globals.set("cpu", cpu)?;
+
+        for (global, harvest) in [("mem", &data.memory_harvest), ("swap", &data.swap_harvest)] {
+            let table = lua.create_table()?;
+            table.set("percent", harvest.use_percent)?;
+            table.set("used", harvest.mem_used_in_kib * 1024)?;
+            table.set("total", harvest.mem_total_in_kib * 1024)?;
+            globals.set(global, table)?;
+        }
+
+        let net = lua.create_table()?;
+        net.set("rx", data.network_harvest.rx)?;
+        net.set("tx", data.network_harvest.tx)?;
+        net.set("errors", data.network_harvest.errors_per_sec)?;
+        net.set("drops", data.network_harvest.drops_per_sec)?;
+        globals.set("net", net)?;
+
+        let load = lua.create_table()?;
+        for (name, value) in ["one", "five", "fifteen"]
+            .iter()
+            .zip(data.load_avg_harvest.iter())
+        {
+            load.set(*name, f64::from(*value))?;
+        }
+        globals.set("load", load)?;
+
+        let temp = lua.create_table()?;
+        let sensors = lua.create_table()?;
+        for sensor in &data.temp_harvest {
+            sensors.set(sensor.name.as_str(), f64::from(sensor.temperature))?;
+        }
+        temp.set(
+            "max",
+            data.temp_harvest
+                .iter()
+                .map(|sensor| f64::from(sensor.temperature))
+                .reduce(f64::max),
+        )?;
+        temp.set("sensors", sensors)?;
+        globals.set("temp", temp)?;
+
+        let disk = lua.create_table()?;
+        let mounts = lua.create_table()?;
+        for disk_harvest in &data.disk_harvest {
+            if let (Some(used), Some(total)) = (disk_harvest.used_space, disk_harvest.total_space) {
+                if total > 0 {
+                    mounts.set(
+                        disk_harvest.mount_point.as_str(),
+                        used as f64 / total as f64 * 100.0,
+                    )?;
+                }
+            }
+        }
+        disk.set(
+            "max",
+            mounts
+                .clone()
+                .pairs::<String, f64>()
+                .filter_map(|pair| pair.ok().map(|(_, percent)| percent))
+                .reduce(f64::max),
+        )?;
+        disk.set("mounts", mounts)?;
+        globals.set("disk", disk)?;
+
+        let plugins = lua.create_table()?;
+        for plugin in &data.plugin_harvest {
+            let series = lua.create_table()?;
+            for plugin_series in &plugin.series {
+                series.set(plugin_series.name.as_str(), plugin_series.value)?;
+            }
+            plugins.set(plugin.name.as_str(), series)?;
+        }
+        globals.set("plugins", plugins)?;
+
+        let derived = lua.create_table()?;
+        globals.set("derived", derived.clone())?;
+
+        Ok(derived)
+    }
+}
+
+/// Without the `derived_metrics` feature, there are never any metrics to work out, as the config
+/// file can't have any.
+#[cfg(not(feature = "derived_metrics"))]
+#[derive(Debug, Default)]
+pub struct DerivedMetrics;
+
+#[cfg(not(feature = "derived_metrics"))]
+impl DerivedMetrics {
+    pub fn new(_configs: &[DerivedMetricConfig]) -> Result<Self, String> {
+        Ok(DerivedMetrics)
+    }
+
+    pub fn evaluate(&self, _data: &DataCollection) -> Vec<PluginHarvest> {
+        Vec::new()
+    }
+}
+
+/// Returns the first line of a Lua error, which leaves off the stack traceback.
+#[cfg(feature = "derived_metrics")]
+fn get_error_message(err: mlua::Error) -> String {
+    err.to_string()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(all(test, feature = "derived_metrics"))]
+mod test {
+    use super::*;
+    use crate::app::data_harvester::cpu::CpuData;
+
+    fn metric(name: &str, expression: &str) -> DerivedMetricConfig {
+        DerivedMetricConfig {
+            name: name.to_string(),
+            expression: expression.to_string(),
+            unit: None,
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let derived_metrics = DerivedMetrics::new(&[
+            metric("cpu_minus_steal", "cpu.total - cpu.steal"),
+            metric("doubled", "derived.cpu_minus_steal * 2"),
+            metric("missing", "swap.percent"),
+            metric("broken", "mem.nope + 1"),
+            metric("text", "local name = 'cpu'\nreturn name"),
+        ])
+        .unwrap();
+
+        let data = DataCollection {
+            cpu_harvest: vec![CpuData {
+                cpu_prefix: "AVG".to_string(),
+                cpu_count: None,
+                cpu_usage: 50.0,
+                breakdown: Some([30.0, 10.0, 0.0, 0.0, 10.0]),
+            }],
+            ..DataCollection::default()
+        };
+
+        let harvest = derived_metrics.evaluate(&data);
+        let values = harvest
+            .iter()
+            .map(|metric| metric.series.first().map(|series| series.value))
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![Some(40.0), Some(80.0), None, None, None]);
+        assert!(harvest[..3].iter().all(|metric| metric.error.is_none()));
+        assert_eq!(
+            harvest[3].error.as_deref(),
+            Some("runtime error: [string \"broken\"]:1: attempt to perform arithmetic on a nil value (field 'nope')")
+        );
+        assert_eq!(
+            harvest[4].error.as_deref(),
+            Some("returned a string, not a number")
+        );
+    }
+
+    #[test]
+    fn test_budget() {
+        let derived_metrics = DerivedMetrics::new(&[
+            metric("forever", "while true do end"),
+            metric("huge", "#string.rep('x', 1e10)"),
+            metric("fine", "1 + 1"),
+        ])
+        .unwrap();
+
+        let harvest = derived_metrics.evaluate(&DataCollection::default());
+        assert!(harvest[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("stopped after 1000000 instructions"));
+        assert!(harvest[1].error.is_some());
+        assert_eq!(harvest[2].series[0].value, 2.0);
+    }
+
+    #[test]
+    fn test_invalid_expression() {
+        assert!(DerivedMetrics::new(&[metric("bad", "cpu.total +")]).is_err());
+        assert!(DerivedMetrics::new(&[metric("io", "io.open('x')")])
+            .unwrap()
+            .evaluate(&DataCollection::default())[0]
+            .error
+            .is_some());
+    }
+}
@@ -0,0 +1,257 @@
+//! Hooks, which are external commands from the `[[hooks]]` section of the config file that are
+//! run when a key is pressed. Details about the selected process or disk are passed to the
+//! command through placeholders, like `gdb -p {pid}`.
+
+use std::process::Command;
+
+use crate::Pid;
+
+/// The key that runs a hook.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HookKey {
+    /// A sequence of printable keys, which is handled by the keybinding engine.
+    Sequence(Vec<char>),
+    /// A function key, like F9.
+    Function(u8),
+}
+
+impl HookKey {
+    /// Parses a key from the config file. `F1` to `F24` are function keys, and anything else is a
+    /// sequence of keys.
+    pub fn parse(key: &str) -> Self {
+        match key.strip_prefix('F').and_then(|number| number.parse().ok()) {
+            Some(number @ 1..=24) => HookKey::Function(number),
+            _ => HookKey::Sequence(key.chars().collect()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hook {
+    pub key: HookKey,
+    pub command: String,
+    /// Whether to wait for Enter to be pressed after the command finishes, so its output can be
+    /// read before bottom is shown again.
+    pub pause: bool,
+}
+
+/// What was selected when a hook was run, which is used to fill in its placeholders.
+#[derive(Debug, Default)]
+pub struct HookContext {
+    pub pid: Option<Pid>,
+    pub name: Option<String>,
+    pub user: Option<String>,
+    pub mount: Option<String>,
+}
+
+/// Fills in the placeholders of a command: `{pid}`, `{name}`, and `{user}` for the selected
+/// process, and `{mount}` for the selected disk. Values other than PIDs are quoted for the shell,
+/// as process names can contain anything. Other text in braces is left alone.
+///
+/// Fails if a placeholder is used but nothing it refers to is selected. On Unix, it also fails if
+/// a quoted placeholder is inside quotes in the command, which would undo the quoting. On
+/// Windows, where `cmd` has no quoting that works for every value, it fails if a value has
+/// characters that `cmd` would interpret even in quotes.
+pub fn expand_command(command: &str, context: &HookContext) -> Result<String, String> {
+    let mut expanded = String::with_capacity(command.len());
+    let mut remaining = command;
+    #[cfg(target_family = "unix")]
+    let mut quotes = ShellQuotes::default();
+
+    while let Some(start) = remaining.find('{') {
+        expanded.push_str(&remaining[..start]);
+        #[cfg(target_family = "unix")]
+        quotes.read(&remaining[..start]);
+        remaining = &remaining[start..];
+
+        let placeholder = remaining.find('}').map(|end| &remaining[1..end]);
+        let value = match placeholder {
+            Some("pid") => Some(context.pid.map(|pid| pid.to_string())),
+            Some("name") => Some(context.name.clone()),
+            Some("user") => Some(context.user.clone()),
+            Some("mount") => Some(context.mount.clone()),
+            _ => None,
+        };
+
+        match (placeholder, value) {
+            (Some(placeholder), Some(value)) => {
+                let value = value.ok_or_else(|| {
+                    if placeholder == "mount" {
+                        "The hook needs a disk to be selected.".to_string()
+                    } else {
+                        "The hook needs a process to be selected.".to_string()
+                    }
+                })?;
+                if placeholder == "pid" {
+                    expanded.push_str(&value);
+                } else {
+                    #[cfg(target_family = "unix")]
+                    if quotes.is_quoted() {
+                        return Err(format!(
+                            "The hook wasn't run, as {{{}}} is inside quotes. Remove them, as the value is already quoted.",
+                            placeholder
+                        ));
+                    }
+                    expanded.push_str(&quote_for_shell(&value)?);
+                }
+                remaining = &remaining[placeholder.len() + 2..];
+            }
+            _ => {
+                expanded.push('{');
+                #[cfg(target_family = "unix")]
+                quotes.read("{");
+                remaining = &remaining[1..];
+            }
+        }
+    }
+    expanded.push_str(remaining);
+
+    Ok(expanded)
+}
+
+/// Tracks whether `sh` is inside quotes as it reads a command.
+#[cfg(target_family = "unix")]
+#[derive(Default)]
+struct ShellQuotes {
+    quote: Option<char>,
+    is_escaped: bool,
+}
+
+#[cfg(target_family = "unix")]
+impl ShellQuotes {
+    fn read(&mut self, text: &str) {
+        for c in text.chars() {
+            if self.is_escaped {
+                self.is_escaped = false;
+                continue;
+            }
+            match (self.quote, c) {
+                (Some('\''), '\'') => self.quote = None,
+                (Some('\''), _) => {}
+                (_, '\\') => self.is_escaped = true,
+                (Some('"'), '"') => self.quote = None,
+                (None, '\'' | '"') => self.quote = Some(c),
+                _ => {}
+            }
+        }
+    }
+
+    fn is_quoted(&self) -> bool {
+        self.quote.is_some()
+    }
+}
+
+#[cfg(target_family = "unix")]
+fn quote_for_shell(value: &str) -> Result<String, String> {
+    Ok(format!("'{}'", value.replace('\'', r"'\''")))
+}
+
+#[cfg(not(target_family = "unix"))]
+fn quote_for_shell(value: &str) -> Result<String, String> {
+    // Nothing stops `cmd` from expanding variables or, outside of quotes, running other commands,
+    // so values that could do either are refused rather than escaped.
+    if value
+        .chars()
+        .any(|c| c.is_control() || "\"%!^&|<>()".contains(c))
+    {
+        return Err(format!(
+            "The hook wasn't run, as '{}' has characters that cmd would interpret.",
+            value
+        ));
+    }
+
+    // A backslash before the closing quote would escape it for the program that's run.
+    let num_trailing = value.len() - value.trim_end_matches('\\').len();
+    Ok(format!("\"{}{}\"", value, "\\".repeat(num_trailing)))
+}
+
+/// Runs a command through the shell and waits for it to finish. Returns an error message if it
+/// couldn't be run or didn't succeed.
+pub fn run_command(command: &str) -> Result<(), String> {
+    #[cfg(target_family = "unix")]
+    let mut shell = {
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    };
+
+    #[cfg(not(target_family = "unix"))]
+    let mut shell = {
+        use std::os::windows::process::CommandExt;
+
+        // `cmd` doesn't split its command line the way `arg` quotes it, so it's passed as is.
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").raw_arg(command);
+        shell
+    };
+
+    match shell.status() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(match status.code() {
+            Some(code) => format!("The hook exited with code {}.", code),
+            None => "The hook was stopped by a signal.".to_string(),
+        }),
+        Err(err) => Err(format!("The hook couldn't be run: {}", err)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_hook_key() {
+        assert_eq!(HookKey::parse("F9"), HookKey::Function(9));
+        assert_eq!(HookKey::parse("F24"), HookKey::Function(24));
+        assert_eq!(HookKey::parse("F"), HookKey::Sequence(vec!['F']));
+        assert_eq!(HookKey::parse("F0"), HookKey::Sequence(vec!['F', '0']));
+        assert_eq!(HookKey::parse("gd"), HookKey::Sequence(vec!['g', 'd']));
+    }
+
+    #[test]
+    fn test_expand_command() {
+        let context = HookContext {
+            pid: Some(42),
+            name: Some("it's".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            expand_command("gdb -p {pid}", &context),
+            Ok("gdb -p 42".to_string())
+        );
+        #[cfg(target_family = "unix")]
+        assert_eq!(
+            expand_command("echo {name}", &context),
+            Ok(r"echo 'it'\''s'".to_string())
+        );
+        assert_eq!(
+            expand_command("awk '{print $1}' {unknown} {", &context),
+            Ok("awk '{print $1}' {unknown} {".to_string())
+        );
+        assert!(expand_command("df {mount}", &context).is_err());
+        assert!(expand_command("id {user}", &context).is_err());
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_expand_command_in_quotes() {
+        let context = HookContext {
+            pid: Some(42),
+            name: Some("$(reboot)".to_string()),
+            ..Default::default()
+        };
+
+        assert!(expand_command("echo \"{name}\"", &context).is_err());
+        assert!(expand_command("echo '{name}'", &context).is_err());
+        assert!(expand_command("echo \"a\\\"b {name}\"", &context).is_err());
+        assert_eq!(
+            expand_command("echo \"pid {pid}\" {name}", &context),
+            Ok("echo \"pid 42\" '$(reboot)'".to_string())
+        );
+        assert_eq!(
+            expand_command("echo 'it'\\''s' \\\" {name}", &context),
+            Ok("echo 'it'\\''s' \\\" '$(reboot)'".to_string())
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! Fetches extra details about a single process, such as its environment, open files, and full
+//! command line.
+//!
+//! Unlike the rest of the process data, none of this is gathered in the normal harvest loop;
+//! it is only read on demand when a process is inspected, as it can be fairly expensive and is
+//! only ever needed for one process at a time.
+
+use crate::Pid;
+
+/// A summary of a process' memory mappings.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MemoryMapsSummary {
+    pub num_maps: usize,
+    pub total_kib: u64,
+    /// Mappings not backed by a file, including the heap and stack.
+    pub anonymous_kib: u64,
+    pub file_backed_kib: u64,
+    /// The number of distinct files that are mapped.
+    pub num_files: usize,
+}
+
+/// Details about a process. Each part is read separately, so one failing (usually due to a lack
+/// of permissions) does not prevent the others from being shown; failures hold an error message.
+pub struct ProcessDetails {
+    pub cwd: Result<String, String>,
+    pub environment: Result<Vec<String>, String>,
+    pub open_files: Result<Vec<String>, String>,
+    pub memory_maps: Result<MemoryMapsSummary, String>,
+}
+
+/// The full command line of a process, along with where it was run from.
+pub struct ProcessCommand {
+    /// The command as shown in the process widget.
+    pub command: String,
+    pub arguments: Result<Vec<String>, String>,
+    pub cwd: Result<String, String>,
+}
+
+#[cfg(target_os = "linux")]
+fn get_cwd(proc_path: &std::path::Path) -> Result<String, String> {
+    std::fs::read_link(proc_path.join("cwd"))
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(target_os = "linux")]
+pub fn inspect_process(pid: Pid) -> ProcessDetails {
+    let proc_path = std::path::PathBuf::from(format!("/proc/{}", pid));
+
+    let cwd = get_cwd(&proc_path);
+
+    let environment = std::fs::read(proc_path.join("environ"))
+        .map(|environ| parse_environ(&environ))
+        .map_err(|err| err.to_string());
+
+    let open_files = std::fs::read_dir(proc_path.join("fd"))
+        .map(|entries| {
+            let mut open_files: Vec<(u64, String)> = entries
+                .filter_map(|entry| {
+                    let entry = entry.ok()?;
+                    let fd = entry.file_name().to_str()?.parse::<u64>().ok()?;
+                    let target = std::fs::read_link(entry.path()).ok()?;
+                    Some((fd, target.to_string_lossy().to_string()))
+                })
+                .collect();
+            open_files.sort_unstable_by_key(|(fd, _)| *fd);
+
+            open_files
+                .into_iter()
+                .map(|(fd, target)| format!("{:>5}  {}", fd, target))
+                .collect()
+        })
+        .map_err(|err| err.to_string());
+
+    let memory_maps = std::fs::read_to_string(proc_path.join("maps"))
+        .map(|maps| parse_memory_maps(&maps))
+        .map_err(|err| err.to_string());
+
+    ProcessDetails {
+        cwd,
+        environment,
+        open_files,
+        memory_maps,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn inspect_process(_pid: Pid) -> ProcessDetails {
+    const UNSUPPORTED: &str = "Not supported on this platform.";
+
+    ProcessDetails {
+        cwd: Err(UNSUPPORTED.to_string()),
+        environment: Err(UNSUPPORTED.to_string()),
+        open_files: Err(UNSUPPORTED.to_string()),
+        memory_maps: Err(UNSUPPORTED.to_string()),
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn get_process_command(pid: Pid, command: &str) -> ProcessCommand {
+    let proc_path = std::path::PathBuf::from(format!("/proc/{}", pid));
+
+    ProcessCommand {
+        command: command.to_string(),
+        arguments: std::fs::read(proc_path.join("cmdline"))
+            .map(|cmdline| parse_cmdline(&cmdline))
+            .map_err(|err| err.to_string()),
+        cwd: get_cwd(&proc_path),
+    }
+}
+
+/// Outside of Linux, the arguments are approximated by splitting the command on whitespace, so
+/// arguments that contain spaces will be split up.
+#[cfg(not(target_os = "linux"))]
+pub fn get_process_command(_pid: Pid, command: &str) -> ProcessCommand {
+    ProcessCommand {
+        command: command.to_string(),
+        arguments: Ok(command.split_whitespace().map(String::from).collect()),
+        cwd: Err("Not supported on this platform.".to_string()),
+    }
+}
+
+/// Parses the contents of `/proc/<pid>/cmdline`, which is a list of null-terminated arguments.
+/// Kernel threads have no arguments.
+#[cfg(target_os = "linux")]
+fn parse_cmdline(cmdline: &[u8]) -> Vec<String> {
+    let cmdline = cmdline.strip_suffix(&[0]).unwrap_or(cmdline);
+    if cmdline.is_empty() {
+        Vec::new()
+    } else {
+        cmdline
+            .split(|byte| *byte == 0)
+            .map(|argument| String::from_utf8_lossy(argument).to_string())
+            .collect()
+    }
+}
+
+/// Parses the contents of `/proc/<pid>/environ`, which is a list of null-separated `KEY=VALUE`
+/// entries. The entries are returned sorted.
+#[cfg(target_os = "linux")]
+fn parse_environ(environ: &[u8]) -> Vec<String> {
+    let mut environment: Vec<String> = environ
+        .split(|byte| *byte == 0)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| String::from_utf8_lossy(entry).to_string())
+        .collect();
+    environment.sort();
+
+    environment
+}
+
+/// Parses the contents of `/proc/<pid>/maps`. Each line looks like
+/// `7f1c2a000000-7f1c2a021000 rw-p 00000000 00:00 0    [heap]`, where the last column is
+/// either a path, a pseudo-path in brackets, or empty for anonymous mappings.
+#[cfg(target_os = "linux")]
+fn parse_memory_maps(maps: &str) -> MemoryMapsSummary {
+    let mut summary = MemoryMapsSummary::default();
+    let mut files = std::collections::HashSet::new();
+
+    for line in maps.lines() {
+        let mut columns = line.split_whitespace();
+        let range = match columns.next() {
+            Some(range) => range,
+            None => continue,
+        };
+        let size_kib = match range.split_once('-') {
+            Some((start, end)) => {
+                match (u64::from_str_radix(start, 16), u64::from_str_radix(end, 16)) {
+                    (Ok(start), Ok(end)) => end.saturating_sub(start) / 1024,
+                    _ => continue,
+                }
+            }
+            None => continue,
+        };
+
+        summary.num_maps += 1;
+        summary.total_kib += size_kib;
+
+        // Skip the permissions, offset, device, and inode columns to get to the path.
+        match columns.nth(4) {
+            Some(path) if path.starts_with('/') => {
+                summary.file_backed_kib += size_kib;
+                files.insert(path);
+            }
+            _ => {
+                summary.anonymous_kib += size_kib;
+            }
+        }
+    }
+    summary.num_files = files.len();
+
+    summary
+}
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_environ() {
+        assert_eq!(
+            parse_environ(b"TERM=xterm\0HOME=/root\0\0"),
+            vec!["HOME=/root".to_string(), "TERM=xterm".to_string()]
+        );
+        assert!(parse_environ(b"").is_empty());
+    }
+
+    #[test]
+    fn test_parse_cmdline() {
+        assert_eq!(
+            parse_cmdline(b"java\0-Xmx2g\0\0-jar\0app with spaces.jar\0"),
+            vec![
+                "java".to_string(),
+                "-Xmx2g".to_string(),
+                "".to_string(),
+                "-jar".to_string(),
+                "app with spaces.jar".to_string()
+            ]
+        );
+        assert!(parse_cmdline(b"").is_empty());
+    }
+
+    #[test]
+    fn test_parse_memory_maps() {
+        let maps = "\
+55d0c4a00000-55d0c4a40000 r--p 00000000 08:01 1311  /usr/bin/btm
+55d0c4a40000-55d0c4b00000 r-xp 00040000 08:01 1311  /usr/bin/btm
+55d0c5e00000-55d0c5f00000 rw-p 00000000 00:00 0     [heap]
+7f1c2a000000-7f1c2a002000 rw-p 00000000 00:00 0
+7f1c2b000000-7f1c2b100000 r-xp 00000000 08:01 2090  /usr/lib/libc.so.6
+";
+
+        assert_eq!(
+            parse_memory_maps(maps),
+            MemoryMapsSummary {
+                num_maps: 5,
+                total_kib: 256 + 768 + 1024 + 8 + 1024,
+                anonymous_kib: 1024 + 8,
+                file_backed_kib: 256 + 768 + 1024,
+                num_files: 2,
+            }
+        );
+    }
+}
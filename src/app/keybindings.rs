@@ -0,0 +1,700 @@
+//! A small keybinding engine for the main (non-dialog) key bindings. This supports multi-key
+//! sequences (like `gg`), count prefixes (like `5j`), and rebinding actions through the
+//! `[keybindings]` section of the config file.
+
+use std::collections::HashMap;
+
+use crate::{
+    app::hooks::{Hook, HookKey},
+    utils::error::{BottomError, Result},
+};
+
+/// An action that can be bound to a key sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyAction {
+    Search,
+    KillProcess,
+    SkipToFirst,
+    SkipToLast,
+    MoveUp,
+    MoveDown,
+    Freeze,
+    SortByCpu,
+    SortByMem,
+    SortByPid,
+    SortByName,
+    ToggleCommand,
+    Help,
+    WidgetLeft,
+    WidgetRight,
+    WidgetUp,
+    WidgetDown,
+    ToggleTree,
+    ZoomIn,
+    ZoomOut,
+    ResetZoom,
+    ExpandWidget,
+    EditLayout,
+    PinProcess,
+    ToggleSort,
+    InvertSort,
+    TogglePercentages,
+    CycleFilter,
+    ToggleNetworkTotals,
+    InspectProcess,
+    ToggleUserGrouping,
+    ChangePriority,
+    ChangeAffinity,
+    SuspendProcess,
+    ResumeProcess,
+    RestartUnit,
+    StopUnit,
+    TogglePseudoFilesystems,
+    ScrollLeft,
+    ScrollRight,
+    ShowCommand,
+    ShowThreads,
+    CycleColourScheme,
+    ShowOptions,
+    NextLayout,
+    PreviousLayout,
+    NextTab,
+    PreviousTab,
+    ScrollLayoutUp,
+    ScrollLayoutDown,
+    ExportGraph,
+    CopyRow,
+    CopyTable,
+    NextHost,
+    PreviousHost,
+    ShowDiagnostics,
+    ToggleFollow,
+    ToggleMark,
+    MarkRange,
+    QueueKill,
+    ShowKillQueue,
+    ResetPeaks,
+    ToggleCompare,
+    TogglePause,
+    ToggleDebugOverlay,
+    /// Runs the hook at this index in the config file.
+    RunHook(usize),
+}
+
+/// Every action, along with its name in the config file.
+const ACTION_NAMES: [(&str, KeyAction); 65] = [
+    ("search", KeyAction::Search),
+    ("kill_process", KeyAction::KillProcess),
+    ("skip_to_first", KeyAction::SkipToFirst),
+    ("skip_to_last", KeyAction::SkipToLast),
+    ("move_up", KeyAction::MoveUp),
+    ("move_down", KeyAction::MoveDown),
+    ("freeze", KeyAction::Freeze),
+    ("sort_by_cpu", KeyAction::SortByCpu),
+    ("sort_by_mem", KeyAction::SortByMem),
+    ("sort_by_pid", KeyAction::SortByPid),
+    ("sort_by_name", KeyAction::SortByName),
+    ("toggle_command", KeyAction::ToggleCommand),
+    ("help", KeyAction::Help),
+    ("widget_left", KeyAction::WidgetLeft),
+    ("widget_right", KeyAction::WidgetRight),
+    ("widget_up", KeyAction::WidgetUp),
+    ("widget_down", KeyAction::WidgetDown),
+    ("toggle_tree", KeyAction::ToggleTree),
+    ("zoom_in", KeyAction::ZoomIn),
+    ("zoom_out", KeyAction::ZoomOut),
+    ("reset_zoom", KeyAction::ResetZoom),
+    ("expand_widget", KeyAction::ExpandWidget),
+    ("edit_layout", KeyAction::EditLayout),
+    ("pin_process", KeyAction::PinProcess),
+    ("toggle_sort", KeyAction::ToggleSort),
+    ("invert_sort", KeyAction::InvertSort),
+    ("toggle_percentages", KeyAction::TogglePercentages),
+    ("cycle_filter", KeyAction::CycleFilter),
+    ("toggle_network_totals", KeyAction::ToggleNetworkTotals),
+    ("inspect_process", KeyAction::InspectProcess),
+    ("toggle_user_grouping", KeyAction::ToggleUserGrouping),
+    ("change_priority", KeyAction::ChangePriority),
+    ("change_affinity", KeyAction::ChangeAffinity),
+    ("suspend_process", KeyAction::SuspendProcess),
+    ("resume_process", KeyAction::ResumeProcess),
+    ("restart_unit", KeyAction::RestartUnit),
+    ("stop_unit", KeyAction::StopUnit),
+    (
+        "toggle_pseudo_filesystems",
+        KeyAction::TogglePseudoFilesystems,
+    ),
+    ("scroll_left", KeyAction::ScrollLeft),
+    ("scroll_right", KeyAction::ScrollRight),
+    ("show_command", KeyAction::ShowCommand),
+    ("show_threads", KeyAction::ShowThreads),
+    ("cycle_color_scheme", KeyAction::CycleColourScheme),
+    ("show_options", KeyAction::ShowOptions),
+    ("next_layout", KeyAction::NextLayout),
+    ("previous_layout", KeyAction::PreviousLayout),
+    ("next_tab", KeyAction::NextTab),
+    ("previous_tab", KeyAction::PreviousTab),
+    ("scroll_layout_up", KeyAction::ScrollLayoutUp),
+    ("scroll_layout_down", KeyAction::ScrollLayoutDown),
+    ("export_graph", KeyAction::ExportGraph),
+    ("copy_row", KeyAction::CopyRow),
+    ("copy_table", KeyAction::CopyTable),
+    ("next_host", KeyAction::NextHost),
+    ("previous_host", KeyAction::PreviousHost),
+    ("show_diagnostics", KeyAction::ShowDiagnostics),
+    ("toggle_follow", KeyAction::ToggleFollow),
+    ("toggle_mark", KeyAction::ToggleMark),
+    ("mark_range", KeyAction::MarkRange),
+    ("queue_kill", KeyAction::QueueKill),
+    ("show_kill_queue", KeyAction::ShowKillQueue),
+    ("reset_peaks", KeyAction::ResetPeaks),
+    ("toggle_compare", KeyAction::ToggleCompare),
+    ("toggle_pause", KeyAction::TogglePause),
+    ("toggle_debug_overlay", KeyAction::ToggleDebugOverlay),
+];
+
+const DEFAULT_KEYBINDINGS: [(&str, KeyAction); 69] = [
+    ("/", KeyAction::Search),
+    ("dd", KeyAction::KillProcess),
+    ("gg", KeyAction::SkipToFirst),
+    ("G", KeyAction::SkipToLast),
+    ("k", KeyAction::MoveUp),
+    ("j", KeyAction::MoveDown),
+    ("f", KeyAction::Freeze),
+    ("c", KeyAction::SortByCpu),
+    ("m", KeyAction::SortByMem),
+    ("p", KeyAction::SortByPid),
+    ("n", KeyAction::SortByName),
+    ("P", KeyAction::ToggleCommand),
+    ("?", KeyAction::Help),
+    ("H", KeyAction::WidgetLeft),
+    ("A", KeyAction::WidgetLeft),
+    ("L", KeyAction::WidgetRight),
+    ("D", KeyAction::WidgetRight),
+    ("K", KeyAction::WidgetUp),
+    ("W", KeyAction::WidgetUp),
+    ("J", KeyAction::WidgetDown),
+    ("S", KeyAction::WidgetDown),
+    ("t", KeyAction::ToggleTree),
+    ("+", KeyAction::ZoomIn),
+    ("-", KeyAction::ZoomOut),
+    ("=", KeyAction::ResetZoom),
+    ("e", KeyAction::ExpandWidget),
+    ("E", KeyAction::EditLayout),
+    ("v", KeyAction::PinProcess),
+    ("s", KeyAction::ToggleSort),
+    ("I", KeyAction::InvertSort),
+    ("%", KeyAction::TogglePercentages),
+    ("F", KeyAction::CycleFilter),
+    ("T", KeyAction::ToggleNetworkTotals),
+    ("i", KeyAction::InspectProcess),
+    ("u", KeyAction::ToggleUserGrouping),
+    ("r", KeyAction::ChangePriority),
+    ("a", KeyAction::ChangeAffinity),
+    ("x", KeyAction::SuspendProcess),
+    ("X", KeyAction::ResumeProcess),
+    ("Ur", KeyAction::RestartUnit),
+    ("Us", KeyAction::StopUnit),
+    ("V", KeyAction::TogglePseudoFilesystems),
+    ("h", KeyAction::ScrollLeft),
+    ("l", KeyAction::ScrollRight),
+    ("C", KeyAction::ShowCommand),
+    ("w", KeyAction::ShowThreads),
+    ("b", KeyAction::CycleColourScheme),
+    ("o", KeyAction::ShowOptions),
+    ("gt", KeyAction::NextLayout),
+    ("gT", KeyAction::PreviousLayout),
+    ("]", KeyAction::NextTab),
+    ("[", KeyAction::PreviousTab),
+    ("{", KeyAction::ScrollLayoutUp),
+    ("}", KeyAction::ScrollLayoutDown),
+    ("ge", KeyAction::ExportGraph),
+    ("y", KeyAction::CopyRow),
+    ("Y", KeyAction::CopyTable),
+    ("gh", KeyAction::NextHost),
+    ("gH", KeyAction::PreviousHost),
+    ("gd", KeyAction::ShowDiagnostics),
+    ("Z", KeyAction::ToggleFollow),
+    (" ", KeyAction::ToggleMark),
+    ("M", KeyAction::MarkRange),
+    ("dq", KeyAction::QueueKill),
+    ("gk", KeyAction::ShowKillQueue),
+    ("gp", KeyAction::ResetPeaks),
+    ("gc", KeyAction::ToggleCompare),
+    ("gf", KeyAction::TogglePause),
+    ("gs", KeyAction::ToggleDebugOverlay),
+];
+
+/// The largest count prefix that will be accepted.
+const MAX_COUNT: usize = 9999;
+
+impl KeyAction {
+    pub fn from_name(name: &str) -> Option<Self> {
+        ACTION_NAMES
+            .iter()
+            .find(|(action_name, _)| *action_name == name)
+            .map(|(_, action)| *action)
+    }
+
+    /// Whether a count prefix repeats this action. Other actions just ignore the count.
+    pub fn is_repeatable(self) -> bool {
+        matches!(
+            self,
+            KeyAction::MoveUp
+                | KeyAction::MoveDown
+                | KeyAction::WidgetLeft
+                | KeyAction::WidgetRight
+                | KeyAction::WidgetUp
+                | KeyAction::WidgetDown
+                | KeyAction::ZoomIn
+                | KeyAction::ZoomOut
+                | KeyAction::ScrollLeft
+                | KeyAction::ScrollRight
+                | KeyAction::NextLayout
+                | KeyAction::PreviousLayout
+                | KeyAction::NextTab
+                | KeyAction::PreviousTab
+                | KeyAction::ScrollLayoutUp
+                | KeyAction::ScrollLayoutDown
+        )
+    }
+
+    /// Whether the action can be done on this platform. Unsupported actions aren't bound to
+    /// anything, even if they're given a key in the config file.
+    pub fn is_supported(self) -> bool {
+        match self {
+            KeyAction::ShowThreads => cfg!(target_os = "linux"),
+            _ => true,
+        }
+    }
+
+    /// Whether the action acts on the processes or services of the machine bottom runs on, which
+    /// aren't the ones shown when connected to a remote host.
+    pub fn is_local_only(self) -> bool {
+        matches!(
+            self,
+            KeyAction::KillProcess
+                | KeyAction::QueueKill
+                | KeyAction::ShowKillQueue
+                | KeyAction::SuspendProcess
+                | KeyAction::ResumeProcess
+                | KeyAction::ChangePriority
+                | KeyAction::ChangeAffinity
+                | KeyAction::InspectProcess
+                | KeyAction::ShowCommand
+                | KeyAction::ShowThreads
+                | KeyAction::RestartUnit
+                | KeyAction::StopUnit
+                | KeyAction::RunHook(_)
+        )
+    }
+}
+
+/// The result of passing a key to [`KeyBindings::on_char`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum KeyResult {
+    /// The key is part of an unfinished sequence or count.
+    Pending,
+    /// A sequence was completed, along with how many times to perform it.
+    Action(KeyAction, usize),
+    /// The key isn't bound to anything.
+    Unbound,
+}
+
+pub struct KeyBindings {
+    bindings: Vec<(Vec<char>, KeyAction)>,
+    pending: Vec<char>,
+    count: Option<usize>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            bindings: DEFAULT_KEYBINDINGS
+                .iter()
+                .filter(|(_, action)| action.is_supported())
+                .map(|(keys, action)| (keys.chars().collect(), *action))
+                .collect(),
+            pending: Vec::new(),
+            count: None,
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Creates the default bindings, with each action in `overrides` rebound to the given key
+    /// sequence instead. Any default bindings that conflict with an override are dropped.
+    pub fn new(overrides: &HashMap<String, String>) -> Result<Self> {
+        let mut key_bindings = KeyBindings::default();
+        let mut user_bindings: Vec<(Vec<char>, KeyAction)> = Vec::new();
+
+        for (name, keys) in overrides {
+            let action = KeyAction::from_name(name).ok_or_else(|| {
+                BottomError::ConfigError(format!("\"{}\" is not a valid keybinding action.", name))
+            })?;
+            let keys = parse_keys(keys);
+            check_keys(&format!("the keybinding for \"{}\"", name), &keys)?;
+
+            if let Some((_, other)) = user_bindings
+                .iter()
+                .find(|(other_keys, _)| is_conflicting(other_keys, &keys))
+            {
+                let other_name = ACTION_NAMES
+                    .iter()
+                    .find(|(_, action)| action == other)
+                    .map(|(name, _)| *name)
+                    .unwrap_or_default();
+                return Err(BottomError::ConfigError(format!(
+                    "the keybindings for \"{}\" and \"{}\" conflict.",
+                    name, other_name
+                )));
+            }
+
+            user_bindings.push((keys, action));
+        }
+
+        key_bindings.bindings.retain(|(keys, action)| {
+            user_bindings.iter().all(|(user_keys, user_action)| {
+                user_action != action && !is_conflicting(user_keys, keys)
+            })
+        });
+        key_bindings.bindings.extend(
+            user_bindings
+                .into_iter()
+                .filter(|(_, action)| action.is_supported()),
+        );
+
+        Ok(key_bindings)
+    }
+
+    /// Binds the hooks that use a key sequence, replacing any bindings they conflict with.
+    pub fn add_hooks(&mut self, hooks: &[Hook]) -> Result<()> {
+        let mut hook_bindings: Vec<(Vec<char>, KeyAction)> = Vec::new();
+
+        for (index, hook) in hooks.iter().enumerate() {
+            if let HookKey::Sequence(keys) = &hook.key {
+                check_keys(&format!("the key for the hook \"{}\"", hook.command), keys)?;
+
+                if let Some((_, KeyAction::RunHook(other))) = hook_bindings
+                    .iter()
+                    .find(|(other_keys, _)| is_conflicting(other_keys, keys))
+                {
+                    return Err(BottomError::ConfigError(format!(
+                        "the keys for the hooks \"{}\" and \"{}\" conflict.",
+                        hooks[*other].command, hook.command
+                    )));
+                }
+
+                hook_bindings.push((keys.clone(), KeyAction::RunHook(index)));
+            }
+        }
+
+        self.bindings.retain(|(keys, _)| {
+            hook_bindings
+                .iter()
+                .all(|(hook_keys, _)| !is_conflicting(hook_keys, keys))
+        });
+        self.bindings.extend(hook_bindings);
+
+        Ok(())
+    }
+
+    /// Clears any unfinished sequence or count.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.count = None;
+    }
+
+    pub fn on_char(&mut self, caught_char: char) -> KeyResult {
+        if self.pending.is_empty() {
+            if let Some(digit) = caught_char.to_digit(10) {
+                // A leading zero isn't a count.
+                if digit != 0 || self.count.is_some() {
+                    let count = self.count.unwrap_or(0) * 10 + digit as usize;
+                    self.count = Some(count.min(MAX_COUNT));
+                    return KeyResult::Pending;
+                }
+            }
+        }
+
+        self.pending.push(caught_char);
+        if let Some(result) = self.match_pending() {
+            return result;
+        }
+
+        // If an unfinished sequence was broken, try again with just the new key.
+        if self.pending.len() > 1 {
+            self.reset();
+            self.pending.push(caught_char);
+            if let Some(result) = self.match_pending() {
+                return result;
+            }
+        }
+
+        self.reset();
+        KeyResult::Unbound
+    }
+
+    fn match_pending(&mut self) -> Option<KeyResult> {
+        if let Some((_, action)) = self.bindings.iter().find(|(keys, _)| *keys == self.pending) {
+            let result = KeyResult::Action(*action, self.count.unwrap_or(1));
+            self.reset();
+            Some(result)
+        } else if self
+            .bindings
+            .iter()
+            .any(|(keys, _)| keys.starts_with(&self.pending))
+        {
+            Some(KeyResult::Pending)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parses a key sequence from the config file. `Space` is the space bar, which is easier to read
+/// than `" "`, and anything else is a sequence of keys.
+fn parse_keys(keys: &str) -> Vec<char> {
+    if keys.eq_ignore_ascii_case("space") {
+        vec![' ']
+    } else {
+        keys.chars().collect()
+    }
+}
+
+/// Checks that a key sequence can be bound. `name` describes what it's for, in error messages.
+/// The space bar can be bound, but not other whitespace, like tabs, which are used elsewhere.
+fn check_keys(name: &str, keys: &[char]) -> Result<()> {
+    if keys.is_empty()
+        || keys
+            .iter()
+            .any(|c| c.is_control() || (c.is_whitespace() && *c != ' '))
+    {
+        Err(BottomError::ConfigError(format!(
+            "{} must be a non-empty sequence of printable keys.",
+            name
+        )))
+    } else if keys[0].is_ascii_digit() {
+        Err(BottomError::ConfigError(format!(
+            "{} cannot start with a number, as numbers are used as counts.",
+            name
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Two key sequences conflict if one is a prefix of the other, as the longer one could never be
+/// reached.
+fn is_conflicting(a: &[char], b: &[char]) -> bool {
+    a.starts_with(b) || b.starts_with(a)
+}
+
+/// Keys that are handled before the key bindings, and what they do instead.
+const HARDCODED_KEYS: [(char, &str); 3] = [
+    ('q', "always quits"),
+    ('h', "moves left in dialogs"),
+    ('l', "moves right in dialogs"),
+];
+
+/// Names of keys that can't be bound, which would otherwise be read as a sequence of letters.
+const UNBINDABLE_KEY_NAMES: [&str; 6] = ["esc", "escape", "enter", "tab", "backspace", "delete"];
+
+/// Returns warnings about the bindings in `overrides` that can be used, but won't work as
+/// expected because of keys that are handled before the key bindings.
+pub fn get_keybinding_warnings(overrides: &HashMap<String, String>) -> Vec<String> {
+    let mut overrides = overrides.iter().collect::<Vec<_>>();
+    overrides.sort();
+
+    let mut warnings = Vec::new();
+    for (name, keys) in overrides {
+        if UNBINDABLE_KEY_NAMES.contains(&keys.to_lowercase().as_str()) {
+            warnings.push(format!(
+                "\"{}\" in [keybindings] is bound to the keys \"{}\", as {} can't be bound.",
+                name, keys, keys
+            ));
+        } else if let Some((key, reason)) = HARDCODED_KEYS
+            .iter()
+            .find(|(key, _)| parse_keys(keys).contains(key))
+        {
+            warnings.push(format!(
+                "\"{}\" in [keybindings] uses \"{}\", which {}.",
+                name, key, reason
+            ));
+        }
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_key_sequences_and_counts() {
+        let mut key_bindings = KeyBindings::default();
+
+        assert_eq!(key_bindings.on_char('g'), KeyResult::Pending);
+        assert_eq!(
+            key_bindings.on_char('g'),
+            KeyResult::Action(KeyAction::SkipToFirst, 1)
+        );
+
+        assert_eq!(key_bindings.on_char('1'), KeyResult::Pending);
+        assert_eq!(key_bindings.on_char('0'), KeyResult::Pending);
+        assert_eq!(
+            key_bindings.on_char('j'),
+            KeyResult::Action(KeyAction::MoveDown, 10)
+        );
+
+        // A broken sequence falls back to the new key.
+        assert_eq!(key_bindings.on_char('d'), KeyResult::Pending);
+        assert_eq!(
+            key_bindings.on_char('k'),
+            KeyResult::Action(KeyAction::MoveUp, 1)
+        );
+
+        assert_eq!(key_bindings.on_char('0'), KeyResult::Unbound);
+        assert_eq!(key_bindings.on_char('z'), KeyResult::Unbound);
+    }
+
+    #[test]
+    fn test_key_overrides() {
+        let overrides = vec![
+            ("move_down".to_string(), "n".to_string()),
+            ("help".to_string(), "g".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let mut key_bindings = KeyBindings::new(&overrides).unwrap();
+
+        assert_eq!(
+            key_bindings.on_char('n'),
+            KeyResult::Action(KeyAction::MoveDown, 1)
+        );
+        assert_eq!(key_bindings.on_char('j'), KeyResult::Unbound);
+        assert_eq!(
+            key_bindings.on_char('g'),
+            KeyResult::Action(KeyAction::Help, 1)
+        );
+
+        let invalid = vec![("not_an_action".to_string(), "x".to_string())]
+            .into_iter()
+            .collect();
+        assert!(KeyBindings::new(&invalid).is_err());
+
+        let conflicting = vec![
+            ("move_down".to_string(), "x".to_string()),
+            ("move_up".to_string(), "xy".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        assert!(KeyBindings::new(&conflicting).is_err());
+    }
+
+    #[test]
+    fn test_default_keys_are_valid() {
+        for (keys, action) in DEFAULT_KEYBINDINGS.iter() {
+            let keys: Vec<char> = keys.chars().collect();
+            assert!(
+                check_keys("a default binding", &keys).is_ok(),
+                "the default binding for {:?} is invalid",
+                action
+            );
+        }
+
+        // An action can be rebound to its own default key, including the space bar.
+        for keys in [" ", "Space", "space"] {
+            let overrides = vec![("toggle_mark".to_string(), keys.to_string())]
+                .into_iter()
+                .collect();
+            let mut key_bindings = KeyBindings::new(&overrides).unwrap();
+            assert_eq!(
+                key_bindings.on_char(' '),
+                KeyResult::Action(KeyAction::ToggleMark, 1)
+            );
+        }
+
+        let tab = vec![("toggle_mark".to_string(), "\t".to_string())]
+            .into_iter()
+            .collect();
+        assert!(KeyBindings::new(&tab).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_actions() {
+        let expected = if cfg!(target_os = "linux") {
+            KeyResult::Action(KeyAction::ShowThreads, 1)
+        } else {
+            KeyResult::Unbound
+        };
+        assert_eq!(KeyBindings::default().on_char('w'), expected);
+
+        let overrides = vec![("show_threads".to_string(), "W".to_string())]
+            .into_iter()
+            .collect();
+        assert_eq!(KeyBindings::new(&overrides).unwrap().on_char('W'), expected);
+    }
+
+    #[test]
+    fn test_hooks() {
+        let hook = |key: &str, command: &str| Hook {
+            key: HookKey::parse(key),
+            command: command.to_string(),
+            pause: false,
+        };
+
+        let mut key_bindings = KeyBindings::default();
+        key_bindings
+            .add_hooks(&[hook("F9", "htop"), hook("gd", "gdb -p {pid}")])
+            .unwrap();
+        assert_eq!(key_bindings.on_char('g'), KeyResult::Pending);
+        assert_eq!(
+            key_bindings.on_char('d'),
+            KeyResult::Action(KeyAction::RunHook(1), 1)
+        );
+        // "gg" doesn't conflict with the hook, so it's kept.
+        assert_eq!(key_bindings.on_char('g'), KeyResult::Pending);
+        assert_eq!(
+            key_bindings.on_char('g'),
+            KeyResult::Action(KeyAction::SkipToFirst, 1)
+        );
+
+        // A hook replaces any binding that conflicts with it.
+        let mut key_bindings = KeyBindings::default();
+        key_bindings.add_hooks(&[hook("g", "htop")]).unwrap();
+        assert_eq!(
+            key_bindings.on_char('g'),
+            KeyResult::Action(KeyAction::RunHook(0), 1)
+        );
+
+        assert!(KeyBindings::default()
+            .add_hooks(&[hook("x", "a"), hook("xy", "b")])
+            .is_err());
+        assert!(KeyBindings::default()
+            .add_hooks(&[hook("1x", "a")])
+            .is_err());
+    }
+
+    #[test]
+    fn test_keybinding_warnings() {
+        let overrides = vec![
+            ("help".to_string(), "gq".to_string()),
+            ("search".to_string(), "Esc".to_string()),
+            ("move_down".to_string(), "n".to_string()),
+            ("toggle_tree".to_string(), "h".to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            get_keybinding_warnings(&overrides),
+            vec![
+                "\"help\" in [keybindings] uses \"q\", which always quits.",
+                "\"search\" in [keybindings] is bound to the keys \"Esc\", as Esc can't be bound.",
+                "\"toggle_tree\" in [keybindings] uses \"h\", which moves left in dialogs.",
+            ]
+        );
+    }
+}
@@ -0,0 +1,464 @@
+//! State for the layout editor dialog, which allows for adding, removing, moving, and resizing
+//! widgets at runtime, and then saving the result back to the config file.
+
+use crate::options::layout_options::{FinalWidget, Row, RowChildren};
+
+/// The widget types that can be cycled through in the layout editor, as they would
+/// be written in the config file.
+const EDITABLE_WIDGET_TYPES: &[&str] = &[
+    "cpu",
+    "mem",
+    "net",
+    "proc",
+    "temp",
+    "disk",
+    #[cfg(feature = "battery")]
+    "battery",
+    "power",
+    "pressure",
+    "load",
+    "cgroup",
+    "latency",
+    "frequency",
+    "events",
+    "info",
+    "top",
+    "users",
+    "empty",
+];
+
+/// Represents a single selectable line in the layout editor.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LayoutEntry {
+    /// A `[[row]]`.
+    Row(usize),
+    /// A `[[row.child]]`, which is either a widget or a column.
+    RowChild(usize, usize),
+    /// A `[[row.child.child]]`, which is a widget within a column.
+    ColWidget(usize, usize, usize),
+}
+
+#[derive(Default)]
+pub struct AppLayoutEditState {
+    pub is_editing: bool,
+    pub rows: Vec<Row>,
+    pub selected_index: usize,
+    pub error: Option<String>,
+}
+
+fn new_widget() -> FinalWidget {
+    FinalWidget {
+        ratio: None,
+        widget_type: "empty".to_string(),
+        default: None,
+        title: None,
+        filter: None,
+        sort: None,
+        sort_order: None,
+        grouped: None,
+        tree: None,
+        plugin: None,
+    }
+}
+
+fn new_row() -> Row {
+    Row {
+        ratio: None,
+        child: Some(vec![RowChildren::Widget(new_widget())]),
+    }
+}
+
+impl AppLayoutEditState {
+    /// Opens the editor with a copy of the given rows.
+    pub fn open(&mut self, rows: Vec<Row>) {
+        self.is_editing = true;
+        self.rows = rows;
+        self.selected_index = 0;
+        self.error = None;
+    }
+
+    pub fn close(&mut self) {
+        self.is_editing = false;
+        self.rows = Vec::new();
+        self.selected_index = 0;
+        self.error = None;
+    }
+
+    /// Returns every selectable entry, in the order they are displayed.
+    pub fn get_entries(&self) -> Vec<LayoutEntry> {
+        let mut entries = Vec::new();
+        for (row_index, row) in self.rows.iter().enumerate() {
+            entries.push(LayoutEntry::Row(row_index));
+            if let Some(children) = &row.child {
+                for (child_index, child) in children.iter().enumerate() {
+                    entries.push(LayoutEntry::RowChild(row_index, child_index));
+                    if let RowChildren::Col { child, .. } = child {
+                        for widget_index in 0..child.len() {
+                            entries.push(LayoutEntry::ColWidget(
+                                row_index,
+                                child_index,
+                                widget_index,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        entries
+    }
+
+    /// Returns the currently selected entry.
+    pub fn get_selected_entry(&self) -> Option<LayoutEntry> {
+        self.get_entries().get(self.selected_index).copied()
+    }
+
+    /// Returns a description of the given entry.
+    pub fn get_entry_text(&self, entry: LayoutEntry) -> String {
+        match entry {
+            LayoutEntry::Row(row) => {
+                format!("Row (ratio {})", self.rows[row].ratio.unwrap_or(1))
+            }
+            LayoutEntry::RowChild(row, child) => match self.get_row_child(row, child) {
+                Some(RowChildren::Widget(widget)) => format!(
+                    "  {} (ratio {})",
+                    widget.widget_type,
+                    widget.ratio.unwrap_or(1)
+                ),
+                Some(RowChildren::Col { ratio, tabs, .. }) => format!(
+                    "  {} (ratio {})",
+                    if *tabs == Some(true) {
+                        "Tabs"
+                    } else {
+                        "Column"
+                    },
+                    ratio.unwrap_or(1)
+                ),
+                None => String::default(),
+            },
+            LayoutEntry::ColWidget(row, child, widget) => {
+                if let Some(RowChildren::Col { child: widgets, .. }) =
+                    self.get_row_child(row, child)
+                {
+                    if let Some(widget) = widgets.get(widget) {
+                        return format!(
+                            "    {} (ratio {})",
+                            widget.widget_type,
+                            widget.ratio.unwrap_or(1)
+                        );
+                    }
+                }
+                String::default()
+            }
+        }
+    }
+
+    fn get_row_child(&self, row: usize, child: usize) -> Option<&RowChildren> {
+        self.rows
+            .get(row)
+            .and_then(|row| row.child.as_ref())
+            .and_then(|children| children.get(child))
+    }
+
+    fn get_row_children_mut(&mut self, row: usize) -> Option<&mut Vec<RowChildren>> {
+        self.rows
+            .get_mut(row)
+            .map(|row| row.child.get_or_insert_with(Vec::new))
+    }
+
+    fn get_col_widgets_mut(&mut self, row: usize, child: usize) -> Option<&mut Vec<FinalWidget>> {
+        match self
+            .get_row_children_mut(row)
+            .and_then(|children| children.get_mut(child))
+        {
+            Some(RowChildren::Col { child, .. }) => Some(child),
+            _ => None,
+        }
+    }
+
+    /// Selects the given entry, if it exists.
+    fn select_entry(&mut self, entry: LayoutEntry) {
+        if let Some(index) = self.get_entries().iter().position(|e| *e == entry) {
+            self.selected_index = index;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected_index + 1 < self.get_entries().len() {
+            self.selected_index += 1;
+        }
+    }
+
+    pub fn select_first(&mut self) {
+        self.selected_index = 0;
+    }
+
+    pub fn select_last(&mut self) {
+        self.selected_index = self.get_entries().len().saturating_sub(1);
+    }
+
+    /// Adds an empty widget after the selected entry.  If a row is selected, the
+    /// widget is added to the end of that row.
+    pub fn add_widget(&mut self) {
+        let new_entry = match self.get_selected_entry() {
+            Some(LayoutEntry::Row(row)) => self.get_row_children_mut(row).map(|children| {
+                children.push(RowChildren::Widget(new_widget()));
+                LayoutEntry::RowChild(row, children.len() - 1)
+            }),
+            Some(LayoutEntry::RowChild(row, child)) => {
+                self.get_row_children_mut(row).map(|children| {
+                    children.insert(child + 1, RowChildren::Widget(new_widget()));
+                    LayoutEntry::RowChild(row, child + 1)
+                })
+            }
+            Some(LayoutEntry::ColWidget(row, child, widget)) => {
+                self.get_col_widgets_mut(row, child).map(|widgets| {
+                    widgets.insert(widget + 1, new_widget());
+                    LayoutEntry::ColWidget(row, child, widget + 1)
+                })
+            }
+            None => None,
+        };
+
+        if let Some(new_entry) = new_entry {
+            self.select_entry(new_entry);
+        }
+    }
+
+    /// Adds an empty widget below the selected widget, turning it into a column if needed.
+    pub fn add_widget_below(&mut self) {
+        match self.get_selected_entry() {
+            Some(LayoutEntry::RowChild(row, child)) => {
+                if let Some(row_child) = self
+                    .get_row_children_mut(row)
+                    .and_then(|children| children.get_mut(child))
+                {
+                    let new_entry = match row_child {
+                        RowChildren::Widget(widget) => {
+                            let ratio = widget.ratio.take();
+                            let widget = widget.clone();
+                            *row_child = RowChildren::Col {
+                                ratio,
+                                child: vec![widget, new_widget()],
+                                tabs: None,
+                            };
+                            LayoutEntry::ColWidget(row, child, 1)
+                        }
+                        RowChildren::Col { child: widgets, .. } => {
+                            widgets.push(new_widget());
+                            LayoutEntry::ColWidget(row, child, widgets.len() - 1)
+                        }
+                    };
+                    self.select_entry(new_entry);
+                }
+            }
+            Some(LayoutEntry::ColWidget(..)) => self.add_widget(),
+            _ => {}
+        }
+    }
+
+    /// Adds a new row, containing a single empty widget, after the row of the selected entry.
+    pub fn add_row(&mut self) {
+        let row = match self.get_selected_entry() {
+            Some(LayoutEntry::Row(row))
+            | Some(LayoutEntry::RowChild(row, _))
+            | Some(LayoutEntry::ColWidget(row, _, _)) => row + 1,
+            None => 0,
+        };
+
+        self.rows.insert(row, new_row());
+        self.select_entry(LayoutEntry::Row(row));
+    }
+
+    /// Removes the selected entry.  Any columns or rows left empty are also removed.
+    pub fn remove_selected(&mut self) {
+        match self.get_selected_entry() {
+            Some(LayoutEntry::Row(row)) => {
+                self.rows.remove(row);
+            }
+            Some(LayoutEntry::RowChild(row, child)) => {
+                if let Some(children) = self.get_row_children_mut(row) {
+                    children.remove(child);
+                    if children.is_empty() {
+                        self.rows.remove(row);
+                    }
+                }
+            }
+            Some(LayoutEntry::ColWidget(row, child, widget)) => {
+                if let Some(widgets) = self.get_col_widgets_mut(row, child) {
+                    widgets.remove(widget);
+                    if widgets.is_empty() {
+                        if let Some(children) = self.get_row_children_mut(row) {
+                            children.remove(child);
+                            if children.is_empty() {
+                                self.rows.remove(row);
+                            }
+                        }
+                    }
+                }
+            }
+            None => {}
+        }
+
+        let num_entries = self.get_entries().len();
+        if self.selected_index >= num_entries {
+            self.selected_index = num_entries.saturating_sub(1);
+        }
+    }
+
+    /// Swaps the selected entry with its previous sibling if `move_back` is true,
+    /// or its next sibling otherwise.
+    pub fn move_selected(&mut self, move_back: bool) {
+        fn swap<T>(list: &mut [T], index: usize, move_back: bool) -> Option<usize> {
+            let other = if move_back {
+                index.checked_sub(1)?
+            } else {
+                index + 1
+            };
+            if other < list.len() {
+                list.swap(index, other);
+                Some(other)
+            } else {
+                None
+            }
+        }
+
+        let new_entry = match self.get_selected_entry() {
+            Some(LayoutEntry::Row(row)) => {
+                swap(&mut self.rows, row, move_back).map(LayoutEntry::Row)
+            }
+            Some(LayoutEntry::RowChild(row, child)) => self
+                .get_row_children_mut(row)
+                .and_then(|children| swap(children, child, move_back))
+                .map(|child| LayoutEntry::RowChild(row, child)),
+            Some(LayoutEntry::ColWidget(row, child, widget)) => self
+                .get_col_widgets_mut(row, child)
+                .and_then(|widgets| swap(widgets, widget, move_back))
+                .map(|widget| LayoutEntry::ColWidget(row, child, widget)),
+            None => None,
+        };
+
+        if let Some(new_entry) = new_entry {
+            self.select_entry(new_entry);
+        }
+    }
+
+    /// Increases or decreases the ratio of the selected entry by one, down to a minimum of one.
+    pub fn change_selected_ratio(&mut self, increase: bool) {
+        fn change(ratio: &mut Option<u32>, increase: bool) {
+            let current = ratio.unwrap_or(1);
+            *ratio = Some(if increase {
+                current.saturating_add(1)
+            } else {
+                std::cmp::max(current.saturating_sub(1), 1)
+            });
+        }
+
+        match self.get_selected_entry() {
+            Some(LayoutEntry::Row(row)) => {
+                if let Some(row) = self.rows.get_mut(row) {
+                    change(&mut row.ratio, increase);
+                }
+            }
+            Some(LayoutEntry::RowChild(row, child)) => {
+                match self
+                    .get_row_children_mut(row)
+                    .and_then(|children| children.get_mut(child))
+                {
+                    Some(RowChildren::Widget(widget)) => change(&mut widget.ratio, increase),
+                    Some(RowChildren::Col { ratio, .. }) => change(ratio, increase),
+                    None => {}
+                }
+            }
+            Some(LayoutEntry::ColWidget(row, child, widget)) => {
+                if let Some(widget) = self
+                    .get_col_widgets_mut(row, child)
+                    .and_then(|widgets| widgets.get_mut(widget))
+                {
+                    change(&mut widget.ratio, increase);
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Switches the column of the selected entry between stacking its widgets and showing them
+    /// as tabs.
+    pub fn toggle_selected_tabs(&mut self) {
+        let (row, child) = match self.get_selected_entry() {
+            Some(LayoutEntry::RowChild(row, child))
+            | Some(LayoutEntry::ColWidget(row, child, _)) => (row, child),
+            _ => return,
+        };
+
+        if let Some(RowChildren::Col { tabs, .. }) = self
+            .get_row_children_mut(row)
+            .and_then(|children| children.get_mut(child))
+        {
+            *tabs = if *tabs == Some(true) {
+                None
+            } else {
+                Some(true)
+            };
+        }
+    }
+
+    /// Cycles the type of the selected widget forwards or backwards.
+    pub fn cycle_selected_type(&mut self, forwards: bool) {
+        let widget = match self.get_selected_entry() {
+            Some(LayoutEntry::RowChild(row, child)) => match self
+                .get_row_children_mut(row)
+                .and_then(|children| children.get_mut(child))
+            {
+                Some(RowChildren::Widget(widget)) => Some(widget),
+                _ => None,
+            },
+            Some(LayoutEntry::ColWidget(row, child, widget)) => self
+                .get_col_widgets_mut(row, child)
+                .and_then(|widgets| widgets.get_mut(widget)),
+            _ => None,
+        };
+
+        if let Some(widget) = widget {
+            let num_types = EDITABLE_WIDGET_TYPES.len();
+            let current_index = widget
+                .widget_type
+                .parse::<crate::app::layout_manager::BottomWidgetType>()
+                .ok()
+                .and_then(|current_type| {
+                    EDITABLE_WIDGET_TYPES.iter().position(|name| {
+                        name.parse::<crate::app::layout_manager::BottomWidgetType>()
+                            .map(|widget_type| widget_type == current_type)
+                            .unwrap_or(false)
+                    })
+                })
+                .unwrap_or(num_types - 1);
+            let new_index = if forwards {
+                (current_index + 1) % num_types
+            } else {
+                (current_index + num_types - 1) % num_types
+            };
+            widget.widget_type = EDITABLE_WIDGET_TYPES[new_index].to_string();
+            // Plugin widgets can't be picked here, so this is never needed after.
+            widget.plugin = None;
+        }
+    }
+
+    /// Returns whether the edited layout contains at least one non-empty widget.
+    pub fn has_widgets(&self) -> bool {
+        self.rows
+            .iter()
+            .filter_map(|row| row.child.as_ref())
+            .flatten()
+            .any(|row_child| match row_child {
+                RowChildren::Widget(widget) => widget.widget_type != "empty",
+                RowChildren::Col { child, .. } => {
+                    child.iter().any(|widget| widget.widget_type != "empty")
+                }
+            })
+    }
+}
@@ -530,9 +530,114 @@ impl BottomLayout {
             }
             height_cursor += row.row_height_ratio;
         }
+
+        // The tabs of a tabbed column are stacked above, so moving up or down into another tab
+        // leaves the column instead.
+        for col in self.rows.iter_mut().flat_map(|row| row.children.iter_mut()) {
+            if col.tabs.is_empty() {
+                continue;
+            }
+
+            let tabs_by_widget: BTreeMap<u64, u64> = col
+                .children
+                .iter()
+                .flat_map(|col_row| &col_row.children)
+                .filter_map(|widget| Some((widget.widget_id, widget.tab?.tab_widget_id)))
+                .collect();
+            let up_exit = col
+                .children
+                .first()
+                .and_then(|col_row| col_row.children.first())
+                .and_then(|widget| widget.up_neighbour);
+            let down_exit = col
+                .children
+                .last()
+                .and_then(|col_row| col_row.children.first())
+                .and_then(|widget| widget.down_neighbour);
+
+            for widget in col
+                .children
+                .iter_mut()
+                .flat_map(|col_row| col_row.children.iter_mut())
+            {
+                let tab_widget_id = match widget.tab {
+                    Some(tab) => tab.tab_widget_id,
+                    None => continue,
+                };
+                let is_other_tab = |neighbour: Option<u64>| {
+                    matches!(
+                        neighbour.and_then(|id| tabs_by_widget.get(&id)),
+                        Some(other) if *other != tab_widget_id
+                    )
+                };
+
+                if is_other_tab(widget.up_neighbour) {
+                    widget.up_neighbour = up_exit;
+                }
+                if is_other_tab(widget.down_neighbour) {
+                    widget.down_neighbour = down_exit;
+                }
+            }
+        }
     }
 
-    pub fn init_basic_default(use_battery: bool) -> Self {
+    /// Resizes the components on either side of `split` so that the split is at
+    /// `position`.  Returns whether anything changed.
+    pub fn resize_split(&mut self, split: &LayoutSplit, position: u16) -> bool {
+        let ratios = match split.get_resized_ratios(position) {
+            Some(ratios) => ratios,
+            None => return false,
+        };
+        let total_ratio = ratios.iter().sum();
+
+        match split.kind {
+            LayoutSplitKind::Row { .. } => {
+                if ratios.len() != self.rows.len() {
+                    return false;
+                }
+                for (row, ratio) in self.rows.iter_mut().zip(ratios) {
+                    row.row_height_ratio = ratio;
+                }
+                self.total_row_height_ratio = total_ratio;
+            }
+            LayoutSplitKind::Col { row, .. } => {
+                if let Some(row) = self.rows.get_mut(row) {
+                    if ratios.len() != row.children.len() {
+                        return false;
+                    }
+                    for (col, ratio) in row.children.iter_mut().zip(ratios) {
+                        col.col_width_ratio = ratio;
+                    }
+                    row.total_col_ratio = total_ratio;
+                } else {
+                    return false;
+                }
+            }
+            LayoutSplitKind::ColRow { row, col, .. } => {
+                if let Some(col) = self
+                    .rows
+                    .get_mut(row)
+                    .and_then(|row| row.children.get_mut(col))
+                {
+                    if ratios.len() != col.children.len() {
+                        return false;
+                    }
+                    for (col_row, ratio) in col.children.iter_mut().zip(ratios) {
+                        col_row.col_row_height_ratio = ratio;
+                    }
+                    col.total_col_row_ratio = total_ratio;
+                } else {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Creates the layout for basic mode, with a summary row for each of the CPU, memory, and
+    /// network widgets in `summary_widgets` above the tables.
+    pub fn init_basic_default(use_battery: bool, summary_widgets: &[BottomWidgetType]) -> Self {
         let table_widgets = if use_battery {
             vec![
                 BottomCol::builder()
@@ -696,75 +801,191 @@ impl BottomLayout {
             ]
         };
 
-        BottomLayout {
-            total_row_height_ratio: 3,
-            rows: vec![
-                BottomRow::builder()
-                    .canvas_handle_height(true)
-                    .children(vec![BottomCol::builder()
-                        .canvas_handle_width(true)
-                        .children(vec![BottomColRow::builder()
-                            .canvas_handle_height(true)
-                            .children(vec![BottomWidget::builder()
-                                .canvas_handle_width(true)
-                                .widget_type(BottomWidgetType::BasicCpu)
-                                .widget_id(1)
-                                .down_neighbour(Some(2))
-                                .build()])
-                            .build()])
-                        .build()])
-                    .build(),
-                BottomRow::builder()
-                    .canvas_handle_height(true)
-                    .children(vec![BottomCol::builder()
-                        .canvas_handle_width(true)
-                        .children(vec![BottomColRow::builder()
-                            .canvas_handle_height(true)
-                            .children(vec![
-                                BottomWidget::builder()
-                                    .canvas_handle_width(true)
-                                    .widget_type(BottomWidgetType::BasicMem)
-                                    .widget_id(2)
-                                    .up_neighbour(Some(1))
-                                    .down_neighbour(Some(100))
-                                    .right_neighbour(Some(3))
-                                    .build(),
-                                BottomWidget::builder()
-                                    .canvas_handle_width(true)
-                                    .widget_type(BottomWidgetType::BasicNet)
-                                    .widget_id(3)
-                                    .up_neighbour(Some(1))
-                                    .down_neighbour(Some(100))
-                                    .left_neighbour(Some(2))
-                                    .build(),
-                            ])
-                            .build()])
-                        .build()])
-                    .build(),
-                BottomRow::builder()
-                    .canvas_handle_height(true)
-                    .children(vec![BottomCol::builder()
-                        .canvas_handle_width(true)
-                        .children(vec![BottomColRow::builder()
-                            .canvas_handle_height(true)
-                            .children(vec![BottomWidget::builder()
-                                .canvas_handle_width(true)
-                                .widget_type(BottomWidgetType::BasicTables)
-                                .widget_id(100)
-                                .up_neighbour(Some(2))
-                                .build()])
-                            .build()])
+        // Each summary row is optional, so the neighbours skip over any that aren't shown.
+        let has_cpu = summary_widgets.contains(&BottomWidgetType::Cpu);
+        let has_mem = summary_widgets.contains(&BottomWidgetType::Mem);
+        let has_net = summary_widgets.contains(&BottomWidgetType::Net);
+        let cpu_id = if has_cpu { Some(1) } else { None };
+        let mem_id = if has_mem { Some(2) } else { None };
+        let net_id = if has_net { Some(3) } else { None };
+
+        let summary_row = |widgets: Vec<BottomWidget>| {
+            BottomRow::builder()
+                .canvas_handle_height(true)
+                .children(vec![BottomCol::builder()
+                    .canvas_handle_width(true)
+                    .children(vec![BottomColRow::builder()
+                        .canvas_handle_height(true)
+                        .children(widgets)
                         .build()])
+                    .build()])
+                .build()
+        };
+
+        let mut rows = Vec::new();
+        if has_cpu {
+            rows.push(summary_row(vec![BottomWidget::builder()
+                .canvas_handle_width(true)
+                .widget_type(BottomWidgetType::BasicCpu)
+                .widget_id(1)
+                .down_neighbour(mem_id.or(net_id).or(Some(100)))
+                .build()]));
+        }
+
+        let mut middle_widgets = Vec::new();
+        if has_mem {
+            middle_widgets.push(
+                BottomWidget::builder()
+                    .canvas_handle_width(true)
+                    .widget_type(BottomWidgetType::BasicMem)
+                    .widget_id(2)
+                    .up_neighbour(cpu_id)
+                    .down_neighbour(Some(100))
+                    .right_neighbour(net_id)
                     .build(),
-                BottomRow::builder()
-                    .canvas_handle_height(true)
-                    .children(table_widgets)
+            );
+        }
+        if has_net {
+            middle_widgets.push(
+                BottomWidget::builder()
+                    .canvas_handle_width(true)
+                    .widget_type(BottomWidgetType::BasicNet)
+                    .widget_id(3)
+                    .up_neighbour(cpu_id)
+                    .down_neighbour(Some(100))
+                    .left_neighbour(mem_id)
                     .build(),
-            ],
+            );
+        }
+        if !middle_widgets.is_empty() {
+            rows.push(summary_row(middle_widgets));
+        }
+
+        rows.push(summary_row(vec![BottomWidget::builder()
+            .canvas_handle_width(true)
+            .widget_type(BottomWidgetType::BasicTables)
+            .widget_id(100)
+            .up_neighbour(mem_id.or(net_id).or(cpu_id))
+            .build()]));
+        rows.push(
+            BottomRow::builder()
+                .canvas_handle_height(true)
+                .children(table_widgets)
+                .build(),
+        );
+
+        BottomLayout {
+            total_row_height_ratio: 3,
+            rows,
         }
     }
 }
 
+/// Represents which siblings a [`LayoutSplit`] sits between.  The index given is
+/// that of the sibling before the split.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LayoutSplitKind {
+    Row {
+        row: usize,
+    },
+    Col {
+        row: usize,
+        col: usize,
+    },
+    ColRow {
+        row: usize,
+        col: usize,
+        col_row: usize,
+    },
+}
+
+/// Represents a border between two sibling layout components that can be
+/// dragged to resize them.
+#[derive(Clone, Debug)]
+pub struct LayoutSplit {
+    pub kind: LayoutSplitKind,
+
+    /// Whether the split separates components stacked vertically, meaning
+    /// it is dragged up and down.
+    pub is_vertical: bool,
+
+    /// The start and length of *every* sibling along the resized axis, as last drawn.
+    pub sibling_bounds: Vec<(u16, u16)>,
+
+    /// The start and end of the split along the other axis.
+    pub cross_bounds: (u16, u16),
+}
+
+/// The smallest size a component can be dragged to, which still leaves room for borders.
+const MIN_SPLIT_LENGTH: u16 = 3;
+
+impl LayoutSplit {
+    /// Returns the index of the sibling before the split.
+    fn index(&self) -> usize {
+        match self.kind {
+            LayoutSplitKind::Row { row } => row,
+            LayoutSplitKind::Col { col, .. } => col,
+            LayoutSplitKind::ColRow { col_row, .. } => col_row,
+        }
+    }
+
+    /// Returns whether the given point lies on the split; that is, on either
+    /// the last line of the first sibling or the first line of the second, or
+    /// within `padding` lines of them.
+    pub fn is_hit(&self, x: u16, y: u16, padding: u16) -> bool {
+        let (along, cross) = if self.is_vertical { (y, x) } else { (x, y) };
+        if cross < self.cross_bounds.0 || cross >= self.cross_bounds.1 {
+            return false;
+        }
+
+        let index = self.index();
+        if let (Some(first), Some(second)) = (
+            self.sibling_bounds.get(index),
+            self.sibling_bounds.get(index + 1),
+        ) {
+            along + 1 + padding >= first.0 + first.1 && along <= second.0 + padding
+        } else {
+            false
+        }
+    }
+
+    /// Returns new ratios for every sibling if the split is dragged to `position`,
+    /// or [`None`] if that would make either side too small.
+    ///
+    /// The returned ratios are simply the lengths of each sibling, so any siblings
+    /// not next to the split keep their current size.
+    pub fn get_resized_ratios(&self, position: u16) -> Option<Vec<u32>> {
+        let index = self.index();
+        let first = self.sibling_bounds.get(index)?;
+        let second = self.sibling_bounds.get(index + 1)?;
+        let combined_length = first.1 + second.1;
+
+        // Dragging puts the border (the last line of the first sibling) at the new position.
+        let new_first_length = (position + 1).checked_sub(first.0)?;
+        if new_first_length < MIN_SPLIT_LENGTH
+            || combined_length.saturating_sub(new_first_length) < MIN_SPLIT_LENGTH
+        {
+            return None;
+        }
+
+        Some(
+            self.sibling_bounds
+                .iter()
+                .enumerate()
+                .map(|(itx, (_start, length))| {
+                    if itx == index {
+                        u32::from(new_first_length)
+                    } else if itx == index + 1 {
+                        u32::from(combined_length - new_first_length)
+                    } else {
+                        u32::from(*length)
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
 /// Represents a single row in the layout.
 #[derive(Clone, Debug, TypedBuilder)]
 pub struct BottomRow {
@@ -801,6 +1022,31 @@ pub struct BottomCol {
 
     #[builder(default = false)]
     pub flex_grow: bool,
+
+    /// If this isn't empty, only one of these tabs is shown at a time, under a line of tab
+    /// headers, rather than stacking the column's rows.
+    #[builder(default)]
+    pub tabs: Vec<LayoutTab>,
+}
+
+/// A tab in a tabbed column, which holds one widget.
+#[derive(Clone, Debug)]
+pub struct LayoutTab {
+    /// The ID of the tab's widget, which is also the [`TabId::tab_widget_id`] of every widget in
+    /// the tab.
+    pub widget_id: u64,
+    pub widget_type: BottomWidgetType,
+    /// The indices of the column rows that make up the tab's widget.
+    pub col_rows: std::ops::Range<usize>,
+}
+
+/// Which tab of which tabbed column a widget is in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TabId {
+    /// The ID of the widget in the column's first tab, which identifies the column.
+    pub container_id: u64,
+    /// The ID of the widget in the tab.
+    pub tab_widget_id: u64,
 }
 
 #[derive(Clone, Default, Debug, TypedBuilder)]
@@ -879,6 +1125,36 @@ pub struct BottomWidget {
     /// Bottom right corner when drawn, for mouse click detection.  (x, y)
     #[builder(default = None)]
     pub bottom_right_corner: Option<(u16, u16)>,
+
+    /// The tab this widget is in, if it's in a tabbed column.
+    #[builder(default = None)]
+    pub tab: Option<TabId>,
+
+    /// The title set for this widget in the layout, shown instead of its usual one.
+    #[builder(default = None)]
+    pub title: Option<String>,
+
+    /// For process widgets, the settings set for this widget in the layout.
+    #[builder(default)]
+    pub process_options: ProcLayoutOptions,
+
+    /// For plugin widgets, the name of the plugin to show.
+    #[builder(default = None)]
+    pub plugin: Option<String>,
+}
+
+/// Settings for a single process widget that are set on it in the layout, which override the ones
+/// for every process widget.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcLayoutOptions {
+    /// The name of the saved filter to start with.
+    pub filter: Option<String>,
+    /// The name of the column to sort by.
+    pub sort: Option<String>,
+    /// The order to sort in, which is the column's usual one if not set.
+    pub sort_order: Option<String>,
+    pub grouped: Option<bool>,
+    pub tree: Option<bool>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -898,17 +1174,34 @@ pub enum BottomWidgetType {
     BasicNet,
     BasicTables,
     Battery,
+    Power,
+    Pressure,
+    LoadAvg,
+    Cgroup,
+    Latency,
+    Frequency,
+    Events,
+    SystemInfo,
+    Users,
+    TopProcesses,
+    Plugin,
 }
 
 impl BottomWidgetType {
     pub fn is_widget_table(&self) -> bool {
         use BottomWidgetType::*;
-        matches!(self, Disk | Proc | ProcSort | Temp | CpuLegend)
+        matches!(
+            self,
+            Disk | Proc | ProcSort | Temp | CpuLegend | Cgroup | Events | Users
+        )
     }
 
     pub fn is_widget_graph(&self) -> bool {
         use BottomWidgetType::*;
-        matches!(self, Cpu | Net | Mem)
+        matches!(
+            self,
+            Cpu | Net | Mem | Power | Pressure | LoadAvg | Latency | Frequency | Plugin
+        )
     }
 
     pub fn get_pretty_name(&self) -> &str {
@@ -921,6 +1214,17 @@ impl BottomWidgetType {
             Temp => "Temperature",
             Disk => "Disks",
             Battery => "Battery",
+            Power => "Power",
+            Pressure => "Pressure",
+            LoadAvg => "Load Average",
+            Cgroup => "Cgroups",
+            Latency => "Latency",
+            Frequency => "Frequency",
+            Events => "Kernel Events",
+            SystemInfo => "System",
+            Users => "Users",
+            TopProcesses => "Top Processes",
+            Plugin => "Plugin",
             _ => "",
         }
     }
@@ -944,6 +1248,17 @@ impl std::str::FromStr for BottomWidgetType {
             "proc" | "process" | "processes" => Ok(BottomWidgetType::Proc),
             "temp" | "temperature" => Ok(BottomWidgetType::Temp),
             "disk" => Ok(BottomWidgetType::Disk),
+            "power" => Ok(BottomWidgetType::Power),
+            "pressure" | "psi" => Ok(BottomWidgetType::Pressure),
+            "load" | "load_avg" => Ok(BottomWidgetType::LoadAvg),
+            "cgroup" | "cgroups" => Ok(BottomWidgetType::Cgroup),
+            "latency" | "ping" => Ok(BottomWidgetType::Latency),
+            "frequency" | "freq" => Ok(BottomWidgetType::Frequency),
+            "events" | "kernel_events" => Ok(BottomWidgetType::Events),
+            "info" | "system_info" => Ok(BottomWidgetType::SystemInfo),
+            "users" | "sessions" => Ok(BottomWidgetType::Users),
+            "top" | "top_processes" => Ok(BottomWidgetType::TopProcesses),
+            "plugin" => Ok(BottomWidgetType::Plugin),
             "empty" => Ok(BottomWidgetType::Empty),
             "battery" | "batt" if cfg!(feature = "battery") => Ok(BottomWidgetType::Battery),
             _ => {
@@ -965,6 +1280,28 @@ Supported widget names:
 +--------------------------+
 |           disk           |
 +--------------------------+
+|           power          |
++--------------------------+
+|       pressure, psi      |
++--------------------------+
+|     load, load_avg       |
++--------------------------+
+|     cgroup, cgroups      |
++--------------------------+
+|      latency, ping       |
++--------------------------+
+|     frequency, freq      |
++--------------------------+
+|  events, kernel_events   |
++--------------------------+
+|    info, system_info     |
++--------------------------+
+|     users, sessions      |
++--------------------------+
+|    top, top_processes    |
++--------------------------+
+|          plugin          |
++--------------------------+
 |       batt, battery      |
 +--------------------------+
                 ",
@@ -987,6 +1324,28 @@ Supported widget names:
 |     temp, temperature    |
 +--------------------------+
 |           disk           |
++--------------------------+
+|           power          |
++--------------------------+
+|       pressure, psi      |
++--------------------------+
+|     load, load_avg       |
++--------------------------+
+|     cgroup, cgroups      |
++--------------------------+
+|      latency, ping       |
++--------------------------+
+|     frequency, freq      |
++--------------------------+
+|  events, kernel_events   |
++--------------------------+
+|    info, system_info     |
++--------------------------+
+|     users, sessions      |
++--------------------------+
+|    top, top_processes    |
++--------------------------+
+|          plugin          |
 +--------------------------+
                 ",
                         s
@@ -1006,4 +1365,15 @@ pub struct UsedWidgets {
     pub use_disk: bool,
     pub use_temp: bool,
     pub use_battery: bool,
+    pub use_power: bool,
+    pub use_pressure: bool,
+    pub use_load_avg: bool,
+    pub use_cgroup: bool,
+    pub use_latency: bool,
+    pub use_frequency: bool,
+    pub use_events: bool,
+    pub use_system_info: bool,
+    pub use_users: bool,
+    pub use_top_processes: bool,
+    pub use_plugins: bool,
 }
@@ -0,0 +1,178 @@
+//! Getting and setting the scheduling (nice) and I/O priorities of processes.
+
+#[cfg(target_family = "unix")]
+use crate::utils::error::{BottomError, Result};
+#[cfg(target_family = "unix")]
+use crate::Pid;
+
+/// The lowest (most favourable) nice value.
+pub const MIN_NICE: i32 = -20;
+
+/// The highest (least favourable) nice value.
+pub const MAX_NICE: i32 = 19;
+
+/// The lowest (least favourable) I/O priority level within a class.
+pub const MAX_IO_LEVEL: u8 = 7;
+
+/// The I/O scheduling class of a process. This is only used on Linux.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IoPriorityClass {
+    /// The I/O priority is derived from the nice value.
+    None,
+    RealTime,
+    BestEffort,
+    Idle,
+}
+
+impl IoPriorityClass {
+    const ALL: [IoPriorityClass; 4] = [
+        IoPriorityClass::None,
+        IoPriorityClass::RealTime,
+        IoPriorityClass::BestEffort,
+        IoPriorityClass::Idle,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IoPriorityClass::None => "none",
+            IoPriorityClass::RealTime => "realtime",
+            IoPriorityClass::BestEffort => "best-effort",
+            IoPriorityClass::Idle => "idle",
+        }
+    }
+
+    /// Whether the class has priority levels.
+    pub fn has_levels(self) -> bool {
+        matches!(
+            self,
+            IoPriorityClass::RealTime | IoPriorityClass::BestEffort
+        )
+    }
+
+    /// Returns the next or previous class, wrapping around.
+    pub fn cycle(self, forward: bool) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|class| *class == self)
+            .unwrap_or(0);
+        let len = Self::ALL.len();
+        Self::ALL[if forward {
+            (index + 1) % len
+        } else {
+            (index + len - 1) % len
+        }]
+    }
+}
+
+/// Converts the last OS error from changing a process' priority into a readable error.
+#[cfg(target_family = "unix")]
+fn get_priority_error(action: &str) -> BottomError {
+    let err = std::io::Error::last_os_error();
+    let reason = match err.raw_os_error() {
+        Some(libc::ESRCH) => "the target process did not exist.",
+        Some(libc::EPERM) | Some(libc::EACCES) => {
+            "permission denied. Raising a process' priority, or changing the priority of another user's process, requires root."
+        }
+        Some(libc::EINVAL) => "an invalid priority was specified.",
+        _ => "Unknown error occurred.",
+    };
+
+    BottomError::GenericError(format!(
+        "Failed to {} - error code {} - {}",
+        action,
+        err.raw_os_error()
+            .map(|code| code.to_string())
+            .unwrap_or_else(|| "???".to_string()),
+        reason
+    ))
+}
+
+/// Returns the nice value of a process.
+#[cfg(target_family = "unix")]
+pub fn get_nice(pid: Pid) -> Result<i32> {
+    // As -1 is a valid nice value, errno has to be cleared to tell if this failed.
+    unsafe {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            *libc::__errno_location() = 0;
+        }
+        #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+        {
+            *libc::__error() = 0;
+        }
+    }
+
+    let nice = unsafe { libc::getpriority(libc::PRIO_PROCESS as _, pid as libc::id_t) };
+    if nice == -1 && std::io::Error::last_os_error().raw_os_error() != Some(0) {
+        Err(get_priority_error("get the nice value"))
+    } else {
+        Ok(nice)
+    }
+}
+
+/// Sets the nice value of a process.
+#[cfg(target_family = "unix")]
+pub fn set_nice(pid: Pid, nice: i32) -> Result<()> {
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS as _, pid as libc::id_t, nice) } != 0 {
+        Err(get_priority_error("set the nice value"))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+
+/// Returns the I/O scheduling class and level of a process.
+#[cfg(target_os = "linux")]
+pub fn get_io_priority(pid: Pid) -> Result<(IoPriorityClass, u8)> {
+    let ioprio = unsafe { libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, pid) };
+    if ioprio < 0 {
+        Err(get_priority_error("get the I/O priority"))
+    } else {
+        let ioprio = ioprio as libc::c_int;
+        let class = match ioprio >> IOPRIO_CLASS_SHIFT {
+            1 => IoPriorityClass::RealTime,
+            2 => IoPriorityClass::BestEffort,
+            3 => IoPriorityClass::Idle,
+            _ => IoPriorityClass::None,
+        };
+        let level = (ioprio & ((1 << IOPRIO_CLASS_SHIFT) - 1)) as u8;
+
+        Ok((class, level.min(MAX_IO_LEVEL)))
+    }
+}
+
+/// Sets the I/O scheduling class and level of a process. The level is ignored for classes
+/// without levels.
+#[cfg(target_os = "linux")]
+pub fn set_io_priority(pid: Pid, class: IoPriorityClass, level: u8) -> Result<()> {
+    let (class_value, level) = match class {
+        IoPriorityClass::None => (0, 0),
+        IoPriorityClass::RealTime => (1, level),
+        IoPriorityClass::BestEffort => (2, level),
+        IoPriorityClass::Idle => (3, 0),
+    };
+    let ioprio = (class_value << IOPRIO_CLASS_SHIFT) | libc::c_int::from(level);
+
+    if unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, pid, ioprio) } != 0 {
+        Err(get_priority_error("set the I/O priority"))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_io_priority_class_cycle() {
+        assert_eq!(IoPriorityClass::None.cycle(true), IoPriorityClass::RealTime);
+        assert_eq!(IoPriorityClass::Idle.cycle(true), IoPriorityClass::None);
+        assert_eq!(IoPriorityClass::None.cycle(false), IoPriorityClass::Idle);
+    }
+}
@@ -7,10 +7,11 @@ use std::{borrow::Cow, collections::VecDeque};
 
 use super::data_harvester::processes::ProcessHarvest;
 
-const DELIMITER_LIST: [char; 6] = ['=', '>', '<', '(', ')', '\"'];
-const COMPARISON_LIST: [&str; 3] = [">", "=", "<"];
+const DELIMITER_LIST: [char; 7] = ['=', '>', '<', '~', '(', ')', '\"'];
+const COMPARISON_LIST: [&str; 4] = [">", "=", "<", "~"];
 const OR_LIST: [&str; 2] = ["or", "||"];
 const AND_LIST: [&str; 2] = ["and", "&&"];
+const NOT_LIST: [&str; 2] = ["not", "!"];
 
 /// In charge of parsing the given query.
 /// We are defining the following language for a query (case-insensitive prefixes):
@@ -21,16 +22,22 @@ const AND_LIST: [&str; 2] = ["and", "&&"];
 /// - PIDs: Use prefix `pid`, can use regex or match word (case is irrelevant).
 /// - CPU: Use prefix `cpu`, cannot use r/m/c (regex, match word, case).  Can compare.
 /// - MEM: Use prefix `mem`, cannot use r/m/c.  Can compare.
+/// - Process names (explicit): Use prefix `name`, followed by `=` or `~`.
 /// - STATE: Use prefix `state`, can use regex, match word, or case.
 /// - USER: Use prefix `user`, can use regex, match word, or case.
+/// - ELEVATED: Use prefix `elevated`, matched against `yes` or `no`.  Only collected on Windows.
 /// - Read/s: Use prefix `r`.  Can compare.
 /// - Write/s: Use prefix `w`.  Can compare.
 /// - Total read: Use prefix `read`.  Can compare.
 /// - Total write: Use prefix `write`.  Can compare.
 ///
+/// String prefixes (`name`, `pid`, `state`, `user`, `elevated`) can be followed by `=`, which follows the
+/// current regex setting, or `~`, which always treats the value as a regex.  Their values may
+/// be quoted.
+///
 /// For queries, whitespaces are our delimiters.  We will merge together any adjacent non-prefixed
 /// or quoted elements after splitting to treat as process names.
-/// Furthermore, we want to support boolean joiners like AND and OR, and brackets.
+/// Furthermore, we want to support boolean joiners like AND and OR, negation with NOT, and brackets.
 pub fn parse_query(
     search_query: &str, is_searching_whole_word: bool, is_ignoring_case: bool,
     is_searching_with_regex: bool,
@@ -64,6 +71,7 @@ pub fn parse_query(
                                 or: Some(Box::new(Or { lhs, rhs })),
                                 regex_prefix: None,
                                 compare_prefix: None,
+                                is_negated: false,
                             },
                             rhs: None,
                         };
@@ -103,6 +111,7 @@ pub fn parse_query(
                             })),
                             regex_prefix: None,
                             compare_prefix: None,
+                            is_negated: false,
                         };
                         rhs = None;
                     } else {
@@ -121,6 +130,39 @@ pub fn parse_query(
         Ok(And { lhs, rhs })
     }
 
+    /// Takes the value of a string prefix, which may be surrounded by quotes.
+    fn process_string_value(query: &mut VecDeque<String>) -> Result<String> {
+        match query.pop_front() {
+            Some(value) if value == "\"" => {
+                let mut quoted_string = String::default();
+                while let Some(next_str) = query.pop_front() {
+                    if next_str == "\"" {
+                        return Ok(quoted_string);
+                    } else {
+                        quoted_string.push_str(&next_str);
+                    }
+                }
+                Err(QueryError("Missing closing quotation".into()))
+            }
+            Some(value) => Ok(value),
+            None => Err(QueryError("Missing value".into())),
+        }
+    }
+
+    /// Whether a `not` followed by `next` negates it.  If there's nothing to negate, a bare
+    /// `not` is searched for as a process name instead.
+    fn is_negatable(next: Option<&String>) -> bool {
+        match next {
+            Some(next) => {
+                let next = next.to_lowercase();
+                next != ")"
+                    && !OR_LIST.contains(&next.as_str())
+                    && !AND_LIST.contains(&next.as_str())
+            }
+            None => false,
+        }
+    }
+
     fn process_prefix(query: &mut VecDeque<String>, inside_quotation: bool) -> Result<Prefix> {
         if let Some(queue_top) = query.pop_front() {
             if inside_quotation {
@@ -135,6 +177,7 @@ pub fn parse_query(
                             StringQuery::Value(String::default()),
                         )),
                         compare_prefix: None,
+                        is_negated: false,
                     });
                 } else {
                     let mut quoted_string = queue_top;
@@ -151,8 +194,15 @@ pub fn parse_query(
                         or: None,
                         regex_prefix: Some((PrefixType::Name, StringQuery::Value(quoted_string))),
                         compare_prefix: None,
+                        is_negated: false,
                     });
                 }
+            } else if NOT_LIST.contains(&queue_top.to_lowercase().as_str())
+                && is_negatable(query.front())
+            {
+                let mut prefix = process_prefix(query, false)?;
+                prefix.is_negated = !prefix.is_negated;
+                return Ok(prefix);
             } else if queue_top == "(" {
                 if query.is_empty() {
                     return Err(QueryError(Cow::Borrowed("Missing closing parentheses")));
@@ -179,6 +229,7 @@ pub fn parse_query(
                         lhs: Prefix {
                             or: list_of_ors.pop_front().map(Box::new),
                             compare_prefix: None,
+                            is_negated: false,
                             regex_prefix: None,
                         },
                         rhs: None,
@@ -190,11 +241,13 @@ pub fn parse_query(
                         lhs: Prefix {
                             or: Some(Box::new(lhs)),
                             compare_prefix: None,
+                            is_negated: false,
                             regex_prefix: None,
                         },
                         rhs: Some(Box::new(Prefix {
                             or: Some(Box::new(rhs)),
                             compare_prefix: None,
+                            is_negated: false,
                             regex_prefix: None,
                         })),
                     },
@@ -207,6 +260,7 @@ pub fn parse_query(
                             or: Some(Box::new(returned_or)),
                             regex_prefix: None,
                             compare_prefix: None,
+                            is_negated: false,
                         });
                     } else {
                         return Err(QueryError("Missing closing parentheses".into()));
@@ -231,9 +285,17 @@ pub fn parse_query(
                     return Err(QueryError("Missing closing quotation".into()));
                 }
             } else {
+                // A bare `name` is just a process name, unless it's followed by an operator.
+                let is_explicit_name = queue_top.to_lowercase() == "name"
+                    && matches!(query.front().map(String::as_str), Some("=") | Some("~"));
+
                 //  Get prefix type...
-                let prefix_type = queue_top.parse::<PrefixType>()?;
-                let content = if let PrefixType::Name = prefix_type {
+                let prefix_type = if is_explicit_name {
+                    PrefixType::Name
+                } else {
+                    queue_top.parse::<PrefixType>()?
+                };
+                let content = if let (PrefixType::Name, false) = (&prefix_type, is_explicit_name) {
                     Some(queue_top)
                 } else {
                     query.pop_front()
@@ -241,43 +303,36 @@ pub fn parse_query(
 
                 if let Some(content) = content {
                     match &prefix_type {
-                        PrefixType::Name => {
+                        PrefixType::Name if !is_explicit_name => {
                             return Ok(Prefix {
                                 or: None,
                                 regex_prefix: Some((prefix_type, StringQuery::Value(content))),
                                 compare_prefix: None,
+                                is_negated: false,
                             })
                         }
-                        PrefixType::Pid | PrefixType::State | PrefixType::User => {
-                            // We have to check if someone put an "="...
-                            if content == "=" {
-                                // Check next string if possible
-                                if let Some(queue_next) = query.pop_front() {
-                                    // TODO: [Query, ???] Need to consider the following cases:
-                                    // - (test)
-                                    // - (test
-                                    // - test)
-                                    // These are split into 2 to 3 different strings due to parentheses being
-                                    // delimiters in our query system.
-                                    //
-                                    // Do we want these to be valid?  They should, as a string, right?
-
-                                    return Ok(Prefix {
-                                        or: None,
-                                        regex_prefix: Some((
-                                            prefix_type,
-                                            StringQuery::Value(queue_next),
-                                        )),
-                                        compare_prefix: None,
-                                    });
-                                }
+                        PrefixType::Name
+                        | PrefixType::Pid
+                        | PrefixType::State
+                        | PrefixType::User
+                        | PrefixType::Elevated => {
+                            let string_query = if content == "=" {
+                                StringQuery::Value(process_string_value(query)?)
+                            } else if content == "~" {
+                                StringQuery::RegexValue(process_string_value(query)?)
+                            } else if content == "\"" {
+                                query.push_front(content);
+                                StringQuery::Value(process_string_value(query)?)
                             } else {
-                                return Ok(Prefix {
-                                    or: None,
-                                    regex_prefix: Some((prefix_type, StringQuery::Value(content))),
-                                    compare_prefix: None,
-                                });
-                            }
+                                StringQuery::Value(content)
+                            };
+
+                            return Ok(Prefix {
+                                or: None,
+                                regex_prefix: Some((prefix_type, string_query)),
+                                compare_prefix: None,
+                                is_negated: false,
+                            });
                         }
                         _ => {
                             // Now we gotta parse the content... yay.
@@ -387,6 +442,7 @@ pub fn parse_query(
                                             prefix_type,
                                             NumericalQuery { condition, value },
                                         )),
+                                        is_negated: false,
                                     });
                                 }
                             }
@@ -406,18 +462,32 @@ pub fn parse_query(
 
     let mut split_query = VecDeque::new();
 
+    // A `!` negates what follows it, like in `!firefox`, but is only split off at the start of a
+    // word, so names with a `!` in the middle can still be searched for.
+    let mut push_word = |word: &str| {
+        let name = word.trim_start_matches('!');
+        if name.is_empty() {
+            split_query.push_back(word.to_owned());
+        } else {
+            for _ in 0..(word.len() - name.len()) {
+                split_query.push_back("!".to_owned());
+            }
+            split_query.push_back(name.to_owned());
+        }
+    };
+
     search_query.split_whitespace().for_each(|s| {
         // From https://stackoverflow.com/a/56923739 in order to get a split but include the parentheses
         let mut last = 0;
         for (index, matched) in s.match_indices(|x| DELIMITER_LIST.contains(&x)) {
             if last != index {
-                split_query.push_back(s[last..index].to_owned());
+                push_word(&s[last..index]);
             }
-            split_query.push_back(matched.to_owned());
+            push_word(matched);
             last = index + matched.len();
         }
         if last < s.len() {
-            split_query.push_back(s[last..].to_owned());
+            push_word(&s[last..]);
         }
     });
 
@@ -568,6 +638,7 @@ pub enum PrefixType {
     Name,
     State,
     User,
+    Elevated,
     __Nonexhaustive,
 }
 
@@ -591,6 +662,7 @@ impl std::str::FromStr for PrefixType {
             "pid" => Ok(Pid),
             "state" => Ok(State),
             "user" => Ok(User),
+            "elevated" => Ok(Elevated),
             _ => Ok(Name),
         }
     }
@@ -601,6 +673,7 @@ pub struct Prefix {
     pub or: Option<Box<Or>>,
     pub regex_prefix: Option<(PrefixType, StringQuery)>,
     pub compare_prefix: Option<(PrefixType, NumericalQuery)>,
+    pub is_negated: bool,
 }
 
 impl Prefix {
@@ -614,16 +687,25 @@ impl Prefix {
                 is_ignoring_case,
                 is_searching_with_regex,
             );
-        } else if let Some((prefix_type, StringQuery::Value(regex_string))) = &mut self.regex_prefix
-        {
+        } else if let Some((prefix_type, string_query)) = &mut self.regex_prefix {
+            let (regex_string, is_regex) = match string_query {
+                StringQuery::Value(regex_string) => (regex_string, is_searching_with_regex),
+                StringQuery::RegexValue(regex_string) => (regex_string, true),
+                StringQuery::Regex(_) => return Ok(()),
+            };
+
             match prefix_type {
-                PrefixType::Pid | PrefixType::Name | PrefixType::State | PrefixType::User => {
+                PrefixType::Pid
+                | PrefixType::Name
+                | PrefixType::State
+                | PrefixType::User
+                | PrefixType::Elevated => {
                     let escaped_regex: String;
                     let final_regex_string = &format!(
                         "{}{}{}{}",
                         if is_searching_whole_word { "^" } else { "" },
                         if is_ignoring_case { "(?i)" } else { "" },
-                        if !is_searching_with_regex {
+                        if !is_regex {
                             escaped_regex = regex::escape(regex_string);
                             &escaped_regex
                         } else {
@@ -648,6 +730,10 @@ impl Prefix {
     }
 
     pub fn check(&self, process: &ProcessHarvest, is_using_command: bool) -> bool {
+        self.check_condition(process, is_using_command) != self.is_negated
+    }
+
+    fn check_condition(&self, process: &ProcessHarvest, is_using_command: bool) -> bool {
         fn matches_condition(condition: &QueryComparison, lhs: f64, rhs: f64) -> bool {
             match condition {
                 QueryComparison::Equal => (lhs - rhs).abs() < std::f64::EPSILON,
@@ -670,15 +756,9 @@ impl Prefix {
                     }),
                     PrefixType::Pid => r.is_match(process.pid.to_string().as_str()),
                     PrefixType::State => r.is_match(process.process_state.0.as_str()),
-                    PrefixType::User => {
-                        #[cfg(target_family = "unix")]
-                        {
-                            r.is_match(process.user.as_ref())
-                        }
-                        #[cfg(not(target_family = "unix"))]
-                        {
-                            false
-                        }
+                    PrefixType::User => r.is_match(process.user.as_ref()),
+                    PrefixType::Elevated => {
+                        r.is_match(if process.is_elevated { "Yes" } else { "No" })
                     }
                     _ => true,
                 }
@@ -733,6 +813,10 @@ impl Prefix {
 
 impl Debug for Prefix {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_negated {
+            f.write_str("NOT ")?;
+        }
+
         if let Some(or) = &self.or {
             f.write_fmt(format_args!("{:?}", or))
         } else if let Some(regex_prefix) = &self.regex_prefix {
@@ -757,6 +841,8 @@ pub enum QueryComparison {
 #[derive(Debug)]
 pub enum StringQuery {
     Value(String),
+    /// A value that is always treated as a regex, regardless of the regex setting.
+    RegexValue(String),
     Regex(regex::Regex),
 }
 
@@ -765,3 +851,58 @@ pub struct NumericalQuery {
     pub condition: QueryComparison,
     pub value: f64,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn matches(query: &str, process: &ProcessHarvest) -> bool {
+        parse_query(query, false, true, false)
+            .unwrap()
+            .check(process, false)
+    }
+
+    #[test]
+    fn test_field_queries() {
+        let process = ProcessHarvest {
+            pid: 42,
+            name: "postgres".to_string(),
+            cpu_usage_percent: 60.0,
+            process_state: ("Sleeping".to_string(), 'S'),
+            ..ProcessHarvest::default()
+        };
+
+        assert!(matches("cpu > 50 and name ~ \"post.*s\"", &process));
+        assert!(matches("name = postgres and state = sleeping", &process));
+        assert!(!matches("name = post.*", &process));
+        assert!(matches(
+            "not pid = 1 and (cpu < 10 or name ~ ^post)",
+            &process
+        ));
+        assert!(!matches("! (cpu > 50 or pid = 1)", &process));
+        assert!(matches("not not postgres", &process));
+        assert!(!matches("!postgres", &process));
+        assert!(matches("!!postgres", &process));
+        assert!(matches("!(pid = 1)", &process));
+        assert!(!matches("cpu > 50 and !name = postgres", &process));
+        assert!(!matches("post!gres", &process));
+        assert!(matches("elevated = no", &process));
+        assert!(!matches("elevated = yes", &process));
+
+        // Without an operator, `name` is just a process name.
+        assert!(!matches("name", &process));
+
+        assert!(parse_query("name ~", false, true, false).is_err());
+
+        // A `not` without anything to negate is just a process name.
+        let not_process = ProcessHarvest {
+            name: "not".to_string(),
+            ..ProcessHarvest::default()
+        };
+        assert!(matches("not", &not_process));
+        assert!(!matches("not", &process));
+        assert!(matches("not or postgres", &process));
+        assert!(matches("(postgres or not)", &not_process));
+        assert!(!matches("!not", &not_process));
+    }
+}
@@ -3,12 +3,27 @@ use std::{collections::HashMap, time::Instant};
 use unicode_segmentation::GraphemeCursor;
 
 use crate::{
-    app::{layout_manager::BottomWidgetType, query::*},
+    app::{
+        affinity,
+        data_harvester::{
+            cpu::topology::CpuGroup,
+            processes::{ProcessHarvest, SystemdUnit},
+        },
+        inspector::{ProcessCommand, ProcessDetails},
+        layout_manager::{BottomWidget, BottomWidgetType},
+        process_priority::{self, IoPriorityClass},
+        query::*,
+        systemd::UnitAction,
+        threads::{ThreadHarvest, ThreadReader},
+    },
     components::text_table::{CellContent, TableComponentColumn, TableComponentState, WidthBounds},
-    constants,
+    constants, Pid,
 };
 
-use super::widgets::{DiskWidgetState, ProcWidget, TempWidgetState};
+use super::widgets::{
+    CgroupWidgetState, DiskWidgetState, EventWidgetState, ProcWidget, TableFilter, TempWidgetState,
+    UserWidgetState,
+};
 
 #[derive(Debug)]
 pub enum ScrollDirection {
@@ -63,6 +78,12 @@ pub struct AppDeleteDialogState {
     pub keyboard_signal_select: usize,
     pub last_number_press: Option<Instant>,
     pub scroll_pos: usize,
+    /// Descendants of the processes being killed, which aren't killed unless
+    /// `kill_descendants` is set.
+    pub descendant_pids: Vec<Pid>,
+    pub kill_descendants: bool,
+    /// Whether the processes being killed are the marked ones, rather than the selected one.
+    pub is_killing_marked: bool,
 }
 
 pub struct AppHelpDialogState {
@@ -81,6 +102,336 @@ impl Default for AppHelpDialogState {
     }
 }
 
+/// The state of the dialog graphing the usage of a pinned process.
+#[derive(Default)]
+pub struct AppProcessGraphState {
+    pub is_showing_graph: bool,
+    pub pid: Pid,
+    pub name: String,
+}
+
+/// The state of the dialog showing the details of a single process.
+#[derive(Default)]
+pub struct AppProcessInspectorState {
+    pub is_showing_inspector: bool,
+    pub pid: Pid,
+    pub name: String,
+    /// Only fetched when the dialog is opened.
+    pub details: Option<ProcessDetails>,
+    pub scroll_state: ParagraphScrollState,
+}
+
+/// The state of the dialog showing the full command line of a single process.
+#[derive(Default)]
+pub struct AppProcessCommandState {
+    pub is_showing_command: bool,
+    pub pid: Pid,
+    pub name: String,
+    /// Only fetched when the dialog is opened.
+    pub command: Option<ProcessCommand>,
+    pub scroll_state: ParagraphScrollState,
+}
+
+/// The state of the dialog listing the threads of a single process.
+#[derive(Default)]
+pub struct AppProcessThreadsState {
+    pub is_showing_threads: bool,
+    pub pid: Pid,
+    pub name: String,
+    pub reader: ThreadReader,
+    /// The threads from the last read.
+    pub threads: Vec<ThreadHarvest>,
+    /// Why the threads couldn't be read, if the last read failed.
+    pub error: Option<String>,
+    pub scroll_state: ParagraphScrollState,
+}
+
+/// A value that can be changed in the process priority dialog.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriorityField {
+    Nice,
+    IoClass,
+    IoLevel,
+}
+
+impl PriorityField {
+    /// The fields that can be changed on this platform. The I/O priority is only supported on
+    /// Linux.
+    pub const ALL: &'static [PriorityField] = if cfg!(target_os = "linux") {
+        &[
+            PriorityField::Nice,
+            PriorityField::IoClass,
+            PriorityField::IoLevel,
+        ]
+    } else {
+        &[PriorityField::Nice]
+    };
+}
+
+/// The state of the dialog for changing the priority of a process, or of several marked
+/// processes.
+pub struct AppProcessPriorityState {
+    pub is_showing_priority: bool,
+    /// The process whose priority is shown.
+    pub pid: Pid,
+    pub name: String,
+    /// The other marked processes, which the priority is also applied to.
+    pub other_pids: Vec<Pid>,
+    pub nice: i32,
+    pub io_class: IoPriorityClass,
+    pub io_level: u8,
+    /// The priority when the dialog was opened, so only values that were changed get applied.
+    pub initial_nice: i32,
+    pub initial_io_priority: (IoPriorityClass, u8),
+    /// An index into [`PriorityField::ALL`].
+    pub selected_field: usize,
+    /// The error from reading or applying the priority, if any.
+    pub error: Option<String>,
+}
+
+impl Default for AppProcessPriorityState {
+    fn default() -> Self {
+        AppProcessPriorityState {
+            is_showing_priority: false,
+            pid: 0,
+            name: String::default(),
+            other_pids: Vec::new(),
+            nice: 0,
+            io_class: IoPriorityClass::None,
+            io_level: 0,
+            initial_nice: 0,
+            initial_io_priority: (IoPriorityClass::None, 0),
+            selected_field: 0,
+            error: None,
+        }
+    }
+}
+
+impl AppProcessPriorityState {
+    pub fn get_selected_field(&self) -> PriorityField {
+        PriorityField::ALL[self.selected_field]
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected_field + 1 < PriorityField::ALL.len() {
+            self.selected_field += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected_field = self.selected_field.saturating_sub(1);
+    }
+
+    /// Raises or lowers the value of the selected field. Lowering the nice value or I/O level
+    /// gives the process a higher priority.
+    pub fn change_selected(&mut self, increase: bool) {
+        match self.get_selected_field() {
+            PriorityField::Nice => {
+                self.nice = if increase {
+                    (self.nice + 1).min(process_priority::MAX_NICE)
+                } else {
+                    (self.nice - 1).max(process_priority::MIN_NICE)
+                };
+            }
+            PriorityField::IoClass => {
+                self.io_class = self.io_class.cycle(increase);
+            }
+            PriorityField::IoLevel => {
+                if self.io_class.has_levels() {
+                    self.io_level = if increase {
+                        (self.io_level + 1).min(process_priority::MAX_IO_LEVEL)
+                    } else {
+                        self.io_level.saturating_sub(1)
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// The state of the dialog for viewing and changing the CPU affinity of a single process.
+#[derive(Default)]
+pub struct AppProcessAffinityState {
+    pub is_showing_affinity: bool,
+    pub pid: Pid,
+    pub name: String,
+    /// Whether the process may run on each CPU.
+    pub cpus: Vec<bool>,
+    /// The affinity when the dialog was opened, so it is only applied if it was changed.
+    pub initial_cpus: Vec<bool>,
+    pub selected_cpu: usize,
+    /// The error from reading or applying the affinity, if any.
+    pub error: Option<String>,
+}
+
+impl AppProcessAffinityState {
+    /// How many CPUs are shown in each row of the dialog.
+    pub const CPUS_PER_ROW: usize = 8;
+
+    /// Moves the selection forwards by `step` CPUs, if there are enough CPUs left.
+    pub fn select_next(&mut self, step: usize) {
+        if self.selected_cpu + step < self.cpus.len() {
+            self.selected_cpu += step;
+        }
+    }
+
+    /// Moves the selection backwards by `step` CPUs, if there are enough CPUs before it.
+    pub fn select_previous(&mut self, step: usize) {
+        if self.selected_cpu >= step {
+            self.selected_cpu -= step;
+        }
+    }
+
+    pub fn toggle_selected(&mut self) {
+        if let Some(allowed) = self.cpus.get_mut(self.selected_cpu) {
+            *allowed = !*allowed;
+        }
+    }
+
+    pub fn allow_all(&mut self) {
+        self.cpus.iter_mut().for_each(|allowed| *allowed = true);
+    }
+
+    pub fn get_cpu_list(&self) -> String {
+        affinity::format_cpu_list(&self.cpus)
+    }
+}
+
+/// A process waiting in the kill queue.
+pub struct QueuedKill {
+    pub pid: Pid,
+    pub name: String,
+    /// When the process started, so that it isn't mistaken for a new process given the same PID
+    /// after it exits.
+    pub start_time: Option<u64>,
+}
+
+impl QueuedKill {
+    /// Returns whether `process`, which has the queued PID, is still the process that was queued.
+    pub fn is_same_process(&self, process: &ProcessHarvest) -> bool {
+        process.name == self.name && process.start_time == self.start_time
+    }
+}
+
+/// The state of the kill queue, which holds processes until they're reviewed and killed together,
+/// and of the dialog that previews it.
+#[derive(Default)]
+pub struct AppKillQueueState {
+    pub is_showing_queue: bool,
+    pub queue: Vec<QueuedKill>,
+    pub selected_entry: usize,
+    /// The error from killing the queued processes, if any.
+    pub error: Option<String>,
+}
+
+impl AppKillQueueState {
+    /// Adds a process to the end of the queue, unless it's already queued. Returns whether it was
+    /// added.
+    pub fn add(&mut self, pid: Pid, name: String, start_time: Option<u64>) -> bool {
+        if self.queue.iter().any(|queued| queued.pid == pid) {
+            false
+        } else {
+            self.queue.push(QueuedKill {
+                pid,
+                name,
+                start_time,
+            });
+            true
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected_entry + 1 < self.queue.len() {
+            self.selected_entry += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected_entry = self.selected_entry.saturating_sub(1);
+    }
+
+    /// Takes the selected process out of the queue.
+    pub fn remove_selected(&mut self) {
+        if self.selected_entry < self.queue.len() {
+            self.queue.remove(self.selected_entry);
+        }
+        self.selected_entry = self.selected_entry.min(self.queue.len().saturating_sub(1));
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.selected_entry = 0;
+    }
+}
+
+/// The state of the dialog that asks before restarting or stopping a process' systemd unit.
+#[derive(Default)]
+pub struct AppUnitControlState {
+    /// The unit and what to do to it, while the dialog is open.
+    pub pending: Option<(SystemdUnit, UnitAction)>,
+    /// Why the last attempt failed, if it did.
+    pub error: Option<String>,
+}
+
+/// An option that can be changed in the options menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OptionsMenuEntry {
+    TemperatureUnit,
+    NetworkUnit,
+    NetworkBinaryPrefix,
+    DotMarker,
+    TreeMode,
+    Grouping,
+}
+
+impl OptionsMenuEntry {
+    pub const ALL: [OptionsMenuEntry; 6] = [
+        OptionsMenuEntry::TemperatureUnit,
+        OptionsMenuEntry::NetworkUnit,
+        OptionsMenuEntry::NetworkBinaryPrefix,
+        OptionsMenuEntry::DotMarker,
+        OptionsMenuEntry::TreeMode,
+        OptionsMenuEntry::Grouping,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            OptionsMenuEntry::TemperatureUnit => "Temperature unit",
+            OptionsMenuEntry::NetworkUnit => "Network unit",
+            OptionsMenuEntry::NetworkBinaryPrefix => "Network binary prefixes",
+            OptionsMenuEntry::DotMarker => "Graph markers",
+            OptionsMenuEntry::TreeMode => "Process tree",
+            OptionsMenuEntry::Grouping => "Group processes",
+        }
+    }
+}
+
+/// The state of the menu for changing options while running.
+#[derive(Default)]
+pub struct AppOptionsMenuState {
+    pub is_showing_options: bool,
+    /// An index into [`OptionsMenuEntry::ALL`].
+    pub selected_entry: usize,
+    /// The result of the last time the options were saved to the config file, if any.
+    pub save_result: Option<Result<(), String>>,
+}
+
+impl AppOptionsMenuState {
+    pub fn get_selected_entry(&self) -> OptionsMenuEntry {
+        OptionsMenuEntry::ALL[self.selected_entry]
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected_entry + 1 < OptionsMenuEntry::ALL.len() {
+            self.selected_entry += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected_entry = self.selected_entry.saturating_sub(1);
+    }
+}
+
 /// AppSearchState deals with generic searching (I might do this in the future).
 pub struct AppSearchState {
     pub is_enabled: bool,
@@ -149,6 +500,10 @@ impl ProcState {
 pub struct NetWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
+    /// Whether to plot the cumulative totals rather than the rates.
+    pub show_totals: bool,
+    /// Whether to plot the errors and dropped packets rather than the rates.
+    pub show_errors: bool,
 }
 
 impl NetWidgetState {
@@ -156,6 +511,8 @@ impl NetWidgetState {
         NetWidgetState {
             current_display_time,
             autohide_timer,
+            show_totals: false,
+            show_errors: false,
         }
     }
 }
@@ -163,6 +520,9 @@ impl NetWidgetState {
 pub struct NetState {
     pub force_update: Option<u64>,
     pub widget_states: HashMap<u64, NetWidgetState>,
+    /// Only interfaces whose name matches this are collected. It's shared by every network widget,
+    /// as they all show the same totals.
+    pub filter: TableFilter,
 }
 
 impl NetState {
@@ -170,6 +530,7 @@ impl NetState {
         NetState {
             force_update: None,
             widget_states,
+            filter: TableFilter::default(),
         }
     }
 
@@ -218,16 +579,38 @@ impl CpuWidgetState {
     }
 }
 
+/// A group of CPUs shown together in the CPU widget, such as a NUMA node.
+pub struct CpuNode {
+    pub name: String,
+    /// The name shown when the legend is narrow.
+    pub short_name: String,
+    /// The numbers of the CPUs in this node.
+    pub cpus: Vec<usize>,
+    /// Whether this node is shown as just the average of its CPUs.
+    pub is_collapsed: bool,
+}
+
 pub struct CpuState {
     pub force_update: Option<u64>,
     pub widget_states: HashMap<u64, CpuWidgetState>,
+    /// The groups that CPUs are shown in, which is empty if they aren't grouped.
+    pub nodes: Vec<CpuNode>,
 }
 
 impl CpuState {
-    pub fn init(widget_states: HashMap<u64, CpuWidgetState>) -> Self {
+    pub fn init(widget_states: HashMap<u64, CpuWidgetState>, nodes: Vec<CpuGroup>) -> Self {
         CpuState {
             force_update: None,
             widget_states,
+            nodes: nodes
+                .into_iter()
+                .map(|group| CpuNode {
+                    name: group.name,
+                    short_name: group.short_name,
+                    cpus: group.cpus,
+                    is_collapsed: false,
+                })
+                .collect(),
         }
     }
 
@@ -275,6 +658,227 @@ impl MemState {
     }
 }
 
+pub struct PowerWidgetState {
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl PowerWidgetState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        PowerWidgetState {
+            current_display_time,
+            autohide_timer,
+        }
+    }
+}
+
+pub struct PowerState {
+    pub force_update: Option<u64>,
+    pub widget_states: HashMap<u64, PowerWidgetState>,
+}
+
+impl PowerState {
+    pub fn init(widget_states: HashMap<u64, PowerWidgetState>) -> Self {
+        PowerState {
+            force_update: None,
+            widget_states,
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut PowerWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&PowerWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct PressureWidgetState {
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl PressureWidgetState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        PressureWidgetState {
+            current_display_time,
+            autohide_timer,
+        }
+    }
+}
+
+pub struct PressureState {
+    pub force_update: Option<u64>,
+    pub widget_states: HashMap<u64, PressureWidgetState>,
+}
+
+impl PressureState {
+    pub fn init(widget_states: HashMap<u64, PressureWidgetState>) -> Self {
+        PressureState {
+            force_update: None,
+            widget_states,
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut PressureWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&PressureWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct LoadAvgWidgetState {
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl LoadAvgWidgetState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        LoadAvgWidgetState {
+            current_display_time,
+            autohide_timer,
+        }
+    }
+}
+
+pub struct LoadAvgState {
+    pub force_update: Option<u64>,
+    pub widget_states: HashMap<u64, LoadAvgWidgetState>,
+}
+
+impl LoadAvgState {
+    pub fn init(widget_states: HashMap<u64, LoadAvgWidgetState>) -> Self {
+        LoadAvgState {
+            force_update: None,
+            widget_states,
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut LoadAvgWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&LoadAvgWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct LatencyWidgetState {
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl LatencyWidgetState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        LatencyWidgetState {
+            current_display_time,
+            autohide_timer,
+        }
+    }
+}
+
+pub struct LatencyState {
+    pub force_update: Option<u64>,
+    pub widget_states: HashMap<u64, LatencyWidgetState>,
+}
+
+impl LatencyState {
+    pub fn init(widget_states: HashMap<u64, LatencyWidgetState>) -> Self {
+        LatencyState {
+            force_update: None,
+            widget_states,
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut LatencyWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&LatencyWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct PluginWidgetState {
+    /// The name of the plugin shown.
+    pub plugin: String,
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl PluginWidgetState {
+    pub fn init(
+        plugin: String, current_display_time: u64, autohide_timer: Option<Instant>,
+    ) -> Self {
+        PluginWidgetState {
+            plugin,
+            current_display_time,
+            autohide_timer,
+        }
+    }
+}
+
+pub struct PluginState {
+    pub force_update: Option<u64>,
+    pub widget_states: HashMap<u64, PluginWidgetState>,
+}
+
+impl PluginState {
+    pub fn init(widget_states: HashMap<u64, PluginWidgetState>) -> Self {
+        PluginState {
+            force_update: None,
+            widget_states,
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut PluginWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&PluginWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct FrequencyWidgetState {
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl FrequencyWidgetState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        FrequencyWidgetState {
+            current_display_time,
+            autohide_timer,
+        }
+    }
+}
+
+pub struct FrequencyState {
+    pub force_update: Option<u64>,
+    pub widget_states: HashMap<u64, FrequencyWidgetState>,
+}
+
+impl FrequencyState {
+    pub fn init(widget_states: HashMap<u64, FrequencyWidgetState>) -> Self {
+        FrequencyState {
+            force_update: None,
+            widget_states,
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut FrequencyWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&FrequencyWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
 pub struct TempState {
     pub widget_states: HashMap<u64, TempWidgetState>,
 }
@@ -293,6 +897,60 @@ impl TempState {
     }
 }
 
+pub struct CgroupState {
+    pub widget_states: HashMap<u64, CgroupWidgetState>,
+}
+
+impl CgroupState {
+    pub fn init(widget_states: HashMap<u64, CgroupWidgetState>) -> Self {
+        CgroupState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut CgroupWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&CgroupWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct EventState {
+    pub widget_states: HashMap<u64, EventWidgetState>,
+}
+
+impl EventState {
+    pub fn init(widget_states: HashMap<u64, EventWidgetState>) -> Self {
+        EventState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut EventWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&EventWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct UserState {
+    pub widget_states: HashMap<u64, UserWidgetState>,
+}
+
+impl UserState {
+    pub fn init(widget_states: HashMap<u64, UserWidgetState>) -> Self {
+        UserState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut UserWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&UserWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
 pub struct DiskState {
     pub widget_states: HashMap<u64, DiskWidgetState>,
 }
@@ -323,10 +981,24 @@ pub struct BasicTableWidgetState {
     pub right_brc: Option<(u16, u16)>,
 }
 
-#[derive(Default)]
 pub struct BatteryWidgetState {
     pub currently_selected_battery_index: usize,
     pub tab_click_locs: Option<Vec<((u16, u16), (u16, u16))>>,
+    pub is_graph_mode: bool,
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl BatteryWidgetState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        BatteryWidgetState {
+            currently_selected_battery_index: 0,
+            tab_click_locs: None,
+            is_graph_mode: false,
+            current_display_time,
+            autohide_timer,
+        }
+    }
 }
 
 pub struct BatteryState {
@@ -347,8 +1019,58 @@ impl BatteryState {
     }
 }
 
+/// The widget states of a layout that was switched away from, so they can be restored when it's
+/// switched back to.
+pub struct SavedLayoutState {
+    /// The ID and type of each widget, to check that the layout hasn't changed since.
+    pub widget_types: Vec<(u64, BottomWidgetType)>,
+    pub current_widget: BottomWidget,
+    pub cpu_state: CpuState,
+    pub mem_state: MemState,
+    pub net_state: NetState,
+    pub proc_state: ProcState,
+    pub temp_state: TempState,
+    pub disk_state: DiskState,
+    pub battery_state: BatteryState,
+    pub power_state: PowerState,
+    pub pressure_state: PressureState,
+    pub load_avg_state: LoadAvgState,
+    pub latency_state: LatencyState,
+    pub plugin_state: PluginState,
+    pub frequency_state: FrequencyState,
+    pub cgroup_state: CgroupState,
+    pub event_state: EventState,
+    pub user_state: UserState,
+    pub basic_table_widget_state: Option<BasicTableWidgetState>,
+}
+
 #[derive(Default)]
 pub struct ParagraphScrollState {
     pub current_scroll_index: u16,
     pub max_scroll_index: u16,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_queued_kill_is_same_process() {
+        let mut kill_queue_state = AppKillQueueState::default();
+        assert!(kill_queue_state.add(10, "postgres".to_string(), Some(1000)));
+        assert!(!kill_queue_state.add(10, "postgres".to_string(), Some(1000)));
+
+        let process = |name: &str, start_time: Option<u64>| ProcessHarvest {
+            pid: 10,
+            name: name.to_string(),
+            start_time,
+            ..ProcessHarvest::default()
+        };
+        let queued = &kill_queue_state.queue[0];
+        assert!(queued.is_same_process(&process("postgres", Some(1000))));
+
+        // The PID was reused after the queued process exited.
+        assert!(!queued.is_same_process(&process("postgres", Some(2000))));
+        assert!(!queued.is_same_process(&process("bash", Some(1000))));
+    }
+}
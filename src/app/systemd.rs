@@ -0,0 +1,77 @@
+//! Controlling the systemd units that processes belong to.
+//!
+//! This calls systemd's D-Bus API through `busctl`, which comes with systemd, so it works
+//! wherever there is a systemd to talk to. Units run by a user's service manager are controlled
+//! through that user's bus, which is only reachable when bottom is run as that user.
+
+#[cfg(target_os = "linux")]
+use crate::{
+    app::data_harvester::processes::SystemdUnit,
+    utils::error::{BottomError, Result},
+};
+
+/// An action that can be run on a systemd unit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnitAction {
+    Restart,
+    Stop,
+}
+
+impl UnitAction {
+    /// The name of the `org.freedesktop.systemd1.Manager` method for this action.
+    #[cfg(target_os = "linux")]
+    fn method(self) -> &'static str {
+        match self {
+            UnitAction::Restart => "RestartUnit",
+            UnitAction::Stop => "StopUnit",
+        }
+    }
+
+    /// The name of the action, as used in titles.
+    pub fn name(self) -> &'static str {
+        match self {
+            UnitAction::Restart => "Restart",
+            UnitAction::Stop => "Stop",
+        }
+    }
+
+    pub fn verb(self) -> &'static str {
+        match self {
+            UnitAction::Restart => "restart",
+            UnitAction::Stop => "stop",
+        }
+    }
+}
+
+/// Queues a job to restart or stop a unit. This returns once systemd has accepted the job, not
+/// once the unit has actually restarted or stopped.
+#[cfg(target_os = "linux")]
+pub fn control_unit(unit: &SystemdUnit, action: UnitAction) -> Result<()> {
+    let mut command = std::process::Command::new("busctl");
+    if unit.is_user {
+        command.arg("--user");
+    }
+    let output = command
+        .args([
+            "call",
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+            action.method(),
+            "ss",
+            &unit.name,
+            "replace",
+        ])
+        .output()?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(BottomError::GenericError(format!(
+            "Failed to {} {}: {}",
+            action.verb(),
+            unit.name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
@@ -0,0 +1,121 @@
+//! Reads the threads of a single process for the thread view. Like the process inspector, this
+//! is only done for the process being viewed, and only while the view is open.
+
+use std::time::Instant;
+
+use fxhash::FxHashMap;
+
+use crate::Pid;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThreadHarvest {
+    pub tid: Pid,
+    pub name: String,
+    /// CPU usage as a percentage of all CPUs, in the same way as the process widget.
+    pub cpu_usage_percent: f64,
+    /// The current state of the thread, as shown in the process widget.
+    pub state: String,
+}
+
+/// Reads the threads of a process, keeping the CPU time of each thread from the last read so
+/// that CPU usage can be calculated. The first read reports no CPU usage.
+#[derive(Debug, Default)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub struct ThreadReader {
+    pid: Pid,
+    prev_cpu_ticks: FxHashMap<Pid, u64>,
+    prev_instant: Option<Instant>,
+}
+
+impl ThreadReader {
+    pub fn new(pid: Pid) -> Self {
+        ThreadReader {
+            pid,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the threads of the process, sorted by CPU usage. Fails if the process is gone or
+    /// can't be read.
+    #[cfg(target_os = "linux")]
+    pub fn read_threads(&mut self) -> Result<Vec<ThreadHarvest>, String> {
+        let process = procfs::process::Process::new(self.pid).map_err(|err| err.to_string())?;
+        let tasks = process.tasks().map_err(|err| err.to_string())?;
+
+        let now = Instant::now();
+        let elapsed_secs = self
+            .prev_instant
+            .map(|prev_instant| now.duration_since(prev_instant).as_secs_f64())
+            .unwrap_or(0.0);
+        let ticks_per_second = procfs::ticks_per_second().unwrap_or(100) as f64;
+        let num_cpus = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) }.max(1) as f64;
+
+        let mut cpu_ticks = FxHashMap::default();
+        let mut threads: Vec<ThreadHarvest> = tasks
+            .filter_map(|task| {
+                let task = task.ok()?;
+                let stat = task.stat().ok()?;
+                let ticks = stat.utime + stat.stime;
+                cpu_ticks.insert(task.tid, ticks);
+
+                let cpu_usage_percent = match self.prev_cpu_ticks.get(&task.tid) {
+                    Some(prev_ticks) => get_cpu_usage_percent(
+                        ticks.saturating_sub(*prev_ticks),
+                        elapsed_secs,
+                        ticks_per_second,
+                        num_cpus,
+                    ),
+                    None => 0.0,
+                };
+
+                Some(ThreadHarvest {
+                    tid: task.tid,
+                    name: stat.comm,
+                    cpu_usage_percent,
+                    state: sysinfo::ProcessStatus::from(stat.state).to_string(),
+                })
+            })
+            .collect();
+        threads.sort_by(|a, b| {
+            b.cpu_usage_percent
+                .partial_cmp(&a.cpu_usage_percent)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.tid.cmp(&b.tid))
+        });
+
+        self.prev_cpu_ticks = cpu_ticks;
+        self.prev_instant = Some(now);
+
+        Ok(threads)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn read_threads(&mut self) -> Result<Vec<ThreadHarvest>, String> {
+        Err("Not supported on this platform.".to_string())
+    }
+}
+
+/// Converts the CPU time a thread used over some period into a percentage of all CPUs.
+#[cfg(any(target_os = "linux", test))]
+fn get_cpu_usage_percent(
+    ticks: u64, elapsed_secs: f64, ticks_per_second: f64, num_cpus: f64,
+) -> f64 {
+    if elapsed_secs > 0.0 {
+        ticks as f64 / ticks_per_second / elapsed_secs / num_cpus * 100.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_cpu_usage_percent() {
+        // A thread using a whole CPU out of four.
+        assert_eq!(get_cpu_usage_percent(200, 2.0, 100.0, 4.0), 25.0);
+        assert_eq!(get_cpu_usage_percent(50, 1.0, 100.0, 1.0), 50.0);
+        assert_eq!(get_cpu_usage_percent(50, 0.0, 100.0, 1.0), 0.0);
+    }
+}
@@ -6,3 +6,15 @@ pub use temperature_table_widget::*;
 
 pub mod disk_table_widget;
 pub use disk_table_widget::*;
+
+pub mod cgroup_table_widget;
+pub use cgroup_table_widget::*;
+
+pub mod event_table_widget;
+pub use event_table_widget::*;
+
+pub mod user_table_widget;
+pub use user_table_widget::*;
+
+pub mod table_filter;
+pub use table_filter::*;
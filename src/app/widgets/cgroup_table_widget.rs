@@ -0,0 +1,32 @@
+use crate::components::text_table::{
+    CellContent, TableComponentColumn, TableComponentState, WidthBounds,
+};
+
+pub struct CgroupWidgetState {
+    pub table_state: TableComponentState,
+}
+
+impl Default for CgroupWidgetState {
+    fn default() -> Self {
+        const CGROUP_HEADERS: [&str; 5] = ["Cgroup", "CPU%", "Mem", "R/s", "W/s"];
+        const WIDTHS: [WidthBounds; CGROUP_HEADERS.len()] = [
+            WidthBounds::soft_from_str(CGROUP_HEADERS[0], Some(0.6)),
+            WidthBounds::Hard(7),
+            WidthBounds::Hard(8),
+            WidthBounds::Hard(8),
+            WidthBounds::Hard(8),
+        ];
+
+        CgroupWidgetState {
+            table_state: TableComponentState::new(
+                CGROUP_HEADERS
+                    .iter()
+                    .zip(WIDTHS)
+                    .map(|(header, width)| {
+                        TableComponentColumn::new_custom(CellContent::new(*header, None), width)
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
@@ -1,14 +1,73 @@
-use crate::components::text_table::{
-    CellContent, TableComponentColumn, TableComponentState, WidthBounds,
+use crate::{
+    app::{data_harvester::disks::DiskHarvest, widgets::TableFilter},
+    components::text_table::{
+        CellContent, SortOrder, SortState, SortableState, TableComponentColumn,
+        TableComponentState, WidthBounds,
+    },
+    data_conversion::TableData,
 };
 
 pub struct DiskWidgetState {
     pub table_state: TableComponentState,
+    /// The rows of this widget, sorted by its own sort column.
+    pub table_data: TableData,
+    pub force_update_data: bool,
+    /// Only disks whose name or mount point matches this are shown.
+    pub filter: TableFilter,
+    pub hide_pseudo_filesystems: bool,
 }
 
-impl Default for DiskWidgetState {
-    fn default() -> Self {
-        const DISK_HEADERS: [&str; 7] = ["Disk", "Mount", "Used", "Free", "Total", "R/s", "W/s"];
+/// Filesystems that don't store data on a device, which can be hidden to cut down on noise on
+/// hosts with many containers or snaps.
+const PSEUDO_FILESYSTEMS: [&str; 12] = [
+    "tmpfs", "devtmpfs", "ramfs", "squashfs", "overlay", "aufs", "proc", "procfs", "sysfs",
+    "devfs", "nullfs", "fdescfs",
+];
+
+impl DiskWidgetState {
+    pub const DISK: usize = 0;
+    pub const MOUNT: usize = 1;
+    pub const USED_PERCENT: usize = 2;
+    pub const FREE: usize = 3;
+    pub const TOTAL: usize = 4;
+    pub const READ_PER_SECOND: usize = 5;
+    pub const WRITE_PER_SECOND: usize = 6;
+    pub const ACTIVE_PERCENT: usize = 7;
+    pub const QUEUE_LENGTH: usize = 8;
+    pub const FREE_INODES: usize = 9;
+    pub const USED_INODES_PERCENT: usize = 10;
+    pub const SMART_TEMPERATURE: usize = 11;
+    pub const SMART_WEAR: usize = 12;
+    pub const SMART_REALLOCATED: usize = 13;
+    pub const SMART_POWER_ON_HOURS: usize = 14;
+    pub const FULL_IN: usize = 15;
+
+    /// The activity columns, which are hidden unless enabled.
+    const ACTIVITY_COLUMNS: std::ops::Range<usize> = 7..9;
+
+    /// The inode columns, which are hidden unless enabled.
+    const INODE_COLUMNS: std::ops::Range<usize> = 9..11;
+
+    /// The SMART columns, which are hidden unless enabled.
+    const SMART_COLUMNS: std::ops::Range<usize> = 11..15;
+
+    /// Returns the mount point of the selected disk.
+    pub fn get_selected_mount(&self) -> Option<&str> {
+        self.table_data
+            .data
+            .get(self.table_state.current_scroll_position)?
+            .row()
+            .get(Self::MOUNT)
+            .map(|mount| mount.main_text().as_ref())
+    }
+
+    pub fn init(
+        show_inodes: bool, show_smart: bool, show_activity: bool, show_full_estimate: bool,
+    ) -> Self {
+        const DISK_HEADERS: [&str; 16] = [
+            "Disk", "Mount", "Used", "Free", "Total", "R/s", "W/s", "Active", "Queue", "IFree",
+            "IUse%", "Temp", "Wear", "Realloc", "Hours", "Full in",
+        ];
         const WIDTHS: [WidthBounds; DISK_HEADERS.len()] = [
             WidthBounds::soft_from_str(DISK_HEADERS[0], Some(0.2)),
             WidthBounds::soft_from_str(DISK_HEADERS[1], Some(0.2)),
@@ -17,18 +76,149 @@ impl Default for DiskWidgetState {
             WidthBounds::Hard(6),
             WidthBounds::Hard(7),
             WidthBounds::Hard(7),
+            WidthBounds::Hard(7),
+            WidthBounds::Hard(6),
+            WidthBounds::Hard(6),
+            WidthBounds::Hard(5),
+            WidthBounds::Hard(6),
+            WidthBounds::Hard(5),
+            WidthBounds::Hard(7),
+            WidthBounds::Hard(7),
+            WidthBounds::Hard(8),
         ];
 
+        // Text columns are sorted alphabetically by default, and the rest from largest to smallest,
+        // other than the estimate of when a disk is full, where the soonest matters most.
+        let default_sort_orderings = (0..DISK_HEADERS.len())
+            .map(|index| {
+                if index <= Self::MOUNT || index == Self::FULL_IN {
+                    SortOrder::Ascending
+                } else {
+                    SortOrder::Descending
+                }
+            })
+            .collect();
+
+        let mut table_state = TableComponentState::new(
+            DISK_HEADERS
+                .iter()
+                .zip(WIDTHS)
+                .map(|(header, width)| {
+                    TableComponentColumn::new_custom(CellContent::new(*header, None), width)
+                })
+                .collect(),
+        )
+        .sort_state(SortState::Sortable(SortableState::new(
+            Self::DISK,
+            SortOrder::Ascending,
+            default_sort_orderings,
+        )));
+
+        if !show_inodes {
+            table_state.columns[Self::INODE_COLUMNS]
+                .iter_mut()
+                .for_each(|column| column.is_hidden = true);
+        }
+
+        if !show_smart {
+            table_state.columns[Self::SMART_COLUMNS]
+                .iter_mut()
+                .for_each(|column| column.is_hidden = true);
+        }
+
+        // Disk activity is only collected on Windows.
+        if !show_activity || !cfg!(target_os = "windows") {
+            table_state.columns[Self::ACTIVITY_COLUMNS]
+                .iter_mut()
+                .for_each(|column| column.is_hidden = true);
+        }
+
+        if !show_full_estimate {
+            table_state.columns[Self::FULL_IN].is_hidden = true;
+        }
+
         DiskWidgetState {
-            table_state: TableComponentState::new(
-                DISK_HEADERS
-                    .iter()
-                    .zip(WIDTHS)
-                    .map(|(header, width)| {
-                        TableComponentColumn::new_custom(CellContent::new(*header, None), width)
-                    })
-                    .collect(),
-            ),
+            table_state,
+            table_data: TableData::default(),
+            force_update_data: true,
+            filter: TableFilter::default(),
+            hide_pseudo_filesystems: false,
+        }
+    }
+
+    pub fn force_data_update(&mut self) {
+        self.force_update_data = true;
+    }
+
+    /// Whether a disk passes the filter, and isn't a hidden pseudo-filesystem.
+    pub fn is_disk_shown(&self, disk: &DiskHarvest) -> bool {
+        if self.hide_pseudo_filesystems
+            && disk
+                .fs_type
+                .as_deref()
+                .map(|fs_type| PSEUDO_FILESYSTEMS.contains(&fs_type))
+                .unwrap_or(false)
+        {
+            return false;
         }
+
+        self.filter.is_match(&disk.name) || self.filter.is_match(&disk.mount_point)
+    }
+
+    /// Changes the filter, moving back to the top of the table as the rows will change.
+    pub fn change_filter(&mut self, change: impl FnOnce(&mut TableFilter)) {
+        change(&mut self.filter);
+        self.table_state.current_scroll_position = 0;
+        self.table_state.scroll_bar = 0;
+        self.force_data_update();
+    }
+
+    pub fn push_filter_char(&mut self, c: char) {
+        self.change_filter(|filter| filter.push_char(c));
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.change_filter(TableFilter::clear);
+    }
+
+    pub fn toggle_pseudo_filesystems(&mut self) {
+        self.hide_pseudo_filesystems = !self.hide_pseudo_filesystems;
+        self.table_state.current_scroll_position = 0;
+        self.table_state.scroll_bar = 0;
+        self.force_data_update();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_disk_shown() {
+        let disk = |name: &str, mount_point: &str, fs_type: &str| DiskHarvest {
+            name: name.to_string(),
+            mount_point: mount_point.to_string(),
+            fs_type: Some(fs_type.to_string()),
+            ..Default::default()
+        };
+        let root = disk("/dev/sda1", "/", "ext4");
+        let snap = disk("/dev/loop0", "/snap/core/1", "squashfs");
+
+        let mut state = DiskWidgetState::init(false, false, false, false);
+        assert!(state.is_disk_shown(&root));
+        assert!(state.is_disk_shown(&snap));
+
+        state.toggle_pseudo_filesystems();
+        assert!(state.is_disk_shown(&root));
+        assert!(!state.is_disk_shown(&snap));
+
+        state.toggle_pseudo_filesystems();
+        state.push_filter_char('S');
+        state.push_filter_char('n');
+        assert!(!state.is_disk_shown(&root));
+        assert!(state.is_disk_shown(&snap));
+
+        state.clear_filter();
+        assert!(state.is_disk_shown(&root));
     }
 }
@@ -0,0 +1,30 @@
+use crate::components::text_table::{
+    CellContent, TableComponentColumn, TableComponentState, WidthBounds,
+};
+
+pub struct EventWidgetState {
+    pub table_state: TableComponentState,
+}
+
+impl Default for EventWidgetState {
+    fn default() -> Self {
+        const EVENT_HEADERS: [&str; 3] = ["Time", "Type", "Event"];
+        const WIDTHS: [WidthBounds; EVENT_HEADERS.len()] = [
+            WidthBounds::Hard(19),
+            WidthBounds::Hard(7),
+            WidthBounds::soft_from_str(EVENT_HEADERS[2], None),
+        ];
+
+        EventWidgetState {
+            table_state: TableComponentState::new(
+                EVENT_HEADERS
+                    .iter()
+                    .zip(WIDTHS)
+                    .map(|(header, width)| {
+                        TableComponentColumn::new_custom(CellContent::new(*header, None), width)
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
@@ -2,17 +2,23 @@ use crate::{
     app::{
         data_farmer::{DataCollection, ProcessData, StringPidMap},
         data_harvester::processes::ProcessHarvest,
+        layout_manager::ProcLayoutOptions,
         query::*,
-        AppSearchState, ScrollDirection, SortState,
+        AppSearchState, CursorDirection, ScrollDirection, SortState,
     },
     components::text_table::{
         CellContent, SortOrder, SortableState, TableComponentColumn, TableComponentHeader,
         TableComponentState, WidthBounds,
     },
+    constants::PROCESS_CPU_HISTORY_LENGTH,
     data_conversion::{
-        binary_byte_string, dec_bytes_per_second_string, dec_bytes_per_string, TableData, TableRow,
+        binary_byte_string, dec_bytes_per_second_string, dec_bytes_per_string, duration_string,
+        timestamp_string, TableData, TableRow,
+    },
+    utils::{
+        error::{self, BottomError},
+        gen_util::sort_partial_fn,
     },
-    utils::gen_util::sort_partial_fn,
     Pid,
 };
 
@@ -21,8 +27,11 @@ use fxhash::{FxHashMap, FxHashSet};
 use itertools::Itertools;
 use std::{
     borrow::Cow,
-    cmp::{max, Reverse},
+    cmp::{max, min, Reverse},
+    collections::VecDeque,
 };
+use unicode_segmentation::GraphemeCursor;
+use unicode_width::UnicodeWidthStr;
 
 /// ProcessSearchState only deals with process' search's current settings and state.
 pub struct ProcessSearchState {
@@ -59,8 +68,15 @@ impl ProcessSearchState {
 
 #[derive(Clone, Debug)]
 pub enum ProcWidgetMode {
-    Tree { collapsed_pids: FxHashSet<Pid> },
+    Tree {
+        collapsed_pids: FxHashSet<Pid>,
+    },
     Grouped,
+    /// Processes are grouped under a summary row for each user, which can be expanded to show
+    /// the user's processes.
+    User {
+        expanded_users: FxHashSet<String>,
+    },
     Normal,
 }
 
@@ -68,6 +84,11 @@ pub enum ProcWidgetMode {
 pub enum ProcWidgetColumn {
     CpuPercent,
     Memory { show_percentage: bool },
+    VirtualMemory,
+    SharedMemory,
+    SwapMemory,
+    GpuPercent,
+    GpuMemory,
     PidOrCount { is_count: bool },
     ProcNameOrCommand { is_command: bool },
     ReadPerSecond,
@@ -75,23 +96,40 @@ pub enum ProcWidgetColumn {
     TotalRead,
     TotalWrite,
     State,
+    Threads,
     User,
+    Elevated,
+    StartTime { is_relative: bool },
+    Uptime,
+    SystemdUnit,
+    CpuHistory,
 }
 
 impl ProcWidgetColumn {
     const CPU_PERCENT: CellContent = CellContent::Simple(Cow::Borrowed("CPU%"));
     const MEM_PERCENT: CellContent = CellContent::Simple(Cow::Borrowed("Mem%"));
     const MEM: CellContent = CellContent::Simple(Cow::Borrowed("Mem"));
+    const VIRTUAL_MEM: CellContent = CellContent::Simple(Cow::Borrowed("Virt"));
+    const SHARED_MEM: CellContent = CellContent::Simple(Cow::Borrowed("Shared"));
+    const SWAP_MEM: CellContent = CellContent::Simple(Cow::Borrowed("Swap"));
+    const GPU_PERCENT: CellContent = CellContent::Simple(Cow::Borrowed("GPU%"));
+    const GPU_MEM: CellContent = CellContent::Simple(Cow::Borrowed("GPU Mem"));
     const READS_PER_SECOND: CellContent = CellContent::Simple(Cow::Borrowed("R/s"));
     const WRITES_PER_SECOND: CellContent = CellContent::Simple(Cow::Borrowed("W/s"));
     const TOTAL_READ: CellContent = CellContent::Simple(Cow::Borrowed("T.Read"));
     const TOTAL_WRITE: CellContent = CellContent::Simple(Cow::Borrowed("T.Write"));
     const STATE: CellContent = CellContent::Simple(Cow::Borrowed("State"));
+    const THREADS: CellContent = CellContent::Simple(Cow::Borrowed("Threads"));
     const PROCESS_NAME: CellContent = CellContent::Simple(Cow::Borrowed("Name"));
     const COMMAND: CellContent = CellContent::Simple(Cow::Borrowed("Command"));
     const PID: CellContent = CellContent::Simple(Cow::Borrowed("PID"));
     const COUNT: CellContent = CellContent::Simple(Cow::Borrowed("Count"));
     const USER: CellContent = CellContent::Simple(Cow::Borrowed("User"));
+    const ELEVATED: CellContent = CellContent::Simple(Cow::Borrowed("Elevated"));
+    const START_TIME: CellContent = CellContent::Simple(Cow::Borrowed("Started"));
+    const UPTIME: CellContent = CellContent::Simple(Cow::Borrowed("Uptime"));
+    const SYSTEMD_UNIT: CellContent = CellContent::Simple(Cow::Borrowed("Unit"));
+    const CPU_HISTORY: CellContent = CellContent::Simple(Cow::Borrowed("CPU Hist"));
 
     const SHORTCUT_CPU_PERCENT: CellContent = CellContent::Simple(Cow::Borrowed("CPU%(c)"));
     const SHORTCUT_MEM_PERCENT: CellContent = CellContent::Simple(Cow::Borrowed("Mem%(m)"));
@@ -110,6 +148,11 @@ impl ProcWidgetColumn {
                     &Self::MEM
                 }
             }
+            ProcWidgetColumn::VirtualMemory => &Self::VIRTUAL_MEM,
+            ProcWidgetColumn::SharedMemory => &Self::SHARED_MEM,
+            ProcWidgetColumn::SwapMemory => &Self::SWAP_MEM,
+            ProcWidgetColumn::GpuPercent => &Self::GPU_PERCENT,
+            ProcWidgetColumn::GpuMemory => &Self::GPU_MEM,
             ProcWidgetColumn::PidOrCount { is_count } => {
                 if *is_count {
                     &Self::COUNT
@@ -129,7 +172,13 @@ impl ProcWidgetColumn {
             ProcWidgetColumn::TotalRead => &Self::TOTAL_READ,
             ProcWidgetColumn::TotalWrite => &Self::TOTAL_WRITE,
             ProcWidgetColumn::State => &Self::STATE,
+            ProcWidgetColumn::Threads => &Self::THREADS,
             ProcWidgetColumn::User => &Self::USER,
+            ProcWidgetColumn::Elevated => &Self::ELEVATED,
+            ProcWidgetColumn::StartTime { .. } => &Self::START_TIME,
+            ProcWidgetColumn::Uptime => &Self::UPTIME,
+            ProcWidgetColumn::SystemdUnit => &Self::SYSTEMD_UNIT,
+            ProcWidgetColumn::CpuHistory => &Self::CPU_HISTORY,
         }
     }
 
@@ -137,6 +186,7 @@ impl ProcWidgetColumn {
     pub fn sort(
         &self, sort_descending: bool, data: &mut [&ProcessHarvest], is_using_command: bool,
         cmd_pid_map: &StringPidMap, name_pid_map: &StringPidMap,
+        cpu_history_averages: &FxHashMap<Pid, f64>,
     ) {
         match self {
             ProcWidgetColumn::CpuPercent => {
@@ -157,6 +207,44 @@ impl ProcWidgetColumn {
                     });
                 }
             }
+            ProcWidgetColumn::VirtualMemory => {
+                data.sort_by_cached_key(|p| p.name.to_lowercase());
+                if sort_descending {
+                    data.sort_by_key(|a| Reverse(a.virtual_bytes));
+                } else {
+                    data.sort_by_key(|a| a.virtual_bytes);
+                }
+            }
+            ProcWidgetColumn::SharedMemory => {
+                data.sort_by_cached_key(|p| p.name.to_lowercase());
+                if sort_descending {
+                    data.sort_by_key(|a| Reverse(a.shared_bytes));
+                } else {
+                    data.sort_by_key(|a| a.shared_bytes);
+                }
+            }
+            ProcWidgetColumn::SwapMemory => {
+                data.sort_by_cached_key(|p| p.name.to_lowercase());
+                if sort_descending {
+                    data.sort_by_key(|a| Reverse(a.swap_bytes));
+                } else {
+                    data.sort_by_key(|a| a.swap_bytes);
+                }
+            }
+            ProcWidgetColumn::GpuPercent => {
+                data.sort_by_cached_key(|p| p.name.to_lowercase());
+                data.sort_by(|a, b| {
+                    sort_partial_fn(sort_descending)(a.gpu_usage_percent, b.gpu_usage_percent)
+                });
+            }
+            ProcWidgetColumn::GpuMemory => {
+                data.sort_by_cached_key(|p| p.name.to_lowercase());
+                if sort_descending {
+                    data.sort_by_key(|a| Reverse(a.gpu_mem_bytes));
+                } else {
+                    data.sort_by_key(|a| a.gpu_mem_bytes);
+                }
+            }
             ProcWidgetColumn::PidOrCount { is_count } => {
                 data.sort_by_cached_key(|c| c.name.to_lowercase());
                 if *is_count {
@@ -171,7 +259,7 @@ impl ProcWidgetColumn {
                             })
                         }
                     } else {
-                        #[allow(clippy::collapsible-else-if)]
+                        #[allow(clippy::collapsible_else_if)]
                         if sort_descending {
                             data.sort_by_cached_key(|p| {
                                 Reverse(name_pid_map.get(&p.name).map(|v| v.len()).unwrap_or(0))
@@ -239,17 +327,75 @@ impl ProcWidgetColumn {
                     data.sort_by_cached_key(|p| p.process_state.0.to_lowercase());
                 }
             }
+            ProcWidgetColumn::Threads => {
+                data.sort_by_cached_key(|p| p.name.to_lowercase());
+                if sort_descending {
+                    data.sort_by_key(|a| Reverse(a.num_threads));
+                } else {
+                    data.sort_by_key(|a| a.num_threads);
+                }
+            }
             ProcWidgetColumn::User => {
-                #[cfg(target_family = "unix")]
-                {
-                    data.sort_by_cached_key(|p| p.name.to_lowercase());
-                    if sort_descending {
-                        data.sort_by_cached_key(|p| Reverse(p.user.to_lowercase()));
-                    } else {
-                        data.sort_by_cached_key(|p| p.user.to_lowercase());
-                    }
+                data.sort_by_cached_key(|p| p.name.to_lowercase());
+                if sort_descending {
+                    data.sort_by_cached_key(|p| Reverse(p.user.to_lowercase()));
+                } else {
+                    data.sort_by_cached_key(|p| p.user.to_lowercase());
                 }
             }
+            ProcWidgetColumn::Elevated => {
+                data.sort_by_cached_key(|p| p.name.to_lowercase());
+                if sort_descending {
+                    data.sort_by_key(|a| Reverse(a.is_elevated));
+                } else {
+                    data.sort_by_key(|a| a.is_elevated);
+                }
+            }
+            ProcWidgetColumn::StartTime { .. } => {
+                data.sort_by_cached_key(|p| p.name.to_lowercase());
+                if sort_descending {
+                    data.sort_by_key(|a| Reverse(a.start_time));
+                } else {
+                    data.sort_by_key(|a| a.start_time);
+                }
+            }
+            ProcWidgetColumn::Uptime => {
+                // The longest running processes are the ones that started first. Processes with an
+                // unknown start time are kept at the bottom when sorting from the longest.
+                data.sort_by_cached_key(|p| p.name.to_lowercase());
+                if sort_descending {
+                    data.sort_by_key(|a| Reverse(a.start_time.map(Reverse)));
+                } else {
+                    data.sort_by_key(|a| a.start_time.map(Reverse));
+                }
+            }
+            ProcWidgetColumn::SystemdUnit => {
+                // Processes without a unit are kept at the bottom.
+                let unit_name = |p: &ProcessHarvest| {
+                    p.systemd_unit.as_ref().map(|unit| unit.name.to_lowercase())
+                };
+
+                data.sort_by_cached_key(|p| p.name.to_lowercase());
+                if sort_descending {
+                    data.sort_by_cached_key(|p| {
+                        let name = unit_name(p);
+                        (name.is_none(), Reverse(name))
+                    });
+                } else {
+                    data.sort_by_cached_key(|p| {
+                        let name = unit_name(p);
+                        (name.is_none(), name)
+                    });
+                }
+            }
+            ProcWidgetColumn::CpuHistory => {
+                // Sort by the average of the recent samples.
+                let average =
+                    |p: &ProcessHarvest| cpu_history_averages.get(&p.pid).copied().unwrap_or(0.0);
+
+                data.sort_by_cached_key(|p| p.name.to_lowercase());
+                data.sort_by(|a, b| sort_partial_fn(sort_descending)(average(a), average(b)));
+            }
         }
     }
 
@@ -263,12 +409,23 @@ impl ProcWidgetColumn {
             | ProcWidgetColumn::WritePerSecond
             | ProcWidgetColumn::TotalRead
             | ProcWidgetColumn::TotalWrite
-            | ProcWidgetColumn::Memory { .. } => SortOrder::Descending,
+            | ProcWidgetColumn::Memory { .. }
+            | ProcWidgetColumn::VirtualMemory
+            | ProcWidgetColumn::SharedMemory
+            | ProcWidgetColumn::SwapMemory
+            | ProcWidgetColumn::GpuPercent
+            | ProcWidgetColumn::GpuMemory
+            | ProcWidgetColumn::Threads
+            | ProcWidgetColumn::Elevated
+            | ProcWidgetColumn::StartTime { .. }
+            | ProcWidgetColumn::Uptime
+            | ProcWidgetColumn::CpuHistory => SortOrder::Descending,
 
             ProcWidgetColumn::PidOrCount { is_count: false }
             | ProcWidgetColumn::ProcNameOrCommand { .. }
             | ProcWidgetColumn::State
-            | ProcWidgetColumn::User => SortOrder::Ascending,
+            | ProcWidgetColumn::User
+            | ProcWidgetColumn::SystemdUnit => SortOrder::Ascending,
         }
     }
 }
@@ -284,6 +441,11 @@ impl TableComponentHeader for ProcWidgetColumn {
                     &Self::SHORTCUT_MEM
                 }
             }
+            ProcWidgetColumn::VirtualMemory => &Self::VIRTUAL_MEM,
+            ProcWidgetColumn::SharedMemory => &Self::SHARED_MEM,
+            ProcWidgetColumn::SwapMemory => &Self::SWAP_MEM,
+            ProcWidgetColumn::GpuPercent => &Self::GPU_PERCENT,
+            ProcWidgetColumn::GpuMemory => &Self::GPU_MEM,
             ProcWidgetColumn::PidOrCount { is_count } => {
                 if *is_count {
                     &Self::COUNT
@@ -303,11 +465,23 @@ impl TableComponentHeader for ProcWidgetColumn {
             ProcWidgetColumn::TotalRead => &Self::TOTAL_READ,
             ProcWidgetColumn::TotalWrite => &Self::TOTAL_WRITE,
             ProcWidgetColumn::State => &Self::STATE,
+            ProcWidgetColumn::Threads => &Self::THREADS,
             ProcWidgetColumn::User => &Self::USER,
+            ProcWidgetColumn::Elevated => &Self::ELEVATED,
+            ProcWidgetColumn::StartTime { .. } => &Self::START_TIME,
+            ProcWidgetColumn::Uptime => &Self::UPTIME,
+            ProcWidgetColumn::SystemdUnit => &Self::SYSTEMD_UNIT,
+            ProcWidgetColumn::CpuHistory => &Self::CPU_HISTORY,
         }
     }
 }
 
+/// A named process filter, set in the config file.
+pub struct SavedFilter {
+    pub name: String,
+    pub query: Query,
+}
+
 pub struct ProcWidget {
     pub mode: ProcWidgetMode,
 
@@ -316,10 +490,25 @@ pub struct ProcWidget {
     pub sort_table_state: TableComponentState,
 
     pub is_sort_open: bool,
+    pub show_cpu_history: bool,
+    pub saved_filters: Vec<SavedFilter>,
+    pub saved_filter_index: Option<usize>,
     pub force_rerender: bool,
     pub force_update_data: bool,
+    /// Whether the selected process is kept in the middle of the table as it moves.
+    pub is_following: bool,
+    /// Whether the next update should leave the selection where it was put, rather than move it to
+    /// the row of the process that was selected.
+    is_selection_reset: bool,
+    /// The processes marked for batch actions, which act on these rather than the selected process.
+    pub marked_pids: FxHashSet<Pid>,
+    /// The process that a range of rows is being marked from, if one is. Every row between it and
+    /// the selected row is marked along with it.
+    pub range_start_pid: Option<Pid>,
 
     pub table_data: TableData,
+    /// When grouping by user, the user of each row that is a user's summary row.
+    pub user_summary_rows: Vec<Option<String>>,
 }
 
 impl ProcWidget {
@@ -327,20 +516,30 @@ impl ProcWidget {
     pub const PROC_NAME_OR_CMD: usize = 1;
     pub const CPU: usize = 2;
     pub const MEM: usize = 3;
-    pub const RPS: usize = 4;
-    pub const WPS: usize = 5;
-    pub const T_READ: usize = 6;
-    pub const T_WRITE: usize = 7;
-    #[cfg(target_family = "unix")]
-    pub const USER: usize = 8;
-    #[cfg(target_family = "unix")]
-    pub const STATE: usize = 9;
-    #[cfg(not(target_family = "unix"))]
-    pub const STATE: usize = 8;
-
+    pub const VIRTUAL_MEM: usize = 4;
+    pub const SHARED_MEM: usize = 5;
+    pub const SWAP_MEM: usize = 6;
+    pub const GPU: usize = 7;
+    pub const GPU_MEM: usize = 8;
+    pub const RPS: usize = 9;
+    pub const WPS: usize = 10;
+    pub const T_READ: usize = 11;
+    pub const T_WRITE: usize = 12;
+    pub const USER: usize = 13;
+    pub const ELEVATED: usize = 14;
+    pub const STATE: usize = 15;
+    pub const THREADS: usize = Self::STATE + 1;
+    pub const START_TIME: usize = Self::THREADS + 1;
+    pub const UPTIME: usize = Self::START_TIME + 1;
+    pub const SYSTEMD_UNIT: usize = Self::UPTIME + 1;
+    pub const CPU_HISTORY: usize = Self::SYSTEMD_UNIT + 1;
+
+    #[allow(clippy::too_many_arguments)]
     pub fn init(
         mode: ProcWidgetMode, is_case_sensitive: bool, is_match_whole_word: bool,
-        is_use_regex: bool, show_memory_as_values: bool, is_command: bool,
+        is_use_regex: bool, show_memory_as_values: bool, is_command: bool, show_cpu_history: bool,
+        show_memory_details: bool, show_gpu: bool, show_start_time: bool,
+        is_relative_start_time: bool, show_systemd_unit: bool,
     ) -> Self {
         let mut process_search_state = ProcessSearchState::default();
 
@@ -380,13 +579,31 @@ impl ProcWidget {
                 TableComponentColumn::new(ProcWidgetColumn::Memory {
                     show_percentage: !show_memory_as_values,
                 }),
+                TableComponentColumn::new_hard(ProcWidgetColumn::VirtualMemory, 8),
+                TableComponentColumn::new_hard(ProcWidgetColumn::SharedMemory, 8),
+                TableComponentColumn::new_hard(ProcWidgetColumn::SwapMemory, 8),
+                TableComponentColumn::new_hard(ProcWidgetColumn::GpuPercent, 6),
+                TableComponentColumn::new_hard(ProcWidgetColumn::GpuMemory, 8),
                 TableComponentColumn::new_hard(ProcWidgetColumn::ReadPerSecond, 8),
                 TableComponentColumn::new_hard(ProcWidgetColumn::WritePerSecond, 8),
                 TableComponentColumn::new_hard(ProcWidgetColumn::TotalRead, 8),
                 TableComponentColumn::new_hard(ProcWidgetColumn::TotalWrite, 8),
-                #[cfg(target_family = "unix")]
                 TableComponentColumn::new_soft(ProcWidgetColumn::User, Some(0.05)),
+                TableComponentColumn::new_hard(ProcWidgetColumn::Elevated, 8),
                 TableComponentColumn::new_hard(ProcWidgetColumn::State, 7),
+                TableComponentColumn::new_hard(ProcWidgetColumn::Threads, 7),
+                TableComponentColumn::new_hard(
+                    ProcWidgetColumn::StartTime {
+                        is_relative: is_relative_start_time,
+                    },
+                    if is_relative_start_time { 10 } else { 16 },
+                ),
+                TableComponentColumn::new_hard(ProcWidgetColumn::Uptime, 8),
+                TableComponentColumn::new_soft(ProcWidgetColumn::SystemdUnit, Some(0.2)),
+                TableComponentColumn::new_hard(
+                    ProcWidgetColumn::CpuHistory,
+                    PROCESS_CPU_HISTORY_LENGTH as u16,
+                ),
             ];
 
             let default_sort_orderings = columns
@@ -401,16 +618,66 @@ impl ProcWidget {
             )))
         };
 
-        ProcWidget {
+        let mut proc_widget = ProcWidget {
             proc_search: process_search_state,
             table_state,
             sort_table_state,
             is_sort_open: false,
             mode,
+            show_cpu_history,
+            saved_filters: Vec::new(),
+            saved_filter_index: None,
             force_rerender: true,
             force_update_data: false,
+            is_following: false,
+            is_selection_reset: false,
+            marked_pids: FxHashSet::default(),
+            range_start_pid: None,
             table_data: TableData::default(),
+            user_summary_rows: Vec::new(),
+        };
+
+        // The CPU history is per-process, so it is never shown for grouped processes.
+        if !show_cpu_history || is_count {
+            proc_widget.hide_column(Self::CPU_HISTORY);
         }
+
+        // Elevation is only collected on Windows.
+        if !cfg!(target_os = "windows") {
+            proc_widget.hide_column(Self::ELEVATED);
+        }
+
+        // Thread counts are only collected on Linux.
+        if !cfg!(target_os = "linux") {
+            proc_widget.hide_column(Self::THREADS);
+        }
+
+        // Of the memory details, only the virtual size is collected outside of Linux.
+        if !show_memory_details {
+            proc_widget.hide_column(Self::VIRTUAL_MEM);
+        }
+        if !show_memory_details || !cfg!(target_os = "linux") {
+            proc_widget.hide_column(Self::SHARED_MEM);
+            proc_widget.hide_column(Self::SWAP_MEM);
+        }
+
+        // Per-process GPU usage is only collected on Windows and for NVIDIA GPUs.
+        if !show_gpu || !cfg!(any(feature = "nvidia", target_os = "windows")) {
+            proc_widget.hide_column(Self::GPU);
+            proc_widget.hide_column(Self::GPU_MEM);
+        }
+
+        if !show_start_time {
+            proc_widget.hide_column(Self::START_TIME);
+            proc_widget.hide_column(Self::UPTIME);
+        }
+
+        // Systemd units are only collected on Linux.
+        if !show_systemd_unit || !cfg!(target_os = "linux") {
+            proc_widget.hide_column(Self::SYSTEMD_UNIT);
+        }
+
+        proc_widget
     }
 
     pub fn is_using_command(&self) -> bool {
@@ -427,23 +694,192 @@ impl ProcWidget {
         }
     }
 
+    /// Switches to the next saved filter, or back to no saved filter after the last one.
+    pub fn cycle_saved_filter(&mut self) {
+        if self.saved_filters.is_empty() {
+            return;
+        }
+
+        self.saved_filter_index = match self.saved_filter_index {
+            Some(index) if index + 1 < self.saved_filters.len() => Some(index + 1),
+            Some(_) => None,
+            None => Some(0),
+        };
+        self.force_data_update();
+    }
+
+    /// Returns the name of the active saved filter, if any.
+    /// Applies the settings set for this widget in the layout, on top of the ones for every
+    /// process widget.
+    pub fn apply_layout_options(&mut self, options: &ProcLayoutOptions) -> error::Result<()> {
+        if let Some(is_tree) = options.tree {
+            self.set_tree_mode(is_tree);
+        }
+        if let Some(is_grouped) = options.grouped {
+            self.set_grouped(is_grouped);
+        }
+
+        if let Some(column) = &options.sort {
+            let index = Self::get_column_index(column).ok_or_else(|| {
+                BottomError::ConfigError(format!(
+                    "\"{}\" is not a process column that can be sorted by.",
+                    column
+                ))
+            })?;
+            let order = match options.sort_order.as_deref() {
+                None => None,
+                Some("ascending") => Some(SortOrder::Ascending),
+                Some("descending") => Some(SortOrder::Descending),
+                Some(order) => {
+                    return Err(BottomError::ConfigError(format!(
+                        "\"{}\" is not a valid sort order, use \"ascending\" or \"descending\".",
+                        order
+                    )));
+                }
+            };
+            self.set_sort_column(index, order);
+        } else if options.sort_order.is_some() {
+            return Err(BottomError::ConfigError(
+                "a sort order is set without a column to sort by.".to_string(),
+            ));
+        }
+
+        if let Some(filter_name) = &options.filter {
+            self.select_saved_filter(filter_name)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the index of the column with the given name, which is its header in lowercase.
+    fn get_column_index(name: &str) -> Option<usize> {
+        match name.to_lowercase().as_str() {
+            "pid" | "count" => Some(Self::PID_OR_COUNT),
+            "name" | "command" => Some(Self::PROC_NAME_OR_CMD),
+            "cpu%" | "cpu" => Some(Self::CPU),
+            "mem%" | "mem" => Some(Self::MEM),
+            "virt" => Some(Self::VIRTUAL_MEM),
+            "shared" => Some(Self::SHARED_MEM),
+            "swap" => Some(Self::SWAP_MEM),
+            "gpu%" | "gpu" => Some(Self::GPU),
+            "gpu mem" => Some(Self::GPU_MEM),
+            "r/s" => Some(Self::RPS),
+            "w/s" => Some(Self::WPS),
+            "t.read" => Some(Self::T_READ),
+            "t.write" => Some(Self::T_WRITE),
+            "user" => Some(Self::USER),
+            "elevated" => Some(Self::ELEVATED),
+            "state" => Some(Self::STATE),
+            "threads" => Some(Self::THREADS),
+            "started" => Some(Self::START_TIME),
+            "uptime" => Some(Self::UPTIME),
+            "unit" => Some(Self::SYSTEMD_UNIT),
+            _ => None,
+        }
+    }
+
+    /// Sorts by the given column, in the given order or the column's usual one if there isn't one.
+    fn set_sort_column(&mut self, index: usize, order: Option<SortOrder>) {
+        if let SortState::Sortable(state) = &mut self.table_state.sort_state {
+            if state.current_index != index {
+                state.update_sort_index(index);
+            }
+            if let Some(order) = order {
+                state.order = order;
+            }
+            self.force_data_update();
+        }
+    }
+
+    /// Switches to the saved filter with the given name.
+    fn select_saved_filter(&mut self, name: &str) -> error::Result<()> {
+        let index = self
+            .saved_filters
+            .iter()
+            .position(|filter| filter.name == name)
+            .ok_or_else(|| {
+                BottomError::ConfigError(format!(
+                    "\"{}\" isn't one of the saved process filters.",
+                    name
+                ))
+            })?;
+        self.saved_filter_index = Some(index);
+        self.force_data_update();
+
+        Ok(())
+    }
+
+    pub fn get_saved_filter_name(&self) -> Option<&str> {
+        self.saved_filter_index
+            .and_then(|index| self.saved_filters.get(index))
+            .map(|filter| filter.name.as_str())
+    }
+
+    /// Checks a process against both the active saved filter and the search query.
+    fn matches_filters(&self, process: &ProcessHarvest, search_query: &Option<Query>) -> bool {
+        let is_using_command = self.is_using_command();
+
+        self.saved_filter_index
+            .and_then(|index| self.saved_filters.get(index))
+            .map(|filter| filter.query.check(process, is_using_command))
+            .unwrap_or(true)
+            && search_query
+                .as_ref()
+                .map(|q| q.check(process, is_using_command))
+                .unwrap_or(true)
+    }
+
     /// This function *only* updates the displayed process data. If there is a need to update the actual *stored* data,
     /// call it before this function.
+    ///
+    /// The selection follows the selected process to its new row, so it isn't lost when the
+    /// processes are sorted differently. If it's gone, or processes are grouped, the selection
+    /// stays on the same row instead.
     pub fn update_displayed_process_data(&mut self, data_collection: &DataCollection) {
+        let selected_pid = if self.is_selection_reset {
+            None
+        } else {
+            self.get_selected_pid()
+        };
+        self.is_selection_reset = false;
+
+        // Processes that have exited can't be acted on, so they're unmarked.
+        let process_harvest = &data_collection.process_data.process_harvest;
+        self.marked_pids
+            .retain(|pid| process_harvest.contains_key(pid));
+        self.range_start_pid = self
+            .range_start_pid
+            .filter(|pid| process_harvest.contains_key(pid));
+
         let search_query = if self.proc_search.search_state.is_invalid_or_blank_search() {
             &None
         } else {
             &self.proc_search.search_state.query
         };
-        let table_data = match &self.mode {
-            ProcWidgetMode::Tree { collapsed_pids } => {
-                self.get_tree_table_data(collapsed_pids, data_collection, search_query)
-            }
-            ProcWidgetMode::Grouped | ProcWidgetMode::Normal => {
-                self.get_normal_table_data(data_collection, search_query)
+        let (table_data, user_summary_rows) = match &self.mode {
+            ProcWidgetMode::Tree { collapsed_pids } => (
+                self.get_tree_table_data(collapsed_pids, data_collection, search_query),
+                Vec::new(),
+            ),
+            ProcWidgetMode::User { expanded_users } => {
+                self.get_user_table_data(expanded_users, data_collection, search_query)
             }
+            ProcWidgetMode::Grouped | ProcWidgetMode::Normal => (
+                self.get_normal_table_data(data_collection, search_query),
+                Vec::new(),
+            ),
         };
 
+        let selected_row = selected_pid.and_then(|pid| {
+            table_data
+                .data
+                .iter()
+                .position(|row| row_pid(row) == Some(pid))
+        });
+        if let Some(row) = selected_row {
+            self.table_state.current_scroll_position = row;
+        }
+
         // Now also update the scroll position if needed (that is, the old scroll position was too big for the new list).
         if self.table_state.current_scroll_position >= table_data.data.len() {
             self.table_state.current_scroll_position = table_data.data.len().saturating_sub(1);
@@ -453,6 +889,7 @@ impl ProcWidget {
 
         // Finally, move this data to the widget itself.
         self.table_data = table_data;
+        self.user_summary_rows = user_summary_rows;
     }
 
     fn get_tree_table_data(
@@ -466,8 +903,6 @@ impl ProcWidget {
 
         let ProcessData {
             process_harvest,
-            cmd_pid_map,
-            name_pid_map,
             process_parent_mapping,
             orphan_pids,
             ..
@@ -486,15 +921,7 @@ impl ProcWidget {
             .process_data
             .process_harvest
             .iter()
-            .map(|(pid, process)| {
-                (
-                    *pid,
-                    search_query
-                        .as_ref()
-                        .map(|q| q.check(process, self.is_using_command()))
-                        .unwrap_or(true),
-                )
-            })
+            .map(|(pid, process)| (*pid, self.matches_filters(process, search_query)))
             .collect::<FxHashMap<_, _>>();
 
         let filtered_tree = {
@@ -602,8 +1029,7 @@ impl ProcWidget {
                 let process_text = self.process_to_text(
                     &summed_process,
                     &mut col_widths,
-                    cmd_pid_map,
-                    name_pid_map,
+                    &data_collection.process_data,
                     Some(prefix),
                     is_disabled,
                 );
@@ -622,8 +1048,7 @@ impl ProcWidget {
                 let process_text = self.process_to_text(
                     process,
                     &mut col_widths,
-                    cmd_pid_map,
-                    name_pid_map,
+                    &data_collection.process_data,
                     Some(prefix),
                     is_disabled,
                 );
@@ -666,6 +1091,106 @@ impl ProcWidget {
         }
     }
 
+    /// Returns a summary row for each user, followed by the user's processes if they are expanded,
+    /// along with the user of each summary row.
+    fn get_user_table_data(
+        &self, expanded_users: &FxHashSet<String>, data_collection: &DataCollection,
+        search_query: &Option<Query>,
+    ) -> (TableData, Vec<Option<String>>) {
+        const BRANCH_ENDING: char = '└';
+        const BRANCH_SPLIT: char = '├';
+        const BRANCH_HORIZONTAL: char = '─';
+
+        let mut col_widths = vec![0; self.table_state.columns.len()];
+
+        let mut user_processes: FxHashMap<&str, Vec<&ProcessHarvest>> = FxHashMap::default();
+        data_collection
+            .process_data
+            .process_harvest
+            .values()
+            .filter(|p| self.matches_filters(p, search_query))
+            .for_each(|process| {
+                user_processes
+                    .entry(get_process_user(process))
+                    .or_default()
+                    .push(process);
+            });
+
+        // Each summary is built off of one of the user's processes, so its PID is used to look
+        // up the user again after sorting.
+        let mut pid_users = FxHashMap::default();
+        let summaries = user_processes
+            .iter()
+            .map(|(user, processes)| {
+                let mut summary = processes[0].clone();
+                processes
+                    .iter()
+                    .skip(1)
+                    .for_each(|process| summary.add(process));
+
+                let name = format!("{} ({})", user, processes.len());
+                summary.name = name.clone();
+                summary.command = name;
+                pid_users.insert(summary.pid, *user);
+
+                summary
+            })
+            .collect_vec();
+        let mut sorted_summaries = summaries.iter().collect_vec();
+        self.try_sort(&mut sorted_summaries, data_collection);
+
+        let mut data = Vec::new();
+        let mut user_summary_rows = Vec::new();
+        for summary in sorted_summaries {
+            let user = pid_users[&summary.pid];
+            let is_expanded = expanded_users.contains(user);
+
+            let mut summary_row = self.process_to_text(
+                summary,
+                &mut col_widths,
+                &data_collection.process_data,
+                Some(if is_expanded { "- " } else { "+ " }.to_string()),
+                false,
+            );
+            // These are per-process, so they don't make sense for a summary.
+            if let TableRow::Raw(cells) = &mut summary_row {
+                for index in [Self::PID_OR_COUNT, Self::STATE, Self::CPU_HISTORY].iter() {
+                    cells[*index] = CellContent::Simple("".into());
+                }
+            }
+            data.push(summary_row);
+            user_summary_rows.push(Some(user.to_string()));
+
+            if is_expanded {
+                let mut processes = user_processes[user].clone();
+                self.try_sort(&mut processes, data_collection);
+
+                let num_processes = processes.len();
+                for (itx, process) in processes.into_iter().enumerate() {
+                    let prefix = format!(
+                        "{}{} ",
+                        if itx + 1 == num_processes {
+                            BRANCH_ENDING
+                        } else {
+                            BRANCH_SPLIT
+                        },
+                        BRANCH_HORIZONTAL
+                    );
+                    data.push(self.process_to_text(
+                        process,
+                        &mut col_widths,
+                        &data_collection.process_data,
+                        Some(prefix),
+                        false,
+                    ));
+                    user_summary_rows.push(None);
+                }
+            }
+        }
+
+        (TableData { data, col_widths }, user_summary_rows)
+    }
+
     fn get_normal_table_data(
         &self, data_collection: &DataCollection, search_query: &Option<Query>,
     ) -> TableData {
@@ -674,12 +1199,7 @@ impl ProcWidget {
             .process_data
             .process_harvest
             .values()
-            .filter(|p| {
-                search_query
-                    .as_ref()
-                    .map(|q| q.check(p, self.is_using_command()))
-                    .unwrap_or(true)
-            });
+            .filter(|p| self.matches_filters(p, search_query));
 
         let mut filtered_data = if let ProcWidgetMode::Grouped = self.mode {
             id_pid_map = FxHashMap::default();
@@ -707,8 +1227,12 @@ impl ProcWidget {
     }
 
     fn try_sort(&self, filtered_data: &mut [&ProcessHarvest], data_collection: &DataCollection) {
-        let cmd_pid_map = &data_collection.process_data.cmd_pid_map;
-        let name_pid_map = &data_collection.process_data.name_pid_map;
+        let ProcessData {
+            cmd_pid_map,
+            name_pid_map,
+            cpu_history_averages,
+            ..
+        } = &data_collection.process_data;
 
         if let SortState::Sortable(state) = &self.table_state.sort_state {
             let index = state.current_index;
@@ -721,15 +1245,22 @@ impl ProcWidget {
                     self.is_using_command(),
                     cmd_pid_map,
                     name_pid_map,
+                    cpu_history_averages,
                 );
             }
         }
     }
 
     fn process_to_text(
-        &self, process: &ProcessHarvest, col_widths: &mut [usize], cmd_pid_map: &StringPidMap,
-        name_pid_map: &StringPidMap, proc_prefix: Option<String>, is_disabled: bool,
+        &self, process: &ProcessHarvest, col_widths: &mut [usize], process_data: &ProcessData,
+        proc_prefix: Option<String>, is_disabled: bool,
     ) -> TableRow {
+        let ProcessData {
+            cmd_pid_map,
+            name_pid_map,
+            cpu_history,
+            ..
+        } = process_data;
         let mut contents = Vec::with_capacity(self.num_shown_columns());
 
         contents.extend(
@@ -749,6 +1280,21 @@ impl ProcWidget {
                                 binary_byte_string(process.mem_usage_bytes).into()
                             }
                         }
+                        ProcWidgetColumn::VirtualMemory => {
+                            binary_byte_string(process.virtual_bytes).into()
+                        }
+                        ProcWidgetColumn::SharedMemory => {
+                            binary_byte_string(process.shared_bytes).into()
+                        }
+                        ProcWidgetColumn::SwapMemory => {
+                            binary_byte_string(process.swap_bytes).into()
+                        }
+                        ProcWidgetColumn::GpuPercent => {
+                            format!("{:.1}%", process.gpu_usage_percent).into()
+                        }
+                        ProcWidgetColumn::GpuMemory => {
+                            binary_byte_string(process.gpu_mem_bytes).into()
+                        }
                         ProcWidgetColumn::PidOrCount { is_count } => {
                             if is_count {
                                 if self.is_using_command() {
@@ -799,20 +1345,36 @@ impl ProcWidget {
                             main: process.process_state.0.clone().into(),
                             alt: process.process_state.1.to_string().into(),
                         },
-                        ProcWidgetColumn::User => {
-                            #[cfg(target_family = "unix")]
-                            {
-                                process.user.clone().into()
-                            }
-                            #[cfg(not(target_family = "unix"))]
-                            {
-                                "".into()
-                            }
+                        ProcWidgetColumn::Threads => process.num_threads.to_string().into(),
+                        ProcWidgetColumn::User => process.user.clone().into(),
+                        ProcWidgetColumn::Elevated => {
+                            if process.is_elevated { "Yes" } else { "No" }.into()
                         }
+                        ProcWidgetColumn::StartTime { is_relative } => match process.start_time {
+                            Some(start_time) if is_relative => {
+                                concat_string!(duration_string(get_uptime(start_time)), " ago")
+                                    .into()
+                            }
+                            Some(start_time) => timestamp_string(start_time).into(),
+                            None => "N/A".into(),
+                        },
+                        ProcWidgetColumn::Uptime => match process.start_time {
+                            Some(start_time) => duration_string(get_uptime(start_time)).into(),
+                            None => "N/A".into(),
+                        },
+                        ProcWidgetColumn::SystemdUnit => match &process.systemd_unit {
+                            Some(unit) => unit.name.clone().into(),
+                            None => "".into(),
+                        },
+                        ProcWidgetColumn::CpuHistory => cpu_history
+                            .get(&process.pid)
+                            .map(cpu_sparkline)
+                            .unwrap_or_default()
+                            .into(),
                     };
 
                     if let Some(curr) = col_widths.get_mut(itx) {
-                        *curr = max(*curr, col_text.len());
+                        *curr = max(*curr, col_text.width());
                     }
 
                     col_text
@@ -829,9 +1391,6 @@ impl ProcWidget {
     fn harvest_to_table_data(
         &self, process_data: &[&ProcessHarvest], data_collection: &DataCollection,
     ) -> TableData {
-        let cmd_pid_map = &data_collection.process_data.cmd_pid_map;
-        let name_pid_map = &data_collection.process_data.name_pid_map;
-
         let mut col_widths = vec![0; self.table_state.columns.len()];
 
         let data = process_data
@@ -840,8 +1399,7 @@ impl ProcWidget {
                 self.process_to_text(
                     process,
                     &mut col_widths,
-                    cmd_pid_map,
-                    name_pid_map,
+                    &data_collection.process_data,
                     None,
                     false,
                 )
@@ -908,19 +1466,47 @@ impl ProcWidget {
         }
     }
 
+    /// Returns the PID of the currently selected process, if processes are not grouped and a
+    /// user's summary row isn't selected.
+    pub fn get_selected_pid(&self) -> Option<Pid> {
+        if let ProcWidgetMode::Grouped = self.mode {
+            None
+        } else if self.get_selected_user_summary().is_some() {
+            None
+        } else {
+            self.table_data
+                .data
+                .get(self.table_state.current_scroll_position)
+                .and_then(row_pid)
+        }
+    }
+
+    /// Returns the user of the currently selected row, if it is a user's summary row.
+    fn get_selected_user_summary(&self) -> Option<&String> {
+        self.user_summary_rows
+            .get(self.table_state.current_scroll_position)
+            .and_then(|user| user.as_ref())
+    }
+
+    /// Expands or collapses the selected user's processes, if a user's summary row is selected.
+    pub fn toggle_user_group(&mut self) {
+        if let Some(user) = self.get_selected_user_summary().cloned() {
+            if let ProcWidgetMode::User { expanded_users } = &mut self.mode {
+                if !expanded_users.remove(&user) {
+                    expanded_users.insert(user);
+                }
+                self.force_data_update();
+            }
+        }
+    }
+
     pub fn toggle_tree_branch(&mut self) {
-        if let ProcWidgetMode::Tree { collapsed_pids } = &mut self.mode {
-            let current_posn = self.table_state.current_scroll_position;
-            if let Some(current_row) = self.table_data.data.get(current_posn) {
-                if let Ok(pid) = current_row.row()[ProcWidget::PID_OR_COUNT]
-                    .main_text()
-                    .parse::<Pid>()
-                {
-                    if !collapsed_pids.remove(&pid) {
-                        collapsed_pids.insert(pid);
-                    }
-                    self.force_data_update();
+        if let Some(pid) = self.get_selected_pid() {
+            if let ProcWidgetMode::Tree { collapsed_pids } = &mut self.mode {
+                if !collapsed_pids.remove(&pid) {
+                    collapsed_pids.insert(pid);
                 }
+                self.force_data_update();
             }
         }
     }
@@ -935,7 +1521,7 @@ impl ProcWidget {
                         *max_percentage = Some(0.7);
                     } else {
                         *max_percentage = match self.mode {
-                            ProcWidgetMode::Tree { .. } => Some(0.5),
+                            ProcWidgetMode::Tree { .. } | ProcWidgetMode::User { .. } => Some(0.5),
                             ProcWidgetMode::Grouped | ProcWidgetMode::Normal => Some(0.3),
                         };
                     }
@@ -954,17 +1540,52 @@ impl ProcWidget {
     ///
     /// Otherwise, if count is disabled, then the User and State columns should be re-enabled, and the mode switched
     /// to [`ProcWidgetMode::Normal`].
+    /// Switches tree mode on or off. Grouped processes are ungrouped first, as they can't be
+    /// shown as a tree.
+    pub fn set_tree_mode(&mut self, is_tree: bool) {
+        if is_tree == matches!(self.mode, ProcWidgetMode::Tree { .. }) {
+            return;
+        }
+
+        if is_tree {
+            self.set_grouped(false);
+            self.mode = ProcWidgetMode::Tree {
+                collapsed_pids: Default::default(),
+            };
+        } else {
+            self.mode = ProcWidgetMode::Normal;
+        }
+        self.force_rerender_and_update();
+    }
+
+    /// Switches grouping processes by name on or off, leaving tree mode or grouping by user if
+    /// needed.
+    pub fn set_grouped(&mut self, is_grouped: bool) {
+        if is_grouped == matches!(self.mode, ProcWidgetMode::Grouped) {
+            return;
+        }
+
+        if is_grouped {
+            self.mode = ProcWidgetMode::Normal;
+        }
+        self.toggle_tab();
+    }
+
     pub fn toggle_tab(&mut self) {
-        if !matches!(self.mode, ProcWidgetMode::Tree { .. }) {
+        if matches!(self.mode, ProcWidgetMode::Normal | ProcWidgetMode::Grouped) {
             if let Some(ProcWidgetColumn::PidOrCount { is_count }) =
                 self.get_mut_proc_col(Self::PID_OR_COUNT)
             {
                 *is_count = !*is_count;
 
                 if *is_count {
-                    #[cfg(target_family = "unix")]
+                    // Grouped processes have no PIDs to mark.
+                    self.clear_marks();
+
                     self.hide_column(Self::USER);
+                    self.hide_column(Self::ELEVATED);
                     self.hide_column(Self::STATE);
+                    self.hide_column(Self::CPU_HISTORY);
                     self.mode = ProcWidgetMode::Grouped;
 
                     self.sort_table_state.current_scroll_position = self
@@ -972,9 +1593,14 @@ impl ProcWidget {
                         .current_scroll_position
                         .clamp(0, self.num_enabled_columns().saturating_sub(1));
                 } else {
-                    #[cfg(target_family = "unix")]
                     self.show_column(Self::USER);
+                    if cfg!(target_os = "windows") {
+                        self.show_column(Self::ELEVATED);
+                    }
                     self.show_column(Self::STATE);
+                    if self.show_cpu_history {
+                        self.show_column(Self::CPU_HISTORY);
+                    }
                     self.mode = ProcWidgetMode::Normal;
                 }
                 self.force_rerender_and_update();
@@ -1030,15 +1656,103 @@ impl ProcWidget {
         }
         self.table_state.scroll_bar = 0;
         self.table_state.current_scroll_position = 0;
+        self.is_selection_reset = true;
 
         self.force_data_update();
     }
 
+    /// Marks or unmarks the selected process.
+    pub fn toggle_mark(&mut self) {
+        if let Some(pid) = self.get_selected_pid() {
+            if !self.marked_pids.remove(&pid) {
+                self.marked_pids.insert(pid);
+            }
+        }
+    }
+
+    /// Starts marking a range of rows from the selected process, or marks the range and stops if
+    /// one is already being marked.
+    pub fn toggle_range_marking(&mut self) {
+        if self.range_start_pid.is_some() {
+            let range_pids = self.get_range_pids();
+            self.marked_pids.extend(range_pids);
+            self.range_start_pid = None;
+        } else {
+            self.range_start_pid = self.get_selected_pid();
+        }
+    }
+
+    /// Returns the PIDs of the rows between the start of the range being marked and the selected
+    /// row, if a range is being marked.
+    fn get_range_pids(&self) -> Vec<Pid> {
+        let start_row = self.range_start_pid.and_then(|pid| {
+            self.table_data
+                .data
+                .iter()
+                .position(|row| row_pid(row) == Some(pid))
+        });
+
+        match start_row {
+            Some(start_row) => {
+                let selected_row = self.table_state.current_scroll_position;
+                self.table_data
+                    .data
+                    .iter()
+                    .take(max(start_row, selected_row) + 1)
+                    .skip(min(start_row, selected_row))
+                    .filter_map(row_pid)
+                    .collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the marked processes, including any range being marked, in order of PID.
+    pub fn get_marked_pids(&self) -> Vec<Pid> {
+        let mut marked_pids = self
+            .marked_pids
+            .iter()
+            .copied()
+            .chain(self.get_range_pids())
+            .collect::<Vec<_>>();
+        marked_pids.sort_unstable();
+        marked_pids.dedup();
+
+        marked_pids
+    }
+
+    /// Unmarks every process and stops marking a range. Returns whether anything was marked.
+    pub fn clear_marks(&mut self) -> bool {
+        let had_marks = !self.marked_pids.is_empty() || self.range_start_pid.is_some();
+        self.marked_pids.clear();
+        self.range_start_pid = None;
+
+        had_marks
+    }
+
+    /// Toggles keeping the selected process in the middle of the table.
+    pub fn toggle_following(&mut self) {
+        self.is_following = !self.is_following;
+        self.table_state.is_centered = self.is_following;
+    }
+
     pub fn clear_search(&mut self) {
         self.proc_search.search_state.reset();
         self.force_data_update();
     }
 
+    /// Opens the search bar with the given query, with the cursor at its end.
+    pub fn set_search_query(&mut self, query: String) {
+        let search_state = &mut self.proc_search.search_state;
+        search_state.is_enabled = true;
+        search_state.grapheme_cursor = GraphemeCursor::new(query.len(), query.len(), true);
+        search_state.char_cursor_position = UnicodeWidthStr::width(query.as_str());
+        search_state.cursor_direction = CursorDirection::Right;
+        search_state.current_search_query = query;
+
+        self.update_query();
+    }
+
     pub fn search_walk_forward(&mut self, start_position: usize) {
         self.proc_search
             .search_state
@@ -1091,6 +1805,41 @@ impl ProcWidget {
     }
 }
 
+/// Returns the PID of a row, or `None` if it doesn't have one, like a grouped process.
+fn row_pid(row: &TableRow) -> Option<Pid> {
+    row.row()[ProcWidget::PID_OR_COUNT]
+        .main_text()
+        .parse::<Pid>()
+        .ok()
+}
+
+/// Returns the user that a process belongs to, used when grouping processes by user.
+fn get_process_user(process: &ProcessHarvest) -> &str {
+    &process.user
+}
+
+/// Returns how long ago a process started, given its start time in seconds since the Unix epoch.
+fn get_uptime(start_time: u64) -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|now| now.as_secs().saturating_sub(start_time))
+        .unwrap_or(0)
+}
+
+/// Returns a sparkline representing the given CPU usage samples, scaled to the largest sample.
+fn cpu_sparkline(history: &VecDeque<f64>) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let max_usage = history.iter().copied().fold(1.0, f64::max);
+    history
+        .iter()
+        .map(|usage| {
+            let index = (usage / max_usage * (BARS.len() - 1) as f64).round() as usize;
+            BARS[index.min(BARS.len() - 1)]
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1098,6 +1847,210 @@ mod test {
     #[test]
     fn test_sort() {}
 
+    #[test]
+    fn test_cpu_sparkline() {
+        assert_eq!(cpu_sparkline(&VecDeque::new()), "");
+        assert_eq!(cpu_sparkline(&vec![0.0, 25.0, 50.0, 100.0].into()), "▁▃▅█");
+        assert_eq!(cpu_sparkline(&vec![0.0, 0.5, 1.0].into()), "▁▅█");
+    }
+
+    #[test]
+    fn test_set_tree_mode_and_grouped() {
+        let mut proc = ProcWidget::init(
+            ProcWidgetMode::Normal,
+            false,
+            false,
+            false,
+            false,
+            false,
+            true,
+            false,
+            false,
+            false,
+            false,
+            false,
+        );
+
+        proc.set_grouped(true);
+        assert!(matches!(proc.mode, ProcWidgetMode::Grouped));
+        assert_eq!(
+            proc.table_state.columns[ProcWidget::PID_OR_COUNT].header,
+            ProcWidgetColumn::PidOrCount { is_count: true }
+        );
+
+        proc.set_tree_mode(true);
+        assert!(matches!(proc.mode, ProcWidgetMode::Tree { .. }));
+        assert_eq!(
+            proc.table_state.columns[ProcWidget::PID_OR_COUNT].header,
+            ProcWidgetColumn::PidOrCount { is_count: false }
+        );
+
+        proc.set_grouped(true);
+        assert!(matches!(proc.mode, ProcWidgetMode::Grouped));
+
+        proc.set_grouped(false);
+        assert!(matches!(proc.mode, ProcWidgetMode::Normal));
+    }
+
+    #[test]
+    fn test_selection_follows_pid() {
+        let mut proc = ProcWidget::init(
+            ProcWidgetMode::Normal,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+        );
+        fn set_cpu_usage(proc: &mut ProcWidget, usages: &[(Pid, f64)]) {
+            let mut data_collection = DataCollection::default();
+            data_collection.process_data.process_harvest = usages
+                .iter()
+                .map(|&(pid, cpu_usage_percent)| {
+                    let process = ProcessHarvest {
+                        pid,
+                        cpu_usage_percent,
+                        ..ProcessHarvest::default()
+                    };
+                    (pid, process)
+                })
+                .collect();
+            proc.update_displayed_process_data(&data_collection);
+        }
+
+        set_cpu_usage(&mut proc, &[(1, 30.0), (2, 20.0), (3, 10.0)]);
+        proc.table_state.current_scroll_position = 2;
+        assert_eq!(proc.get_selected_pid(), Some(3));
+
+        // Sorted by CPU usage, so process 3 moves to the top.
+        set_cpu_usage(&mut proc, &[(1, 30.0), (2, 20.0), (3, 40.0)]);
+        assert_eq!(proc.table_state.current_scroll_position, 0);
+        assert_eq!(proc.get_selected_pid(), Some(3));
+
+        // Once it's gone, the selection stays on the same row.
+        set_cpu_usage(&mut proc, &[(1, 30.0), (2, 20.0)]);
+        assert_eq!(proc.get_selected_pid(), Some(1));
+    }
+
+    #[test]
+    fn test_apply_layout_options() {
+        let mut proc = ProcWidget::init(
+            ProcWidgetMode::Normal,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+        );
+        proc.apply_layout_options(&ProcLayoutOptions {
+            sort: Some("PID".to_string()),
+            sort_order: Some("descending".to_string()),
+            grouped: Some(true),
+            ..ProcLayoutOptions::default()
+        })
+        .unwrap();
+        assert!(matches!(proc.mode, ProcWidgetMode::Grouped));
+        match &proc.table_state.sort_state {
+            SortState::Sortable(state) => {
+                assert_eq!(state.current_index, ProcWidget::PID_OR_COUNT);
+                assert_eq!(state.order, SortOrder::Descending);
+            }
+            SortState::Unsortable => panic!("the process table should be sortable"),
+        }
+
+        let invalid_options = [
+            ProcLayoutOptions {
+                sort: Some("not_real".to_string()),
+                ..ProcLayoutOptions::default()
+            },
+            ProcLayoutOptions {
+                sort: Some("cpu".to_string()),
+                sort_order: Some("sideways".to_string()),
+                ..ProcLayoutOptions::default()
+            },
+            ProcLayoutOptions {
+                sort_order: Some("ascending".to_string()),
+                ..ProcLayoutOptions::default()
+            },
+            ProcLayoutOptions {
+                filter: Some("not_saved".to_string()),
+                ..ProcLayoutOptions::default()
+            },
+        ];
+        for options in &invalid_options {
+            assert!(proc.apply_layout_options(options).is_err());
+        }
+    }
+
+    #[test]
+    fn test_marking() {
+        let mut proc = ProcWidget::init(
+            ProcWidgetMode::Normal,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+        );
+        fn set_processes(proc: &mut ProcWidget, pids: &[Pid]) {
+            let mut data_collection = DataCollection::default();
+            data_collection.process_data.process_harvest = pids
+                .iter()
+                .map(|&pid| {
+                    let process = ProcessHarvest {
+                        pid,
+                        cpu_usage_percent: 100.0 - pid as f64,
+                        ..ProcessHarvest::default()
+                    };
+                    (pid, process)
+                })
+                .collect();
+            proc.update_displayed_process_data(&data_collection);
+        }
+
+        set_processes(&mut proc, &[1, 2, 3, 4, 5]);
+        proc.toggle_mark();
+        assert_eq!(proc.get_marked_pids(), vec![1]);
+        proc.toggle_mark();
+        assert!(proc.get_marked_pids().is_empty());
+
+        // A range includes every row between where it started and the selected row.
+        proc.table_state.current_scroll_position = 3;
+        proc.toggle_range_marking();
+        proc.table_state.current_scroll_position = 1;
+        assert_eq!(proc.get_marked_pids(), vec![2, 3, 4]);
+        proc.toggle_range_marking();
+        proc.table_state.current_scroll_position = 0;
+        proc.toggle_mark();
+        assert_eq!(proc.get_marked_pids(), vec![1, 2, 3, 4]);
+
+        // Processes that exit are unmarked.
+        set_processes(&mut proc, &[1, 2, 4, 5]);
+        assert_eq!(proc.get_marked_pids(), vec![1, 2, 4]);
+
+        assert!(proc.clear_marks());
+        assert!(proc.get_marked_pids().is_empty());
+        assert!(!proc.clear_marks());
+    }
+
     #[test]
     fn assert_correct_columns() {
         #[track_caller]
@@ -1106,7 +2059,10 @@ mod test {
             let is_command = is_cmd;
             let show_percentage = !mem_as_val;
 
-            let proc = ProcWidget::init(mode, false, false, false, mem_as_val, is_command);
+            let proc = ProcWidget::init(
+                mode, false, false, false, mem_as_val, is_command, true, true, true, true, false,
+                true,
+            );
             let columns = &proc.table_state.columns;
 
             assert_eq!(
@@ -1125,6 +2081,26 @@ mod test {
                 columns[ProcWidget::MEM].header,
                 ProcWidgetColumn::Memory { show_percentage }
             );
+            assert!(matches!(
+                columns[ProcWidget::VIRTUAL_MEM].header,
+                ProcWidgetColumn::VirtualMemory
+            ));
+            assert!(matches!(
+                columns[ProcWidget::SHARED_MEM].header,
+                ProcWidgetColumn::SharedMemory
+            ));
+            assert!(matches!(
+                columns[ProcWidget::SWAP_MEM].header,
+                ProcWidgetColumn::SwapMemory
+            ));
+            assert!(matches!(
+                columns[ProcWidget::GPU].header,
+                ProcWidgetColumn::GpuPercent
+            ));
+            assert!(matches!(
+                columns[ProcWidget::GPU_MEM].header,
+                ProcWidgetColumn::GpuMemory
+            ));
             assert!(matches!(
                 columns[ProcWidget::RPS].header,
                 ProcWidgetColumn::ReadPerSecond
@@ -1141,17 +2117,38 @@ mod test {
                 columns[ProcWidget::T_WRITE].header,
                 ProcWidgetColumn::TotalWrite
             ));
-            #[cfg(target_family = "unix")]
-            {
-                assert!(matches!(
-                    columns[ProcWidget::USER].header,
-                    ProcWidgetColumn::User
-                ));
-            }
+            assert!(matches!(
+                columns[ProcWidget::USER].header,
+                ProcWidgetColumn::User
+            ));
+            assert!(matches!(
+                columns[ProcWidget::ELEVATED].header,
+                ProcWidgetColumn::Elevated
+            ));
             assert!(matches!(
                 columns[ProcWidget::STATE].header,
                 ProcWidgetColumn::State
             ));
+            assert!(matches!(
+                columns[ProcWidget::THREADS].header,
+                ProcWidgetColumn::Threads
+            ));
+            assert_eq!(
+                columns[ProcWidget::START_TIME].header,
+                ProcWidgetColumn::StartTime { is_relative: false }
+            );
+            assert!(matches!(
+                columns[ProcWidget::UPTIME].header,
+                ProcWidgetColumn::Uptime
+            ));
+            assert!(matches!(
+                columns[ProcWidget::SYSTEMD_UNIT].header,
+                ProcWidgetColumn::SystemdUnit
+            ));
+            assert!(matches!(
+                columns[ProcWidget::CPU_HISTORY].header,
+                ProcWidgetColumn::CpuHistory
+            ));
         }
 
         test_columns(ProcWidgetMode::Grouped, true, true);
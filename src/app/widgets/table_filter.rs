@@ -0,0 +1,144 @@
+use regex::Regex;
+
+use crate::app::widgets::ProcessSearchState;
+
+/// A filter that can be typed into the disk, temperature, and network widgets to only show the
+/// entries whose names match. It has the same case, whole word, and regex options as the process
+/// search, and reuses its state, but has no query syntax.
+#[derive(Default)]
+pub struct TableFilter {
+    pub search: ProcessSearchState,
+    /// The query as a regex, if it's set and valid.
+    regex: Option<Regex>,
+}
+
+impl TableFilter {
+    /// Whether the filter is being typed in.
+    pub fn is_open(&self) -> bool {
+        self.search.search_state.is_enabled
+    }
+
+    pub fn set_open(&mut self, is_open: bool) {
+        self.search.search_state.is_enabled = is_open;
+    }
+
+    pub fn query(&self) -> &str {
+        &self.search.search_state.current_search_query
+    }
+
+    /// Whether the filter should be shown, which is while it's being typed in or is set.
+    pub fn is_shown(&self) -> bool {
+        self.is_open() || !self.query().is_empty()
+    }
+
+    pub fn regex(&self) -> Option<&Regex> {
+        self.regex.as_ref()
+    }
+
+    /// Whether a name passes the filter. Everything passes while the query is blank or invalid, as
+    /// with the process search.
+    pub fn is_match(&self, name: &str) -> bool {
+        self.regex
+            .as_ref()
+            .map(|regex| regex.is_match(name))
+            .unwrap_or(true)
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.search.search_state.current_search_query.push(c);
+        self.update_regex();
+    }
+
+    pub fn pop_char(&mut self) {
+        self.search.search_state.current_search_query.pop();
+        self.update_regex();
+    }
+
+    pub fn clear(&mut self) {
+        self.search.search_state.reset();
+        self.update_regex();
+    }
+
+    pub fn toggle_ignore_case(&mut self) {
+        self.search.search_toggle_ignore_case();
+        self.update_regex();
+    }
+
+    pub fn toggle_whole_word(&mut self) {
+        self.search.search_toggle_whole_word();
+        self.update_regex();
+    }
+
+    pub fn toggle_regex(&mut self) {
+        self.search.search_toggle_regex();
+        self.update_regex();
+    }
+
+    /// Builds the regex from the query and options, like the process search does for names. Whole
+    /// words are grouped so that they also apply to every branch of a regex like `eth|wlan`.
+    fn update_regex(&mut self) {
+        let search_state = &mut self.search.search_state;
+        search_state.is_blank_search = search_state.current_search_query.is_empty();
+        search_state.is_invalid_search = false;
+        search_state.error_message = None;
+        self.regex = None;
+
+        if search_state.is_blank_search {
+            return;
+        }
+
+        let query = &search_state.current_search_query;
+        let mut regex_string = if self.search.is_searching_with_regex {
+            query.to_string()
+        } else {
+            regex::escape(query)
+        };
+        if self.search.is_searching_whole_word {
+            regex_string = format!("^(?:{})$", regex_string);
+        }
+        if self.search.is_ignoring_case {
+            regex_string = format!("(?i){}", regex_string);
+        }
+
+        match Regex::new(&regex_string) {
+            Ok(regex) => self.regex = Some(regex),
+            Err(err) => {
+                search_state.is_invalid_search = true;
+                search_state.error_message = Some(err.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_table_filter() {
+        let mut filter = TableFilter::default();
+        assert!(filter.is_match("eth0"));
+
+        "ETH".chars().for_each(|c| filter.push_char(c));
+        assert!(filter.is_match("eth0"));
+        assert!(!filter.is_match("wlan0"));
+
+        filter.toggle_ignore_case();
+        assert!(!filter.is_match("eth0"));
+
+        filter.clear();
+        "eth|wlan".chars().for_each(|c| filter.push_char(c));
+        assert!(!filter.is_match("eth0"));
+        filter.toggle_regex();
+        assert!(filter.is_match("eth0"));
+        assert!(filter.is_match("wlan0"));
+
+        filter.toggle_whole_word();
+        assert!(!filter.is_match("eth0"));
+
+        filter.clear();
+        filter.push_char('(');
+        assert!(filter.search.search_state.is_invalid_search);
+        assert!(filter.is_match("eth0"));
+    }
+}
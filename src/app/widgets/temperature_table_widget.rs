@@ -1,9 +1,36 @@
-use crate::components::text_table::{
-    CellContent, TableComponentColumn, TableComponentState, WidthBounds,
+use crate::{
+    app::widgets::TableFilter,
+    components::text_table::{
+        CellContent, SortOrder, SortState, SortableState, TableComponentColumn,
+        TableComponentState, WidthBounds,
+    },
+    data_conversion::TableData,
 };
 
 pub struct TempWidgetState {
     pub table_state: TableComponentState,
+    /// The rows of this widget, sorted by its own sort column.
+    pub table_data: TableData,
+    pub force_update_data: bool,
+    /// Only sensors whose name matches this are shown.
+    pub filter: TableFilter,
+}
+
+impl TempWidgetState {
+    pub const SENSOR: usize = 0;
+    pub const TEMPERATURE: usize = 1;
+
+    pub fn force_data_update(&mut self) {
+        self.force_update_data = true;
+    }
+
+    /// Changes the filter, moving back to the top of the table as the rows will change.
+    pub fn change_filter(&mut self, change: impl FnOnce(&mut TableFilter)) {
+        change(&mut self.filter);
+        self.table_state.current_scroll_position = 0;
+        self.table_state.scroll_bar = 0;
+        self.force_data_update();
+    }
 }
 
 impl Default for TempWidgetState {
@@ -23,7 +50,15 @@ impl Default for TempWidgetState {
                         TableComponentColumn::new_custom(CellContent::new(*header, None), width)
                     })
                     .collect(),
-            ),
+            )
+            .sort_state(SortState::Sortable(SortableState::new(
+                Self::SENSOR,
+                SortOrder::Ascending,
+                vec![SortOrder::Ascending, SortOrder::Descending],
+            ))),
+            table_data: TableData::default(),
+            force_update_data: true,
+            filter: TableFilter::default(),
         }
     }
 }
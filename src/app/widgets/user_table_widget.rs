@@ -0,0 +1,32 @@
+use crate::components::text_table::{
+    CellContent, TableComponentColumn, TableComponentState, WidthBounds,
+};
+
+pub struct UserWidgetState {
+    pub table_state: TableComponentState,
+}
+
+impl Default for UserWidgetState {
+    fn default() -> Self {
+        const USER_HEADERS: [&str; 5] = ["User", "TTY", "From", "Login", "Idle"];
+        const WIDTHS: [WidthBounds; USER_HEADERS.len()] = [
+            WidthBounds::soft_from_str(USER_HEADERS[0], Some(0.2)),
+            WidthBounds::soft_from_str(USER_HEADERS[1], Some(0.1)),
+            WidthBounds::soft_from_str(USER_HEADERS[2], Some(0.3)),
+            WidthBounds::Hard(16),
+            WidthBounds::soft_from_str(USER_HEADERS[4], None),
+        ];
+
+        UserWidgetState {
+            table_state: TableComponentState::new(
+                USER_HEADERS
+                    .iter()
+                    .zip(WIDTHS)
+                    .map(|(header, width)| {
+                        TableComponentColumn::new_custom(CellContent::new(*header, None), width)
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
@@ -4,7 +4,7 @@
 #[macro_use]
 extern crate log;
 
-use bottom::{canvas, constants::*, data_conversion::*, options::*, *};
+use bottom::{canvas, constants::*, options::*, *};
 
 use std::{
     boxed::Box,
@@ -14,7 +14,6 @@ use std::{
         atomic::{AtomicBool, Ordering},
         mpsc, Arc, Condvar, Mutex,
     },
-    thread,
     time::Duration,
 };
 
@@ -28,19 +27,67 @@ use tui::{backend::CrosstermBackend, Terminal};
 
 fn main() -> Result<()> {
     let matches = clap::get_matches();
-    #[cfg(all(feature = "fern", debug_assertions))]
-    {
-        utils::logging::init_logger(log::LevelFilter::Debug, std::ffi::OsStr::new("debug.log"))?;
+    if let Some(log_file) = matches.value_of_os("log_file") {
+        #[cfg(feature = "fern")]
+        {
+            let log_level = matches
+                .value_of("log_level")
+                .and_then(|log_level| log_level.parse().ok())
+                .unwrap_or(log::LevelFilter::Info);
+            utils::logging::init_logger(log_level, log_file)
+                .context("Unable to open the log file.")?;
+        }
+        #[cfg(not(feature = "fern"))]
+        {
+            let _ = log_file;
+            anyhow::bail!("This build of bottom doesn't support logging.");
+        }
+    } else {
+        #[cfg(all(feature = "fern", debug_assertions))]
+        {
+            utils::logging::init_logger(
+                log::LevelFilter::Debug,
+                std::ffi::OsStr::new("debug.log"),
+            )?;
+        }
+    }
+
+    // The config subcommands only work with the config file, so they don't need a terminal.
+    if let Some(("config", config_matches)) = matches.subcommand() {
+        let config_path = read_config(matches.value_of("config_location"))
+            .context("Unable to access the given config file location.")?;
+        return config_command::run_config_command(&matches, config_matches, config_path);
     }
 
-    // Check if the current environment is in a terminal.
-    check_if_terminal();
+    // Check if the current environment is in a terminal. Agents don't draw anything, so they can
+    // run without one.
+    let is_agent = matches.is_present("agent");
+    if !is_agent {
+        check_if_terminal();
+    }
 
     // Read from config file.
     let config_path = read_config(matches.value_of("config_location"))
         .context("Unable to access the given config file location.")?;
-    let mut config: Config = create_or_get_config(&config_path)
+    let (mut config, config_string) = create_or_get_config(&config_path)
         .context("Unable to properly parse or create the config file.")?;
+    let config_warnings = config_warnings::get_config_warnings(&matches, &config_string, &config);
+
+    if is_agent {
+        for warning in &config_warnings {
+            eprintln!("Warning: {}", warning);
+        }
+
+        remote::run_agent(
+            matches.value_of("listen").unwrap_or_default(),
+            get_remote_token(&matches)
+                .context("An agent needs a token, given with --token or BTM_TOKEN.")?,
+            &get_app_config_fields(&matches, &config)?,
+            get_data_filters(&config)?,
+        )
+        .context("Unable to run the agent.")?;
+        return Ok(());
+    }
 
     // Get widget layout separately
     let (widget_layout, default_widget_id, default_widget_type_option) =
@@ -56,10 +103,20 @@ fn main() -> Result<()> {
         &default_widget_type_option,
         config_path,
     )?;
+    app.config_warnings = config_warnings;
+
+    let remote_token = get_remote_token(&matches).unwrap_or_default();
+    if remote_token.is_empty() && app.remote_hosts.iter().any(|host| !host.is_ssh) {
+        anyhow::bail!("Connecting to an agent needs its token, given with --token or BTM_TOKEN.");
+    }
 
     // Create painter and set colours.
-    let mut painter =
-        canvas::Painter::init(widget_layout, &config, get_color_scheme(&matches, &config)?)?;
+    let mut painter = canvas::Painter::init(
+        widget_layout,
+        &config,
+        get_color_scheme(&matches, &config)?,
+        get_graph_renderer(&matches, &config)?,
+    )?;
 
     // Create termination mutex and cvar
     #[allow(clippy::mutex_atomic)]
@@ -68,44 +125,63 @@ fn main() -> Result<()> {
 
     // Set up input handling
     let (sender, receiver) = mpsc::channel();
-    let _input_thread = create_input_thread(sender.clone(), thread_termination_lock.clone());
-
-    // Cleaning loop
-    let _cleaning_thread = {
-        let lock = thread_termination_lock.clone();
-        let cvar = thread_termination_cvar.clone();
-        let cleaning_sender = sender.clone();
-        const OFFSET_WAIT_TIME: u64 = constants::STALE_MAX_MILLISECONDS + 60000;
-        thread::spawn(move || {
-            loop {
-                let result = cvar.wait_timeout(
-                    lock.lock().unwrap(),
-                    Duration::from_millis(OFFSET_WAIT_TIME),
-                );
-                if let Ok(result) = result {
-                    if *(result.0) {
-                        break;
-                    }
-                }
-                if cleaning_sender.send(BottomEvent::Clean).is_err() {
-                    // debug!("Failed to send cleaning sender...");
-                    break;
-                }
-            }
-        })
-    };
+    let input_paused = Arc::new(AtomicBool::new(false));
+    let _input_thread = create_input_thread(
+        sender.clone(),
+        thread_termination_lock.clone(),
+        input_paused.clone(),
+    );
+
+    // Config file watcher, for applying changes while running
+    let _config_watcher_thread = app.config_path.clone().map(|config_path| {
+        create_config_watcher_thread(
+            sender.clone(),
+            config_path,
+            thread_termination_lock.clone(),
+            thread_termination_cvar.clone(),
+        )
+    });
 
     // Event loop
     let (collection_thread_ctrl_sender, collection_thread_ctrl_receiver) = mpsc::channel();
-    let _collection_thread = create_collection_thread(
-        sender,
-        collection_thread_ctrl_receiver,
-        thread_termination_lock.clone(),
-        thread_termination_cvar.clone(),
-        &app.app_config_fields,
-        app.filters.clone(),
-        app.used_widgets.clone(),
-    );
+    let _collection_threads = if app.remote_hosts.is_empty() {
+        vec![create_collection_thread(
+            sender,
+            collection_thread_ctrl_receiver,
+            thread_termination_lock.clone(),
+            thread_termination_cvar.clone(),
+            &app.app_config_fields,
+            app.filters.clone(),
+            app.used_widgets.clone(),
+        )]
+    } else {
+        app.remote_hosts
+            .iter()
+            .enumerate()
+            .map(|(index, host)| {
+                if host.is_ssh {
+                    remote::ssh::create_ssh_thread(
+                        sender.clone(),
+                        index,
+                        host.address.clone(),
+                        &app.app_config_fields,
+                        app.filters.clone(),
+                        thread_termination_lock.clone(),
+                        thread_termination_cvar.clone(),
+                    )
+                } else {
+                    remote::create_remote_thread(
+                        sender.clone(),
+                        index,
+                        host.address.clone(),
+                        remote_token.clone(),
+                        thread_termination_lock.clone(),
+                        thread_termination_cvar.clone(),
+                    )
+                }
+            })
+            .collect()
+    };
 
     // Set up up tui and crossterm
     let mut stdout_val = stdout();
@@ -126,6 +202,7 @@ fn main() -> Result<()> {
         ist_clone.store(true, Ordering::SeqCst);
     })?;
     let mut first_run = true;
+    let mut last_terminal_size = None;
 
     while !is_terminated.load(Ordering::SeqCst) {
         if let Ok(recv) = receiver.recv_timeout(Duration::from_millis(TICK_RATE_IN_MILLISECONDS)) {
@@ -134,14 +211,84 @@ fn main() -> Result<()> {
                     if handle_key_event_or_break(event, &mut app, &collection_thread_ctrl_sender) {
                         break;
                     }
+                    if std::mem::take(&mut app.pending_colour_scheme_cycle) {
+                        painter.cycle_colour_scheme(&app.config);
+                    }
+                    if std::mem::take(&mut app.pending_config_update) {
+                        let _ =
+                            collection_thread_ctrl_sender.send(ThreadControlEvent::UpdateConfig(
+                                Box::new(app.app_config_fields.clone()),
+                            ));
+                    }
+                    if let Some((command, pause)) = app.pending_hook.take() {
+                        if let Err(err) =
+                            run_hook_command(&mut terminal, &command, pause, &input_paused)?
+                        {
+                            app.set_status_message(err);
+                        }
+                        // Ctrl-c while the command was running was meant for the command.
+                        is_terminated.store(false, Ordering::SeqCst);
+                        app.is_force_redraw = true;
+                    }
+                    if let Some(text) = app.pending_clipboard.take() {
+                        if let Err(err) =
+                            app::clipboard::copy_to_clipboard(terminal.backend_mut(), &text)
+                        {
+                            app.set_status_message(format!(
+                                "Couldn't copy to the clipboard: {}",
+                                err
+                            ));
+                        }
+                    }
+                    if std::mem::take(&mut app.pending_net_query_update) {
+                        let _ = collection_thread_ctrl_sender.send(
+                            ThreadControlEvent::UpdateNetworkQuery(
+                                app.net_state.filter.regex().cloned(),
+                            ),
+                        );
+                    }
+                    if std::mem::take(&mut app.pending_pause_update) {
+                        let _ = collection_thread_ctrl_sender
+                            .send(ThreadControlEvent::Pause(app.is_paused));
+                    }
+                    if std::mem::take(&mut app.pending_data_conversion) {
+                        convert_collected_data(&mut app);
+                    }
+                    if std::mem::take(&mut app.pending_options_save) {
+                        app.options_menu_state.save_result =
+                            Some(save_options(&mut app).map_err(|err| err.to_string()));
+                    }
+                    if let Some(rows) = app.pending_layout_edit.take() {
+                        match apply_layout_edit(&matches, &mut app, rows) {
+                            Ok(widget_layout) => {
+                                painter.set_widget_layout(widget_layout);
+                                let _ = collection_thread_ctrl_sender.send(
+                                    ThreadControlEvent::UpdateUsedWidgets(Box::new(
+                                        app.used_widgets.clone(),
+                                    )),
+                                );
+                            }
+                            Err(err) => {
+                                app.layout_edit_state.error = Some(err.to_string());
+                            }
+                        }
+                    }
                     update_data(&mut app);
                 }
                 BottomEvent::MouseInput(event) => {
                     handle_mouse_event(event, &mut app);
                     update_data(&mut app);
                 }
-                BottomEvent::Update(data) => {
+                BottomEvent::RemoteUpdate(host, data) if host != app.selected_host => {
+                    app.eat_host_data(host, data);
+                }
+                BottomEvent::Update(data) | BottomEvent::RemoteUpdate(_, data) => {
+                    app.notice_harvest_errors(&data.harvest_errors);
                     app.data_collection.eat_data(data);
+                    app.data_collection
+                        .clean_data(app.app_config_fields.retention);
+                    app.update_derived_metrics();
+                    app.check_alerts();
 
                     // This thing is required as otherwise, some widgets can't draw correctly w/o
                     // some data (or they need to be re-drawn).
@@ -151,85 +298,52 @@ fn main() -> Result<()> {
                     }
 
                     if !app.is_frozen {
-                        // Convert all data into tui-compliant components
-
-                        // Network
-                        if app.used_widgets.use_net {
-                            let network_data = convert_network_data_points(
-                                &app.data_collection,
-                                app.app_config_fields.use_basic_mode
-                                    || app.app_config_fields.use_old_network_legend,
-                                &app.app_config_fields.network_scale_type,
-                                &app.app_config_fields.network_unit_type,
-                                app.app_config_fields.network_use_binary_prefix,
-                            );
-                            app.converted_data.network_data_rx = network_data.rx;
-                            app.converted_data.network_data_tx = network_data.tx;
-                            app.converted_data.rx_display = network_data.rx_display;
-                            app.converted_data.tx_display = network_data.tx_display;
-                            if let Some(total_rx_display) = network_data.total_rx_display {
-                                app.converted_data.total_rx_display = total_rx_display;
-                            }
-                            if let Some(total_tx_display) = network_data.total_tx_display {
-                                app.converted_data.total_tx_display = total_tx_display;
-                            }
-                        }
-
-                        // Disk
-                        if app.used_widgets.use_disk {
-                            app.converted_data.disk_data = convert_disk_row(&app.data_collection);
-                        }
-
-                        // Temperatures
-                        if app.used_widgets.use_temp {
-                            app.converted_data.temp_sensor_data = convert_temp_row(&app);
-                        }
-
-                        // Memory
-                        if app.used_widgets.use_mem {
-                            app.converted_data.mem_data =
-                                convert_mem_data_points(&app.data_collection);
-                            app.converted_data.swap_data =
-                                convert_swap_data_points(&app.data_collection);
-                            let (memory_labels, swap_labels) =
-                                convert_mem_labels(&app.data_collection);
-
-                            app.converted_data.mem_labels = memory_labels;
-                            app.converted_data.swap_labels = swap_labels;
-                        }
-
-                        if app.used_widgets.use_cpu {
-                            // CPU
-
-                            convert_cpu_data_points(
-                                &app.data_collection,
-                                &mut app.converted_data.cpu_data,
-                            );
-                            app.converted_data.load_avg_data = app.data_collection.load_avg_harvest;
-                        }
-
-                        // Processes
-                        if app.used_widgets.use_proc {
-                            for proc in app.proc_state.widget_states.values_mut() {
-                                proc.force_data_update();
-                            }
-                        }
+                        convert_collected_data(&mut app);
+                    }
+                }
+                BottomEvent::RemoteStatus(host, status) => {
+                    app.set_host_status(host, status);
+                }
+                BottomEvent::ConfigChange => {
+                    // The breakpoints may have changed, so check them again.
+                    last_terminal_size = None;
+                    reload_config(
+                        &matches,
+                        &mut app,
+                        &mut painter,
+                        &collection_thread_ctrl_sender,
+                    );
+                }
+            }
+        }
 
-                        // Battery
-                        #[cfg(feature = "battery")]
-                        {
-                            if app.used_widgets.use_battery {
-                                app.converted_data.battery_data =
-                                    convert_battery_harvest(&app.data_collection);
-                            }
-                        }
+        // Switch to the layout for the terminal's size whenever it's resized.
+        let terminal_size = terminal.size()?;
+        if last_terminal_size != Some(terminal_size) {
+            last_terminal_size = Some(terminal_size);
+            if !app.app_config_fields.use_basic_mode {
+                let layout_index = app.config.get_breakpoint_layout(
+                    app.current_layout,
+                    terminal_size.width,
+                    terminal_size.height,
+                );
+                if layout_index != app.current_layout {
+                    app.pending_layout_switch = Some(layout_index);
+                }
+            }
+        }
 
-                        update_data(&mut app);
-                    }
+        if let Some(layout_index) = app.pending_layout_switch.take() {
+            match switch_layout(&matches, &mut app, layout_index) {
+                Ok(widget_layout) => {
+                    painter.set_widget_layout(widget_layout);
+                    let _ = collection_thread_ctrl_sender.send(
+                        ThreadControlEvent::UpdateUsedWidgets(Box::new(app.used_widgets.clone())),
+                    );
+                    update_data(&mut app);
                 }
-                BottomEvent::Clean => {
-                    app.data_collection
-                        .clean_data(constants::STALE_MAX_MILLISECONDS);
+                Err(err) => {
+                    app.set_status_message(format!("{:#}", err));
                 }
             }
         }
@@ -246,5 +360,19 @@ fn main() -> Result<()> {
 
     cleanup_terminal(&mut terminal)?;
 
+    // Save any layout changes made with the mouse, if enabled.
+    if app.app_config_fields.save_layout && painter.is_layout_modified() {
+        if let Some(config_path) = &app.config_path {
+            save_widget_layout(
+                &matches,
+                &mut app.config,
+                config_path,
+                app.current_layout,
+                painter.widget_layout(),
+            )
+            .context("Unable to save the layout to the config file.")?;
+        }
+    }
+
     Ok(())
 }
@@ -1,5 +1,5 @@
 use itertools::izip;
-use std::str::FromStr;
+use std::{cell::RefCell, str::FromStr};
 
 use tui::{
     backend::Backend,
@@ -10,11 +10,15 @@ use tui::{
 };
 
 use canvas_colours::*;
+use graphics::{GraphImageWriter, GraphImages, GraphRenderer, GraphicsProtocol};
 
 use crate::{
     app::{
         self,
-        layout_manager::{BottomColRow, BottomLayout, BottomWidgetType},
+        layout_manager::{
+            BottomColRow, BottomLayout, BottomWidgetType, LayoutSplit, LayoutSplitKind,
+        },
+        states::{AppProcessAffinityState, OptionsMenuEntry},
         App,
     },
     constants::*,
@@ -23,13 +27,24 @@ use crate::{
     utils::error::BottomError,
 };
 
+mod ascii;
+mod background;
 mod canvas_colours;
 mod dialogs;
 mod drawing_utils;
+mod graph_export;
+pub mod graphics;
 mod widgets;
 
-#[derive(Debug)]
+use dialogs::config_error_dialog::get_config_warnings_dialog_height;
+use drawing_utils::scroll_loc;
+use widgets::layout_tabs::get_tab_col_row_locs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColourScheme {
+    /// Either [`ColourScheme::Default`] or [`ColourScheme::DefaultLight`], depending on the
+    /// terminal's background. This is resolved when the [`Painter`] is created.
+    Auto,
     Default,
     DefaultLight,
     Gruvbox,
@@ -45,6 +60,7 @@ impl FromStr for ColourScheme {
     fn from_str(s: &str) -> error::Result<Self> {
         let lower_case = s.to_lowercase();
         match lower_case.as_str() {
+            "auto" => Ok(ColourScheme::Auto),
             "default" => Ok(ColourScheme::Default),
             "default-light" => Ok(ColourScheme::DefaultLight),
             "gruvbox" => Ok(ColourScheme::Gruvbox),
@@ -59,9 +75,32 @@ impl FromStr for ColourScheme {
     }
 }
 
+impl ColourScheme {
+    /// The order in which colour schemes are cycled through at runtime.
+    const CYCLE: [ColourScheme; 6] = [
+        ColourScheme::Default,
+        ColourScheme::DefaultLight,
+        ColourScheme::Gruvbox,
+        ColourScheme::GruvboxLight,
+        ColourScheme::Nord,
+        ColourScheme::NordLight,
+    ];
+
+    /// Returns the colour scheme after this one when cycling. Custom colours from the config
+    /// file are included after the built-in schemes if there are any.
+    fn next(self, has_custom_colours: bool) -> ColourScheme {
+        match Self::CYCLE.iter().position(|scheme| *scheme == self) {
+            Some(index) if index + 1 < Self::CYCLE.len() => Self::CYCLE[index + 1],
+            Some(_) if has_custom_colours => ColourScheme::Custom,
+            _ => ColourScheme::Default,
+        }
+    }
+}
+
 /// Handles the canvas' state.
 pub struct Painter {
     pub colours: CanvasColours,
+    colour_scheme: ColourScheme,
     height: u16,
     width: u16,
     styled_help_text: Vec<Spans<'static>>,
@@ -74,28 +113,150 @@ pub struct Painter {
     layout_constraints: Vec<Vec<Vec<Vec<Constraint>>>>,
     derived_widget_draw_locs: Vec<Vec<Vec<Vec<Rect>>>>,
     widget_layout: BottomLayout,
+    is_layout_modified: bool,
+    /// The last widget the layout was scrolled to show, so it's only scrolled to when selected.
+    layout_scroll_widget_id: Option<u64>,
+
+    /// How to draw graphs as images, if they aren't drawn as text.
+    graphics_protocol: Option<GraphicsProtocol>,
+    /// The size of a cell in pixels for the current frame, if graphs are drawn as images in it.
+    graph_cell_size: Option<(u16, u16)>,
+    /// The graphs of the current frame, if they're drawn as images or one is being saved.
+    graph_images: RefCell<GraphImages>,
+    graph_image_writer: GraphImageWriter,
+    /// Whether the selected graph is being saved to a file this frame.
+    is_exporting_graph: bool,
 }
 
 impl Painter {
     pub fn init(
         widget_layout: BottomLayout, config: &Config, colour_scheme: ColourScheme,
+        graph_renderer: GraphRenderer,
     ) -> anyhow::Result<Self> {
+        let mut painter = Painter {
+            colours: CanvasColours::default(),
+            colour_scheme: ColourScheme::Default,
+            height: 0,
+            width: 0,
+            styled_help_text: Vec::default(),
+            is_mac_os: cfg!(target_os = "macos"),
+            row_constraints: Vec::default(),
+            col_constraints: Vec::default(),
+            col_row_constraints: Vec::default(),
+            layout_constraints: Vec::default(),
+            widget_layout,
+            derived_widget_draw_locs: Vec::default(),
+            is_layout_modified: false,
+            layout_scroll_widget_id: None,
+            graphics_protocol: graph_renderer.get_protocol(),
+            graph_cell_size: None,
+            graph_images: RefCell::default(),
+            graph_image_writer: GraphImageWriter::default(),
+            is_exporting_graph: false,
+        };
+
+        painter.generate_layout_constraints();
+
+        let colour_scheme = if let ColourScheme::Auto = colour_scheme {
+            if background::is_light_background() == Some(true) {
+                ColourScheme::DefaultLight
+            } else {
+                ColourScheme::Default
+            }
+        } else {
+            colour_scheme
+        };
+        painter.set_colour_scheme(colour_scheme, config)?;
+
+        Ok(painter)
+    }
+
+    /// Replaces the current colours with those of the given colour scheme. If this fails, the
+    /// current colours are kept.
+    pub fn set_colour_scheme(
+        &mut self, colour_scheme: ColourScheme, config: &Config,
+    ) -> anyhow::Result<()> {
+        let mut colours = CanvasColours::default();
+        match colour_scheme {
+            ColourScheme::Auto | ColourScheme::Default => {
+                // Don't have to do anything.
+            }
+            ColourScheme::DefaultLight => {
+                colours.set_colours_from_palette(&DEFAULT_LIGHT_MODE_COLOUR_PALETTE)?;
+            }
+            ColourScheme::Gruvbox => {
+                colours.set_colours_from_palette(&GRUVBOX_COLOUR_PALETTE)?;
+            }
+            ColourScheme::GruvboxLight => {
+                colours.set_colours_from_palette(&GRUVBOX_LIGHT_COLOUR_PALETTE)?;
+            }
+            ColourScheme::Nord => {
+                colours.set_colours_from_palette(&NORD_COLOUR_PALETTE)?;
+            }
+            ColourScheme::NordLight => {
+                colours.set_colours_from_palette(&NORD_LIGHT_COLOUR_PALETTE)?;
+            }
+            ColourScheme::Custom => {
+                if let Some(config_colours) = &config.colors {
+                    colours.set_colours_from_palette(config_colours)?;
+                }
+            }
+        }
+
+        self.colours = colours;
+        self.colour_scheme = colour_scheme;
+        self.complete_painter_init();
+
+        Ok(())
+    }
+
+    /// Applies the colour scheme from a reloaded config file. As the terminal can't be asked for
+    /// its background while running, [`ColourScheme::Auto`] keeps the current scheme if it is one
+    /// that could have been picked automatically.
+    pub fn reload_colour_scheme(
+        &mut self, colour_scheme: ColourScheme, config: &Config,
+    ) -> anyhow::Result<()> {
+        let colour_scheme = match colour_scheme {
+            ColourScheme::Auto => match self.colour_scheme {
+                ColourScheme::DefaultLight => ColourScheme::DefaultLight,
+                _ => ColourScheme::Default,
+            },
+            _ => colour_scheme,
+        };
+        self.set_colour_scheme(colour_scheme, config)
+    }
+
+    /// Switches to the next colour scheme.
+    pub fn cycle_colour_scheme(&mut self, config: &Config) {
+        let has_custom_colours = matches!(&config.colors, Some(colours) if !colours.is_empty());
+        if self
+            .set_colour_scheme(self.colour_scheme.next(has_custom_colours), config)
+            .is_err()
+        {
+            // Only custom colours can fail, in which case they're skipped.
+            let _ = self.set_colour_scheme(ColourScheme::Default, config);
+        }
+    }
+
+    /// Generates the constraints used to split up the drawing area, based on the
+    /// ratios in the widget layout.
+    fn generate_layout_constraints(&mut self) {
         // Now for modularity; we have to also initialize the base layouts!
-        // We want to do this ONCE and reuse; after this we can just construct
-        // based on the console size.
+        // We want to do this ONCE (or again if the layout is resized) and reuse;
+        // after this we can just construct based on the console size.
 
         let mut row_constraints = Vec::new();
         let mut col_constraints = Vec::new();
         let mut col_row_constraints = Vec::new();
         let mut layout_constraints = Vec::new();
 
-        widget_layout.rows.iter().for_each(|row| {
+        self.widget_layout.rows.iter().for_each(|row| {
             if row.canvas_handle_height {
                 row_constraints.push(Constraint::Length(0));
             } else {
                 row_constraints.push(Constraint::Ratio(
                     row.row_height_ratio,
-                    widget_layout.total_row_height_ratio,
+                    self.widget_layout.total_row_height_ratio,
                 ));
             }
 
@@ -147,79 +308,220 @@ impl Painter {
             col_constraints.push(new_col_constraints);
         });
 
-        let mut painter = Painter {
-            colours: CanvasColours::default(),
-            height: 0,
-            width: 0,
-            styled_help_text: Vec::default(),
-            is_mac_os: cfg!(target_os = "macos"),
-            row_constraints,
-            col_constraints,
-            col_row_constraints,
-            layout_constraints,
-            widget_layout,
-            derived_widget_draw_locs: Vec::default(),
-        };
-
-        if let ColourScheme::Custom = colour_scheme {
-            painter.generate_config_colours(config)?;
-        } else {
-            painter.generate_colour_scheme(colour_scheme)?;
-        }
-        painter.complete_painter_init();
-
-        Ok(painter)
+        self.row_constraints = row_constraints;
+        self.col_constraints = col_constraints;
+        self.col_row_constraints = col_row_constraints;
+        self.layout_constraints = layout_constraints;
     }
 
-    /// Determines the border style.
-    pub fn get_border_style(&self, widget_id: u64, selected_widget_id: u64) -> tui::style::Style {
-        let is_on_widget = widget_id == selected_widget_id;
-        if is_on_widget {
-            self.colours.highlighted_border_style
-        } else {
-            self.colours.border_style
-        }
+    /// Returns the current widget layout.
+    pub fn widget_layout(&self) -> &BottomLayout {
+        &self.widget_layout
     }
 
-    fn generate_config_colours(&mut self, config: &Config) -> anyhow::Result<()> {
-        if let Some(colours) = &config.colors {
-            self.colours.set_colours_from_palette(colours)?;
-        }
+    /// Replaces the current widget layout, such as after editing it at runtime.
+    pub fn set_widget_layout(&mut self, widget_layout: BottomLayout) {
+        self.widget_layout = widget_layout;
+        self.is_layout_modified = false;
+        self.derived_widget_draw_locs = Vec::default();
+        self.layout_scroll_widget_id = None;
+        self.generate_layout_constraints();
+    }
 
-        Ok(())
+    /// Returns whether the widget layout has been resized since starting.
+    pub fn is_layout_modified(&self) -> bool {
+        self.is_layout_modified
     }
 
-    fn generate_colour_scheme(&mut self, colour_scheme: ColourScheme) -> anyhow::Result<()> {
-        match colour_scheme {
-            ColourScheme::Default => {
-                // Don't have to do anything.
-            }
-            ColourScheme::DefaultLight => {
-                self.colours
-                    .set_colours_from_palette(&*DEFAULT_LIGHT_MODE_COLOUR_PALETTE)?;
-            }
-            ColourScheme::Gruvbox => {
-                self.colours
-                    .set_colours_from_palette(&*GRUVBOX_COLOUR_PALETTE)?;
+    /// Returns the borders between layout components that can be dragged to resize them.
+    /// Levels containing components with a size handled by the canvas are skipped.
+    fn get_layout_splits(&self, draw_loc: Rect) -> Vec<LayoutSplit> {
+        let mut splits = Vec::new();
+
+        let row_locs = Layout::default()
+            .margin(0)
+            .constraints(self.row_constraints.as_slice())
+            .direction(Direction::Vertical)
+            .split(draw_loc);
+
+        if self
+            .widget_layout
+            .rows
+            .iter()
+            .all(|row| !row.canvas_handle_height)
+        {
+            let sibling_bounds: Vec<(u16, u16)> =
+                row_locs.iter().map(|loc| (loc.y, loc.height)).collect();
+            for row in 0..row_locs.len().saturating_sub(1) {
+                splits.push(LayoutSplit {
+                    kind: LayoutSplitKind::Row { row },
+                    is_vertical: true,
+                    sibling_bounds: sibling_bounds.clone(),
+                    cross_bounds: (draw_loc.x, draw_loc.x + draw_loc.width),
+                });
             }
-            ColourScheme::GruvboxLight => {
-                self.colours
-                    .set_colours_from_palette(&*GRUVBOX_LIGHT_COLOUR_PALETTE)?;
+        }
+
+        for (row_index, (row_loc, col_constraint, col_row_constraint, row)) in izip!(
+            &row_locs,
+            &self.col_constraints,
+            &self.col_row_constraints,
+            &self.widget_layout.rows
+        )
+        .enumerate()
+        {
+            let col_locs = Layout::default()
+                .constraints(col_constraint.as_slice())
+                .direction(Direction::Horizontal)
+                .split(*row_loc);
+
+            if row.children.iter().all(|col| !col.canvas_handle_width) {
+                let sibling_bounds: Vec<(u16, u16)> =
+                    col_locs.iter().map(|loc| (loc.x, loc.width)).collect();
+                for col in 0..col_locs.len().saturating_sub(1) {
+                    splits.push(LayoutSplit {
+                        kind: LayoutSplitKind::Col {
+                            row: row_index,
+                            col,
+                        },
+                        is_vertical: false,
+                        sibling_bounds: sibling_bounds.clone(),
+                        cross_bounds: (row_loc.y, row_loc.y + row_loc.height),
+                    });
+                }
             }
-            ColourScheme::Nord => {
-                self.colours
-                    .set_colours_from_palette(&*NORD_COLOUR_PALETTE)?;
+
+            for (col_index, (col_loc, constraint, col)) in
+                izip!(&col_locs, col_row_constraint, &row.children).enumerate()
+            {
+                if col.tabs.is_empty()
+                    && col
+                        .children
+                        .iter()
+                        .all(|col_row| !col_row.canvas_handle_height && !col_row.flex_grow)
+                {
+                    let col_row_locs = Layout::default()
+                        .constraints(constraint.as_slice())
+                        .direction(Direction::Vertical)
+                        .split(*col_loc);
+                    let sibling_bounds: Vec<(u16, u16)> =
+                        col_row_locs.iter().map(|loc| (loc.y, loc.height)).collect();
+                    for col_row in 0..col_row_locs.len().saturating_sub(1) {
+                        splits.push(LayoutSplit {
+                            kind: LayoutSplitKind::ColRow {
+                                row: row_index,
+                                col: col_index,
+                                col_row,
+                            },
+                            is_vertical: true,
+                            sibling_bounds: sibling_bounds.clone(),
+                            cross_bounds: (col_loc.x, col_loc.x + col_loc.width),
+                        });
+                    }
+                }
             }
-            ColourScheme::NordLight => {
-                self.colours
-                    .set_colours_from_palette(&*NORD_LIGHT_COLOUR_PALETTE)?;
+        }
+
+        splits
+    }
+
+    /// Returns where to lay out the rows, as a location in the layout rather than on the screen.
+    /// This is the size of `viewport`, unless fitting the rows on it would make any of them shorter
+    /// than `min_row_height`, in which case it's as tall as needed and has to be scrolled.
+    fn get_layout_loc(&self, viewport: Rect, min_row_height: u16) -> Rect {
+        let min_ratio = self
+            .widget_layout
+            .rows
+            .iter()
+            .filter(|row| !row.canvas_handle_height)
+            .map(|row| row.row_height_ratio)
+            .min();
+        let height = match min_ratio {
+            Some(min_ratio) if min_ratio > 0 && min_row_height > 0 => {
+                let total_ratio = f64::from(self.widget_layout.total_row_height_ratio);
+                let needed_height =
+                    (f64::from(min_row_height) * total_ratio / f64::from(min_ratio)).ceil();
+                (needed_height.min(f64::from(u16::MAX / 2)) as u16).max(viewport.height)
             }
-            ColourScheme::Custom => {
-                // This case should never occur, just do nothing.
+            _ => viewport.height,
+        };
+
+        Rect {
+            y: 0,
+            height,
+            ..viewport
+        }
+    }
+
+    /// Returns how many lines to scroll the layout down by. This keeps the scroll within the layout,
+    /// and scrolls to the row of the selected widget when it's newly selected.
+    fn get_layout_scroll(&mut self, app_state: &App, layout_loc: Rect, viewport: Rect) -> u16 {
+        let mut scroll = app_state.layout_scroll;
+        let widget_id = app_state.current_widget.widget_id;
+
+        if self.layout_scroll_widget_id != Some(widget_id) {
+            self.layout_scroll_widget_id = Some(widget_id);
+
+            let row_locs = Layout::default()
+                .constraints(self.row_constraints.as_slice())
+                .direction(Direction::Vertical)
+                .split(layout_loc);
+            let selected_row_loc = row_locs
+                .iter()
+                .zip(&self.widget_layout.rows)
+                .find(|(_, row)| {
+                    row.children
+                        .iter()
+                        .flat_map(|col| &col.children)
+                        .flat_map(|col_row| &col_row.children)
+                        .any(|widget| widget.widget_id == widget_id)
+                })
+                .map(|(row_loc, _)| *row_loc);
+
+            if let Some(row_loc) = selected_row_loc {
+                if row_loc.y < scroll {
+                    scroll = row_loc.y;
+                } else if row_loc.bottom() > scroll + viewport.height {
+                    scroll = row_loc.y.min(row_loc.bottom() - viewport.height);
+                }
             }
         }
 
-        Ok(())
+        scroll.min(layout_loc.height.saturating_sub(viewport.height))
+    }
+
+    /// Draws arrows on the right edge of the screen if the layout is scrolled, showing which way
+    /// there's more of it.
+    fn draw_layout_scroll_arrows<B: Backend>(
+        &self, f: &mut Frame<'_, B>, scroll: u16, layout_loc: Rect, viewport: Rect,
+    ) {
+        if viewport.width == 0 || viewport.height == 0 {
+            return;
+        }
+
+        let arrow_x = viewport.x + viewport.width - 1;
+        if scroll > 0 {
+            f.render_widget(
+                Paragraph::new(Span::styled("▲", self.colours.highlighted_border_style)),
+                Rect::new(arrow_x, viewport.y, 1, 1),
+            );
+        }
+        if scroll + viewport.height < layout_loc.height {
+            f.render_widget(
+                Paragraph::new(Span::styled("▼", self.colours.highlighted_border_style)),
+                Rect::new(arrow_x, viewport.y + viewport.height - 1, 1, 1),
+            );
+        }
+    }
+
+    /// Determines the border style.
+    pub fn get_border_style(&self, widget_id: u64, selected_widget_id: u64) -> tui::style::Style {
+        let is_on_widget = widget_id == selected_widget_id;
+        if is_on_widget {
+            self.colours.highlighted_border_style
+        } else {
+            self.colours.border_style
+        }
     }
 
     /// Must be run once before drawing, but after setting colours.
@@ -256,68 +558,555 @@ impl Painter {
         self.styled_help_text = styled_help_spans.into_iter().map(Spans::from).collect();
     }
 
-    fn draw_frozen_indicator<B: Backend>(&self, f: &mut Frame<'_, B>, draw_loc: Rect) {
-        f.render_widget(
-            Paragraph::new(Span::styled(
-                "Frozen, press 'f' to unfreeze",
-                self.colours.currently_selected_text_style,
-            )),
-            Layout::default()
-                .horizontal_margin(1)
-                .constraints([Constraint::Length(1)])
-                .split(draw_loc)[0],
-        )
-    }
+    /// Draws the line at the bottom of the screen, which shows the remote host being shown, whether
+    /// the data is frozen, and the current status message.
+    fn draw_status_line<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect) {
+        let mut spans = Vec::new();
+        if let Some(host) = app_state.remote_hosts.get(app_state.selected_host) {
+            let mut text = host.name().to_string();
+            if app_state.remote_hosts.len() > 1 {
+                text.push_str(&format!(
+                    " ({}/{})",
+                    app_state.selected_host + 1,
+                    app_state.remote_hosts.len()
+                ));
+            }
+            if host.is_connected {
+                spans.push(Span::styled(text, self.colours.widget_title_style));
+            } else {
+                text.push_str(", disconnected");
+                spans.push(Span::styled(text, self.colours.invalid_query_style));
+            }
+        }
+        if app_state.is_frozen {
+            if !spans.is_empty() {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::styled(
+                "Frozen, press 'f' to unfreeze",
+                self.colours.currently_selected_text_style,
+            ));
+        }
+        if app_state.is_paused {
+            if !spans.is_empty() {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::styled(
+                "Collection paused, press 'gf' to resume",
+                self.colours.currently_selected_text_style,
+            ));
+        }
+        if let Some((message, _)) = &app_state.status_message {
+            if !spans.is_empty() {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::styled(message.clone(), self.colours.text_style));
+        }
+
+        f.render_widget(
+            Paragraph::new(Spans::from(spans)),
+            Layout::default()
+                .horizontal_margin(1)
+                .constraints([Constraint::Length(1)])
+                .split(draw_loc)[0],
+        )
+    }
+
+    /// Returns where graphs should be collected to be drawn as images or saved, or `None` if they
+    /// aren't needed for either.
+    pub fn graph_image_sink(&self) -> Option<&RefCell<GraphImages>> {
+        if self.graph_cell_size.is_some() || self.is_exporting_graph {
+            Some(&self.graph_images)
+        } else {
+            None
+        }
+    }
+
+    pub fn draw_data<B: Backend + std::io::Write>(
+        &mut self, terminal: &mut Terminal<B>, app_state: &mut app::App,
+    ) -> error::Result<()> {
+        use BottomWidgetType::*;
+
+        if let Some((split, position)) = app_state.pending_layout_resize.take() {
+            if self.widget_layout.resize_split(&split, position) {
+                self.generate_layout_constraints();
+                self.is_layout_modified = true;
+                app_state.is_force_redraw = true;
+            }
+        }
+
+        // Show the tab of the selected widget, however it was selected.
+        if let Some(tab) = app_state.current_widget.tab {
+            if app_state
+                .selected_tabs
+                .insert(tab.container_id, tab.tab_widget_id)
+                != Some(tab.tab_widget_id)
+            {
+                app_state.is_force_redraw = true;
+            }
+        }
+
+        if let Some((_, set_instant)) = &app_state.status_message {
+            if set_instant.elapsed().as_millis()
+                >= u128::from(STATUS_MESSAGE_DURATION_IN_MILLISECONDS)
+            {
+                app_state.status_message = None;
+                app_state.is_force_redraw = true;
+            }
+        }
+
+        // The cell size is checked every frame, as it changes with the font size.
+        self.graph_cell_size = self
+            .graphics_protocol
+            .and_then(|_| graphics::get_cell_size());
+        self.is_exporting_graph = std::mem::take(&mut app_state.pending_graph_export);
+        let graph_images = self.graph_images.get_mut();
+        graph_images.replace_text = self.graph_cell_size.is_some();
+        graph_images.images.clear();
+
+        terminal.draw(|f| {
+            let (terminal_size, status_draw_loc) = if app_state.is_frozen
+                || app_state.is_paused
+                || app_state.status_message.is_some()
+                || !app_state.remote_hosts.is_empty()
+            {
+                let split_loc = Layout::default()
+                    .constraints([Constraint::Min(0), Constraint::Length(1)])
+                    .split(f.size());
+                (split_loc[0], Some(split_loc[1]))
+            } else {
+                (f.size(), None)
+            };
+            let terminal_height = terminal_size.height;
+            let terminal_width = terminal_size.width;
+
+            if (self.height == 0 && self.width == 0)
+                || (self.height != terminal_height || self.width != terminal_width)
+            {
+                app_state.is_force_redraw = true;
+                self.height = terminal_height;
+                self.width = terminal_width;
+            }
+
+            let layout_loc =
+                self.get_layout_loc(terminal_size, app_state.app_config_fields.min_row_height);
+            if !app_state.app_config_fields.use_basic_mode && !app_state.is_expanded {
+                let layout_scroll = self.get_layout_scroll(app_state, layout_loc, terminal_size);
+                if layout_scroll != app_state.layout_scroll {
+                    app_state.layout_scroll = layout_scroll;
+                    app_state.is_force_redraw = true;
+                }
+            }
+
+            if app_state.should_get_widget_bounds() {
+                // If we're force drawing, reset ALL mouse boundaries.
+                for widget in app_state.widget_map.values_mut() {
+                    widget.top_left_corner = None;
+                    widget.bottom_right_corner = None;
+                }
+
+                // Reset dd_dialog...
+                app_state.delete_dialog_state.button_positions = vec![];
+
+                // Reset battery dialog...
+                for battery_widget in app_state.battery_state.widget_states.values_mut() {
+                    battery_widget.tab_click_locs = None;
+                }
+
+                // Reset layout splits and tab headers...
+                app_state.layout_splits = vec![];
+                app_state.layout_tab_click_locs = vec![];
+            }
+
+            if let Some(config_error) = &app_state.config_error {
+                const CONFIG_ERROR_DIALOG_HEIGHT: u16 = 9;
+
+                let border_len = terminal_height.saturating_sub(CONFIG_ERROR_DIALOG_HEIGHT) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(border_len),
+                        Constraint::Length(CONFIG_ERROR_DIALOG_HEIGHT),
+                        Constraint::Length(border_len),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(20),
+                            Constraint::Percentage(60),
+                            Constraint::Percentage(20),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_config_error_dialog(f, config_error, middle_dialog_chunk[1]);
+            } else if !app_state.config_warnings.is_empty() {
+                let dialog_width = if terminal_width < 100 {
+                    terminal_width * 9 / 10
+                } else {
+                    terminal_width * 3 / 5
+                };
+                let dialog_height =
+                    get_config_warnings_dialog_height(&app_state.config_warnings, dialog_width)
+                        .min(terminal_height);
+
+                let border_len = terminal_height.saturating_sub(dialog_height) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(border_len),
+                        Constraint::Length(dialog_height),
+                        Constraint::Length(border_len),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(20),
+                            Constraint::Percentage(60),
+                            Constraint::Percentage(20),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_config_warnings_dialog(
+                    f,
+                    &app_state.config_warnings,
+                    middle_dialog_chunk[1],
+                );
+            } else if app_state.is_showing_diagnostics {
+                let lines = self.get_diagnostics_lines(
+                    &app_state.data_collection.harvest_errors,
+                    &app_state.data_collection.unavailable_harvests,
+                );
+                let dialog_height = (lines.len() as u16 + 2).min(terminal_height);
+
+                let border_len = terminal_height.saturating_sub(dialog_height) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(border_len),
+                        Constraint::Length(dialog_height),
+                        Constraint::Length(border_len),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(20),
+                            Constraint::Percentage(60),
+                            Constraint::Percentage(20),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_diagnostics_dialog(f, lines, middle_dialog_chunk[1]);
+            } else if app_state.help_dialog_state.is_showing_help {
+                let gen_help_len = GENERAL_HELP_TEXT.len() as u16 + 3;
+                let border_len = terminal_height.saturating_sub(gen_help_len) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(border_len),
+                        Constraint::Length(gen_help_len),
+                        Constraint::Length(border_len),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        // TODO: [REFACTOR] The point we start changing size at currently hard-coded in.
+                        [
+                            Constraint::Percentage(0),
+                            Constraint::Percentage(100),
+                            Constraint::Percentage(0),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(20),
+                            Constraint::Percentage(60),
+                            Constraint::Percentage(20),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_help_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.layout_edit_state.is_editing {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Percentage(10),
+                        Constraint::Percentage(80),
+                        Constraint::Percentage(10),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(0),
+                            Constraint::Percentage(100),
+                            Constraint::Percentage(0),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(20),
+                            Constraint::Percentage(60),
+                            Constraint::Percentage(20),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_layout_edit_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.process_graph_state.is_showing_graph {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Percentage(10),
+                        Constraint::Percentage(80),
+                        Constraint::Percentage(10),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([
+                        Constraint::Percentage(10),
+                        Constraint::Percentage(80),
+                        Constraint::Percentage(10),
+                    ])
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_process_graph_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.process_inspector_state.is_showing_inspector {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Percentage(10),
+                        Constraint::Percentage(80),
+                        Constraint::Percentage(10),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(0),
+                            Constraint::Percentage(100),
+                            Constraint::Percentage(0),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(10),
+                            Constraint::Percentage(80),
+                            Constraint::Percentage(10),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_process_inspector_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.process_command_state.is_showing_command {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Percentage(10),
+                        Constraint::Percentage(80),
+                        Constraint::Percentage(10),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(0),
+                            Constraint::Percentage(100),
+                            Constraint::Percentage(0),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(10),
+                            Constraint::Percentage(80),
+                            Constraint::Percentage(10),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_process_command_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.process_threads_state.is_showing_threads {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Percentage(10),
+                        Constraint::Percentage(80),
+                        Constraint::Percentage(10),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(0),
+                            Constraint::Percentage(100),
+                            Constraint::Percentage(0),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(10),
+                            Constraint::Percentage(80),
+                            Constraint::Percentage(10),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_process_threads_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.process_priority_state.is_showing_priority {
+                const PRIORITY_DIALOG_HEIGHT: u16 = 11;
+
+                let border_len = terminal_height.saturating_sub(PRIORITY_DIALOG_HEIGHT) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(border_len),
+                        Constraint::Length(PRIORITY_DIALOG_HEIGHT),
+                        Constraint::Length(border_len),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(25),
+                            Constraint::Percentage(50),
+                            Constraint::Percentage(25),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
 
-    pub fn draw_data<B: Backend>(
-        &mut self, terminal: &mut Terminal<B>, app_state: &mut app::App,
-    ) -> error::Result<()> {
-        use BottomWidgetType::*;
+                self.draw_process_priority_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.process_affinity_state.is_showing_affinity {
+                let affinity_state = &app_state.process_affinity_state;
+                let num_rows = affinity_state
+                    .cpus
+                    .len()
+                    .div_ceil(AppProcessAffinityState::CPUS_PER_ROW);
+                // The borders, the CPU list, the hint, the error, and the gaps between them.
+                let dialog_height = (num_rows as u16 + 8).min(terminal_height);
+
+                let border_len = terminal_height.saturating_sub(dialog_height) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(border_len),
+                        Constraint::Length(dialog_height),
+                        Constraint::Length(border_len),
+                    ])
+                    .split(terminal_size);
 
-        terminal.draw(|f| {
-            let (terminal_size, frozen_draw_loc) = if app_state.is_frozen {
-                let split_loc = Layout::default()
-                    .constraints([Constraint::Min(0), Constraint::Length(1)])
-                    .split(f.size());
-                (split_loc[0], Some(split_loc[1]))
-            } else {
-                (f.size(), None)
-            };
-            let terminal_height = terminal_size.height;
-            let terminal_width = terminal_size.width;
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(20),
+                            Constraint::Percentage(60),
+                            Constraint::Percentage(20),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
 
-            if (self.height == 0 && self.width == 0)
-                || (self.height != terminal_height || self.width != terminal_width)
-            {
-                app_state.is_force_redraw = true;
-                self.height = terminal_height;
-                self.width = terminal_width;
-            }
+                self.draw_process_affinity_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.kill_queue_state.is_showing_queue {
+                // The queued processes, the hints, the error, the gaps between them, and the
+                // borders.
+                let num_rows = app_state.kill_queue_state.queue.len().max(1);
+                let dialog_height = (num_rows as u16 + 7).min(terminal_height);
 
-            if app_state.should_get_widget_bounds() {
-                // If we're force drawing, reset ALL mouse boundaries.
-                for widget in app_state.widget_map.values_mut() {
-                    widget.top_left_corner = None;
-                    widget.bottom_right_corner = None;
-                }
+                let border_len = terminal_height.saturating_sub(dialog_height) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(border_len),
+                        Constraint::Length(dialog_height),
+                        Constraint::Length(border_len),
+                    ])
+                    .split(terminal_size);
 
-                // Reset dd_dialog...
-                app_state.delete_dialog_state.button_positions = vec![];
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(20),
+                            Constraint::Percentage(60),
+                            Constraint::Percentage(20),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
 
-                // Reset battery dialog...
-                for battery_widget in app_state.battery_state.widget_states.values_mut() {
-                    battery_widget.tab_click_locs = None;
+                self.draw_kill_queue_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.unit_control_state.pending.is_some() {
+                // The question, the hint, and the borders, with room for an error that wraps onto
+                // a second line.
+                let dialog_height = if app_state.unit_control_state.error.is_some() {
+                    9
+                } else {
+                    5
                 }
-            }
+                .min(terminal_height);
 
-            if app_state.help_dialog_state.is_showing_help {
-                let gen_help_len = GENERAL_HELP_TEXT.len() as u16 + 3;
-                let border_len = terminal_height.saturating_sub(gen_help_len) / 2;
+                let border_len = terminal_height.saturating_sub(dialog_height) / 2;
                 let vertical_dialog_chunk = Layout::default()
                     .direction(Direction::Vertical)
                     .constraints([
                         Constraint::Length(border_len),
-                        Constraint::Length(gen_help_len),
+                        Constraint::Length(dialog_height),
                         Constraint::Length(border_len),
                     ])
                     .split(terminal_size);
@@ -325,11 +1114,10 @@ impl Painter {
                 let middle_dialog_chunk = Layout::default()
                     .direction(Direction::Horizontal)
                     .constraints(if terminal_width < 100 {
-                        // TODO: [REFACTOR] The point we start changing size at currently hard-coded in.
                         [
-                            Constraint::Percentage(0),
-                            Constraint::Percentage(100),
-                            Constraint::Percentage(0),
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
                         ]
                     } else {
                         [
@@ -340,7 +1128,40 @@ impl Painter {
                     })
                     .split(vertical_dialog_chunk[1]);
 
-                self.draw_help_dialog(f, app_state, middle_dialog_chunk[1]);
+                self.draw_unit_control_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.options_menu_state.is_showing_options {
+                // The options, the hint, the result of saving, the gaps between them, and the
+                // borders.
+                let dialog_height = (OptionsMenuEntry::ALL.len() as u16 + 8).min(terminal_height);
+
+                let border_len = terminal_height.saturating_sub(dialog_height) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(border_len),
+                        Constraint::Length(dialog_height),
+                        Constraint::Length(border_len),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(5),
+                            Constraint::Percentage(90),
+                            Constraint::Percentage(5),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(25),
+                            Constraint::Percentage(50),
+                            Constraint::Percentage(25),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_options_menu_dialog(f, app_state, middle_dialog_chunk[1]);
             } else if app_state.delete_dialog_state.is_showing_dd {
                 // TODO: This needs the paragraph wrap feature from tui-rs to be pushed to complete... but for now it's pretty close!
                 // The main problem right now is that I cannot properly calculate the height offset since
@@ -362,6 +1183,14 @@ impl Painter {
                     7
                 } else {
                     22
+                } + if app_state.delete_dialog_state.descendant_pids.is_empty() {
+                    0
+                } else {
+                    3
+                } + if app_state.delete_dialog_state.is_killing_marked {
+                    2
+                } else {
+                    0
                 };
 
                 // let (text_width, text_height) = if let Some(dd_text) = &dd_text {
@@ -418,17 +1247,39 @@ impl Painter {
                 // This is a bit nasty, but it works well... I guess.
                 app_state.delete_dialog_state.is_showing_dd =
                     self.draw_dd_dialog(f, dd_text, app_state, middle_dialog_chunk[1]);
+            } else if app_state.app_config_fields.screen_reader {
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
+                }
+
+                self.draw_screen_reader_text(f, app_state, terminal_size);
             } else if app_state.is_expanded {
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
-                let rect = Layout::default()
-                    .margin(0)
-                    .constraints([Constraint::Percentage(100)])
-                    .split(terminal_size);
+                let rect = if app_state.app_config_fields.show_mini_widgets {
+                    let split_loc = Layout::default()
+                        .margin(0)
+                        .constraints([Constraint::Min(0), Constraint::Length(1)])
+                        .split(terminal_size);
+                    self.draw_mini_widgets(f, app_state, split_loc[1]);
+                    split_loc
+                } else {
+                    Layout::default()
+                        .margin(0)
+                        .constraints([Constraint::Percentage(100)])
+                        .split(terminal_size)
+                };
                 match &app_state.current_widget.widget_type {
                     Cpu => self.draw_cpu(f, app_state, rect[0], app_state.current_widget.widget_id),
+                    // The basic CPU widget has no legend of its own to go alongside its graph.
+                    BasicCpu => self.draw_cpu_graph(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
                     CpuLegend => self.draw_cpu(
                         f,
                         app_state,
@@ -455,7 +1306,7 @@ impl Painter {
                         true,
                         app_state.current_widget.widget_id,
                     ),
-                    Net => self.draw_network_graph(
+                    Net | BasicNet => self.draw_network_graph(
                         f,
                         app_state,
                         rect[0],
@@ -479,13 +1330,84 @@ impl Painter {
                         true,
                         app_state.current_widget.widget_id,
                     ),
+                    Power => self.draw_power_graph(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    Pressure => self.draw_pressure_graph(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    LoadAvg => self.draw_load_avg_graph(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    Latency => self.draw_latency_graph(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    Plugin => self.draw_plugin_graph(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    Frequency => self.draw_frequency_graph(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    Cgroup => self.draw_cgroup_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Events => self.draw_event_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    Users => self.draw_user_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    SystemInfo => self.draw_system_info(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
+                    TopProcesses => self.draw_top_processes(
+                        f,
+                        app_state,
+                        rect[0],
+                        true,
+                        app_state.current_widget.widget_id,
+                    ),
                     _ => {}
                 }
             } else if app_state.app_config_fields.use_basic_mode {
                 // Basic mode.  This basically removes all graphs but otherwise
                 // the same info.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
                 let actual_cpu_data_len = app_state.converted_data.cpu_data.len().saturating_sub(1);
@@ -502,12 +1424,20 @@ impl Painter {
                     }
                 };
 
+                // Summary rows that were turned off take up no space.
+                let used_widgets = &app_state.used_widgets;
+                let (use_cpu, use_mem, use_net) = (
+                    used_widgets.use_cpu,
+                    used_widgets.use_mem,
+                    used_widgets.use_net,
+                );
+
                 let vertical_chunks = Layout::default()
                     .direction(Direction::Vertical)
                     .margin(0)
                     .constraints([
-                        Constraint::Length(cpu_height),
-                        Constraint::Length(2),
+                        Constraint::Length(if use_cpu { cpu_height } else { 0 }),
+                        Constraint::Length(if use_mem || use_net { 2 } else { 0 }),
                         Constraint::Length(2),
                         Constraint::Min(5),
                     ])
@@ -515,16 +1445,22 @@ impl Painter {
 
                 let middle_chunks = Layout::default()
                     .direction(Direction::Horizontal)
-                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .constraints(if use_mem && use_net {
+                        [Constraint::Percentage(50), Constraint::Percentage(50)]
+                    } else if use_mem {
+                        [Constraint::Percentage(100), Constraint::Percentage(0)]
+                    } else {
+                        [Constraint::Percentage(0), Constraint::Percentage(100)]
+                    })
                     .split(vertical_chunks[1]);
 
-                if vertical_chunks[0].width >= 2 {
+                if use_cpu && vertical_chunks[0].width >= 2 {
                     self.draw_basic_cpu(f, app_state, vertical_chunks[0], 1);
                 }
-                if middle_chunks[0].width >= 2 {
+                if use_mem && middle_chunks[0].width >= 2 {
                     self.draw_basic_memory(f, app_state, middle_chunks[0], 2);
                 }
-                if middle_chunks[1].width >= 2 {
+                if use_net && middle_chunks[1].width >= 2 {
                     self.draw_basic_network(f, app_state, middle_chunks[1], 3);
                 }
 
@@ -581,16 +1517,22 @@ impl Painter {
                 }
             } else {
                 // Draws using the passed in (or default) layout.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
+                // Dragging borders to resize isn't supported while the layout scrolls.
+                if app_state.should_get_widget_bounds() && layout_loc.height == terminal_height {
+                    app_state.layout_splits = self.get_layout_splits(terminal_size);
+                }
+
+                let layout_scroll = app_state.layout_scroll;
                 if self.derived_widget_draw_locs.is_empty() || app_state.is_force_redraw {
                     let draw_locs = Layout::default()
                         .margin(0)
                         .constraints(self.row_constraints.as_slice())
                         .direction(Direction::Vertical)
-                        .split(terminal_size);
+                        .split(layout_loc);
 
                     self.derived_widget_draw_locs = izip!(
                         draw_locs,
@@ -618,21 +1560,38 @@ impl Painter {
                                 &cols.children
                             )
                             .map(|(split_loc, constraint, col_constraint_vec, col_rows)| {
-                                izip!(
-                                    Layout::default()
+                                let col_row_locs = match col_rows.tabs.first() {
+                                    Some(tab) => get_tab_col_row_locs(
+                                        col_rows,
+                                        split_loc,
+                                        app_state.get_selected_tab(tab.widget_id),
+                                    ),
+                                    None => Layout::default()
                                         .constraints(constraint.as_slice())
                                         .direction(Direction::Vertical)
-                                        .split(split_loc)
-                                        .into_iter(),
+                                        .split(split_loc),
+                                };
+
+                                izip!(
+                                    col_row_locs.into_iter(),
                                     col_constraint_vec,
                                     &col_rows.children
                                 )
                                 .map(|(draw_loc, col_row_constraint_vec, widgets)| {
                                     // Note that col_row_constraint_vec CONTAINS the widget constraints
-                                    let widget_draw_locs = Layout::default()
+                                    let widget_draw_locs: Vec<Rect> = Layout::default()
                                         .constraints(col_row_constraint_vec.as_slice())
                                         .direction(Direction::Horizontal)
-                                        .split(draw_loc);
+                                        .split(draw_loc)
+                                        .into_iter()
+                                        .map(|widget_draw_loc| {
+                                            scroll_loc(
+                                                widget_draw_loc,
+                                                terminal_size,
+                                                layout_scroll,
+                                            )
+                                        })
+                                        .collect();
 
                                     // Side effect, draw here.
                                     self.draw_widgets_with_constraints(
@@ -666,9 +1625,42 @@ impl Painter {
                             );
                         });
                 }
+
+                self.draw_layout_tabs(f, app_state, layout_loc, terminal_size);
+                self.draw_layout_scroll_arrows(f, layout_scroll, layout_loc, terminal_size);
+            }
+
+            if app_state.is_showing_debug_overlay {
+                self.draw_debug_overlay(f, app_state, terminal_size);
+            }
+
+            if app_state.app_config_fields.no_unicode {
+                f.render_widget(ascii::AsciiFilter, f.size());
             }
         })?;
 
+        if let Some(protocol) = self.graphics_protocol {
+            if app_state.is_force_redraw {
+                self.graph_image_writer.reset();
+            }
+            let graph_images = self.graph_images.get_mut();
+            self.graph_image_writer.write_images(
+                terminal.backend_mut(),
+                protocol,
+                if graph_images.replace_text {
+                    &graph_images.images
+                } else {
+                    &[]
+                },
+                self.graph_cell_size.unwrap_or_default(),
+            )?;
+        }
+
+        if self.is_exporting_graph {
+            let message = self.export_selected_graph(app_state);
+            app_state.set_status_message(message);
+        }
+
         if let Some(updated_current_widget) = app_state
             .widget_map
             .get(&app_state.current_widget.widget_id)
@@ -714,6 +1706,55 @@ impl Painter {
                         true,
                         widget.widget_id,
                     ),
+                    Power => {
+                        self.draw_power_graph(f, app_state, *widget_draw_loc, widget.widget_id)
+                    }
+                    Pressure => {
+                        self.draw_pressure_graph(f, app_state, *widget_draw_loc, widget.widget_id)
+                    }
+                    LoadAvg => {
+                        self.draw_load_avg_graph(f, app_state, *widget_draw_loc, widget.widget_id)
+                    }
+                    Latency => {
+                        self.draw_latency_graph(f, app_state, *widget_draw_loc, widget.widget_id)
+                    }
+                    Plugin => {
+                        self.draw_plugin_graph(f, app_state, *widget_draw_loc, widget.widget_id)
+                    }
+                    Frequency => {
+                        self.draw_frequency_graph(f, app_state, *widget_draw_loc, widget.widget_id)
+                    }
+                    Cgroup => self.draw_cgroup_table(
+                        f,
+                        app_state,
+                        *widget_draw_loc,
+                        true,
+                        widget.widget_id,
+                    ),
+                    Events => self.draw_event_table(
+                        f,
+                        app_state,
+                        *widget_draw_loc,
+                        true,
+                        widget.widget_id,
+                    ),
+                    Users => {
+                        self.draw_user_table(f, app_state, *widget_draw_loc, true, widget.widget_id)
+                    }
+                    SystemInfo => self.draw_system_info(
+                        f,
+                        app_state,
+                        *widget_draw_loc,
+                        true,
+                        widget.widget_id,
+                    ),
+                    TopProcesses => self.draw_top_processes(
+                        f,
+                        app_state,
+                        *widget_draw_loc,
+                        true,
+                        widget.widget_id,
+                    ),
                     _ => {}
                 }
             }
@@ -0,0 +1,112 @@
+//! Replacing the Unicode symbols bottom draws with ASCII, for terminals and fonts that can't show
+//! them well.
+//!
+//! Rather than have every widget pick its symbols based on the mode, everything is drawn as usual
+//! and [`AsciiFilter`] then rewrites the finished frame. Text that isn't one of bottom's own
+//! symbols, like process names, is left alone.
+
+use tui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+/// A widget that turns the symbols of everything drawn under it into ASCII.
+pub struct AsciiFilter;
+
+impl Widget for AsciiFilter {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                if let Some(ascii) = to_ascii(&cell.symbol) {
+                    cell.set_char(ascii);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the ASCII stand-in for a symbol, or `None` if it's already ASCII or isn't one that
+/// bottom draws.
+fn to_ascii(symbol: &str) -> Option<char> {
+    let mut chars = symbol.chars();
+    let c = match (chars.next(), chars.next()) {
+        (Some(c), None) if !c.is_ascii() => c,
+        _ => return None,
+    };
+
+    let ascii = match c {
+        // Braille graph markers, which can hold up to eight dots.
+        '\u{2800}'..='\u{28FF}' => braille_to_ascii(c as u32 - 0x2800),
+        // Borders and tree lines.
+        '─' | '━' | '═' | '┄' | '┈' => '-',
+        '│' | '┃' | '║' | '┆' | '┊' => '|',
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╭' | '╮' | '╰' | '╯' | '┏' | '┓'
+        | '┗' | '┛' | '╔' | '╗' | '╚' | '╝' => '+',
+        // Bars, gauges, and sparklines.
+        '▁' | '▂' | '▃' => '_',
+        '▄' | '▅' | '▆' | '▇' | '█' | '▉' | '▊' | '▋' | '▌' | '▍' | '▎' | '▏' | '░' | '▒' | '▓' => {
+            '#'
+        }
+        // Arrows and other markers.
+        '▲' | '↑' => '^',
+        '▼' | '↓' => 'v',
+        '◀' | '←' => '<',
+        '▶' | '→' => '>',
+        '•' | '·' | '●' => '*',
+        '…' => '~',
+        '°' => '\'',
+        _ => return None,
+    };
+
+    Some(ascii)
+}
+
+/// Picks a character by where the dots of a Braille pattern are. Dots only near the top or bottom
+/// of the cell become `'` or `.`, dots spread over the whole cell become `|`, and anything else
+/// becomes `-`.
+fn braille_to_ascii(dots: u32) -> char {
+    // Each bit is a dot, going down the left side and then the right, with the bottom row last.
+    const ROWS: [u32; 4] = [0x01 | 0x08, 0x02 | 0x10, 0x04 | 0x20, 0x40 | 0x80];
+
+    let rows_with_dots = (0..ROWS.len())
+        .filter(|index| dots & ROWS[*index] != 0)
+        .collect::<Vec<_>>();
+
+    match (rows_with_dots.first(), rows_with_dots.last()) {
+        (Some(0), Some(0)) => '\'',
+        (Some(3), Some(3)) => '.',
+        (Some(top), Some(bottom)) if bottom - top >= 2 => '|',
+        (Some(_), Some(_)) => '-',
+        _ => ' ',
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_ascii() {
+        assert_eq!(to_ascii("a"), None);
+        assert_eq!(to_ascii(" "), None);
+        assert_eq!(to_ascii("日"), None);
+        assert_eq!(to_ascii(""), None);
+
+        assert_eq!(to_ascii("─"), Some('-'));
+        assert_eq!(to_ascii("│"), Some('|'));
+        assert_eq!(to_ascii("┌"), Some('+'));
+        assert_eq!(to_ascii("█"), Some('#'));
+        assert_eq!(to_ascii("▲"), Some('^'));
+        assert_eq!(to_ascii("…"), Some('~'));
+        assert_eq!(to_ascii("•"), Some('*'));
+    }
+
+    #[test]
+    fn test_braille_to_ascii() {
+        assert_eq!(to_ascii("\u{2800}"), Some(' '));
+        assert_eq!(to_ascii("⠁"), Some('\''));
+        assert_eq!(to_ascii("⣀"), Some('.'));
+        assert_eq!(to_ascii("⠒"), Some('-'));
+        assert_eq!(to_ascii("⠤"), Some('-'));
+        assert_eq!(to_ascii("⡇"), Some('|'));
+        assert_eq!(to_ascii("⣿"), Some('|'));
+    }
+}
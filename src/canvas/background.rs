@@ -0,0 +1,149 @@
+//! Detection of whether the terminal has a light or dark background, for the "auto" colour scheme.
+//!
+//! This asks the terminal for its background colour with an OSC 11 query, followed by a primary
+//! device attributes (DA1) query. Nearly every terminal answers the latter, so if its answer
+//! arrives without an answer to the former, the terminal doesn't support OSC 11 and there's no
+//! need to wait out the timeout.
+
+#[cfg(target_family = "unix")]
+use std::time::{Duration, Instant};
+
+/// How long to wait for the terminal to answer.
+#[cfg(target_family = "unix")]
+const QUERY_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Returns whether the terminal's background is light, or `None` if it couldn't be detected.
+/// This must be called before raw mode is enabled and the input thread is started, as it
+/// reads the answer from the terminal itself.
+pub fn is_light_background() -> Option<bool> {
+    let (red, green, blue) = parse_background_response(&query_terminal(b"\x1b]11;?\x1b\\")?)?;
+    Some(relative_luminance(red, green, blue) > 0.5)
+}
+
+/// Sends `query` to the terminal followed by a DA1 query, and returns everything the terminal
+/// answers until the answer to the DA1 query arrives or the timeout is reached. Like
+/// [`is_light_background`], this must be called before raw mode is enabled.
+#[cfg(target_family = "unix")]
+pub(super) fn query_terminal(query: &[u8]) -> Option<String> {
+    use std::{
+        fs::OpenOptions,
+        io::{Read, Write},
+        os::unix::io::AsRawFd,
+    };
+
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+
+    // Raw mode is needed so the answer isn't echoed, and can be read without a newline.
+    crossterm::terminal::enable_raw_mode().ok()?;
+
+    let mut response = String::new();
+    if tty.write_all(query).is_ok() && tty.write_all(b"\x1b[c").is_ok() && tty.flush().is_ok() {
+        let start = Instant::now();
+        let mut buffer = [0; 256];
+        while !is_response_complete(&response) {
+            let remaining = QUERY_TIMEOUT.saturating_sub(start.elapsed());
+            if remaining.is_zero() {
+                break;
+            }
+
+            let mut poll_fd = libc::pollfd {
+                fd: tty.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            if unsafe { libc::poll(&mut poll_fd, 1, remaining.as_millis() as libc::c_int) } <= 0 {
+                break;
+            }
+            match tty.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(read) => response.push_str(&String::from_utf8_lossy(&buffer[..read])),
+            }
+        }
+    }
+
+    let _ = crossterm::terminal::disable_raw_mode();
+
+    Some(response)
+}
+
+#[cfg(not(target_family = "unix"))]
+pub(super) fn query_terminal(_query: &[u8]) -> Option<String> {
+    None
+}
+
+/// Whether the answer to the DA1 query, which is sent last, has arrived. It looks like
+/// `ESC [ ? 62 ; 22 c`.
+#[cfg(target_family = "unix")]
+fn is_response_complete(response: &str) -> bool {
+    match response.rfind("\x1b[?") {
+        Some(start) => response[start..].ends_with('c'),
+        None => false,
+    }
+}
+
+/// Returns the red, green, and blue components, from 0 to 1, of the answer to an OSC 11 query.
+/// The answer looks like `ESC ] 11 ; rgb:RRRR/GGGG/BBBB` followed by `BEL` or `ESC \`, where each
+/// component has one to four hex digits.
+fn parse_background_response(response: &str) -> Option<(f64, f64, f64)> {
+    let start = response.find("\x1b]11;rgb:")? + "\x1b]11;rgb:".len();
+    let colour = &response[start..];
+    let colour = &colour[..colour.find(&['\x07', '\x1b'][..])?];
+
+    let mut components = colour.split('/').map(|component| {
+        if component.is_empty() || component.len() > 4 {
+            return None;
+        }
+        let value = u32::from_str_radix(component, 16).ok()?;
+        let max = (1_u32 << (4 * component.len())) - 1;
+        Some(f64::from(value) / f64::from(max))
+    });
+
+    let red = components.next()??;
+    let green = components.next()??;
+    let blue = components.next()??;
+    if components.next().is_some() {
+        return None;
+    }
+
+    Some((red, green, blue))
+}
+
+/// Returns the relative luminance of a colour, from 0 for black to 1 for white.
+fn relative_luminance(red: f64, green: f64, blue: f64) -> f64 {
+    0.2126 * red + 0.7152 * green + 0.0722 * blue
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_background_response() {
+        assert_eq!(
+            parse_background_response("\x1b]11;rgb:ffff/ffff/ffff\x1b\\\x1b[?62;22c"),
+            Some((1.0, 1.0, 1.0))
+        );
+        assert_eq!(
+            parse_background_response("\x1b]11;rgb:00/80/ff\x07"),
+            Some((0.0, 128.0 / 255.0, 1.0))
+        );
+        assert_eq!(parse_background_response("\x1b[?62;22c"), None);
+        assert_eq!(parse_background_response("\x1b]11;rgb:ffff/ffff\x07"), None);
+        assert_eq!(
+            parse_background_response("\x1b]11;rgb:ffff/ffff/ffff"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_relative_luminance() {
+        assert!(relative_luminance(1.0, 1.0, 1.0) > 0.5);
+        assert!(relative_luminance(0.99, 0.96, 0.89) > 0.5);
+        assert!(relative_luminance(0.0, 0.0, 0.0) <= 0.5);
+        assert!(relative_luminance(0.16, 0.17, 0.2) <= 0.5);
+    }
+}
@@ -18,6 +18,10 @@ pub struct CanvasColours {
     pub all_colour_style: Style,
     pub avg_colour_style: Style,
     pub cpu_colour_styles: Vec<Style>,
+    /// Styles for the series of graphs other than the CPU graph, such as the load average graph.
+    pub series_colour_styles: Vec<Style>,
+    pub network_error_style: Style,
+    pub network_drop_style: Style,
     pub border_style: Style,
     pub highlighted_border_style: Style,
     pub text_style: Style,
@@ -28,11 +32,25 @@ pub struct CanvasColours {
     pub low_battery_colour: Style,
     pub invalid_query_style: Style,
     pub disabled_text_style: Style,
+    pub stopped_process_style: Style,
+    pub marked_process_style: Style,
 }
 
 impl Default for CanvasColours {
     fn default() -> Self {
         let text_colour = Color::Gray;
+        let cpu_colour_styles = vec![
+            Style::default().fg(Color::LightMagenta),
+            Style::default().fg(Color::LightYellow),
+            Style::default().fg(Color::LightCyan),
+            Style::default().fg(Color::LightGreen),
+            Style::default().fg(Color::LightBlue),
+            Style::default().fg(Color::LightRed),
+            Style::default().fg(Color::Cyan),
+            Style::default().fg(Color::Green),
+            Style::default().fg(Color::Blue),
+            Style::default().fg(Color::Red),
+        ];
 
         CanvasColours {
             currently_selected_text_colour: Color::Black,
@@ -49,18 +67,10 @@ impl Default for CanvasColours {
             total_tx_style: Style::default().fg(STANDARD_FOURTH_COLOUR),
             all_colour_style: Style::default().fg(ALL_COLOUR),
             avg_colour_style: Style::default().fg(AVG_COLOUR),
-            cpu_colour_styles: vec![
-                Style::default().fg(Color::LightMagenta),
-                Style::default().fg(Color::LightYellow),
-                Style::default().fg(Color::LightCyan),
-                Style::default().fg(Color::LightGreen),
-                Style::default().fg(Color::LightBlue),
-                Style::default().fg(Color::LightRed),
-                Style::default().fg(Color::Cyan),
-                Style::default().fg(Color::Green),
-                Style::default().fg(Color::Blue),
-                Style::default().fg(Color::Red),
-            ],
+            series_colour_styles: cpu_colour_styles.clone(),
+            cpu_colour_styles,
+            network_error_style: Style::default().fg(Color::Red),
+            network_drop_style: Style::default().fg(Color::Yellow),
             border_style: Style::default().fg(text_colour),
             highlighted_border_style: Style::default().fg(STANDARD_HIGHLIGHT_COLOUR),
             text_style: Style::default().fg(text_colour),
@@ -71,6 +81,8 @@ impl Default for CanvasColours {
             low_battery_colour: Style::default().fg(Color::Red),
             invalid_query_style: Style::default().fg(tui::style::Color::Red),
             disabled_text_style: Style::default().fg(Color::DarkGray),
+            stopped_process_style: Style::default().fg(Color::Yellow),
+            marked_process_style: Style::default().fg(Color::LightMagenta),
         }
     }
 }
@@ -107,6 +119,13 @@ impl CanvasColours {
                 .context("Update 'cpu_core_colors' in your config file..")?;
         }
 
+        // This must come after the CPU colours, which are also used for other graphs if these
+        // aren't set.
+        if let Some(series_colors) = &colours.series_colors {
+            self.set_series_colours(series_colors)
+                .context("Update 'series_colors' in your config file.")?;
+        }
+
         if let Some(ram_color) = &colours.ram_color {
             self.set_ram_colour(ram_color)
                 .context("Update 'ram_color' in your config file..")?;
@@ -172,6 +191,31 @@ impl CanvasColours {
                 .context("Update 'disabled_text_color' in your config file.")?;
         }
 
+        if let Some(stopped_process_color) = &colours.stopped_process_color {
+            self.set_stopped_process_colour(stopped_process_color)
+                .context("Update 'stopped_process_color' in your config file.")?;
+        }
+
+        if let Some(marked_process_color) = &colours.marked_process_color {
+            self.set_marked_process_colour(marked_process_color)
+                .context("Update 'marked_process_color' in your config file.")?;
+        }
+
+        if let Some(network_error_color) = &colours.network_error_color {
+            self.set_network_error_colour(network_error_color)
+                .context("Update 'network_error_color' in your config file.")?;
+        }
+
+        if let Some(network_drop_color) = &colours.network_drop_color {
+            self.set_network_drop_colour(network_drop_color)
+                .context("Update 'network_drop_color' in your config file.")?;
+        }
+
+        if let Some(error_color) = &colours.error_color {
+            self.set_error_colour(error_color)
+                .context("Update 'error_color' in your config file.")?;
+        }
+
         if let Some(rx_total_color) = &colours.rx_total_color {
             self.set_rx_total_colour(rx_total_color)?;
         }
@@ -188,6 +232,16 @@ impl CanvasColours {
         Ok(())
     }
 
+    pub fn set_stopped_process_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.stopped_process_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_marked_process_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.marked_process_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
     pub fn set_text_colour(&mut self, colour: &str) -> error::Result<()> {
         self.text_style = get_style_from_config(colour)?;
         Ok(())
@@ -255,6 +309,30 @@ impl CanvasColours {
             .iter()
             .map(|colour| get_style_from_config(colour))
             .collect::<error::Result<Vec<Style>>>()?;
+        self.series_colour_styles = self.cpu_colour_styles.clone();
+        Ok(())
+    }
+
+    pub fn set_series_colours(&mut self, colours: &[String]) -> error::Result<()> {
+        self.series_colour_styles = colours
+            .iter()
+            .map(|colour| get_style_from_config(colour))
+            .collect::<error::Result<Vec<Style>>>()?;
+        Ok(())
+    }
+
+    pub fn set_network_error_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.network_error_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_network_drop_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.network_drop_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_error_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.invalid_query_style = get_style_from_config(colour)?;
         Ok(())
     }
 
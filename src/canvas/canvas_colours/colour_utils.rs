@@ -3,7 +3,7 @@ use std::collections::HashMap;
 
 use tui::style::{Color, Style};
 
-use crate::utils::error;
+use crate::utils::{error, gen_util::get_closest_match};
 
 // Approx, good enough for use (also Clippy gets mad if it's too long)
 pub const STANDARD_FIRST_COLOUR: Color = Color::LightMagenta;
@@ -154,8 +154,14 @@ fn convert_name_to_color(color_name: &str) -> error::Result<Color> {
         return Ok(*color);
     }
 
+    let mut names = COLOR_NAME_LOOKUP_TABLE.keys().copied().collect::<Vec<_>>();
+    names.sort_unstable();
+    let suggestion = get_closest_match(&color_name.to_lowercase().replace(' ', ""), &names)
+        .map(|name| format!(" Did you mean \"{}\"?", name))
+        .unwrap_or_default();
+
     Err(error::BottomError::ConfigError(format!(
-        "\"{}\" is an invalid named colour.
+        "\"{}\" is an invalid named colour.{}
         
 The following are supported strings: 
 +--------+------------+--------------+
@@ -172,7 +178,7 @@ The following are supported strings:
 |  Blue  | LightGreen |              |
 +--------+------------+--------------+
         ",
-        color_name
+        color_name, suggestion
     )))
 }
 
@@ -1,2 +1,14 @@
+pub mod config_error_dialog;
 pub mod dd_dialog;
+pub mod diagnostics_dialog;
 pub mod help_dialog;
+pub mod kill_queue_dialog;
+pub mod layout_edit_dialog;
+pub mod options_menu_dialog;
+pub mod process_affinity_dialog;
+pub mod process_command_dialog;
+pub mod process_graph_dialog;
+pub mod process_inspector_dialog;
+pub mod process_priority_dialog;
+pub mod process_threads_dialog;
+pub mod unit_control_dialog;
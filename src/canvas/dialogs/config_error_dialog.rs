@@ -0,0 +1,95 @@
+use crate::canvas::Painter;
+
+use unicode_width::UnicodeWidthStr;
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+const CONFIG_WARNINGS_INTRO: &str = "The config file was loaded, but has some mistakes:";
+
+/// Returns how tall the config warnings dialog has to be to fit every warning at this width.
+pub fn get_config_warnings_dialog_height(config_warnings: &[String], width: u16) -> u16 {
+    let text_width = usize::from(width.saturating_sub(2)).max(1);
+    let warning_lines: usize = config_warnings
+        .iter()
+        .map(|warning| (warning.width() + 2) / text_width + 1)
+        .sum();
+
+    (CONFIG_WARNINGS_INTRO.width() / text_width + warning_lines + 4) as u16
+}
+
+impl Painter {
+    pub fn draw_config_error_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, config_error: &str, draw_loc: Rect,
+    ) {
+        let lines = vec![
+            Spans::from(Span::styled(
+                "The config file couldn't be reloaded, so the current settings are kept:",
+                self.colours.text_style,
+            )),
+            Spans::default(),
+            Spans::from(Span::styled(
+                config_error.to_string(),
+                self.colours.invalid_query_style,
+            )),
+        ];
+
+        let title = Spans::from(vec![
+            Span::styled(" Config error ", self.colours.widget_title_style),
+            Span::styled("─ Esc to close ", self.colours.border_style),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.highlighted_border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left)
+                .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+    }
+
+    pub fn draw_config_warnings_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, config_warnings: &[String], draw_loc: Rect,
+    ) {
+        let mut lines = vec![
+            Spans::from(Span::styled(CONFIG_WARNINGS_INTRO, self.colours.text_style)),
+            Spans::default(),
+        ];
+        lines.extend(config_warnings.iter().map(|warning| {
+            Spans::from(vec![
+                Span::styled("• ", self.colours.text_style),
+                Span::styled(warning.as_str(), self.colours.invalid_query_style),
+            ])
+        }));
+
+        let title = Spans::from(vec![
+            Span::styled(" Config warnings ", self.colours.widget_title_style),
+            Span::styled("─ Esc to close ", self.colours.border_style),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.highlighted_border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left)
+                .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+    }
+}
@@ -21,40 +21,98 @@ impl Painter {
         if let Some(dd_err) = &app_state.dd_err {
             return Some(Text::from(vec![
                 Spans::default(),
-                Spans::from("Failed to kill process."),
+                Spans::from("Failed to send a signal to the process."),
                 Spans::from(dd_err.clone()),
                 Spans::from("Please press ENTER or ESC to close this dialog."),
             ]));
         } else if let Some(to_kill_processes) = app_state.get_to_delete_processes() {
             if let Some(first_pid) = to_kill_processes.1.first() {
-                return Some(Text::from(vec![
-                    Spans::from(""),
-                    if app_state
-                        .proc_state
-                        .widget_states
-                        .get(&app_state.current_widget.widget_id)
-                        .map(|p| matches!(p.mode, ProcWidgetMode::Grouped))
-                        .unwrap_or(false)
-                    {
-                        if to_kill_processes.1.len() != 1 {
-                            Spans::from(format!(
+                let mut text = vec![Spans::from("")];
+                if app_state.delete_dialog_state.is_killing_marked {
+                    text.push(Spans::from(format!(
+                        "Kill {} marked process{}?  Press ENTER to confirm.",
+                        to_kill_processes.1.len(),
+                        if to_kill_processes.1.len() == 1 {
+                            ""
+                        } else {
+                            "es"
+                        }
+                    )));
+                    text.push(Spans::from(to_kill_processes.0));
+                } else {
+                    text.push(
+                        if app_state
+                            .proc_state
+                            .widget_states
+                            .get(&app_state.current_widget.widget_id)
+                            .map(|p| matches!(p.mode, ProcWidgetMode::Grouped))
+                            .unwrap_or(false)
+                        {
+                            if to_kill_processes.1.len() != 1 {
+                                Spans::from(format!(
                                 "Kill {} processes with the name \"{}\"?  Press ENTER to confirm.",
                                 to_kill_processes.1.len(),
                                 to_kill_processes.0
                             ))
+                            } else {
+                                Spans::from(format!(
+                                    "Kill 1 process with the name \"{}\"?  Press ENTER to confirm.",
+                                    to_kill_processes.0
+                                ))
+                            }
                         } else {
                             Spans::from(format!(
-                                "Kill 1 process with the name \"{}\"?  Press ENTER to confirm.",
-                                to_kill_processes.0
+                                "Kill process \"{}\" with PID {}?  Press ENTER to confirm.",
+                                to_kill_processes.0, first_pid
                             ))
+                        },
+                    );
+                }
+
+                let descendant_pids = &app_state.delete_dialog_state.descendant_pids;
+                if !descendant_pids.is_empty() {
+                    const MAX_SHOWN_PIDS: usize = 8;
+
+                    let mut shown_pids = descendant_pids
+                        .iter()
+                        .take(MAX_SHOWN_PIDS)
+                        .map(|pid| pid.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    if descendant_pids.len() > MAX_SHOWN_PIDS {
+                        shown_pids.push_str(&format!(
+                            ", and {} more",
+                            descendant_pids.len() - MAX_SHOWN_PIDS
+                        ));
+                    }
+
+                    text.push(Spans::from(format!(
+                        "{} {} child process{}: {}",
+                        if to_kill_processes.1.len() == 1 {
+                            "It has"
+                        } else {
+                            "They have"
+                        },
+                        descendant_pids.len(),
+                        if descendant_pids.len() == 1 { "" } else { "es" },
+                        shown_pids
+                    )));
+                    text.push(Spans::from(format!(
+                        "[{}] Also kill {} child processes (press t to toggle)",
+                        if app_state.delete_dialog_state.kill_descendants {
+                            "x"
+                        } else {
+                            " "
+                        },
+                        if to_kill_processes.1.len() == 1 {
+                            "its"
+                        } else {
+                            "their"
                         }
-                    } else {
-                        Spans::from(format!(
-                            "Kill process \"{}\" with PID {}?  Press ENTER to confirm.",
-                            to_kill_processes.0, first_pid
-                        ))
-                    },
-                ]));
+                    )));
+                }
+
+                return Some(Text::from(text));
             }
         }
 
@@ -0,0 +1,98 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    app::data_harvester::{HarvestError, UnavailableHarvest},
+    canvas::Painter,
+    data_conversion::duration_string,
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+impl Painter {
+    /// Returns the lines of the diagnostics dialog, which lists everything that's failing to be
+    /// collected, why, and for how long, then everything that's skipped as it can't be read.
+    pub fn get_diagnostics_lines(
+        &self, harvest_errors: &[HarvestError], unavailable_harvests: &[UnavailableHarvest],
+    ) -> Vec<Spans<'_>> {
+        if harvest_errors.is_empty() && unavailable_harvests.is_empty() {
+            return vec![Spans::from(Span::styled(
+                "Everything is being collected.",
+                self.colours.text_style,
+            ))];
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        harvest_errors
+            .iter()
+            .flat_map(|harvest_error| {
+                vec![
+                    Spans::from(vec![
+                        Span::styled(
+                            format!("{}: ", harvest_error.collector),
+                            self.colours.invalid_query_style,
+                        ),
+                        Span::styled(harvest_error.message.clone(), self.colours.text_style),
+                    ]),
+                    Spans::from(Span::styled(
+                        format!(
+                            "  Failing for {}, {} {} in a row",
+                            duration_string(now.saturating_sub(harvest_error.since)),
+                            harvest_error.failures,
+                            if harvest_error.failures == 1 {
+                                "time"
+                            } else {
+                                "times"
+                            }
+                        ),
+                        self.colours.text_style,
+                    )),
+                ]
+            })
+            .chain(unavailable_harvests.iter().map(|unavailable| {
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{}: ", unavailable.collector),
+                        self.colours.table_header_style,
+                    ),
+                    Span::styled(
+                        format!("Not collected, as {}", unavailable.reason),
+                        self.colours.text_style,
+                    ),
+                ])
+            }))
+            .collect()
+    }
+
+    pub fn draw_diagnostics_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, lines: Vec<Spans<'_>>, draw_loc: Rect,
+    ) {
+        let title = Spans::from(vec![
+            Span::styled(" Diagnostics ", self.colours.widget_title_style),
+            Span::styled("─ Esc to close ", self.colours.border_style),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.highlighted_border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left)
+                .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{app::App, canvas::Painter};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+impl Painter {
+    /// Draws the kill queue, previewing which processes will be killed before any of them are.
+    pub fn draw_kill_queue_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let kill_queue_state = &app_state.kill_queue_state;
+        let process_harvest = &app_state.data_collection.process_data.process_harvest;
+
+        let mut lines = if kill_queue_state.queue.is_empty() {
+            vec![Spans::from(Span::styled(
+                "No processes are queued. Press dq on a process to queue it.",
+                self.colours.text_style,
+            ))]
+        } else {
+            // Only the rows around the selected one fit when the queue is long.
+            let max_rows = usize::from(draw_loc.height.saturating_sub(7)).max(1);
+            let first_row = (kill_queue_state.selected_entry + 1).saturating_sub(max_rows);
+
+            kill_queue_state
+                .queue
+                .iter()
+                .enumerate()
+                .skip(first_row)
+                .take(max_rows)
+                .map(|(index, queued)| {
+                    let is_running = matches!(
+                        process_harvest.get(&queued.pid),
+                        Some(process) if queued.is_same_process(process)
+                    );
+                    let style = if index == kill_queue_state.selected_entry {
+                        self.colours.currently_selected_text_style
+                    } else if is_running {
+                        self.colours.text_style
+                    } else {
+                        self.colours.disabled_text_style
+                    };
+
+                    Spans::from(Span::styled(
+                        format!(
+                            "{:>8}  {}{}",
+                            queued.pid,
+                            queued.name,
+                            if is_running { "" } else { " (exited)" }
+                        ),
+                        style,
+                    ))
+                })
+                .collect()
+        };
+
+        lines.push(Spans::default());
+        lines.push(Spans::from(Span::styled(
+            "Nothing is killed until ENTER is pressed, which kills every queued process.",
+            self.colours.text_style,
+        )));
+        lines.push(Spans::from(Span::styled(
+            "Press d to remove the selected process from the queue, or c to clear it.",
+            self.colours.text_style,
+        )));
+        if let Some(error) = &kill_queue_state.error {
+            lines.push(Spans::default());
+            lines.push(Spans::from(Span::styled(
+                error.clone(),
+                self.colours.invalid_query_style,
+            )));
+        }
+
+        let title = Spans::from(vec![
+            Span::styled(
+                format!(" Kill queue ({}) ", kill_queue_state.queue.len()),
+                self.colours.widget_title_style,
+            ),
+            Span::styled("─ Esc to close ", self.colours.border_style),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.highlighted_border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left)
+                .wrap(Wrap { trim: false }),
+            draw_loc,
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{app::App, canvas::Painter};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+};
+
+const LAYOUT_EDIT_BASE: &str = " Edit Layout ── Enter to save, Esc to cancel ";
+const LAYOUT_EDIT_HINTS: &[&str] = &[
+    "h/l: change type  +/-: change ratio  K/J: move  x: remove",
+    "a: add widget  c: add widget below  r: add row",
+];
+
+impl Painter {
+    pub fn draw_layout_edit_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let layout_edit_title = Spans::from(vec![
+            Span::styled(" Edit Layout ", self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "─{}─ Enter to save, Esc to cancel ",
+                    "─".repeat(
+                        usize::from(draw_loc.width)
+                            .saturating_sub(LAYOUT_EDIT_BASE.chars().count() + 2)
+                    )
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let edit_state = &app_state.layout_edit_state;
+        let mut text = LAYOUT_EDIT_HINTS
+            .iter()
+            .map(|hint| Spans::from(Span::styled(*hint, self.colours.text_style)))
+            .collect::<Vec<_>>();
+        if let Some(error) = &edit_state.error {
+            text.push(Spans::from(Span::styled(
+                error.as_str(),
+                self.colours.invalid_query_style,
+            )));
+        }
+        text.push(Spans::default());
+        let header_len = text.len();
+
+        text.extend(
+            edit_state
+                .get_entries()
+                .into_iter()
+                .enumerate()
+                .map(|(itx, entry)| {
+                    Spans::from(Span::styled(
+                        edit_state.get_entry_text(entry),
+                        if itx == edit_state.selected_index {
+                            self.colours.currently_selected_text_style
+                        } else {
+                            self.colours.text_style
+                        },
+                    ))
+                }),
+        );
+
+        // Scroll so that the selected entry is always visible.
+        let visible_entries =
+            usize::from(draw_loc.height.saturating_sub(2)).saturating_sub(header_len);
+        let scroll = (edit_state.selected_index + 1).saturating_sub(visible_entries);
+        if scroll > 0 {
+            text.drain(header_len..header_len + scroll);
+        }
+
+        f.render_widget(
+            Paragraph::new(text)
+                .block(
+                    Block::default()
+                        .title(layout_edit_title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left),
+            draw_loc,
+        );
+    }
+}
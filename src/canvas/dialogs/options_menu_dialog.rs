@@ -0,0 +1,80 @@
+use crate::{
+    app::{states::OptionsMenuEntry, App},
+    canvas::Painter,
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+impl Painter {
+    pub fn draw_options_menu_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let options_state = &app_state.options_menu_state;
+        let selected_entry = options_state.get_selected_entry();
+
+        let mut lines: Vec<Spans<'_>> = OptionsMenuEntry::ALL
+            .iter()
+            .map(|entry| {
+                Spans::from(vec![
+                    Span::styled(format!("{:<26}", entry.get_name()), self.colours.text_style),
+                    Span::styled(
+                        format!("< {} >", app_state.get_option_value(*entry)),
+                        if *entry == selected_entry {
+                            self.colours.currently_selected_text_style
+                        } else {
+                            self.colours.text_style
+                        },
+                    ),
+                ])
+            })
+            .collect();
+
+        lines.push(Spans::default());
+        lines.push(Spans::from(Span::styled(
+            "Changes last until bottom is closed. Press s to save them to the config file.",
+            self.colours.text_style,
+        )));
+        match &options_state.save_result {
+            Some(Ok(())) => {
+                lines.push(Spans::default());
+                lines.push(Spans::from(Span::styled(
+                    "Saved to the config file.",
+                    self.colours.text_style,
+                )));
+            }
+            Some(Err(err)) => {
+                lines.push(Spans::default());
+                lines.push(Spans::from(Span::styled(
+                    err.clone(),
+                    self.colours.invalid_query_style,
+                )));
+            }
+            None => {}
+        }
+
+        let title = Spans::from(vec![
+            Span::styled(" Options ", self.colours.widget_title_style),
+            Span::styled("─ Esc to close ", self.colours.border_style),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.highlighted_border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left)
+                .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{
+    app::{states::AppProcessAffinityState, App},
+    canvas::Painter,
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+impl Painter {
+    pub fn draw_process_affinity_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let affinity_state = &app_state.process_affinity_state;
+
+        let mut lines = vec![
+            Spans::from(vec![
+                Span::styled("Allowed CPUs: ", self.colours.text_style),
+                Span::styled(affinity_state.get_cpu_list(), self.colours.text_style),
+            ]),
+            Spans::default(),
+        ];
+
+        lines.extend(
+            affinity_state
+                .cpus
+                .chunks(AppProcessAffinityState::CPUS_PER_ROW)
+                .enumerate()
+                .map(|(row, cpus)| {
+                    let row_start = row * AppProcessAffinityState::CPUS_PER_ROW;
+                    Spans::from(
+                        cpus.iter()
+                            .enumerate()
+                            .map(|(offset, allowed)| {
+                                let cpu = row_start + offset;
+                                Span::styled(
+                                    format!("{:>3}[{}] ", cpu, if *allowed { "x" } else { " " }),
+                                    if cpu == affinity_state.selected_cpu {
+                                        self.colours.currently_selected_text_style
+                                    } else {
+                                        self.colours.text_style
+                                    },
+                                )
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                }),
+        );
+
+        lines.push(Spans::default());
+        lines.push(Spans::from(Span::styled(
+            "Press SPACE to toggle a CPU, a to allow all CPUs, and ENTER to apply.",
+            self.colours.text_style,
+        )));
+        if let Some(error) = &affinity_state.error {
+            lines.push(Spans::default());
+            lines.push(Spans::from(Span::styled(
+                error.clone(),
+                self.colours.invalid_query_style,
+            )));
+        }
+
+        let title = Spans::from(vec![
+            Span::styled(
+                format!(" {} ({}) ", affinity_state.name, affinity_state.pid),
+                self.colours.widget_title_style,
+            ),
+            Span::styled("─ Esc to close ", self.colours.border_style),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.highlighted_border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left)
+                .wrap(Wrap { trim: false }),
+            draw_loc,
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{app::App, canvas::Painter};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::Style,
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+};
+use unicode_width::UnicodeWidthChar;
+
+impl Painter {
+    pub fn draw_process_command_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let command_state = &mut app_state.process_command_state;
+        let width = usize::from(draw_loc.width.saturating_sub(2));
+        let mut lines: Vec<Spans<'_>> = Vec::new();
+
+        if let Some(command) = &command_state.command {
+            let mut push_section = |title: String, result: Result<Vec<String>, String>| {
+                if !lines.is_empty() {
+                    lines.push(Spans::default());
+                }
+                lines.push(Spans::from(Span::styled(
+                    title,
+                    self.colours.table_header_style,
+                )));
+                let (entries, style) = match result {
+                    Ok(entries) => (entries, self.colours.text_style),
+                    Err(err) => (vec![err], self.colours.invalid_query_style),
+                };
+                push_wrapped(&mut lines, &entries, width, style);
+            };
+
+            push_section("Command".to_string(), Ok(vec![command.command.clone()]));
+            push_section(
+                "Working directory".to_string(),
+                command.cwd.clone().map(|cwd| vec![cwd]),
+            );
+            push_section(
+                match &command.arguments {
+                    Ok(arguments) => format!("Arguments ({})", arguments.len()),
+                    Err(_) => "Arguments".to_string(),
+                },
+                command
+                    .arguments
+                    .as_ref()
+                    .map_err(String::clone)
+                    .map(|arguments| {
+                        arguments
+                            .iter()
+                            .enumerate()
+                            .map(|(index, argument)| format!("{:>3}  {}", index, argument))
+                            .collect()
+                    }),
+            );
+        }
+
+        let scroll_state = &mut command_state.scroll_state;
+        scroll_state.max_scroll_index =
+            (lines.len() as u16).saturating_sub(draw_loc.height.saturating_sub(2)) + 1;
+        if scroll_state.current_scroll_index >= scroll_state.max_scroll_index {
+            scroll_state.current_scroll_index = scroll_state.max_scroll_index.saturating_sub(1);
+        }
+
+        let title = Spans::from(vec![
+            Span::styled(
+                format!(" {} ({}) ", command_state.name, command_state.pid),
+                self.colours.widget_title_style,
+            ),
+            Span::styled("─ Esc to close ", self.colours.border_style),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.highlighted_border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left)
+                .scroll((scroll_state.current_scroll_index, 0)),
+            draw_loc,
+        );
+    }
+}
+
+/// Wraps each entry to the given width, breaking anywhere rather than just at spaces, as long
+/// commands and paths often have none. This is done here rather than by the [`Paragraph`] so
+/// the number of lines is known when scrolling.
+fn push_wrapped(lines: &mut Vec<Spans<'_>>, entries: &[String], width: usize, style: Style) {
+    for entry in entries {
+        let mut line = String::new();
+        let mut line_width = 0;
+        for c in entry.chars() {
+            let char_width = UnicodeWidthChar::width(c).unwrap_or(0);
+            if line_width + char_width > width && !line.is_empty() {
+                lines.push(Spans::from(Span::styled(std::mem::take(&mut line), style)));
+                line_width = 0;
+            }
+            line.push(c);
+            line_width += char_width;
+        }
+        lines.push(Spans::from(Span::styled(line, style)));
+    }
+}
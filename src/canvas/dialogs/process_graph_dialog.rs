@@ -0,0 +1,97 @@
+use std::borrow::Cow;
+
+use crate::{
+    app::App,
+    canvas::Painter,
+    components::time_graph::{GraphData, TimeGraph},
+    constants::TIME_LABEL_HEIGHT_LIMIT,
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    terminal::Frame,
+};
+
+impl Painter {
+    /// Draws the graph of a pinned process' usage over time. Like the other views of a single
+    /// process, this is a dialog rather than a widget in the layout, as it only has something to
+    /// show once a process is pinned, and a widget would need room set aside in every layout.
+    pub fn draw_process_graph_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        const MEM_Y_BOUNDS: [f64; 2] = [0.0, 100.5];
+        const MEM_Y_LABELS: [Cow<'static, str>; 2] = [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
+
+        let graph_state = &app_state.process_graph_state;
+        let cpu_data = &app_state.converted_data.pinned_process_cpu_data;
+        let mem_data = &app_state.converted_data.pinned_process_mem_data;
+
+        let split_draw_loc = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(draw_loc);
+        let x_bounds = [0, app_state.app_config_fields.default_time_value];
+        let hide_x_labels = app_state.app_config_fields.hide_time
+            || split_draw_loc[0].height < TIME_LABEL_HEIGHT_LIMIT;
+
+        // Process CPU usage may go above 100% if it is not averaged out by the number of threads.
+        let cpu_max = cpu_data
+            .iter()
+            .fold(100.0, |max_usage: f64, (_, usage)| max_usage.max(*usage));
+        let cpu_y_labels = [Cow::Borrowed("  0%"), format!("{:.0}%", cpu_max).into()];
+
+        let cpu_title = format!(" {} ({}) CPU ", graph_state.name, graph_state.pid);
+        TimeGraph {
+            use_dot: app_state.app_config_fields.use_dot,
+            x_bounds,
+            hide_x_labels,
+            y_bounds: [0.0, cpu_max + 0.5],
+            y_labels: &cpu_y_labels,
+            graph_style: self.colours.graph_style,
+            border_style: self.colours.highlighted_border_style,
+            title: cpu_title.into(),
+            is_expanded: true,
+            title_style: self.colours.widget_title_style,
+            legend_constraints: None,
+            graph_images: self.graph_image_sink(),
+            gaps: &app_state.converted_data.gaps,
+            shade_gaps: app_state.app_config_fields.shade_gaps,
+        }
+        .draw_time_graph(
+            f,
+            split_draw_loc[0],
+            &[GraphData {
+                points: cpu_data,
+                style: self.colours.avg_colour_style,
+                name: None,
+            }],
+        );
+
+        TimeGraph {
+            use_dot: app_state.app_config_fields.use_dot,
+            x_bounds,
+            hide_x_labels,
+            y_bounds: MEM_Y_BOUNDS,
+            y_labels: &MEM_Y_LABELS,
+            graph_style: self.colours.graph_style,
+            border_style: self.colours.highlighted_border_style,
+            title: " Memory ".into(),
+            is_expanded: false,
+            title_style: self.colours.widget_title_style,
+            legend_constraints: None,
+            graph_images: self.graph_image_sink(),
+            gaps: &app_state.converted_data.gaps,
+            shade_gaps: app_state.app_config_fields.shade_gaps,
+        }
+        .draw_time_graph(
+            f,
+            split_draw_loc[1],
+            &[GraphData {
+                points: mem_data,
+                style: self.colours.ram_style,
+                name: None,
+            }],
+        );
+    }
+}
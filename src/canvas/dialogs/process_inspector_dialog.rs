@@ -0,0 +1,112 @@
+use crate::{app::App, canvas::Painter, utils::gen_util::get_binary_bytes};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+};
+
+impl Painter {
+    pub fn draw_process_inspector_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let inspector_state = &mut app_state.process_inspector_state;
+        let mut lines: Vec<Spans<'_>> = Vec::new();
+
+        if let Some(details) = &inspector_state.details {
+            let mut push_section =
+                |title: String, result: Result<Vec<String>, String>| {
+                    if !lines.is_empty() {
+                        lines.push(Spans::default());
+                    }
+                    lines.push(Spans::from(Span::styled(
+                        title,
+                        self.colours.table_header_style,
+                    )));
+                    match result {
+                        Ok(entries) => lines.extend(entries.into_iter().map(|entry| {
+                            Spans::from(Span::styled(entry, self.colours.text_style))
+                        })),
+                        Err(err) => lines.push(Spans::from(Span::styled(
+                            err,
+                            self.colours.invalid_query_style,
+                        ))),
+                    }
+                };
+
+            push_section(
+                "Working directory".to_string(),
+                details.cwd.clone().map(|cwd| vec![cwd]),
+            );
+            push_section(
+                match &details.environment {
+                    Ok(environment) => format!("Environment ({})", environment.len()),
+                    Err(_) => "Environment".to_string(),
+                },
+                details.environment.clone(),
+            );
+            push_section(
+                match &details.open_files {
+                    Ok(open_files) => format!("Open files ({})", open_files.len()),
+                    Err(_) => "Open files".to_string(),
+                },
+                details.open_files.clone(),
+            );
+            push_section(
+                "Memory maps".to_string(),
+                details
+                    .memory_maps
+                    .as_ref()
+                    .map_err(String::clone)
+                    .map(|summary| {
+                        let to_string = |kib: u64| {
+                            let (value, unit) = get_binary_bytes(kib * 1024);
+                            format!("{:.1}{}", value, unit)
+                        };
+
+                        vec![
+                            format!("Mappings:     {}", summary.num_maps),
+                            format!("Total size:   {}", to_string(summary.total_kib)),
+                            format!("Anonymous:    {}", to_string(summary.anonymous_kib)),
+                            format!(
+                                "File-backed:  {} across {} files",
+                                to_string(summary.file_backed_kib),
+                                summary.num_files
+                            ),
+                        ]
+                    }),
+            );
+        }
+
+        let scroll_state = &mut inspector_state.scroll_state;
+        scroll_state.max_scroll_index =
+            (lines.len() as u16).saturating_sub(draw_loc.height.saturating_sub(2)) + 1;
+        if scroll_state.current_scroll_index >= scroll_state.max_scroll_index {
+            scroll_state.current_scroll_index = scroll_state.max_scroll_index.saturating_sub(1);
+        }
+
+        let title = Spans::from(vec![
+            Span::styled(
+                format!(" {} ({}) ", inspector_state.name, inspector_state.pid),
+                self.colours.widget_title_style,
+            ),
+            Span::styled("─ Esc to close ", self.colours.border_style),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.highlighted_border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left)
+                .scroll((scroll_state.current_scroll_index, 0)),
+            draw_loc,
+        );
+    }
+}
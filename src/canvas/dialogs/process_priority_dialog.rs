@@ -0,0 +1,95 @@
+use crate::{
+    app::{states::PriorityField, App},
+    canvas::Painter,
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+impl Painter {
+    pub fn draw_process_priority_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let priority_state = &app_state.process_priority_state;
+        let selected_field = priority_state.get_selected_field();
+
+        let mut lines: Vec<Spans<'_>> = PriorityField::ALL
+            .iter()
+            .map(|field| {
+                let (label, value) = match field {
+                    PriorityField::Nice => ("Nice value", priority_state.nice.to_string()),
+                    PriorityField::IoClass => {
+                        ("I/O class", priority_state.io_class.name().to_string())
+                    }
+                    PriorityField::IoLevel => (
+                        "I/O priority",
+                        if priority_state.io_class.has_levels() {
+                            priority_state.io_level.to_string()
+                        } else {
+                            "N/A".to_string()
+                        },
+                    ),
+                };
+
+                Spans::from(vec![
+                    Span::styled(format!("{:<14}", label), self.colours.text_style),
+                    Span::styled(
+                        format!("< {} >", value),
+                        if *field == selected_field {
+                            self.colours.currently_selected_text_style
+                        } else {
+                            self.colours.text_style
+                        },
+                    ),
+                ])
+            })
+            .collect();
+
+        lines.push(Spans::default());
+        lines.push(Spans::from(Span::styled(
+            "Lower values are a higher priority. Press ENTER to apply.",
+            self.colours.text_style,
+        )));
+        if let Some(error) = &priority_state.error {
+            lines.push(Spans::default());
+            lines.push(Spans::from(Span::styled(
+                error.clone(),
+                self.colours.invalid_query_style,
+            )));
+        }
+
+        let title = if priority_state.other_pids.is_empty() {
+            format!(" {} ({}) ", priority_state.name, priority_state.pid)
+        } else {
+            format!(
+                " {} ({}) and {} more ",
+                priority_state.name,
+                priority_state.pid,
+                priority_state.other_pids.len()
+            )
+        };
+        let title = Spans::from(vec![
+            Span::styled(title, self.colours.widget_title_style),
+            Span::styled("─ Esc to close ", self.colours.border_style),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.highlighted_border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left)
+                .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+    }
+}
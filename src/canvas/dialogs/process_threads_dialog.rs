@@ -0,0 +1,85 @@
+use crate::{app::App, canvas::Painter};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+};
+
+impl Painter {
+    pub fn draw_process_threads_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let threads_state = &mut app_state.process_threads_state;
+
+        let title = Spans::from(vec![
+            Span::styled(
+                format!(
+                    " Threads of {} ({}) ",
+                    threads_state.name, threads_state.pid
+                ),
+                self.colours.widget_title_style,
+            ),
+            Span::styled("─ Esc to close ", self.colours.border_style),
+        ]);
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(self.colours.highlighted_border_style);
+        let inner_loc = block.inner(draw_loc);
+        f.render_widget(block, draw_loc);
+
+        let lines: Vec<Spans<'_>> = match &threads_state.error {
+            Some(err) => vec![Spans::from(Span::styled(
+                err.clone(),
+                self.colours.invalid_query_style,
+            ))],
+            None => threads_state
+                .threads
+                .iter()
+                .map(|thread| {
+                    Spans::from(Span::styled(
+                        format!(
+                            "{:>8}  {:>6}  {:<12}  {}",
+                            thread.tid,
+                            format!("{:.1}%", thread.cpu_usage_percent),
+                            thread.state,
+                            thread.name
+                        ),
+                        self.colours.text_style,
+                    ))
+                })
+                .collect(),
+        };
+
+        // The header stays in place while the threads are scrolled.
+        let split_loc = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(inner_loc);
+
+        let scroll_state = &mut threads_state.scroll_state;
+        scroll_state.max_scroll_index =
+            (lines.len() as u16).saturating_sub(split_loc[1].height) + 1;
+        if scroll_state.current_scroll_index >= scroll_state.max_scroll_index {
+            scroll_state.current_scroll_index = scroll_state.max_scroll_index.saturating_sub(1);
+        }
+
+        f.render_widget(
+            Paragraph::new(Spans::from(Span::styled(
+                format!("{:>8}  {:>6}  {:<12}  {}", "TID", "CPU%", "State", "Name"),
+                self.colours.table_header_style,
+            ))),
+            split_loc[0],
+        );
+        f.render_widget(
+            Paragraph::new(lines)
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left)
+                .scroll((scroll_state.current_scroll_index, 0)),
+            split_loc[1],
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{app::App, canvas::Painter};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+impl Painter {
+    /// Draws the dialog that asks before restarting or stopping a process' systemd unit.
+    pub fn draw_unit_control_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let unit_control_state = &app_state.unit_control_state;
+        let (unit, action) = match &unit_control_state.pending {
+            Some(pending) => pending,
+            None => return,
+        };
+
+        let mut lines = vec![
+            Spans::from(vec![
+                Span::styled(
+                    format!(
+                        "{} the {} unit ",
+                        action.name(),
+                        if unit.is_user { "user" } else { "system" }
+                    ),
+                    self.colours.text_style,
+                ),
+                Span::styled(
+                    unit.name.clone(),
+                    self.colours.currently_selected_text_style,
+                ),
+                Span::styled("?", self.colours.text_style),
+            ]),
+            Spans::from(Span::styled(
+                format!("Press ENTER to {} it, or Esc to cancel.", action.verb()),
+                self.colours.text_style,
+            )),
+        ];
+        if let Some(error) = &unit_control_state.error {
+            lines.push(Spans::default());
+            lines.push(Spans::from(Span::styled(
+                error.clone(),
+                self.colours.invalid_query_style,
+            )));
+        }
+
+        let title = Spans::from(vec![
+            Span::styled(
+                format!(" {} unit ", action.name()),
+                self.colours.widget_title_style,
+            ),
+            Span::styled("─ Esc to close ", self.colours.border_style),
+        ]);
+
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.highlighted_border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left)
+                .wrap(Wrap { trim: false }),
+            draw_loc,
+        );
+    }
+}
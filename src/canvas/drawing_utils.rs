@@ -1,6 +1,8 @@
 use tui::layout::Rect;
 
 use crate::app::CursorDirection;
+use crate::components::time_graph::Point;
+use crate::data_conversion::{duration_string, get_compare_points, GraphStats};
 use std::{cmp::min, time::Instant};
 
 pub fn get_search_start_position(
@@ -48,6 +50,33 @@ pub fn calculate_basic_use_bars(use_percentage: f64, num_bars_available: usize)
     )
 }
 
+/// Returns horizontal lines at the max, mean, and min of a graph, from `time_start` to now,
+/// along with legend labels like `max 93%`.
+pub fn get_stats_lines(
+    stats: &GraphStats, time_start: f64, label_prefix: &str,
+) -> Vec<([Point; 2], String)> {
+    [("max", stats.max), ("avg", stats.mean), ("min", stats.min)]
+        .iter()
+        .map(|(name, value)| {
+            (
+                [(time_start, *value), (0.0, *value)],
+                format!("{}{} {:.0}%", label_prefix, name, value),
+            )
+        })
+        .collect()
+}
+
+/// Returns the points of a graph from `offset` milliseconds ago, lined up with the ones shown over
+/// the last `time_window` milliseconds, along with a legend label like `RAM 5m 0s ago`.
+pub fn get_compare_line(
+    points: &[Point], offset: u64, time_window: u64, label_prefix: &str,
+) -> (Vec<Point>, String) {
+    (
+        get_compare_points(points, offset, time_window),
+        format!("{}{} ago", label_prefix, duration_string(offset / 1000)),
+    )
+}
+
 /// Determine whether a graph x-label should be hidden.
 pub fn should_hide_x_label(
     always_hide_time: bool, autohide_time: bool, timer: &mut Option<Instant>, draw_loc: Rect,
@@ -68,6 +97,22 @@ pub fn should_hide_x_label(
     }
 }
 
+/// Moves a location in a layout that's scrolled down by `scroll` lines onto `viewport`, cutting off
+/// any part of it that's scrolled out of view. Locations that are entirely out of view are empty.
+pub fn scroll_loc(loc: Rect, viewport: Rect, scroll: u16) -> Rect {
+    let top = loc.y.max(scroll);
+    let bottom = (loc.y + loc.height).min(scroll.saturating_add(viewport.height));
+    if bottom <= top {
+        Rect::default()
+    } else {
+        Rect {
+            y: viewport.y + top - scroll,
+            height: bottom - top,
+            ..loc
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -87,6 +132,37 @@ mod test {
         assert_eq!(calculate_basic_use_bars(150.0, 15), 15);
     }
 
+    #[test]
+    fn test_scroll_loc() {
+        let viewport = Rect::new(0, 0, 80, 20);
+
+        // Not scrolled, so anything on the screen stays where it is.
+        assert_eq!(
+            scroll_loc(Rect::new(5, 3, 10, 10), viewport, 0),
+            Rect::new(5, 3, 10, 10)
+        );
+
+        // Partly scrolled off the top or bottom.
+        assert_eq!(
+            scroll_loc(Rect::new(5, 3, 10, 10), viewport, 8),
+            Rect::new(5, 0, 10, 5)
+        );
+        assert_eq!(
+            scroll_loc(Rect::new(5, 25, 10, 10), viewport, 8),
+            Rect::new(5, 17, 10, 3)
+        );
+
+        // Entirely out of view.
+        assert_eq!(
+            scroll_loc(Rect::new(5, 3, 10, 10), viewport, 13),
+            Rect::default()
+        );
+        assert_eq!(
+            scroll_loc(Rect::new(5, 30, 10, 10), viewport, 8),
+            Rect::default()
+        );
+    }
+
     #[test]
     fn test_should_hide_x_label() {
         use crate::constants::*;
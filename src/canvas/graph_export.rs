@@ -0,0 +1,578 @@
+//! Saving the graphs of a widget to image files, so they can be shared outside of the terminal.
+//!
+//! Graphs are saved as an SVG, which has the titles, labels, and legends, and as a PNG of just the
+//! lines and grid, for places that don't take SVGs. The SVG is written by hand, as is the PNG,
+//! other than compressing its pixels, which is done with `miniz_oxide`.
+
+use std::{
+    fmt::Write as _,
+    fs::{self, File, OpenOptions},
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    app::{layout_manager::BottomWidgetType, App},
+    canvas::{
+        graphics::{colour_to_rgb, GraphImage},
+        Painter,
+    },
+    data_conversion::timestamp_string,
+};
+
+/// The width of a saved image, in pixels.
+const IMAGE_WIDTH: usize = 1200;
+/// The height of each graph in a saved image, in pixels.
+const PANEL_HEIGHT: usize = 400;
+/// The space around the lines of each graph for its title, labels, and legend, in pixels.
+const MARGIN_LEFT: usize = 70;
+const MARGIN_RIGHT: usize = 20;
+const MARGIN_TOP: usize = 40;
+const MARGIN_BOTTOM: usize = 60;
+
+const BACKGROUND_COLOUR: (u8, u8, u8) = (30, 30, 30);
+const GRID_COLOUR: (u8, u8, u8) = (70, 70, 70);
+const TEXT_COLOUR: (u8, u8, u8) = (210, 210, 210);
+
+impl Painter {
+    /// Saves the graphs of the selected widget, as drawn in the last frame, to an SVG and a PNG
+    /// in the current directory. Returns a message saying where they were saved, or why they
+    /// weren't.
+    pub(super) fn export_selected_graph(&self, app_state: &App) -> String {
+        let current_widget = &app_state.current_widget;
+        let (widget_id, widget_type) = match current_widget.widget_type {
+            BottomWidgetType::CpuLegend => (current_widget.widget_id - 1, BottomWidgetType::Cpu),
+            BottomWidgetType::BasicCpu => (current_widget.widget_id, BottomWidgetType::Cpu),
+            BottomWidgetType::BasicMem => (current_widget.widget_id, BottomWidgetType::Mem),
+            BottomWidgetType::BasicNet => (current_widget.widget_id, BottomWidgetType::Net),
+            ref widget_type => (current_widget.widget_id, widget_type.clone()),
+        };
+
+        let bounds = app_state
+            .widget_map
+            .get(&widget_id)
+            .and_then(|widget| Some((widget.top_left_corner?, widget.bottom_right_corner?)));
+        let graph_images = self.graph_images.borrow();
+        let images = graph_images
+            .images
+            .iter()
+            .filter(|image| match bounds {
+                Some(((left, top), (right, bottom))) => {
+                    image.area.x >= left
+                        && image.area.y >= top
+                        && image.area.right() <= right
+                        && image.area.bottom() <= bottom
+                }
+                None => false,
+            })
+            .collect::<Vec<_>>();
+
+        if images.is_empty() {
+            return "The selected widget has no graph to save".to_string();
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let name = widget_type
+            .get_pretty_name()
+            .to_lowercase()
+            .replace(' ', "-");
+
+        let result = create_files(Path::new(""), &format!("bottom-{}-{}", name, now)).and_then(
+            |((svg_path, mut svg_file), (png_path, mut png_file))| {
+                svg_file.write_all(to_svg(&images, &timestamp_string(now)).as_bytes())?;
+                png_file.write_all(&to_png(&images))?;
+                Ok((svg_path, png_path))
+            },
+        );
+        match result {
+            Ok((svg_path, png_path)) => format!(
+                "Saved the graph to {} and {}",
+                svg_path.display(),
+                png_path.display()
+            ),
+            Err(err) => format!("Couldn't save the graph: {}", err),
+        }
+    }
+}
+
+/// Creates a new SVG and PNG in `dir` named `name`, never overwriting any files. If graphs were
+/// already saved with that name, like when saving twice in the same second, a number is added to
+/// it.
+fn create_files(dir: &Path, name: &str) -> io::Result<((PathBuf, File), (PathBuf, File))> {
+    let create_new = |path: &Path| OpenOptions::new().write(true).create_new(true).open(path);
+    let mut count = 0;
+
+    loop {
+        let stem = if count == 0 {
+            name.to_string()
+        } else {
+            format!("{}-{}", name, count)
+        };
+        count += 1;
+
+        let svg_path = dir.join(format!("{}.svg", stem));
+        let svg_file = match create_new(&svg_path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        };
+        let png_path = dir.join(format!("{}.png", stem));
+        match create_new(&png_path) {
+            Ok(png_file) => return Ok(((svg_path, svg_file), (png_path, png_file))),
+            Err(err) => {
+                let _ = fs::remove_file(&svg_path);
+                if err.kind() != io::ErrorKind::AlreadyExists {
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
+
+/// Where the lines of a graph go in its panel, as the left, top, width, and height in pixels.
+fn plot_area() -> (usize, usize, usize, usize) {
+    (
+        MARGIN_LEFT,
+        MARGIN_TOP,
+        IMAGE_WIDTH - MARGIN_LEFT - MARGIN_RIGHT,
+        PANEL_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM,
+    )
+}
+
+/// Returns how far down the plot area each y-axis label goes, spread evenly from the bottom up.
+fn y_label_offsets(num_labels: usize, plot_height: usize) -> Vec<usize> {
+    match num_labels {
+        0 => vec![],
+        1 => vec![plot_height],
+        _ => (0..num_labels)
+            .map(|index| plot_height - index * plot_height / (num_labels - 1))
+            .collect(),
+    }
+}
+
+/// Returns the labels for the start and end of the x-axis, like `60s` and `0s`.
+fn x_labels(image: &GraphImage) -> (String, String) {
+    let label = |bound: f64| format!("{}s", (-bound / 1000.0).round().max(0.0) as u64);
+    (label(image.x_bounds[0]), label(image.x_bounds[1]))
+}
+
+fn rgb_string((red, green, blue): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", red, green, blue)
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Returns the graphs as an SVG, one below the other.
+fn to_svg(images: &[&GraphImage], timestamp: &str) -> String {
+    let (plot_left, plot_top, plot_width, plot_height) = plot_area();
+    let height = PANEL_HEIGHT * images.len();
+    let text_colour = rgb_string(TEXT_COLOUR);
+    let grid_colour = rgb_string(GRID_COLOUR);
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"13\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        rgb_string(BACKGROUND_COLOUR),
+        width = IMAGE_WIDTH,
+        height = height,
+    );
+
+    for (index, image) in images.iter().enumerate() {
+        let top = index * PANEL_HEIGHT + plot_top;
+        let bottom = top + plot_height;
+        let right = plot_left + plot_width;
+
+        let _ = writeln!(
+            svg,
+            "<text x=\"{}\" y=\"{}\" fill=\"{}\" font-size=\"16\">{}</text>",
+            plot_left,
+            top - 15,
+            text_colour,
+            escape_xml(&image.title)
+        );
+        if index == 0 {
+            let _ = writeln!(
+                svg,
+                "<text x=\"{}\" y=\"{}\" fill=\"{}\" text-anchor=\"end\">{}</text>",
+                right,
+                top - 15,
+                text_colour,
+                escape_xml(timestamp)
+            );
+        }
+
+        for (label, offset) in image
+            .y_labels
+            .iter()
+            .zip(y_label_offsets(image.y_labels.len(), plot_height))
+        {
+            let y = top + offset;
+            let _ = writeln!(
+                svg,
+                "<line x1=\"{}\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\" stroke=\"{}\"/>\n\
+                 <text x=\"{}\" y=\"{}\" fill=\"{}\" text-anchor=\"end\">{}</text>",
+                plot_left,
+                right,
+                grid_colour,
+                plot_left - 8,
+                y + 4,
+                text_colour,
+                escape_xml(label.trim()),
+                y = y,
+            );
+        }
+
+        let (start_label, end_label) = x_labels(image);
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"{}\"/>\n\
+             <text x=\"{}\" y=\"{}\" fill=\"{}\">{}</text>\n\
+             <text x=\"{}\" y=\"{}\" fill=\"{}\" text-anchor=\"end\">{}</text>",
+            plot_left,
+            top,
+            plot_width,
+            plot_height,
+            grid_colour,
+            plot_left,
+            bottom + 18,
+            text_colour,
+            start_label,
+            right,
+            bottom + 18,
+            text_colour,
+            end_label,
+        );
+
+        let _ = writeln!(
+            svg,
+            "<clipPath id=\"plot{index}\"><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/></clipPath>\n\
+             <g clip-path=\"url(#plot{index})\" fill=\"none\" stroke-width=\"1.5\" stroke-linejoin=\"round\">",
+            plot_left,
+            top,
+            plot_width,
+            plot_height,
+            index = index,
+        );
+        let x_span = image.x_bounds[1] - image.x_bounds[0];
+        let y_span = image.y_bounds[1] - image.y_bounds[0];
+        if x_span > 0.0 && y_span > 0.0 {
+            for line in image.lines.iter().filter(|line| !line.points.is_empty()) {
+                let points = line
+                    .points
+                    .iter()
+                    .map(|(x, y)| {
+                        format!(
+                            "{:.1},{:.1}",
+                            plot_left as f64 + (x - image.x_bounds[0]) / x_span * plot_width as f64,
+                            top as f64
+                                + (1.0 - (y - image.y_bounds[0]) / y_span) * plot_height as f64
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let _ = writeln!(
+                    svg,
+                    "<polyline points=\"{}\" stroke=\"{}\"/>",
+                    points,
+                    rgb_string(colour_to_rgb(line.colour))
+                );
+            }
+        }
+        let _ = writeln!(svg, "</g>");
+
+        // The legend goes along the bottom, with a swatch of each line's colour.
+        let mut x = plot_left;
+        for line in &image.lines {
+            if let Some(name) = line.name.as_deref().filter(|name| !name.is_empty()) {
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"12\" height=\"4\" fill=\"{}\"/>\n\
+                     <text x=\"{}\" y=\"{}\" fill=\"{}\">{}</text>",
+                    x,
+                    bottom + 36,
+                    rgb_string(colour_to_rgb(line.colour)),
+                    x + 18,
+                    bottom + 42,
+                    text_colour,
+                    escape_xml(name)
+                );
+                x += 18 + name.chars().count() * 8 + 20;
+            }
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Returns the graphs as a PNG, one below the other. There's no text, so only the lines, the
+/// border of each graph, and a grid line for each y-axis label are drawn.
+fn to_png(images: &[&GraphImage]) -> Vec<u8> {
+    let (plot_left, plot_top, plot_width, plot_height) = plot_area();
+    let height = PANEL_HEIGHT * images.len();
+
+    let mut rgb = Vec::with_capacity(IMAGE_WIDTH * height * 3);
+    for _ in 0..IMAGE_WIDTH * height {
+        rgb.extend_from_slice(&[
+            BACKGROUND_COLOUR.0,
+            BACKGROUND_COLOUR.1,
+            BACKGROUND_COLOUR.2,
+        ]);
+    }
+
+    for (index, image) in images.iter().enumerate() {
+        let top = index * PANEL_HEIGHT + plot_top;
+        let bottom = top + plot_height;
+        let right = plot_left + plot_width;
+
+        let mut grid_rows = y_label_offsets(image.y_labels.len(), plot_height)
+            .into_iter()
+            .map(|offset| top + offset)
+            .collect::<Vec<_>>();
+        grid_rows.extend([top, bottom]);
+        for y in grid_rows {
+            for x in plot_left..=right {
+                set_pixel(&mut rgb, x, y, GRID_COLOUR);
+            }
+        }
+        for y in top..=bottom {
+            set_pixel(&mut rgb, plot_left, y, GRID_COLOUR);
+            set_pixel(&mut rgb, right, y, GRID_COLOUR);
+        }
+
+        let lines = image.rasterize_lines(plot_width, plot_height, 0.75);
+        for (pixel_index, pixel) in lines.rgba.chunks_exact(4).enumerate() {
+            if pixel[3] == 0 {
+                continue;
+            }
+            let x = plot_left + pixel_index % plot_width;
+            let y = top + pixel_index / plot_width;
+            let alpha = f64::from(pixel[3]) / 255.0;
+            let index = (y * IMAGE_WIDTH + x) * 3;
+            for (channel, value) in rgb[index..index + 3].iter_mut().zip(&pixel[..3]) {
+                *channel =
+                    (f64::from(*value) * alpha + f64::from(*channel) * (1.0 - alpha)).round() as u8;
+            }
+        }
+    }
+
+    encode_png(IMAGE_WIDTH, height, &rgb)
+}
+
+fn set_pixel(rgb: &mut [u8], x: usize, y: usize, (red, green, blue): (u8, u8, u8)) {
+    let index = (y * IMAGE_WIDTH + x) * 3;
+    rgb[index..index + 3].copy_from_slice(&[red, green, blue]);
+}
+
+/// Encodes 8-bit RGB pixels as a PNG.
+fn encode_png(width: usize, height: usize, rgb: &[u8]) -> Vec<u8> {
+    // Each row starts with its filter type, which is always none.
+    let mut raw = Vec::with_capacity((width * 3 + 1) * height);
+    for row in rgb.chunks_exact(width * 3) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    // 8 bits per channel, RGB, and the default compression, filtering, and no interlacing.
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_png_chunk(&mut png, b"IHDR", &header);
+    write_png_chunk(
+        &mut png,
+        b"IDAT",
+        &miniz_oxide::deflate::compress_to_vec_zlib(&raw, 6),
+    );
+    write_png_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryInto;
+
+    use tui::{layout::Rect, style::Color};
+
+    use super::*;
+    use crate::canvas::graphics::GraphLine;
+
+    /// Decodes a PNG written by [`encode_png`] into its width, height, and RGB pixels, checking
+    /// the CRC of each chunk.
+    fn decode_png(png: &[u8]) -> (usize, usize, Vec<u8>) {
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let (mut width, mut height, mut compressed) = (0, 0, Vec::new());
+        let mut rest = &png[8..];
+        while !rest.is_empty() {
+            let length = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let (chunk, crc) = rest[4..8 + length + 4].split_at(4 + length);
+            assert_eq!(crc32(chunk), u32::from_be_bytes(crc.try_into().unwrap()));
+            let (kind, data) = chunk.split_at(4);
+            match kind {
+                b"IHDR" => {
+                    width = u32::from_be_bytes(data[..4].try_into().unwrap()) as usize;
+                    height = u32::from_be_bytes(data[4..8].try_into().unwrap()) as usize;
+                    assert_eq!(&data[8..], &[8, 2, 0, 0, 0]);
+                }
+                b"IDAT" => compressed.extend_from_slice(data),
+                _ => {}
+            }
+            rest = &rest[8 + length + 4..];
+        }
+
+        let raw = miniz_oxide::inflate::decompress_to_vec_zlib(&compressed).unwrap();
+        let mut rgb = Vec::with_capacity(width * height * 3);
+        for row in raw.chunks_exact(width * 3 + 1) {
+            assert_eq!(row[0], 0);
+            rgb.extend_from_slice(&row[1..]);
+        }
+        (width, height, rgb)
+    }
+
+    #[test]
+    fn test_create_files() {
+        let dir = std::env::temp_dir().join(format!("btm_graph_export_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("graph-1.png"), "").unwrap();
+
+        let get_names = |((svg_path, _), (png_path, _)): ((PathBuf, File), (PathBuf, File))| {
+            (
+                svg_path.file_name().unwrap().to_owned(),
+                png_path.file_name().unwrap().to_owned(),
+            )
+        };
+        assert_eq!(
+            get_names(create_files(&dir, "graph").unwrap()),
+            ("graph.svg".into(), "graph.png".into())
+        );
+        // The first name is taken, and the second has a PNG already, which is left alone.
+        assert_eq!(
+            get_names(create_files(&dir, "graph").unwrap()),
+            ("graph-2.svg".into(), "graph-2.png".into())
+        );
+        assert!(!dir.join("graph-1.svg").exists());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 5);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+    }
+
+    #[test]
+    fn test_encode_png() {
+        let rgb = (0..4 * 3 * 3).map(|value| value as u8).collect::<Vec<_>>();
+        assert_eq!(decode_png(&encode_png(4, 3, &rgb)), (4, 3, rgb));
+    }
+
+    #[test]
+    fn test_y_label_offsets() {
+        assert_eq!(y_label_offsets(0, 100), Vec::<usize>::new());
+        assert_eq!(y_label_offsets(1, 100), vec![100]);
+        assert_eq!(y_label_offsets(3, 100), vec![100, 50, 0]);
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let image = GraphImage {
+            title: "CPU <all>".to_string(),
+            area: Rect::new(0, 0, 10, 5),
+            legend_area: None,
+            x_bounds: [-60000.0, 0.0],
+            y_bounds: [0.0, 100.0],
+            y_labels: vec!["0%".to_string(), "100%".to_string()],
+            lines: vec![GraphLine {
+                name: Some("AVG".to_string()),
+                points: vec![(-60000.0, 0.0), (0.0, 100.0)],
+                colour: Color::Rgb(255, 0, 0),
+            }],
+        };
+        let svg = to_svg(&[&image], "2022-05-01 14:03");
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(">CPU &lt;all&gt;</text>"));
+        assert!(svg.contains(">60s</text>"));
+        assert!(svg.contains(">0s</text>"));
+        assert!(svg.contains(">100%</text>"));
+        assert!(svg.contains(">AVG</text>"));
+        assert!(svg.contains("<polyline points=\"70.0,340.0 1180.0,40.0\" stroke=\"#ff0000\"/>"));
+    }
+
+    #[test]
+    fn test_to_png() {
+        let image = GraphImage {
+            title: "Memory".to_string(),
+            area: Rect::new(0, 0, 10, 5),
+            legend_area: None,
+            x_bounds: [-60000.0, 0.0],
+            y_bounds: [0.0, 100.0],
+            y_labels: vec!["0%".to_string(), "100%".to_string()],
+            lines: vec![GraphLine {
+                name: None,
+                points: vec![(-60000.0, 50.0), (0.0, 50.0)],
+                colour: Color::Rgb(255, 0, 0),
+            }],
+        };
+        let (width, height, rgb) = decode_png(&to_png(&[&image, &image]));
+        assert_eq!((width, height), (IMAGE_WIDTH, PANEL_HEIGHT * 2));
+
+        let (plot_left, plot_top, plot_width, plot_height) = plot_area();
+        let get_pixel = |x: usize, y: usize| {
+            let index = (y * width + x) * 3;
+            (rgb[index], rgb[index + 1], rgb[index + 2])
+        };
+        for top in [plot_top, PANEL_HEIGHT + plot_top] {
+            assert_eq!(get_pixel(0, top), BACKGROUND_COLOUR);
+            assert_eq!(get_pixel(plot_left, top), GRID_COLOUR);
+            assert_eq!(
+                get_pixel(plot_left + plot_width, top + plot_height),
+                GRID_COLOUR
+            );
+            // The line is antialiased, so it's blended with the background.
+            let (red, green, blue) = get_pixel(plot_left + plot_width / 2, top + plot_height / 2);
+            assert!(red > 150 && green < BACKGROUND_COLOUR.1 && blue < BACKGROUND_COLOUR.2);
+        }
+    }
+}
@@ -0,0 +1,746 @@
+//! Drawing graphs as high-resolution images, with the Kitty graphics protocol or Sixel.
+//!
+//! Graphs are drawn as usual, except that their lines are left out of the text and collected as
+//! [`GraphImage`]s instead. Once the rest of the frame is drawn, each one is rasterized with
+//! anti-aliasing and written over the space that was left for it.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    io::Write,
+    str::FromStr,
+};
+
+use tui::{layout::Rect, style::Color};
+
+use crate::{
+    components::time_graph::Point,
+    utils::{
+        error::{self, BottomError},
+        gen_util::base64_encode,
+    },
+};
+
+/// The image ID that Kitty images start from, so they don't clash with other programs' images.
+const KITTY_FIRST_IMAGE_ID: usize = 7100;
+
+/// How graphs are drawn, as set in the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphRenderer {
+    /// With braille or dot characters.
+    Text,
+    /// As images if the terminal supports them, and as text otherwise. This is resolved when the
+    /// [`super::Painter`] is created.
+    Auto,
+    Kitty,
+    Sixel,
+}
+
+impl FromStr for GraphRenderer {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> error::Result<Self> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(GraphRenderer::Text),
+            "auto" => Ok(GraphRenderer::Auto),
+            "kitty" => Ok(GraphRenderer::Kitty),
+            "sixel" => Ok(GraphRenderer::Sixel),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid graph renderer, use one of text, auto, kitty, or sixel.",
+                s
+            ))),
+        }
+    }
+}
+
+/// A way of drawing images in the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Sixel,
+}
+
+impl GraphRenderer {
+    /// Returns the protocol to draw graphs with, asking the terminal what it supports for
+    /// [`GraphRenderer::Auto`]. This must be called before raw mode is enabled.
+    pub fn get_protocol(self) -> Option<GraphicsProtocol> {
+        match self {
+            GraphRenderer::Text => None,
+            GraphRenderer::Auto => {
+                // A graphics query for a 1x1 image, which only Kitty-compatible terminals answer.
+                let response = super::background::query_terminal(
+                    b"\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\",
+                )?;
+                parse_protocol_response(&response)
+            }
+            GraphRenderer::Kitty => Some(GraphicsProtocol::Kitty),
+            GraphRenderer::Sixel => Some(GraphicsProtocol::Sixel),
+        }
+    }
+}
+
+/// Picks a protocol from the answers to a Kitty graphics query and a DA1 query. Terminals that
+/// support Sixel list attribute 4 in their DA1 answer, like `ESC [ ? 62 ; 4 ; 22 c`.
+fn parse_protocol_response(response: &str) -> Option<GraphicsProtocol> {
+    if response.contains("\x1b_Gi=31;OK") {
+        return Some(GraphicsProtocol::Kitty);
+    }
+
+    let start = response.rfind("\x1b[?")? + "\x1b[?".len();
+    let attributes = response[start..].strip_suffix('c')?;
+    if attributes
+        .split(';')
+        .skip(1)
+        .any(|attribute| attribute == "4")
+    {
+        Some(GraphicsProtocol::Sixel)
+    } else {
+        None
+    }
+}
+
+/// Returns the size of a cell in pixels, if the terminal reports it.
+#[cfg(target_family = "unix")]
+pub fn get_cell_size() -> Option<(u16, u16)> {
+    let mut size = libc::winsize {
+        ws_row: 0,
+        ws_col: 0,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } != 0
+        || size.ws_col == 0
+        || size.ws_row == 0
+    {
+        return None;
+    }
+
+    match (size.ws_xpixel / size.ws_col, size.ws_ypixel / size.ws_row) {
+        (0, _) | (_, 0) => None,
+        cell_size => Some(cell_size),
+    }
+}
+
+#[cfg(not(target_family = "unix"))]
+pub fn get_cell_size() -> Option<(u16, u16)> {
+    None
+}
+
+/// The graphs drawn in a frame, collected to be drawn as images or saved to a file.
+#[derive(Debug, Default)]
+pub struct GraphImages {
+    /// Whether the graphs are drawn as images, so their lines are left out of the text.
+    pub replace_text: bool,
+    pub images: Vec<GraphImage>,
+}
+
+/// The lines of a graph, to be drawn as an image.
+#[derive(Debug, Clone)]
+pub struct GraphImage {
+    pub title: String,
+    /// Where the lines go, in cells.
+    pub area: Rect,
+    /// Where the graph's legend is, in cells. This is left clear so it can still be read.
+    pub legend_area: Option<Rect>,
+    /// The shown time range, in milliseconds before now as negative numbers.
+    pub x_bounds: [f64; 2],
+    pub y_bounds: [f64; 2],
+    /// The labels of the y-axis, from bottom to top.
+    pub y_labels: Vec<String>,
+    pub lines: Vec<GraphLine>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GraphLine {
+    pub name: Option<String>,
+    pub points: Vec<Point>,
+    pub colour: Color,
+}
+
+/// A rasterized [`GraphImage`], as straight RGBA.
+pub(super) struct Pixels {
+    pub(super) width: usize,
+    pub(super) height: usize,
+    pub(super) rgba: Vec<u8>,
+}
+
+impl Pixels {
+    /// Returns the part of the image in the given rectangle, which must be inside it.
+    fn crop(&self, left: usize, top: usize, width: usize, height: usize) -> Pixels {
+        let rgba = (top..top + height)
+            .flat_map(|y| {
+                let start = (y * self.width + left) * 4;
+                self.rgba[start..start + width * 4].iter().copied()
+            })
+            .collect();
+
+        Pixels {
+            width,
+            height,
+            rgba,
+        }
+    }
+}
+
+impl GraphImage {
+    /// Draws the lines to go over the graph's cells, leaving the legend clear.
+    fn rasterize(&self, (cell_width, cell_height): (u16, u16)) -> Pixels {
+        let width = usize::from(self.area.width) * usize::from(cell_width);
+        let height = usize::from(self.area.height) * usize::from(cell_height);
+        let mut pixels =
+            self.rasterize_lines(width, height, (f64::from(cell_height) / 12.0).max(0.75));
+
+        if let Some(legend_area) = self.legend_area {
+            let left =
+                usize::from(legend_area.x.saturating_sub(self.area.x)) * usize::from(cell_width);
+            let top =
+                usize::from(legend_area.y.saturating_sub(self.area.y)) * usize::from(cell_height);
+            let right =
+                (left + usize::from(legend_area.width) * usize::from(cell_width)).min(width);
+            let bottom =
+                (top + usize::from(legend_area.height) * usize::from(cell_height)).min(height);
+            for y in top..bottom {
+                for x in left..right {
+                    pixels.rgba[(y * width + x) * 4 + 3] = 0;
+                }
+            }
+        }
+
+        pixels
+    }
+
+    /// Draws the lines on a transparent image of the given size.
+    pub(super) fn rasterize_lines(
+        &self, width: usize, height: usize, half_thickness: f64,
+    ) -> Pixels {
+        let mut rgba = vec![0_u8; width * height * 4];
+
+        let x_span = self.x_bounds[1] - self.x_bounds[0];
+        let y_span = self.y_bounds[1] - self.y_bounds[0];
+        if width == 0 || height == 0 || x_span <= 0.0 || y_span <= 0.0 {
+            return Pixels {
+                width,
+                height,
+                rgba,
+            };
+        }
+
+        let to_pixel = |(x, y): Point| {
+            (
+                (x - self.x_bounds[0]) / x_span * width as f64,
+                (1.0 - (y - self.y_bounds[0]) / y_span) * height as f64,
+            )
+        };
+
+        let mut coverage = vec![0.0_f64; width * height];
+        for line in &self.lines {
+            coverage.iter_mut().for_each(|value| *value = 0.0);
+
+            let pixel_points = line
+                .points
+                .iter()
+                .map(|point| to_pixel(*point))
+                .collect::<Vec<_>>();
+            // A lone point is drawn as a dot.
+            let segments: Vec<_> = if pixel_points.len() <= 1 {
+                pixel_points.iter().map(|point| (*point, *point)).collect()
+            } else {
+                pixel_points
+                    .windows(2)
+                    .map(|pair| (pair[0], pair[1]))
+                    .collect()
+            };
+
+            for (start, end) in segments {
+                if (start.0 < -half_thickness && end.0 < -half_thickness)
+                    || (start.0 > width as f64 + half_thickness
+                        && end.0 > width as f64 + half_thickness)
+                {
+                    continue;
+                }
+                draw_segment(&mut coverage, width, height, start, end, half_thickness);
+            }
+
+            // Blend each line over the ones before it, so where lines cross, the newer one is on
+            // top.
+            let (red, green, blue) = colour_to_rgb(line.colour);
+            for (pixel, alpha) in rgba.chunks_exact_mut(4).zip(&coverage) {
+                if *alpha <= 0.0 {
+                    continue;
+                }
+                let old_alpha = f64::from(pixel[3]) / 255.0;
+                let new_alpha = alpha + old_alpha * (1.0 - alpha);
+                for (channel, value) in pixel.iter_mut().zip([red, green, blue]) {
+                    let blended = (f64::from(value) * alpha
+                        + f64::from(*channel) * old_alpha * (1.0 - alpha))
+                        / new_alpha;
+                    *channel = blended.round() as u8;
+                }
+                pixel[3] = (new_alpha * 255.0).round() as u8;
+            }
+        }
+
+        Pixels {
+            width,
+            height,
+            rgba,
+        }
+    }
+}
+
+/// Adds a line from `start` to `end` to `coverage`, where each pixel is covered by how close its
+/// centre is to the line, for smooth edges.
+fn draw_segment(
+    coverage: &mut [f64], width: usize, height: usize, start: (f64, f64), end: (f64, f64),
+    half_thickness: f64,
+) {
+    let reach = half_thickness + 1.0;
+    let clamp = |value: f64, max: usize| value.max(0.0).min(max as f64) as usize;
+    let left = clamp(start.0.min(end.0) - reach, width);
+    let right = clamp(start.0.max(end.0) + reach, width);
+    let top = clamp(start.1.min(end.1) - reach, height);
+    let bottom = clamp(start.1.max(end.1) + reach, height);
+
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length_squared = dx * dx + dy * dy;
+
+    for y in top..bottom {
+        for x in left..right {
+            let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+            let t = if length_squared > 0.0 {
+                (((px - start.0) * dx + (py - start.1) * dy) / length_squared).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let distance = (px - (start.0 + t * dx)).hypot(py - (start.1 + t * dy));
+            let value = (half_thickness + 0.5 - distance).clamp(0.0, 1.0);
+
+            let pixel = &mut coverage[y * width + x];
+            *pixel = pixel.max(value);
+        }
+    }
+}
+
+/// Returns the RGB value of a colour. The 16 named colours use xterm's defaults, as there's no
+/// way to know what the terminal actually uses for them.
+pub(super) fn colour_to_rgb(colour: Color) -> (u8, u8, u8) {
+    const ANSI: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+
+    let index = match colour {
+        Color::Rgb(red, green, blue) => return (red, green, blue),
+        Color::Indexed(index) => index,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White | Color::Reset => 15,
+    };
+
+    match index {
+        0..=15 => ANSI[usize::from(index)],
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let cube = index - 16;
+            (level(cube / 36), level(cube / 6 % 6), level(cube % 6))
+        }
+        _ => {
+            let grey = 8 + (index - 232) * 10;
+            (grey, grey, grey)
+        }
+    }
+}
+
+/// Draws graph images on the terminal, keeping track of what's already shown so unchanged Kitty
+/// images aren't sent again.
+#[derive(Debug, Default)]
+pub struct GraphImageWriter {
+    /// Where each shown Kitty image is, and a hash of its pixels. Each one's ID is its index plus
+    /// [`KITTY_FIRST_IMAGE_ID`].
+    shown_kitty_images: Vec<(Rect, u64)>,
+}
+
+impl GraphImageWriter {
+    /// Forgets what's shown, so every image is sent again, like after the screen is cleared.
+    pub fn reset(&mut self) {
+        self.shown_kitty_images.clear();
+    }
+
+    /// Writes the images of a frame, and removes any Kitty images from the last frame that are
+    /// gone.
+    pub fn write_images<W: Write>(
+        &mut self, out: &mut W, protocol: GraphicsProtocol, images: &[GraphImage],
+        cell_size: (u16, u16),
+    ) -> std::io::Result<()> {
+        let mut shown_kitty_images = Vec::with_capacity(images.len());
+        for (index, image) in images.iter().enumerate() {
+            let pixels = image.rasterize(cell_size);
+            if pixels.width == 0 || pixels.height == 0 {
+                shown_kitty_images.push((image.area, 0));
+                continue;
+            }
+
+            match protocol {
+                GraphicsProtocol::Kitty => {
+                    let mut hasher = DefaultHasher::new();
+                    pixels.rgba.hash(&mut hasher);
+                    let shown_image = (image.area, hasher.finish());
+
+                    if self.shown_kitty_images.get(index) != Some(&shown_image) {
+                        let id = KITTY_FIRST_IMAGE_ID + index;
+                        write!(out, "\x1b_Ga=d,d=I,i={},q=2\x1b\\", id)?;
+                        move_to(out, image.area)?;
+                        write_kitty_image(out, &pixels, id)?;
+                    }
+                    shown_kitty_images.push(shown_image);
+                }
+                GraphicsProtocol::Sixel => {
+                    for region in sixel_regions(image) {
+                        let left = usize::from(region.x - image.area.x) * usize::from(cell_size.0);
+                        let top = usize::from(region.y - image.area.y) * usize::from(cell_size.1);
+                        let cropped = pixels.crop(
+                            left,
+                            top,
+                            usize::from(region.width) * usize::from(cell_size.0),
+                            usize::from(region.height) * usize::from(cell_size.1),
+                        );
+
+                        move_to(out, region)?;
+                        write_sixel_image(out, &cropped, image)?;
+                    }
+                }
+            }
+        }
+
+        for index in shown_kitty_images.len()..self.shown_kitty_images.len() {
+            write!(
+                out,
+                "\x1b_Ga=d,d=I,i={},q=2\x1b\\",
+                KITTY_FIRST_IMAGE_ID + index
+            )?;
+        }
+        self.shown_kitty_images = shown_kitty_images;
+
+        out.flush()
+    }
+}
+
+/// Moves the cursor to the top left of `area`.
+fn move_to<W: Write>(out: &mut W, area: Rect) -> std::io::Result<()> {
+    write!(out, "\x1b[{};{}H", area.y + 1, area.x + 1)
+}
+
+/// Writes an image with the Kitty graphics protocol, in chunks of base64-encoded RGBA. The cursor
+/// isn't moved, and the terminal is asked not to answer.
+fn write_kitty_image<W: Write>(out: &mut W, pixels: &Pixels, id: usize) -> std::io::Result<()> {
+    const CHUNK_SIZE: usize = 4096;
+
+    let encoded = base64_encode(&pixels.rgba);
+    let chunks = encoded.as_bytes().chunks(CHUNK_SIZE);
+    let num_chunks = chunks.len();
+    for (index, chunk) in chunks.enumerate() {
+        let more = if index + 1 < num_chunks { 1 } else { 0 };
+        if index == 0 {
+            write!(
+                out,
+                "\x1b_Ga=T,f=32,s={},v={},i={},q=2,C=1,m={};",
+                pixels.width, pixels.height, id, more
+            )?;
+        } else {
+            write!(out, "\x1b_Gm={};", more)?;
+        }
+        out.write_all(chunk)?;
+        out.write_all(b"\x1b\\")?;
+    }
+
+    Ok(())
+}
+
+/// Returns the parts of a graph to draw with Sixel. Sixel images cover the text under them, so
+/// the graph is split around its legend, which is in its top right corner.
+fn sixel_regions(image: &GraphImage) -> Vec<Rect> {
+    let area = image.area;
+    match image
+        .legend_area
+        .map(|legend_area| legend_area.intersection(area))
+    {
+        Some(legend_area) if legend_area.width > 0 && legend_area.height > 0 => {
+            let top_height = legend_area.bottom() - area.y;
+            vec![
+                Rect::new(area.x, area.y, legend_area.x - area.x, top_height),
+                Rect::new(
+                    area.x,
+                    legend_area.bottom(),
+                    area.width,
+                    area.height - top_height,
+                ),
+            ]
+            .into_iter()
+            .filter(|region| region.width > 0 && region.height > 0)
+            .collect()
+        }
+        _ => vec![area],
+    }
+}
+
+/// Writes an image with Sixel. Sixel has no partial transparency, so each pixel that's at least
+/// half covered gets the colour of the line it belongs to, and the rest are left transparent.
+fn write_sixel_image<W: Write>(
+    out: &mut W, pixels: &Pixels, image: &GraphImage,
+) -> std::io::Result<()> {
+    let palette = image
+        .lines
+        .iter()
+        .map(|line| colour_to_rgb(line.colour))
+        .collect::<Vec<_>>();
+    if palette.is_empty() {
+        return Ok(());
+    }
+
+    // The palette index of each pixel, if it's drawn.
+    let indices = pixels
+        .rgba
+        .chunks_exact(4)
+        .map(|pixel| {
+            if pixel[3] < 128 {
+                return None;
+            }
+            palette
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (red, green, blue))| {
+                    let distance = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+                    distance(pixel[0], *red)
+                        + distance(pixel[1], *green)
+                        + distance(pixel[2], *blue)
+                })
+                .map(|(index, _)| index)
+        })
+        .collect::<Vec<_>>();
+
+    // Pixels that aren't drawn are filled with the background colour, so the last frame's image
+    // is erased.
+    write!(out, "\x1bP0;0;0q\"1;1;{};{}", pixels.width, pixels.height)?;
+    for (index, (red, green, blue)) in palette.iter().enumerate() {
+        let percent = |value: u8| u32::from(value) * 100 / 255;
+        write!(
+            out,
+            "#{};2;{};{};{}",
+            index,
+            percent(*red),
+            percent(*green),
+            percent(*blue)
+        )?;
+    }
+
+    // Each band is six rows of pixels, drawn once for each colour.
+    for band_top in (0..pixels.height).step_by(6) {
+        for colour_index in 0..palette.len() {
+            let sixels = (0..pixels.width)
+                .map(|x| {
+                    (0..6)
+                        .filter(|row| {
+                            let y = band_top + row;
+                            y < pixels.height && indices[y * pixels.width + x] == Some(colour_index)
+                        })
+                        .fold(0_u8, |bits, row| bits | (1 << row))
+                })
+                .collect::<Vec<_>>();
+            if sixels.iter().all(|bits| *bits == 0) {
+                continue;
+            }
+
+            write!(out, "#{}", colour_index)?;
+            for (bits, run) in group_runs(&sixels) {
+                let symbol = char::from(63 + bits);
+                if run > 3 {
+                    write!(out, "!{}{}", run, symbol)?;
+                } else {
+                    for _ in 0..run {
+                        write!(out, "{}", symbol)?;
+                    }
+                }
+            }
+            out.write_all(b"$")?;
+        }
+        out.write_all(b"-")?;
+    }
+
+    out.write_all(b"\x1b\\")
+}
+
+/// Groups repeated bytes into runs of each byte and how many times it repeats.
+fn group_runs(bytes: &[u8]) -> Vec<(u8, usize)> {
+    let mut runs: Vec<(u8, usize)> = Vec::new();
+    for byte in bytes {
+        match runs.last_mut() {
+            Some((last, count)) if last == byte => *count += 1,
+            _ => runs.push((*byte, 1)),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_graph_image(
+        area: Rect, legend_area: Option<Rect>, points: Vec<Point>, colour: Color,
+    ) -> GraphImage {
+        GraphImage {
+            title: String::new(),
+            area,
+            legend_area,
+            x_bounds: [0.0, f64::from(area.width)],
+            y_bounds: [0.0, f64::from(area.height)],
+            y_labels: vec![],
+            lines: vec![GraphLine {
+                name: None,
+                points,
+                colour,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_parse_protocol_response() {
+        assert_eq!(
+            parse_protocol_response("\x1b_Gi=31;OK\x1b\\\x1b[?62;22c"),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            parse_protocol_response("\x1b[?62;4;22c"),
+            Some(GraphicsProtocol::Sixel)
+        );
+        assert_eq!(parse_protocol_response("\x1b[?4;22c"), None);
+        assert_eq!(parse_protocol_response("\x1b[?62;22c"), None);
+        assert_eq!(parse_protocol_response(""), None);
+    }
+
+    #[test]
+    fn test_group_runs() {
+        assert_eq!(group_runs(&[]), vec![]);
+        assert_eq!(group_runs(&[1, 1, 1, 2, 1]), vec![(1, 3), (2, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn test_colour_to_rgb() {
+        assert_eq!(colour_to_rgb(Color::Rgb(1, 2, 3)), (1, 2, 3));
+        assert_eq!(colour_to_rgb(Color::LightRed), (255, 0, 0));
+        assert_eq!(colour_to_rgb(Color::Indexed(16)), (0, 0, 0));
+        assert_eq!(colour_to_rgb(Color::Indexed(231)), (255, 255, 255));
+        assert_eq!(colour_to_rgb(Color::Indexed(232)), (8, 8, 8));
+    }
+
+    #[test]
+    fn test_sixel_regions() {
+        let mut image = create_graph_image(
+            Rect::new(2, 1, 10, 8),
+            Some(Rect::new(8, 1, 4, 3)),
+            vec![],
+            Color::Red,
+        );
+        assert_eq!(
+            sixel_regions(&image),
+            vec![Rect::new(2, 1, 6, 3), Rect::new(2, 4, 10, 5)]
+        );
+
+        image.legend_area = None;
+        assert_eq!(sixel_regions(&image), vec![Rect::new(2, 1, 10, 8)]);
+    }
+
+    #[test]
+    fn test_write_images() {
+        let images = vec![create_graph_image(
+            Rect::new(1, 1, 4, 2),
+            None,
+            vec![(0.0, 0.0), (4.0, 2.0)],
+            Color::Green,
+        )];
+
+        let mut writer = GraphImageWriter::default();
+        let mut out = Vec::new();
+        writer
+            .write_images(&mut out, GraphicsProtocol::Kitty, &images, (2, 4))
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("\x1b[2;2H\x1b_Ga=T,f=32,s=8,v=8,i=7100,q=2,C=1,m=0;"));
+
+        // Unchanged images aren't sent again, and gone ones are removed.
+        let mut out = Vec::new();
+        writer
+            .write_images(&mut out, GraphicsProtocol::Kitty, &images, (2, 4))
+            .unwrap();
+        assert!(out.is_empty());
+
+        let mut out = Vec::new();
+        writer
+            .write_images(&mut out, GraphicsProtocol::Kitty, &[], (2, 4))
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b_Ga=d,d=I,i=7100,q=2\x1b\\"
+        );
+
+        let mut out = Vec::new();
+        writer
+            .write_images(&mut out, GraphicsProtocol::Sixel, &images, (2, 4))
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\x1b[2;2H\x1bP0;0;0q\"1;1;8;8#0;2;0;80;0#0"));
+        assert!(out.ends_with("-\x1b\\"));
+    }
+
+    #[test]
+    fn test_rasterize() {
+        let image = create_graph_image(
+            Rect::new(0, 0, 4, 2),
+            Some(Rect::new(3, 0, 1, 1)),
+            vec![(0.0, 1.0), (4.0, 1.0)],
+            Color::Rgb(255, 0, 0),
+        );
+        let pixels = image.rasterize((2, 4));
+        assert_eq!((pixels.width, pixels.height), (8, 8));
+
+        let alpha = |x: usize, y: usize| pixels.rgba[(y * pixels.width + x) * 4 + 3];
+        let red = |x: usize, y: usize| pixels.rgba[(y * pixels.width + x) * 4];
+
+        // The line runs across the middle, and nothing else is drawn.
+        assert!(alpha(1, 3) > 0 && alpha(1, 4) > 0);
+        assert_eq!(red(1, 4), 255);
+        assert_eq!(alpha(1, 0), 0);
+        assert_eq!(alpha(1, 7), 0);
+
+        // The legend's cell is left clear.
+        assert_eq!(alpha(7, 3), 0);
+        assert!(alpha(7, 4) > 0);
+    }
+}
@@ -1,11 +1,27 @@
 pub mod basic_table_arrows;
 pub mod battery_display;
+pub mod cgroup_table;
 pub mod cpu_basic;
 pub mod cpu_graph;
+pub mod debug_overlay;
 pub mod disk_table;
+pub mod event_table;
+pub mod frequency_graph;
+pub mod latency_graph;
+pub mod layout_tabs;
+pub mod load_avg_graph;
 pub mod mem_basic;
 pub mod mem_graph;
+pub mod mini_widgets;
 pub mod network_basic;
 pub mod network_graph;
+pub mod plugin_graph;
+pub mod power_graph;
+pub mod pressure_graph;
 pub mod process_table;
+pub mod screen_reader;
+pub mod system_info;
+pub mod table_filter;
 pub mod temp_table;
+pub mod top_processes;
+pub mod user_table;
@@ -1,6 +1,12 @@
+use std::borrow::Cow;
+
 use crate::{
     app::App,
-    canvas::{drawing_utils::calculate_basic_use_bars, Painter},
+    canvas::{
+        drawing_utils::{calculate_basic_use_bars, should_hide_x_label},
+        Painter,
+    },
+    components::time_graph::{GraphData, TimeGraph},
     constants::*,
 };
 
@@ -18,6 +24,7 @@ impl Painter {
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
         widget_id: u64,
     ) {
+        let widget_title = app_state.get_widget_title(widget_id, "Battery");
         let should_get_widget_bounds = app_state.should_get_widget_bounds();
         if let Some(battery_widget_state) =
             app_state.battery_state.widget_states.get_mut(&widget_id)
@@ -28,31 +35,136 @@ impl Painter {
             } else {
                 self.colours.border_style
             };
+
+            if battery_widget_state.is_graph_mode {
+                if let Some(battery_details) = app_state
+                    .converted_data
+                    .battery_data
+                    .get(battery_widget_state.currently_selected_battery_index)
+                {
+                    const CHARGE_Y_BOUNDS: [f64; 2] = [0.0, 100.5];
+                    const CHARGE_Y_LABELS: [Cow<'static, str>; 2] =
+                        [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
+
+                    let x_bounds = [0, battery_widget_state.current_display_time];
+                    let hide_x_labels = should_hide_x_label(
+                        app_state.app_config_fields.hide_time,
+                        app_state.app_config_fields.autohide_time,
+                        &mut battery_widget_state.autohide_timer,
+                        draw_loc,
+                    );
+
+                    // Scale the power graph to the highest draw seen, with a bit of headroom.
+                    let max_power = battery_details
+                        .power_data
+                        .iter()
+                        .fold(0.0_f64, |acc, (_time, power)| acc.max(*power));
+                    let power_upper_bound = if max_power > 0.0 {
+                        max_power * 1.1
+                    } else {
+                        1.0
+                    };
+                    let power_y_labels = [
+                        Cow::Borrowed("0W"),
+                        Cow::Owned(format!("{:.1}W", power_upper_bound)),
+                    ];
+
+                    let graph_draw_locs = Layout::default()
+                        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .direction(Direction::Vertical)
+                        .split(draw_loc);
+
+                    TimeGraph {
+                        use_dot: app_state.app_config_fields.use_dot,
+                        x_bounds,
+                        hide_x_labels,
+                        y_bounds: CHARGE_Y_BOUNDS,
+                        y_labels: &CHARGE_Y_LABELS,
+                        graph_style: self.colours.graph_style,
+                        border_style,
+                        title: format!(" {} Charge ", battery_details.battery_name).into(),
+                        is_expanded: app_state.is_expanded,
+                        title_style: self.colours.widget_title_style,
+                        legend_constraints: None,
+                        graph_images: self.graph_image_sink(),
+                        gaps: &app_state.converted_data.gaps,
+                        shade_gaps: app_state.app_config_fields.shade_gaps,
+                    }
+                    .draw_time_graph(
+                        f,
+                        graph_draw_locs[0],
+                        &[GraphData {
+                            points: &battery_details.charge_data,
+                            style: self.colours.high_battery_colour,
+                            name: Some(format!("{:.0}%", battery_details.charge_percentage).into()),
+                        }],
+                    );
+
+                    TimeGraph {
+                        use_dot: app_state.app_config_fields.use_dot,
+                        x_bounds,
+                        hide_x_labels,
+                        y_bounds: [0.0, power_upper_bound],
+                        y_labels: &power_y_labels,
+                        graph_style: self.colours.graph_style,
+                        border_style,
+                        title: " Power ".into(),
+                        is_expanded: false,
+                        title_style: self.colours.widget_title_style,
+                        legend_constraints: None,
+                        graph_images: self.graph_image_sink(),
+                        gaps: &app_state.converted_data.gaps,
+                        shade_gaps: app_state.app_config_fields.shade_gaps,
+                    }
+                    .draw_time_graph(
+                        f,
+                        graph_draw_locs[1],
+                        &[GraphData {
+                            points: &battery_details.power_data,
+                            style: self.colours.medium_battery_colour,
+                            name: Some(battery_details.watt_consumption.as_str().into()),
+                        }],
+                    );
+
+                    if should_get_widget_bounds {
+                        // Tabs aren't drawn in graph mode, so there is nothing to click.
+                        battery_widget_state.tab_click_locs = None;
+
+                        if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                            widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                            widget.bottom_right_corner =
+                                Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+                        }
+                    }
+
+                    return;
+                }
+            }
+
             let table_gap = if draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
                 0
             } else {
                 app_state.app_config_fields.table_gap
             };
 
+            let battery_title = format!(" {} ", widget_title);
             let title = if app_state.is_expanded {
-                const TITLE_BASE: &str = " Battery ── Esc to go back ";
+                const TITLE_BASE: &str = "── Esc to go back ";
+                let title_len = UnicodeSegmentation::graphemes(battery_title.as_str(), true)
+                    .count()
+                    + UnicodeSegmentation::graphemes(TITLE_BASE, true).count();
                 Spans::from(vec![
-                    Span::styled(" Battery ".to_string(), self.colours.widget_title_style),
+                    Span::styled(battery_title, self.colours.widget_title_style),
                     Span::styled(
                         format!(
                             "─{}─ Esc to go back ",
-                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
-                            ))
+                            "─".repeat(usize::from(draw_loc.width).saturating_sub(title_len + 2))
                         ),
                         border_style,
                     ),
                 ])
             } else {
-                Spans::from(Span::styled(
-                    " Battery ".to_string(),
-                    self.colours.widget_title_style,
-                ))
+                Spans::from(Span::styled(battery_title, self.colours.widget_title_style))
             };
 
             let battery_block = if draw_border {
@@ -72,12 +72,13 @@ impl Painter {
                     let bar_length = chunk_width - COMBINED_SPACING;
                     (0..num_cpus)
                         .map(|cpu_index| {
-                            let use_percentage =
-                                if let Some(cpu_usage) = cpu_data[cpu_index].cpu_data.last() {
-                                    cpu_usage.1
-                                } else {
-                                    0.0
-                                };
+                            let use_percentage = if let Some(cpu_usage) =
+                                cpu_data[cpu_index].cpu_data.points().last()
+                            {
+                                cpu_usage.1
+                            } else {
+                                0.0
+                            };
 
                             let num_bars = calculate_basic_use_bars(use_percentage, bar_length);
                             format!(
@@ -100,12 +101,13 @@ impl Painter {
                 } else if chunk_width >= REDUCED_SPACING {
                     (0..num_cpus)
                         .map(|cpu_index| {
-                            let use_percentage =
-                                if let Some(cpu_usage) = cpu_data[cpu_index].cpu_data.last() {
-                                    cpu_usage.1
-                                } else {
-                                    0.0
-                                };
+                            let use_percentage = if let Some(cpu_usage) =
+                                cpu_data[cpu_index].cpu_data.points().last()
+                            {
+                                cpu_usage.1
+                            } else {
+                                0.0
+                            };
 
                             format!(
                                 "{:3} {:3.0}%",
@@ -125,12 +127,13 @@ impl Painter {
                 } else {
                     (0..num_cpus)
                         .map(|cpu_index| {
-                            let use_percentage =
-                                if let Some(cpu_usage) = cpu_data[cpu_index].cpu_data.last() {
-                                    cpu_usage.1
-                                } else {
-                                    0.0
-                                };
+                            let use_percentage = if let Some(cpu_usage) =
+                                cpu_data[cpu_index].cpu_data.points().last()
+                            {
+                                cpu_usage.1
+                            } else {
+                                0.0
+                            };
 
                             format!("{:3.0}%", use_percentage.round(),)
                         })
@@ -1,13 +1,16 @@
-use std::{borrow::Cow, iter};
+use std::borrow::Cow;
 
 use crate::{
-    app::{layout_manager::WidgetDirection, App, CpuWidgetState},
-    canvas::{drawing_utils::should_hide_x_label, Painter},
+    app::{data_harvester::cpu::CPU_STATES, layout_manager::WidgetDirection, App, CpuWidgetState},
+    canvas::{
+        drawing_utils::{get_compare_line, get_stats_lines, should_hide_x_label},
+        Painter,
+    },
     components::{
         text_table::{CellContent, TextTable},
         time_graph::{GraphData, TimeGraph},
     },
-    data_conversion::{ConvertedCpuData, TableData, TableRow},
+    data_conversion::{get_top_process_on_cpu, ConvertedCpuData, CpuEntry, TableData, TableRow},
 };
 
 use concat_string::concat_string;
@@ -16,10 +19,10 @@ use itertools::Either;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     terminal::Frame,
 };
 
-const AVG_POSITION: usize = 1;
 const ALL_POSITION: usize = 0;
 
 impl Painter {
@@ -118,49 +121,77 @@ impl Painter {
         }
     }
 
+    /// Returns the style of an entry in the CPU widget. Each node has the style of the CPU with
+    /// the same number, as nodes are usually drawn instead of their CPUs.
+    fn get_cpu_style(&self, entry: CpuEntry) -> Style {
+        match entry {
+            CpuEntry::All => self.colours.all_colour_style,
+            CpuEntry::Average => self.colours.avg_colour_style,
+            CpuEntry::Cpu(cpu) | CpuEntry::Node { node: cpu, .. } => {
+                self.colours.cpu_colour_styles[cpu % self.colours.cpu_colour_styles.len()]
+            }
+        }
+    }
+
     fn generate_points<'a>(
         &self, cpu_widget_state: &CpuWidgetState, cpu_data: &'a [ConvertedCpuData],
-        show_avg_cpu: bool,
+        is_stacked: bool, is_breakdown: bool,
     ) -> Vec<GraphData<'a>> {
-        let show_avg_offset = if show_avg_cpu { AVG_POSITION } else { 0 };
-
         let current_scroll_position = cpu_widget_state.table_state.current_scroll_position;
-        if current_scroll_position == ALL_POSITION {
-            // This case ensures the other cases cannot have the position be equal to 0.
+        if is_stacked {
+            // The highest line is drawn first, so each CPU's colour shows down to the line below.
             cpu_data
                 .iter()
-                .enumerate()
+                .filter(|cpu| !cpu.stacked_data.points().is_empty())
                 .rev()
-                .map(|(itx, cpu)| {
-                    let style = if show_avg_cpu && itx == AVG_POSITION {
-                        self.colours.avg_colour_style
-                    } else if itx == ALL_POSITION {
-                        self.colours.all_colour_style
-                    } else {
-                        let offset_position = itx - 1; // Because of the all position
-                        self.colours.cpu_colour_styles[(offset_position - show_avg_offset)
-                            % self.colours.cpu_colour_styles.len()]
-                    };
-
-                    GraphData {
-                        points: &cpu.cpu_data[..],
-                        style,
-                        name: None,
-                    }
+                .map(|cpu| GraphData {
+                    points: cpu.stacked_data.points(),
+                    style: self.get_cpu_style(cpu.entry),
+                    name: None,
+                })
+                .collect::<Vec<_>>()
+        } else if is_breakdown {
+            cpu_data
+                .get(current_scroll_position)
+                .map(|cpu| {
+                    cpu.breakdown_data
+                        .iter()
+                        .zip(CPU_STATES.iter())
+                        .enumerate()
+                        .map(|(itx, (points, state))| GraphData {
+                            points: points.points(),
+                            style: self.colours.cpu_colour_styles
+                                [itx % self.colours.cpu_colour_styles.len()],
+                            name: Some((*state).into()),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else if current_scroll_position == ALL_POSITION {
+            // This case ensures the other cases cannot have the position be equal to 0. Expanded
+            // nodes are left out, as their CPUs are drawn instead.
+            cpu_data
+                .iter()
+                .filter(|cpu| {
+                    !matches!(
+                        cpu.entry,
+                        CpuEntry::Node {
+                            is_collapsed: false,
+                            ..
+                        }
+                    )
+                })
+                .rev()
+                .map(|cpu| GraphData {
+                    points: cpu.cpu_data.points(),
+                    style: self.get_cpu_style(cpu.entry),
+                    name: None,
                 })
                 .collect::<Vec<_>>()
         } else if let Some(cpu) = cpu_data.get(current_scroll_position) {
-            let style = if show_avg_cpu && current_scroll_position == AVG_POSITION {
-                self.colours.avg_colour_style
-            } else {
-                let offset_position = current_scroll_position - 1; // Because of the all position
-                self.colours.cpu_colour_styles
-                    [(offset_position - show_avg_offset) % self.colours.cpu_colour_styles.len()]
-            };
-
             vec![GraphData {
-                points: &cpu.cpu_data[..],
-                style,
+                points: cpu.cpu_data.points(),
+                style: self.get_cpu_style(cpu.entry),
                 name: None,
             }]
         } else {
@@ -168,9 +199,10 @@ impl Painter {
         }
     }
 
-    fn draw_cpu_graph<B: Backend>(
+    pub fn draw_cpu_graph<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
+        let widget_title = app_state.get_widget_title(widget_id, "CPU");
         const Y_BOUNDS: [f64; 2] = [0.0, 100.5];
         const Y_LABELS: [Cow<'static, str>; 2] = [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
 
@@ -185,11 +217,111 @@ impl Painter {
                 draw_loc,
             );
 
-            let points = self.generate_points(
-                cpu_widget_state,
-                cpu_data,
-                app_state.app_config_fields.show_average_cpu,
-            );
+            let current_scroll_position = cpu_widget_state.table_state.current_scroll_position;
+            let is_stacked =
+                app_state.app_config_fields.stacked_cpu && current_scroll_position == ALL_POSITION;
+            let is_breakdown = app_state.app_config_fields.show_cpu_breakdown
+                && current_scroll_position != ALL_POSITION
+                && cpu_data
+                    .get(current_scroll_position)
+                    .map(|cpu| {
+                        cpu.breakdown_data
+                            .iter()
+                            .any(|points| !points.points().is_empty())
+                    })
+                    .unwrap_or(false);
+            let mut points =
+                self.generate_points(cpu_widget_state, cpu_data, is_stacked, is_breakdown);
+
+            // When stacked, the y-axis goes up to 100% for each CPU.
+            let num_cpus = app_state
+                .data_collection
+                .cpu_harvest
+                .iter()
+                .filter(|cpu| cpu.cpu_count.is_some())
+                .count();
+            let (y_bounds, y_labels) = if is_stacked && num_cpus > 1 {
+                let max_label = format!("{}%", num_cpus * 100);
+                (
+                    [0.0, num_cpus as f64 * 100.0 + 0.5],
+                    vec![
+                        format!("{:>width$}", "0%", width = max_label.len()).into(),
+                        max_label.into(),
+                    ],
+                )
+            } else {
+                (Y_BOUNDS, Y_LABELS.to_vec())
+            };
+
+            // The stats are of the selected CPU, or the average if all are shown. They're left out
+            // of the stacked graph, as the average isn't on the same scale, and of the breakdown,
+            // as the overall usage isn't drawn.
+            let (stats_cpu, stats_style) = if is_stacked || is_breakdown {
+                (None, self.colours.graph_style)
+            } else if current_scroll_position == ALL_POSITION {
+                (
+                    cpu_data.iter().find(|cpu| cpu.entry == CpuEntry::Average),
+                    self.colours.avg_colour_style,
+                )
+            } else {
+                (
+                    cpu_data.get(current_scroll_position),
+                    points
+                        .first()
+                        .map(|graph_data| graph_data.style)
+                        .unwrap_or(self.colours.graph_style),
+                )
+            };
+            let stats_lines = stats_cpu
+                .and_then(|cpu| cpu.stats)
+                .map(|stats| {
+                    get_stats_lines(&stats, -(cpu_widget_state.current_display_time as f64), "")
+                })
+                .unwrap_or_default();
+            points.extend(stats_lines.iter().map(|(line, name)| GraphData {
+                points: line,
+                style: stats_style.add_modifier(Modifier::DIM),
+                name: Some(name.into()),
+            }));
+
+            // The comparison overlay is of the same CPU as the stats.
+            let compare_line = match (stats_cpu, app_state.app_config_fields.compare_offset) {
+                (Some(cpu), Some(offset)) if app_state.is_comparing => Some(get_compare_line(
+                    cpu.cpu_data.points(),
+                    offset,
+                    cpu_widget_state.current_display_time,
+                    "",
+                )),
+                _ => None,
+            };
+            points.extend(compare_line.iter().map(|(line, name)| GraphData {
+                points: line,
+                style: stats_style.add_modifier(Modifier::DIM),
+                name: Some(name.into()),
+            }));
+
+            // The selected core's top process, if a single core is selected.
+            let top_process_str = match cpu_data.get(current_scroll_position) {
+                Some(ConvertedCpuData {
+                    entry: CpuEntry::Cpu(cpu),
+                    ..
+                }) if app_state.app_config_fields.show_cpu_top_process => {
+                    get_top_process_on_cpu(&app_state.data_collection.process_data, *cpu)
+                        .map(|process| {
+                            format!(
+                                "─ Top: {} ({}) {:.1}% ",
+                                process.name, process.pid, process.cpu_usage_percent
+                            )
+                        })
+                        .unwrap_or_default()
+                }
+                _ => String::default(),
+            };
+
+            let peak_str = match &app_state.converted_data.peak_display.cpu {
+                Some(peak) if app_state.app_config_fields.show_peaks => format!("─ {} ", peak),
+                _ => String::default(),
+            };
 
             // TODO: Maybe hide load avg if too long? Or maybe the CPU part.
             let title = if cfg!(target_family = "unix") {
@@ -199,23 +331,34 @@ impl Painter {
                     load_avg[0], load_avg[1], load_avg[2]
                 );
 
-                concat_string!(" CPU ", load_avg_str).into()
+                concat_string!(
+                    " ",
+                    widget_title,
+                    " ",
+                    load_avg_str,
+                    peak_str,
+                    top_process_str
+                )
+                .into()
             } else {
-                " CPU ".into()
+                concat_string!(" ", widget_title, " ", peak_str, top_process_str).into()
             };
 
             TimeGraph {
                 use_dot: app_state.app_config_fields.use_dot,
                 x_bounds,
                 hide_x_labels,
-                y_bounds: Y_BOUNDS,
-                y_labels: &Y_LABELS,
+                y_bounds,
+                y_labels: &y_labels,
                 graph_style: self.colours.graph_style,
                 border_style,
                 title,
                 is_expanded: app_state.is_expanded,
                 title_style: self.colours.widget_title_style,
                 legend_constraints: None,
+                graph_images: self.graph_image_sink(),
+                gaps: &app_state.converted_data.gaps,
+                shade_gaps: app_state.app_config_fields.shade_gaps,
             }
             .draw_time_graph(f, draw_loc, &points);
         }
@@ -230,24 +373,14 @@ impl Painter {
             // TODO: This line (and the one above, see caller) is pretty dumb but I guess needed.
             cpu_widget_state.is_legend_hidden = false;
 
-            let show_avg_cpu = app_state.app_config_fields.show_average_cpu;
             let cpu_data = {
                 let col_widths = vec![1, 3]; // TODO: Should change this to take const generics (usize) and an array.
-                let colour_iter = if show_avg_cpu {
-                    Either::Left(
-                        iter::once(&self.colours.all_colour_style)
-                            .chain(iter::once(&self.colours.avg_colour_style))
-                            .chain(self.colours.cpu_colour_styles.iter().cycle()),
-                    )
-                } else {
-                    Either::Right(
-                        iter::once(&self.colours.all_colour_style)
-                            .chain(self.colours.cpu_colour_styles.iter().cycle()),
-                    )
-                };
-
                 let data = {
-                    let iter = app_state.converted_data.cpu_data.iter().zip(colour_iter);
+                    let iter = app_state
+                        .converted_data
+                        .cpu_data
+                        .iter()
+                        .map(|cpu| (cpu, self.get_cpu_style(cpu.entry)));
                     const CPU_WIDTH_CHECK: u16 = 10; // This is hard-coded, it's terrible.
                     if draw_loc.width < CPU_WIDTH_CHECK {
                         Either::Left(iter.map(|(cpu, style)| {
@@ -259,7 +392,7 @@ impl Painter {
                                     cpu.legend_value.clone().into()
                                 }),
                             ];
-                            TableRow::Styled(row, *style)
+                            TableRow::Styled(row, style)
                         }))
                     } else {
                         Either::Right(iter.map(|(cpu, style)| {
@@ -270,7 +403,7 @@ impl Painter {
                                 },
                                 CellContent::Simple(cpu.legend_value.clone().into()),
                             ];
-                            TableRow::Styled(row, *style)
+                            TableRow::Styled(row, style)
                         }))
                     }
                 }
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use crate::{app::App, canvas::Painter, utils::gen_util::get_binary_bytes};
+
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+
+fn duration_millis_string(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
+
+impl Painter {
+    /// Draws the debug overlay in the top right corner, over whatever is there. This shows
+    /// bottom's own usage, how long the last frame took to draw, and how long each collector took
+    /// the last time it ran.
+    pub fn draw_debug_overlay<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    ) {
+        let data = &app_state.data_collection;
+        let mut rows = vec![];
+        match &data.self_usage {
+            Some(self_usage) => {
+                let (rss, unit) = get_binary_bytes(self_usage.rss_in_kib * 1024);
+                rows.push(("CPU".to_string(), format!("{:.1}%", self_usage.cpu_usage)));
+                rows.push(("Memory".to_string(), format!("{:.1}{}", rss, unit)));
+            }
+            None => {
+                rows.push(("CPU".to_string(), "N/A".to_string()));
+                rows.push(("Memory".to_string(), "N/A".to_string()));
+            }
+        }
+        rows.push((
+            "Drawing".to_string(),
+            duration_millis_string(app_state.last_render_duration),
+        ));
+        rows.push((
+            "Harvest".to_string(),
+            duration_millis_string(data.harvest_duration),
+        ));
+        rows.extend(data.harvest_timings.iter().map(|timing| {
+            (
+                format!("  {}", timing.collector),
+                duration_millis_string(timing.duration),
+            )
+        }));
+
+        // Values are right-aligned after the longest label.
+        let label_width = rows
+            .iter()
+            .map(|(label, _)| label.width())
+            .max()
+            .unwrap_or(0);
+        let value_width = rows
+            .iter()
+            .map(|(_, value)| value.width())
+            .max()
+            .unwrap_or(0);
+        let lines = rows
+            .into_iter()
+            .map(|(label, value)| {
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{:width$} ", label, width = label_width),
+                        self.colours.table_header_style,
+                    ),
+                    Span::styled(
+                        format!("{:>width$}", value, width = value_width),
+                        self.colours.text_style,
+                    ),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        const TITLE: &str = " Debug ─ gs to close ";
+        let width =
+            ((label_width + 1 + value_width).max(TITLE.width()) as u16 + 2).min(draw_loc.width);
+        let height = (lines.len() as u16 + 2).min(draw_loc.height);
+        let overlay_loc = Rect::new(
+            draw_loc.x + draw_loc.width - width,
+            draw_loc.y,
+            width,
+            height,
+        );
+
+        f.render_widget(Clear, overlay_loc);
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(Span::styled(TITLE, self.colours.widget_title_style))
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.highlighted_border_style),
+                )
+                .style(self.colours.text_style),
+            overlay_loc,
+        );
+    }
+}
@@ -4,6 +4,7 @@ use crate::{
     app,
     canvas::Painter,
     components::text_table::{TextTable, TextTableTitle},
+    data_conversion::TableRow,
 };
 
 impl Painter {
@@ -11,9 +12,19 @@ impl Painter {
         &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
         widget_id: u64,
     ) {
+        let widget_title = app_state.get_widget_title(widget_id, "Disks");
         let recalculate_column_widths = app_state.should_get_widget_bounds();
         if let Some(disk_widget_state) = app_state.disk_state.widget_states.get_mut(&widget_id) {
             let is_on_widget = app_state.current_widget.widget_id == widget_id;
+
+            let table_draw_loc = self.draw_table_filter(
+                f,
+                &disk_widget_state.filter,
+                is_on_widget,
+                draw_loc,
+                draw_border,
+            );
+
             let (border_style, highlighted_text_style) = if is_on_widget {
                 (
                     self.colours.highlighted_border_style,
@@ -22,6 +33,15 @@ impl Painter {
             } else {
                 (self.colours.border_style, self.colours.text_style)
             };
+
+            // Styled rows are disks reporting SMART failures or estimated to be full soon, so
+            // highlight them with the same colour used for a low battery.
+            for row in &mut disk_widget_state.table_data.data {
+                if let TableRow::Styled(_, style) = row {
+                    *style = style.patch(self.colours.low_battery_colour);
+                }
+            }
+
             TextTable {
                 table_gap: app_state.app_config_fields.table_gap,
                 is_force_redraw: app_state.is_force_redraw,
@@ -30,7 +50,11 @@ impl Painter {
                 border_style,
                 highlighted_text_style,
                 title: Some(TextTableTitle {
-                    title: " Disks ".into(),
+                    title: if disk_widget_state.hide_pseudo_filesystems {
+                        format!(" {} [pseudo-filesystems hidden] ", widget_title).into()
+                    } else {
+                        format!(" {} ", widget_title).into()
+                    },
                     is_expanded: app_state.is_expanded,
                 }),
                 is_on_widget,
@@ -42,9 +66,9 @@ impl Painter {
             }
             .draw_text_table(
                 f,
-                draw_loc,
+                table_draw_loc,
                 &mut disk_widget_state.table_state,
-                &app_state.converted_data.disk_data,
+                &disk_widget_state.table_data,
                 app_state.widget_map.get_mut(&widget_id),
             );
         }
@@ -0,0 +1,147 @@
+use std::borrow::Cow;
+
+use crate::{
+    app::App,
+    canvas::{drawing_utils::should_hide_x_label, Painter},
+    components::time_graph::{GraphData, TimeGraph},
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    terminal::Frame,
+};
+
+impl Painter {
+    pub fn draw_frequency_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let widget_title = app_state.get_widget_title(widget_id, "Frequency");
+        if let Some(frequency_widget_state) =
+            app_state.frequency_state.widget_states.get_mut(&widget_id)
+        {
+            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let x_bounds = [0, frequency_widget_state.current_display_time];
+            let hide_x_labels = should_hide_x_label(
+                app_state.app_config_fields.hide_time,
+                app_state.app_config_fields.autohide_time,
+                &mut frequency_widget_state.autohide_timer,
+                draw_loc,
+            );
+            let frequency_data = &app_state.converted_data.frequency_data;
+
+            // Each CPU is drawn under the average, and only the average is in the legend.
+            let mut frequency_points: Vec<GraphData<'_>> = frequency_data
+                .cpus
+                .iter()
+                .zip(self.colours.cpu_colour_styles.iter().cycle())
+                .map(|(points, style)| GraphData {
+                    points,
+                    style: *style,
+                    name: None,
+                })
+                .collect();
+            if let Some(current_average) = frequency_data.current_average {
+                frequency_points.push(GraphData {
+                    points: &frequency_data.average,
+                    style: self.colours.avg_colour_style,
+                    name: Some(format!("Avg: {:.2}GHz", current_average / 1000.0).into()),
+                });
+            }
+
+            // Scale the graph to the highest clock speed the CPUs can reach if it's known, so turbo
+            // shows as how close it gets to the top.
+            let max_frequency = frequency_data
+                .cpus
+                .iter()
+                .flat_map(|points| points.iter())
+                .fold(0.0_f64, |acc, (_time, frequency)| acc.max(*frequency));
+            let frequency_upper_bound = match frequency_data.max_frequency {
+                Some(max) => max.max(max_frequency),
+                None if max_frequency > 0.0 => max_frequency * 1.1,
+                None => 1000.0,
+            };
+            let frequency_y_labels = [
+                Cow::Borrowed("0GHz"),
+                Cow::Owned(format!("{:.1}GHz", frequency_upper_bound / 1000.0)),
+            ];
+
+            let c_state_points: Vec<GraphData<'_>> = frequency_data
+                .c_states
+                .iter()
+                .zip(self.colours.series_colour_styles.iter().cycle())
+                .map(|((name, points, current), style)| GraphData {
+                    points,
+                    style: *style,
+                    name: Some(format!("{}: {:.1}%", name, current).into()),
+                })
+                .collect();
+
+            // C-state residency is a percentage rather than a clock speed, so it gets its own graph
+            // under the clock speeds when it's available.
+            let (frequency_loc, c_state_loc) = if c_state_points.is_empty() {
+                (draw_loc, None)
+            } else {
+                let split_loc = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Ratio(3, 5), Constraint::Ratio(2, 5)])
+                    .split(draw_loc);
+                (split_loc[0], Some(split_loc[1]))
+            };
+
+            TimeGraph {
+                use_dot: app_state.app_config_fields.use_dot,
+                x_bounds,
+                hide_x_labels: hide_x_labels || c_state_loc.is_some(),
+                y_bounds: [0.0, frequency_upper_bound],
+                y_labels: &frequency_y_labels,
+                graph_style: self.colours.graph_style,
+                border_style,
+                title: if frequency_data.cpus.is_empty() {
+                    format!(" {} (Unavailable) ", widget_title).into()
+                } else {
+                    format!(" {} ", widget_title).into()
+                },
+                is_expanded: app_state.is_expanded,
+                title_style: self.colours.widget_title_style,
+                legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                graph_images: self.graph_image_sink(),
+                gaps: &app_state.converted_data.gaps,
+                shade_gaps: app_state.app_config_fields.shade_gaps,
+            }
+            .draw_time_graph(f, frequency_loc, &frequency_points);
+
+            if let Some(c_state_loc) = c_state_loc {
+                const C_STATE_Y_LABELS: [Cow<'static, str>; 2] =
+                    [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
+
+                TimeGraph {
+                    use_dot: app_state.app_config_fields.use_dot,
+                    x_bounds,
+                    hide_x_labels,
+                    y_bounds: [0.0, 100.5],
+                    y_labels: &C_STATE_Y_LABELS,
+                    graph_style: self.colours.graph_style,
+                    border_style,
+                    title: " C-states ".into(),
+                    is_expanded: false,
+                    title_style: self.colours.widget_title_style,
+                    legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                    graph_images: self.graph_image_sink(),
+                    gaps: &app_state.converted_data.gaps,
+                    shade_gaps: app_state.app_config_fields.shade_gaps,
+                }
+                .draw_time_graph(f, c_state_loc, &c_state_points);
+            }
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
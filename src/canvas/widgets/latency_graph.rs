@@ -0,0 +1,92 @@
+use std::borrow::Cow;
+
+use crate::{
+    app::App,
+    canvas::{drawing_utils::should_hide_x_label, Painter},
+    components::time_graph::{GraphData, TimeGraph},
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    terminal::Frame,
+};
+
+impl Painter {
+    pub fn draw_latency_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let widget_title = app_state.get_widget_title(widget_id, "Latency");
+        if let Some(latency_widget_state) =
+            app_state.latency_state.widget_states.get_mut(&widget_id)
+        {
+            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let x_bounds = [0, latency_widget_state.current_display_time];
+            let hide_x_labels = should_hide_x_label(
+                app_state.app_config_fields.hide_time,
+                app_state.app_config_fields.autohide_time,
+                &mut latency_widget_state.autohide_timer,
+                draw_loc,
+            );
+
+            let points: Vec<GraphData<'_>> = app_state
+                .converted_data
+                .latency_history
+                .iter()
+                .zip(app_state.converted_data.latency_legend.iter())
+                .zip(self.colours.series_colour_styles.iter().cycle())
+                .map(|((history, legend), style)| GraphData {
+                    points: history,
+                    style: *style,
+                    name: Some(legend.into()),
+                })
+                .collect();
+
+            // Scale the graph to the highest round-trip time shown, with a bit of headroom.
+            let max_rtt_ms = points
+                .iter()
+                .flat_map(|graph_data| graph_data.points.iter())
+                .fold(0.0_f64, |acc, (_time, rtt_ms)| acc.max(*rtt_ms));
+            let rtt_upper_bound = if max_rtt_ms > 0.0 {
+                (max_rtt_ms * 1.1).ceil()
+            } else {
+                1.0
+            };
+            let y_labels = [
+                Cow::Borrowed("0ms"),
+                Cow::Owned(format!("{}ms", rtt_upper_bound)),
+            ];
+
+            TimeGraph {
+                use_dot: app_state.app_config_fields.use_dot,
+                x_bounds,
+                hide_x_labels,
+                y_bounds: [0.0, rtt_upper_bound],
+                y_labels: &y_labels,
+                graph_style: self.colours.graph_style,
+                border_style,
+                title: if app_state.app_config_fields.latency_hosts.is_empty() {
+                    format!(" {} (No hosts) ", widget_title).into()
+                } else {
+                    format!(" {} ", widget_title).into()
+                },
+                is_expanded: app_state.is_expanded,
+                title_style: self.colours.widget_title_style,
+                legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                graph_images: self.graph_image_sink(),
+                gaps: &app_state.converted_data.gaps,
+                shade_gaps: app_state.app_config_fields.shade_gaps,
+            }
+            .draw_time_graph(f, draw_loc, &points);
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
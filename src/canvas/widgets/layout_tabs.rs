@@ -0,0 +1,114 @@
+use crate::{
+    app::{layout_manager::BottomCol, App},
+    canvas::{drawing_utils::scroll_loc, Painter},
+};
+
+use itertools::izip;
+use tui::{
+    backend::Backend,
+    layout::{Direction, Layout, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Returns where to draw each row of a tabbed column. Only the rows of the shown tab get any
+/// space, which is everything under the line of tab headers.
+pub fn get_tab_col_row_locs(col: &BottomCol, col_loc: Rect, selected_tab: u64) -> Vec<Rect> {
+    let body_loc = Rect {
+        y: col_loc.y + col_loc.height.min(1),
+        height: col_loc.height.saturating_sub(1),
+        ..col_loc
+    };
+    let selected_col_rows = col
+        .tabs
+        .iter()
+        .find(|tab| tab.widget_id == selected_tab)
+        .or_else(|| col.tabs.first())
+        .map(|tab| tab.col_rows.clone())
+        .unwrap_or_default();
+
+    col.children
+        .iter()
+        .enumerate()
+        .map(|(index, col_row)| {
+            if selected_col_rows.contains(&index) && !col_row.canvas_handle_height {
+                body_loc
+            } else {
+                Rect::default()
+            }
+        })
+        .collect()
+}
+
+impl Painter {
+    /// Draws the line of tab headers at the top of each tabbed column, in a layout at `layout_loc`
+    /// that's scrolled onto `viewport`.
+    pub fn draw_layout_tabs<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, layout_loc: Rect, viewport: Rect,
+    ) {
+        let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        let row_locs = Layout::default()
+            .constraints(self.row_constraints.as_slice())
+            .direction(Direction::Vertical)
+            .split(layout_loc);
+
+        for (row_loc, col_constraint, row) in
+            izip!(row_locs, &self.col_constraints, &self.widget_layout.rows)
+        {
+            let col_locs = Layout::default()
+                .constraints(col_constraint.as_slice())
+                .direction(Direction::Horizontal)
+                .split(row_loc);
+
+            for (col_loc, col) in col_locs.iter().zip(&row.children) {
+                let header_loc = scroll_loc(
+                    Rect {
+                        height: col_loc.height.min(1),
+                        ..*col_loc
+                    },
+                    viewport,
+                    app_state.layout_scroll,
+                );
+                let container_id = match col.tabs.first() {
+                    Some(tab) if header_loc.height > 0 => tab.widget_id,
+                    _ => continue,
+                };
+                let selected_tab = app_state.get_selected_tab(container_id);
+                let right_edge = header_loc.x + header_loc.width;
+
+                let mut spans = Vec::with_capacity(col.tabs.len() * 2);
+                let mut x = header_loc.x;
+                for tab in &col.tabs {
+                    if !spans.is_empty() {
+                        spans.push(Span::styled("│", self.colours.border_style));
+                        x += 1;
+                    }
+
+                    let header = format!(" {} ", tab.widget_type.get_pretty_name());
+                    let header_width = header.width() as u16;
+                    if should_get_widget_bounds && x < right_edge {
+                        app_state.layout_tab_click_locs.push((
+                            (x, header_loc.y),
+                            ((x + header_width).min(right_edge), header_loc.y + 1),
+                            tab.widget_id,
+                        ));
+                    }
+                    x += header_width;
+
+                    spans.push(Span::styled(
+                        header,
+                        if tab.widget_id == selected_tab {
+                            self.colours.currently_selected_text_style
+                        } else {
+                            self.colours.text_style
+                        },
+                    ));
+                }
+
+                f.render_widget(Paragraph::new(Spans::from(spans)), header_loc);
+            }
+        }
+    }
+}
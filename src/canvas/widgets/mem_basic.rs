@@ -17,8 +17,8 @@ impl Painter {
     pub fn draw_basic_memory<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
-        let mem_data: &[(f64, f64)] = &app_state.converted_data.mem_data;
-        let swap_data: &[(f64, f64)] = &app_state.converted_data.swap_data;
+        let mem_data: &[(f64, f64)] = app_state.converted_data.mem_data.points();
+        let swap_data: &[(f64, f64)] = app_state.converted_data.swap_data.points();
 
         let margined_loc = Layout::default()
             .constraints([Constraint::Percentage(100)])
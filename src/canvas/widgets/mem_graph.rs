@@ -2,13 +2,18 @@ use std::borrow::Cow;
 
 use crate::{
     app::App,
-    canvas::{drawing_utils::should_hide_x_label, Painter},
+    canvas::{
+        drawing_utils::{get_compare_line, get_stats_lines, should_hide_x_label},
+        widgets::network_graph::{adjust_rate_data_point, get_max_rate_entry},
+        Painter,
+    },
     components::time_graph::{GraphData, TimeGraph},
 };
 
 use tui::{
     backend::Backend,
-    layout::{Constraint, Rect},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
     terminal::Frame,
 };
 
@@ -16,6 +21,7 @@ impl Painter {
     pub fn draw_memory_graph<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
+        let widget_title = app_state.get_widget_title(widget_id, "Memory");
         const Y_BOUNDS: [f64; 2] = [0.0, 100.5];
         const Y_LABELS: [Cow<'static, str>; 2] = [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
 
@@ -28,12 +34,48 @@ impl Painter {
                 &mut mem_widget_state.autohide_timer,
                 draw_loc,
             );
+            let time_start = -(mem_widget_state.current_display_time as f64);
+            let mem_stats_lines = app_state
+                .converted_data
+                .mem_stats
+                .map(|stats| get_stats_lines(&stats, time_start, "RAM "))
+                .unwrap_or_default();
+            let swap_stats_lines = app_state
+                .converted_data
+                .swap_stats
+                .map(|stats| get_stats_lines(&stats, time_start, "SWP "))
+                .unwrap_or_default();
+            let (mem_compare_line, swap_compare_line) =
+                match app_state.app_config_fields.compare_offset {
+                    Some(offset) if app_state.is_comparing => {
+                        let display_time = mem_widget_state.current_display_time;
+                        (
+                            app_state.converted_data.mem_labels.as_ref().map(|_| {
+                                get_compare_line(
+                                    app_state.converted_data.mem_data.points(),
+                                    offset,
+                                    display_time,
+                                    "RAM ",
+                                )
+                            }),
+                            app_state.converted_data.swap_labels.as_ref().map(|_| {
+                                get_compare_line(
+                                    app_state.converted_data.swap_data.points(),
+                                    offset,
+                                    display_time,
+                                    "SWP ",
+                                )
+                            }),
+                        )
+                    }
+                    _ => (None, None),
+                };
             let points = {
                 let mut points = Vec::with_capacity(2);
                 if let Some((label_percent, label_frac)) = &app_state.converted_data.mem_labels {
                     let mem_label = format!("RAM:{}{}", label_percent, label_frac);
                     points.push(GraphData {
-                        points: &app_state.converted_data.mem_data,
+                        points: app_state.converted_data.mem_data.points(),
                         style: self.colours.ram_style,
                         name: Some(mem_label.into()),
                     });
@@ -41,29 +83,119 @@ impl Painter {
                 if let Some((label_percent, label_frac)) = &app_state.converted_data.swap_labels {
                     let swap_label = format!("SWP:{}{}", label_percent, label_frac);
                     points.push(GraphData {
-                        points: &app_state.converted_data.swap_data,
+                        points: app_state.converted_data.swap_data.points(),
                         style: self.colours.swap_style,
                         name: Some(swap_label.into()),
                     });
                 }
+                points.extend(mem_stats_lines.iter().map(|(line, name)| GraphData {
+                    points: line,
+                    style: self.colours.ram_style.add_modifier(Modifier::DIM),
+                    name: Some(name.into()),
+                }));
+                points.extend(swap_stats_lines.iter().map(|(line, name)| GraphData {
+                    points: line,
+                    style: self.colours.swap_style.add_modifier(Modifier::DIM),
+                    name: Some(name.into()),
+                }));
+                points.extend(mem_compare_line.iter().map(|(line, name)| GraphData {
+                    points: line,
+                    style: self.colours.ram_style.add_modifier(Modifier::DIM),
+                    name: Some(name.into()),
+                }));
+                points.extend(swap_compare_line.iter().map(|(line, name)| GraphData {
+                    points: line,
+                    style: self.colours.swap_style.add_modifier(Modifier::DIM),
+                    name: Some(name.into()),
+                }));
 
                 points
             };
 
+            let peak_str = match &app_state.converted_data.peak_display.mem {
+                Some(peak) if app_state.app_config_fields.show_peaks => format!("─ {} ", peak),
+                _ => String::default(),
+            };
+
+            // Swap activity is drawn under the usage graph with its own scale, and the time labels
+            // are then only shown under it.
+            let (mem_loc, swap_activity_loc) = if app_state.app_config_fields.show_swap_activity {
+                let split_loc = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Ratio(2, 3), Constraint::Ratio(1, 3)])
+                    .split(draw_loc);
+                (split_loc[0], Some(split_loc[1]))
+            } else {
+                (draw_loc, None)
+            };
+
             TimeGraph {
                 use_dot: app_state.app_config_fields.use_dot,
                 x_bounds,
-                hide_x_labels,
+                hide_x_labels: hide_x_labels || swap_activity_loc.is_some(),
                 y_bounds: Y_BOUNDS,
                 y_labels: &Y_LABELS,
                 graph_style: self.colours.graph_style,
                 border_style,
-                title: " Memory ".into(),
+                title: format!(" {} {}", widget_title, peak_str).into(),
                 is_expanded: app_state.is_expanded,
                 title_style: self.colours.widget_title_style,
                 legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                graph_images: self.graph_image_sink(),
+                gaps: &app_state.converted_data.gaps,
+                shade_gaps: app_state.app_config_fields.shade_gaps,
+            }
+            .draw_time_graph(f, mem_loc, &points);
+
+            if let Some(swap_activity_loc) = swap_activity_loc {
+                let swap_in_points = app_state.converted_data.swap_in_data.points();
+                let swap_out_points = app_state.converted_data.swap_out_data.points();
+                let (max_range, labels) = adjust_rate_data_point(get_max_rate_entry(
+                    swap_in_points,
+                    swap_out_points,
+                    time_start,
+                ));
+                let y_labels = labels.iter().map(|label| label.into()).collect::<Vec<_>>();
+                let (in_label, out_label) = app_state
+                    .converted_data
+                    .swap_activity_labels
+                    .clone()
+                    .unwrap_or_else(|| ("IN:N/A".to_string(), "OUT:N/A".to_string()));
+
+                // Pages in and out are drawn with the same colours as network RX and TX.
+                TimeGraph {
+                    use_dot: app_state.app_config_fields.use_dot,
+                    x_bounds,
+                    hide_x_labels,
+                    y_bounds: [0.0, max_range],
+                    y_labels: &y_labels,
+                    graph_style: self.colours.graph_style,
+                    border_style,
+                    title: format!(" {} (Pages swapped) ", widget_title).into(),
+                    is_expanded: false,
+                    title_style: self.colours.widget_title_style,
+                    legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                    graph_images: self.graph_image_sink(),
+                    gaps: &app_state.converted_data.gaps,
+                    shade_gaps: app_state.app_config_fields.shade_gaps,
+                }
+                .draw_time_graph(
+                    f,
+                    swap_activity_loc,
+                    &[
+                        GraphData {
+                            points: swap_in_points,
+                            style: self.colours.rx_style,
+                            name: Some(in_label.into()),
+                        },
+                        GraphData {
+                            points: swap_out_points,
+                            style: self.colours.tx_style,
+                            name: Some(out_label.into()),
+                        },
+                    ],
+                );
             }
-            .draw_time_graph(f, draw_loc, &points);
         }
 
         if app_state.should_get_widget_bounds() {
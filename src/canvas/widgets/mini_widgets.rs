@@ -0,0 +1,79 @@
+use crate::{
+    app::{layout_manager::BottomWidgetType, App},
+    canvas::Painter,
+    data_conversion::{format_network_rate, get_average_cpu_usage},
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+
+impl Painter {
+    /// Draws a one-line summary of the CPU, memory, and network widgets, for when another widget
+    /// is expanded. Widgets that aren't in the layout, or that are the one expanded, are skipped.
+    pub fn draw_mini_widgets<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    ) {
+        use BottomWidgetType::*;
+
+        let expanded_type = &app_state.current_widget.widget_type;
+        let mut summaries: Vec<(&str, String, Style)> = Vec::new();
+
+        if app_state.used_widgets.use_cpu && !matches!(expanded_type, Cpu | CpuLegend) {
+            if let Some(usage) = get_average_cpu_usage(&app_state.data_collection.cpu_harvest) {
+                summaries.push((
+                    "CPU",
+                    format!("{:.0}%", usage),
+                    self.colours.avg_colour_style,
+                ));
+            }
+        }
+
+        if app_state.used_widgets.use_mem && !matches!(expanded_type, Mem | BasicMem) {
+            if let Some((percent, _)) = &app_state.converted_data.mem_labels {
+                summaries.push(("Mem", percent.trim().to_string(), self.colours.ram_style));
+            }
+            if let Some((percent, _)) = &app_state.converted_data.swap_labels {
+                summaries.push(("Swap", percent.trim().to_string(), self.colours.swap_style));
+            }
+        }
+
+        if app_state.used_widgets.use_net && !matches!(expanded_type, Net) {
+            let network_harvest = &app_state.data_collection.network_harvest;
+            let unit_type = &app_state.app_config_fields.network_unit_type;
+            let use_binary_prefix = app_state.app_config_fields.network_use_binary_prefix;
+            summaries.push((
+                "RX",
+                format_network_rate(network_harvest.rx as f64, unit_type, use_binary_prefix),
+                self.colours.rx_style,
+            ));
+            summaries.push((
+                "TX",
+                format_network_rate(network_harvest.tx as f64, unit_type, use_binary_prefix),
+                self.colours.tx_style,
+            ));
+        }
+
+        let mut spans = Vec::with_capacity(summaries.len() * 3);
+        for (label, value, style) in summaries {
+            if !spans.is_empty() {
+                spans.push(Span::raw("   "));
+            }
+            spans.push(Span::styled(format!("{} ", label), self.colours.text_style));
+            spans.push(Span::styled(value, style));
+        }
+
+        f.render_widget(
+            Paragraph::new(Spans::from(spans)),
+            Layout::default()
+                .horizontal_margin(1)
+                .constraints([Constraint::Length(1)])
+                .split(draw_loc)[0],
+        )
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
     app::{App, AxisScaling},
-    canvas::{drawing_utils::should_hide_x_label, Painter},
+    canvas::{
+        drawing_utils::{get_compare_line, should_hide_x_label},
+        Painter,
+    },
     components::time_graph::{GraphData, Point, TimeGraph},
     units::data_units::DataUnit,
     utils::gen_util::*,
@@ -9,6 +12,7 @@ use crate::{
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
     terminal::Frame,
     text::Text,
     widgets::{Block, Borders, Row, Table},
@@ -18,21 +22,29 @@ impl Painter {
     pub fn draw_network<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
+        let graph_loc = self.draw_table_filter(
+            f,
+            &app_state.net_state.filter,
+            app_state.current_widget.widget_id == widget_id,
+            draw_loc,
+            true,
+        );
+
         if app_state.app_config_fields.use_old_network_legend {
             const LEGEND_HEIGHT: u16 = 4;
             let network_chunk = Layout::default()
                 .direction(Direction::Vertical)
                 .margin(0)
                 .constraints([
-                    Constraint::Length(draw_loc.height.saturating_sub(LEGEND_HEIGHT)),
+                    Constraint::Length(graph_loc.height.saturating_sub(LEGEND_HEIGHT)),
                     Constraint::Length(LEGEND_HEIGHT),
                 ])
-                .split(draw_loc);
+                .split(graph_loc);
 
             self.draw_network_graph(f, app_state, network_chunk[0], widget_id, true);
             self.draw_network_labels(f, app_state, network_chunk[1], widget_id);
         } else {
-            self.draw_network_graph(f, app_state, draw_loc, widget_id, false);
+            self.draw_network_graph(f, app_state, graph_loc, widget_id, false);
         }
 
         if app_state.should_get_widget_bounds() {
@@ -51,9 +63,27 @@ impl Painter {
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
         hide_legend: bool,
     ) {
+        let widget_title = app_state.get_widget_title(widget_id, "Network");
         if let Some(network_widget_state) = app_state.net_state.widget_states.get_mut(&widget_id) {
-            let network_data_rx: &[(f64, f64)] = &app_state.converted_data.network_data_rx;
-            let network_data_tx: &[(f64, f64)] = &app_state.converted_data.network_data_tx;
+            let show_errors =
+                network_widget_state.show_errors && app_state.app_config_fields.show_network_errors;
+            let (network_data_rx, network_data_tx): (&[(f64, f64)], &[(f64, f64)]) = if show_errors
+            {
+                (
+                    app_state.converted_data.network_data_errors.points(),
+                    app_state.converted_data.network_data_drops.points(),
+                )
+            } else if network_widget_state.show_totals {
+                (
+                    app_state.converted_data.network_data_total_rx.points(),
+                    app_state.converted_data.network_data_total_tx.points(),
+                )
+            } else {
+                (
+                    app_state.converted_data.network_data_rx.points(),
+                    app_state.converted_data.network_data_tx.points(),
+                )
+            };
             let time_start = -(network_widget_state.current_display_time as f64);
             let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
             let x_bounds = [0, network_widget_state.current_display_time];
@@ -69,24 +99,28 @@ impl Painter {
             // - Old max time is off screen
             // - A new time interval is better and does not fit (check from end of vector to last checked; we only want to update if it is TOO big!)
 
-            // Find the maximal rx/tx so we know how to scale, and return it.
-            let (_best_time, max_entry) = get_max_entry(
-                network_data_rx,
-                network_data_tx,
-                time_start,
-                &app_state.app_config_fields.network_scale_type,
-                app_state.app_config_fields.network_use_binary_prefix,
-            );
-
-            let (max_range, labels) = adjust_network_data_point(
-                max_entry,
-                &app_state.app_config_fields.network_scale_type,
-                &app_state.app_config_fields.network_unit_type,
-                app_state.app_config_fields.network_use_binary_prefix,
-            );
-
-            let y_labels = labels.iter().map(|label| label.into()).collect::<Vec<_>>();
-            let y_bounds = [0.0, max_range];
+            let app_config_fields = &app_state.app_config_fields;
+            let get_y_axis = |first: &[Point], second: &[Point]| {
+                if show_errors {
+                    adjust_rate_data_point(get_max_rate_entry(first, second, time_start))
+                } else {
+                    // Find the maximal rx/tx so we know how to scale, and return it.
+                    let (_best_time, max_entry) = get_max_entry(
+                        first,
+                        second,
+                        time_start,
+                        &app_config_fields.network_scale_type,
+                        app_config_fields.network_use_binary_prefix,
+                    );
+
+                    adjust_network_data_point(
+                        max_entry,
+                        &app_config_fields.network_scale_type,
+                        &app_config_fields.network_unit_type,
+                        app_config_fields.network_use_binary_prefix,
+                    )
+                }
+            };
 
             let legend_constraints = if hide_legend {
                 (Constraint::Ratio(0, 1), Constraint::Ratio(0, 1))
@@ -95,37 +129,34 @@ impl Painter {
             };
 
             // TODO: Add support for clicking on legend to only show that value on chart.
-            let points = if app_state.app_config_fields.use_old_network_legend && !hide_legend {
-                vec![
+            let (first, second) = if show_errors {
+                (
                     GraphData {
                         points: network_data_rx,
-                        style: self.colours.rx_style,
-                        name: Some(format!("RX: {:7}", app_state.converted_data.rx_display).into()),
+                        style: self.colours.network_error_style,
+                        name: Some((&app_state.converted_data.error_display).into()),
                     },
                     GraphData {
                         points: network_data_tx,
-                        style: self.colours.tx_style,
-                        name: Some(format!("TX: {:7}", app_state.converted_data.tx_display).into()),
+                        style: self.colours.network_drop_style,
+                        name: Some((&app_state.converted_data.drop_display).into()),
                     },
+                )
+            } else if app_config_fields.use_old_network_legend && !hide_legend {
+                (
                     GraphData {
-                        points: &[],
-                        style: self.colours.total_rx_style,
-                        name: Some(
-                            format!("Total RX: {:7}", app_state.converted_data.total_rx_display)
-                                .into(),
-                        ),
+                        points: network_data_rx,
+                        style: self.colours.rx_style,
+                        name: Some(format!("RX: {:7}", app_state.converted_data.rx_display).into()),
                     },
                     GraphData {
-                        points: &[],
-                        style: self.colours.total_tx_style,
-                        name: Some(
-                            format!("Total TX: {:7}", app_state.converted_data.total_tx_display)
-                                .into(),
-                        ),
+                        points: network_data_tx,
+                        style: self.colours.tx_style,
+                        name: Some(format!("TX: {:7}", app_state.converted_data.tx_display).into()),
                     },
-                ]
+                )
             } else {
-                vec![
+                (
                     GraphData {
                         points: network_data_rx,
                         style: self.colours.rx_style,
@@ -136,48 +167,300 @@ impl Painter {
                         style: self.colours.tx_style,
                         name: Some((&app_state.converted_data.tx_display).into()),
                     },
-                ]
+                )
             };
 
-            TimeGraph {
-                use_dot: app_state.app_config_fields.use_dot,
-                x_bounds,
-                hide_x_labels,
-                y_bounds,
-                y_labels: &y_labels,
-                graph_style: self.colours.graph_style,
-                border_style,
-                title: " Network ".into(),
-                is_expanded: app_state.is_expanded,
-                title_style: self.colours.widget_title_style,
-                legend_constraints: Some(legend_constraints),
+            // The comparison overlay is dimmed, and is counted when scaling so it isn't cut off.
+            let (first_compare_line, second_compare_line) = match app_config_fields.compare_offset {
+                Some(offset) if app_state.is_comparing => {
+                    let display_time = network_widget_state.current_display_time;
+                    let (first_prefix, second_prefix) = if show_errors {
+                        ("Errors ", "Drops ")
+                    } else if network_widget_state.show_totals {
+                        ("Total RX ", "Total TX ")
+                    } else {
+                        ("RX ", "TX ")
+                    };
+                    (
+                        Some(get_compare_line(
+                            first.points,
+                            offset,
+                            display_time,
+                            first_prefix,
+                        )),
+                        Some(get_compare_line(
+                            second.points,
+                            offset,
+                            display_time,
+                            second_prefix,
+                        )),
+                    )
+                }
+                _ => (None, None),
+            };
+            let first_compare = first_compare_line.as_ref().map(|(line, name)| GraphData {
+                points: line,
+                style: first.style.add_modifier(Modifier::DIM),
+                name: Some(name.into()),
+            });
+            let second_compare = second_compare_line.as_ref().map(|(line, name)| GraphData {
+                points: line,
+                style: second.style.add_modifier(Modifier::DIM),
+                name: Some(name.into()),
+            });
+
+            // Entries that are only shown in the legend.
+            let mut legend_entries = Vec::new();
+            if app_config_fields.use_old_network_legend && !hide_legend && !show_errors {
+                legend_entries.push(GraphData {
+                    points: &[],
+                    style: self.colours.total_rx_style,
+                    name: Some(
+                        format!("Total RX: {:7}", app_state.converted_data.total_rx_display).into(),
+                    ),
+                });
+                legend_entries.push(GraphData {
+                    points: &[],
+                    style: self.colours.total_tx_style,
+                    name: Some(
+                        format!("Total TX: {:7}", app_state.converted_data.total_tx_display).into(),
+                    ),
+                });
+            }
+
+            // Outside of the errors view, the rate stats are shown under their graphs.
+            let (first_stats, second_stats) = match &app_state.converted_data.network_stats_display
+            {
+                Some((rx_stats_display, tx_stats_display)) if !show_errors => (
+                    Some(GraphData {
+                        points: &[],
+                        style: self.colours.rx_style,
+                        name: Some(rx_stats_display.into()),
+                    }),
+                    Some(GraphData {
+                        points: &[],
+                        style: self.colours.tx_style,
+                        name: Some(tx_stats_display.into()),
+                    }),
+                ),
+                _ => (None, None),
+            };
+
+            // Peaks are only shown for the rates, as totals only go up.
+            let peak_str = match &app_state.converted_data.peak_display.network {
+                Some(peak)
+                    if app_config_fields.show_peaks
+                        && !show_errors
+                        && !network_widget_state.show_totals =>
+                {
+                    format!("─ {} ", peak)
+                }
+                _ => String::default(),
+            };
+
+            // Wireless interfaces get a details line in the legend.
+            legend_entries.extend(app_state.converted_data.wireless_display.iter().map(
+                |wireless_display| GraphData {
+                    points: &[],
+                    style: self.colours.text_style,
+                    name: Some(wireless_display.into()),
+                },
+            ));
+
+            // As are interface addresses and the public IP, if enabled.
+            legend_entries.extend(app_state.converted_data.address_display.iter().map(
+                |address_display| GraphData {
+                    points: &[],
+                    style: self.colours.text_style,
+                    name: Some(address_display.into()),
+                },
+            ));
+
+            // Outside of the errors view, the error and drop counts are only shown in the legend.
+            if app_config_fields.show_network_errors && !show_errors {
+                legend_entries.push(GraphData {
+                    points: &[],
+                    style: self.colours.network_error_style,
+                    name: Some((&app_state.converted_data.error_display).into()),
+                });
+                legend_entries.push(GraphData {
+                    points: &[],
+                    style: self.colours.network_drop_style,
+                    name: Some((&app_state.converted_data.drop_display).into()),
+                });
+            }
+
+            if app_config_fields.network_split {
+                // Each graph is scaled on its own, so a small TX doesn't flatten against a large RX.
+                let (first_name, second_name) = if show_errors {
+                    ("Errors", "Drops")
+                } else if network_widget_state.show_totals {
+                    ("Total RX", "Total TX")
+                } else {
+                    ("RX", "TX")
+                };
+                let split_loc = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+                    .split(draw_loc);
+
+                let (first_max_range, first_labels) = get_y_axis(
+                    first.points,
+                    first_compare_line
+                        .as_ref()
+                        .map(|(line, _)| line.as_slice())
+                        .unwrap_or_default(),
+                );
+                let first_y_labels = first_labels
+                    .iter()
+                    .map(|label| label.into())
+                    .collect::<Vec<_>>();
+                let mut first_points = vec![first];
+                first_points.extend(first_compare);
+                first_points.extend(first_stats);
+                first_points.extend(legend_entries);
+
+                // The time labels are only shown under the lower graph.
+                TimeGraph {
+                    use_dot: app_config_fields.use_dot,
+                    x_bounds,
+                    hide_x_labels: true,
+                    y_bounds: [0.0, first_max_range],
+                    y_labels: &first_y_labels,
+                    graph_style: self.colours.graph_style,
+                    border_style,
+                    title: format!(" {} ({}) {}", widget_title, first_name, peak_str).into(),
+                    is_expanded: app_state.is_expanded,
+                    title_style: self.colours.widget_title_style,
+                    legend_constraints: Some(legend_constraints),
+                    graph_images: self.graph_image_sink(),
+                    gaps: &app_state.converted_data.gaps,
+                    shade_gaps: app_state.app_config_fields.shade_gaps,
+                }
+                .draw_time_graph(f, split_loc[0], &first_points);
+
+                let (second_max_range, second_labels) = get_y_axis(
+                    second.points,
+                    second_compare_line
+                        .as_ref()
+                        .map(|(line, _)| line.as_slice())
+                        .unwrap_or_default(),
+                );
+                let second_y_labels = second_labels
+                    .iter()
+                    .map(|label| label.into())
+                    .collect::<Vec<_>>();
+                let mut second_points = vec![second];
+                second_points.extend(second_compare);
+                second_points.extend(second_stats);
+
+                TimeGraph {
+                    use_dot: app_config_fields.use_dot,
+                    x_bounds,
+                    hide_x_labels,
+                    y_bounds: [0.0, second_max_range],
+                    y_labels: &second_y_labels,
+                    graph_style: self.colours.graph_style,
+                    border_style,
+                    title: format!(" {} ({}) ", widget_title, second_name).into(),
+                    is_expanded: false,
+                    title_style: self.colours.widget_title_style,
+                    legend_constraints: Some(legend_constraints),
+                    graph_images: self.graph_image_sink(),
+                    gaps: &app_state.converted_data.gaps,
+                    shade_gaps: app_state.app_config_fields.shade_gaps,
+                }
+                .draw_time_graph(f, split_loc[1], &second_points);
+            } else {
+                let (max_range, labels) = match (&first_compare_line, &second_compare_line) {
+                    (Some((first_line, _)), Some((second_line, _))) => {
+                        let current = get_y_axis(first.points, second.points);
+                        let compared = get_y_axis(first_line, second_line);
+                        if compared.0 > current.0 {
+                            compared
+                        } else {
+                            current
+                        }
+                    }
+                    _ => get_y_axis(first.points, second.points),
+                };
+                let y_labels = labels.iter().map(|label| label.into()).collect::<Vec<_>>();
+                let mut points = vec![first, second];
+                points.extend(first_compare);
+                points.extend(second_compare);
+                points.extend(first_stats);
+                points.extend(second_stats);
+                points.extend(legend_entries);
+
+                TimeGraph {
+                    use_dot: app_config_fields.use_dot,
+                    x_bounds,
+                    hide_x_labels,
+                    y_bounds: [0.0, max_range],
+                    y_labels: &y_labels,
+                    graph_style: self.colours.graph_style,
+                    border_style,
+                    title: if show_errors {
+                        format!(" {} (Errors) ", widget_title).into()
+                    } else if network_widget_state.show_totals {
+                        format!(" {} (Total) ", widget_title).into()
+                    } else {
+                        format!(" {} {}", widget_title, peak_str).into()
+                    },
+                    is_expanded: app_state.is_expanded,
+                    title_style: self.colours.widget_title_style,
+                    legend_constraints: Some(legend_constraints),
+                    graph_images: self.graph_image_sink(),
+                    gaps: &app_state.converted_data.gaps,
+                    shade_gaps: app_state.app_config_fields.shade_gaps,
+                }
+                .draw_time_graph(f, draw_loc, &points);
             }
-            .draw_time_graph(f, draw_loc, &points);
         }
     }
 
     fn draw_network_labels<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
-        const NETWORK_HEADERS: [&str; 4] = ["RX", "TX", "Total RX", "Total TX"];
+        const NETWORK_HEADERS: [&str; 6] = ["RX", "TX", "Total RX", "Total TX", "Errors", "Drops"];
 
         let rx_display = &app_state.converted_data.rx_display;
         let tx_display = &app_state.converted_data.tx_display;
         let total_rx_display = &app_state.converted_data.total_rx_display;
         let total_tx_display = &app_state.converted_data.total_tx_display;
 
+        let num_columns = if app_state.app_config_fields.show_network_errors {
+            6
+        } else {
+            4
+        };
+
         // Gross but I need it to work...
-        let total_network = vec![Row::new(vec![
+        let mut network_row = vec![
             Text::styled(rx_display, self.colours.rx_style),
             Text::styled(tx_display, self.colours.tx_style),
             Text::styled(total_rx_display, self.colours.total_rx_style),
             Text::styled(total_tx_display, self.colours.total_tx_style),
-        ])];
+        ];
+        if app_state.app_config_fields.show_network_errors {
+            network_row.push(Text::styled(
+                &app_state.converted_data.error_display,
+                self.colours.network_error_style,
+            ));
+            network_row.push(Text::styled(
+                &app_state.converted_data.drop_display,
+                self.colours.network_drop_style,
+            ));
+        }
+        let total_network = vec![Row::new(network_row)];
 
         // Draw
         f.render_widget(
             Table::new(total_network)
-                .header(Row::new(NETWORK_HEADERS.to_vec()).style(self.colours.table_header_style))
+                .header(
+                    Row::new(NETWORK_HEADERS[..num_columns].to_vec())
+                        .style(self.colours.table_header_style),
+                )
                 .block(Block::default().borders(Borders::ALL).border_style(
                     if app_state.current_widget.widget_id == widget_id {
                         self.colours.highlighted_border_style
@@ -187,9 +470,12 @@ impl Painter {
                 ))
                 .style(self.colours.text_style)
                 .widths(
-                    &((std::iter::repeat(draw_loc.width.saturating_sub(2) / 4))
-                        .take(4)
-                        .map(Constraint::Length)
+                    &((0..num_columns)
+                        .map(|_| {
+                            Constraint::Length(
+                                draw_loc.width.saturating_sub(2) / num_columns as u16,
+                            )
+                        })
                         .collect::<Vec<_>>()),
                 ),
             draw_loc,
@@ -317,32 +603,85 @@ fn get_max_entry(
     }
 }
 
+/// Returns the largest per-second count since the given time, like errors, dropped packets, or
+/// swapped pages.
+pub(super) fn get_max_rate_entry(first: &[Point], second: &[Point], time_start: f64) -> f64 {
+    first
+        .iter()
+        .chain(second)
+        .filter(|(time, _data)| *time >= time_start && *time <= 0.0)
+        .map(|(_time, data)| *data)
+        .fold(0.0, f64::max)
+}
+
+/// Returns the required max data point and labels for per-second counts, like errors and dropped
+/// packets, which are always shown on a linear scale.
+pub(super) fn adjust_rate_data_point(max_entry: f64) -> (f64, Vec<String>) {
+    // Leave some room above a single event per second, so a quiet graph has a sensible scale.
+    let (step, num_steps) = get_nice_axis(max_entry.max(1.0));
+    let labels = (0..=num_steps)
+        .map(|index| {
+            if index == 0 {
+                "0/s".to_string()
+            } else {
+                format_axis_value(f64::from(index) * step)
+            }
+        })
+        .collect();
+
+    (f64::from(num_steps) * step, pad_labels(labels))
+}
+
+/// Splits an axis from zero to just above `max` into a few steps of a "nice" size, which is 1, 2,
+/// 2.5, or 5 times a power of ten. Returns the size and number of steps.
+fn get_nice_axis(max: f64) -> (f64, u32) {
+    const TARGET_STEPS: f64 = 3.0;
+
+    if !max.is_normal() || max.is_sign_negative() {
+        return (1.0, 1);
+    }
+
+    let rough_step = max / TARGET_STEPS;
+    let magnitude = 10_f64.powf(rough_step.log10().floor());
+    let step = [1.0, 2.0, 2.5, 5.0, 10.0]
+        .iter()
+        .map(|multiplier| multiplier * magnitude)
+        .find(|step| *step >= rough_step)
+        .unwrap_or(10.0 * magnitude);
+
+    // Always leave room above the max, so it isn't drawn on the top border.
+    (step, (max / step).floor() as u32 + 1)
+}
+
+/// Formats an axis value with as few decimals as needed, like `2.5` or `10`.
+fn format_axis_value(value: f64) -> String {
+    let formatted = format!("{:.2}", value);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Right-aligns labels to the same width.
+fn pad_labels(labels: Vec<String>) -> Vec<String> {
+    let width = labels.iter().map(|label| label.len()).max().unwrap_or(0);
+    labels
+        .into_iter()
+        .map(|label| format!("{:>width$}", label, width = width))
+        .collect()
+}
+
 /// Returns the required max data point and labels.
 fn adjust_network_data_point(
     max_entry: f64, network_scale_type: &AxisScaling, network_unit_type: &DataUnit,
     network_use_binary_prefix: bool,
 ) -> (f64, Vec<String>) {
-    // So, we're going with an approach like this for linear data:
-    // - Main goal is to maximize the amount of information displayed given a specific height.
-    //   We don't want to drown out some data if the ranges are too far though!  Nor do we want to filter
-    //   out too much data...
-    // - Change the y-axis unit (kilo/kibi, mega/mebi...) dynamically based on max load.
-    //
-    // The idea is we take the top value, build our scale such that each "point" is a scaled version of that.
-    // So for example, let's say I use 390 Mb/s.  If I drew 4 segments, it would be 97.5, 195, 292.5, 390, and
-    // probably something like 438.75?
-    //
-    // So, how do we do this in tui-rs?  Well, if we  are using intervals that tie in perfectly to the max
-    // value we want... then it's actually not that hard.  Since tui-rs accepts a vector as labels and will
-    // properly space them all out... we just work with that and space it out properly.
+    // For linear data, the axis goes from zero to just above the largest visible value in a few
+    // steps of a "nice" size, like 0, 2.5, 5, and 7.5 MiB, in whichever unit keeps the labels
+    // short. As the largest visible value changes, so does the axis.
     //
-    // Dynamic chart idea based off of FreeNAS's chart design.
-    //
-    // ===
-    //
-    // For log data, we just use the old method of log intervals (kilo/mega/giga/etc.).  Keep it nice and simple.
-
-    // Now just check the largest unit we correspond to... then proceed to build some entries from there!
+    // For log data, we just use the old method of log intervals (kilo/mega/giga/etc.), which are
+    // already nice.
 
     let unit_char = match network_unit_type {
         DataUnit::Byte => "B",
@@ -351,67 +690,50 @@ fn adjust_network_data_point(
 
     match network_scale_type {
         AxisScaling::Linear => {
-            let (k_limit, m_limit, g_limit, t_limit) = if network_use_binary_prefix {
-                (
-                    KIBI_LIMIT_F64,
-                    MEBI_LIMIT_F64,
-                    GIBI_LIMIT_F64,
-                    TEBI_LIMIT_F64,
-                )
+            let units = if network_use_binary_prefix {
+                [
+                    (1.0, ""),
+                    (KIBI_LIMIT_F64, "Ki"),
+                    (MEBI_LIMIT_F64, "Mi"),
+                    (GIBI_LIMIT_F64, "Gi"),
+                    (TEBI_LIMIT_F64, "Ti"),
+                ]
             } else {
-                (
-                    KILO_LIMIT_F64,
-                    MEGA_LIMIT_F64,
-                    GIGA_LIMIT_F64,
-                    TERA_LIMIT_F64,
-                )
+                [
+                    (1.0, ""),
+                    (KILO_LIMIT_F64, "K"),
+                    (MEGA_LIMIT_F64, "M"),
+                    (GIGA_LIMIT_F64, "G"),
+                    (TERA_LIMIT_F64, "T"),
+                ]
             };
 
-            let bumped_max_entry = max_entry * 1.5; // We use the bumped up version to calculate our unit type.
-            let (max_value_scaled, unit_prefix, unit_type): (f64, &str, &str) =
-                if bumped_max_entry < k_limit {
-                    (max_entry, "", unit_char)
-                } else if bumped_max_entry < m_limit {
-                    (
-                        max_entry / k_limit,
-                        if network_use_binary_prefix { "Ki" } else { "K" },
-                        unit_char,
-                    )
-                } else if bumped_max_entry < g_limit {
-                    (
-                        max_entry / m_limit,
-                        if network_use_binary_prefix { "Mi" } else { "M" },
-                        unit_char,
-                    )
-                } else if bumped_max_entry < t_limit {
-                    (
-                        max_entry / g_limit,
-                        if network_use_binary_prefix { "Gi" } else { "G" },
-                        unit_char,
-                    )
-                } else {
-                    (
-                        max_entry / t_limit,
-                        if network_use_binary_prefix { "Ti" } else { "T" },
-                        unit_char,
+            // Use the smallest unit where the top of the axis is under 1000 of it.
+            let (unit_limit, unit_prefix, step, num_steps) = units
+                .iter()
+                .map(|&(unit_limit, unit_prefix)| {
+                    let (step, num_steps) = get_nice_axis(max_entry / unit_limit);
+                    (unit_limit, unit_prefix, step, num_steps)
+                })
+                .find(|(_, _, step, num_steps)| step * f64::from(*num_steps) < 1000.0)
+                .unwrap_or_else(|| {
+                    let (unit_limit, unit_prefix) = units[units.len() - 1];
+                    let (step, num_steps) = get_nice_axis(max_entry / unit_limit);
+                    (unit_limit, unit_prefix, step, num_steps)
+                });
+
+            let labels = (0..=num_steps)
+                .map(|index| {
+                    format!(
+                        "{}{}{}",
+                        format_axis_value(f64::from(index) * step),
+                        unit_prefix,
+                        unit_char
                     )
-                };
+                })
+                .collect();
 
-            // Finally, build an acceptable range starting from there, using the given height!
-            // Note we try to put more of a weight on the bottom section vs. the top, since the top has less data.
-
-            let base_unit = max_value_scaled;
-            let labels: Vec<String> = vec![
-                format!("0{}{}", unit_prefix, unit_type),
-                format!("{:.1}", base_unit * 0.5),
-                format!("{:.1}", base_unit),
-                format!("{:.1}", base_unit * 1.5),
-            ]
-            .into_iter()
-            .map(|s| format!("{:>5}", s)) // Pull 5 as the longest legend value is generally going to be 5 digits (if they somehow hit over 5 terabits per second)
-            .collect();
-
-            (bumped_max_entry, labels)
+            (f64::from(num_steps) * step * unit_limit, pad_labels(labels))
         }
         AxisScaling::Log => {
             let (m_limit, g_limit, t_limit) = if network_use_binary_prefix {
@@ -519,3 +841,35 @@ fn adjust_network_data_point(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_nice_axis() {
+        assert_eq!(get_nice_axis(6.5), (2.5, 3));
+        assert_eq!(get_nice_axis(10.0), (5.0, 3));
+        assert_eq!(get_nice_axis(390.0), (200.0, 2));
+        assert_eq!(get_nice_axis(0.9), (0.5, 2));
+        assert_eq!(get_nice_axis(0.0), (1.0, 1));
+    }
+
+    #[test]
+    fn test_adjust_network_data_point() {
+        let (max, labels) = adjust_network_data_point(
+            6.5 * MEBI_LIMIT_F64,
+            &AxisScaling::Linear,
+            &DataUnit::Byte,
+            true,
+        );
+        assert_eq!(max, 7.5 * MEBI_LIMIT_F64);
+        assert_eq!(labels, vec!["  0MiB", "2.5MiB", "  5MiB", "7.5MiB"]);
+
+        // Switches to the next unit rather than going past 1000.
+        let (max, labels) =
+            adjust_network_data_point(950_000.0, &AxisScaling::Linear, &DataUnit::Bit, false);
+        assert_eq!(max, 1_000_000.0);
+        assert_eq!(labels, vec!["  0Mb", "0.5Mb", "  1Mb"]);
+    }
+}
@@ -0,0 +1,123 @@
+use std::borrow::Cow;
+
+use crate::{
+    app::App,
+    canvas::{drawing_utils::should_hide_x_label, Painter},
+    components::time_graph::{GraphData, TimeGraph},
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    terminal::Frame,
+};
+
+impl Painter {
+    pub fn draw_plugin_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        if let Some(plugin_widget_state) = app_state.plugin_state.widget_states.get_mut(&widget_id)
+        {
+            let widget_title = app_state
+                .widget_map
+                .get(&widget_id)
+                .and_then(|widget| widget.title.clone())
+                .unwrap_or_else(|| plugin_widget_state.plugin.clone());
+            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let x_bounds = [0, plugin_widget_state.current_display_time];
+            let hide_x_labels = should_hide_x_label(
+                app_state.app_config_fields.hide_time,
+                app_state.app_config_fields.autohide_time,
+                &mut plugin_widget_state.autohide_timer,
+                draw_loc,
+            );
+
+            let plugin_data = app_state
+                .converted_data
+                .plugin_data
+                .get(&plugin_widget_state.plugin);
+            let points: Vec<GraphData<'_>> = plugin_data
+                .map(|plugin_data| {
+                    plugin_data
+                        .history
+                        .iter()
+                        .zip(plugin_data.legend.iter())
+                        .zip(self.colours.series_colour_styles.iter().cycle())
+                        .map(|((history, legend), style)| GraphData {
+                            points: history,
+                            style: *style,
+                            name: Some(legend.into()),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            // Plugins can report anything, so scale the graph to fit whatever is shown, with a
+            // bit of headroom. Zero is always included.
+            let (min_value, max_value) = points
+                .iter()
+                .flat_map(|graph_data| graph_data.points.iter())
+                .fold((0.0_f64, 0.0_f64), |(min, max), (_time, value)| {
+                    (min.min(*value), max.max(*value))
+                });
+            let lower_bound = (min_value * 1.1).floor();
+            let upper_bound = if max_value > 0.0 {
+                (max_value * 1.1).ceil()
+            } else if lower_bound < 0.0 {
+                0.0
+            } else {
+                1.0
+            };
+            let app_config_fields = &app_state.app_config_fields;
+            let unit = app_config_fields
+                .plugins
+                .iter()
+                .find(|plugin| plugin.name == plugin_widget_state.plugin)
+                .map(|plugin| &plugin.unit)
+                .or_else(|| {
+                    app_config_fields
+                        .derived_metrics
+                        .iter()
+                        .find(|derived_metric| derived_metric.name == plugin_widget_state.plugin)
+                        .map(|derived_metric| &derived_metric.unit)
+                })
+                .cloned()
+                .flatten()
+                .unwrap_or_default();
+            let y_labels = [
+                Cow::Owned(format!("{}{}", lower_bound, unit)),
+                Cow::Owned(format!("{}{}", upper_bound, unit)),
+            ];
+
+            TimeGraph {
+                use_dot: app_state.app_config_fields.use_dot,
+                x_bounds,
+                hide_x_labels,
+                y_bounds: [lower_bound, upper_bound],
+                y_labels: &y_labels,
+                graph_style: self.colours.graph_style,
+                border_style,
+                title: match plugin_data.and_then(|plugin_data| plugin_data.error.as_ref()) {
+                    Some(error) => format!(" {} ({}) ", widget_title, error).into(),
+                    None => format!(" {} ", widget_title).into(),
+                },
+                is_expanded: app_state.is_expanded,
+                title_style: self.colours.widget_title_style,
+                legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                graph_images: self.graph_image_sink(),
+                gaps: &app_state.converted_data.gaps,
+                shade_gaps: app_state.app_config_fields.shade_gaps,
+            }
+            .draw_time_graph(f, draw_loc, &points);
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
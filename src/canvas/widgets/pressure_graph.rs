@@ -0,0 +1,92 @@
+use std::borrow::Cow;
+
+use crate::{
+    app::App,
+    canvas::{drawing_utils::should_hide_x_label, Painter},
+    components::time_graph::{GraphData, TimeGraph},
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    terminal::Frame,
+};
+
+impl Painter {
+    pub fn draw_pressure_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let widget_title = app_state.get_widget_title(widget_id, "Pressure");
+        if let Some(pressure_widget_state) =
+            app_state.pressure_state.widget_states.get_mut(&widget_id)
+        {
+            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let x_bounds = [0, pressure_widget_state.current_display_time];
+            let hide_x_labels = should_hide_x_label(
+                app_state.app_config_fields.hide_time,
+                app_state.app_config_fields.autohide_time,
+                &mut pressure_widget_state.autohide_timer,
+                draw_loc,
+            );
+
+            let points: Vec<GraphData<'_>> = app_state
+                .converted_data
+                .pressure_data
+                .iter()
+                .zip(self.colours.series_colour_styles.iter().cycle())
+                .map(|(series, style)| GraphData {
+                    points: &series.data,
+                    style: *style,
+                    name: Some(format!("{}: {}", series.name, series.label).into()),
+                })
+                .collect();
+
+            // Stalls are usually a small fraction of the time, so scale the graph to the highest
+            // value shown rather than always going up to 100%.
+            let max_pressure = points
+                .iter()
+                .flat_map(|graph_data| graph_data.points.iter())
+                .fold(0.0_f64, |acc, (_time, pressure)| acc.max(*pressure));
+            let pressure_upper_bound = if max_pressure > 0.0 {
+                (max_pressure * 1.1).min(100.0)
+            } else {
+                1.0
+            };
+            let y_labels = [
+                Cow::Borrowed("0%"),
+                Cow::Owned(format!("{:.1}%", pressure_upper_bound)),
+            ];
+
+            TimeGraph {
+                use_dot: app_state.app_config_fields.use_dot,
+                x_bounds,
+                hide_x_labels,
+                y_bounds: [0.0, pressure_upper_bound],
+                y_labels: &y_labels,
+                graph_style: self.colours.graph_style,
+                border_style,
+                title: if points.is_empty() {
+                    format!(" {} (Unavailable) ", widget_title).into()
+                } else {
+                    format!(" {} ", widget_title).into()
+                },
+                is_expanded: app_state.is_expanded,
+                title_style: self.colours.widget_title_style,
+                legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                graph_images: self.graph_image_sink(),
+                gaps: &app_state.converted_data.gaps,
+                shade_gaps: app_state.app_config_fields.shade_gaps,
+            }
+            .draw_time_graph(f, draw_loc, &points);
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
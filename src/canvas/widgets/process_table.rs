@@ -1,9 +1,13 @@
 use crate::{
-    app::App,
+    app::{
+        widgets::{ProcWidget, ProcWidgetMode, ProcessSearchState},
+        App,
+    },
     canvas::{drawing_utils::get_search_start_position, Painter},
     components::text_table::{TextTable, TextTableTitle},
     constants::*,
     data_conversion::{TableData, TableRow},
+    Pid,
 };
 
 use tui::{
@@ -76,6 +80,7 @@ impl Painter {
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
         widget_id: u64,
     ) {
+        let widget_title = app_state.get_widget_title(widget_id, "Processes");
         let should_get_widget_bounds = app_state.should_get_widget_bounds();
         if let Some(proc_widget_state) = app_state.proc_state.widget_states.get_mut(&widget_id) {
             let recalculate_column_widths =
@@ -91,14 +96,70 @@ impl Painter {
                 (self.colours.border_style, self.colours.text_style)
             };
 
-            // TODO: [Refactor] This is an ugly hack to add the disabled style...
+            // TODO: [Refactor] This is an ugly hack to add the disabled, stopped, and marked styles...
             // this could be solved by storing style locally to the widget.
+            // Grouped rows show a count rather than a PID, so they are never shown as stopped or
+            // marked.
+            let is_grouped = matches!(proc_widget_state.mode, ProcWidgetMode::Grouped);
+            let process_harvest = &app_state.data_collection.process_data.process_harvest;
+            let marked_pids = proc_widget_state.get_marked_pids();
             for row in &mut proc_widget_state.table_data.data {
-                if let TableRow::Styled(_, style) = row {
+                let pid = if is_grouped {
+                    None
+                } else {
+                    row.row()[ProcWidget::PID_OR_COUNT]
+                        .main_text()
+                        .parse::<Pid>()
+                        .ok()
+                };
+                let is_marked = pid
+                    .map(|pid| marked_pids.binary_search(&pid).is_ok())
+                    .unwrap_or(false);
+                let is_stopped = pid
+                    .and_then(|pid| process_harvest.get(&pid))
+                    .map(|process| process.is_stopped())
+                    .unwrap_or(false);
+
+                let row_style = if is_marked {
+                    Some(self.colours.marked_process_style)
+                } else if is_stopped {
+                    Some(self.colours.stopped_process_style)
+                } else {
+                    None
+                };
+
+                if let Some(row_style) = row_style {
+                    match row {
+                        TableRow::Raw(cells) => {
+                            *row = TableRow::Styled(std::mem::take(cells), row_style);
+                        }
+                        TableRow::Styled(_, style) => *style = row_style,
+                    }
+                } else if let TableRow::Styled(_, style) = row {
                     *style = style.patch(self.colours.disabled_text_style);
                 }
             }
 
+            let following = if proc_widget_state.is_following {
+                "(following) "
+            } else {
+                ""
+            };
+            let num_queued = app_state.kill_queue_state.queue.len();
+            let queued = if num_queued > 0 {
+                format!("({} queued to kill) ", num_queued)
+            } else {
+                String::new()
+            };
+            let title = match proc_widget_state.get_saved_filter_name() {
+                Some(filter_name) => format!(
+                    " {} [{}] {}{}",
+                    widget_title, filter_name, following, queued
+                )
+                .into(),
+                None => format!(" {} {}{}", widget_title, following, queued).into(),
+            };
+
             TextTable {
                 table_gap: app_state.app_config_fields.table_gap,
                 is_force_redraw: app_state.is_force_redraw,
@@ -107,7 +168,7 @@ impl Painter {
                 border_style,
                 highlighted_text_style,
                 title: Some(TextTableTitle {
-                    title: " Processes ".into(),
+                    title,
                     is_expanded: app_state.is_expanded,
                 }),
                 is_on_widget,
@@ -223,43 +284,7 @@ impl Painter {
                 search_vec
             })];
 
-            // Text options shamelessly stolen from VS Code.
-            let case_style = if !proc_widget_state.proc_search.is_ignoring_case {
-                self.colours.currently_selected_text_style
-            } else {
-                self.colours.text_style
-            };
-
-            let whole_word_style = if proc_widget_state.proc_search.is_searching_whole_word {
-                self.colours.currently_selected_text_style
-            } else {
-                self.colours.text_style
-            };
-
-            let regex_style = if proc_widget_state.proc_search.is_searching_with_regex {
-                self.colours.currently_selected_text_style
-            } else {
-                self.colours.text_style
-            };
-
-            // TODO: [MOUSE] Mouse support for these in search
-            // TODO: [MOVEMENT] Movement support for these in search
-            let option_text = Spans::from(vec![
-                Span::styled(
-                    format!("Case({})", if self.is_mac_os { "F1" } else { "Alt+C" }),
-                    case_style,
-                ),
-                Span::raw("  "),
-                Span::styled(
-                    format!("Whole({})", if self.is_mac_os { "F2" } else { "Alt+W" }),
-                    whole_word_style,
-                ),
-                Span::raw("  "),
-                Span::styled(
-                    format!("Regex({})", if self.is_mac_os { "F3" } else { "Alt+R" }),
-                    regex_style,
-                ),
-            ]);
+            let option_text = self.get_search_options_text(&proc_widget_state.proc_search);
 
             search_text.push(Spans::from(Span::styled(
                 if let Some(err) = &proc_widget_state.proc_search.search_state.error_message {
@@ -332,6 +357,48 @@ impl Painter {
         }
     }
 
+    /// Returns the line of search options, with the enabled ones highlighted. This is also used by
+    /// the filters of other tables.
+    pub fn get_search_options_text(&self, search: &ProcessSearchState) -> Spans<'static> {
+        // Text options shamelessly stolen from VS Code.
+        let case_style = if !search.is_ignoring_case {
+            self.colours.currently_selected_text_style
+        } else {
+            self.colours.text_style
+        };
+
+        let whole_word_style = if search.is_searching_whole_word {
+            self.colours.currently_selected_text_style
+        } else {
+            self.colours.text_style
+        };
+
+        let regex_style = if search.is_searching_with_regex {
+            self.colours.currently_selected_text_style
+        } else {
+            self.colours.text_style
+        };
+
+        // TODO: [MOUSE] Mouse support for these in search
+        // TODO: [MOVEMENT] Movement support for these in search
+        Spans::from(vec![
+            Span::styled(
+                format!("Case({})", if self.is_mac_os { "F1" } else { "Alt+C" }),
+                case_style,
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("Whole({})", if self.is_mac_os { "F2" } else { "Alt+W" }),
+                whole_word_style,
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("Regex({})", if self.is_mac_os { "F3" } else { "Alt+R" }),
+                regex_style,
+            ),
+        ])
+    }
+
     /// Draws the process sort box.
     /// - `widget_id` represents the widget ID of the sort box itself --- NOT the process widget
     /// state that is stored.
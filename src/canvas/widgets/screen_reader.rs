@@ -0,0 +1,320 @@
+use itertools::Itertools;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Layout, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+
+use crate::{
+    app::{layout_manager::BottomWidgetType, App},
+    canvas::Painter,
+    components::text_table::{strip_sort_shortcut, TableComponentHeader, TableComponentState},
+    data_conversion::{CpuEntry, TableData},
+};
+
+/// How many entries of a list are shown for widgets that aren't selected.
+const MAX_UNSELECTED_ENTRIES: usize = 5;
+
+/// The lines of text for one widget, and which of them is selected, if any.
+struct WidgetText {
+    lines: Vec<String>,
+    selected_line: Option<usize>,
+}
+
+impl WidgetText {
+    fn new(lines: Vec<String>) -> Self {
+        Self {
+            lines,
+            selected_line: None,
+        }
+    }
+}
+
+impl Painter {
+    /// Draws every widget in the layout as plain lines of text, for screen readers. The first line
+    /// names the selected widget, so it's read out whenever the selection changes, and the cursor
+    /// is put on the selected widget or table row.
+    pub fn draw_screen_reader_text<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    ) {
+        use BottomWidgetType::*;
+
+        let current_widget = &app_state.current_widget;
+        let selected_widget_id = match current_widget.widget_type {
+            CpuLegend | ProcSearch => current_widget.widget_id - 1,
+            ProcSort => current_widget.widget_id - 2,
+            _ => current_widget.widget_id,
+        };
+
+        let mut announcement = String::new();
+        let mut lines: Vec<String> = Vec::new();
+        let mut heading_line = 0;
+        let mut cursor_line = 0;
+
+        for widget in self
+            .widget_layout
+            .rows
+            .iter()
+            .flat_map(|row| &row.children)
+            .flat_map(|col| &col.children)
+            .flat_map(|col_row| &col_row.children)
+        {
+            let widget_type = match widget.widget_type {
+                BasicCpu => Cpu,
+                BasicMem => Mem,
+                BasicNet => Net,
+                ref widget_type => widget_type.clone(),
+            };
+            let name = widget_type.get_pretty_name();
+            if name.is_empty() {
+                continue;
+            }
+
+            let is_selected = widget.widget_id == selected_widget_id;
+            let text = get_widget_text(app_state, &widget_type, widget.widget_id, is_selected);
+            let num_lines = text.lines.len();
+
+            if is_selected {
+                heading_line = lines.len();
+                cursor_line = heading_line + text.selected_line.map_or(0, |line| line + 1);
+                announcement = format!("Selected: {}", name);
+                if let Some(line) = text.selected_line {
+                    announcement.push_str(&format!(", {} of {}", line + 1, num_lines));
+                }
+                lines.push(format!("> {}:", name));
+            } else {
+                lines.push(format!("{}:", name));
+            }
+
+            for (index, line) in text.lines.into_iter().enumerate() {
+                if is_selected && text.selected_line == Some(index) {
+                    lines.push(format!("  > {}", line));
+                } else {
+                    lines.push(format!("    {}", line));
+                }
+            }
+        }
+
+        let split_loc = Layout::default()
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(draw_loc);
+        let body_height = usize::from(split_loc[1].height);
+
+        // Keep the selected widget at the top if it fits, and otherwise scroll so its selected row
+        // is at the bottom.
+        let scroll = if cursor_line < heading_line + body_height {
+            heading_line.min(lines.len().saturating_sub(body_height))
+        } else {
+            cursor_line + 1 - body_height
+        };
+
+        f.render_widget(
+            Paragraph::new(Span::styled(announcement, self.colours.widget_title_style)),
+            split_loc[0],
+        );
+        f.render_widget(
+            Paragraph::new(
+                lines
+                    .into_iter()
+                    .skip(scroll)
+                    .take(body_height)
+                    .map(|line| Spans::from(Span::styled(line, self.colours.text_style)))
+                    .collect::<Vec<_>>(),
+            ),
+            split_loc[1],
+        );
+
+        if body_height > 0 {
+            f.set_cursor(
+                split_loc[1].x,
+                split_loc[1].y + (cursor_line - scroll) as u16,
+            );
+        }
+    }
+}
+
+/// Returns the text for a widget. Lists are cut short unless the widget is selected.
+fn get_widget_text(
+    app_state: &App, widget_type: &BottomWidgetType, widget_id: u64, is_selected: bool,
+) -> WidgetText {
+    use BottomWidgetType::*;
+
+    let converted_data = &app_state.converted_data;
+    let mut text = match widget_type {
+        Cpu => WidgetText::new(
+            converted_data
+                .cpu_data
+                .iter()
+                .filter(|cpu| cpu.entry != CpuEntry::All)
+                .map(|cpu| format!("{}: {}", cpu.cpu_name.trim(), cpu.legend_value.trim()))
+                .collect(),
+        ),
+        Mem => WidgetText::new(
+            [
+                ("RAM", &converted_data.mem_labels),
+                ("Swap", &converted_data.swap_labels),
+            ]
+            .iter()
+            .filter_map(|(name, labels)| {
+                let (percent, frac) = labels.as_ref()?;
+                Some(format!("{}: {}, {}", name, percent.trim(), frac.trim()))
+            })
+            .collect(),
+        ),
+        Net => WidgetText::new(vec![
+            format!("RX: {}", converted_data.rx_display),
+            format!("TX: {}", converted_data.tx_display),
+            format!("Total RX: {}", converted_data.total_rx_display),
+            format!("Total TX: {}", converted_data.total_tx_display),
+        ]),
+        Proc => match app_state.proc_state.widget_states.get(&widget_id) {
+            Some(state) => get_table_text(&state.table_state, &state.table_data),
+            None => WidgetText::new(vec![]),
+        },
+        Temp => match app_state.temp_state.widget_states.get(&widget_id) {
+            Some(state) => get_table_text(&state.table_state, &state.table_data),
+            None => WidgetText::new(vec![]),
+        },
+        Disk => match app_state.disk_state.widget_states.get(&widget_id) {
+            Some(state) => get_table_text(&state.table_state, &state.table_data),
+            None => WidgetText::new(vec![]),
+        },
+        Cgroup => match app_state.cgroup_state.widget_states.get(&widget_id) {
+            Some(state) => get_table_text(&state.table_state, &converted_data.cgroup_data),
+            None => WidgetText::new(vec![]),
+        },
+        Events => match app_state.event_state.widget_states.get(&widget_id) {
+            Some(state) => get_table_text(&state.table_state, &converted_data.event_data),
+            None => WidgetText::new(vec![]),
+        },
+        Users => match app_state.user_state.widget_states.get(&widget_id) {
+            Some(state) => get_table_text(&state.table_state, &converted_data.user_data),
+            None => WidgetText::new(vec![]),
+        },
+        SystemInfo => WidgetText::new(
+            converted_data
+                .system_info_display
+                .iter()
+                .map(|(label, value)| format!("{}: {}", label, value))
+                .collect(),
+        ),
+        TopProcesses => WidgetText::new(
+            converted_data
+                .top_process_display
+                .iter()
+                .map(|(label, value)| format!("{}: {}", label, value))
+                .collect(),
+        ),
+        Battery => WidgetText::new(
+            converted_data
+                .battery_data
+                .iter()
+                .map(|battery| {
+                    let mut line = format!(
+                        "{}: {:.0}%, {}",
+                        battery.battery_name, battery.charge_percentage, battery.watt_consumption
+                    );
+                    if let Some(duration) = &battery.duration_until_full {
+                        line.push_str(&format!(", full in {}", duration));
+                    } else if let Some(duration) = &battery.duration_until_empty {
+                        line.push_str(&format!(", empty in {}", duration));
+                    }
+                    line
+                })
+                .collect(),
+        ),
+        Power => WidgetText::new(
+            [
+                ("Package", &converted_data.power_data.package_label),
+                ("Cores", &converted_data.power_data.core_label),
+                ("DRAM", &converted_data.power_data.dram_label),
+            ]
+            .iter()
+            .filter_map(|(name, label)| Some(format!("{}: {}", name, label.as_ref()?)))
+            .collect(),
+        ),
+        Pressure => WidgetText::new(
+            converted_data
+                .pressure_data
+                .iter()
+                .map(|pressure| format!("{}: {}", pressure.name, pressure.label))
+                .collect(),
+        ),
+        LoadAvg => {
+            let load_avg = converted_data.load_avg_data;
+            WidgetText::new(vec![format!(
+                "1 minute: {:.2}, 5 minutes: {:.2}, 15 minutes: {:.2}",
+                load_avg[0], load_avg[1], load_avg[2]
+            )])
+        }
+        Latency => WidgetText::new(converted_data.latency_legend.clone()),
+        Plugin => WidgetText::new(
+            app_state
+                .plugin_state
+                .widget_states
+                .get(&widget_id)
+                .and_then(|state| converted_data.plugin_data.get(&state.plugin))
+                .map(|plugin_data| match &plugin_data.error {
+                    Some(error) => vec![error.clone()],
+                    None => plugin_data.legend.clone(),
+                })
+                .unwrap_or_default(),
+        ),
+        Frequency => WidgetText::new(
+            converted_data
+                .frequency_data
+                .current_average
+                .map(|average| vec![format!("Average: {:.0} MHz", average)])
+                .unwrap_or_default(),
+        ),
+        _ => WidgetText::new(vec![]),
+    };
+
+    if is_selected {
+        if let Some(selected_line) = text.selected_line {
+            text.selected_line = Some(selected_line.min(text.lines.len().saturating_sub(1)));
+        }
+    } else if text.lines.len() > MAX_UNSELECTED_ENTRIES {
+        let num_hidden = text.lines.len() - MAX_UNSELECTED_ENTRIES;
+        text.lines.truncate(MAX_UNSELECTED_ENTRIES);
+        text.lines.push(format!("and {} more", num_hidden));
+    }
+
+    text
+}
+
+/// Returns each row of a table as a line, with each shown cell after its column's name.
+fn get_table_text<H: TableComponentHeader>(
+    table_state: &TableComponentState<H>, table_data: &TableData,
+) -> WidgetText {
+    let lines = table_data
+        .data
+        .iter()
+        .map(|row| {
+            table_state
+                .columns
+                .iter()
+                .zip(row.row())
+                .filter(|(column, _)| !column.is_hidden)
+                .map(|(column, cell)| {
+                    format!(
+                        "{} {}",
+                        strip_sort_shortcut(column.header.header_text().main_text().trim()),
+                        cell.main_text().trim()
+                    )
+                })
+                .join(", ")
+        })
+        .collect::<Vec<_>>();
+
+    WidgetText {
+        selected_line: if lines.is_empty() {
+            None
+        } else {
+            Some(table_state.current_scroll_position)
+        },
+        lines,
+    }
+}
@@ -0,0 +1,85 @@
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{app::widgets::TableFilter, canvas::Painter};
+
+impl Painter {
+    /// Draws the filter of a widget at its bottom if it's shown, and returns the area left for the
+    /// rest of the widget. The search options are only shown while the filter is being typed in.
+    pub fn draw_table_filter<B: Backend>(
+        &self, f: &mut Frame<'_, B>, filter: &TableFilter, is_on_widget: bool, draw_loc: Rect,
+        draw_border: bool,
+    ) -> Rect {
+        if !filter.is_shown() {
+            return draw_loc;
+        }
+
+        let is_typing = is_on_widget && filter.is_open();
+        let text_height = if is_typing { 2 } else { 1 };
+        let filter_height = if draw_border {
+            text_height + 2
+        } else {
+            text_height
+        };
+        let filter_chunk = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(filter_height)])
+            .split(draw_loc);
+
+        let mut query_text = vec![
+            Span::styled(
+                "Filter: ",
+                if is_typing {
+                    self.colours.table_header_style
+                } else {
+                    self.colours.text_style
+                },
+            ),
+            Span::styled(filter.query().to_string(), self.colours.text_style),
+        ];
+        if is_typing {
+            query_text.push(Span::styled(
+                " ",
+                self.colours.currently_selected_text_style,
+            ));
+        }
+
+        let mut filter_text = vec![Spans::from(query_text)];
+        if is_typing {
+            filter_text.push(match &filter.search.search_state.error_message {
+                Some(err) => Spans::from(Span::styled(
+                    err.lines().last().unwrap_or_default().to_string(),
+                    self.colours.invalid_query_style,
+                )),
+                None => self.get_search_options_text(&filter.search),
+            });
+        }
+
+        let border_style = if filter.search.search_state.is_invalid_search {
+            self.colours.invalid_query_style
+        } else if is_typing {
+            self.colours.highlighted_border_style
+        } else {
+            self.colours.border_style
+        };
+        let filter_block = if draw_border {
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+        } else {
+            Block::default().borders(Borders::NONE)
+        };
+
+        f.render_widget(
+            Paragraph::new(filter_text).block(filter_block),
+            filter_chunk[1],
+        );
+
+        filter_chunk[0]
+    }
+}
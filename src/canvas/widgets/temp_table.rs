@@ -11,9 +11,17 @@ impl Painter {
         &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
         widget_id: u64,
     ) {
+        let widget_title = app_state.get_widget_title(widget_id, "Temperatures");
         let recalculate_column_widths = app_state.should_get_widget_bounds();
         if let Some(temp_widget_state) = app_state.temp_state.widget_states.get_mut(&widget_id) {
             let is_on_widget = app_state.current_widget.widget_id == widget_id;
+            let table_draw_loc = self.draw_table_filter(
+                f,
+                &temp_widget_state.filter,
+                is_on_widget,
+                draw_loc,
+                draw_border,
+            );
 
             let (border_style, highlighted_text_style) = if is_on_widget {
                 (
@@ -31,7 +39,7 @@ impl Painter {
                 border_style,
                 highlighted_text_style,
                 title: Some(TextTableTitle {
-                    title: " Temperatures ".into(),
+                    title: format!(" {} ", widget_title).into(),
                     is_expanded: app_state.is_expanded,
                 }),
                 is_on_widget,
@@ -43,9 +51,9 @@ impl Painter {
             }
             .draw_text_table(
                 f,
-                draw_loc,
+                table_draw_loc,
                 &mut temp_widget_state.table_state,
-                &app_state.converted_data.temp_sensor_data,
+                &temp_widget_state.table_data,
                 app_state.widget_map.get_mut(&widget_id),
             );
         }
@@ -0,0 +1,88 @@
+use crate::{app::App, canvas::Painter, constants::*};
+
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+impl Painter {
+    pub fn draw_top_processes<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
+        widget_id: u64,
+    ) {
+        let widget_title = app_state.get_widget_title(widget_id, "Top Processes");
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        let border_style = if is_on_widget {
+            self.colours.highlighted_border_style
+        } else {
+            self.colours.border_style
+        };
+
+        let top_processes_title = format!(" {} ", widget_title);
+        let title = if app_state.is_expanded {
+            const TITLE_BASE: &str = "── Esc to go back ";
+            let title_len = UnicodeSegmentation::graphemes(top_processes_title.as_str(), true)
+                .count()
+                + UnicodeSegmentation::graphemes(TITLE_BASE, true).count();
+            Spans::from(vec![
+                Span::styled(top_processes_title, self.colours.widget_title_style),
+                Span::styled(
+                    format!(
+                        "─{}─ Esc to go back ",
+                        "─".repeat(usize::from(draw_loc.width).saturating_sub(title_len + 2))
+                    ),
+                    border_style,
+                ),
+            ])
+        } else {
+            Spans::from(Span::styled(
+                top_processes_title,
+                self.colours.widget_title_style,
+            ))
+        };
+
+        let block = if draw_border {
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(border_style)
+        } else if is_on_widget {
+            Block::default()
+                .borders(SIDE_BORDERS)
+                .border_style(self.colours.highlighted_border_style)
+        } else {
+            Block::default().borders(Borders::NONE)
+        };
+
+        // Everything is on one line, so the widget can be kept to a single row of the layout.
+        let spans = app_state
+            .converted_data
+            .top_process_display
+            .iter()
+            .enumerate()
+            .flat_map(|(index, (label, value))| {
+                vec![
+                    Span::styled(
+                        format!("{}{} ", if index > 0 { "  " } else { "" }, label),
+                        self.colours.table_header_style,
+                    ),
+                    Span::styled(value.clone(), self.colours.text_style),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        f.render_widget(Paragraph::new(Spans::from(spans)).block(block), draw_loc);
+
+        if app_state.should_get_widget_bounds() {
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
@@ -9,10 +9,14 @@ const TEMPLATE: &str = "\
 USAGE:{usage}
 
 FLAGS:
-{options}";
+{options}
+
+SUBCOMMANDS:
+{subcommands}";
 
 const USAGE: &str = "
-    btm [FLAG]";
+    btm [FLAG]
+    btm config <SUBCOMMAND>";
 
 const DEFAULT_WIDGET_TYPE_STR: &str = if cfg!(feature = "battery") {
     "\
@@ -123,6 +127,16 @@ pub fn build_app() -> Command<'static> {
             "Hides graphs and uses a more basic look. Design is largely inspired by htop's.",
         );
 
+    let basic_widgets = Arg::new("basic_widgets")
+        .long("basic_widgets")
+        .takes_value(true)
+        .value_name("WIDGETS")
+        .help("Sets which summary rows are shown in basic mode, like cpu,net.")
+        .long_help(
+            "Sets which of the CPU, memory, and network summary rows are shown above the tables in \
+            basic mode, as a comma-separated list of cpu, mem, and net. Defaults to all of them.",
+        );
+
     let case_sensitive = Arg::new("case_sensitive")
         .short('S')
         .long("case_sensitive")
@@ -142,12 +156,85 @@ pub fn build_app() -> Command<'static> {
         .help("Disables mouse clicks.")
         .long_help("Disables mouse clicks from interacting with the program.");
 
+    let termux = Arg::new("termux")
+        .long("termux")
+        .help("Uses the Termux profile, for running on Android.")
+        .long_help(
+            "Skips collecting what Android doesn't let Termux read, like /proc/stat and \
+            /sys/class/thermal, and lists it in the diagnostics dialog instead. Mouse and touch \
+            taps that land just outside of a button, tab, or layout split also hit it. This is \
+            used by default when running in Termux.",
+        );
+
+    let disk_inodes = Arg::new("disk_inodes")
+        .long("disk_inodes")
+        .help("Shows inode usage in the disk widget.")
+        .long_help(
+            "Shows the number of free inodes and the percentage of inodes used for each filesystem in the \
+            disk widget. This is not supported on Windows.",
+        );
+
+    let disk_smart = Arg::new("disk_smart")
+        .long("disk_smart")
+        .help("Shows SMART health information in the disk widget.")
+        .long_help(
+            "Shows SMART health information (temperature, wear level, reallocated sectors, and power-on \
+            hours) in the disk widget, and highlights disks that are failing. This is only supported on \
+            Linux for NVMe and ATA devices, and generally requires root.",
+        );
+
+    let disk_activity = Arg::new("disk_activity")
+        .long("disk_activity")
+        .help("Shows how busy each disk is in the disk widget.")
+        .long_help(
+            "Shows the percentage of time each disk was busy, and the average number of reads and \
+            writes queued on it, in the disk widget. This is only supported on Windows.",
+        );
+
+    let disk_full_estimate = Arg::new("disk_full_estimate")
+        .long("disk_full_estimate")
+        .help("Shows when each disk is estimated to be full in the disk widget.")
+        .long_help(
+            "Shows a \"Full in\" column in the disk widget, estimating when each filesystem will be \
+            full if it keeps filling at the rate it has over the retention period. Disks estimated to \
+            be full within the time set by --disk_full_warning are highlighted.",
+        );
+
+    let disk_full_warning = Arg::new("disk_full_warning")
+        .long("disk_full_warning")
+        .takes_value(true)
+        .value_name("TIME")
+        .help("Highlights disks estimated to be full within this long, like 12h or 7d.")
+        .long_help(
+            "Highlights disks in the disk widget that are estimated to be full within this long, if \
+            --disk_full_estimate is enabled. This is a number followed by s, m, h, or d, like 12h \
+            or 7d, or a number of milliseconds. Defaults to 1d.",
+        );
+
     let dot_marker = Arg::new("dot_marker")
         .short('m')
         .long("dot_marker")
         .help("Uses a dot marker for graphs.")
         .long_help("Uses a dot marker for graphs as opposed to the default braille marker.");
 
+    let no_unicode = Arg::new("no_unicode")
+        .long("no_unicode")
+        .help("Draws everything with ASCII characters only.")
+        .long_help(
+            "Draws graphs, borders, and other symbols with ASCII characters only, rather than \
+            braille and box-drawing characters. Useful for terminals, fonts, or screen readers \
+            that don't show these well.",
+        );
+
+    let screen_reader = Arg::new("screen_reader")
+        .long("screen_reader")
+        .help("Shows widgets as plain text for screen readers.")
+        .long_help(
+            "Shows every widget as plain lines of text, like current values and sorted lists, \
+            rather than graphs and tables. The selected widget is named on the first line and \
+            the cursor follows it, so screen readers can announce it as the selection changes.",
+        );
+
     let group = Arg::new("group") // TODO: Rename this to something like "group_process", would be "breaking" though.
         .short('g')
         .long("group")
@@ -175,6 +262,56 @@ pub fn build_app() -> Command<'static> {
         .help("Show processes as their commands by default.")
         .long_help("Show processes as their commands by default in the process widget.");
 
+    let process_sparkline = Arg::new("process_sparkline")
+        .long("process_sparkline")
+        .help("Shows a graph of recent CPU usage for each process.")
+        .long_help(
+            "Shows a column in the process widget with a small graph of each process' recent CPU usage. \
+            This column is not shown when processes are grouped.",
+        );
+
+    let process_memory_details = Arg::new("process_memory_details")
+        .long("process_memory_details")
+        .help("Shows virtual, shared, and swapped memory for each process.")
+        .long_help(
+            "Shows columns in the process widget with the virtual size, shared memory, and swapped-out \
+            memory of each process. Shared and swapped memory are only supported on Linux.",
+        );
+
+    let precise_process_memory = Arg::new("precise_process_memory")
+        .long("precise_process_memory")
+        .help("Reads shared and swapped process memory from smaps_rollup.")
+        .long_help(
+            "Reads the shared and swapped memory of each process from /proc/<PID>/smaps_rollup, which \
+            only counts memory that is really shared with another process, but is slower to read. \
+            Only has an effect on Linux with --process_memory_details.",
+        );
+
+    let process_gpu = Arg::new("process_gpu")
+        .long("process_gpu")
+        .help("Shows the GPU usage and GPU memory of each process.")
+        .long_help(
+            "Shows columns in the process widget with the GPU utilization and GPU memory of each \
+            process. Supported on Windows for any GPU, and elsewhere only for NVIDIA GPUs if bottom \
+            was built with the gpu feature.",
+        );
+
+    let process_start_time = Arg::new("process_start_time")
+        .long("process_start_time")
+        .help("Shows when each process started and how long it has run.")
+        .long_help(
+            "Shows columns in the process widget with when each process started and how long it has \
+            been running. Grouped processes show their earliest start time.",
+        );
+
+    let process_systemd_unit = Arg::new("process_systemd_unit")
+        .long("process_systemd_unit")
+        .help("Shows the systemd unit of each process.")
+        .long_help(
+            "Shows a column in the process widget with the systemd unit that each process belongs \
+            to. Only supported on Linux.",
+        );
+
     let left_legend = Arg::new("left_legend")
         .short('l')
         .long("left_legend")
@@ -192,11 +329,38 @@ pub fn build_app() -> Command<'static> {
         .help("Hides advanced process killing.")
         .long_help("Hides advanced options to stop a process on Unix-like systems. The only option shown is 15 (TERM).");
 
+    let save_layout = Arg::new("save_layout")
+        .long("save_layout")
+        .help("Saves layout changes made with the mouse on exit.")
+        .long_help(
+            "Saves any changes made to the layout by dragging widget borders with the mouse to the config \
+            file on exit. Note that this rewrites the config file, which will remove any comments.",
+        );
+
     let show_table_scroll_position = Arg::new("show_table_scroll_position")
         .long("show_table_scroll_position")
         .help("Shows the scroll position tracker in table widgets.")
         .long_help("Shows the list scroll position tracker in the widget title for table widgets.");
 
+    let mini_widgets = Arg::new("mini_widgets")
+        .long("mini_widgets")
+        .help("Shows a summary of other widgets when one is expanded.")
+        .long_help(
+            "When a widget is expanded, keeps a one-line summary of the CPU, memory, and network widgets \
+            along the bottom edge, so their current usage stays visible.",
+        );
+
+    let min_row_height = Arg::new("min_row_height")
+        .long("min_row_height")
+        .takes_value(true)
+        .value_name("LINES")
+        .help("Keeps each layout row at least this tall, scrolling if needed.")
+        .long_help(
+            "Keeps each row of the layout at least this many lines tall. If the rows don't fit on the \
+            screen, the layout grows taller than the screen and can be scrolled. By default, rows are \
+            always squeezed to fit the screen.",
+        );
+
     let use_old_network_legend = Arg::new("use_old_network_legend")
         .long("use_old_network_legend")
         .help("DEPRECATED - uses a separate network legend.")
@@ -219,18 +383,100 @@ pub fn build_app() -> Command<'static> {
         .long("config")
         .takes_value(true)
         .value_name("CONFIG PATH")
+        .global(true)
         .help("Sets the location of the config file.")
         .long_help(
             "Sets the location of the config file. Expects a config file in the TOML format. \
             If it doesn't exist, one is created.",
         );
 
+    let log_file = Arg::new("log_file")
+        .long("log_file")
+        .takes_value(true)
+        .allow_invalid_utf8(true)
+        .value_name("PATH")
+        .help("Logs what couldn't be collected, and why, to a file.")
+        .long_help(
+            "Logs to a file when something couldn't be collected, like a sensor that can't be read, \
+            and why, as well as when it's collected again. Other diagnostics are logged too, \
+            depending on --log_level. The file is appended to if it already exists.",
+        );
+
+    let log_level = Arg::new("log_level")
+        .long("log_level")
+        .takes_value(true)
+        .value_name("LEVEL")
+        .possible_values(["error", "warn", "info", "debug", "trace"])
+        .requires("log_file")
+        .help("Sets how much is logged with --log_file.")
+        .long_help(
+            "Sets how much is logged with --log_file. Failures are logged as warnings when they start \
+            or change, recoveries as info, and repeated failures as debug. Defaults to info.",
+        );
+
+    let agent = Arg::new("agent")
+        .long("agent")
+        .requires("listen")
+        .conflicts_with_all(&["connect", "ssh"])
+        .help("Serves data to other instances instead of showing it.")
+        .long_help(
+            "Runs bottom as an agent, which collects data and serves it to instances started with \
+            --connect, rather than showing it. Requires --listen and a token. The data isn't \
+            encrypted, so only listening on 127.0.0.1 and connecting through an SSH tunnel is \
+            supported.",
+        );
+
+    let listen = Arg::new("listen")
+        .long("listen")
+        .takes_value(true)
+        .value_name("ADDRESS")
+        .requires("agent")
+        .help("Sets the address an agent listens on, like 127.0.0.1:7777.");
+
+    let token = Arg::new("token")
+        .long("token")
+        .takes_value(true)
+        .value_name("TOKEN")
+        .help("Sets the token agents require from clients.")
+        .long_help(
+            "Sets the token that an agent started with --agent requires clients to send, and that \
+            --connect sends to agents. Can also be given with the BTM_TOKEN environment variable, \
+            which keeps it out of the process list.",
+        );
+
+    let connect = Arg::new("connect")
+        .long("connect")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .value_name("ADDRESS")
+        .help("Shows the data of an agent instead of this machine.")
+        .long_help(
+            "Shows the data of an agent started with --agent, at an address like 127.0.0.1:7777, \
+            instead of the data of this machine, using the agent's token from --token. Processes \
+            can't be killed or otherwise acted on. Can be given more than once to connect to \
+            several agents, which are switched between with 'gh' and 'gH'.",
+        );
+
+    let ssh = Arg::new("ssh")
+        .long("ssh")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .value_name("DESTINATION")
+        .help("Shows the data of a Linux host collected over SSH.")
+        .long_help(
+            "Shows the data of a Linux host, at a destination like user@server, collected by \
+            running standard commands over SSH, instead of the data of this machine. Nothing needs \
+            to be installed on the host, but logging in must not need a password, such as by using \
+            a key. Can be given more than once, and used along with --connect.",
+        );
+
     // TODO: Fix this, its broken in the manpage
     let color = Arg::new("color")
         .long("color")
         .takes_value(true)
         .value_name("COLOR SCHEME")
         .possible_values(&[
+            "auto",
             "default",
             "default-light",
             "gruvbox",
@@ -244,6 +490,8 @@ pub fn build_app() -> Command<'static> {
             "\
 Use a pre-defined color scheme. Currently supported values are:
 
++------------------------------------------------------------+
+| auto (default or default-light, based on the background)   |
 +------------------------------------------------------------+
 | default                                                    |
 +------------------------------------------------------------+
@@ -259,6 +507,32 @@ Use a pre-defined color scheme. Currently supported values are:
 +------------------------------------------------------------+
 
 Defaults to \"default\".
+",
+        );
+
+    let graph_renderer = Arg::new("graph_renderer")
+        .long("graph_renderer")
+        .takes_value(true)
+        .value_name("RENDERER")
+        .possible_values(["text", "auto", "kitty", "sixel"])
+        .hide_possible_values(true)
+        .help("Draws graphs as images, use --help for info.")
+        .long_help(
+            "\
+Sets how graphs are drawn. Currently supported values are:
+
++------------------------------------------------------------+
+| text (braille or dots)                                     |
++------------------------------------------------------------+
+| auto (images if the terminal supports them, else text)      |
++------------------------------------------------------------+
+| kitty (images with the Kitty graphics protocol)            |
++------------------------------------------------------------+
+| sixel (images with Sixel)                                  |
++------------------------------------------------------------+
+
+Images need the terminal to report its size in pixels, and fall
+back to text otherwise. Defaults to \"text\".
 ",
         );
 
@@ -309,6 +583,27 @@ use CPU (3) as the default instead.
         .help("Sets the default widget type, use --help for info.")
         .long_help(DEFAULT_WIDGET_TYPE_STR);
 
+    let process_start_time_format = Arg::new("process_start_time_format")
+        .long("process_start_time_format")
+        .takes_value(true)
+        .value_name("FORMAT")
+        .possible_values(["relative", "absolute"])
+        .help("Shows process start times as relative or absolute.")
+        .long_help(
+            "Shows process start times in the process widget either as how long ago they were, like \
+            \"2h 5m ago\" (relative), or as a date and time in the local time zone (absolute). \
+            Defaults to absolute.",
+        );
+
+    let peaks = Arg::new("peaks")
+        .long("peaks")
+        .help("Shows the peak values seen this session in widget titles.")
+        .long_help(
+            "Shows the highest average CPU usage, memory usage, and network rates seen since bottom \
+            started in the titles of their widgets, and the highest temperature of each sensor in \
+            the temperature widget. These can be reset with gp.",
+        );
+
     let rate = Arg::new("rate")
         .short('r')
         .long("rate")
@@ -317,6 +612,46 @@ use CPU (3) as the default instead.
         .help("Sets a refresh rate in ms.")
         .long_help("Sets a refresh rate in milliseconds. The minimum is 250ms, and defaults to 1000ms. Smaller values may take more computer resources.");
 
+    let compare = Arg::new("compare")
+        .long("compare")
+        .takes_value(true)
+        .value_name("TIME")
+        .help("Overlays graphs with their values from this long ago, like 5m or 1h.")
+        .long_help(
+            "Overlays the CPU, memory, and network graphs with a dimmed copy of their values from this \
+            long ago, to compare the current load with an earlier one. This is a number followed by \
+            s, m, h, or d, like 5m or 1h, and has to be less than the retention. The overlay can be \
+            turned on and off with gc.",
+        );
+
+    let shade_gaps = Arg::new("shade_gaps")
+        .long("shade_gaps")
+        .help("Shades gaps in graphs where no data was collected.")
+        .long_help(
+            "Shades the parts of graphs where no data was collected, like while the system was asleep. \
+            Lines are never drawn across these gaps, whether or not they are shaded.",
+        );
+
+    let retention = Arg::new("retention")
+        .long("retention")
+        .takes_value(true)
+        .value_name("TIME")
+        .help("How long to keep data for graphs, like 10m or 6h.")
+        .long_help(
+            "How long to keep data for graphs, which is also how far out they can be zoomed. This is \
+            a number followed by s, m, h, or d, like 10m or 6h, or a number of milliseconds. The \
+            minimum is 30s, the maximum is 1d, and defaults to 10m.",
+        );
+
+    let swap_activity = Arg::new("swap_activity")
+        .long("swap_activity")
+        .help("Graphs pages swapped in and out in the memory widget.")
+        .long_help(
+            "Graphs how many pages are swapped in and out per second under the memory graph, which \
+            shows when a system is thrashing even if little swap is used. This is only supported on \
+            Linux.",
+        );
+
     let time_delta = Arg::new("time_delta")
         .short('d')
         .long("time_delta")
@@ -341,6 +676,83 @@ use CPU (3) as the default instead.
         .help("Displays the network widget with a log scale.")
         .long_help("Displays the network widget with a log scale. Defaults to a non-log scale.");
 
+    let network_errors = Arg::new("network_errors")
+        .long("network_errors")
+        .help("Shows network errors and dropped packets in the network widget.")
+        .long_help(
+            "Shows the rate of errors and dropped packets across all interfaces in the network widget's \
+            legend, and allows plotting them with the network totals toggle. Dropped packets are not \
+            reported on Windows and FreeBSD.",
+        );
+
+    let stacked_cpu = Arg::new("stacked_cpu")
+        .long("stacked_cpu")
+        .help("Stacks the usage of each CPU when all are shown in the CPU graph.")
+        .long_help(
+            "When all CPUs are shown in the CPU graph, stacks the usage of each on top of the ones \
+            before it, up to 100% per CPU, so the top line is the total load of the system. \
+            Selecting a single CPU still shows just its usage.",
+        );
+
+    let cpu_breakdown = Arg::new("cpu_breakdown")
+        .long("cpu_breakdown")
+        .help("Shows how the usage of the selected CPU breaks down by state.")
+        .long_help(
+            "When a single CPU or the average is selected in the CPU widget, plots the time it spends \
+            in user, system, I/O wait, IRQ, and steal states rather than its overall usage. Only \
+            user and system time are reported on macOS and Windows, and this isn't supported on \
+            FreeBSD.",
+        );
+
+    let cpu_top_process = Arg::new("cpu_top_process")
+        .long("cpu_top_process")
+        .help("Shows the process using the most CPU on the selected core.")
+        .long_help(
+            "When a single core is selected in the CPU widget, shows the process that last ran on \
+            it and uses the most CPU in the graph's title. This is only supported on Linux.",
+        );
+
+    let graph_stats = Arg::new("graph_stats")
+        .long("graph_stats")
+        .help("Shows min, average, and max lines on the CPU and memory graphs.")
+        .long_help(
+            "Shows lines at the lowest, average, and highest values over the time shown on the CPU \
+            and memory graphs, with the values in their legends. For the CPU graph, these are of \
+            the selected CPU, or of the average CPU usage if all are shown.",
+        );
+
+    let network_stats = Arg::new("network_stats")
+        .long("network_stats")
+        .help("Shows the min, max, and mean rates in the network widget.")
+        .long_help(
+            "Shows the lowest, highest, and mean RX and TX rates over the time shown by the network \
+            widget in its legend.",
+        );
+
+    let network_split = Arg::new("network_split")
+        .long("network_split")
+        .help("Draws RX and TX as separate graphs in the network widget.")
+        .long_help(
+            "Draws RX and TX as two stacked graphs in the network widget, each with its own y-axis, \
+            rather than on one shared graph. This helps when one is much larger than the other.",
+        );
+
+    let network_addresses = Arg::new("network_addresses")
+        .long("network_addresses")
+        .help("Shows the IP addresses of each interface in the network widget.")
+        .long_help(
+            "Shows the IPv4 and IPv6 addresses of each interface in the network widget's legend. \
+            Loopback and link-local addresses are not shown. This is only supported on Linux and macOS.",
+        );
+
+    let public_ip = Arg::new("public_ip")
+        .long("public_ip")
+        .help("Shows the public IP address in the network widget.")
+        .long_help(
+            "Shows the public IP address in the network widget's legend. This is looked up every 10 \
+            minutes by sending a request to api.ipify.org.",
+        );
+
     let network_use_binary_prefix = Arg::new("network_use_binary_prefix")
         .long("network_use_binary_prefix")
         .help("Displays the network widget with binary prefixes.")
@@ -348,8 +760,44 @@ use CPU (3) as the default instead.
             "Displays the network widget with binary prefixes (i.e. kibibits, mebibits) rather than a decimal prefix (i.e. kilobits, megabits). Defaults to decimal prefixes.",
         );
 
+    let config_command = Command::new("config")
+        .about("Creates, checks, or migrates the config file.")
+        .long_about(
+            "Creates, checks, or migrates the config file. This is the default config file, or \
+            the one given with --config.",
+        )
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("init")
+                .about("Writes a default config file, with every option commented out.")
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .help("Overwrites the config file if it already exists."),
+                ),
+        )
+        .subcommand(
+            Command::new("check")
+                .about("Checks the config file for errors and warnings, without starting bottom."),
+        )
+        .subcommand(
+            Command::new("migrate")
+                .about("Updates options in the config file that were renamed or removed.")
+                .long_about(
+                    "Updates options in the config file that were renamed or removed. The \
+                    config file is kept as it was otherwise, and the old one is saved next to it \
+                    with a .bak extension.",
+                )
+                .arg(
+                    Arg::new("dry_run")
+                        .long("dry_run")
+                        .help("Prints the updated config file instead of writing it."),
+                ),
+        );
+
     let app = Command::new(crate_name!())
         .version(crate_version!())
+        .propagate_version(true)
         .author(crate_authors!())
         .about(crate_description!())
         .override_usage(USAGE)
@@ -364,33 +812,75 @@ use CPU (3) as the default instead.
         .group(ArgGroup::new("TEMPERATURE_TYPE").args(&["kelvin", "fahrenheit", "celsius"]))
         .arg(autohide_time)
         .arg(basic)
+        .arg(basic_widgets)
         .arg(case_sensitive)
         .arg(process_command)
+        .arg(process_sparkline)
+        .arg(process_memory_details)
+        .arg(precise_process_memory)
+        .arg(process_gpu)
+        .arg(process_start_time)
+        .arg(process_start_time_format)
+        .arg(process_systemd_unit)
         .arg(config_location)
+        .arg(log_file)
+        .arg(log_level)
+        .arg(agent)
+        .arg(listen)
+        .arg(token)
+        .arg(connect)
+        .arg(ssh)
         .arg(color)
+        .arg(graph_renderer)
         .arg(mem_as_value)
         .arg(default_time_value)
         .arg(default_widget_count)
         .arg(default_widget_type)
         .arg(disable_click)
+        .arg(termux)
+        .arg(disk_inodes)
+        .arg(disk_smart)
+        .arg(disk_activity)
+        .arg(disk_full_estimate)
+        .arg(disk_full_warning)
         .arg(dot_marker)
+        .arg(no_unicode)
         .arg(group)
         .arg(hide_avg_cpu)
         .arg(hide_table_gap)
         .arg(hide_time)
         .arg(show_table_scroll_position)
+        .arg(mini_widgets)
+        .arg(min_row_height)
         .arg(left_legend)
         .arg(disable_advanced_kill)
+        .arg(peaks)
+        .arg(compare)
+        .arg(shade_gaps)
         .arg(rate)
         .arg(regex)
+        .arg(retention)
+        .arg(save_layout)
+        .arg(screen_reader)
+        .arg(swap_activity)
         .arg(time_delta)
         .arg(tree)
         .arg(network_use_bytes)
         .arg(network_use_log)
         .arg(network_use_binary_prefix)
+        .arg(network_errors)
+        .arg(stacked_cpu)
+        .arg(cpu_breakdown)
+        .arg(cpu_top_process)
+        .arg(graph_stats)
+        .arg(network_stats)
+        .arg(network_split)
+        .arg(network_addresses)
+        .arg(public_ip)
         .arg(current_usage)
         .arg(use_old_network_legend)
-        .arg(whole_word);
+        .arg(whole_word)
+        .subcommand(config_command);
 
     if cfg!(feature = "battery") {
         let battery = Arg::new("battery")
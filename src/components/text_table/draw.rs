@@ -13,6 +13,7 @@ use tui::{
     Frame,
 };
 use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
     app::{self, layout_manager::BottomWidget},
@@ -83,7 +84,7 @@ impl<'a> TextTable<'a> {
                         ") "
                     );
 
-                    if title_string.len() + 2 <= draw_loc.width.into() {
+                    if title_string.width() + 2 <= draw_loc.width.into() {
                         title_string
                     } else {
                         title.to_string()
@@ -161,13 +162,22 @@ impl<'a> TextTable<'a> {
 
             let sliced_vec = {
                 let num_rows = usize::from(inner_height.saturating_sub(table_gap + header_height));
-                let start = get_start_position(
-                    num_rows,
-                    &state.scroll_direction,
-                    &mut state.scroll_bar,
-                    state.current_scroll_position,
-                    self.is_force_redraw,
-                );
+                let start = if state.is_centered {
+                    state.scroll_bar = get_centered_start_position(
+                        num_rows,
+                        state.current_scroll_position,
+                        table_data.data.len(),
+                    );
+                    state.scroll_bar
+                } else {
+                    get_start_position(
+                        num_rows,
+                        &state.scroll_direction,
+                        &mut state.scroll_bar,
+                        state.current_scroll_position,
+                        self.is_force_redraw,
+                    )
+                };
                 let end = min(table_data.data.len(), start + num_rows);
                 state
                     .table_state
@@ -176,7 +186,7 @@ impl<'a> TextTable<'a> {
             };
 
             // Calculate widths
-            if self.recalculate_column_widths {
+            if self.recalculate_column_widths || state.is_width_stale {
                 state
                     .columns
                     .iter_mut()
@@ -189,7 +199,7 @@ impl<'a> TextTable<'a> {
                         } => {
                             *desired = max(
                                 *desired,
-                                max(column.header.header_text().len(), *data_width) as u16,
+                                max(column.header.header_text().width(), *data_width) as u16,
                             );
                         }
                         WidthBounds::CellWidth => {}
@@ -202,13 +212,7 @@ impl<'a> TextTable<'a> {
                     let row_widths = state
                         .columns
                         .iter()
-                        .filter_map(|c| {
-                            if c.calculated_width == 0 {
-                                None
-                            } else {
-                                Some(c.calculated_width)
-                            }
-                        })
+                        .map(|c| c.calculated_width)
                         .collect::<Vec<_>>();
 
                     st.update_visual_index(inner_rect, &row_widths);
@@ -343,15 +347,13 @@ fn truncate_text(content: &CellContent, width: usize, row_style: Option<Style>)
     };
 
     let mut text = {
-        let graphemes: Vec<&str> =
-            UnicodeSegmentation::graphemes(main_text.as_ref(), true).collect();
-        if graphemes.len() > width && width > 0 {
+        if main_text.width() > width && width > 0 {
             if let Some(s) = alt_text {
                 // If an alternative exists, use that.
                 Text::raw(s.as_ref())
             } else {
                 // Truncate with ellipsis
-                let first_n = graphemes[..(width - 1)].concat();
+                let first_n = take_width(main_text, width - 1);
                 Text::raw(concat_string!(first_n, "…"))
             }
         } else {
@@ -379,15 +381,13 @@ fn truncate_suffixed_text<'a>(
 
     let mut text = {
         let suffixed = concat_string!(main_text, suffix);
-        let graphemes: Vec<&str> =
-            UnicodeSegmentation::graphemes(suffixed.as_str(), true).collect();
-        if graphemes.len() > width && width > 1 {
+        if suffixed.width() > width && width > suffix.width() {
             if let Some(alt) = alt_text {
                 // If an alternative exists, use that + arrow.
                 Text::raw(concat_string!(alt, suffix))
             } else {
                 // Truncate with ellipsis + arrow.
-                let first_n = graphemes[..(width - 2)].concat();
+                let first_n = take_width(main_text, width - suffix.width() - 1);
                 Text::raw(concat_string!(first_n, "…", suffix))
             }
         } else {
@@ -402,6 +402,20 @@ fn truncate_suffixed_text<'a>(
     text
 }
 
+/// Returns the longest start of `text` that fits in `width` columns on the screen. This never
+/// splits a grapheme, and a wide character that would only half fit is left out.
+fn take_width(text: &str, width: usize) -> &str {
+    let mut used_width = 0;
+    for (index, grapheme) in UnicodeSegmentation::grapheme_indices(text, true) {
+        used_width += grapheme.width();
+        if used_width > width {
+            return &text[..index];
+        }
+    }
+
+    text
+}
+
 /// Gets the starting position of a table.
 pub fn get_start_position(
     num_rows: usize, scroll_direction: &app::ScrollDirection, scroll_position_bar: &mut usize,
@@ -440,10 +454,36 @@ pub fn get_start_position(
     }
 }
 
+/// Returns the first row to show so that the selected row is in the middle, without leaving
+/// space past either end of the table.
+fn get_centered_start_position(
+    num_rows: usize, currently_selected_position: usize, num_entries: usize,
+) -> usize {
+    currently_selected_position
+        .saturating_sub(num_rows / 2)
+        .min(num_entries.saturating_sub(num_rows))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn test_take_width() {
+        assert_eq!(take_width("bottom", 3), "bot");
+        assert_eq!(take_width("bottom", 10), "bottom");
+        assert_eq!(take_width("", 2), "");
+
+        // Wide characters take up two columns, and aren't cut in half.
+        assert_eq!(take_width("日本語", 4), "日本");
+        assert_eq!(take_width("日本語", 5), "日本");
+        assert_eq!(take_width("a🦀b", 2), "a");
+        assert_eq!(take_width("a🦀b", 3), "a🦀");
+
+        // Combining characters stay with their base character.
+        assert_eq!(take_width("e\u{301}x", 1), "e\u{301}");
+    }
+
     #[test]
     fn test_get_start_position() {
         use crate::app::ScrollDirection::{self, Down, Up};
@@ -502,4 +542,14 @@ mod test {
         // Test jumping up
         test_get(10, 10, Up, 0, false, 0, 0);
     }
+
+    #[test]
+    fn test_get_centered_start_position() {
+        assert_eq!(get_centered_start_position(10, 20, 100), 15);
+
+        // Near either end, the table isn't scrolled past it.
+        assert_eq!(get_centered_start_position(10, 2, 100), 0);
+        assert_eq!(get_centered_start_position(10, 98, 100), 90);
+        assert_eq!(get_centered_start_position(10, 3, 5), 0);
+    }
 }
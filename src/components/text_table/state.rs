@@ -2,6 +2,7 @@ use std::{borrow::Cow, convert::TryInto, ops::Range};
 
 use itertools::Itertools;
 use tui::{layout::Rect, widgets::TableState};
+use unicode_width::UnicodeWidthStr;
 
 use crate::app::ScrollDirection;
 
@@ -84,6 +85,13 @@ impl CellContent {
         }
     }
 
+    /// Returns how many columns the [`CellContent`] takes up on the screen, which differs from its
+    /// length for wide characters like CJK or emoji. Like [`CellContent::len`], this is the width
+    /// of the "main" field for a [`CellContent::HasAlt`].
+    pub fn width(&self) -> usize {
+        self.main_text().width()
+    }
+
     /// Whether the [`CellContent`]'s text is empty.
     pub fn is_empty(&self) -> bool {
         self.len() == 0
@@ -101,6 +109,16 @@ pub trait TableComponentHeader {
     fn header_text(&self) -> &CellContent;
 }
 
+/// Removes the key for sorting by a column from its name, like the `(p)` in `PID(p)`, for when the
+/// name is used outside of the table.
+pub fn strip_sort_shortcut(header: &str) -> &str {
+    header
+        .strip_suffix(')')
+        .and_then(|header| header.rsplit_once('('))
+        .filter(|(_, key)| key.chars().count() == 1)
+        .map_or(header, |(name, _)| name)
+}
+
 impl TableComponentHeader for CellContent {
     fn header_text(&self) -> &CellContent {
         self
@@ -167,7 +185,7 @@ impl<H: TableComponentHeader> TableComponentColumn<H> {
     }
 
     pub fn new_soft(header: H, max_percentage: Option<f32>) -> Self {
-        let min_width = header.header_text().len() as u16;
+        let min_width = header.header_text().width() as u16;
         Self {
             header,
             width_bounds: WidthBounds::Soft {
@@ -249,7 +267,8 @@ impl SortableState {
         }
     }
 
-    /// Updates the visual index.
+    /// Updates the visual index. There should be a width for every column, where columns that
+    /// aren't drawn have a width of zero, so that clicks map back to the right column.
     ///
     /// This function will create a *sorted* range list - in debug mode,
     /// the program will assert this, but it will not do so in release mode!
@@ -259,6 +278,10 @@ impl SortableState {
             .iter()
             .map(|width| {
                 let range_start = start;
+                if *width == 0 {
+                    return range_start..range_start;
+                }
+
                 let range_end = start + width + 1; // +1 for the gap b/w cols.
                 start = range_end;
                 range_start..range_end
@@ -309,22 +332,12 @@ impl SortableState {
         }
     }
 
-    /// Given a `needle` coordinate, select the corresponding index and value.
+    /// Given a `needle` coordinate, select the corresponding index and value. Columns that aren't
+    /// drawn have empty ranges, so they are never selected.
     fn get_range(&self, needle: u16) -> Option<usize> {
-        match self
-            .visual_mappings
-            .binary_search_by_key(&needle, |range| range.start)
-        {
-            Ok(index) => Some(index),
-            Err(index) => index.checked_sub(1),
-        }
-        .and_then(|index| {
-            if needle < self.visual_mappings[index].end {
-                Some(index)
-            } else {
-                None
-            }
-        })
+        self.visual_mappings
+            .iter()
+            .position(|range| range.contains(&needle))
     }
 }
 
@@ -336,6 +349,16 @@ pub struct TableComponentState<H: TableComponentHeader = CellContent> {
     pub table_state: TableState,
     pub columns: Vec<TableComponentColumn<H>>,
     pub sort_state: SortState,
+
+    /// How many of the shown columns have been scrolled past on the left.
+    pub horizontal_scroll: usize,
+
+    /// Whether the table is scrolled to keep the selected row in the middle.
+    pub is_centered: bool,
+
+    /// Whether the column widths need to be recalculated on the next draw, as the table was
+    /// scrolled horizontally.
+    pub(super) is_width_stale: bool,
 }
 
 impl<H: TableComponentHeader> TableComponentState<H> {
@@ -347,6 +370,9 @@ impl<H: TableComponentHeader> TableComponentState<H> {
             table_state: Default::default(),
             columns,
             sort_state: SortState::Unsortable,
+            horizontal_scroll: 0,
+            is_centered: false,
+            is_width_stale: false,
         }
     }
 
@@ -355,12 +381,66 @@ impl<H: TableComponentHeader> TableComponentState<H> {
         self
     }
 
+    /// Sorts by the column whose header is at the given location, or toggles the sort order if
+    /// the table is already sorted by that column. Returns whether a header was clicked, which is
+    /// always false for unsortable tables.
+    pub fn try_select_header(&mut self, x: u16, y: u16) -> bool {
+        match &mut self.sort_state {
+            SortState::Sortable(state) => state.try_select_location(x, y).is_some(),
+            SortState::Unsortable => false,
+        }
+    }
+
+    /// Sorts by the next column that isn't hidden, wrapping around to the first column.
+    pub fn cycle_sort_column(&mut self) {
+        if let SortState::Sortable(state) = &mut self.sort_state {
+            let num_columns = self.columns.len();
+            let columns = &self.columns;
+            if let Some(index) = (1..=num_columns)
+                .map(|offset| (state.current_index + offset) % num_columns)
+                .find(|index| !columns[*index].is_hidden)
+            {
+                state.update_sort_index(index);
+            }
+        }
+    }
+
+    /// Scrolls the table one column to the left, if it has been scrolled right.
+    pub fn scroll_left(&mut self) {
+        if self.horizontal_scroll > 0 {
+            self.horizontal_scroll -= 1;
+            self.is_width_stale = true;
+        }
+    }
+
+    /// Scrolls the table one column to the right, if there is a column after the first drawn one
+    /// that is cut off or not drawn at all. This relies on the widths from the last draw.
+    pub fn scroll_right(&mut self) {
+        let is_cut_off = self
+            .columns
+            .iter()
+            .filter(|column| !column.is_hidden)
+            .skip(self.horizontal_scroll + 1)
+            .any(|column| match column.width_bounds {
+                WidthBounds::Soft { desired, .. } => column.calculated_width < desired,
+                WidthBounds::CellWidth | WidthBounds::Hard(_) => column.is_zero_width(),
+            });
+
+        if is_cut_off {
+            self.horizontal_scroll += 1;
+            self.is_width_stale = true;
+        }
+    }
+
     /// Calculates widths for the columns for this table.
     ///
     /// * `total_width` is the, well, total width available.
     /// * `left_to_right` is a boolean whether to go from left to right if true, or right to left if
     ///   false.
     ///
+    /// Columns that have been scrolled past are skipped, and if the table is scrolled, the first
+    /// drawn column ignores its maximum percentage so that long text like commands can be read.
+    ///
     /// **NOTE:** Trailing 0's may break tui-rs, remember to filter them out later!
     pub fn calculate_column_widths(&mut self, total_width: u16, left_to_right: bool) {
         use itertools::Either;
@@ -368,10 +448,23 @@ impl<H: TableComponentHeader> TableComponentState<H> {
 
         let mut total_width_left = total_width;
 
+        let num_shown = self.columns.iter().filter(|c| !c.is_hidden).count();
+        self.horizontal_scroll = min(self.horizontal_scroll, num_shown.saturating_sub(1));
+        self.is_width_stale = false;
+        let is_scrolled = self.horizontal_scroll > 0;
+        let first_index = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.is_hidden)
+            .nth(self.horizontal_scroll)
+            .map(|(index, _)| index)
+            .unwrap_or(0);
+
         let columns = if left_to_right {
-            Either::Left(self.columns.iter_mut())
+            Either::Left(self.columns.iter_mut().enumerate())
         } else {
-            Either::Right(self.columns.iter_mut().rev())
+            Either::Right(self.columns.iter_mut().enumerate().rev())
         };
 
         let arrow_offset = match self.sort_state {
@@ -381,10 +474,10 @@ impl<H: TableComponentHeader> TableComponentState<H> {
 
         let mut num_columns = 0;
         let mut skip_iter = false;
-        for column in columns {
+        for (index, column) in columns {
             column.calculated_width = 0;
 
-            if column.is_hidden || skip_iter {
+            if column.is_hidden || skip_iter || index < first_index {
                 continue;
             }
 
@@ -401,11 +494,12 @@ impl<H: TableComponentHeader> TableComponentState<H> {
                     }
 
                     let soft_limit = max(
-                        if let Some(max_percentage) = max_percentage {
-                            // TODO: Rust doesn't have an `into()` or `try_into()` for floats to integers.
-                            ((*max_percentage * f32::from(total_width)).ceil()) as u16
-                        } else {
-                            *desired
+                        match max_percentage {
+                            Some(max_percentage) if !(is_scrolled && index == first_index) => {
+                                // TODO: Rust doesn't have an `into()` or `try_into()` for floats to integers.
+                                ((*max_percentage * f32::from(total_width)).ceil()) as u16
+                            }
+                            _ => *desired,
                         },
                         min_width,
                     );
@@ -420,7 +514,7 @@ impl<H: TableComponentHeader> TableComponentState<H> {
                     }
                 }
                 WidthBounds::CellWidth => {
-                    let width = column.header.header_text().len() as u16;
+                    let width = column.header.header_text().width() as u16;
                     let min_width = width + arrow_offset;
 
                     if min_width > total_width_left || min_width == 0 {
@@ -501,6 +595,15 @@ impl<H: TableComponentHeader> TableComponentState<H> {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_strip_sort_shortcut() {
+        assert_eq!(strip_sort_shortcut("PID(p)"), "PID");
+        assert_eq!(strip_sort_shortcut("CPU%(c)"), "CPU%");
+        assert_eq!(strip_sort_shortcut("R/s"), "R/s");
+        assert_eq!(strip_sort_shortcut("Used(%)(u)"), "Used(%)");
+        assert_eq!(strip_sort_shortcut("Name (long)"), "Name (long)");
+    }
+
     #[test]
     fn test_scroll_update_position() {
         #[track_caller]
@@ -519,6 +622,9 @@ mod test {
             table_state: Default::default(),
             columns: vec![],
             sort_state: SortState::Unsortable,
+            horizontal_scroll: 0,
+            is_centered: false,
+            is_width_stale: false,
         };
         let s = &mut scroll;
 
@@ -678,4 +784,146 @@ mod test {
         // Click on first column should then go to descending as it is already selected and ascending.
         test_selection(&mut state, 3, 0, (Some(0), Descending));
     }
+
+    #[test]
+    fn test_visual_index_selection_with_hidden_columns() {
+        let mut state = SortableState::new(
+            0,
+            SortOrder::Ascending,
+            vec![
+                SortOrder::Ascending,
+                SortOrder::Ascending,
+                SortOrder::Descending,
+            ],
+        );
+
+        // The middle column isn't drawn, so clicking right after the first column should select
+        // the last one.
+        state.update_visual_index(Rect::new(0, 0, 20, 15), &[4, 0, 14]);
+        assert_eq!(state.try_select_location(3, 0), Some(0));
+        assert_eq!(state.try_select_location(5, 0), Some(2));
+        assert_eq!(state.order, SortOrder::Descending);
+        assert_eq!(state.try_select_location(19, 0), Some(2));
+        assert_eq!(state.try_select_location(20, 0), None);
+    }
+
+    #[test]
+    fn test_cycle_sort_column() {
+        let mut state = TableComponentState::new(vec![
+            TableComponentColumn::new(CellContent::new("a", None)),
+            TableComponentColumn::new(CellContent::new("b", None)),
+            TableComponentColumn::new(CellContent::new("c", None)),
+        ])
+        .sort_state(SortState::Sortable(SortableState::new(
+            0,
+            SortOrder::Ascending,
+            vec![
+                SortOrder::Ascending,
+                SortOrder::Ascending,
+                SortOrder::Descending,
+            ],
+        )));
+        state.columns[1].is_hidden = true;
+
+        let current_sort = |state: &TableComponentState| match &state.sort_state {
+            SortState::Sortable(sort) => (sort.current_index, sort.order),
+            SortState::Unsortable => unreachable!(),
+        };
+
+        state.cycle_sort_column();
+        assert_eq!(current_sort(&state), (2, SortOrder::Descending));
+        state.cycle_sort_column();
+        assert_eq!(current_sort(&state), (0, SortOrder::Ascending));
+    }
+
+    #[test]
+    fn test_try_select_header() {
+        let columns = || {
+            vec![
+                TableComponentColumn::new(CellContent::new("a", None)),
+                TableComponentColumn::new(CellContent::new("b", None)),
+            ]
+        };
+
+        let mut unsortable = TableComponentState::new(columns());
+        assert!(!unsortable.try_select_header(0, 0));
+
+        let mut sortable = TableComponentState::new(columns()).sort_state(SortState::Sortable(
+            SortableState::new(
+                0,
+                SortOrder::Ascending,
+                vec![SortOrder::Ascending, SortOrder::Descending],
+            ),
+        ));
+        if let SortState::Sortable(sort) = &mut sortable.sort_state {
+            sort.update_visual_index(Rect::new(0, 1, 20, 15), &[4, 4]);
+        }
+
+        // Only the header row can be clicked.
+        assert!(!sortable.try_select_header(6, 2));
+        assert!(sortable.try_select_header(6, 1));
+        assert!(matches!(
+            &sortable.sort_state,
+            SortState::Sortable(sort) if sort.current_index == 1 && sort.order == SortOrder::Descending
+        ));
+        assert!(sortable.try_select_header(6, 1));
+        assert!(matches!(
+            &sortable.sort_state,
+            SortState::Sortable(sort) if sort.order == SortOrder::Ascending
+        ));
+    }
+
+    #[test]
+    fn test_horizontal_scroll() {
+        #[track_caller]
+        fn test_widths(state: &mut TableComponentState, expected: Vec<u16>) {
+            state.calculate_column_widths(20, true);
+            assert_eq!(
+                state
+                    .columns
+                    .iter()
+                    .map(|c| c.calculated_width)
+                    .collect::<Vec<_>>(),
+                expected
+            );
+        }
+
+        let mut state = TableComponentState::new(vec![
+            TableComponentColumn::new_hard(CellContent::from("a"), 3),
+            TableComponentColumn::new_custom(
+                "b".into(),
+                WidthBounds::Soft {
+                    min_width: 2,
+                    desired: 30,
+                    max_percentage: Some(0.5),
+                },
+            ),
+            TableComponentColumn::new_hard(CellContent::from("c"), 4),
+        ]);
+        test_widths(&mut state, vec![3, 10, 4]);
+
+        // The second column is cut off, so it can take all of the space once scrolled to.
+        state.scroll_right();
+        assert_eq!(state.horizontal_scroll, 1);
+        test_widths(&mut state, vec![0, 20, 0]);
+
+        state.scroll_right();
+        assert_eq!(state.horizontal_scroll, 2);
+        test_widths(&mut state, vec![0, 0, 19]);
+
+        // Nothing is left to the right.
+        state.scroll_right();
+        assert_eq!(state.horizontal_scroll, 2);
+
+        state.scroll_left();
+        state.scroll_left();
+        state.scroll_left();
+        assert_eq!(state.horizontal_scroll, 0);
+
+        // Hidden columns aren't counted.
+        state.horizontal_scroll = 2;
+        state.columns[1].is_hidden = true;
+        test_widths(&mut state, vec![0, 0, 19]);
+        assert_eq!(state.horizontal_scroll, 1);
+    }
 }
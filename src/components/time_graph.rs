@@ -1,9 +1,9 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, cell::RefCell};
 
 use tui::{
     backend::Backend,
     layout::{Constraint, Rect},
-    style::Style,
+    style::{Color, Modifier, Style},
     symbols::Marker,
     text::{Span, Spans},
     widgets::{Block, Borders, GraphType},
@@ -14,6 +14,7 @@ use concat_string::concat_string;
 use unicode_segmentation::UnicodeSegmentation;
 
 use super::tui_widget::time_chart::{Axis, Dataset, TimeChart, DEFAULT_LEGEND_CONSTRAINTS};
+use crate::canvas::graphics::{GraphImage, GraphImages, GraphLine};
 
 /// A single graph point.
 pub type Point = (f64, f64);
@@ -58,6 +59,15 @@ pub struct TimeGraph<'a> {
 
     /// Any legend constraints.
     pub legend_constraints: Option<(Constraint, Constraint)>,
+
+    /// Where to put the graph to be drawn as an image or saved to a file, if anywhere.
+    pub graph_images: Option<&'a RefCell<GraphImages>>,
+
+    /// Ranges of the x-axis with no data, which lines aren't drawn across.
+    pub gaps: &'a [Point],
+
+    /// Whether to shade the gaps.
+    pub shade_gaps: bool,
 }
 
 impl<'a> TimeGraph<'a> {
@@ -128,6 +138,7 @@ impl<'a> TimeGraph<'a> {
         &self, f: &mut Frame<'_, B>, draw_loc: Rect, graph_data: &[GraphData<'_>],
     ) {
         let x_axis = self.generate_x_axis();
+        let x_bounds = x_axis.bounds;
         let y_axis = self.generate_y_axis();
 
         // This is some ugly manual loop unswitching. Maybe unnecessary.
@@ -148,21 +159,68 @@ impl<'a> TimeGraph<'a> {
             .borders(Borders::ALL)
             .border_style(self.border_style);
 
-        f.render_widget(
-            TimeChart::new(data)
-                .block(block)
-                .x_axis(x_axis)
-                .y_axis(y_axis)
-                .legend_style(self.graph_style)
-                .hidden_legend_constraints(
-                    self.legend_constraints
-                        .unwrap_or(DEFAULT_LEGEND_CONSTRAINTS),
-                ),
-            draw_loc,
-        )
+        let time_chart = TimeChart::new(data)
+            .block(block)
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .legend_style(self.graph_style)
+            .gaps(self.gaps)
+            .gap_style(if self.shade_gaps {
+                Some(self.graph_style.add_modifier(Modifier::DIM))
+            } else {
+                None
+            })
+            .hidden_legend_constraints(
+                self.legend_constraints
+                    .unwrap_or(DEFAULT_LEGEND_CONSTRAINTS),
+            );
+
+        if let Some(graph_images) = self.graph_images {
+            let mut graph_images = graph_images.borrow_mut();
+            let (area, legend_area) = time_chart.graph_and_legend_areas(draw_loc);
+            if area.width > 0 && area.height > 0 {
+                graph_images.images.push(GraphImage {
+                    title: self.title.trim().to_string(),
+                    area,
+                    legend_area,
+                    x_bounds,
+                    y_bounds: self.y_bounds,
+                    y_labels: self
+                        .y_labels
+                        .iter()
+                        .map(|label| label.to_string())
+                        .collect(),
+                    lines: graph_data
+                        .iter()
+                        .map(|data| GraphLine {
+                            name: data.name.as_ref().map(|name| name.trim().to_string()),
+                            points: visible_points(data.points, x_bounds[0]),
+                            colour: data.style.fg.unwrap_or(Color::Reset),
+                        })
+                        .collect(),
+                });
+            }
+            f.render_widget(
+                time_chart.hide_datasets(graph_images.replace_text),
+                draw_loc,
+            )
+        } else {
+            f.render_widget(time_chart, draw_loc)
+        }
     }
 }
 
+/// Returns the points from the last one before `start` onwards, so the line still reaches the
+/// left edge.
+fn visible_points(points: &[Point], start: f64) -> Vec<Point> {
+    let first_visible = points
+        .iter()
+        .position(|(x, _)| *x >= start)
+        .unwrap_or(points.len());
+
+    points[first_visible.saturating_sub(1)..].to_vec()
+}
+
 /// Creates a new [`Dataset`].
 fn create_dataset<'a>(data: &'a GraphData<'a>, marker: Marker) -> Dataset<'a> {
     let GraphData {
@@ -217,6 +275,9 @@ mod test {
             is_expanded: false,
             title_style: Style::default().fg(Color::Cyan),
             legend_constraints: None,
+            graph_images: None,
+            gaps: &[],
+            shade_gaps: false,
         }
     }
 
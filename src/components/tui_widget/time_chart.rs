@@ -1,4 +1,6 @@
 use std::{borrow::Cow, cmp::max};
+
+use itertools::Itertools;
 use tui::{
     buffer::Buffer,
     layout::{Constraint, Rect},
@@ -150,7 +152,9 @@ struct ChartLayout {
 /// Main changes:
 /// - Styling option for the legend box
 /// - Automatically trimming out redundant draws in the x-bounds.
-/// - Automatic interpolation to points that fall *just* outside of the screen.
+/// - Automatic interpolation onto the edges of the screen from points that fall *just* outside of it,
+///   so lines reach the edges even when no point is on them.
+/// - Breaks in the lines over gaps in the data, which can also be shaded.
 ///
 /// TODO: Support for putting the legend on the left side.
 #[derive(Debug, Clone)]
@@ -169,6 +173,12 @@ pub struct TimeChart<'a> {
     legend_style: Style,
     /// Constraints used to determine whether the legend should be shown or not
     hidden_legend_constraints: (Constraint, Constraint),
+    /// Whether to leave out the lines of the datasets, like when they're drawn some other way
+    hide_datasets: bool,
+    /// Ranges of the x-axis with no data, which lines aren't drawn across
+    gaps: &'a [(f64, f64)],
+    /// The style to shade the gaps with, if they're shaded
+    gap_style: Option<Style>,
 }
 
 pub const DEFAULT_LEGEND_CONSTRAINTS: (Constraint, Constraint) =
@@ -188,6 +198,9 @@ impl<'a> TimeChart<'a> {
             legend_style: Default::default(),
             datasets,
             hidden_legend_constraints: DEFAULT_LEGEND_CONSTRAINTS,
+            hide_datasets: false,
+            gaps: &[],
+            gap_style: None,
         }
     }
 
@@ -224,6 +237,35 @@ impl<'a> TimeChart<'a> {
         self
     }
 
+    /// Set whether to leave out the lines of the datasets. Everything else is still drawn.
+    pub fn hide_datasets(mut self, hide_datasets: bool) -> TimeChart<'a> {
+        self.hide_datasets = hide_datasets;
+        self
+    }
+
+    /// Set the ranges of the x-axis with no data. These **must** be sorted too.
+    pub fn gaps(mut self, gaps: &'a [(f64, f64)]) -> TimeChart<'a> {
+        self.gaps = gaps;
+        self
+    }
+
+    /// Set the style to shade the gaps with, or [`None`] to leave them blank.
+    pub fn gap_style(mut self, gap_style: Option<Style>) -> TimeChart<'a> {
+        self.gap_style = gap_style;
+        self
+    }
+
+    /// Returns where the lines of the datasets and the legend go if the chart is drawn in `area`.
+    pub fn graph_and_legend_areas(&self, area: Rect) -> (Rect, Option<Rect>) {
+        let chart_area = match &self.block {
+            Some(block) => block.inner(area),
+            None => area,
+        };
+        let layout = self.layout(chart_area);
+
+        (layout.graph_area, layout.legend_area)
+    }
+
     /// Compute the internal layout of the chart given the area. If the area is too small some
     /// elements may be automatically hidden
     fn layout(&self, area: Rect) -> ChartLayout {
@@ -346,6 +388,32 @@ impl<'a> TimeChart<'a> {
         }
     }
 
+    /// Shades the cells of the graph that fall within a gap. Lines are drawn over this afterwards.
+    fn render_gaps(&self, buf: &mut Buffer, graph_area: Rect, gap_style: Style) {
+        let [left, right] = self.x_axis.bounds;
+        let width = right - left;
+        if width <= 0.0 {
+            return;
+        }
+
+        let get_column = |x: f64| {
+            let column =
+                ((x.clamp(left, right) - left) / width * f64::from(graph_area.width)) as u16;
+            graph_area.left() + column.min(graph_area.width - 1)
+        };
+        for (gap_start, gap_end) in self.gaps {
+            if *gap_end < left || *gap_start > right {
+                continue;
+            }
+
+            for x in get_column(*gap_start)..=get_column(*gap_end) {
+                for y in graph_area.top()..graph_area.bottom() {
+                    buf.get_mut(x, y).set_symbol("░").set_style(gap_style);
+                }
+            }
+        }
+    }
+
     fn render_y_labels(
         &mut self, buf: &mut Buffer, layout: &ChartLayout, chart_area: Rect, graph_area: Rect,
     ) {
@@ -418,91 +486,56 @@ impl<'a> Widget for TimeChart<'a> {
             }
         }
 
-        for dataset in &self.datasets {
+        if let Some(gap_style) = self.gap_style {
+            self.render_gaps(buf, graph_area, gap_style);
+        }
+
+        let drawn_datasets = if self.hide_datasets {
+            &[]
+        } else {
+            self.datasets.as_slice()
+        };
+        for dataset in drawn_datasets {
             Canvas::default()
                 .background_color(self.style.bg.unwrap_or(Color::Reset))
                 .x_bounds(self.x_axis.bounds)
                 .y_bounds(self.y_axis.bounds)
                 .marker(dataset.marker)
                 .paint(|ctx| {
-                    let start_bound = self.x_axis.bounds[0];
-                    let end_bound = self.x_axis.bounds[1];
-
-                    let (start_index, interpolate_start) = get_start(dataset, start_bound);
-                    let (end_index, interpolate_end) = get_end(dataset, end_bound);
-
-                    let data_slice = &dataset.data[start_index..end_index];
+                    let color = dataset.style.fg.unwrap_or(Color::Reset);
+                    let (start_point, data_slice, end_point) =
+                        get_visible_points(dataset, self.x_axis.bounds, self.gaps);
 
                     ctx.draw(&Points {
                         coords: data_slice,
-                        color: dataset.style.fg.unwrap_or(Color::Reset),
+                        color,
                     });
-
-                    if let Some(interpolate_start) = interpolate_start {
-                        if let (Some(older_point), Some(newer_point)) = (
-                            dataset.data.get(interpolate_start),
-                            dataset.data.get(interpolate_start + 1),
-                        ) {
-                            let interpolated_point = (
-                                self.x_axis.bounds[0],
-                                interpolate_point(older_point, newer_point, self.x_axis.bounds[0]),
-                            );
-
-                            ctx.draw(&Points {
-                                coords: &[interpolated_point],
-                                color: dataset.style.fg.unwrap_or(Color::Reset),
-                            });
-
-                            if let GraphType::Line = dataset.graph_type {
-                                ctx.draw(&Line {
-                                    x1: interpolated_point.0,
-                                    y1: interpolated_point.1,
-                                    x2: newer_point.0,
-                                    y2: newer_point.1,
-                                    color: dataset.style.fg.unwrap_or(Color::Reset),
-                                });
-                            }
-                        }
+                    for point in start_point.iter().chain(end_point.iter()) {
+                        ctx.draw(&Points {
+                            coords: &[*point],
+                            color,
+                        });
                     }
 
                     if let GraphType::Line = dataset.graph_type {
-                        for data in data_slice.windows(2) {
+                        for (older_point, newer_point) in start_point
+                            .iter()
+                            .chain(data_slice)
+                            .chain(end_point.iter())
+                            .tuple_windows()
+                            .filter(|(older_point, newer_point)| {
+                                !is_across_gap(older_point, newer_point, self.gaps)
+                            })
+                        {
                             ctx.draw(&Line {
-                                x1: data[0].0,
-                                y1: data[0].1,
-                                x2: data[1].0,
-                                y2: data[1].1,
-                                color: dataset.style.fg.unwrap_or(Color::Reset),
+                                x1: older_point.0,
+                                y1: older_point.1,
+                                x2: newer_point.0,
+                                y2: newer_point.1,
+                                color,
                             });
                         }
                     }
-
-                    if let Some(interpolate_end) = interpolate_end {
-                        if let (Some(older_point), Some(newer_point)) = (
-                            dataset.data.get(interpolate_end - 1),
-                            dataset.data.get(interpolate_end),
-                        ) {
-                            let interpolated_point = (
-                                self.x_axis.bounds[1],
-                                interpolate_point(older_point, newer_point, self.x_axis.bounds[1]),
-                            );
-
-                            ctx.draw(&Points {
-                                coords: &[interpolated_point],
-                                color: dataset.style.fg.unwrap_or(Color::Reset),
-                            });
-
-                            if let GraphType::Line = dataset.graph_type {
-                                ctx.draw(&Line {
-                                    x1: older_point.0,
-                                    y1: older_point.1,
-                                    x2: interpolated_point.0,
-                                    y2: interpolated_point.1,
-                                    color: dataset.style.fg.unwrap_or(Color::Reset),
-                                });
-                            }
-                        }
-                    }
                 })
                 .render(graph_area, buf);
         }
@@ -555,6 +588,43 @@ impl<'a> Widget for TimeChart<'a> {
     }
 }
 
+/// Returns the points of a dataset within `bounds`, along with points on the left and right bounds
+/// interpolated from the points on either side of them. Lines then reach the edges of the graph even
+/// when the bounds fall between two points, or when no point is within them at all. Nothing is
+/// interpolated across a gap.
+fn get_visible_points<'a>(
+    dataset: &Dataset<'a>, bounds: [f64; 2], gaps: &[(f64, f64)],
+) -> (Option<(f64, f64)>, &'a [(f64, f64)], Option<(f64, f64)>) {
+    let (start_index, interpolate_start) = get_start(dataset, bounds[0]);
+    let (end_index, interpolate_end) = get_end(dataset, bounds[1]);
+    let interpolate = |older_index: usize, x: f64| match (
+        dataset.data.get(older_index),
+        dataset.data.get(older_index + 1),
+    ) {
+        (Some(older_point), Some(newer_point))
+            if !is_across_gap(older_point, newer_point, gaps) =>
+        {
+            Some((x, interpolate_point(older_point, newer_point, x)))
+        }
+        _ => None,
+    };
+
+    (
+        interpolate_start.and_then(|index| interpolate(index, bounds[0])),
+        &dataset.data[start_index..end_index.max(start_index)],
+        interpolate_end.and_then(|index| interpolate(index - 1, bounds[1])),
+    )
+}
+
+/// Returns whether the line between two points would cross one of `gaps`. As no points are within
+/// a gap, this is whether the line crosses its middle, which doesn't depend on rounding at its ends.
+fn is_across_gap(older_point: &(f64, f64), newer_point: &(f64, f64), gaps: &[(f64, f64)]) -> bool {
+    gaps.iter().any(|(gap_start, gap_end)| {
+        let middle = (gap_start + gap_end) / 2.0;
+        older_point.0 < middle && newer_point.0 > middle
+    })
+}
+
 /// Returns the start index and potential interpolation index given the start time and the dataset.
 fn get_start(dataset: &Dataset<'_>, start_bound: f64) -> (usize, Option<usize>) {
     match dataset
@@ -577,14 +647,16 @@ fn get_end(dataset: &Dataset<'_>, end_bound: f64) -> (usize, Option<usize>) {
         Ok(index) => (index.saturating_add(1), None),
         // In the fail case, this means we did not find an index, and the returned index is where one would *insert*
         // the location. This index is where one would insert to fit inside the dataset - and since this is an end
-        // bound, index is, in a sense, already "+1" for our range later.
-        Err(index) => (index, {
-            let sum = index.checked_add(1);
-            match sum {
-                Some(s) if s < dataset.data.len() => sum,
-                _ => None,
-            }
-        }),
+        // bound, index is, in a sense, already "+1" for our range later. It's also the first point past the end
+        // bound, which is interpolated from along with the one before it.
+        Err(index) => (
+            index,
+            if index > 0 && index < dataset.data.len() {
+                Some(index)
+            } else {
+                None
+            },
+        ),
     }
 }
 
@@ -647,14 +719,67 @@ mod test {
 
         // Test end point cases (miss and hit)
         assert_eq!(get_end(&dataset, -2.5), (2, None));
-        assert_eq!(get_end(&dataset, -2.4), (2, Some(3)));
-        assert_eq!(get_end(&dataset, -1.4), (3, Some(4)));
+        assert_eq!(get_end(&dataset, -2.4), (2, Some(2)));
+        assert_eq!(get_end(&dataset, -1.4), (3, Some(3)));
         assert_eq!(get_end(&dataset, -1.0), (4, None));
         assert_eq!(get_end(&dataset, 0.0), (5, None));
         assert_eq!(get_end(&dataset, 1.0), (5, None));
         assert_eq!(get_end(&dataset, 100.0), (5, None));
     }
 
+    #[test]
+    fn time_chart_test_visible_points() {
+        let data = [(-4.0, 8.0), (-2.0, 6.0), (-1.0, 4.0), (0.0, 5.0)];
+        let dataset = Dataset::default().data(&data);
+
+        // The window starts between two points, so the line starts on the boundary.
+        assert_eq!(
+            get_visible_points(&dataset, [-3.0, 0.0], &[]),
+            (Some((-3.0, 7.0)), &data[1..], None)
+        );
+        assert_eq!(
+            get_visible_points(&dataset, [-2.0, -0.5], &[]),
+            (None, &data[1..3], Some((-0.5, 4.5)))
+        );
+
+        // No point is within the window, but there's still a line across it.
+        assert_eq!(
+            get_visible_points(&dataset, [-3.5, -2.5], &[]),
+            (Some((-3.5, 7.5)), &data[1..1], Some((-2.5, 6.5)))
+        );
+
+        // There's nothing to interpolate from before the first point or after the last.
+        assert_eq!(
+            get_visible_points(&dataset, [-10.0, 1.0], &[]),
+            (None, &data[..], None)
+        );
+        assert_eq!(
+            get_visible_points(&dataset, [1.0, 2.0], &[]),
+            (None, &data[4..], None)
+        );
+    }
+
+    #[test]
+    fn time_chart_test_gaps() {
+        let data = [(-40.0, 8.0), (-30.0, 6.0), (-10.0, 4.0), (0.0, 5.0)];
+        let dataset = Dataset::default().data(&data);
+        let gaps = [(-30.0, -10.0)];
+
+        assert!(is_across_gap(&data[1], &data[2], &gaps));
+        assert!(!is_across_gap(&data[0], &data[1], &gaps));
+        assert!(!is_across_gap(&data[2], &data[3], &gaps));
+
+        // Nothing is interpolated from across the gap.
+        assert_eq!(
+            get_visible_points(&dataset, [-20.0, 0.0], &gaps),
+            (None, &data[2..], None)
+        );
+        assert_eq!(
+            get_visible_points(&dataset, [-35.0, -20.0], &gaps),
+            (Some((-35.0, 7.0)), &data[1..2], None)
+        );
+    }
+
     struct LegendTestCase {
         chart_area: Rect,
         hidden_legend_constraints: (Constraint, Constraint),
@@ -4,8 +4,9 @@ use once_cell::sync::Lazy;
 // Default widget ID
 pub const DEFAULT_WIDGET_ID: u64 = 56709;
 
-// How long to store data.
+// How long to store data by default, and the most that can be stored.
 pub const STALE_MAX_MILLISECONDS: u64 = 600 * 1000; // Keep 10 minutes of data.
+pub const MAX_RETENTION_MILLISECONDS: u64 = 24 * 60 * 60 * 1000; // Up to a day.
 
 // How much data is SHOWN
 pub const DEFAULT_TIME_MILLISECONDS: u64 = 60 * 1000; // Defaults to 1 min.
@@ -14,9 +15,33 @@ pub const TIME_CHANGE_MILLISECONDS: u64 = 15 * 1000; // How much to increment ea
 pub const AUTOHIDE_TIMEOUT_MILLISECONDS: u64 = 5000; // 5 seconds to autohide
 
 pub const TICK_RATE_IN_MILLISECONDS: u64 = 200;
+// How many buckets a graph is downsampled to over the time it shows, each keeping its lowest and
+// highest point
+pub const GRAPH_BUCKETS_PER_WINDOW: u64 = 500;
+// How long the input thread waits for input at a time
+pub const INPUT_POLL_IN_MILLISECONDS: u64 = 20;
 // How fast the screen refreshes
 pub const DEFAULT_REFRESH_RATE_IN_MILLISECONDS: u64 = 1000;
+// How often kernel events are read if no rate is set for them, as the whole kernel log is read
+pub const DEFAULT_EVENTS_REFRESH_RATE_IN_MILLISECONDS: u64 = 30000;
 pub const MAX_KEY_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
+// How often the config file is checked for changes
+pub const CONFIG_POLL_INTERVAL_IN_MILLISECONDS: u64 = 1000;
+// How long status messages, such as for reloading the config file, are shown
+pub const STATUS_MESSAGE_DURATION_IN_MILLISECONDS: u64 = 3000;
+
+// How many CPU usage samples to keep per process for the process sparkline column
+pub const PROCESS_CPU_HISTORY_LENGTH: usize = 10;
+
+// How far apart two samples have to be to be treated as a gap in the data, like from the system
+// sleeping, both as a multiple of the interval before and in milliseconds
+pub const GAP_INTERVAL_MULTIPLIER: u32 = 3;
+pub const GAP_MIN_MILLISECONDS: u64 = 5 * 1000;
+
+// How much disk usage history is needed before estimating when a disk will be full
+pub const DISK_TREND_MIN_MILLISECONDS: u64 = 30 * 1000;
+// How soon a disk has to be estimated to fill up before it's highlighted, by default
+pub const DEFAULT_DISK_FULL_WARNING_MILLISECONDS: u64 = 24 * 60 * 60 * 1000;
 
 // Limits for when we should stop showing table gaps/labels (anything less means not shown)
 pub const TABLE_GAP_HEIGHT_LIMIT: u16 = 7;
@@ -99,6 +124,11 @@ pub static GRUVBOX_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColo
     high_battery_color: Some("#98971a".to_string()),
     medium_battery_color: Some("#fabd2f".to_string()),
     low_battery_color: Some("#fb4934".to_string()),
+    stopped_process_color: Some("#fabd2f".to_string()),
+    marked_process_color: Some("#d3869b".to_string()),
+    network_error_color: Some("#fb4934".to_string()),
+    network_drop_color: Some("#fabd2f".to_string()),
+    ..ConfigColours::default()
 });
 
 pub static GRUVBOX_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
@@ -144,6 +174,11 @@ pub static GRUVBOX_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| Conf
     high_battery_color: Some("#98971a".to_string()),
     medium_battery_color: Some("#d79921".to_string()),
     low_battery_color: Some("#cc241d".to_string()),
+    stopped_process_color: Some("#d79921".to_string()),
+    marked_process_color: Some("#b16286".to_string()),
+    network_error_color: Some("#cc241d".to_string()),
+    network_drop_color: Some("#d79921".to_string()),
+    ..ConfigColours::default()
 });
 
 pub static NORD_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
@@ -177,6 +212,11 @@ pub static NORD_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours
     high_battery_color: Some("#a3be8c".to_string()),
     medium_battery_color: Some("#ebcb8b".to_string()),
     low_battery_color: Some("#bf616a".to_string()),
+    stopped_process_color: Some("#ebcb8b".to_string()),
+    marked_process_color: Some("#b48ead".to_string()),
+    network_error_color: Some("#bf616a".to_string()),
+    network_drop_color: Some("#ebcb8b".to_string()),
+    ..ConfigColours::default()
 });
 
 pub static NORD_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
@@ -210,10 +250,15 @@ pub static NORD_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigC
     high_battery_color: Some("#a3be8c".to_string()),
     medium_battery_color: Some("#ebcb8b".to_string()),
     low_battery_color: Some("#bf616a".to_string()),
+    stopped_process_color: Some("#ebcb8b".to_string()),
+    marked_process_color: Some("#b48ead".to_string()),
+    network_error_color: Some("#bf616a".to_string()),
+    network_drop_color: Some("#ebcb8b".to_string()),
+    ..ConfigColours::default()
 });
 
 // Help text
-pub const HELP_CONTENTS_TEXT: [&str; 8] = [
+pub const HELP_CONTENTS_TEXT: [&str; 10] = [
     "Press the corresponding numbers to jump to the section, or scroll:",
     "1 - General",
     "2 - CPU widget",
@@ -222,11 +267,13 @@ pub const HELP_CONTENTS_TEXT: [&str; 8] = [
     "5 - Process sort widget",
     "6 - Battery widget",
     "7 - Basic memory widget",
+    "8 - Network widget",
+    "9 - Disk and temperature widgets",
 ];
 
 // TODO [Help]: Search in help?
 // TODO [Help]: Move to using tables for easier formatting?
-pub const GENERAL_HELP_TEXT: [&str; 31] = [
+pub const GENERAL_HELP_TEXT: [&str; 49] = [
     "1 - General",
     "q, Ctrl-c        Quit",
     "Esc              Close dialog windows, search, widgets, or exit expanded mode",
@@ -244,28 +291,47 @@ pub const GENERAL_HELP_TEXT: [&str; 31] = [
     "Ctrl-Down,       ",
     "Shift-Down,      Move widget selection down",
     "J, S             ",
-    "Left, h          Move left within widget",
+    "Left, h          Move left within widget or scroll a table left",
     "Down, j          Move down within widget",
     "Up, k            Move up within widget",
-    "Right, l         Move right within widget",
+    "Right, l         Move right within widget or scroll a table right",
+    "<count>j, k      Move down/up by <count> entries, e.g. 5j",
     "?                Open help menu",
     "gg               Jump to the first entry",
     "G                Jump to the last entry",
     "e                Toggle expanding the currently selected widget",
+    "E                Edit the widget layout",
+    "gt               Switch to the next layout",
+    "gT               Switch to the previous layout",
+    "]                Select the next tab of a tabbed column",
+    "[                Select the previous tab of a tabbed column",
+    "{, }             Scroll a layout taller than the screen up or down",
+    "b                Switch to the next color scheme",
+    "o                Open the options menu",
+    "ge               Save the selected graph as an SVG and a PNG",
+    "y, Y             Copy the selected table row, or the whole table, to the clipboard",
+    "gh, gH           Show the next or previous remote host",
+    "gd               Show what isn't being collected, and why",
+    "gp               Reset the peak values shown with --peaks",
+    "gc               Toggle the graph overlay set with --compare",
+    "gf               Pause or resume collecting data, unlike f which only freezes the display",
+    "gs               Show bottom's own usage, and how long collecting and drawing take",
     "+                Zoom in on chart (decrease time range)",
     "-                Zoom out on chart (increase time range)",
     "=                Reset zoom",
     "PgUp, PgDown     Scroll up/down a table by a page",
     "Mouse scroll     Scroll through the tables or zoom in/out of charts by scrolling up/down",
+    "Shift-scroll     Scroll a table left/right to see columns that are cut off",
     "Mouse click      Selects the clicked widget, table entry, dialog option, or tab",
 ];
 
-pub const CPU_HELP_TEXT: [&str; 2] = [
+pub const CPU_HELP_TEXT: [&str; 3] = [
     "2 - CPU widget\n",
     "Mouse scroll     Scrolling over an CPU core/average shows only that entry on the chart",
+    "+, -             Collapse/expand the selected NUMA node, socket, or kind of core",
 ];
 
-pub const PROCESS_HELP_TEXT: [&str; 15] = [
+pub const PROCESS_HELP_TEXT: &[&str] = &[
     "3 - Process widget",
     "dd, F9           Kill the selected process",
     "c                Sort by CPU usage, press again to reverse sorting order",
@@ -279,11 +345,29 @@ pub const PROCESS_HELP_TEXT: [&str; 15] = [
     "I                Invert current sort",
     "%                Toggle between values and percentages for memory usage",
     "t, F5            Toggle tree mode",
-    "+, -, click      Collapse/expand a branch while in tree mode",
+    "u                Toggle grouping processes by user",
+    "r                Change the selected process' nice value and I/O priority",
+    "a                View and change the selected process' CPU affinity",
+    "x                Suspend the selected process with SIGSTOP",
+    "X                Resume the selected process with SIGCONT",
+    "Ur               Restart the selected process' systemd unit",
+    "Us               Stop the selected process' systemd unit",
+    "v                Graph the selected process' usage, press v in the graph to stop tracking it",
+    "i                Inspect the selected process' environment, open files, and memory maps",
+    "C                Show the selected process' full command line, working directory, and arguments",
+    #[cfg(target_os = "linux")]
+    "w                Show the threads of the selected process",
+    "F                Cycle through the saved process filters from the config file",
+    "Z                Keep the selected process in the middle of the table as it moves",
+    "Space            Mark or unmark the selected process for killing, signalling, or renicing",
+    "M                Start marking a range of processes, press again to stop",
+    "dq               Queue the selected process to be killed later",
+    "gk               Review the kill queue, and kill the queued processes",
+    "+, -, click      Collapse/expand a branch while in tree mode, or a user while grouped by user",
     "click on header  Sorts the entries by that column, click again to invert the sort",
 ];
 
-pub const SEARCH_HELP_TEXT: [&str; 48] = [
+pub const SEARCH_HELP_TEXT: [&str; 52] = [
     "4 - Process search widget",
     "Esc              Close the search widget (retains the filter)",
     "Ctrl-a           Skip to the start of the search query",
@@ -301,6 +385,7 @@ pub const SEARCH_HELP_TEXT: [&str; 48] = [
     "",
     "Supported search types:",
     "<by name/cmd>    ex: btm",
+    "name             ex: name ~ \"post.*\"",
     "pid              ex: pid 825",
     "cpu, cpu%        ex: cpu > 4.2",
     "mem, mem%        ex: mem < 4.2",
@@ -309,7 +394,8 @@ pub const SEARCH_HELP_TEXT: [&str; 48] = [
     "write, w/s       ex: write <= 1 tb",
     "tread, t.read    ex: tread = 1",
     "twrite, t.write  ex: twrite = 1",
-    "user            ex: user = root",
+    "user             ex: user = root",
+    "elevated         ex: elevated = yes",
     "state            ex: state = running",
     "",
     "Comparison operators:",
@@ -318,10 +404,12 @@ pub const SEARCH_HELP_TEXT: [&str; 48] = [
     "<                ex: cpu < 1",
     ">=               ex: cpu >= 1",
     "<=               ex: cpu <= 1",
+    "~                ex: user ~ ^ro, always uses regex",
     "",
     "Logical operators:",
     "and, &&, <Space> ex: btm and cpu > 1 and mem > 1",
     "or, ||           ex: btm or firefox",
+    "not, !           ex: not user = root",
     "",
     "Supported units:",
     "B                ex: read > 1 b",
@@ -343,10 +431,11 @@ pub const SORT_HELP_TEXT: [&str; 6] = [
     "Enter            Sort by current selected column",
 ];
 
-pub const BATTERY_HELP_TEXT: [&str; 3] = [
+pub const BATTERY_HELP_TEXT: [&str; 4] = [
     "6 - Battery widget",
     "Left             Go to previous battery",
     "Right            Go to next battery",
+    "Tab              Toggle between the battery details and charge/power graphs",
 ];
 
 pub const BASIC_MEM_HELP_TEXT: [&str; 2] = [
@@ -354,15 +443,32 @@ pub const BASIC_MEM_HELP_TEXT: [&str; 2] = [
     "%                Toggle between values and percentages for memory usage",
 ];
 
+pub const NETWORK_HELP_TEXT: [&str; 2] = [
+    "8 - Network widget",
+    "T                Cycle between plotting the rates, the totals, and errors if enabled",
+];
+
+pub const DISK_TEMP_HELP_TEXT: [&str; 7] = [
+    "9 - Disk and temperature widgets",
+    "s                Sort by the next column",
+    "I                Invert current sort",
+    "click on header  Sorts the entries by that column, click again to invert the sort",
+    "Ctrl-f, /        Filter disks by name or mount point, Esc clears the filter once closed",
+    "Ctrl-u           Clear the disk filter",
+    "V                Toggle hiding pseudo-filesystems like tmpfs, squashfs, and overlay",
+];
+
 pub const HELP_TEXT: &[&[&str]] = &[
     &HELP_CONTENTS_TEXT,
     &GENERAL_HELP_TEXT,
     &CPU_HELP_TEXT,
-    &PROCESS_HELP_TEXT,
+    PROCESS_HELP_TEXT,
     &SEARCH_HELP_TEXT,
     &SORT_HELP_TEXT,
     &BATTERY_HELP_TEXT,
     &BASIC_MEM_HELP_TEXT,
+    &NETWORK_HELP_TEXT,
+    &DISK_TEMP_HELP_TEXT,
 ];
 
 // Default layouts
@@ -432,11 +538,18 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 # added when running (ie: btm -a) will override this config file if an option
 # is also set here.
 
+# Changes to this file are applied while bottom is running as soon as it is saved,
+# except for the layout and basic mode, which need a restart.
+
 [flags]
 # Whether to hide the average cpu entry.
 #hide_avg_cpu = false
 # Whether to use dot markers rather than braille.
 #dot_marker = false
+# Whether to draw everything with ASCII characters only.
+#no_unicode = false
+# Whether to show widgets as plain text for screen readers.
+#screen_reader = false
 # The update rate of the application.
 #rate = 1000
 # Whether to put the CPU legend to the left.
@@ -462,13 +575,19 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #default_time_value = 60000
 # The time delta on each zoom in/out action (in milliseconds).
 #time_delta = 15000
+# How long to keep data for graphs, like "10m" or "6h".
+#retention = "10m"
 # Hides the time scale.
 #hide_time = false
+# Hides the time scale again shortly after zooming.
+#autohide_time = false
 # Override layout default widget
 #default_widget_type = "proc"
 #default_widget_count = 1
 # Use basic mode
 #basic = false
+# Which summary rows to show in basic mode
+#basic_widgets = ["cpu", "mem", "net"]
 # Use the old network legend style
 #use_old_network_legend = false
 # Remove space in tables
@@ -477,29 +596,90 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #battery = false
 # Disable mouse clicks
 #disable_click = false
-# Built-in themes.  Valid values are "default", "default-light", "gruvbox", "gruvbox-light", "nord", "nord-light"
+# Skip collecting what Android doesn't let Termux read, and make small click targets easier to tap. Used by default in Termux.
+#termux = false
+# Built-in themes.  Valid values are "auto", "default", "default-light", "gruvbox", "gruvbox-light", "nord", "nord-light"
 #color = "default"
+# How to draw graphs.  Valid values are "text", "auto", "kitty", "sixel"
+#graph_renderer = "text"
 # Show memory values in the processes widget as values by default
 #mem_as_value = false
 # Show tree mode by default in the processes widget.
 #tree = false
 # Shows an indicator in table widgets tracking where in the list you are.
 #show_table_scroll_position = false
+# Keep a one-line summary of the CPU, memory, and network widgets visible when a widget is expanded.
+#mini_widgets = false
+# Keep each layout row at least this many lines tall, scrolling the layout if it doesn't fit on the screen.
+#min_row_height = 10
 # Show processes as their commands by default in the process widget.
 #process_command = false
+# Show a graph of recent CPU usage for each process in the process widget.
+#process_sparkline = false
+# Show the virtual size, shared memory, and swapped-out memory of each process in the process widget.
+#process_memory_details = false
+# Read shared and swapped process memory from smaps_rollup, which is more accurate but slower. Only supported on Linux.
+#precise_process_memory = false
+# Show the GPU usage and GPU memory of each process in the process widget. Only supported on Windows and for NVIDIA GPUs.
+#process_gpu = false
+# Show when each process started and how long it has been running in the process widget.
+#process_start_time = false
+# Show process start times as how long ago they were ("relative") or as a date and time ("absolute").
+#process_start_time_format = "absolute"
+# Show the systemd unit of each process in the process widget. Only supported on Linux.
+#process_systemd_unit = false
+# Show inode usage in the disk widget.
+#disk_inodes = false
+# Show SMART health information in the disk widget. Only supported on Linux, and generally requires root.
+#disk_smart = false
+# Show how busy each disk is, and how many reads and writes are queued on it, in the disk widget. Only supported on Windows.
+#disk_activity = false
+# Show an estimate of when each disk will be full in the disk widget.
+#disk_full_estimate = false
+# Highlight disks estimated to be full within this long, like 12h or 7d.
+#disk_full_warning = "1d"
+# Graph pages swapped in and out under the memory graph. Only supported on Linux.
+#swap_activity = false
+# Show the peak values seen this session in widget titles.
+#peaks = false
+# Overlay graphs with their values from this long ago, like "5m" or "1h".
+#compare = "5m"
+# Shade gaps in graphs where no data was collected, like while the system was asleep.
+#shade_gaps = false
 # Displays the network widget with binary prefixes.
 #network_use_binary_prefix = false
 # Displays the network widget using bytes.
 #network_use_bytes = false
 # Displays the network widget with a log scale.
 #network_use_log = false
+# Shows network errors and dropped packets in the network widget.
+#network_errors = false
+# Draws RX and TX as separate graphs in the network widget.
+#network_split = false
+# Shows the min, max, and mean rates in the network widget.
+#network_stats = false
+# Stacks the usage of each CPU when all are shown in the CPU graph.
+#stacked_cpu = false
+# Shows how the usage of the selected CPU breaks down by state in the CPU graph.
+#cpu_breakdown = false
+# Shows the process using the most CPU on the selected core in the CPU graph's title.
+#cpu_top_process = false
+# Shows min, average, and max lines on the CPU and memory graphs.
+#graph_stats = false
+# Shows the IP addresses of each interface in the network widget.
+#network_addresses = false
+# Shows the public IP address in the network widget. This sends a request to api.ipify.org.
+#public_ip = false
 # Hides advanced options to stop a process on Unix-like systems.
 #disable_advanced_kill = false
+# Saves layout changes made by dragging widget borders to this file on exit.
+#save_layout = false
 
 # These are all the components that support custom theming.  Note that colour support
 # will depend on terminal support.
 
-#[colors] # Uncomment if you want to use custom colors
+# Changes to these are applied while bottom is running, unless a colour scheme is set with --color.
+#[colors] # Uncomment if you want to use custom colors. This can also be called [theme].
 # Represents the colour of table headers (processes, CPU, disks, temperature).
 #table_header_color="LightBlue"
 # Represents the colour of the label each widget has.
@@ -508,6 +688,8 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #avg_cpu_color="Red"
 # Represents the colour the core will use in the CPU legend and graph.
 #cpu_core_colors=["LightMagenta", "LightYellow", "LightCyan", "LightGreen", "LightBlue", "LightRed", "Cyan", "Green", "Blue", "Red"]
+# Represents the colours of the lines in other graphs with several lines, like the load average graph. Defaults to the CPU colours.
+#series_colors=["LightMagenta", "LightYellow", "LightCyan", "LightGreen", "LightBlue", "LightRed", "Cyan", "Green", "Blue", "Red"]
 # Represents the colour RAM will use in the memory legend and graph.
 #ram_color="LightMagenta"
 # Represents the colour SWAP will use in the memory legend and graph.
@@ -532,6 +714,15 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #high_battery_color="green"
 #medium_battery_color="yellow"
 #low_battery_color="red"
+# Represents the colour of processes that have been stopped.
+#stopped_process_color="Yellow"
+# Represents the colour of processes that have been marked for batch actions.
+#marked_process_color="LightMagenta"
+# Represents the colours of network errors and dropped packets in the network legend and graph.
+#network_error_color="Red"
+#network_drop_color="Yellow"
+# Represents the colour of invalid search queries and error messages.
+#error_color="Red"
 
 # Layout - layouts follow a pattern like this:
 # [[row]] represents a row in the application.
@@ -540,6 +731,7 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #
 # All widgets must have the type value set to one of ["cpu", "mem", "proc", "net", "temp", "disk", "empty"].
 # All layout components have a ratio value - if this is not set, then it defaults to 1.
+# A column with tabs=true shows its widgets one at a time as tabs, switched between with [ and ].
 # The default widget layout:
 #[[row]]
 #  ratio=30
@@ -564,6 +756,19 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #    type="proc"
 #    default=true
 
+# More layouts can be added under [[layouts]], each with a name and rows like the ones above. Switch between them
+# with gt and gT while running, or set max_width and/or max_height to switch to one automatically while the
+# terminal is at most that size.
+#[[layouts]]
+#name = "network-debug"
+#max_width = 99
+#[[layouts.row]]
+#  [[layouts.row.child]]
+#    type="net"
+#[[layouts.row]]
+#  [[layouts.row.child]]
+#    type="latency"
+
 
 # Filters - you can hide specific temperature sensors, network interfaces, and disks using filters.  This is admittedly
 # a bit hard to use as of now, and there is a planned in-app interface for managing this in the future:
@@ -594,6 +799,86 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #regex = true
 #case_sensitive = false
 #whole_word = false
+
+# These are named process filters, which can be cycled through with F in the process widget.
+#[[process_filters]]
+#name = "docker"
+#query = "name ~ \"^docker\" or user = docker"
+
+# These are hosts to show the round-trip time to in the latency widget. Hosts with a port are
+# timed by opening a TCP connection, and hosts without one are pinged.
+#[[latency_hosts]]
+#host = "1.1.1.1"
+#[[latency_hosts]]
+#host = "example.com"
+#port = 443
+#name = "Example"
+
+# These are plugins, which are commands that report their own values to be shown in plugin
+# widgets. See the documentation for how plugins talk to bottom.
+#[[plugins]]
+#name = "redis"
+#command = "python3 ~/.config/bottom/redis_plugin.py"
+#unit = ""
+
+# These are derived metrics, which are Lua expressions worked out from the other metrics after
+# every harvest. They can be shown in plugin widgets and alerted on.
+#[[derived_metrics]]
+#name = "cpu_minus_steal"
+#expression = "cpu.total - cpu.steal"
+#unit = "%"
+
+# These colour temperatures from green to yellow to red as they approach the warning and
+# critical temperatures, in Celsius. Sensors can be given their own thresholds by name.
+# "alert_on_critical" shows an alert when any sensor goes over its critical temperature.
+#[temp_thresholds]
+#warning = 70.0
+#critical = 90.0
+#sensors = { "nvme Composite" = { warning = 50.0, critical = 70.0 } }
+#alert_on_critical = true
+
+# These rename temperature sensors, disks, and network interfaces, as "name" = "new name",
+# and hide any listed in "hidden". Disks can be matched by name or by mount point.
+#[labels]
+#hidden = ["acpitz temp1"]
+#temperature = { "coretemp Package id 0" = "CPU" }
+#disk = { "/" = "System" }
+#network = { "wlp3s0" = "Wi-Fi" }
+
+# These set how often each type of data is refreshed, in milliseconds.
+# Any that are not set use the "rate" flag, except for events, which default to 30 seconds.
+#[refresh_rates]
+#network = 250
+#processes = 2000
+#temperature = 5000
+
+# These are rebindings for key bindings, as "action" = "key sequence".
+# See the documentation for the list of actions.
+#[keybindings]
+#move_down = "j"
+#skip_to_first = "gg"
+#kill_process = "dd"
+
+# Hooks run external commands when a key is pressed. {pid}, {name}, and {user} are
+# replaced with details of the selected process, and {mount} with the selected disk.
+#[[hooks]]
+#key = "F9"
+#command = "gdb -p {pid}"
+#[[hooks]]
+#key = "gl"
+#command = "lsof -p {pid}"
+#pause = true
+
+# Alerts show a message when a metric goes over a threshold, and can also send a desktop
+# notification or POST a JSON payload to an http:// webhook.
+#[[alerts]]
+#metric = "cpu"
+#threshold = 90.0
+#notify = true
+#[[alerts]]
+#metric = "disk"
+#threshold = 95.0
+#webhook = "http://localhost:8080/alerts"
 "##;
 
 pub const CONFIG_TOP_HEAD: &str = r##"# This is bottom's config file.
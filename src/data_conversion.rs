@@ -1,14 +1,27 @@
 //! This mainly concerns converting collected data into things that the canvas
 //! can actually handle.
 
-use crate::components::text_table::CellContent;
+use crate::components::text_table::{CellContent, SortState};
 use crate::components::time_graph::Point;
 use crate::{app::AxisScaling, units::data_units::DataUnit, Pid};
 use crate::{
-    app::{data_farmer, data_harvester, App},
+    app::{
+        data_farmer, data_harvester,
+        data_harvester::{
+            cpu::CPU_STATES,
+            temperature::{TempThresholds, TemperatureType},
+        },
+        widgets::{DiskWidgetState, TableFilter, TempWidgetState},
+        App, CpuNode,
+    },
     utils::gen_util::*,
 };
 
+use std::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+
 use concat_string::concat_string;
 use fxhash::FxHashMap;
 
@@ -20,6 +33,56 @@ pub struct ConvertedBatteryData {
     pub duration_until_full: Option<String>,
     pub duration_until_empty: Option<String>,
     pub health: String,
+    /// Tuple is time, charge percentage
+    pub charge_data: Vec<Point>,
+    /// Tuple is time, power draw in watts
+    pub power_data: Vec<Point>,
+}
+
+#[derive(Default, Debug)]
+pub struct ConvertedPowerData {
+    /// Tuple is time, power draw in watts
+    pub package_data: Vec<Point>,
+    pub core_data: Vec<Point>,
+    pub dram_data: Vec<Point>,
+    /// The current power draw of each domain, if it is supported.
+    pub package_label: Option<String>,
+    pub core_label: Option<String>,
+    pub dram_label: Option<String>,
+}
+
+#[derive(Default, Debug)]
+pub struct ConvertedPluginData {
+    /// A legend entry for each series, like `hits: 12.5`.
+    pub legend: Vec<String>,
+    /// The values of each series over time.
+    pub history: Vec<Vec<Point>>,
+    /// Why the plugin isn't replying, if it isn't.
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug)]
+pub struct ConvertedFrequencyData {
+    /// The average clock speed of all CPUs in MHz over time.
+    pub average: Vec<Point>,
+    /// The clock speed of each CPU in MHz over time.
+    pub cpus: Vec<Vec<Point>>,
+    /// The current average clock speed in MHz.
+    pub current_average: Option<f64>,
+    /// The highest clock speed any CPU can reach in MHz, if known.
+    pub max_frequency: Option<f64>,
+    /// The name, residency percentage over time, and current residency percentage of each
+    /// C-state.
+    pub c_states: Vec<(String, Vec<Point>, f64)>,
+}
+
+#[derive(Default, Debug)]
+pub struct ConvertedPressureData {
+    pub name: &'static str,
+    /// Tuple is time, 10 second average stall percentage
+    pub data: Vec<Point>,
+    /// The current 10 and 60 second average stall percentages.
+    pub label: String,
 }
 
 #[derive(Default, Debug)]
@@ -45,29 +108,74 @@ impl TableRow {
 
 #[derive(Default, Debug)]
 pub struct ConvertedNetworkData {
-    pub rx: Vec<Point>,
-    pub tx: Vec<Point>,
     pub rx_display: String,
     pub tx_display: String,
     pub total_rx_display: Option<String>,
     pub total_tx_display: Option<String>,
-    // TODO: [NETWORKING] add min/max/mean of each
-    // min_rx : f64,
-    // max_rx : f64,
-    // mean_rx: f64,
-    // min_tx: f64,
-    // max_tx: f64,
-    // mean_tx: f64,
+    pub error_display: String,
+    pub drop_display: String,
+    pub stats: Option<NetworkStats>,
+    /// The stats as lines for RX and TX, if they were asked for.
+    pub stats_display: Option<(String, String)>,
 }
 
-#[derive(Clone, Default, Debug)]
+/// The lowest, highest, and mean rates received and transmitted over some window of time, in
+/// bits per second.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NetworkStats {
+    pub min_rx: f64,
+    pub max_rx: f64,
+    pub mean_rx: f64,
+    pub min_tx: f64,
+    pub max_tx: f64,
+    pub mean_tx: f64,
+}
+
+/// What an entry in the CPU widget shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CpuEntry {
+    All,
+    Average,
+    /// A single CPU, by its number.
+    Cpu(usize),
+    /// The average of a group of CPUs, by its index in [`crate::app::CpuState::nodes`]. Its CPUs are only
+    /// shown if it isn't collapsed.
+    Node {
+        node: usize,
+        is_collapsed: bool,
+    },
+}
+
+#[derive(Clone, Debug)]
 pub struct ConvertedCpuData {
+    pub entry: CpuEntry,
     pub cpu_name: String,
     pub short_cpu_name: String,
-    /// Tuple is time, value
-    pub cpu_data: Vec<Point>,
+    pub cpu_data: GraphPoints,
+    /// The usage of this CPU added to that of the CPUs before it, for the stacked graph. This is
+    /// only converted for CPUs, not the average, and only if stacking is turned on.
+    pub stacked_data: GraphPoints,
+    /// The usage of this CPU in each of [`CPU_STATES`], if the breakdown is turned on.
+    pub breakdown_data: Vec<GraphPoints>,
     /// Represents the value displayed on the legend.
     pub legend_value: String,
+    pub stats: Option<GraphStats>,
+}
+
+/// The lowest, mean, and highest values of a graph over some window of time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GraphStats {
+    pub min: f64,
+    pub mean: f64,
+    pub max: f64,
+}
+
+/// The session peaks of each widget, which are only set once there's data for them.
+#[derive(Default, Debug)]
+pub struct PeakDisplay {
+    pub cpu: Option<String>,
+    pub mem: Option<String>,
+    pub network: Option<String>,
 }
 
 #[derive(Default)]
@@ -76,10 +184,34 @@ pub struct ConvertedData {
     pub tx_display: String,
     pub total_rx_display: String,
     pub total_tx_display: String,
-    pub network_data_rx: Vec<Point>,
-    pub network_data_tx: Vec<Point>,
-    pub disk_data: TableData,
-    pub temp_sensor_data: TableData,
+    pub network_data_rx: GraphPoints,
+    pub network_data_tx: GraphPoints,
+    pub network_data_total_rx: GraphPoints,
+    pub network_data_total_tx: GraphPoints,
+    /// Errors and dropped packets per second.
+    pub network_data_errors: GraphPoints,
+    pub network_data_drops: GraphPoints,
+    pub error_display: String,
+    pub drop_display: String,
+    /// Stats for the memory and swap graphs over the visible time, if enabled.
+    pub mem_stats: Option<GraphStats>,
+    pub swap_stats: Option<GraphStats>,
+    /// Min/max/mean lines for RX and TX over the visible time, if enabled.
+    pub network_stats_display: Option<(String, String)>,
+    /// A details line for each wireless interface.
+    pub wireless_display: Vec<String>,
+    /// An addresses line for each interface, and for the public IP if it is shown.
+    pub address_display: Vec<String>,
+    pub cgroup_data: TableData,
+    pub event_data: TableData,
+    pub user_data: TableData,
+    /// A label and value for each line of the system info widget.
+    pub system_info_display: Vec<(&'static str, String)>,
+    pub top_process_display: Vec<(&'static str, String)>,
+    /// The session peaks shown in widget titles, if enabled.
+    pub peak_display: PeakDisplay,
+    /// Where graphs have gaps in their data, like from the system sleeping, as ranges of the x-axis.
+    pub gaps: Vec<Point>,
 
     /// A mapping from a process name to any PID with that name.
     pub process_name_pid_map: FxHashMap<String, Vec<Pid>>,
@@ -90,42 +222,165 @@ pub struct ConvertedData {
     pub mem_labels: Option<(String, String)>,
     pub swap_labels: Option<(String, String)>,
 
-    pub mem_data: Vec<Point>, // TODO: Switch this and all data points over to a better data structure...
-    pub swap_data: Vec<Point>,
+    pub mem_data: GraphPoints,
+    pub swap_data: GraphPoints,
+    /// Pages swapped in and out per second, and legend entries for the latest rates.
+    pub swap_in_data: GraphPoints,
+    pub swap_out_data: GraphPoints,
+    pub swap_activity_labels: Option<(String, String)>,
     pub load_avg_data: [f32; 3],
+    /// The 1, 5, and 15 minute load averages over time.
+    pub load_avg_history: [Vec<Point>; 3],
+    /// A legend entry for each latency host, like `1.1.1.1: 12.3 ms`.
+    pub latency_legend: Vec<String>,
+    /// The round-trip times of each latency host over time.
+    pub latency_history: Vec<Vec<Point>>,
+    /// The series of each plugin, keyed by the plugin's name.
+    pub plugin_data: FxHashMap<String, ConvertedPluginData>,
     pub cpu_data: Vec<ConvertedCpuData>,
     pub battery_data: Vec<ConvertedBatteryData>,
+    pub power_data: ConvertedPowerData,
+    pub pressure_data: Vec<ConvertedPressureData>,
+    pub frequency_data: ConvertedFrequencyData,
+
+    /// The CPU and memory usage of the pinned process currently being shown.
+    pub pinned_process_cpu_data: Vec<Point>,
+    pub pinned_process_mem_data: Vec<Point>,
+}
+
+impl ConvertedData {
+    /// Clears the network graphs, so they're converted again from scratch. Their points depend on
+    /// the scale and unit, so this is needed whenever those change.
+    pub fn clear_network_graphs(&mut self) {
+        for points in [
+            &mut self.network_data_rx,
+            &mut self.network_data_tx,
+            &mut self.network_data_total_rx,
+            &mut self.network_data_total_tx,
+        ]
+        .iter_mut()
+        {
+            points.clear();
+        }
+    }
 }
 
-pub fn convert_temp_row(app: &App) -> TableData {
-    let current_data = &app.data_collection;
-    let temp_type = &app.app_config_fields.temperature_type;
+/// Names to show in place of the harvested names of temperature sensors, disks, and network
+/// interfaces, and the harvested names of any to hide. Sensor thresholds still go by the
+/// harvested names.
+#[derive(Clone, Debug, Default)]
+pub struct Labels {
+    pub temperature: HashMap<String, String>,
+    /// Disks are matched by name, like `/dev/sda1`, or by mount point.
+    pub disk: HashMap<String, String>,
+    pub network: HashMap<String, String>,
+    pub hidden: HashSet<String>,
+}
+
+impl Labels {
+    pub fn is_hidden(&self, name: &str) -> bool {
+        self.hidden.contains(name)
+    }
+
+    pub fn temperature<'a>(&'a self, name: &'a str) -> &'a str {
+        self.temperature.get(name).map_or(name, String::as_str)
+    }
+
+    pub fn network<'a>(&'a self, name: &'a str) -> &'a str {
+        self.network.get(name).map_or(name, String::as_str)
+    }
+
+    pub fn is_disk_hidden(&self, disk: &data_harvester::disks::DiskHarvest) -> bool {
+        self.is_hidden(&disk.name) || self.is_hidden(&disk.mount_point)
+    }
+
+    pub fn disk<'a>(&'a self, disk: &'a data_harvester::disks::DiskHarvest) -> &'a str {
+        self.disk
+            .get(&disk.name)
+            .or_else(|| self.disk.get(&disk.mount_point))
+            .map_or(&disk.name, String::as_str)
+    }
+}
+
+/// Sorts `data` by a key, where keys that can't be compared are treated as equal. The sort is
+/// stable, so entries with equal keys keep their harvested order.
+fn sort_by_partial_key<T, K: PartialOrd>(data: &mut [T], descending: bool, key: impl Fn(&T) -> K) {
+    data.sort_by(|a, b| sort_partial_fn(descending)(key(a), key(b)));
+}
+
+/// Returns a row for each sensor, sorted by the widget's sort column.
+pub fn convert_temp_row(
+    current_data: &data_farmer::DataCollection, temp_type: &TemperatureType,
+    temp_thresholds: &TempThresholds, labels: &Labels, filter: &TableFilter,
+    sort_state: &SortState, show_peaks: bool,
+) -> TableData {
+    let unit = match temp_type {
+        TemperatureType::Celsius => "°C",
+        TemperatureType::Kelvin => "K",
+        TemperatureType::Fahrenheit => "°F",
+    };
+
     let mut col_widths = vec![0; 2];
 
-    let mut sensor_vector: Vec<TableRow> = current_data
+    let mut sensors = current_data
         .temp_harvest
         .iter()
+        .filter(|temp_harvest| {
+            !labels.is_hidden(&temp_harvest.name)
+                && (filter.is_match(&temp_harvest.name)
+                    || filter.is_match(labels.temperature(&temp_harvest.name)))
+        })
+        .collect::<Vec<_>>();
+    if let SortState::Sortable(sort) = sort_state {
+        let descending = sort.order.is_descending();
+        match sort.current_index {
+            TempWidgetState::SENSOR => sort_by_partial_key(&mut sensors, descending, |t| {
+                labels.temperature(&t.name).to_lowercase()
+            }),
+            TempWidgetState::TEMPERATURE => {
+                sort_by_partial_key(&mut sensors, descending, |t| t.temperature)
+            }
+            _ => {}
+        }
+    }
+
+    let mut sensor_vector: Vec<TableRow> = sensors
+        .into_iter()
         .map(|temp_harvest| {
             let row = vec![
-                CellContent::Simple(temp_harvest.name.clone().into()),
+                CellContent::Simple(labels.temperature(&temp_harvest.name).to_string().into()),
                 CellContent::Simple(
-                    concat_string!(
-                        (temp_harvest.temperature.ceil() as u64).to_string(),
-                        match temp_type {
-                            data_harvester::temperature::TemperatureType::Celsius => "°C",
-                            data_harvester::temperature::TemperatureType::Kelvin => "K",
-                            data_harvester::temperature::TemperatureType::Fahrenheit => "°F",
-                        }
-                    )
+                    match current_data.peaks.temperatures.get(&temp_harvest.name) {
+                        Some(peak) if show_peaks => format!(
+                            "{}{} (peak {}{})",
+                            temp_harvest.temperature.ceil() as u64,
+                            unit,
+                            peak.ceil() as u64,
+                            unit
+                        ),
+                        _ => concat_string!(
+                            (temp_harvest.temperature.ceil() as u64).to_string(),
+                            unit
+                        ),
+                    }
                     .into(),
                 ),
             ];
 
             col_widths.iter_mut().zip(&row).for_each(|(curr, r)| {
-                *curr = std::cmp::max(*curr, r.len());
+                *curr = std::cmp::max(*curr, r.width());
             });
 
-            TableRow::Raw(row)
+            if let Some(threshold) = temp_thresholds.get_threshold(&temp_harvest.name) {
+                let colour = get_temperature_colour(
+                    temp_harvest.temperature,
+                    temp_type.convert_from_celsius(threshold.warning),
+                    temp_type.convert_from_celsius(threshold.critical),
+                );
+                TableRow::Styled(row, tui::style::Style::default().fg(colour))
+            } else {
+                TableRow::Raw(row)
+            }
         })
         .collect();
 
@@ -142,15 +397,347 @@ pub fn convert_temp_row(app: &App) -> TableData {
     }
 }
 
-pub fn convert_disk_row(current_data: &data_farmer::DataCollection) -> TableData {
+/// Returns a row for each cgroup, sorted by CPU usage.
+pub fn convert_cgroup_row(app: &App) -> TableData {
+    let mut col_widths = vec![0; 5];
+
+    let mut cgroups = app
+        .data_collection
+        .cgroup_harvest
+        .iter()
+        .collect::<Vec<_>>();
+    cgroups.sort_by(|a, b| {
+        b.cpu_usage_percent
+            .partial_cmp(&a.cpu_usage_percent)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let format_rate = |rate: Option<u64>| match rate {
+        Some(rate) => {
+            let (value, unit) = get_decimal_bytes(rate);
+            format!("{:.0}{}/s", value, unit).into()
+        }
+        None => "N/A".into(),
+    };
+
+    let mut cgroup_vector: Vec<TableRow> = cgroups
+        .into_iter()
+        .map(|cgroup| {
+            let row = vec![
+                CellContent::Simple(cgroup.name.clone().into()),
+                CellContent::Simple(format!("{:.1}%", cgroup.cpu_usage_percent).into()),
+                CellContent::Simple(match cgroup.mem_usage_bytes {
+                    Some(mem_usage_bytes) => {
+                        let (value, unit) = get_binary_bytes(mem_usage_bytes);
+                        format!("{:.1}{}", value, unit).into()
+                    }
+                    None => "N/A".into(),
+                }),
+                CellContent::Simple(format_rate(cgroup.read_bytes_per_sec)),
+                CellContent::Simple(format_rate(cgroup.write_bytes_per_sec)),
+            ];
+
+            col_widths.iter_mut().zip(&row).for_each(|(curr, r)| {
+                *curr = std::cmp::max(*curr, r.width());
+            });
+
+            TableRow::Raw(row)
+        })
+        .collect();
+
+    if cgroup_vector.is_empty() {
+        cgroup_vector.push(TableRow::Raw(vec![
+            CellContent::Simple("No Cgroups Found".into()),
+            CellContent::Simple("".into()),
+            CellContent::Simple("".into()),
+            CellContent::Simple("".into()),
+            CellContent::Simple("".into()),
+        ]));
+    }
+
+    TableData {
+        data: cgroup_vector,
+        col_widths,
+    }
+}
+
+/// Returns a row for each kernel event, newest first.
+pub fn convert_event_row(app: &App) -> TableData {
+    let mut col_widths = vec![0; 3];
+
+    let mut event_vector: Vec<TableRow> = app
+        .data_collection
+        .kernel_event_harvest
+        .iter()
+        .rev()
+        .map(|event| {
+            let row = vec![
+                CellContent::Simple(event.time.clone().into()),
+                CellContent::Simple(event.event_type.get_name().into()),
+                CellContent::Simple(event.message.clone().into()),
+            ];
+
+            col_widths.iter_mut().zip(&row).for_each(|(curr, r)| {
+                *curr = std::cmp::max(*curr, r.width());
+            });
+
+            TableRow::Raw(row)
+        })
+        .collect();
+
+    if event_vector.is_empty() {
+        event_vector.push(TableRow::Raw(vec![
+            CellContent::Simple("No Events Found".into()),
+            CellContent::Simple("".into()),
+            CellContent::Simple("".into()),
+        ]));
+    }
+
+    TableData {
+        data: event_vector,
+        col_widths,
+    }
+}
+
+/// Returns a label and value for each line of the system info widget, like `("Uptime", "3d 4h")`.
+/// Anything that isn't known is shown as `N/A`.
+pub fn convert_system_info(app: &App) -> Vec<(&'static str, String)> {
+    let system_info = app.data_collection.system_info_harvest.as_ref();
+    let get_value = |value: Option<String>| value.unwrap_or_else(|| "N/A".to_string());
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or(0);
+
+    vec![
+        (
+            "Host",
+            get_value(system_info.and_then(|info| info.hostname.clone())),
+        ),
+        (
+            "OS",
+            get_value(system_info.and_then(|info| info.os_name.clone())),
+        ),
+        (
+            "Kernel",
+            get_value(system_info.and_then(|info| info.kernel_version.clone())),
+        ),
+        (
+            "Uptime",
+            get_value(system_info.map(|info| duration_string(info.uptime_secs))),
+        ),
+        (
+            "Users",
+            get_value(
+                system_info
+                    .and_then(|info| info.num_users)
+                    .map(|num_users| num_users.to_string()),
+            ),
+        ),
+        ("Time", timestamp_string(now)),
+    ]
+}
+
+/// Returns a label and value for the process using the most CPU and the process using the most
+/// memory, like `("CPU", "firefox 23.4%")`, or `N/A` if there are no processes.
+pub fn convert_top_processes(app: &App) -> Vec<(&'static str, String)> {
+    let processes = &app.data_collection.process_data.process_harvest;
+
+    let top_cpu = processes
+        .values()
+        .max_by(|a, b| partial_ordering(a.cpu_usage_percent, b.cpu_usage_percent))
+        .map(|process| format!("{} {:.1}%", process.name, process.cpu_usage_percent));
+    let top_mem = processes
+        .values()
+        .max_by_key(|process| process.mem_usage_bytes)
+        .map(|process| {
+            format!(
+                "{} {} ({:.1}%)",
+                process.name,
+                binary_byte_string(process.mem_usage_bytes),
+                process.mem_usage_percent
+            )
+        });
+
+    vec![
+        ("CPU", top_cpu.unwrap_or_else(|| "N/A".to_string())),
+        ("Mem", top_mem.unwrap_or_else(|| "N/A".to_string())),
+    ]
+}
+
+/// Returns a row for each user session, sorted by user and then by login time.
+pub fn convert_user_row(app: &App) -> TableData {
+    let mut col_widths = vec![0; 5];
+
+    let mut sessions: Vec<_> = app.data_collection.user_session_harvest.iter().collect();
+    sessions.sort_by(|a, b| (&a.user, a.login_time).cmp(&(&b.user, b.login_time)));
+
+    let mut user_vector: Vec<TableRow> = sessions
+        .into_iter()
+        .map(|session| {
+            let row = vec![
+                CellContent::Simple(session.user.clone().into()),
+                CellContent::Simple(session.tty.clone().into()),
+                CellContent::Simple(session.host.clone().unwrap_or_default().into()),
+                CellContent::Simple(timestamp_string(session.login_time).into()),
+                CellContent::Simple(
+                    session
+                        .idle_secs
+                        .map(duration_string)
+                        .unwrap_or_else(|| "N/A".to_string())
+                        .into(),
+                ),
+            ];
+
+            col_widths.iter_mut().zip(&row).for_each(|(curr, r)| {
+                *curr = std::cmp::max(*curr, r.width());
+            });
+
+            TableRow::Raw(row)
+        })
+        .collect();
+
+    if user_vector.is_empty() {
+        user_vector.push(TableRow::Raw(vec![
+            CellContent::Simple("No Users Found".into()),
+            CellContent::Simple("".into()),
+            CellContent::Simple("".into()),
+            CellContent::Simple("".into()),
+            CellContent::Simple("".into()),
+        ]));
+    }
+
+    TableData {
+        data: user_vector,
+        col_widths,
+    }
+}
+
+/// Returns the colour of a temperature on a gradient that goes from green, to yellow at the
+/// warning temperature, to red at the critical temperature. The green to yellow section spans
+/// the same range below the warning temperature as the warning to critical section.
+fn get_temperature_colour(temperature: f32, warning: f32, critical: f32) -> tui::style::Color {
+    const GREEN: (f32, f32, f32) = (0.0, 200.0, 0.0);
+    const YELLOW: (f32, f32, f32) = (230.0, 200.0, 0.0);
+    const RED: (f32, f32, f32) = (220.0, 0.0, 0.0);
+
+    let range = critical - warning;
+    let ((r1, g1, b1), (r2, g2, b2), ratio) = if temperature < warning {
+        (GREEN, YELLOW, 1.0 - (warning - temperature) / range)
+    } else {
+        (YELLOW, RED, (temperature - warning) / range)
+    };
+    let ratio = ratio.clamp(0.0, 1.0);
+    let interpolate = |start: f32, end: f32| (start + (end - start) * ratio).round() as u8;
+
+    tui::style::Color::Rgb(
+        interpolate(r1, r2),
+        interpolate(g1, g2),
+        interpolate(b1, b2),
+    )
+}
+
+/// Returns a row for each disk shown by the widget, sorted by the widget's sort column.
+pub fn convert_disk_row(
+    current_data: &data_farmer::DataCollection, temp_type: &TemperatureType, labels: &Labels,
+    disk_full_warning: u64, disk_widget_state: &DiskWidgetState,
+) -> TableData {
     let mut disk_vector: Vec<TableRow> = Vec::new();
-    let mut col_widths = vec![0; 8];
+    let mut col_widths = vec![0; 16];
 
-    current_data
+    let mut disks = current_data
         .disk_harvest
         .iter()
         .zip(&current_data.io_labels)
-        .for_each(|(disk, (io_read, io_write))| {
+        .zip(&current_data.io_labels_and_prev)
+        .map(|((disk, io_labels), (io_rates, _io_prev))| (disk, io_labels, *io_rates))
+        .filter(|(disk, ..)| !labels.is_disk_hidden(disk) && disk_widget_state.is_disk_shown(disk))
+        .collect::<Vec<_>>();
+    let show_full_estimate =
+        !disk_widget_state.table_state.columns[DiskWidgetState::FULL_IN].is_hidden;
+    let get_time_until_full = |disk: &data_harvester::disks::DiskHarvest| {
+        if show_full_estimate {
+            current_data.get_disk_time_until_full(&disk.mount_point)
+        } else {
+            None
+        }
+    };
+
+    if let SortState::Sortable(sort) = &disk_widget_state.table_state.sort_state {
+        let descending = sort.order.is_descending();
+        let disks = &mut disks;
+        let get_percentage = |used: Option<u64>, total: Option<u64>| match (used, total) {
+            (Some(used), Some(total)) if total > 0 => Some(used as f64 / total as f64),
+            _ => None,
+        };
+
+        match sort.current_index {
+            DiskWidgetState::DISK => {
+                sort_by_partial_key(disks, descending, |(d, ..)| labels.disk(d).to_lowercase())
+            }
+            DiskWidgetState::MOUNT => {
+                sort_by_partial_key(disks, descending, |(d, ..)| d.mount_point.to_lowercase())
+            }
+            DiskWidgetState::USED_PERCENT => sort_by_partial_key(disks, descending, |(d, ..)| {
+                get_percentage(d.used_space, d.total_space)
+            }),
+            DiskWidgetState::FREE => sort_by_partial_key(disks, descending, |(d, ..)| d.free_space),
+            DiskWidgetState::TOTAL => {
+                sort_by_partial_key(disks, descending, |(d, ..)| d.total_space)
+            }
+            DiskWidgetState::READ_PER_SECOND => {
+                sort_by_partial_key(disks, descending, |(.., (read, _))| *read)
+            }
+            DiskWidgetState::WRITE_PER_SECOND => {
+                sort_by_partial_key(disks, descending, |(.., (_, write))| *write)
+            }
+            DiskWidgetState::ACTIVE_PERCENT => sort_by_partial_key(disks, descending, |(d, ..)| {
+                d.activity.map(|a| a.active_percent)
+            }),
+            DiskWidgetState::QUEUE_LENGTH => sort_by_partial_key(disks, descending, |(d, ..)| {
+                d.activity.map(|a| a.queue_length)
+            }),
+            DiskWidgetState::FREE_INODES => {
+                sort_by_partial_key(disks, descending, |(d, ..)| d.free_inodes)
+            }
+            DiskWidgetState::USED_INODES_PERCENT => {
+                sort_by_partial_key(disks, descending, |(d, ..)| {
+                    get_percentage(
+                        d.total_inodes
+                            .zip(d.free_inodes)
+                            .map(|(total, free)| total.saturating_sub(free)),
+                        d.total_inodes,
+                    )
+                })
+            }
+            DiskWidgetState::SMART_TEMPERATURE => {
+                sort_by_partial_key(disks, descending, |(d, ..)| {
+                    d.smart.as_ref().and_then(|s| s.temperature)
+                })
+            }
+            DiskWidgetState::SMART_WEAR => sort_by_partial_key(disks, descending, |(d, ..)| {
+                d.smart.as_ref().and_then(|s| s.wear_level)
+            }),
+            DiskWidgetState::SMART_REALLOCATED => {
+                sort_by_partial_key(disks, descending, |(d, ..)| {
+                    d.smart.as_ref().and_then(|s| s.reallocated_sectors)
+                })
+            }
+            DiskWidgetState::SMART_POWER_ON_HOURS => {
+                sort_by_partial_key(disks, descending, |(d, ..)| {
+                    d.smart.as_ref().and_then(|s| s.power_on_hours)
+                })
+            }
+            DiskWidgetState::FULL_IN => sort_by_partial_key(disks, descending, |(d, ..)| {
+                get_time_until_full(d).unwrap_or(std::time::Duration::MAX)
+            }),
+            _ => {}
+        }
+    }
+
+    disks
+        .into_iter()
+        .for_each(|(disk, (io_read, io_write), _io_rates)| {
             let free_space_fmt = if let Some(free_space) = disk.free_space {
                 let converted_free_space = get_decimal_bytes(free_space);
                 format!("{:.*}{}", 0, converted_free_space.0, converted_free_space.1).into()
@@ -176,19 +763,106 @@ pub fn convert_disk_row(current_data: &data_farmer::DataCollection) -> TableData
                 "N/A".into()
             };
 
+            let (active_fmt, queue_fmt) = if let Some(activity) = disk.activity {
+                (
+                    format!("{:.0}%", activity.active_percent).into(),
+                    format!("{:.1}", activity.queue_length).into(),
+                )
+            } else {
+                ("N/A".into(), "N/A".into())
+            };
+
+            let inode_free_fmt = if let Some(free_inodes) = disk.free_inodes {
+                let converted_free_inodes = get_decimal_prefix(free_inodes, "");
+                format!(
+                    "{:.*}{}",
+                    0, converted_free_inodes.0, converted_free_inodes.1
+                )
+                .into()
+            } else {
+                "N/A".into()
+            };
+            let inode_usage_fmt = if let (Some(total_inodes), Some(free_inodes)) =
+                (disk.total_inodes, disk.free_inodes)
+            {
+                format!(
+                    "{:.0}%",
+                    total_inodes.saturating_sub(free_inodes) as f64 / total_inodes as f64 * 100_f64
+                )
+                .into()
+            } else {
+                "N/A".into()
+            };
+
+            let smart = disk.smart.as_ref();
+            let smart_temp_fmt = if let Some(temperature) = smart.and_then(|s| s.temperature) {
+                concat_string!(
+                    (temperature.ceil() as u64).to_string(),
+                    match temp_type {
+                        TemperatureType::Celsius => "°C",
+                        TemperatureType::Kelvin => "K",
+                        TemperatureType::Fahrenheit => "°F",
+                    }
+                )
+                .into()
+            } else {
+                "N/A".into()
+            };
+            let wear_fmt = if let Some(wear_level) = smart.and_then(|s| s.wear_level) {
+                format!("{}%", wear_level).into()
+            } else {
+                "N/A".into()
+            };
+            let reallocated_fmt =
+                if let Some(reallocated) = smart.and_then(|s| s.reallocated_sectors) {
+                    reallocated.to_string().into()
+                } else {
+                    "N/A".into()
+                };
+            let power_on_fmt = if let Some(hours) = smart.and_then(|s| s.power_on_hours) {
+                hours.to_string().into()
+            } else {
+                "N/A".into()
+            };
+            let time_until_full = get_time_until_full(disk);
+            let full_in_fmt = if let Some(time_until_full) = time_until_full {
+                concat_string!("~", duration_string(time_until_full.as_secs())).into()
+            } else {
+                "N/A".into()
+            };
+
             let row = vec![
-                CellContent::Simple(disk.name.clone().into()),
+                CellContent::Simple(labels.disk(disk).to_string().into()),
                 CellContent::Simple(disk.mount_point.clone().into()),
                 CellContent::Simple(usage_fmt),
                 CellContent::Simple(free_space_fmt),
                 CellContent::Simple(total_space_fmt),
                 CellContent::Simple(io_read.clone().into()),
                 CellContent::Simple(io_write.clone().into()),
+                CellContent::Simple(active_fmt),
+                CellContent::Simple(queue_fmt),
+                CellContent::Simple(inode_free_fmt),
+                CellContent::Simple(inode_usage_fmt),
+                CellContent::Simple(smart_temp_fmt),
+                CellContent::Simple(wear_fmt),
+                CellContent::Simple(reallocated_fmt),
+                CellContent::Simple(power_on_fmt),
+                CellContent::Simple(full_in_fmt),
             ];
             col_widths.iter_mut().zip(&row).for_each(|(curr, r)| {
-                *curr = std::cmp::max(*curr, r.len());
+                *curr = std::cmp::max(*curr, r.width());
             });
-            disk_vector.push(TableRow::Raw(row));
+
+            // Failing disks and disks that will soon be full are styled; the actual style is
+            // applied by the painter.
+            let is_filling_soon = time_until_full
+                .map(|time_until_full| time_until_full.as_millis() < disk_full_warning.into())
+                .unwrap_or(false);
+            if smart.map(|s| s.is_failing).unwrap_or(false) || is_filling_soon {
+                disk_vector.push(TableRow::Styled(row, tui::style::Style::default()));
+            } else {
+                disk_vector.push(TableRow::Raw(row));
+            }
         });
 
     if disk_vector.is_empty() {
@@ -204,120 +878,768 @@ pub fn convert_disk_row(current_data: &data_farmer::DataCollection) -> TableData
     }
 }
 
+/// Converts the CPU data that came in since the last conversion, downsampled to buckets of
+/// `bucket_width` milliseconds. If `stats_time_window` is set, the stats of each CPU over that
+/// many milliseconds are also worked out. If `stacked` is set, so is the stacked usage of each
+/// CPU, and if `breakdown` is set, so is the usage of each CPU in each state.
 pub fn convert_cpu_data_points(
     current_data: &data_farmer::DataCollection, existing_cpu_data: &mut Vec<ConvertedCpuData>,
+    nodes: &[CpuNode], stats_time_window: Option<u64>, bucket_width: f64, stacked: bool,
+    breakdown: bool,
 ) {
-    let current_time = if let Some(frozen_instant) = current_data.frozen_instant {
-        frozen_instant
-    } else {
-        current_data.current_instant
+    let cpu_harvest = &current_data.cpu_harvest;
+    let get_index = |cpu: usize| {
+        cpu_harvest
+            .iter()
+            .position(|cpu_data| cpu_data.cpu_count == Some(cpu))
     };
 
-    // Initialize cpu_data_vector if the lengths don't match...
-    if let Some((_time, data)) = &current_data.timed_data_vec.last() {
-        if data.cpu_data.len() + 1 != existing_cpu_data.len() {
-            *existing_cpu_data = vec![ConvertedCpuData {
-                cpu_name: "All".to_string(),
-                short_cpu_name: "".to_string(),
-                cpu_data: vec![],
-                legend_value: String::new(),
-            }];
-
-            existing_cpu_data.extend(
-                data.cpu_data
+    // Each entry, along with the indices of the harvested CPUs it's the average of. The average
+    // comes first, then each node followed by its CPUs unless it's collapsed, then any CPUs that
+    // aren't in a node.
+    let mut entries = vec![(CpuEntry::All, Vec::new())];
+    entries.extend(
+        cpu_harvest
+            .iter()
+            .position(|cpu_data| cpu_data.cpu_count.is_none())
+            .map(|itx| (CpuEntry::Average, vec![itx])),
+    );
+    for (node, cpu_node) in nodes.iter().enumerate() {
+        let indices = cpu_node
+            .cpus
+            .iter()
+            .filter_map(|cpu| get_index(*cpu))
+            .collect::<Vec<_>>();
+        if indices.is_empty() {
+            continue;
+        }
+
+        entries.push((
+            CpuEntry::Node {
+                node,
+                is_collapsed: cpu_node.is_collapsed,
+            },
+            indices,
+        ));
+        if !cpu_node.is_collapsed {
+            entries.extend(
+                cpu_node
+                    .cpus
                     .iter()
-                    .enumerate()
-                    .map(|(itx, cpu_usage)| ConvertedCpuData {
-                        cpu_name: if let Some(cpu_harvest) = current_data.cpu_harvest.get(itx) {
-                            if let Some(cpu_count) = cpu_harvest.cpu_count {
-                                format!("{}{}", cpu_harvest.cpu_prefix, cpu_count)
-                            } else {
-                                cpu_harvest.cpu_prefix.to_string()
-                            }
-                        } else {
-                            String::default()
-                        },
-                        short_cpu_name: if let Some(cpu_harvest) = current_data.cpu_harvest.get(itx)
-                        {
-                            if let Some(cpu_count) = cpu_harvest.cpu_count {
-                                cpu_count.to_string()
-                            } else {
-                                cpu_harvest.cpu_prefix.to_string()
-                            }
-                        } else {
-                            String::default()
-                        },
-                        legend_value: format!("{:.0}%", cpu_usage.round()),
-                        cpu_data: vec![],
-                    })
-                    .collect::<Vec<ConvertedCpuData>>(),
+                    .filter_map(|cpu| get_index(*cpu).map(|itx| (CpuEntry::Cpu(*cpu), vec![itx]))),
             );
+        }
+    }
+    entries.extend(
+        cpu_harvest
+            .iter()
+            .enumerate()
+            .filter_map(|(itx, cpu_data)| cpu_data.cpu_count.map(|cpu| (itx, cpu)))
+            .filter(|(_itx, cpu)| !nodes.iter().any(|cpu_node| cpu_node.cpus.contains(cpu)))
+            .map(|(itx, cpu)| (CpuEntry::Cpu(cpu), vec![itx])),
+    );
+
+    // Rebuild the entries if they've changed, keeping the points of any that are still there.
+    if existing_cpu_data.len() != entries.len()
+        || existing_cpu_data
+            .iter()
+            .zip(entries.iter())
+            .any(|(cpu, (entry, _indices))| cpu.entry != *entry)
+    {
+        let mut old_cpu_data = std::mem::take(existing_cpu_data);
+        *existing_cpu_data = entries
+            .iter()
+            .map(
+                |(entry, indices)| match old_cpu_data.iter().position(|cpu| cpu.entry == *entry) {
+                    Some(old_itx) => old_cpu_data.swap_remove(old_itx),
+                    None => {
+                        let (cpu_name, short_cpu_name) = match entry {
+                            CpuEntry::All => ("All".to_string(), String::new()),
+                            CpuEntry::Node { node, .. } => match nodes.get(*node) {
+                                Some(cpu_node) => {
+                                    (cpu_node.name.clone(), cpu_node.short_name.clone())
+                                }
+                                None => (String::default(), String::default()),
+                            },
+                            CpuEntry::Average | CpuEntry::Cpu(_) => {
+                                match indices.first().and_then(|itx| cpu_harvest.get(*itx)) {
+                                    Some(cpu_harvest) => match cpu_harvest.cpu_count {
+                                        Some(cpu_count) => (
+                                            format!("{}{}", cpu_harvest.cpu_prefix, cpu_count),
+                                            cpu_count.to_string(),
+                                        ),
+                                        None => (
+                                            cpu_harvest.cpu_prefix.to_string(),
+                                            cpu_harvest.cpu_prefix.to_string(),
+                                        ),
+                                    },
+                                    None => (String::default(), String::default()),
+                                }
+                            }
+                        };
+
+                        ConvertedCpuData {
+                            entry: *entry,
+                            cpu_name,
+                            short_cpu_name,
+                            cpu_data: GraphPoints::default(),
+                            stacked_data: GraphPoints::default(),
+                            breakdown_data: Vec::new(),
+                            legend_value: String::new(),
+                            stats: None,
+                        }
+                    }
+                },
+            )
+            .collect();
+    }
+
+    if let Some((_time, data)) = &current_data.timed_data.back() {
+        for (cpu, (_entry, indices)) in existing_cpu_data.iter_mut().zip(entries.iter()).skip(1) {
+            if let Some(cpu_usage) = average_of(indices, |itx| {
+                data.cpu_data.get(itx).map(|cpu| f64::from(*cpu))
+            }) {
+                cpu.legend_value = format!("{:.0}%", cpu_usage.round());
+            }
+        }
+    }
+
+    // The CPUs and collapsed nodes are stacked in order, while the average and expanded nodes
+    // aren't part of the stack.
+    let mut stacked_indices = Vec::new();
+    for (cpu, (entry, indices)) in existing_cpu_data.iter_mut().zip(entries.iter()).skip(1) {
+        cpu.cpu_data.update(current_data, bucket_width, |data| {
+            average_of(indices, |itx| {
+                data.cpu_data.get(itx).map(|cpu| f64::from(*cpu))
+            })
+        });
+        cpu.stats = stats_time_window
+            .and_then(|time_window| get_graph_stats(cpu.cpu_data.points(), time_window));
+
+        let is_stacked = match entry {
+            CpuEntry::Cpu(_) => true,
+            CpuEntry::Node { is_collapsed, .. } => *is_collapsed,
+            CpuEntry::All | CpuEntry::Average => false,
+        };
+        if stacked && is_stacked {
+            stacked_indices.extend(indices.iter().copied());
+            cpu.stacked_data.update(current_data, bucket_width, |data| {
+                stacked_indices
+                    .iter()
+                    .map(|itx| data.cpu_data.get(*itx).map(|cpu| f64::from(*cpu)))
+                    .sum()
+            });
         } else {
-            existing_cpu_data
-                .iter_mut()
-                .skip(1)
-                .zip(&data.cpu_data)
-                .for_each(|(cpu, cpu_usage)| {
-                    cpu.cpu_data = vec![];
-                    cpu.legend_value = format!("{:.0}%", cpu_usage.round());
+            cpu.stacked_data.clear();
+        }
+
+        if breakdown {
+            cpu.breakdown_data
+                .resize_with(CPU_STATES.len(), GraphPoints::default);
+            for (state, points) in cpu.breakdown_data.iter_mut().enumerate() {
+                points.update(current_data, bucket_width, |data| {
+                    average_of(indices, |itx| {
+                        data.cpu_breakdown_data
+                            .get(itx)
+                            .map(|cpu| f64::from(cpu[state]))
+                    })
                 });
+            }
+        } else {
+            cpu.breakdown_data.clear();
+        }
+    }
+}
+
+/// Returns the average of `get_value` over `indices`, or `None` if any value is missing.
+fn average_of(indices: &[usize], get_value: impl Fn(usize) -> Option<f64>) -> Option<f64> {
+    if indices.is_empty() {
+        return None;
+    }
+
+    let mut sum = 0.0;
+    for itx in indices {
+        sum += get_value(*itx)?;
+    }
+    Some(sum / indices.len() as f64)
+}
+
+/// The points of a graph, kept between conversions so that only the samples that came in since
+/// the last one need to be converted. The x-values are relative to the current time, and are
+/// shifted back as time moves on.
+///
+/// Points are downsampled as they're added, keeping just the lowest and highest point in each
+/// bucket of `bucket_width` milliseconds. Unlike averaging, this keeps short spikes visible.
+/// Buckets are counted from the oldest sample rather than from now, so a finished bucket never
+/// changes. The newest point is always kept, so the graph still reaches the present.
+#[derive(Clone, Debug, Default)]
+pub struct GraphPoints {
+    points: Vec<Point>,
+    bucket_width: f64,
+    /// The time that buckets are counted from.
+    origin: Option<Instant>,
+    /// The time of the newest sample converted so far.
+    newest_time: Option<Instant>,
+    /// Where x is 0, in milliseconds since `origin`.
+    current_offset: f64,
+    /// The bucket being filled, as its index and its lowest, highest, and newest points. Unlike
+    /// `points`, these have x-values in milliseconds since `origin`.
+    current_bucket: Option<(i64, Point, Point, Point)>,
+    /// How many points at the end of `points` are from `current_bucket`, which are redone
+    /// whenever it changes.
+    current_bucket_len: usize,
+}
+
+impl GraphPoints {
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    pub fn clear(&mut self) {
+        *self = GraphPoints::default();
+    }
+
+    /// Converts the samples that came in since the last update, where `get_value` returns the
+    /// value of a sample if it has one. Points older than the oldest sample still stored are
+    /// dropped. Everything is converted again if the bucket width has changed, or if the samples
+    /// converted before are all gone, like after the data is reset.
+    pub fn update(
+        &mut self, current_data: &data_farmer::DataCollection, bucket_width: f64,
+        get_value: impl Fn(&data_farmer::TimedData) -> Option<f64>,
+    ) {
+        let current_time = current_data
+            .frozen_instant
+            .unwrap_or(current_data.current_instant);
+        let oldest_time = match current_data.timed_data.front() {
+            Some((oldest_time, _)) => *oldest_time,
+            None => {
+                self.clear();
+                return;
+            }
+        };
+
+        let has_changed = (bucket_width - self.bucket_width).abs() > f64::EPSILON;
+        let is_gone = matches!(self.newest_time, Some(newest_time) if newest_time < oldest_time);
+        if has_changed || is_gone {
+            self.clear();
+            self.bucket_width = bucket_width;
+        }
+
+        let origin = *self.origin.get_or_insert(oldest_time);
+        let millis_since_origin =
+            |time: Instant| time.saturating_duration_since(origin).as_millis() as f64;
+        let offset = millis_since_origin(current_time);
+
+        // The current bucket is redone with any new samples, and everything else is shifted back.
+        self.points
+            .truncate(self.points.len() - self.current_bucket_len);
+        let shift = offset - self.current_offset;
+        if shift > 0.0 {
+            self.points.iter_mut().for_each(|point| point.0 -= shift);
+        }
+        self.current_offset = offset;
+
+        let newest_time = self.newest_time;
+        let num_new = current_data
+            .timed_data
+            .iter()
+            .rev()
+            .take_while(|(time, _)| match newest_time {
+                Some(newest_time) => *time > newest_time,
+                None => true,
+            })
+            .count();
+        let new_samples = current_data
+            .timed_data
+            .range(current_data.timed_data.len() - num_new..);
+        for (time, data) in new_samples {
+            if *time > current_time {
+                break;
+            }
+            self.newest_time = Some(*time);
+
+            let point = match get_value(data) {
+                Some(value) => (millis_since_origin(*time), value),
+                None => continue,
+            };
+            let bucket = if bucket_width.is_normal() {
+                (point.0 / bucket_width).floor() as i64
+            } else {
+                point.0 as i64
+            };
+            self.current_bucket = match self.current_bucket {
+                Some((current, min, max, _)) if current == bucket => Some((
+                    current,
+                    if point.1 < min.1 { point } else { min },
+                    if point.1 > max.1 { point } else { max },
+                    point,
+                )),
+                Some((_, min, max, _)) => {
+                    push_bucket(&mut self.points, min, max, offset);
+                    Some((bucket, point, point, point))
+                }
+                None => Some((bucket, point, point, point)),
+            };
+        }
+
+        let oldest_x = millis_since_origin(oldest_time) - offset;
+        let num_old = self
+            .points
+            .iter()
+            .take_while(|(x, _)| *x < oldest_x)
+            .count();
+        self.points.drain(..num_old);
+
+        let len = self.points.len();
+        if let Some((_, min, max, newest)) = self.current_bucket {
+            push_bucket(&mut self.points, min, max, offset);
+            let newest = (newest.0 - offset, newest.1);
+            if self.points.last() != Some(&newest) {
+                self.points.push(newest);
+            }
+        }
+        self.current_bucket_len = self.points.len() - len;
+    }
+}
+
+/// Adds the lowest and highest points of a bucket in time order, with their x-values made
+/// relative to `offset`.
+fn push_bucket(points: &mut Vec<Point>, min: Point, max: Point, offset: f64) {
+    let (first, second) = if min.0 <= max.0 {
+        (min, max)
+    } else {
+        (max, min)
+    };
+    points.push((first.0 - offset, first.1));
+    if first != second {
+        points.push((second.0 - offset, second.1));
+    }
+}
+
+/// Returns the stats of the points of a graph over the last `time_window` milliseconds, or `None`
+/// if there are no points in that time.
+pub fn get_graph_stats(points: &[Point], time_window: u64) -> Option<GraphStats> {
+    let time_start = -(time_window as f64);
+    let mut visible_values = points
+        .iter()
+        .filter(|(time, _value)| *time >= time_start)
+        .map(|(_time, value)| *value);
+
+    let first = visible_values.next()?;
+    let mut stats = GraphStats {
+        min: first,
+        mean: first,
+        max: first,
+    };
+    let mut count = 1.0;
+    for value in visible_values {
+        stats.min = stats.min.min(value);
+        stats.max = stats.max.max(value);
+        stats.mean += value;
+        count += 1.0;
+    }
+    stats.mean /= count;
+
+    Some(stats)
+}
+
+/// Returns the points of a graph from `offset` milliseconds before the last `time_window`
+/// milliseconds, moved forward by `offset` so they line up with the points shown now. The point
+/// just before the window is kept so the line reaches the left edge.
+pub fn get_compare_points(points: &[Point], offset: u64, time_window: u64) -> Vec<Point> {
+    let offset = offset as f64;
+    let time_start = -(offset + time_window as f64);
+    let start = points
+        .partition_point(|(time, _value)| *time < time_start)
+        .saturating_sub(1);
+    let end = points.partition_point(|(time, _value)| *time <= -offset);
+
+    points[start..end.max(start)]
+        .iter()
+        .map(|(time, value)| (time + offset, *value))
+        .collect()
+}
+
+/// Returns the process using the most CPU out of those that last ran on the CPU `cpu`, if any.
+pub fn get_top_process_on_cpu(
+    process_data: &data_farmer::ProcessData, cpu: usize,
+) -> Option<&data_harvester::processes::ProcessHarvest> {
+    process_data
+        .process_harvest
+        .values()
+        .filter(|process| process.last_cpu == Some(cpu))
+        .max_by(|a, b| {
+            a.cpu_usage_percent
+                .partial_cmp(&b.cpu_usage_percent)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+}
+
+pub fn convert_mem_data_points(
+    current_data: &data_farmer::DataCollection, existing_mem_data: &mut GraphPoints,
+    bucket_width: f64,
+) {
+    existing_mem_data.update(current_data, bucket_width, |data| data.mem_data);
+}
+
+/// Returns the CPU and memory usage points of a pinned process.
+pub fn convert_pinned_process_data_points(
+    current_data: &data_farmer::DataCollection, pid: Pid,
+) -> (Vec<Point>, Vec<Point>) {
+    let mut cpu_result: Vec<Point> = Vec::new();
+    let mut mem_result: Vec<Point> = Vec::new();
+    let current_time = if let Some(frozen_instant) = current_data.frozen_instant {
+        frozen_instant
+    } else {
+        current_data.current_instant
+    };
+
+    for (time, data) in &current_data.timed_data {
+        if let Some((cpu_usage, mem_usage)) = data.pinned_process_data.get(&pid) {
+            let time_from_start: f64 =
+                (current_time.duration_since(*time).as_millis() as f64).floor();
+            cpu_result.push((-time_from_start, *cpu_usage));
+            mem_result.push((-time_from_start, *mem_usage));
+        }
+        if *time == current_time {
+            break;
+        }
+    }
+
+    (cpu_result, mem_result)
+}
+
+pub fn convert_swap_data_points(
+    current_data: &data_farmer::DataCollection, existing_swap_data: &mut GraphPoints,
+    bucket_width: f64,
+) {
+    existing_swap_data.update(current_data, bucket_width, |data| data.swap_data);
+}
+
+pub fn convert_swap_activity_data_points(
+    current_data: &data_farmer::DataCollection, existing_swap_in_data: &mut GraphPoints,
+    existing_swap_out_data: &mut GraphPoints, bucket_width: f64,
+) {
+    existing_swap_in_data.update(current_data, bucket_width, |data| {
+        data.swap_activity_data.map(|(pages_in, _)| pages_in)
+    });
+    existing_swap_out_data.update(current_data, bucket_width, |data| {
+        data.swap_activity_data.map(|(_, pages_out)| pages_out)
+    });
+}
+
+/// Returns legend entries for the latest pages swapped in and out per second, like `IN:12/s`.
+pub fn convert_swap_activity_labels(
+    current_data: &data_farmer::DataCollection,
+) -> Option<(String, String)> {
+    let (_, latest_data) = current_data.timed_data.back()?;
+    let (pages_in, pages_out) = latest_data.swap_activity_data?;
+
+    Some((
+        format!("IN:{:.0}/s", pages_in),
+        format!("OUT:{:.0}/s", pages_out),
+    ))
+}
+
+pub fn convert_power_data(current_data: &data_farmer::DataCollection) -> ConvertedPowerData {
+    let current_time = if let Some(frozen_instant) = current_data.frozen_instant {
+        frozen_instant
+    } else {
+        current_data.current_instant
+    };
+
+    let to_label = |watts: Option<f64>| watts.map(|watts| format!("{:.2}W", watts));
+    let mut result = match &current_data.power_harvest {
+        Some(power_harvest) => ConvertedPowerData {
+            package_label: to_label(power_harvest.package),
+            core_label: to_label(power_harvest.core),
+            dram_label: to_label(power_harvest.dram),
+            ..ConvertedPowerData::default()
+        },
+        None => ConvertedPowerData::default(),
+    };
+
+    for (time, data) in &current_data.timed_data {
+        if let Some(power_data) = data.power_data {
+            let time_from_start: f64 =
+                (current_time.duration_since(*time).as_millis() as f64).floor();
+            if let Some(package) = power_data.package {
+                result.package_data.push((-time_from_start, package));
+            }
+            if let Some(core) = power_data.core {
+                result.core_data.push((-time_from_start, core));
+            }
+            if let Some(dram) = power_data.dram {
+                result.dram_data.push((-time_from_start, dram));
+            }
+        }
+        if *time == current_time {
+            break;
         }
     }
 
-    for (time, data) in &current_data.timed_data_vec {
+    result
+}
+
+/// Returns a legend entry for each latency host with its last round-trip time.
+pub fn convert_latency_legend(current_data: &data_farmer::DataCollection) -> Vec<String> {
+    current_data
+        .latency_harvest
+        .iter()
+        .map(|host| match (host.rtt_ms, host.is_pending) {
+            (Some(rtt_ms), _) => format!("{}: {:.1} ms", host.name, rtt_ms),
+            (None, true) => format!("{}: ...", host.name),
+            (None, false) => format!("{}: timeout", host.name),
+        })
+        .collect()
+}
+
+/// Returns the round-trip times of each latency host over time. Checks that timed out are
+/// skipped.
+pub fn convert_latency_data_points(current_data: &data_farmer::DataCollection) -> Vec<Vec<Point>> {
+    let mut result: Vec<Vec<Point>> = vec![Vec::new(); current_data.latency_harvest.len()];
+    let current_time = if let Some(frozen_instant) = current_data.frozen_instant {
+        frozen_instant
+    } else {
+        current_data.current_instant
+    };
+
+    for (time, data) in &current_data.timed_data {
         let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+        for (points, rtt_ms) in result.iter_mut().zip(data.latency_data.iter()) {
+            if let Some(rtt_ms) = rtt_ms {
+                points.push((-time_from_start, *rtt_ms));
+            }
+        }
+        if *time == current_time {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Returns the legend entries and values over time of each plugin's series, and of each derived
+/// metric. Series stay shown while they have values in the graph, even if the plugin has stopped
+/// reporting them.
+pub fn convert_plugin_data(
+    current_data: &data_farmer::DataCollection,
+) -> FxHashMap<String, ConvertedPluginData> {
+    let current_time = if let Some(frozen_instant) = current_data.frozen_instant {
+        frozen_instant
+    } else {
+        current_data.current_instant
+    };
 
-        for (itx, cpu) in data.cpu_data.iter().enumerate() {
-            if let Some(cpu_data) = existing_cpu_data.get_mut(itx + 1) {
-                cpu_data.cpu_data.push((-time_from_start, *cpu));
+    let mut history: Vec<Vec<Point>> = vec![Vec::new(); current_data.plugin_series.len()];
+    for (time, data) in &current_data.timed_data {
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+        for (points, value) in history.iter_mut().zip(data.plugin_data.iter()) {
+            if let Some(value) = value {
+                points.push((-time_from_start, *value));
             }
         }
+        if *time == current_time {
+            break;
+        }
+    }
 
+    current_data
+        .plugin_harvest
+        .iter()
+        .chain(current_data.derived_harvest.iter())
+        .map(|plugin| {
+            let mut converted = ConvertedPluginData {
+                error: plugin.error.clone(),
+                ..ConvertedPluginData::default()
+            };
+            for ((plugin_name, series_name), points) in
+                current_data.plugin_series.iter().zip(history.iter_mut())
+            {
+                if *plugin_name != plugin.name {
+                    continue;
+                }
+
+                let current_value = plugin
+                    .series
+                    .iter()
+                    .find(|series| series.name == *series_name)
+                    .map(|series| series.value);
+                if current_value.is_none() && points.is_empty() {
+                    continue;
+                }
+
+                converted.legend.push(match current_value {
+                    Some(value) => format!("{}: {:.1}", series_name, value),
+                    None => format!("{}: N/A", series_name),
+                });
+                converted.history.push(std::mem::take(points));
+            }
+
+            (plugin.name.clone(), converted)
+        })
+        .collect()
+}
+
+pub fn convert_frequency_data(
+    current_data: &data_farmer::DataCollection,
+) -> ConvertedFrequencyData {
+    let frequency_harvest = match &current_data.frequency_harvest {
+        Some(frequency_harvest) => frequency_harvest,
+        None => return ConvertedFrequencyData::default(),
+    };
+    let average_of = |frequencies: &[f64]| {
+        if frequencies.is_empty() {
+            None
+        } else {
+            Some(frequencies.iter().sum::<f64>() / frequencies.len() as f64)
+        }
+    };
+
+    let mut result = ConvertedFrequencyData {
+        average: Vec::new(),
+        cpus: vec![Vec::new(); frequency_harvest.frequencies.len()],
+        current_average: average_of(&frequency_harvest.frequencies),
+        max_frequency: frequency_harvest.max_frequency,
+        c_states: frequency_harvest
+            .c_states
+            .iter()
+            .map(|(name, residency)| (name.clone(), Vec::new(), *residency))
+            .collect(),
+    };
+    let current_time = if let Some(frozen_instant) = current_data.frozen_instant {
+        frozen_instant
+    } else {
+        current_data.current_instant
+    };
+
+    for (time, data) in &current_data.timed_data {
+        if let Some(frequency_data) = &data.frequency_data {
+            let time_from_start: f64 =
+                (current_time.duration_since(*time).as_millis() as f64).floor();
+            if let Some(average) = average_of(&frequency_data.frequencies) {
+                result.average.push((-time_from_start, average));
+            }
+            for (points, frequency) in result.cpus.iter_mut().zip(&frequency_data.frequencies) {
+                points.push((-time_from_start, *frequency));
+            }
+            for (name, residency) in &frequency_data.c_states {
+                if let Some((_, points, _)) = result
+                    .c_states
+                    .iter_mut()
+                    .find(|(other_name, _, _)| other_name == name)
+                {
+                    points.push((-time_from_start, *residency));
+                }
+            }
+        }
         if *time == current_time {
             break;
         }
     }
+
+    result
+}
+
+/// Returns the gaps in the data as ranges of the x-axis of graphs, oldest first. Gaps after the
+/// time the data was frozen at are left out.
+pub fn convert_gaps(current_data: &data_farmer::DataCollection) -> Vec<Point> {
+    let current_time = current_data
+        .frozen_instant
+        .unwrap_or(current_data.current_instant);
+
+    current_data
+        .gaps
+        .iter()
+        .take_while(|(gap_start, _gap_end)| *gap_start < current_time)
+        .map(|(gap_start, gap_end)| {
+            let millis_ago =
+                |time: &Instant| current_time.saturating_duration_since(*time).as_millis() as f64;
+            (-millis_ago(gap_start), -millis_ago(gap_end))
+        })
+        .collect()
 }
 
-pub fn convert_mem_data_points(current_data: &data_farmer::DataCollection) -> Vec<Point> {
-    let mut result: Vec<Point> = Vec::new();
+pub fn convert_load_avg_data_points(current_data: &data_farmer::DataCollection) -> [Vec<Point>; 3] {
+    let mut result: [Vec<Point>; 3] = Default::default();
     let current_time = if let Some(frozen_instant) = current_data.frozen_instant {
         frozen_instant
     } else {
         current_data.current_instant
     };
 
-    for (time, data) in &current_data.timed_data_vec {
-        if let Some(mem_data) = data.mem_data {
+    for (time, data) in &current_data.timed_data {
+        if let Some(load_avg_data) = data.load_avg_data {
             let time_from_start: f64 =
                 (current_time.duration_since(*time).as_millis() as f64).floor();
-            result.push((-time_from_start, mem_data));
-            if *time == current_time {
-                break;
+            for (points, load_avg) in result.iter_mut().zip(load_avg_data.iter()) {
+                points.push((-time_from_start, f64::from(*load_avg)));
             }
         }
+        if *time == current_time {
+            break;
+        }
     }
 
     result
 }
 
-pub fn convert_swap_data_points(current_data: &data_farmer::DataCollection) -> Vec<Point> {
-    let mut result: Vec<Point> = Vec::new();
+/// Returns the values of each pressure series, in the order they are graphed. The CPU's `full`
+/// value isn't included, as it is always zero at the system level.
+fn get_pressure_series(
+    pressure: &data_harvester::pressure::PressureHarvest,
+) -> [(
+    &'static str,
+    Option<data_harvester::pressure::PressureValues>,
+); 5] {
+    [
+        ("CPU some", pressure.cpu.map(|cpu| cpu.some)),
+        ("MEM some", pressure.memory.map(|memory| memory.some)),
+        ("MEM full", pressure.memory.and_then(|memory| memory.full)),
+        ("IO some", pressure.io.map(|io| io.some)),
+        ("IO full", pressure.io.and_then(|io| io.full)),
+    ]
+}
+
+pub fn convert_pressure_data(
+    current_data: &data_farmer::DataCollection,
+) -> Vec<ConvertedPressureData> {
     let current_time = if let Some(frozen_instant) = current_data.frozen_instant {
         frozen_instant
     } else {
         current_data.current_instant
     };
 
-    for (time, data) in &current_data.timed_data_vec {
-        if let Some(swap_data) = data.swap_data {
+    let current_series = match &current_data.pressure_harvest {
+        Some(pressure_harvest) => get_pressure_series(pressure_harvest),
+        None => return Vec::new(),
+    };
+    let mut result: Vec<ConvertedPressureData> = current_series
+        .iter()
+        .filter_map(|(name, values)| {
+            values.map(|values| ConvertedPressureData {
+                name,
+                data: Vec::new(),
+                label: format!("{:.2}% (60s: {:.2}%)", values.avg10, values.avg60),
+            })
+        })
+        .collect();
+
+    for (time, data) in &current_data.timed_data {
+        if let Some(pressure_data) = &data.pressure_data {
             let time_from_start: f64 =
                 (current_time.duration_since(*time).as_millis() as f64).floor();
-            result.push((-time_from_start, swap_data));
-            if *time == current_time {
-                break;
+            for (name, values) in &get_pressure_series(pressure_data) {
+                if let Some(values) = values {
+                    if let Some(series) = result.iter_mut().find(|series| series.name == *name) {
+                        series.data.push((-time_from_start, values.avg10));
+                    }
+                }
             }
         }
+        if *time == current_time {
+            break;
+        }
     }
 
     result
@@ -393,67 +1715,195 @@ pub fn convert_mem_labels(
     )
 }
 
-pub fn get_rx_tx_data_points(
-    current_data: &data_farmer::DataCollection, network_scale_type: &AxisScaling,
-    network_unit_type: &DataUnit, network_use_binary_prefix: bool,
-) -> (Vec<Point>, Vec<Point>) {
-    let mut rx: Vec<Point> = Vec::new();
-    let mut tx: Vec<Point> = Vec::new();
-
-    let current_time = if let Some(frozen_instant) = current_data.frozen_instant {
-        frozen_instant
-    } else {
-        current_data.current_instant
+/// Converts the network rates that came in since the last conversion, downsampled to buckets of
+/// `bucket_width` milliseconds.
+pub fn convert_rx_tx_data_points(
+    current_data: &data_farmer::DataCollection, existing_rx: &mut GraphPoints,
+    existing_tx: &mut GraphPoints, network_scale_type: &AxisScaling, network_unit_type: &DataUnit,
+    network_use_binary_prefix: bool, bucket_width: f64,
+) {
+    let scale = |value| {
+        Some(scale_network_value(
+            value,
+            network_scale_type,
+            network_unit_type,
+            network_use_binary_prefix,
+        ))
     };
+    existing_rx.update(current_data, bucket_width, |data| scale(data.rx_data));
+    existing_tx.update(current_data, bucket_width, |data| scale(data.tx_data));
+}
 
-    for (time, data) in &current_data.timed_data_vec {
-        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+/// Like [`convert_rx_tx_data_points`], but for the cumulative amount received and transmitted.
+pub fn convert_total_rx_tx_data_points(
+    current_data: &data_farmer::DataCollection, existing_total_rx: &mut GraphPoints,
+    existing_total_tx: &mut GraphPoints, network_scale_type: &AxisScaling,
+    network_unit_type: &DataUnit, network_use_binary_prefix: bool, bucket_width: f64,
+) {
+    let scale = |value| {
+        Some(scale_network_value(
+            value,
+            network_scale_type,
+            network_unit_type,
+            network_use_binary_prefix,
+        ))
+    };
+    existing_total_rx.update(current_data, bucket_width, |data| scale(data.total_rx_data));
+    existing_total_tx.update(current_data, bucket_width, |data| scale(data.total_tx_data));
+}
 
-        let (rx_data, tx_data) = match network_scale_type {
-            AxisScaling::Log => {
-                if network_use_binary_prefix {
-                    match network_unit_type {
-                        DataUnit::Byte => {
-                            // As dividing by 8 is equal to subtracting 4 in base 2!
-                            ((data.rx_data).log2() - 4.0, (data.tx_data).log2() - 4.0)
-                        }
-                        DataUnit::Bit => ((data.rx_data).log2(), (data.tx_data).log2()),
-                    }
-                } else {
-                    match network_unit_type {
-                        DataUnit::Byte => {
-                            ((data.rx_data / 8.0).log10(), (data.tx_data / 8.0).log10())
-                        }
-                        DataUnit::Bit => ((data.rx_data).log10(), (data.tx_data).log10()),
-                    }
+/// Converts a network value in bits to the unit and scale of the graph.
+fn scale_network_value(
+    value: f64, network_scale_type: &AxisScaling, network_unit_type: &DataUnit,
+    network_use_binary_prefix: bool,
+) -> f64 {
+    match network_scale_type {
+        AxisScaling::Log => {
+            if network_use_binary_prefix {
+                match network_unit_type {
+                    // As dividing by 8 is equal to subtracting 4 in base 2!
+                    DataUnit::Byte => value.log2() - 4.0,
+                    DataUnit::Bit => value.log2(),
+                }
+            } else {
+                match network_unit_type {
+                    DataUnit::Byte => (value / 8.0).log10(),
+                    DataUnit::Bit => value.log10(),
                 }
             }
-            AxisScaling::Linear => match network_unit_type {
-                DataUnit::Byte => (data.rx_data / 8.0, data.tx_data / 8.0),
-                DataUnit::Bit => (data.rx_data, data.tx_data),
-            },
-        };
-
-        rx.push((-time_from_start, rx_data));
-        tx.push((-time_from_start, tx_data));
-        if *time == current_time {
-            break;
         }
+        AxisScaling::Linear => match network_unit_type {
+            DataUnit::Byte => value / 8.0,
+            DataUnit::Bit => value,
+        },
+    }
+}
+
+/// Converts the errors and dropped packets per second that came in since the last conversion,
+/// which are always plotted on a linear scale.
+pub fn convert_error_drop_data_points(
+    current_data: &data_farmer::DataCollection, existing_errors: &mut GraphPoints,
+    existing_drops: &mut GraphPoints, bucket_width: f64,
+) {
+    existing_errors.update(current_data, bucket_width, |data| Some(data.error_data));
+    existing_drops.update(current_data, bucket_width, |data| Some(data.drop_data));
+}
+
+/// Returns the legend text for errors or dropped packets, like `Drops: 2.5/s  All: 340 (eth0)`,
+/// listing the interfaces that are currently losing packets.
+fn get_loss_display<'a>(
+    label: &str, per_sec: f64, total: u64, lossy_interfaces: impl Iterator<Item = &'a str>,
+) -> String {
+    let lossy_interfaces = lossy_interfaces.collect::<Vec<_>>();
+    if lossy_interfaces.is_empty() {
+        format!("{}: {:.1}/s  All: {}", label, per_sec, total)
+    } else {
+        format!(
+            "{}: {:.1}/s  All: {} ({})",
+            label,
+            per_sec,
+            total,
+            lossy_interfaces.join(", ")
+        )
+    }
+}
+
+/// Converts the network legend. If `stats_time_window` is set, the min, max, and mean rates over
+/// that many milliseconds are also worked out.
+/// Formats a network rate given in bits per second, like `1.2Mb/s`.
+pub fn format_network_rate(
+    bits: f64, network_unit_type: &DataUnit, network_use_binary_prefix: bool,
+) -> String {
+    let (quantity, unit) = match network_unit_type {
+        DataUnit::Byte => (bits as u64 / 8, "B/s"),
+        DataUnit::Bit => (bits as u64, "b/s"),
+    };
+    let (value, unit) = if network_use_binary_prefix {
+        get_binary_prefix(quantity, unit)
+    } else {
+        get_decimal_prefix(quantity, unit)
+    };
+    format!("{:.1}{}", value, unit)
+}
+
+/// Returns the session peaks for the CPU, memory, and network widget titles, like `Peak 97.3%`.
+pub fn convert_peaks(
+    current_data: &data_farmer::DataCollection, network_unit_type: &DataUnit,
+    network_use_binary_prefix: bool,
+) -> PeakDisplay {
+    let peaks = &current_data.peaks;
+
+    PeakDisplay {
+        cpu: peaks
+            .cpu_usage
+            .map(|cpu_usage| format!("Peak {:.1}%", cpu_usage)),
+        mem: peaks.memory.as_ref().map(|memory| {
+            format!(
+                "Peak {} ({:.1}%)",
+                binary_byte_string(memory.mem_used_in_kib * 1024),
+                memory.use_percent.unwrap_or(0.0)
+            )
+        }),
+        network: if peaks.rx > 0 || peaks.tx > 0 {
+            Some(format!(
+                "Peak RX {} TX {}",
+                format_network_rate(
+                    peaks.rx as f64,
+                    network_unit_type,
+                    network_use_binary_prefix
+                ),
+                format_network_rate(
+                    peaks.tx as f64,
+                    network_unit_type,
+                    network_use_binary_prefix
+                )
+            ))
+        } else {
+            None
+        },
     }
+}
 
-    (rx, tx)
+/// Returns the current average CPU usage, using the average entry if it's collected, or else
+/// averaging the CPUs.
+pub fn get_average_cpu_usage(cpu_harvest: &[data_harvester::cpu::CpuData]) -> Option<f64> {
+    if let Some(average) = cpu_harvest.iter().find(|cpu| cpu.cpu_count.is_none()) {
+        return Some(average.cpu_usage);
+    }
+    if cpu_harvest.is_empty() {
+        None
+    } else {
+        Some(cpu_harvest.iter().map(|cpu| cpu.cpu_usage).sum::<f64>() / cpu_harvest.len() as f64)
+    }
 }
 
 pub fn convert_network_data_points(
     current_data: &data_farmer::DataCollection, need_four_points: bool,
-    network_scale_type: &AxisScaling, network_unit_type: &DataUnit,
-    network_use_binary_prefix: bool,
+    network_unit_type: &DataUnit, network_use_binary_prefix: bool, stats_time_window: Option<u64>,
+    labels: &Labels,
 ) -> ConvertedNetworkData {
-    let (rx, tx) = get_rx_tx_data_points(
-        current_data,
-        network_scale_type,
-        network_unit_type,
-        network_use_binary_prefix,
+    let network_harvest = &current_data.network_harvest;
+    let error_display = get_loss_display(
+        "Errors",
+        network_harvest.errors_per_sec,
+        network_harvest.total_errors,
+        network_harvest
+            .lossy_interfaces
+            .iter()
+            .filter(|interface| {
+                interface.errors_per_sec > 0.0 && !labels.is_hidden(&interface.name)
+            })
+            .map(|interface| labels.network(&interface.name)),
+    );
+    let drop_display = get_loss_display(
+        "Drops",
+        network_harvest.drops_per_sec,
+        network_harvest.total_drops,
+        network_harvest
+            .lossy_interfaces
+            .iter()
+            .filter(|interface| interface.drops_per_sec > 0.0 && !labels.is_hidden(&interface.name))
+            .map(|interface| labels.network(&interface.name)),
     );
 
     let unit = match network_unit_type {
@@ -461,6 +1911,43 @@ pub fn convert_network_data_points(
         DataUnit::Bit => "b/s",
     };
 
+    let stats = stats_time_window.and_then(|time_window| {
+        let current_time = current_data
+            .frozen_instant
+            .unwrap_or(current_data.current_instant);
+        get_network_stats(
+            current_data
+                .timed_data
+                .iter()
+                .filter(|(time, _)| {
+                    current_time
+                        .checked_duration_since(*time)
+                        .map(|age| age.as_millis() <= u128::from(time_window))
+                        .unwrap_or(false)
+                })
+                .map(|(_, data)| (data.rx_data, data.tx_data)),
+        )
+    });
+    let stats_display = stats.map(|stats| {
+        let format_rate =
+            |bits: f64| format_network_rate(bits, network_unit_type, network_use_binary_prefix);
+
+        (
+            format!(
+                "RX min {}  max {}  avg {}",
+                format_rate(stats.min_rx),
+                format_rate(stats.max_rx),
+                format_rate(stats.mean_rx)
+            ),
+            format!(
+                "TX min {}  max {}  avg {}",
+                format_rate(stats.min_tx),
+                format_rate(stats.max_tx),
+                format_rate(stats.mean_tx)
+            ),
+        )
+    });
+
     let (rx_data, tx_data, total_rx_data, total_tx_data) = match network_unit_type {
         DataUnit::Byte => (
             current_data.network_harvest.rx / 8,
@@ -514,12 +2001,14 @@ pub fn convert_network_data_points(
             1, total_tx_converted_result.0, total_tx_converted_result.1
         ));
         ConvertedNetworkData {
-            rx,
-            tx,
             rx_display,
             tx_display,
             total_rx_display,
             total_tx_display,
+            error_display,
+            drop_display,
+            stats,
+            stats_display,
         }
     } else {
         let rx_display = format!(
@@ -562,16 +2051,123 @@ pub fn convert_network_data_points(
         );
 
         ConvertedNetworkData {
-            rx,
-            tx,
             rx_display,
             tx_display,
             total_rx_display: None,
             total_tx_display: None,
+            error_display,
+            drop_display,
+            stats,
+            stats_display,
         }
     }
 }
 
+/// Returns the min, max, and mean of some RX and TX rates, or `None` if there are none.
+fn get_network_stats(rates: impl Iterator<Item = (f64, f64)>) -> Option<NetworkStats> {
+    let mut count = 0;
+    let mut stats = NetworkStats {
+        min_rx: f64::MAX,
+        max_rx: 0.0,
+        mean_rx: 0.0,
+        min_tx: f64::MAX,
+        max_tx: 0.0,
+        mean_tx: 0.0,
+    };
+
+    for (rx, tx) in rates {
+        count += 1;
+        stats.min_rx = stats.min_rx.min(rx);
+        stats.max_rx = stats.max_rx.max(rx);
+        stats.mean_rx += rx;
+        stats.min_tx = stats.min_tx.min(tx);
+        stats.max_tx = stats.max_tx.max(tx);
+        stats.mean_tx += tx;
+    }
+
+    if count == 0 {
+        None
+    } else {
+        stats.mean_rx /= f64::from(count);
+        stats.mean_tx /= f64::from(count);
+        Some(stats)
+    }
+}
+
+/// Whether an interface isn't hidden, and matches the network filter. The filter only goes by the
+/// harvested name, as that's all the collection thread knows of.
+fn is_interface_shown(interface: &str, labels: &Labels, filter: &TableFilter) -> bool {
+    !labels.is_hidden(interface) && filter.is_match(interface)
+}
+
+/// Returns an addresses line for each interface, like `eth0: 192.168.1.2, 2001:db8::2`, followed
+/// by the public IP if it is shown.
+pub fn convert_address_data(
+    current_data: &data_farmer::DataCollection, show_public_ip: bool, labels: &Labels,
+    filter: &TableFilter,
+) -> Vec<String> {
+    let mut address_display: Vec<String> = current_data
+        .address_harvest
+        .iter()
+        .filter(|interface| is_interface_shown(&interface.interface, labels, filter))
+        .map(|interface| {
+            format!(
+                "{}: {}",
+                labels.network(&interface.interface),
+                interface
+                    .addresses
+                    .iter()
+                    .map(|address| address.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+        .collect();
+
+    if show_public_ip {
+        address_display.push(match current_data.public_ip {
+            Some(public_ip) => format!("Public IP: {}", public_ip),
+            None => "Public IP: unknown".to_string(),
+        });
+    }
+
+    address_display
+}
+
+/// Returns a details line for each wireless interface, like `wlan0: -54 dBm, 80%, 866.7Mb/s`.
+pub fn convert_wireless_data(
+    current_data: &data_farmer::DataCollection, labels: &Labels, filter: &TableFilter,
+) -> Vec<String> {
+    current_data
+        .wireless_harvest
+        .iter()
+        .filter(|wireless| is_interface_shown(&wireless.interface, labels, filter))
+        .map(|wireless| {
+            let name = labels.network(&wireless.interface);
+            let details = vec![
+                wireless
+                    .signal_dbm
+                    .map(|signal_dbm| format!("{:.0} dBm", signal_dbm)),
+                wireless
+                    .link_quality
+                    .map(|link_quality| format!("{:.0}%", link_quality)),
+                wireless
+                    .link_speed_mbps
+                    .map(|link_speed_mbps| format!("{:.1}Mb/s", link_speed_mbps)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+            if details.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}: {}", name, details.join(", "))
+            }
+        })
+        .collect()
+}
+
 /// Returns a string given a value that is converted to the closest binary variant.
 /// If the value is greater than a gibibyte, then it will return a decimal place.
 pub fn binary_byte_string(value: u64) -> String {
@@ -605,11 +2201,77 @@ pub fn dec_bytes_per_second_string(value: u64) -> String {
     }
 }
 
+/// Returns a short string for a duration in seconds, using its two largest units, like `2h 5m`.
+pub fn duration_string(secs: u64) -> String {
+    let days = secs / 86400;
+    let hours = secs / 3600 % 24;
+    let minutes = secs / 60 % 60;
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Returns a Unix timestamp in seconds as a date and time in the local time zone, like
+/// `2022-05-01 14:03`.
+pub fn timestamp_string(timestamp: u64) -> String {
+    use std::convert::TryFrom;
+
+    let timestamp = i64::try_from(timestamp).unwrap_or(i64::MAX);
+    time::OffsetDateTime::from_unix_timestamp(timestamp)
+        .ok()
+        .and_then(|date_time| {
+            date_time
+                .to_offset(get_local_offset(timestamp))
+                .format(&time::macros::format_description!(
+                    "[year]-[month]-[day] [hour]:[minute]"
+                ))
+                .ok()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the local time zone's offset from UTC at a Unix timestamp, or UTC if it isn't known.
+/// This doesn't use `time`'s own lookup, as that refuses to run once there's more than one thread.
+fn get_local_offset(timestamp: i64) -> time::UtcOffset {
+    #[cfg(target_family = "unix")]
+    {
+        use std::convert::TryFrom;
+
+        let time = timestamp as libc::time_t;
+        // SAFETY: `localtime_r` only writes to the `tm` passed in, which is fully initialized.
+        let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+        if !unsafe { libc::localtime_r(&time, &mut tm) }.is_null() {
+            if let Some(offset) = i32::try_from(tm.tm_gmtoff)
+                .ok()
+                .and_then(|offset| time::UtcOffset::from_whole_seconds(offset).ok())
+            {
+                return offset;
+            }
+        }
+    }
+    #[cfg(not(target_family = "unix"))]
+    let _ = timestamp;
+
+    time::UtcOffset::UTC
+}
+
 #[cfg(feature = "battery")]
 pub fn convert_battery_harvest(
     current_data: &data_farmer::DataCollection,
 ) -> Vec<ConvertedBatteryData> {
-    current_data
+    let current_time = if let Some(frozen_instant) = current_data.frozen_instant {
+        frozen_instant
+    } else {
+        current_data.current_instant
+    };
+
+    let mut converted_batteries: Vec<ConvertedBatteryData> = current_data
         .battery_harvest
         .iter()
         .enumerate()
@@ -649,9 +2311,31 @@ pub fn convert_battery_harvest(
             } else {
                 None
             },
-            health: format!("{:.2}%", battery_harvest.health_percent),
+            health: match battery_harvest.health_percent {
+                Some(health_percent) => format!("{:.2}%", health_percent),
+                None => "N/A".to_string(),
+            },
+            charge_data: vec![],
+            power_data: vec![],
         })
-        .collect()
+        .collect();
+
+    for (time, data) in &current_data.timed_data {
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+
+        for (itx, (charge, power)) in data.battery_data.iter().enumerate() {
+            if let Some(battery) = converted_batteries.get_mut(itx) {
+                battery.charge_data.push((-time_from_start, *charge));
+                battery.power_data.push((-time_from_start, *power));
+            }
+        }
+
+        if *time == current_time {
+            break;
+        }
+    }
+
+    converted_batteries
 }
 
 #[cfg(test)]
@@ -683,6 +2367,355 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_graph_points() {
+        use std::time::Duration;
+
+        let mut data = data_farmer::DataCollection::default();
+        let start = Instant::now();
+        let add_samples = |data: &mut data_farmer::DataCollection, samples: &[(u64, f64)]| {
+            for (millis, value) in samples {
+                let time = start + Duration::from_millis(*millis);
+                data.timed_data.push_back((
+                    time,
+                    data_farmer::TimedData {
+                        mem_data: Some(*value),
+                        ..Default::default()
+                    },
+                ));
+                data.current_instant = time;
+            }
+        };
+        let mut points = GraphPoints::default();
+
+        // Few enough points are left alone.
+        add_samples(&mut data, &[(0, 1.0), (1000, 2.0), (2000, 3.0)]);
+        points.update(&data, 1000.0, |data| data.mem_data);
+        assert_eq!(
+            points.points(),
+            &[(-2000.0, 1.0), (-1000.0, 2.0), (0.0, 3.0)]
+        );
+
+        // Otherwise, the lowest and highest points of each bucket are kept, in order, and only the
+        // new samples are added while the older points are shifted back.
+        add_samples(
+            &mut data,
+            &[
+                (3100, 5.0),
+                (3300, 1.0),
+                (3500, 9.0),
+                (3700, 4.0),
+                (4100, 2.0),
+            ],
+        );
+        points.update(&data, 1000.0, |data| data.mem_data);
+        assert_eq!(
+            points.points(),
+            &[
+                (-4100.0, 1.0),
+                (-3100.0, 2.0),
+                (-2100.0, 3.0),
+                (-800.0, 1.0),
+                (-600.0, 9.0),
+                (0.0, 2.0)
+            ]
+        );
+
+        // Points older than the oldest sample are dropped.
+        data.timed_data.drain(..3);
+        points.update(&data, 1000.0, |data| data.mem_data);
+        assert_eq!(points.points()[0], (-800.0, 1.0));
+
+        // Everything is converted again if the bucket width changes.
+        points.update(&data, 100.0, |data| data.mem_data);
+        assert_eq!(points.points().len(), 5);
+    }
+
+    #[test]
+    fn test_stacked_cpu_data_points() {
+        use crate::app::data_harvester::cpu::CpuData;
+
+        let mut data = data_farmer::DataCollection {
+            cpu_harvest: vec![
+                CpuData {
+                    cpu_prefix: "AVG".to_string(),
+                    cpu_count: None,
+                    cpu_usage: 30.0,
+                    breakdown: None,
+                },
+                CpuData {
+                    cpu_prefix: "CPU".to_string(),
+                    cpu_count: Some(0),
+                    cpu_usage: 20.0,
+                    breakdown: None,
+                },
+                CpuData {
+                    cpu_prefix: "CPU".to_string(),
+                    cpu_count: Some(1),
+                    cpu_usage: 40.0,
+                    breakdown: None,
+                },
+            ],
+            ..Default::default()
+        };
+        data.timed_data.push_back((
+            data.current_instant,
+            data_farmer::TimedData {
+                cpu_data: vec![30.0, 20.0, 40.0].into_boxed_slice(),
+                ..Default::default()
+            },
+        ));
+
+        let mut cpu_data = Vec::new();
+        convert_cpu_data_points(&data, &mut cpu_data, &[], None, 1000.0, true, false);
+        let stacked = cpu_data
+            .iter()
+            .map(|cpu| cpu.stacked_data.points().to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stacked,
+            vec![vec![], vec![], vec![(0.0, 20.0)], vec![(0.0, 60.0)]]
+        );
+    }
+
+    #[test]
+    fn test_cpu_node_data_points() {
+        use crate::app::data_harvester::cpu::CpuData;
+
+        let mut data = data_farmer::DataCollection {
+            cpu_harvest: (0..4)
+                .map(|cpu| CpuData {
+                    cpu_prefix: "CPU".to_string(),
+                    cpu_count: Some(cpu),
+                    ..CpuData::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        data.timed_data.push_back((
+            data.current_instant,
+            data_farmer::TimedData {
+                cpu_data: vec![10.0, 20.0, 30.0, 50.0].into_boxed_slice(),
+                ..Default::default()
+            },
+        ));
+        let mut nodes = vec![
+            CpuNode {
+                name: "Node0".to_string(),
+                short_name: "N0".to_string(),
+                cpus: vec![0, 2],
+                is_collapsed: false,
+            },
+            CpuNode {
+                name: "Node1".to_string(),
+                short_name: "N1".to_string(),
+                cpus: vec![1, 3],
+                is_collapsed: true,
+            },
+        ];
+
+        let mut cpu_data = Vec::new();
+        convert_cpu_data_points(&data, &mut cpu_data, &nodes, None, 1000.0, false, false);
+        let entries = cpu_data
+            .iter()
+            .map(|cpu| (cpu.entry, cpu.cpu_data.points().to_vec()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            vec![
+                (CpuEntry::All, vec![]),
+                (
+                    CpuEntry::Node {
+                        node: 0,
+                        is_collapsed: false
+                    },
+                    vec![(0.0, 20.0)]
+                ),
+                (CpuEntry::Cpu(0), vec![(0.0, 10.0)]),
+                (CpuEntry::Cpu(2), vec![(0.0, 30.0)]),
+                (
+                    CpuEntry::Node {
+                        node: 1,
+                        is_collapsed: true
+                    },
+                    vec![(0.0, 35.0)]
+                ),
+            ]
+        );
+
+        nodes[0].is_collapsed = true;
+        convert_cpu_data_points(&data, &mut cpu_data, &nodes, None, 1000.0, false, false);
+        assert_eq!(cpu_data.len(), 3);
+        assert_eq!(cpu_data[1].cpu_name, "Node0");
+        assert_eq!(cpu_data[1].legend_value, "20%");
+    }
+
+    #[test]
+    fn test_get_graph_stats() {
+        let points = [
+            (-90_000.0, 100.0),
+            (-30_000.0, 20.0),
+            (-10_000.0, 40.0),
+            (0.0, 30.0),
+        ];
+
+        assert_eq!(
+            get_graph_stats(&points, 60_000),
+            Some(GraphStats {
+                min: 20.0,
+                mean: 30.0,
+                max: 40.0,
+            })
+        );
+        assert_eq!(get_graph_stats(&points, 120_000).unwrap().max, 100.0);
+        assert_eq!(get_graph_stats(&[], 60_000), None);
+    }
+
+    #[test]
+    fn test_get_compare_points() {
+        let points = [
+            (-400_000.0, 10.0),
+            (-350_000.0, 20.0),
+            (-320_000.0, 30.0),
+            (-300_000.0, 40.0),
+            (-290_000.0, 50.0),
+            (0.0, 60.0),
+        ];
+
+        assert_eq!(
+            get_compare_points(&points, 300_000, 60_000),
+            vec![
+                (-100_000.0, 10.0),
+                (-50_000.0, 20.0),
+                (-20_000.0, 30.0),
+                (0.0, 40.0)
+            ]
+        );
+        assert_eq!(
+            get_compare_points(&points, 300_000, 30_000),
+            vec![(-50_000.0, 20.0), (-20_000.0, 30.0), (0.0, 40.0)]
+        );
+        assert!(get_compare_points(&points, 600_000, 60_000).is_empty());
+        assert!(get_compare_points(&[], 300_000, 60_000).is_empty());
+    }
+
+    #[test]
+    fn test_get_network_stats() {
+        assert_eq!(get_network_stats(std::iter::empty()), None);
+        assert_eq!(
+            get_network_stats(vec![(10.0, 0.0), (30.0, 5.0), (20.0, 1.0)].into_iter()),
+            Some(NetworkStats {
+                min_rx: 10.0,
+                max_rx: 30.0,
+                mean_rx: 20.0,
+                min_tx: 0.0,
+                max_tx: 5.0,
+                mean_tx: 2.0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_temperature_colour() {
+        use tui::style::Color;
+
+        assert_eq!(
+            get_temperature_colour(20.0, 70.0, 90.0),
+            Color::Rgb(0, 200, 0)
+        );
+        assert_eq!(
+            get_temperature_colour(60.0, 70.0, 90.0),
+            Color::Rgb(115, 200, 0)
+        );
+        assert_eq!(
+            get_temperature_colour(70.0, 70.0, 90.0),
+            Color::Rgb(230, 200, 0)
+        );
+        assert_eq!(
+            get_temperature_colour(80.0, 70.0, 90.0),
+            Color::Rgb(225, 100, 0)
+        );
+        assert_eq!(
+            get_temperature_colour(100.0, 70.0, 90.0),
+            Color::Rgb(220, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_labels() {
+        use crate::app::data_harvester::{disks::DiskHarvest, temperature::TempHarvest};
+
+        let labels = Labels {
+            temperature: vec![("coretemp Package id 0".to_string(), "CPU".to_string())]
+                .into_iter()
+                .collect(),
+            disk: vec![("/".to_string(), "System".to_string())]
+                .into_iter()
+                .collect(),
+            network: HashMap::new(),
+            hidden: vec!["acpitz temp1".to_string(), "/boot".to_string()]
+                .into_iter()
+                .collect(),
+        };
+
+        let current_data = data_farmer::DataCollection {
+            temp_harvest: vec![
+                TempHarvest {
+                    name: "coretemp Package id 0".to_string(),
+                    temperature: 50.0,
+                },
+                TempHarvest {
+                    name: "acpitz temp1".to_string(),
+                    temperature: 30.0,
+                },
+                TempHarvest {
+                    name: "nvme Composite".to_string(),
+                    temperature: 40.0,
+                },
+            ],
+            ..Default::default()
+        };
+        let names = convert_temp_row(
+            &current_data,
+            &TemperatureType::Celsius,
+            &TempThresholds::default(),
+            &labels,
+            &TableFilter::default(),
+            &SortState::Unsortable,
+            false,
+        )
+        .data
+        .iter()
+        .map(|row| row.row()[0].main_text().to_string())
+        .collect::<Vec<_>>();
+        assert_eq!(names, vec!["CPU", "nvme Composite"]);
+
+        let root = DiskHarvest {
+            name: "/dev/sda2".to_string(),
+            mount_point: "/".to_string(),
+            ..Default::default()
+        };
+        let boot = DiskHarvest {
+            name: "/dev/sda1".to_string(),
+            mount_point: "/boot".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(labels.disk(&root), "System");
+        assert_eq!(labels.disk(&boot), "/dev/sda1");
+        assert!(!labels.is_disk_hidden(&root));
+        assert!(labels.is_disk_hidden(&boot));
+        assert_eq!(labels.network("eth0"), "eth0");
+    }
+
+    #[test]
+    fn test_duration_string() {
+        assert_eq!(duration_string(0), "0s");
+        assert_eq!(duration_string(59), "59s");
+        assert_eq!(duration_string(125), "2m 5s");
+        assert_eq!(duration_string(2 * 3600 + 5 * 60 + 9), "2h 5m");
+        assert_eq!(duration_string(3 * 86400 + 4 * 3600 + 59), "3d 4h");
+    }
+
     #[test]
     fn test_dec_bytes_per_second_string() {
         assert_eq!(dec_bytes_per_second_string(0), "0B/s".to_string());
@@ -717,4 +2750,71 @@ mod test {
             "10.4TB/s".to_string()
         );
     }
+
+    #[test]
+    fn test_top_process_on_cpu() {
+        use crate::app::data_harvester::processes::ProcessHarvest;
+
+        let mut process_data = data_farmer::ProcessData::default();
+        for (pid, last_cpu, cpu_usage_percent) in [
+            (1, Some(0), 5.0),
+            (2, Some(1), 50.0),
+            (3, Some(0), 20.0),
+            (4, None, 90.0),
+        ] {
+            process_data.process_harvest.insert(
+                pid,
+                ProcessHarvest {
+                    pid,
+                    last_cpu,
+                    cpu_usage_percent,
+                    ..ProcessHarvest::default()
+                },
+            );
+        }
+
+        let top = get_top_process_on_cpu(&process_data, 0).map(|process| process.pid);
+        assert_eq!(top, Some(3));
+        let top = get_top_process_on_cpu(&process_data, 1).map(|process| process.pid);
+        assert_eq!(top, Some(2));
+        assert!(get_top_process_on_cpu(&process_data, 2).is_none());
+    }
+
+    #[test]
+    fn test_average_cpu_usage() {
+        use crate::app::data_harvester::cpu::CpuData;
+
+        let cpu = |cpu_count, cpu_usage| CpuData {
+            cpu_prefix: "CPU".to_string(),
+            cpu_count,
+            cpu_usage,
+            breakdown: None,
+        };
+
+        assert_eq!(get_average_cpu_usage(&[]), None);
+        assert_eq!(
+            get_average_cpu_usage(&[cpu(Some(0), 10.0), cpu(Some(1), 30.0)]),
+            Some(20.0)
+        );
+        assert_eq!(
+            get_average_cpu_usage(&[cpu(None, 25.0), cpu(Some(0), 10.0), cpu(Some(1), 30.0)]),
+            Some(25.0)
+        );
+    }
+
+    #[test]
+    fn test_format_network_rate() {
+        assert_eq!(
+            format_network_rate(800.0, &DataUnit::Bit, false),
+            "800.0b/s"
+        );
+        assert_eq!(
+            format_network_rate(8_000_000.0, &DataUnit::Byte, false),
+            "1.0MB/s"
+        );
+        assert_eq!(
+            format_network_rate(8_192.0, &DataUnit::Byte, true),
+            "1.0KiB/s"
+        );
+    }
 }
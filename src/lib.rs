@@ -19,6 +19,7 @@ use std::{
     io::{stderr, stdout, Write},
     panic::PanicInfo,
     path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
     sync::Arc,
     sync::Condvar,
     sync::Mutex,
@@ -27,10 +28,13 @@ use std::{
 };
 
 use crossterm::{
-    event::{poll, read, DisableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent},
+    event::{
+        poll, read, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent,
+        KeyModifiers, MouseEvent,
+    },
     execute,
     style::Print,
-    terminal::{disable_raw_mode, LeaveAlternateScreen},
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 
 use app::{
@@ -55,6 +59,7 @@ pub mod components;
 pub mod constants;
 pub mod data_conversion;
 pub mod options;
+pub mod remote;
 pub mod units;
 
 #[cfg(target_family = "windows")]
@@ -68,25 +73,43 @@ pub enum BottomEvent<I, J> {
     KeyInput(I),
     MouseInput(J),
     Update(Box<data_harvester::Data>),
-    Clean,
+    ConfigChange,
+    /// Data from the remote agent at an index of [`App::remote_hosts`].
+    RemoteUpdate(usize, Box<data_harvester::Data>),
+    /// A change in the connection to the remote agent at an index of [`App::remote_hosts`].
+    RemoteStatus(usize, remote::HostStatus),
 }
 
 #[derive(Debug)]
 pub enum ThreadControlEvent {
     Reset,
     UpdateConfig(Box<app::AppConfigFields>),
+    UpdateFilters(Box<app::DataFilters>),
+    /// Only collects the network interfaces matching this, or all of them if it's `None`.
+    UpdateNetworkQuery(Option<regex::Regex>),
     UpdateUsedWidgets(Box<UsedWidgets>),
     UpdateUpdateTime(u64),
+    /// Stops harvesting anything while `true`, and starts again once `false`.
+    Pause(bool),
 }
 
 pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
     match event {
+        MouseEvent::ScrollUp(_x, _y, modifiers) if modifiers.contains(KeyModifiers::SHIFT) => {
+            app.handle_scroll_left()
+        }
+        MouseEvent::ScrollDown(_x, _y, modifiers) if modifiers.contains(KeyModifiers::SHIFT) => {
+            app.handle_scroll_right()
+        }
         MouseEvent::ScrollUp(_x, _y, _modifiers) => app.handle_scroll_up(),
         MouseEvent::ScrollDown(_x, _y, _modifiers) => app.handle_scroll_down(),
         MouseEvent::Down(button, x, y, _modifiers) => {
             if !app.app_config_fields.disable_click {
                 match button {
                     crossterm::event::MouseButton::Left => {
+                        // Start dragging a layout split if one was clicked
+                        app.on_left_mouse_down(x, y);
+
                         // Trigger left click widget activity
                         app.on_left_mouse_up(x, y);
                     }
@@ -95,6 +118,14 @@ pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
                 }
             }
         }
+        MouseEvent::Drag(crossterm::event::MouseButton::Left, x, y, _modifiers)
+            if !app.app_config_fields.disable_click =>
+        {
+            app.on_left_mouse_drag(x, y);
+        }
+        MouseEvent::Up(crossterm::event::MouseButton::Left, _x, _y, _modifiers) => {
+            app.on_left_mouse_release();
+        }
         _ => {}
     };
 }
@@ -114,6 +145,12 @@ pub fn handle_key_event_or_break(
         if event.code == KeyCode::Char('q') && !app.is_in_search_widget() {
             return true;
         }
+        // Hooks take priority over the built-in function keys.
+        if let KeyCode::F(number) = event.code {
+            if app.on_function_key(number) {
+                return false;
+            }
+        }
         match event.code {
             KeyCode::End => app.skip_to_last(),
             KeyCode::Home => app.skip_to_first(),
@@ -228,11 +265,13 @@ pub fn read_config(config_location: Option<&str>) -> error::Result<Option<PathBu
     Ok(config_path)
 }
 
-pub fn create_or_get_config(config_path: &Option<PathBuf>) -> error::Result<Config> {
+/// Reads the config file, or creates it if it doesn't exist. This also returns the text of the
+/// config file, which is empty if it was just created.
+pub fn create_or_get_config(config_path: &Option<PathBuf>) -> error::Result<(Config, String)> {
     if let Some(path) = config_path {
         if let Ok(config_string) = fs::read_to_string(path) {
             // We found a config file!
-            Ok(toml::from_str(config_string.as_str())?)
+            Ok((toml::from_str(config_string.as_str())?, config_string))
         } else {
             // Config file DNE...
             if let Some(parent_path) = path.parent() {
@@ -240,11 +279,11 @@ pub fn create_or_get_config(config_path: &Option<PathBuf>) -> error::Result<Conf
             }
             // fs::File::create(path)?.write_all(CONFIG_TOP_HEAD.as_bytes())?;
             fs::File::create(path)?.write_all(CONFIG_TEXT.as_bytes())?;
-            Ok(Config::default())
+            Ok((Config::default(), String::new()))
         }
     } else {
         // Don't write, the config path was somehow None...
-        Ok(Config::default())
+        Ok((Config::default(), String::new()))
     }
 }
 
@@ -252,10 +291,12 @@ pub fn try_drawing(
     terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<std::io::Stdout>>,
     app: &mut App, painter: &mut canvas::Painter,
 ) -> error::Result<()> {
+    let start = Instant::now();
     if let Err(err) = painter.draw_data(terminal, app) {
         cleanup_terminal(terminal)?;
         return Err(err);
     }
+    app.last_render_duration = start.elapsed();
 
     Ok(())
 }
@@ -274,6 +315,38 @@ pub fn cleanup_terminal(
     Ok(())
 }
 
+/// Hands the terminal over to the command of a hook while it runs, then sets the terminal back up
+/// for bottom. The input thread is paused meanwhile so that the command gets all of the input.
+/// Returns an error message if the command failed.
+pub fn run_hook_command(
+    terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<std::io::Stdout>>,
+    command: &str, pause: bool, input_paused: &AtomicBool,
+) -> error::Result<Result<(), String>> {
+    input_paused.store(true, Ordering::SeqCst);
+    // Let the input thread finish any poll it was in the middle of.
+    thread::sleep(Duration::from_millis(INPUT_POLL_IN_MILLISECONDS * 2));
+    cleanup_terminal(terminal)?;
+
+    let result = app::hooks::run_command(command);
+    if pause {
+        print!("\nPress Enter to return to bottom...");
+        stdout().flush()?;
+        let _ = std::io::stdin().read_line(&mut String::new());
+    }
+
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableMouseCapture
+    )?;
+    enable_raw_mode()?;
+    terminal.clear()?;
+    terminal.hide_cursor()?;
+    input_paused.store(false, Ordering::SeqCst);
+
+    Ok(result)
+}
+
 /// Check and report to the user if the current environment is not a terminal.
 pub fn check_if_terminal() {
     use crossterm::tty::IsTty;
@@ -319,6 +392,246 @@ pub fn panic_hook(panic_info: &PanicInfo<'_>) {
     .unwrap();
 }
 
+/// Returns how far back the stats of a graph should go, which is the longest time shown by any of
+/// its widgets, or `None` if they're turned off.
+pub fn get_stats_time_window(
+    is_enabled: bool, display_times: impl Iterator<Item = u64>, default_time_value: u64,
+) -> Option<u64> {
+    if is_enabled {
+        Some(display_times.max().unwrap_or(default_time_value))
+    } else {
+        None
+    }
+}
+
+/// Returns how many milliseconds each bucket of a downsampled graph covers, based on the
+/// shortest time shown by any of its widgets.
+pub fn get_graph_bucket_width(
+    display_times: impl Iterator<Item = u64>, default_time_value: u64,
+) -> f64 {
+    display_times.min().unwrap_or(default_time_value) as f64 / GRAPH_BUCKETS_PER_WINDOW as f64
+}
+
+/// Converts the collected data into what the widgets draw. This is done whenever new data arrives,
+/// unless the data is frozen.
+pub fn convert_collected_data(app: &mut App) {
+    // Network
+    if app.used_widgets.use_net {
+        let network_data = convert_network_data_points(
+            &app.data_collection,
+            app.app_config_fields.use_basic_mode || app.app_config_fields.use_old_network_legend,
+            &app.app_config_fields.network_unit_type,
+            app.app_config_fields.network_use_binary_prefix,
+            get_stats_time_window(
+                app.app_config_fields.show_network_stats,
+                app.net_state
+                    .widget_states
+                    .values()
+                    .map(|state| state.current_display_time),
+                app.app_config_fields.default_time_value,
+            ),
+            &app.app_config_fields.labels,
+        );
+        update_network_data_points(app);
+        app.converted_data.error_display = network_data.error_display;
+        app.converted_data.drop_display = network_data.drop_display;
+        app.converted_data.network_stats_display = network_data.stats_display;
+        app.converted_data.wireless_display = convert_wireless_data(
+            &app.data_collection,
+            &app.app_config_fields.labels,
+            &app.net_state.filter,
+        );
+        app.converted_data.address_display = convert_address_data(
+            &app.data_collection,
+            app.app_config_fields.show_public_ip,
+            &app.app_config_fields.labels,
+            &app.net_state.filter,
+        );
+        app.converted_data.rx_display = network_data.rx_display;
+        app.converted_data.tx_display = network_data.tx_display;
+        if let Some(total_rx_display) = network_data.total_rx_display {
+            app.converted_data.total_rx_display = total_rx_display;
+        }
+        if let Some(total_tx_display) = network_data.total_tx_display {
+            app.converted_data.total_tx_display = total_tx_display;
+        }
+    }
+
+    // Disk
+    if app.used_widgets.use_disk {
+        for disk in app.disk_state.widget_states.values_mut() {
+            disk.force_data_update();
+        }
+    }
+
+    // Temperatures
+    if app.used_widgets.use_temp {
+        for temp in app.temp_state.widget_states.values_mut() {
+            temp.force_data_update();
+        }
+    }
+
+    // Cgroups
+    if app.used_widgets.use_cgroup {
+        app.converted_data.cgroup_data = convert_cgroup_row(app);
+    }
+
+    // Kernel events
+    if app.used_widgets.use_events {
+        app.converted_data.event_data = convert_event_row(app);
+    }
+
+    // Users
+    if app.used_widgets.use_users {
+        app.converted_data.user_data = convert_user_row(app);
+    }
+
+    // System info
+    if app.used_widgets.use_system_info {
+        app.converted_data.system_info_display = convert_system_info(app);
+    }
+
+    // Top processes
+    if app.used_widgets.use_top_processes {
+        app.converted_data.top_process_display = convert_top_processes(app);
+    }
+
+    // Gaps
+    app.converted_data.gaps = convert_gaps(&app.data_collection);
+
+    // Peaks
+    if app.app_config_fields.show_peaks {
+        app.converted_data.peak_display = convert_peaks(
+            &app.data_collection,
+            &app.app_config_fields.network_unit_type,
+            app.app_config_fields.network_use_binary_prefix,
+        );
+    }
+
+    // Memory
+    if app.used_widgets.use_mem {
+        let bucket_width = get_graph_bucket_width(
+            app.mem_state
+                .widget_states
+                .values()
+                .map(|state| state.current_display_time),
+            app.app_config_fields.default_time_value,
+        );
+        convert_mem_data_points(
+            &app.data_collection,
+            &mut app.converted_data.mem_data,
+            bucket_width,
+        );
+        convert_swap_data_points(
+            &app.data_collection,
+            &mut app.converted_data.swap_data,
+            bucket_width,
+        );
+        if app.app_config_fields.show_swap_activity {
+            convert_swap_activity_data_points(
+                &app.data_collection,
+                &mut app.converted_data.swap_in_data,
+                &mut app.converted_data.swap_out_data,
+                bucket_width,
+            );
+        }
+        update_mem_stats(app);
+        let (memory_labels, swap_labels) = convert_mem_labels(&app.data_collection);
+
+        app.converted_data.mem_labels = memory_labels;
+        app.converted_data.swap_labels = swap_labels;
+        app.converted_data.swap_activity_labels =
+            convert_swap_activity_labels(&app.data_collection);
+    }
+
+    // Power
+    if app.used_widgets.use_power {
+        app.converted_data.power_data = convert_power_data(&app.data_collection);
+    }
+
+    // Pressure
+    if app.used_widgets.use_pressure {
+        app.converted_data.pressure_data = convert_pressure_data(&app.data_collection);
+    }
+
+    // Load average
+    if app.used_widgets.use_load_avg {
+        app.converted_data.load_avg_data = app.data_collection.load_avg_harvest;
+        app.converted_data.load_avg_history = convert_load_avg_data_points(&app.data_collection);
+    }
+
+    // Latency
+    if app.used_widgets.use_latency {
+        app.converted_data.latency_legend = convert_latency_legend(&app.data_collection);
+        app.converted_data.latency_history = convert_latency_data_points(&app.data_collection);
+    }
+
+    // Plugins
+    if app.used_widgets.use_plugins {
+        app.converted_data.plugin_data = convert_plugin_data(&app.data_collection);
+    }
+
+    // Frequency
+    if app.used_widgets.use_frequency {
+        app.converted_data.frequency_data = convert_frequency_data(&app.data_collection);
+    }
+
+    if app.used_widgets.use_cpu {
+        // CPU
+
+        convert_cpu_data_points(
+            &app.data_collection,
+            &mut app.converted_data.cpu_data,
+            &app.cpu_state.nodes,
+            get_stats_time_window(
+                app.app_config_fields.show_graph_stats,
+                app.cpu_state
+                    .widget_states
+                    .values()
+                    .map(|state| state.current_display_time),
+                app.app_config_fields.default_time_value,
+            ),
+            get_graph_bucket_width(
+                app.cpu_state
+                    .widget_states
+                    .values()
+                    .map(|state| state.current_display_time),
+                app.app_config_fields.default_time_value,
+            ),
+            app.app_config_fields.stacked_cpu,
+            app.app_config_fields.show_cpu_breakdown,
+        );
+        app.converted_data.load_avg_data = app.data_collection.load_avg_harvest;
+    }
+
+    // Processes
+    if app.used_widgets.use_proc {
+        for proc in app.proc_state.widget_states.values_mut() {
+            proc.force_data_update();
+        }
+    }
+
+    // Pinned process
+    if app.process_graph_state.is_showing_graph {
+        app.update_pinned_process_data();
+    }
+
+    // Process threads
+    if app.process_threads_state.is_showing_threads {
+        app.update_process_threads();
+    }
+
+    // Battery
+    #[cfg(feature = "battery")]
+    {
+        if app.used_widgets.use_battery {
+            app.converted_data.battery_data = convert_battery_harvest(&app.data_collection);
+        }
+    }
+
+    update_data(app);
+}
+
 pub fn update_data(app: &mut App) {
     for proc in app.proc_state.widget_states.values_mut() {
         if proc.force_update_data {
@@ -327,37 +640,188 @@ pub fn update_data(app: &mut App) {
         }
     }
 
+    for disk in app.disk_state.widget_states.values_mut() {
+        if disk.force_update_data {
+            disk.table_data = convert_disk_row(
+                &app.data_collection,
+                &app.app_config_fields.temperature_type,
+                &app.app_config_fields.labels,
+                app.app_config_fields.disk_full_warning,
+                disk,
+            );
+            disk.force_update_data = false;
+        }
+    }
+
+    for temp in app.temp_state.widget_states.values_mut() {
+        if temp.force_update_data {
+            temp.table_data = convert_temp_row(
+                &app.data_collection,
+                &app.app_config_fields.temperature_type,
+                &app.app_config_fields.temp_thresholds,
+                &app.app_config_fields.labels,
+                &temp.filter,
+                &temp.table_state.sort_state,
+                app.app_config_fields.show_peaks,
+            );
+            temp.force_update_data = false;
+        }
+    }
+
     if app.cpu_state.force_update.is_some() {
-        convert_cpu_data_points(&app.data_collection, &mut app.converted_data.cpu_data);
+        convert_cpu_data_points(
+            &app.data_collection,
+            &mut app.converted_data.cpu_data,
+            &app.cpu_state.nodes,
+            get_stats_time_window(
+                app.app_config_fields.show_graph_stats,
+                app.cpu_state
+                    .widget_states
+                    .values()
+                    .map(|state| state.current_display_time),
+                app.app_config_fields.default_time_value,
+            ),
+            get_graph_bucket_width(
+                app.cpu_state
+                    .widget_states
+                    .values()
+                    .map(|state| state.current_display_time),
+                app.app_config_fields.default_time_value,
+            ),
+            app.app_config_fields.stacked_cpu,
+            app.app_config_fields.show_cpu_breakdown,
+        );
         app.converted_data.load_avg_data = app.data_collection.load_avg_harvest;
         app.cpu_state.force_update = None;
     }
 
     // TODO: [OPT] Prefer reassignment over new vectors?
     if app.mem_state.force_update.is_some() {
-        app.converted_data.mem_data = convert_mem_data_points(&app.data_collection);
-        app.converted_data.swap_data = convert_swap_data_points(&app.data_collection);
+        let bucket_width = get_graph_bucket_width(
+            app.mem_state
+                .widget_states
+                .values()
+                .map(|state| state.current_display_time),
+            app.app_config_fields.default_time_value,
+        );
+        convert_mem_data_points(
+            &app.data_collection,
+            &mut app.converted_data.mem_data,
+            bucket_width,
+        );
+        convert_swap_data_points(
+            &app.data_collection,
+            &mut app.converted_data.swap_data,
+            bucket_width,
+        );
+        if app.app_config_fields.show_swap_activity {
+            convert_swap_activity_data_points(
+                &app.data_collection,
+                &mut app.converted_data.swap_in_data,
+                &mut app.converted_data.swap_out_data,
+                bucket_width,
+            );
+        }
+        update_mem_stats(app);
         app.mem_state.force_update = None;
     }
 
     if app.net_state.force_update.is_some() {
-        let (rx, tx) = get_rx_tx_data_points(
-            &app.data_collection,
-            &app.app_config_fields.network_scale_type,
-            &app.app_config_fields.network_unit_type,
-            app.app_config_fields.network_use_binary_prefix,
-        );
-        app.converted_data.network_data_rx = rx;
-        app.converted_data.network_data_tx = tx;
+        update_network_data_points(app);
         app.net_state.force_update = None;
     }
+
+    if app.power_state.force_update.is_some() {
+        app.converted_data.power_data = convert_power_data(&app.data_collection);
+        app.power_state.force_update = None;
+    }
+
+    if app.pressure_state.force_update.is_some() {
+        app.converted_data.pressure_data = convert_pressure_data(&app.data_collection);
+        app.pressure_state.force_update = None;
+    }
+
+    if app.load_avg_state.force_update.is_some() {
+        app.converted_data.load_avg_history = convert_load_avg_data_points(&app.data_collection);
+        app.load_avg_state.force_update = None;
+    }
+
+    if app.latency_state.force_update.is_some() {
+        app.converted_data.latency_history = convert_latency_data_points(&app.data_collection);
+        app.latency_state.force_update = None;
+    }
+
+    if app.plugin_state.force_update.is_some() {
+        app.converted_data.plugin_data = convert_plugin_data(&app.data_collection);
+        app.plugin_state.force_update = None;
+    }
+
+    if app.frequency_state.force_update.is_some() {
+        app.converted_data.frequency_data = convert_frequency_data(&app.data_collection);
+        app.frequency_state.force_update = None;
+    }
+}
+
+/// Converts the points of the network graphs that came in since they were last converted.
+pub fn update_network_data_points(app: &mut App) {
+    let bucket_width = get_graph_bucket_width(
+        app.net_state
+            .widget_states
+            .values()
+            .map(|state| state.current_display_time),
+        app.app_config_fields.default_time_value,
+    );
+    let config = &app.app_config_fields;
+    let converted_data = &mut app.converted_data;
+
+    convert_rx_tx_data_points(
+        &app.data_collection,
+        &mut converted_data.network_data_rx,
+        &mut converted_data.network_data_tx,
+        &config.network_scale_type,
+        &config.network_unit_type,
+        config.network_use_binary_prefix,
+        bucket_width,
+    );
+    convert_total_rx_tx_data_points(
+        &app.data_collection,
+        &mut converted_data.network_data_total_rx,
+        &mut converted_data.network_data_total_tx,
+        &config.network_scale_type,
+        &config.network_unit_type,
+        config.network_use_binary_prefix,
+        bucket_width,
+    );
+    convert_error_drop_data_points(
+        &app.data_collection,
+        &mut converted_data.network_data_errors,
+        &mut converted_data.network_data_drops,
+        bucket_width,
+    );
+}
+
+/// Works out the stats of the memory and swap graphs, if they're turned on.
+pub fn update_mem_stats(app: &mut App) {
+    let stats_time_window = get_stats_time_window(
+        app.app_config_fields.show_graph_stats,
+        app.mem_state
+            .widget_states
+            .values()
+            .map(|state| state.current_display_time),
+        app.app_config_fields.default_time_value,
+    );
+    app.converted_data.mem_stats = stats_time_window
+        .and_then(|time_window| get_graph_stats(app.converted_data.mem_data.points(), time_window));
+    app.converted_data.swap_stats = stats_time_window.and_then(|time_window| {
+        get_graph_stats(app.converted_data.swap_data.points(), time_window)
+    });
 }
 
 pub fn create_input_thread(
     sender: std::sync::mpsc::Sender<
         BottomEvent<crossterm::event::KeyEvent, crossterm::event::MouseEvent>,
     >,
-    termination_ctrl_lock: Arc<Mutex<bool>>,
+    termination_ctrl_lock: Arc<Mutex<bool>>, input_paused: Arc<AtomicBool>,
 ) -> std::thread::JoinHandle<()> {
     thread::spawn(move || {
         let mut mouse_timer = Instant::now();
@@ -371,7 +835,11 @@ pub fn create_input_thread(
                     break;
                 }
             }
-            if let Ok(poll) = poll(Duration::from_millis(20)) {
+            if input_paused.load(Ordering::SeqCst) {
+                thread::sleep(Duration::from_millis(INPUT_POLL_IN_MILLISECONDS));
+                continue;
+            }
+            if let Ok(poll) = poll(Duration::from_millis(INPUT_POLL_IN_MILLISECONDS)) {
                 if poll {
                     if let Ok(event) = read() {
                         if let Event::Key(key) = event {
@@ -396,6 +864,119 @@ pub fn create_input_thread(
     })
 }
 
+/// Watches the config file for changes by checking when it was last modified, and sends a
+/// [`BottomEvent::ConfigChange`] whenever it changes.
+pub fn create_config_watcher_thread(
+    sender: std::sync::mpsc::Sender<
+        BottomEvent<crossterm::event::KeyEvent, crossterm::event::MouseEvent>,
+    >,
+    config_path: PathBuf, termination_ctrl_lock: Arc<Mutex<bool>>,
+    termination_ctrl_cvar: Arc<Condvar>,
+) -> std::thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let get_modified_time = || {
+            fs::metadata(&config_path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        };
+        let mut last_modified_time = get_modified_time();
+
+        loop {
+            let result = termination_ctrl_cvar.wait_timeout(
+                termination_ctrl_lock.lock().unwrap(),
+                Duration::from_millis(CONFIG_POLL_INTERVAL_IN_MILLISECONDS),
+            );
+            if let Ok(result) = result {
+                if *(result.0) {
+                    break;
+                }
+            }
+
+            let modified_time = get_modified_time();
+            if modified_time != last_modified_time {
+                last_modified_time = modified_time;
+                if sender.send(BottomEvent::ConfigChange).is_err() {
+                    break;
+                }
+            }
+        }
+    })
+}
+
+/// Re-reads the config file and applies the options that can be changed while running, such as
+/// the refresh rates, units, filters, key bindings, and colours. Options that change the layout,
+/// like the widget rows or basic mode, still need a restart. If the file can't be read or has an
+/// invalid option, nothing is changed and the error is shown instead.
+pub fn reload_config(
+    matches: &::clap::ArgMatches, app: &mut App, painter: &mut canvas::Painter,
+    collection_thread_ctrl_sender: &std::sync::mpsc::Sender<ThreadControlEvent>,
+) {
+    match apply_config(matches, app, painter, collection_thread_ctrl_sender) {
+        Ok(()) => {
+            app.config_error = None;
+            app.set_status_message("Reloaded the config file".to_string());
+        }
+        Err(err) => {
+            app.config_error = Some(format!("{:#}", err));
+            app.is_force_redraw = true;
+        }
+    }
+}
+
+fn apply_config(
+    matches: &::clap::ArgMatches, app: &mut App, painter: &mut canvas::Painter,
+    collection_thread_ctrl_sender: &std::sync::mpsc::Sender<ThreadControlEvent>,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    let config_path = match &app.config_path {
+        Some(config_path) => config_path,
+        None => return Ok(()),
+    };
+    let config_string =
+        fs::read_to_string(config_path).context("Unable to read the config file.")?;
+    let config: Config =
+        toml::from_str(&config_string).context("Unable to parse the config file.")?;
+
+    // Everything is read before anything is changed, so an invalid option changes nothing.
+    let mut app_config_fields = get_app_config_fields(matches, &config)?;
+    let filters = get_data_filters(&config)?;
+    let hooks = get_hooks(&config);
+    let key_bindings = get_key_bindings(&config, &hooks)?;
+    let alerts = get_alerts(&config).context("Update the 'alerts' section in your config file.")?;
+    let config_warnings = config_warnings::get_config_warnings(matches, &config_string, &config);
+    let derived_metrics =
+        app::derived_metrics::DerivedMetrics::new(&app_config_fields.derived_metrics)
+            .map_err(error::BottomError::ConfigError)
+            .context("Update the 'derived_metrics' section in your config file.")?;
+    painter.reload_colour_scheme(get_color_scheme(matches, &config)?, &config)?;
+
+    // Basic mode has its own layout, so it can't be switched while running.
+    app_config_fields.use_basic_mode = app.app_config_fields.use_basic_mode;
+
+    let previous_default_time_value = app.app_config_fields.default_time_value;
+    app.app_config_fields = app_config_fields;
+    app.update_default_time_value(previous_default_time_value);
+
+    app.converted_data.clear_network_graphs();
+    app.filters = filters;
+    app.key_bindings = key_bindings;
+    app.hooks = hooks;
+    app.alerts = alerts;
+    app.derived_metrics = derived_metrics;
+    app.config_warnings = config_warnings;
+    app.config = config;
+
+    let _ = collection_thread_ctrl_sender.send(ThreadControlEvent::UpdateConfig(Box::new(
+        app.app_config_fields.clone(),
+    )));
+    let _ = collection_thread_ctrl_sender.send(ThreadControlEvent::UpdateFilters(Box::new(
+        app.filters.clone(),
+    )));
+
+    Ok(())
+}
+
 pub fn create_collection_thread(
     sender: std::sync::mpsc::Sender<
         BottomEvent<crossterm::event::KeyEvent, crossterm::event::MouseEvent>,
@@ -407,8 +988,20 @@ pub fn create_collection_thread(
 ) -> std::thread::JoinHandle<()> {
     let temp_type = app_config_fields.temperature_type.clone();
     let use_current_cpu_total = app_config_fields.use_current_cpu_total;
+    let show_process_memory_details = app_config_fields.show_process_memory_details;
+    let precise_process_memory = app_config_fields.precise_process_memory;
+    let show_process_gpu = app_config_fields.show_process_gpu;
     let show_average_cpu = app_config_fields.show_average_cpu;
-    let update_rate_in_milliseconds = app_config_fields.update_rate_in_milliseconds;
+    let show_cpu_breakdown = app_config_fields.show_cpu_breakdown;
+    let show_disk_smart = app_config_fields.show_disk_smart;
+    let show_disk_activity = app_config_fields.show_disk_activity;
+    let use_termux_profile = app_config_fields.use_termux_profile;
+    let show_network_addresses = app_config_fields.show_network_addresses;
+    let show_public_ip = app_config_fields.show_public_ip;
+    let latency_hosts = app_config_fields.latency_hosts.clone();
+    let plugins = app_config_fields.plugins.clone();
+    let harvest_intervals = app_config_fields.harvest_intervals.clone();
+    let mut update_rate_in_milliseconds = harvest_intervals.get_shortest_interval();
 
     thread::spawn(move || {
         let mut data_state = data_harvester::DataCollector::new(filters);
@@ -416,10 +1009,22 @@ pub fn create_collection_thread(
         data_state.set_data_collection(used_widget_set);
         data_state.set_temperature_type(temp_type);
         data_state.set_use_current_cpu_total(use_current_cpu_total);
+        data_state.set_process_memory_details(show_process_memory_details, precise_process_memory);
+        data_state.set_process_gpu(show_process_gpu);
         data_state.set_show_average_cpu(show_average_cpu);
+        data_state.set_show_cpu_breakdown(show_cpu_breakdown);
+        data_state.set_enable_smart(show_disk_smart);
+        data_state.set_enable_disk_activity(show_disk_activity);
+        data_state.set_termux_profile(use_termux_profile);
+        data_state.set_show_addresses(show_network_addresses);
+        data_state.set_show_public_ip(show_public_ip);
+        data_state.set_latency_hosts(latency_hosts);
+        data_state.set_plugins(plugins);
+        data_state.set_harvest_intervals(harvest_intervals);
 
         data_state.init();
 
+        let mut is_paused = false;
         loop {
             // Check once at the very top...
             if let Ok(is_terminated) = termination_ctrl_lock.try_lock() {
@@ -441,7 +1046,31 @@ pub fn create_collection_thread(
                         data_state.set_temperature_type(app_config_fields.temperature_type.clone());
                         data_state
                             .set_use_current_cpu_total(app_config_fields.use_current_cpu_total);
+                        data_state.set_process_memory_details(
+                            app_config_fields.show_process_memory_details,
+                            app_config_fields.precise_process_memory,
+                        );
+                        data_state.set_process_gpu(app_config_fields.show_process_gpu);
                         data_state.set_show_average_cpu(app_config_fields.show_average_cpu);
+                        data_state.set_show_cpu_breakdown(app_config_fields.show_cpu_breakdown);
+                        data_state.set_enable_smart(app_config_fields.show_disk_smart);
+                        data_state.set_enable_disk_activity(app_config_fields.show_disk_activity);
+                        data_state.set_termux_profile(app_config_fields.use_termux_profile);
+                        data_state.set_show_addresses(app_config_fields.show_network_addresses);
+                        data_state.set_show_public_ip(app_config_fields.show_public_ip);
+                        data_state.set_latency_hosts(app_config_fields.latency_hosts.clone());
+                        data_state.set_plugins(app_config_fields.plugins.clone());
+                        update_rate_in_milliseconds =
+                            app_config_fields.harvest_intervals.get_shortest_interval();
+                        update_time = update_rate_in_milliseconds;
+                        data_state
+                            .set_harvest_intervals(app_config_fields.harvest_intervals.clone());
+                    }
+                    ThreadControlEvent::UpdateFilters(filters) => {
+                        data_state.set_filters(*filters);
+                    }
+                    ThreadControlEvent::UpdateNetworkQuery(net_query) => {
+                        data_state.set_net_query(net_query);
                     }
                     ThreadControlEvent::UpdateUsedWidgets(used_widget_set) => {
                         data_state.set_data_collection(*used_widget_set);
@@ -449,8 +1078,28 @@ pub fn create_collection_thread(
                     ThreadControlEvent::UpdateUpdateTime(new_time) => {
                         update_time = new_time;
                     }
+                    ThreadControlEvent::Pause(pause) => {
+                        is_paused = pause;
+                    }
                 }
             }
+
+            // While paused, this only waits for messages.
+            if is_paused {
+                if let Ok((is_terminated, _wait_timeout_result)) = termination_ctrl_cvar
+                    .wait_timeout(
+                        termination_ctrl_lock.lock().unwrap(),
+                        Duration::from_millis(update_time),
+                    )
+                {
+                    if *is_terminated {
+                        drop(is_terminated);
+                        break;
+                    }
+                }
+                continue;
+            }
+
             futures::executor::block_on(data_state.update_data());
 
             // Yet another check to bail if needed...
@@ -4,19 +4,38 @@ use std::{
     borrow::Cow,
     collections::{HashMap, HashSet},
     convert::TryInto,
-    path::PathBuf,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
     str::FromStr,
     time::Instant,
 };
 
 use crate::{
     app::{
+        alerts::{parse_webhook_url, Alert, AlertMetric},
+        data_harvester::{
+            cpu::topology,
+            latency::LatencyHost,
+            plugins::PluginConfig,
+            temperature::{TempThreshold, TempThresholds},
+            HarvestIntervals, HarvestType,
+        },
+        derived_metrics::{DerivedMetricConfig, DerivedMetrics},
+        hooks::{Hook, HookKey},
+        keybindings::KeyBindings,
         layout_manager::*,
-        widgets::{DiskWidgetState, ProcWidget, ProcWidgetMode, TempWidgetState},
+        query::parse_query,
+        widgets::{
+            CgroupWidgetState, DiskWidgetState, EventWidgetState, ProcWidget, ProcWidgetMode,
+            SavedFilter, TempWidgetState, UserWidgetState,
+        },
         *,
     },
-    canvas::ColourScheme,
+    canvas::{graphics::GraphRenderer, ColourScheme},
     constants::*,
+    data_conversion::Labels,
+    remote::{self, RemoteHost},
     units::data_units::DataUnit,
     utils::error::{self, BottomError},
 };
@@ -25,6 +44,8 @@ use typed_builder::*;
 
 use layout_options::*;
 
+pub mod config_command;
+pub mod config_warnings;
 pub mod layout_options;
 
 use anyhow::{Context, Result};
@@ -32,12 +53,24 @@ use anyhow::{Context, Result};
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Config {
     pub flags: Option<ConfigFlags>,
+    #[serde(alias = "theme")]
     pub colors: Option<ConfigColours>,
     pub row: Option<Vec<Row>>,
+    pub layouts: Option<Vec<NamedLayout>>,
     pub disk_filter: Option<IgnoreList>,
     pub mount_filter: Option<IgnoreList>,
     pub temp_filter: Option<IgnoreList>,
     pub net_filter: Option<IgnoreList>,
+    pub keybindings: Option<HashMap<String, String>>,
+    pub hooks: Option<Vec<ConfigHook>>,
+    pub alerts: Option<Vec<ConfigAlert>>,
+    pub process_filters: Option<Vec<SavedProcessFilter>>,
+    pub latency_hosts: Option<Vec<LatencyHost>>,
+    pub plugins: Option<Vec<PluginConfig>>,
+    pub derived_metrics: Option<Vec<DerivedMetricConfig>>,
+    pub refresh_rates: Option<RefreshRates>,
+    pub temp_thresholds: Option<ConfigTempThresholds>,
+    pub labels: Option<ConfigLabels>,
 }
 
 impl Config {
@@ -50,6 +83,96 @@ impl Config {
 
         Ok(config_string.concat().as_bytes().to_vec())
     }
+
+    /// Writes the config to `config_path`, replacing the whole file, so any comments in it are
+    /// lost. It's written to a temporary file next to it first, then moved over it, so the config
+    /// file is never left partly written. If `config_path` is a symlink, like into a dotfiles
+    /// repository, the file it links to is the one replaced.
+    pub fn save(&self, config_path: &Path) -> anyhow::Result<()> {
+        let bytes = self.get_config_as_bytes()?;
+        let config_path =
+            &fs::canonicalize(config_path).unwrap_or_else(|_| config_path.to_path_buf());
+        let mut temp_file_name = config_path
+            .file_name()
+            .context("The config path has no file name.")?
+            .to_os_string();
+        temp_file_name.push(".tmp");
+        let temp_path = config_path.with_file_name(temp_file_name);
+
+        let result = fs::File::create(&temp_path)
+            .and_then(|mut file| {
+                // The new file would otherwise have the default permissions, rather than any that
+                // were set on the config file to keep it private.
+                if let Ok(metadata) = fs::metadata(config_path) {
+                    file.set_permissions(metadata.permissions())?;
+                }
+                file.write_all(&bytes)?;
+                file.sync_all()
+            })
+            .and_then(|_| fs::rename(&temp_path, config_path));
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+
+        Ok(result?)
+    }
+
+    /// Returns the names of the layouts that can be switched between, starting with the one in
+    /// `[[row]]`.
+    pub fn layout_names(&self) -> Vec<&str> {
+        std::iter::once(DEFAULT_LAYOUT_NAME)
+            .chain(
+                self.layouts
+                    .iter()
+                    .flatten()
+                    .map(|layout| layout.name.as_str()),
+            )
+            .collect()
+    }
+
+    /// Returns the rows of the layout at `index` in [`Config::layout_names`], if there is one.
+    pub fn layout_rows(&self, index: usize) -> Option<&Option<Vec<Row>>> {
+        match index {
+            0 => Some(&self.row),
+            _ => self
+                .layouts
+                .as_ref()
+                .and_then(|layouts| layouts.get(index - 1))
+                .map(|layout| &layout.row),
+        }
+    }
+
+    /// Returns the index in [`Config::layout_names`] of the layout to show in a terminal of this
+    /// size, going by the breakpoints of the layouts.  The first layout whose breakpoints all fit is
+    /// used.  If none of them fit, a layout with breakpoints is switched back to the default one,
+    /// while any other layout is kept.
+    pub fn get_breakpoint_layout(&self, current_layout: usize, width: u16, height: u16) -> usize {
+        let layouts = self.layouts.as_deref().unwrap_or_default();
+        if let Some(index) = layouts
+            .iter()
+            .position(|layout| layout.fits_breakpoints(width, height))
+        {
+            index + 1
+        } else if current_layout > 0
+            && matches!(layouts.get(current_layout - 1), Some(layout) if layout.has_breakpoints())
+        {
+            0
+        } else {
+            current_layout
+        }
+    }
+
+    /// Returns the rows of the layout at `index` in [`Config::layout_names`], if there is one.
+    pub fn layout_rows_mut(&mut self, index: usize) -> Option<&mut Option<Vec<Row>>> {
+        match index {
+            0 => Some(&mut self.row),
+            _ => self
+                .layouts
+                .as_mut()
+                .and_then(|layouts| layouts.get_mut(index - 1))
+                .map(|layout| &mut layout.row),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, TypedBuilder)]
@@ -60,6 +183,12 @@ pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
     pub dot_marker: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub no_unicode: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub screen_reader: Option<bool>,
+
     #[builder(default, setter(strip_option))]
     pub temperature_type: Option<String>,
 
@@ -87,12 +216,18 @@ pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
     pub basic: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub basic_widgets: Option<Vec<String>>,
+
     #[builder(default, setter(strip_option))]
     pub default_time_value: Option<u64>,
 
     #[builder(default, setter(strip_option))]
     pub time_delta: Option<u64>,
 
+    #[builder(default, setter(strip_option))]
+    pub retention: Option<String>,
+
     #[builder(default, setter(strip_option))]
     pub autohide_time: Option<bool>,
 
@@ -117,13 +252,22 @@ pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
     pub disable_click: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub termux: Option<bool>,
+
     #[builder(default, setter(strip_option))]
     pub no_write: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub save_layout: Option<bool>,
+
     // For built-in colour palettes.
     #[builder(default, setter(strip_option))]
     pub color: Option<String>,
 
+    #[builder(default, setter(strip_option))]
+    pub graph_renderer: Option<String>,
+
     // This is a huge hack to enable hashmap functionality WITHOUT being able to serializing the field.
     // Basically, keep a hashmap in the struct, and convert to a vector every time.
     #[builder(default, setter(strip_option))]
@@ -157,12 +301,66 @@ pub struct ConfigFlags {
     #[builder(default, setter(strip_option))]
     show_table_scroll_position: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub mini_widgets: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub min_row_height: Option<u16>,
+
     #[builder(default, setter(strip_option))]
     pub process_command: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub process_sparkline: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_memory_details: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub precise_process_memory: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_gpu: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_start_time: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_start_time_format: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub process_systemd_unit: Option<bool>,
+
     #[builder(default, setter(strip_option))]
     pub disable_advanced_kill: Option<bool>,
 
+    #[builder(default, setter(strip_option))]
+    pub disk_inodes: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub disk_smart: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub disk_activity: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub disk_full_estimate: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub disk_full_warning: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub swap_activity: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub peaks: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub compare: Option<String>,
+
+    #[builder(default, setter(strip_option))]
+    pub shade_gaps: Option<bool>,
+
     #[builder(default, setter(strip_option))]
     pub network_use_bytes: Option<bool>,
 
@@ -171,6 +369,33 @@ pub struct ConfigFlags {
 
     #[builder(default, setter(strip_option))]
     pub network_use_binary_prefix: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub network_errors: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub network_split: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub network_stats: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub stacked_cpu: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub cpu_breakdown: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub cpu_top_process: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub graph_stats: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub network_addresses: Option<bool>,
+
+    #[builder(default, setter(strip_option))]
+    pub public_ip: Option<bool>,
 }
 
 #[derive(Clone, Default, Debug, Deserialize, Serialize)]
@@ -191,12 +416,86 @@ impl WidgetIdEnabled {
     }
 }
 
+/// A named process filter, which can be cycled through in the process widget.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SavedProcessFilter {
+    pub name: String,
+    pub query: String,
+}
+
+/// An external command that is run when a key is pressed.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ConfigHook {
+    pub key: String,
+    pub command: String,
+    pub pause: Option<bool>,
+}
+
+/// A threshold for a metric, which shows a message and optionally sends a desktop notification or
+/// a webhook when it's crossed.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ConfigAlert {
+    pub metric: String,
+    pub threshold: f64,
+    pub notify: Option<bool>,
+    pub webhook: Option<String>,
+}
+
+/// How often each type of data is refreshed, in milliseconds. Any that are not set use `rate`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct RefreshRates {
+    pub cpu: Option<u64>,
+    pub memory: Option<u64>,
+    pub network: Option<u64>,
+    pub processes: Option<u64>,
+    pub temperature: Option<u64>,
+    pub disk: Option<u64>,
+    pub battery: Option<u64>,
+    pub power: Option<u64>,
+    pub pressure: Option<u64>,
+    pub cgroup: Option<u64>,
+    pub latency: Option<u64>,
+    pub frequency: Option<u64>,
+    pub events: Option<u64>,
+    pub system_info: Option<u64>,
+    pub users: Option<u64>,
+    pub plugins: Option<u64>,
+}
+
+/// Warning and critical temperatures in Celsius, used for all sensors unless overridden by name
+/// in `sensors`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ConfigTempThresholds {
+    pub warning: Option<f32>,
+    pub critical: Option<f32>,
+    pub sensors: Option<HashMap<String, ConfigTempThreshold>>,
+    /// Whether to show an alert when any sensor goes over its critical temperature.
+    pub alert_on_critical: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ConfigTempThreshold {
+    pub warning: f32,
+    pub critical: f32,
+}
+
+/// Names to show in place of the harvested names of temperature sensors, disks, and network
+/// interfaces, as "harvested name" = "shown name", and the harvested names of any to hide.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ConfigLabels {
+    pub temperature: Option<HashMap<String, String>>,
+    pub disk: Option<HashMap<String, String>>,
+    pub network: Option<HashMap<String, String>>,
+    pub hidden: Option<Vec<String>>,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct ConfigColours {
     pub table_header_color: Option<String>,
     pub all_cpu_color: Option<String>,
     pub avg_cpu_color: Option<String>,
     pub cpu_core_colors: Option<Vec<String>>,
+    pub series_colors: Option<Vec<String>>,
     pub ram_color: Option<String>,
     pub swap_color: Option<String>,
     pub rx_color: Option<String>,
@@ -214,6 +513,11 @@ pub struct ConfigColours {
     pub high_battery_color: Option<String>,
     pub medium_battery_color: Option<String>,
     pub low_battery_color: Option<String>,
+    pub stopped_process_color: Option<String>,
+    pub marked_process_color: Option<String>,
+    pub network_error_color: Option<String>,
+    pub network_drop_color: Option<String>,
+    pub error_color: Option<String>,
 }
 
 impl ConfigColours {
@@ -254,10 +558,10 @@ pub fn build_app(
     config_path: Option<PathBuf>,
 ) -> Result<App> {
     use BottomWidgetType::*;
-    let autohide_time = get_autohide_time(matches, config);
-    let default_time_value = get_default_time_value(matches, config)
-        .context("Update 'default_time_value' in your config file.")?;
-    let use_basic_mode = get_use_basic_mode(matches, config);
+    let app_config_fields = get_app_config_fields(matches, config)?;
+    let autohide_time = app_config_fields.autohide_time;
+    let default_time_value = app_config_fields.default_time_value;
+    let use_basic_mode = app_config_fields.use_basic_mode;
 
     // For processes
     let is_grouped = get_app_grouping(matches, config);
@@ -273,6 +577,15 @@ pub fn build_app(
     let mut temp_state_map: HashMap<u64, TempWidgetState> = HashMap::new();
     let mut disk_state_map: HashMap<u64, DiskWidgetState> = HashMap::new();
     let mut battery_state_map: HashMap<u64, BatteryWidgetState> = HashMap::new();
+    let mut power_state_map: HashMap<u64, PowerWidgetState> = HashMap::new();
+    let mut pressure_state_map: HashMap<u64, PressureWidgetState> = HashMap::new();
+    let mut load_avg_state_map: HashMap<u64, LoadAvgWidgetState> = HashMap::new();
+    let mut cgroup_state_map: HashMap<u64, CgroupWidgetState> = HashMap::new();
+    let mut event_state_map: HashMap<u64, EventWidgetState> = HashMap::new();
+    let mut user_state_map: HashMap<u64, UserWidgetState> = HashMap::new();
+    let mut latency_state_map: HashMap<u64, LatencyWidgetState> = HashMap::new();
+    let mut plugin_state_map: HashMap<u64, PluginWidgetState> = HashMap::new();
+    let mut frequency_state_map: HashMap<u64, FrequencyWidgetState> = HashMap::new();
 
     let autohide_timer = if autohide_time {
         Some(Instant::now())
@@ -288,11 +601,17 @@ pub fn build_app(
     let show_memory_as_values = get_mem_as_value(matches, config);
     let is_default_tree = get_is_default_tree(matches, config);
     let is_default_command = get_is_default_process_command(matches, config);
-    let is_advanced_kill = !get_is_advanced_kill_disabled(matches, config);
-
-    let network_unit_type = get_network_unit_type(matches, config);
-    let network_scale_type = get_network_scale_type(matches, config);
-    let network_use_binary_prefix = get_network_use_binary_prefix(matches, config);
+    let show_process_sparkline = get_show_process_sparkline(matches, config);
+    let show_process_memory_details = app_config_fields.show_process_memory_details;
+    let show_process_gpu = app_config_fields.show_process_gpu;
+    let show_process_start_time = get_show_process_start_time(matches, config);
+    let is_relative_start_time = get_is_relative_start_time(matches, config)
+        .context("Update 'process_start_time_format' in your config file.")?;
+    let show_process_systemd_unit = get_show_process_systemd_unit(matches, config);
+    let show_disk_inodes = get_show_disk_inodes(matches, config);
+    let show_disk_smart = app_config_fields.show_disk_smart;
+    let show_disk_activity = app_config_fields.show_disk_activity;
+    let show_disk_full_estimate = get_show_disk_full_estimate(matches, config);
 
     for row in &widget_layout.rows {
         for col in &row.children {
@@ -333,19 +652,19 @@ pub fn build_app(
                     used_widget_set.insert(widget.widget_type.clone());
 
                     match widget.widget_type {
-                        Cpu => {
+                        Cpu | BasicCpu => {
                             cpu_state_map.insert(
                                 widget.widget_id,
                                 CpuWidgetState::init(default_time_value, autohide_timer),
                             );
                         }
-                        Mem => {
+                        Mem | BasicMem => {
                             mem_state_map.insert(
                                 widget.widget_id,
                                 MemWidgetState::init(default_time_value, autohide_timer),
                             );
                         }
-                        Net => {
+                        Net | BasicNet => {
                             net_state_map.insert(
                                 widget.widget_id,
                                 NetWidgetState::init(default_time_value, autohide_timer),
@@ -362,27 +681,100 @@ pub fn build_app(
                                 ProcWidgetMode::Normal
                             };
 
-                            proc_state_map.insert(
-                                widget.widget_id,
-                                ProcWidget::init(
-                                    mode,
-                                    is_case_sensitive,
-                                    is_match_whole_word,
-                                    is_use_regex,
-                                    show_memory_as_values,
-                                    is_default_command,
-                                ),
+                            let mut proc_widget = ProcWidget::init(
+                                mode,
+                                is_case_sensitive,
+                                is_match_whole_word,
+                                is_use_regex,
+                                show_memory_as_values,
+                                is_default_command,
+                                show_process_sparkline,
+                                show_process_memory_details,
+                                show_process_gpu,
+                                show_process_start_time,
+                                is_relative_start_time,
+                                show_process_systemd_unit,
                             );
+                            proc_widget.saved_filters = get_saved_filters(
+                                config,
+                                is_case_sensitive,
+                                is_match_whole_word,
+                                is_use_regex,
+                            )
+                            .context("Update 'process_filters' in your config file.")?;
+                            proc_widget
+                                .apply_layout_options(&widget.process_options)
+                                .context("Update the process widget's settings in your layout.")?;
+
+                            proc_state_map.insert(widget.widget_id, proc_widget);
                         }
                         Disk => {
-                            disk_state_map.insert(widget.widget_id, DiskWidgetState::default());
+                            disk_state_map.insert(
+                                widget.widget_id,
+                                DiskWidgetState::init(
+                                    show_disk_inodes,
+                                    show_disk_smart,
+                                    show_disk_activity,
+                                    show_disk_full_estimate,
+                                ),
+                            );
                         }
                         Temp => {
                             temp_state_map.insert(widget.widget_id, TempWidgetState::default());
                         }
                         Battery => {
-                            battery_state_map
-                                .insert(widget.widget_id, BatteryWidgetState::default());
+                            battery_state_map.insert(
+                                widget.widget_id,
+                                BatteryWidgetState::init(default_time_value, autohide_timer),
+                            );
+                        }
+                        Power => {
+                            power_state_map.insert(
+                                widget.widget_id,
+                                PowerWidgetState::init(default_time_value, autohide_timer),
+                            );
+                        }
+                        Pressure => {
+                            pressure_state_map.insert(
+                                widget.widget_id,
+                                PressureWidgetState::init(default_time_value, autohide_timer),
+                            );
+                        }
+                        LoadAvg => {
+                            load_avg_state_map.insert(
+                                widget.widget_id,
+                                LoadAvgWidgetState::init(default_time_value, autohide_timer),
+                            );
+                        }
+                        Cgroup => {
+                            cgroup_state_map.insert(widget.widget_id, CgroupWidgetState::default());
+                        }
+                        Latency => {
+                            latency_state_map.insert(
+                                widget.widget_id,
+                                LatencyWidgetState::init(default_time_value, autohide_timer),
+                            );
+                        }
+                        Plugin => {
+                            let plugin = widget.plugin.clone().unwrap_or_default();
+                            check_plugin_exists(&plugin, &app_config_fields)
+                                .context("Update the plugin widget's settings in your layout.")?;
+                            plugin_state_map.insert(
+                                widget.widget_id,
+                                PluginWidgetState::init(plugin, default_time_value, autohide_timer),
+                            );
+                        }
+                        Frequency => {
+                            frequency_state_map.insert(
+                                widget.widget_id,
+                                FrequencyWidgetState::init(default_time_value, autohide_timer),
+                            );
+                        }
+                        Events => {
+                            event_state_map.insert(widget.widget_id, EventWidgetState::default());
+                        }
+                        Users => {
+                            user_state_map.insert(widget.widget_id, UserWidgetState::default());
                         }
                         _ => {}
                     }
@@ -416,37 +808,6 @@ pub fn build_app(
         None
     };
 
-    let app_config_fields = AppConfigFields {
-        update_rate_in_milliseconds: get_update_rate_in_milliseconds(matches, config)
-            .context("Update 'rate' in your config file.")?,
-        temperature_type: get_temperature(matches, config)
-            .context("Update 'temperature_type' in your config file.")?,
-        show_average_cpu: get_show_average_cpu(matches, config),
-        use_dot: get_use_dot(matches, config),
-        left_legend: get_use_left_legend(matches, config),
-        use_current_cpu_total: get_use_current_cpu_total(matches, config),
-        use_basic_mode,
-        default_time_value,
-        time_interval: get_time_interval(matches, config)
-            .context("Update 'time_delta' in your config file.")?,
-        hide_time: get_hide_time(matches, config),
-        autohide_time,
-        use_old_network_legend: get_use_old_network_legend(matches, config),
-        table_gap: if get_hide_table_gap(matches, config) {
-            0
-        } else {
-            1
-        },
-        disable_click: get_disable_click(matches, config),
-        // no_write: get_no_write(matches, config),
-        no_write: false,
-        show_table_scroll_position: get_show_table_scroll_position(matches, config),
-        is_advanced_kill,
-        network_scale_type,
-        network_unit_type,
-        network_use_binary_prefix,
-    };
-
     let used_widgets = UsedWidgets {
         use_cpu: used_widget_set.get(&Cpu).is_some() || used_widget_set.get(&BasicCpu).is_some(),
         use_mem: used_widget_set.get(&Mem).is_some() || used_widget_set.get(&BasicMem).is_some(),
@@ -455,16 +816,26 @@ pub fn build_app(
         use_disk: used_widget_set.get(&Disk).is_some(),
         use_temp: used_widget_set.get(&Temp).is_some(),
         use_battery: used_widget_set.get(&Battery).is_some(),
+        use_power: used_widget_set.contains(&Power),
+        use_pressure: used_widget_set.contains(&Pressure),
+        use_load_avg: used_widget_set.contains(&LoadAvg),
+        use_cgroup: used_widget_set.contains(&Cgroup),
+        use_latency: used_widget_set.contains(&Latency),
+        use_frequency: used_widget_set.contains(&Frequency),
+        use_events: used_widget_set.contains(&Events),
+        use_system_info: used_widget_set.contains(&SystemInfo),
+        use_users: used_widget_set.contains(&Users),
+        use_top_processes: used_widget_set.contains(&TopProcesses),
+        use_plugins: used_widget_set.contains(&Plugin),
     };
 
-    let disk_filter =
-        get_ignore_list(&config.disk_filter).context("Update 'disk_filter' in your config file")?;
-    let mount_filter = get_ignore_list(&config.mount_filter)
-        .context("Update 'mount_filter' in your config file")?;
-    let temp_filter =
-        get_ignore_list(&config.temp_filter).context("Update 'temp_filter' in your config file")?;
-    let net_filter =
-        get_ignore_list(&config.net_filter).context("Update 'net_filter' in your config file")?;
+    let filters = get_data_filters(config)?;
+    let hooks = get_hooks(config);
+    let key_bindings = get_key_bindings(config, &hooks)?;
+    let alerts = get_alerts(config).context("Update the 'alerts' section in your config file.")?;
+    let derived_metrics = DerivedMetrics::new(&app_config_fields.derived_metrics)
+        .map_err(BottomError::ConfigError)
+        .context("Update the 'derived_metrics' section in your config file.")?;
 
     // One more thing - we have to update the search settings of our proc_state_map, and create the hashmaps if needed!
     // Note that if you change your layout, this might not actually match properly... not sure if/where we should deal with that...
@@ -508,33 +879,253 @@ pub fn build_app(
         }
     }
 
-    Ok(App::builder()
+    // Basic mode just shows each CPU, so there's no need to group them. This machine's CPUs don't
+    // say anything about a remote host's either.
+    let cpu_nodes = if use_basic_mode || matches.is_present("connect") || matches.is_present("ssh")
+    {
+        Vec::new()
+    } else {
+        topology::get_cpu_nodes()
+    };
+    let is_comparing = app_config_fields.compare_offset.is_some();
+
+    let mut app = App::builder()
         .app_config_fields(app_config_fields)
-        .cpu_state(CpuState::init(cpu_state_map))
+        .is_comparing(is_comparing)
+        .cpu_state(CpuState::init(cpu_state_map, cpu_nodes))
         .mem_state(MemState::init(mem_state_map))
         .net_state(NetState::init(net_state_map))
         .proc_state(ProcState::init(proc_state_map))
         .disk_state(DiskState::init(disk_state_map))
         .temp_state(TempState::init(temp_state_map))
         .battery_state(BatteryState::init(battery_state_map))
+        .power_state(PowerState::init(power_state_map))
+        .pressure_state(PressureState::init(pressure_state_map))
+        .load_avg_state(LoadAvgState::init(load_avg_state_map))
+        .cgroup_state(CgroupState::init(cgroup_state_map))
+        .event_state(EventState::init(event_state_map))
+        .user_state(UserState::init(user_state_map))
+        .latency_state(LatencyState::init(latency_state_map))
+        .plugin_state(PluginState::init(plugin_state_map))
+        .frequency_state(FrequencyState::init(frequency_state_map))
         .basic_table_widget_state(basic_table_widget_state)
         .current_widget(widget_map.get(&initial_widget_id).unwrap().clone()) // TODO: [UNWRAP] - many of the unwraps are fine (like this one) but do a once-over and/or switch to expect?
         .widget_map(widget_map)
         .used_widgets(used_widgets)
-        .filters(DataFilters {
-            disk_filter,
-            mount_filter,
-            temp_filter,
-            net_filter,
-        })
+        .filters(filters)
+        .key_bindings(key_bindings)
+        .hooks(hooks)
+        .alerts(alerts)
+        .derived_metrics(derived_metrics)
+        .remote_hosts(
+            matches
+                .values_of("connect")
+                .into_iter()
+                .flatten()
+                .map(|address| RemoteHost::new(address.to_string()))
+                .chain(
+                    matches
+                        .values_of("ssh")
+                        .into_iter()
+                        .flatten()
+                        .map(|destination| RemoteHost::ssh(destination.to_string())),
+                )
+                .collect(),
+        )
         .config(config.clone())
         .config_path(config_path)
-        .build())
+        .build();
+    app.update_process_cpu_history();
+
+    Ok(app)
+}
+
+/// Reads the options that are stored in [`AppConfigFields`]. This is also used when the config
+/// file is reloaded while running.
+pub fn get_app_config_fields(
+    matches: &clap::ArgMatches, config: &Config,
+) -> Result<AppConfigFields> {
+    let update_rate_in_milliseconds = get_update_rate_in_milliseconds(matches, config)
+        .context("Update 'rate' in your config file.")?;
+    let harvest_intervals = get_harvest_intervals(update_rate_in_milliseconds, config)
+        .context("Update the 'refresh_rates' section in your config file.")?;
+    let retention =
+        get_retention(matches, config).context("Update 'retention' in your config file.")?;
+
+    Ok(AppConfigFields {
+        update_rate_in_milliseconds,
+        harvest_intervals,
+        temperature_type: get_temperature(matches, config)
+            .context("Update 'temperature_type' in your config file.")?,
+        temp_thresholds: get_temp_thresholds(config)
+            .context("Update the 'temp_thresholds' section in your config file.")?,
+        labels: get_labels(config),
+        show_average_cpu: get_show_average_cpu(matches, config),
+        use_dot: get_use_dot(matches, config),
+        no_unicode: get_no_unicode(matches, config),
+        screen_reader: get_screen_reader(matches, config),
+        left_legend: get_use_left_legend(matches, config),
+        use_current_cpu_total: get_use_current_cpu_total(matches, config),
+        use_basic_mode: get_use_basic_mode(matches, config),
+        default_time_value: get_default_time_value(matches, config, retention)
+            .context("Update 'default_time_value' in your config file.")?,
+        time_interval: get_time_interval(matches, config, retention)
+            .context("Update 'time_delta' in your config file.")?,
+        retention,
+        hide_time: get_hide_time(matches, config),
+        autohide_time: get_autohide_time(matches, config),
+        use_old_network_legend: get_use_old_network_legend(matches, config),
+        table_gap: if get_hide_table_gap(matches, config) {
+            0
+        } else {
+            1
+        },
+        disable_click: get_disable_click(matches, config),
+        use_termux_profile: get_use_termux_profile(matches, config),
+        // no_write: get_no_write(matches, config),
+        no_write: false,
+        save_layout: get_save_layout(matches, config),
+        show_table_scroll_position: get_show_table_scroll_position(matches, config),
+        show_mini_widgets: get_show_mini_widgets(matches, config),
+        min_row_height: get_min_row_height(matches, config)
+            .context("Update 'min_row_height' in your config file.")?,
+        is_advanced_kill: !get_is_advanced_kill_disabled(matches, config),
+        show_process_memory_details: get_show_process_memory_details(matches, config),
+        precise_process_memory: get_precise_process_memory(matches, config),
+        show_process_gpu: get_show_process_gpu(matches, config),
+        show_disk_smart: get_show_disk_smart(matches, config),
+        show_disk_activity: get_show_disk_activity(matches, config),
+        disk_full_warning: get_disk_full_warning(matches, config)
+            .context("Update 'disk_full_warning' in your config file.")?,
+        show_swap_activity: get_show_swap_activity(matches, config),
+        show_peaks: get_show_peaks(matches, config),
+        compare_offset: get_compare_offset(matches, config, retention)
+            .context("Update 'compare' in your config file.")?,
+        shade_gaps: get_shade_gaps(matches, config),
+        network_scale_type: get_network_scale_type(matches, config),
+        network_unit_type: get_network_unit_type(matches, config),
+        network_use_binary_prefix: get_network_use_binary_prefix(matches, config),
+        show_network_errors: get_show_network_errors(matches, config),
+        network_split: get_network_split(matches, config),
+        show_network_stats: get_show_network_stats(matches, config),
+        stacked_cpu: get_stacked_cpu(matches, config),
+        show_cpu_breakdown: get_show_cpu_breakdown(matches, config),
+        show_cpu_top_process: get_show_cpu_top_process(matches, config),
+        show_graph_stats: get_show_graph_stats(matches, config),
+        show_network_addresses: get_show_network_addresses(matches, config),
+        show_public_ip: get_show_public_ip(matches, config),
+        latency_hosts: get_latency_hosts(config)
+            .context("Update the 'latency_hosts' section in your config file.")?,
+        plugins: get_plugins(config)
+            .context("Update the 'plugins' section in your config file.")?,
+        derived_metrics: get_derived_metrics(config)
+            .context("Update the 'derived_metrics' section in your config file.")?,
+    })
+}
+
+/// Reads the filters for disks, mounts, temperature sensors, and network interfaces.
+pub fn get_data_filters(config: &Config) -> Result<DataFilters> {
+    Ok(DataFilters {
+        disk_filter: get_ignore_list(&config.disk_filter)
+            .context("Update 'disk_filter' in your config file")?,
+        mount_filter: get_ignore_list(&config.mount_filter)
+            .context("Update 'mount_filter' in your config file")?,
+        temp_filter: get_ignore_list(&config.temp_filter)
+            .context("Update 'temp_filter' in your config file")?,
+        net_filter: get_ignore_list(&config.net_filter)
+            .context("Update 'net_filter' in your config file")?,
+    })
+}
+
+/// Reads the key bindings, which are the defaults changed by the `[keybindings]` section, along
+/// with the keys for the given hooks.
+pub fn get_key_bindings(config: &Config, hooks: &[Hook]) -> Result<KeyBindings> {
+    let mut key_bindings = match &config.keybindings {
+        Some(keybindings) => KeyBindings::new(keybindings)
+            .context("Update the 'keybindings' section in your config file")?,
+        None => KeyBindings::default(),
+    };
+    key_bindings
+        .add_hooks(hooks)
+        .context("Update the 'hooks' section in your config file")?;
+
+    Ok(key_bindings)
+}
+
+/// Reads the commands from the `[[hooks]]` section.
+pub fn get_hooks(config: &Config) -> Vec<Hook> {
+    config
+        .hooks
+        .iter()
+        .flatten()
+        .map(|hook| Hook {
+            key: HookKey::parse(&hook.key),
+            command: hook.command.clone(),
+            pause: hook.pause.unwrap_or(false),
+        })
+        .collect()
+}
+
+/// Reads the thresholds from the `[[alerts]]` section.
+pub fn get_alerts(config: &Config) -> error::Result<Vec<Alert>> {
+    config
+        .alerts
+        .iter()
+        .flatten()
+        .map(|alert| {
+            let metric = AlertMetric::parse(&alert.metric)
+                .or_else(|| {
+                    config
+                        .derived_metrics
+                        .iter()
+                        .flatten()
+                        .find(|derived_metric| derived_metric.name == alert.metric)
+                        .map(|derived_metric| AlertMetric::Derived(derived_metric.name.clone()))
+                })
+                .ok_or_else(|| {
+                    BottomError::ConfigError(format!(
+                        "'{}' is not a metric that can be alerted on. Use cpu, memory, swap, temperature, disk, or the name of a derived metric.",
+                        alert.metric
+                    ))
+                })?;
+            if let Some(webhook) = &alert.webhook {
+                parse_webhook_url(webhook).map_err(BottomError::ConfigError)?;
+            }
+
+            Ok(Alert::new(
+                metric,
+                alert.threshold,
+                alert.notify.unwrap_or(false),
+                alert.webhook.clone(),
+            ))
+        })
+        .chain(
+            config
+                .temp_thresholds
+                .as_ref()
+                .and_then(|temp_thresholds| temp_thresholds.alert_on_critical)
+                .filter(|alert_on_critical| *alert_on_critical)
+                .map(|_| Ok(Alert::new(AlertMetric::CriticalTemperature, 0.0, false, None))),
+        )
+        .collect()
 }
 
 pub fn get_widget_layout(
     matches: &clap::ArgMatches, config: &Config,
 ) -> error::Result<(BottomLayout, u64, Option<BottomWidgetType>)> {
+    let layout_names = config.layout_names();
+    if let Some(name) = layout_names
+        .iter()
+        .enumerate()
+        .find(|(index, name)| layout_names[..*index].contains(name))
+        .map(|(_, name)| name)
+    {
+        return Err(error::BottomError::ConfigError(format!(
+            "the layout name '{}' is used more than once.",
+            name
+        )));
+    }
+
     let left_legend = get_use_left_legend(matches, config);
     let (default_widget_type, mut default_widget_count) =
         get_default_widget_and_count(matches, config)?;
@@ -543,7 +1134,10 @@ pub fn get_widget_layout(
     let bottom_layout = if get_use_basic_mode(matches, config) {
         default_widget_id = DEFAULT_WIDGET_ID;
 
-        BottomLayout::init_basic_default(get_use_battery(matches, config))
+        BottomLayout::init_basic_default(
+            get_use_battery(matches, config),
+            &get_basic_widgets(matches, config)?,
+        )
     } else {
         let ref_row: Vec<Row>; // Required to handle reference
         let rows = match &config.row {
@@ -597,6 +1191,163 @@ pub fn get_widget_layout(
     Ok((bottom_layout, default_widget_id, default_widget_type))
 }
 
+/// Copies the ratios of the given [`BottomLayout`] into the config's layout at `layout_index` in
+/// [`Config::layout_names`], then writes the config to `config_path`.
+pub fn save_widget_layout(
+    matches: &clap::ArgMatches, config: &mut Config, config_path: &Path, layout_index: usize,
+    widget_layout: &BottomLayout,
+) -> anyhow::Result<()> {
+    let use_battery = get_use_battery(matches, config);
+    let layout_rows = config
+        .layout_rows_mut(layout_index)
+        .context("The current layout is no longer in the config file.")?;
+    let mut rows = match layout_rows.take() {
+        Some(rows) => rows,
+        None => toml::from_str::<Config>(if use_battery {
+            DEFAULT_BATTERY_LAYOUT
+        } else {
+            DEFAULT_LAYOUT
+        })?
+        .row
+        .unwrap_or_default(),
+    };
+
+    for (row, bottom_row) in rows.iter_mut().zip(&widget_layout.rows) {
+        row.update_ratios(bottom_row);
+    }
+    *layout_rows = Some(rows);
+
+    config.save(config_path)?;
+
+    Ok(())
+}
+
+/// Writes the options that can be changed in the options menu to the flags in the config file.
+pub fn save_options(app: &mut App) -> anyhow::Result<()> {
+    let config_path = app
+        .config_path
+        .clone()
+        .context("There is no config file to save the options to.")?;
+
+    let app_config_fields = &app.app_config_fields;
+    let is_tree = app.are_all_processes_in_mode(|mode| matches!(mode, ProcWidgetMode::Tree { .. }));
+    let is_grouped = app.are_all_processes_in_mode(|mode| matches!(mode, ProcWidgetMode::Grouped));
+
+    let flags = app.config.flags.get_or_insert_with(ConfigFlags::default);
+    flags.temperature_type = Some(
+        match app_config_fields.temperature_type {
+            data_harvester::temperature::TemperatureType::Celsius => "celsius",
+            data_harvester::temperature::TemperatureType::Fahrenheit => "fahrenheit",
+            data_harvester::temperature::TemperatureType::Kelvin => "kelvin",
+        }
+        .to_string(),
+    );
+    flags.network_use_bytes = Some(matches!(
+        app_config_fields.network_unit_type,
+        DataUnit::Byte
+    ));
+    flags.network_use_binary_prefix = Some(app_config_fields.network_use_binary_prefix);
+    flags.dot_marker = Some(app_config_fields.use_dot);
+    flags.tree = Some(is_tree);
+    flags.group_processes = Some(is_grouped);
+
+    app.config.save(&config_path)?;
+
+    Ok(())
+}
+
+/// Rebuilds the [`App`] using the given rows for the current layout, keeping any collected data,
+/// and saves the new layout to the config file if there is one.  Returns the new [`BottomLayout`].
+pub fn apply_layout_edit(
+    matches: &clap::ArgMatches, app: &mut App, rows: Vec<Row>,
+) -> anyhow::Result<BottomLayout> {
+    let mut config = app.config.clone();
+    *config
+        .layout_rows_mut(app.current_layout)
+        .context("The current layout is no longer in the config file.")? = Some(rows);
+
+    let (new_app, widget_layout) = rebuild_app(matches, app, config, app.current_layout)?;
+
+    if let Some(config_path) = &new_app.config_path {
+        new_app.config.save(config_path)?;
+    }
+    let previous_app = std::mem::replace(app, new_app);
+    app.restore_edited_layout_state(previous_app.into_layout_state());
+
+    Ok(widget_layout)
+}
+
+/// Switches to the layout at `layout_index` in [`Config::layout_names`], keeping any collected
+/// data.  The widget states of the current layout are saved, and restored if it's switched back
+/// to later.  Returns the new [`BottomLayout`].
+pub fn switch_layout(
+    matches: &clap::ArgMatches, app: &mut App, layout_index: usize,
+) -> anyhow::Result<BottomLayout> {
+    let layout_names = app.config.layout_names();
+    let previous_name = layout_names
+        .get(app.current_layout)
+        .map(|name| name.to_string());
+    let name = layout_names
+        .get(layout_index)
+        .context("There is no layout to switch to.")?
+        .to_string();
+
+    let (mut new_app, widget_layout) = rebuild_app(matches, app, app.config.clone(), layout_index)
+        .with_context(|| format!("Unable to switch to the '{}' layout.", name))?;
+    let saved_state = new_app.saved_layout_states.remove(&name);
+
+    let previous_app = std::mem::replace(app, new_app);
+    if let Some(previous_name) = previous_name {
+        app.saved_layout_states
+            .insert(previous_name, previous_app.into_layout_state());
+    }
+    if let Some(saved_state) = saved_state {
+        app.restore_layout_state(saved_state);
+    }
+    app.set_status_message(format!("Switched to the '{}' layout", name));
+
+    Ok(widget_layout)
+}
+
+/// Builds a new [`App`] showing the layout at `layout_index` in `config`, moving over any
+/// collected data and options changed while running from `app`.
+fn rebuild_app(
+    matches: &clap::ArgMatches, app: &mut App, config: Config, layout_index: usize,
+) -> anyhow::Result<(App, BottomLayout)> {
+    // The rest of the options are built from the top-level rows, so the layout's rows are
+    // swapped in for those.
+    let mut layout_config = config.clone();
+    layout_config.row = config
+        .layout_rows(layout_index)
+        .cloned()
+        .context("There is no layout to switch to.")?;
+
+    let (widget_layout, default_widget_id, default_widget_type_option) =
+        get_widget_layout(matches, &layout_config)?;
+    let mut new_app = build_app(
+        matches,
+        &mut layout_config,
+        &widget_layout,
+        default_widget_id,
+        &default_widget_type_option,
+        app.config_path.clone(),
+    )?;
+
+    new_app.config = config;
+    new_app.app_config_fields = app.app_config_fields.clone();
+    new_app.data_collection = std::mem::take(&mut app.data_collection);
+    new_app.update_process_cpu_history();
+    new_app.converted_data = std::mem::take(&mut app.converted_data);
+    new_app.saved_layout_states = std::mem::take(&mut app.saved_layout_states);
+    new_app.current_layout = layout_index;
+    new_app.is_frozen = app.is_frozen;
+    new_app.is_paused = app.is_paused;
+    new_app.is_showing_debug_overlay = app.is_showing_debug_overlay;
+    new_app.is_force_redraw = true;
+
+    Ok((new_app, widget_layout))
+}
+
 fn get_update_rate_in_milliseconds(
     matches: &clap::ArgMatches, config: &Config,
 ) -> error::Result<u64> {
@@ -625,30 +1376,146 @@ fn get_update_rate_in_milliseconds(
     Ok(update_rate_in_milliseconds)
 }
 
-fn get_temperature(
-    matches: &clap::ArgMatches, config: &Config,
-) -> error::Result<data_harvester::temperature::TemperatureType> {
-    if matches.is_present("fahrenheit") {
-        return Ok(data_harvester::temperature::TemperatureType::Fahrenheit);
-    } else if matches.is_present("kelvin") {
-        return Ok(data_harvester::temperature::TemperatureType::Kelvin);
-    } else if matches.is_present("celsius") {
-        return Ok(data_harvester::temperature::TemperatureType::Celsius);
-    } else if let Some(flags) = &config.flags {
-        if let Some(temp_type) = &flags.temperature_type {
-            // Give lowest priority to config.
-            return match temp_type.as_str() {
-                "fahrenheit" | "f" => Ok(data_harvester::temperature::TemperatureType::Fahrenheit),
-                "kelvin" | "k" => Ok(data_harvester::temperature::TemperatureType::Kelvin),
-                "celsius" | "c" => Ok(data_harvester::temperature::TemperatureType::Celsius),
-                _ => Err(BottomError::ConfigError(format!(
-                    "\"{}\" is an invalid temperature type, use \"<kelvin|k|celsius|c|fahrenheit|f>\".",
-                    temp_type
-                ))),
-            };
+fn get_harvest_intervals(
+    update_rate_in_milliseconds: u64, config: &Config,
+) -> error::Result<HarvestIntervals> {
+    let mut harvest_intervals = HarvestIntervals::new(update_rate_in_milliseconds);
+    harvest_intervals.set_interval(
+        HarvestType::Events,
+        update_rate_in_milliseconds.max(DEFAULT_EVENTS_REFRESH_RATE_IN_MILLISECONDS),
+    );
+
+    if let Some(refresh_rates) = &config.refresh_rates {
+        for (name, harvest_type, rate) in &[
+            ("cpu", HarvestType::Cpu, refresh_rates.cpu),
+            ("memory", HarvestType::Memory, refresh_rates.memory),
+            ("network", HarvestType::Network, refresh_rates.network),
+            ("processes", HarvestType::Processes, refresh_rates.processes),
+            (
+                "temperature",
+                HarvestType::Temperature,
+                refresh_rates.temperature,
+            ),
+            ("disk", HarvestType::Disk, refresh_rates.disk),
+            ("battery", HarvestType::Battery, refresh_rates.battery),
+            ("power", HarvestType::Power, refresh_rates.power),
+            ("pressure", HarvestType::Pressure, refresh_rates.pressure),
+            ("cgroup", HarvestType::Cgroup, refresh_rates.cgroup),
+            ("latency", HarvestType::Latency, refresh_rates.latency),
+            ("frequency", HarvestType::Frequency, refresh_rates.frequency),
+            ("events", HarvestType::Events, refresh_rates.events),
+            (
+                "system_info",
+                HarvestType::SystemInfo,
+                refresh_rates.system_info,
+            ),
+            ("users", HarvestType::Users, refresh_rates.users),
+            ("plugins", HarvestType::Plugins, refresh_rates.plugins),
+        ] {
+            if let Some(rate) = rate {
+                if *rate < 250 {
+                    return Err(BottomError::ConfigError(format!(
+                        "set the refresh rate for \"{}\" to be at least 250 milliseconds.",
+                        name
+                    )));
+                }
+                harvest_intervals.set_interval(*harvest_type, *rate);
+            }
         }
     }
-    Ok(data_harvester::temperature::TemperatureType::Celsius)
+
+    Ok(harvest_intervals)
+}
+
+fn get_temp_thresholds(config: &Config) -> error::Result<TempThresholds> {
+    fn get_threshold(name: &str, warning: f32, critical: f32) -> error::Result<TempThreshold> {
+        if warning < critical {
+            Ok(TempThreshold { warning, critical })
+        } else {
+            Err(BottomError::ConfigError(format!(
+                "the warning temperature for {} must be lower than the critical temperature.",
+                name
+            )))
+        }
+    }
+
+    let mut temp_thresholds = TempThresholds::default();
+
+    if let Some(config_thresholds) = &config.temp_thresholds {
+        temp_thresholds.default = match (config_thresholds.warning, config_thresholds.critical) {
+            (Some(warning), Some(critical)) => {
+                Some(get_threshold("all sensors", warning, critical)?)
+            }
+            (None, None) => None,
+            _ => {
+                return Err(BottomError::ConfigError(
+                    "set both a warning and a critical temperature.".to_string(),
+                ));
+            }
+        };
+
+        if let Some(sensors) = &config_thresholds.sensors {
+            for (sensor, threshold) in sensors {
+                temp_thresholds.sensors.insert(
+                    sensor.clone(),
+                    get_threshold(
+                        &format!("\"{}\"", sensor),
+                        threshold.warning,
+                        threshold.critical,
+                    )?,
+                );
+            }
+        }
+
+        if config_thresholds.alert_on_critical == Some(true)
+            && temp_thresholds.default.is_none()
+            && temp_thresholds.sensors.is_empty()
+        {
+            return Err(BottomError::ConfigError(
+                "set a critical temperature to alert on with alert_on_critical.".to_string(),
+            ));
+        }
+    }
+
+    Ok(temp_thresholds)
+}
+
+fn get_labels(config: &Config) -> Labels {
+    match &config.labels {
+        Some(labels) => Labels {
+            temperature: labels.temperature.clone().unwrap_or_default(),
+            disk: labels.disk.clone().unwrap_or_default(),
+            network: labels.network.clone().unwrap_or_default(),
+            hidden: labels.hidden.iter().flatten().cloned().collect(),
+        },
+        None => Labels::default(),
+    }
+}
+
+fn get_temperature(
+    matches: &clap::ArgMatches, config: &Config,
+) -> error::Result<data_harvester::temperature::TemperatureType> {
+    if matches.is_present("fahrenheit") {
+        return Ok(data_harvester::temperature::TemperatureType::Fahrenheit);
+    } else if matches.is_present("kelvin") {
+        return Ok(data_harvester::temperature::TemperatureType::Kelvin);
+    } else if matches.is_present("celsius") {
+        return Ok(data_harvester::temperature::TemperatureType::Celsius);
+    } else if let Some(flags) = &config.flags {
+        if let Some(temp_type) = &flags.temperature_type {
+            // Give lowest priority to config.
+            return match temp_type.as_str() {
+                "fahrenheit" | "f" => Ok(data_harvester::temperature::TemperatureType::Fahrenheit),
+                "kelvin" | "k" => Ok(data_harvester::temperature::TemperatureType::Kelvin),
+                "celsius" | "c" => Ok(data_harvester::temperature::TemperatureType::Celsius),
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{}\" is an invalid temperature type, use \"<kelvin|k|celsius|c|fahrenheit|f>\".",
+                    temp_type
+                ))),
+            };
+        }
+    }
+    Ok(data_harvester::temperature::TemperatureType::Celsius)
 }
 
 /// Yes, this function gets whether to show average CPU (true) or not (false)
@@ -675,6 +1542,28 @@ fn get_use_dot(matches: &clap::ArgMatches, config: &Config) -> bool {
     false
 }
 
+fn get_no_unicode(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("no_unicode") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(no_unicode) = flags.no_unicode {
+            return no_unicode;
+        }
+    }
+    false
+}
+
+fn get_screen_reader(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("screen_reader") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(screen_reader) = flags.screen_reader {
+            return screen_reader;
+        }
+    }
+    false
+}
+
 fn get_use_left_legend(matches: &clap::ArgMatches, config: &Config) -> bool {
     if matches.is_present("left_legend") {
         return true;
@@ -699,6 +1588,45 @@ fn get_use_current_cpu_total(matches: &clap::ArgMatches, config: &Config) -> boo
     false
 }
 
+/// Returns which summary rows to show in basic mode, which are all of them by default.
+fn get_basic_widgets(
+    matches: &clap::ArgMatches, config: &Config,
+) -> error::Result<Vec<BottomWidgetType>> {
+    let widget_names: Vec<String> = if let Some(basic_widgets) = matches.value_of("basic_widgets") {
+        basic_widgets
+            .split(',')
+            .map(|name| name.trim().to_string())
+            .collect()
+    } else if let Some(basic_widgets) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.basic_widgets.as_ref())
+    {
+        basic_widgets.clone()
+    } else {
+        return Ok(vec![
+            BottomWidgetType::Cpu,
+            BottomWidgetType::Mem,
+            BottomWidgetType::Net,
+        ]);
+    };
+
+    widget_names
+        .iter()
+        .map(|name| match name.parse::<BottomWidgetType>() {
+            Ok(
+                widget_type @ (BottomWidgetType::Cpu
+                | BottomWidgetType::Mem
+                | BottomWidgetType::Net),
+            ) => Ok(widget_type),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" can't be shown in basic mode, only cpu, mem, and net can be set in basic_widgets.",
+                name
+            ))),
+        })
+        .collect()
+}
+
 fn get_use_basic_mode(matches: &clap::ArgMatches, config: &Config) -> bool {
     if matches.is_present("basic") {
         return true;
@@ -711,7 +1639,9 @@ fn get_use_basic_mode(matches: &clap::ArgMatches, config: &Config) -> bool {
     false
 }
 
-fn get_default_time_value(matches: &clap::ArgMatches, config: &Config) -> error::Result<u64> {
+fn get_default_time_value(
+    matches: &clap::ArgMatches, config: &Config, retention: u64,
+) -> error::Result<u64> {
     let default_time = if let Some(default_time_value) = matches.value_of("default_time_value") {
         default_time_value.parse::<u64>().map_err(|_| {
             BottomError::ConfigError(
@@ -732,17 +1662,19 @@ fn get_default_time_value(matches: &clap::ArgMatches, config: &Config) -> error:
         return Err(BottomError::ConfigError(
             "set your default value to be at least 30000 milliseconds.".to_string(),
         ));
-    } else if default_time > STALE_MAX_MILLISECONDS {
+    } else if default_time > retention {
         return Err(BottomError::ConfigError(format!(
-            "set your default value to be at most {} milliseconds.",
-            STALE_MAX_MILLISECONDS
+            "set your default value to be at most {} milliseconds, the retention period.",
+            retention
         )));
     }
 
     Ok(default_time)
 }
 
-fn get_time_interval(matches: &clap::ArgMatches, config: &Config) -> error::Result<u64> {
+fn get_time_interval(
+    matches: &clap::ArgMatches, config: &Config, retention: u64,
+) -> error::Result<u64> {
     let time_interval = if let Some(time_interval) = matches.value_of("time_delta") {
         time_interval.parse::<u64>().map_err(|_| {
             BottomError::ConfigError(
@@ -763,16 +1695,67 @@ fn get_time_interval(matches: &clap::ArgMatches, config: &Config) -> error::Resu
         return Err(BottomError::ConfigError(
             "set your time delta to be at least 1000 milliseconds.".to_string(),
         ));
-    } else if time_interval > STALE_MAX_MILLISECONDS {
+    } else if time_interval > retention {
         return Err(BottomError::ConfigError(format!(
-            "set your time delta to be at most {} milliseconds.",
-            STALE_MAX_MILLISECONDS
+            "set your time delta to be at most {} milliseconds, the retention period.",
+            retention
         )));
     }
 
     Ok(time_interval)
 }
 
+fn get_retention(matches: &clap::ArgMatches, config: &Config) -> error::Result<u64> {
+    let retention = if let Some(retention) = matches.value_of("retention") {
+        parse_duration(retention)?
+    } else if let Some(retention) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.retention.as_ref())
+    {
+        parse_duration(retention)?
+    } else {
+        STALE_MAX_MILLISECONDS
+    };
+
+    if retention < STALE_MIN_MILLISECONDS {
+        Err(BottomError::ConfigError(
+            "set your retention to be at least 30s.".to_string(),
+        ))
+    } else if retention > MAX_RETENTION_MILLISECONDS {
+        Err(BottomError::ConfigError(
+            "set your retention to be at most 1d.".to_string(),
+        ))
+    } else {
+        Ok(retention)
+    }
+}
+
+/// Parses a duration like `90s`, `10m`, `6h`, or `1d` into milliseconds. A plain number is taken
+/// to already be in milliseconds.
+fn parse_duration(duration: &str) -> error::Result<u64> {
+    let duration = duration.trim();
+    let (number, multiplier) = match duration.char_indices().last() {
+        Some((index, 's')) => (&duration[..index], 1000),
+        Some((index, 'm')) => (&duration[..index], 60 * 1000),
+        Some((index, 'h')) => (&duration[..index], 60 * 60 * 1000),
+        Some((index, 'd')) => (&duration[..index], 24 * 60 * 60 * 1000),
+        _ => (duration, 1),
+    };
+
+    number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+        .ok_or_else(|| {
+            BottomError::ConfigError(format!(
+                "could not parse \"{}\" as a time, like 10m or 6h.",
+                duration
+            ))
+        })
+}
+
 pub fn get_app_grouping(matches: &clap::ArgMatches, config: &Config) -> bool {
     if matches.is_present("group") {
         return true;
@@ -901,6 +1884,19 @@ fn get_disable_click(matches: &clap::ArgMatches, config: &Config) -> bool {
     false
 }
 
+fn get_use_termux_profile(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("termux") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(termux) = flags.termux {
+            return termux;
+        }
+    }
+
+    // Termux sets this in its shell.
+    std::env::var_os("TERMUX_VERSION").is_some()
+}
+
 fn get_use_old_network_legend(matches: &clap::ArgMatches, config: &Config) -> bool {
     if matches.is_present("use_old_network_legend") {
         return true;
@@ -923,6 +1919,16 @@ fn get_hide_table_gap(matches: &clap::ArgMatches, config: &Config) -> bool {
     false
 }
 
+/// Returns the token that an agent requires, or that is sent to agents, from `--token` or
+/// [`remote::TOKEN_VAR`]. Empty tokens count as none being given.
+pub fn get_remote_token(matches: &clap::ArgMatches) -> Option<String> {
+    matches
+        .value_of("token")
+        .map(str::to_string)
+        .or_else(|| std::env::var(remote::TOKEN_VAR).ok())
+        .filter(|token| !token.is_empty())
+}
+
 fn get_use_battery(matches: &clap::ArgMatches, config: &Config) -> bool {
     if cfg!(feature = "battery") {
         if matches.is_present("battery") {
@@ -948,6 +1954,17 @@ fn get_no_write(matches: &clap::ArgMatches, config: &Config) -> bool {
     false
 }
 
+fn get_save_layout(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("save_layout") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(save_layout) = flags.save_layout {
+            return save_layout;
+        }
+    }
+    false
+}
+
 fn get_ignore_list(ignore_list: &Option<IgnoreList>) -> error::Result<Option<Filter>> {
     if let Some(ignore_list) = ignore_list {
         let list: Result<Vec<_>, _> = ignore_list
@@ -1012,6 +2029,20 @@ pub fn get_color_scheme(
     Ok(ColourScheme::Default)
 }
 
+pub fn get_graph_renderer(
+    matches: &clap::ArgMatches, config: &Config,
+) -> error::Result<GraphRenderer> {
+    if let Some(graph_renderer) = matches.value_of("graph_renderer") {
+        return GraphRenderer::from_str(graph_renderer);
+    } else if let Some(flags) = &config.flags {
+        if let Some(graph_renderer) = &flags.graph_renderer {
+            return GraphRenderer::from_str(graph_renderer);
+        }
+    }
+
+    Ok(GraphRenderer::Text)
+}
+
 fn get_mem_as_value(matches: &clap::ArgMatches, config: &Config) -> bool {
     if matches.is_present("mem_as_value") {
         return true;
@@ -1045,6 +2076,31 @@ fn get_show_table_scroll_position(matches: &clap::ArgMatches, config: &Config) -
     false
 }
 
+fn get_show_mini_widgets(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("mini_widgets") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(mini_widgets) = flags.mini_widgets {
+            return mini_widgets;
+        }
+    }
+    false
+}
+
+fn get_min_row_height(matches: &clap::ArgMatches, config: &Config) -> error::Result<u16> {
+    if let Some(min_row_height) = matches.value_of("min_row_height") {
+        min_row_height.parse::<u16>().map_err(|_| {
+            BottomError::ConfigError(
+                "could not parse as a valid 16-bit unsigned integer".to_string(),
+            )
+        })
+    } else if let Some(flags) = &config.flags {
+        Ok(flags.min_row_height.unwrap_or(0))
+    } else {
+        Ok(0)
+    }
+}
+
 fn get_is_default_process_command(matches: &clap::ArgMatches, config: &Config) -> bool {
     if matches.is_present("process_command") {
         return true;
@@ -1056,6 +2112,97 @@ fn get_is_default_process_command(matches: &clap::ArgMatches, config: &Config) -
     false
 }
 
+fn get_show_process_sparkline(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("process_sparkline") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_sparkline) = flags.process_sparkline {
+            return process_sparkline;
+        }
+    }
+    false
+}
+
+fn get_show_process_memory_details(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("process_memory_details") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_memory_details) = flags.process_memory_details {
+            return process_memory_details;
+        }
+    }
+    false
+}
+
+fn get_show_process_gpu(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("process_gpu") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_gpu) = flags.process_gpu {
+            return process_gpu;
+        }
+    }
+    false
+}
+
+fn get_show_process_start_time(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("process_start_time") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_start_time) = flags.process_start_time {
+            return process_start_time;
+        }
+    }
+    false
+}
+
+fn get_show_process_systemd_unit(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("process_systemd_unit") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_systemd_unit) = flags.process_systemd_unit {
+            return process_systemd_unit;
+        }
+    }
+    false
+}
+
+/// Returns whether process start times are shown as how long ago they were (true), or as a date
+/// and time (false).
+fn get_is_relative_start_time(matches: &clap::ArgMatches, config: &Config) -> error::Result<bool> {
+    let format = if let Some(format) = matches.value_of("process_start_time_format") {
+        format
+    } else if let Some(format) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.process_start_time_format.as_deref())
+    {
+        format
+    } else {
+        return Ok(false);
+    };
+
+    match format {
+        "relative" => Ok(true),
+        "absolute" => Ok(false),
+        _ => Err(BottomError::ConfigError(format!(
+            "\"{}\" is an invalid process start time format, use \"<relative|absolute>\".",
+            format
+        ))),
+    }
+}
+
+fn get_precise_process_memory(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("precise_process_memory") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(precise_process_memory) = flags.precise_process_memory {
+            return precise_process_memory;
+        }
+    }
+    false
+}
+
 fn get_is_advanced_kill_disabled(matches: &clap::ArgMatches, config: &Config) -> bool {
     if matches.is_present("disable_advanced_kill") {
         return true;
@@ -1067,6 +2214,367 @@ fn get_is_advanced_kill_disabled(matches: &clap::ArgMatches, config: &Config) ->
     false
 }
 
+fn get_show_disk_inodes(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("disk_inodes") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(disk_inodes) = flags.disk_inodes {
+            return disk_inodes;
+        }
+    }
+    false
+}
+
+fn get_show_disk_smart(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("disk_smart") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(disk_smart) = flags.disk_smart {
+            return disk_smart;
+        }
+    }
+    false
+}
+
+fn get_show_disk_activity(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("disk_activity") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(disk_activity) = flags.disk_activity {
+            return disk_activity;
+        }
+    }
+    false
+}
+
+fn get_show_disk_full_estimate(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("disk_full_estimate") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(disk_full_estimate) = flags.disk_full_estimate {
+            return disk_full_estimate;
+        }
+    }
+    false
+}
+
+fn get_disk_full_warning(matches: &clap::ArgMatches, config: &Config) -> error::Result<u64> {
+    if let Some(disk_full_warning) = matches.value_of("disk_full_warning") {
+        parse_duration(disk_full_warning)
+    } else if let Some(disk_full_warning) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.disk_full_warning.as_ref())
+    {
+        parse_duration(disk_full_warning)
+    } else {
+        Ok(DEFAULT_DISK_FULL_WARNING_MILLISECONDS)
+    }
+}
+
+fn get_show_swap_activity(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("swap_activity") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(swap_activity) = flags.swap_activity {
+            return swap_activity;
+        }
+    }
+    false
+}
+
+fn get_show_peaks(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("peaks") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(peaks) = flags.peaks {
+            return peaks;
+        }
+    }
+    false
+}
+
+/// Returns how far back the comparison overlay on graphs is from, in milliseconds, if set.
+fn get_compare_offset(
+    matches: &clap::ArgMatches, config: &Config, retention: u64,
+) -> error::Result<Option<u64>> {
+    let compare_offset = if let Some(compare) = matches.value_of("compare") {
+        parse_duration(compare)?
+    } else if let Some(compare) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.compare.as_ref())
+    {
+        parse_duration(compare)?
+    } else {
+        return Ok(None);
+    };
+
+    if compare_offset == 0 || compare_offset >= retention {
+        Err(BottomError::ConfigError(
+            "set compare to be more than 0 and less than the retention, so there's data to compare with."
+                .to_string(),
+        ))
+    } else {
+        Ok(Some(compare_offset))
+    }
+}
+
+fn get_shade_gaps(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("shade_gaps") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(shade_gaps) = flags.shade_gaps {
+            return shade_gaps;
+        }
+    }
+    false
+}
+
+fn get_show_network_errors(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("network_errors") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(network_errors) = flags.network_errors {
+            return network_errors;
+        }
+    }
+    false
+}
+
+fn get_network_split(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("network_split") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(network_split) = flags.network_split {
+            return network_split;
+        }
+    }
+    false
+}
+
+fn get_show_network_stats(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("network_stats") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(network_stats) = flags.network_stats {
+            return network_stats;
+        }
+    }
+    false
+}
+
+fn get_stacked_cpu(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("stacked_cpu") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(stacked_cpu) = flags.stacked_cpu {
+            return stacked_cpu;
+        }
+    }
+    false
+}
+
+fn get_show_cpu_breakdown(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("cpu_breakdown") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(cpu_breakdown) = flags.cpu_breakdown {
+            return cpu_breakdown;
+        }
+    }
+    false
+}
+
+fn get_show_cpu_top_process(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("cpu_top_process") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(cpu_top_process) = flags.cpu_top_process {
+            return cpu_top_process;
+        }
+    }
+    false
+}
+
+fn get_show_graph_stats(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("graph_stats") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(graph_stats) = flags.graph_stats {
+            return graph_stats;
+        }
+    }
+    false
+}
+
+fn get_show_network_addresses(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("network_addresses") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(network_addresses) = flags.network_addresses {
+            return network_addresses;
+        }
+    }
+    false
+}
+
+fn get_show_public_ip(matches: &clap::ArgMatches, config: &Config) -> bool {
+    if matches.is_present("public_ip") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(public_ip) = flags.public_ip {
+            return public_ip;
+        }
+    }
+    false
+}
+
+fn get_latency_hosts(config: &Config) -> error::Result<Vec<LatencyHost>> {
+    if let Some(latency_hosts) = &config.latency_hosts {
+        if latency_hosts.iter().any(|host| host.host.trim().is_empty()) {
+            return Err(BottomError::ConfigError(
+                "set a non-empty \"host\" for every latency host.".to_string(),
+            ));
+        }
+
+        Ok(latency_hosts.clone())
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+fn get_plugins(config: &Config) -> error::Result<Vec<PluginConfig>> {
+    if let Some(plugins) = &config.plugins {
+        if plugins.iter().any(|plugin| plugin.name.trim().is_empty()) {
+            return Err(BottomError::ConfigError(
+                "set a non-empty \"name\" for every plugin.".to_string(),
+            ));
+        }
+        if plugins
+            .iter()
+            .any(|plugin| plugin.command.trim().is_empty())
+        {
+            return Err(BottomError::ConfigError(
+                "set a non-empty \"command\" for every plugin.".to_string(),
+            ));
+        }
+        for (index, plugin) in plugins.iter().enumerate() {
+            if plugins[..index]
+                .iter()
+                .any(|other| other.name == plugin.name)
+            {
+                return Err(BottomError::ConfigError(format!(
+                    "there's more than one plugin named \"{}\".",
+                    plugin.name
+                )));
+            }
+        }
+
+        Ok(plugins.clone())
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+fn get_derived_metrics(config: &Config) -> error::Result<Vec<DerivedMetricConfig>> {
+    if let Some(derived_metrics) = &config.derived_metrics {
+        if !cfg!(feature = "derived_metrics") && !derived_metrics.is_empty() {
+            return Err(BottomError::ConfigError(
+                "derived metrics need bottom to be built with the \"derived_metrics\" feature."
+                    .to_string(),
+            ));
+        }
+        if derived_metrics
+            .iter()
+            .any(|derived_metric| derived_metric.name.trim().is_empty())
+        {
+            return Err(BottomError::ConfigError(
+                "set a non-empty \"name\" for every derived metric.".to_string(),
+            ));
+        }
+        if derived_metrics
+            .iter()
+            .any(|derived_metric| derived_metric.expression.trim().is_empty())
+        {
+            return Err(BottomError::ConfigError(
+                "set a non-empty \"expression\" for every derived metric.".to_string(),
+            ));
+        }
+        for (index, derived_metric) in derived_metrics.iter().enumerate() {
+            if derived_metrics[..index]
+                .iter()
+                .any(|other| other.name == derived_metric.name)
+                || config
+                    .plugins
+                    .iter()
+                    .flatten()
+                    .any(|plugin| plugin.name == derived_metric.name)
+            {
+                return Err(BottomError::ConfigError(format!(
+                    "there's more than one plugin or derived metric named \"{}\".",
+                    derived_metric.name
+                )));
+            }
+        }
+
+        Ok(derived_metrics.clone())
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// Checks that a plugin widget shows a plugin or derived metric that's in the config file.
+fn check_plugin_exists(plugin: &str, app_config_fields: &AppConfigFields) -> error::Result<()> {
+    if app_config_fields
+        .plugins
+        .iter()
+        .any(|config| config.name == plugin)
+        || app_config_fields
+            .derived_metrics
+            .iter()
+            .any(|config| config.name == plugin)
+    {
+        Ok(())
+    } else {
+        Err(BottomError::ConfigError(format!(
+            "a plugin widget shows \"{}\", but there's no plugin or derived metric with that name.",
+            plugin
+        )))
+    }
+}
+
+fn get_saved_filters(
+    config: &Config, is_case_sensitive: bool, is_match_whole_word: bool, is_use_regex: bool,
+) -> error::Result<Vec<SavedFilter>> {
+    if let Some(process_filters) = &config.process_filters {
+        process_filters
+            .iter()
+            .map(|filter| {
+                let query = parse_query(
+                    &filter.query,
+                    is_match_whole_word,
+                    !is_case_sensitive,
+                    is_use_regex,
+                )
+                .map_err(|err| {
+                    BottomError::ConfigError(format!(
+                        "the query for the process filter \"{}\" is invalid: {}",
+                        filter.name, err
+                    ))
+                })?;
+
+                Ok(SavedFilter {
+                    name: filter.name.clone(),
+                    query,
+                })
+            })
+            .collect()
+    } else {
+        Ok(Vec::new())
+    }
+}
+
 fn get_network_unit_type(matches: &clap::ArgMatches, config: &Config) -> DataUnit {
     if matches.is_present("network_use_bytes") {
         return DataUnit::Byte;
@@ -1105,3 +2613,164 @@ fn get_network_use_binary_prefix(matches: &clap::ArgMatches, config: &Config) ->
     }
     false
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_derived_metrics_need_feature() {
+        let config: Config = toml::from_str(
+            "[[derived_metrics]]\nname = \"doubled\"\nexpression = \"cpu.total * 2\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            get_derived_metrics(&config).is_ok(),
+            cfg!(feature = "derived_metrics")
+        );
+        assert!(get_derived_metrics(&Config::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_alert_on_critical() {
+        let config: Config = toml::from_str(
+            "[temp_thresholds]\nwarning = 70.0\ncritical = 90.0\nalert_on_critical = true\n",
+        )
+        .unwrap();
+        let alerts = get_alerts(&config).unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].metric, AlertMetric::CriticalTemperature);
+
+        // There's nothing to alert on without a critical temperature.
+        let config: Config =
+            toml::from_str("[temp_thresholds]\nalert_on_critical = true\n").unwrap();
+        assert!(get_temp_thresholds(&config).is_err());
+        assert!(get_alerts(&Config::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_events_refresh_rate() {
+        let harvest_intervals = get_harvest_intervals(1000, &Config::default()).unwrap();
+        assert_eq!(harvest_intervals.get_interval(HarvestType::Cpu), 1000);
+        assert_eq!(harvest_intervals.get_interval(HarvestType::Events), 30000);
+        assert_eq!(harvest_intervals.get_shortest_interval(), 1000);
+
+        let harvest_intervals = get_harvest_intervals(60000, &Config::default()).unwrap();
+        assert_eq!(harvest_intervals.get_interval(HarvestType::Events), 60000);
+
+        let config: Config = toml::from_str("[refresh_rates]\nevents = 5000\n").unwrap();
+        let harvest_intervals = get_harvest_intervals(1000, &config).unwrap();
+        assert_eq!(harvest_intervals.get_interval(HarvestType::Events), 5000);
+    }
+
+    #[test]
+    fn test_layout_edit_keeps_widget_states() {
+        let matches = crate::clap::build_app().get_matches_from(["btm"]);
+        let mut config: Config = toml::from_str(DEFAULT_LAYOUT).unwrap();
+        let (widget_layout, default_widget_id, default_widget_type_option) =
+            get_widget_layout(&matches, &config).unwrap();
+        let mut app = build_app(
+            &matches,
+            &mut config,
+            &widget_layout,
+            default_widget_id,
+            &default_widget_type_option,
+            None,
+        )
+        .unwrap();
+
+        let get_proc_id = |app: &App| *app.proc_state.widget_states.keys().next().unwrap();
+        let previous_proc_id = get_proc_id(&app);
+        app.proc_state
+            .get_mut_widget_state(previous_proc_id)
+            .unwrap()
+            .proc_search
+            .search_state
+            .current_search_query = "firefox".to_string();
+
+        // Removing the CPU row moves the process widget.
+        let mut rows = app.config.row.clone().unwrap();
+        rows.remove(0);
+        apply_layout_edit(&matches, &mut app, rows).unwrap();
+
+        let proc_id = get_proc_id(&app);
+        assert_ne!(proc_id, previous_proc_id);
+        assert_eq!(app.current_widget.widget_id, proc_id);
+        assert_eq!(
+            app.proc_state
+                .get_widget_state(proc_id)
+                .unwrap()
+                .proc_search
+                .search_state
+                .current_search_query,
+            "firefox"
+        );
+    }
+
+    #[test]
+    fn test_save_config() {
+        let config_dir =
+            std::env::temp_dir().join(format!("btm_save_config_{}", std::process::id()));
+        fs::create_dir_all(&config_dir).unwrap();
+        let config_path = config_dir.join("bottom.toml");
+        fs::write(&config_path, "# A comment\n[flags]\ntree = false\n").unwrap();
+
+        let mut config: Config =
+            toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        config.flags.as_mut().unwrap().tree = Some(true);
+        config.save(&config_path).unwrap();
+
+        let saved = fs::read_to_string(&config_path).unwrap();
+        let saved_config: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(saved_config.flags.unwrap().tree, Some(true));
+        assert!(!saved.contains("# A comment"));
+        assert_eq!(fs::read_dir(&config_dir).unwrap().count(), 1);
+
+        // Saving through a symlink replaces the file it links to, and keeps the link.
+        #[cfg(target_family = "unix")]
+        {
+            let link_path = config_dir.join("link.toml");
+            std::os::unix::fs::symlink(&config_path, &link_path).unwrap();
+            config.flags.as_mut().unwrap().tree = Some(false);
+            config.save(&link_path).unwrap();
+
+            assert!(fs::symlink_metadata(&link_path)
+                .unwrap()
+                .file_type()
+                .is_symlink());
+            let saved_config: Config =
+                toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+            assert_eq!(saved_config.flags.unwrap().tree, Some(false));
+        }
+
+        let _ = fs::remove_dir_all(config_dir);
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90s").unwrap(), 90 * 1000);
+        assert_eq!(parse_duration("10m").unwrap(), 10 * 60 * 1000);
+        assert_eq!(parse_duration("6h").unwrap(), 6 * 60 * 60 * 1000);
+        assert_eq!(parse_duration("1d").unwrap(), 24 * 60 * 60 * 1000);
+
+        // Without a unit, it's in milliseconds.
+        assert_eq!(parse_duration("1500").unwrap(), 1500);
+        assert_eq!(parse_duration("0").unwrap(), 0);
+
+        assert_eq!(parse_duration("  10m ").unwrap(), 10 * 60 * 1000);
+        assert_eq!(parse_duration("10 m").unwrap(), 10 * 60 * 1000);
+
+        // This is just under the limit...
+        assert_eq!(
+            parse_duration("99999999999d").unwrap(),
+            99_999_999_999 * 24 * 60 * 60 * 1000
+        );
+        // ...while these overflow.
+        assert!(parse_duration("999999999999d").is_err());
+        assert!(parse_duration("18446744073709551616").is_err());
+
+        for garbage in &["", "s", "10x", "ten minutes", "-5m", "1.5h", "10ms", "m10"] {
+            assert!(parse_duration(garbage).is_err(), "{:?}", garbage);
+        }
+    }
+}
@@ -0,0 +1,363 @@
+//! The `btm config` subcommands, which create, check, and migrate the config file without starting
+//! bottom. These read the config file into the same [`Config`] as bottom does on startup, and go
+//! through the same checks, so a config file that passes `btm config check` starts without errors.
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context};
+
+use super::{
+    build_app, config_warnings::get_config_warnings, get_color_scheme, get_graph_renderer,
+    get_widget_layout, Config,
+};
+use crate::{
+    canvas::{graphics::GraphRenderer, ColourScheme, Painter},
+    constants::CONFIG_TEXT,
+};
+
+/// An option that was renamed or removed.
+pub struct DeprecatedKey {
+    /// The table the option is in.
+    pub section: &'static str,
+    pub key: &'static str,
+    /// The option that replaced it, or [`None`] if it was removed.
+    pub replacement: Option<&'static str>,
+    /// Whether the replacement means the opposite, like `hide_avg_cpu` for `avg_cpu`.
+    pub is_inverted: bool,
+}
+
+/// Every option that was renamed or removed.
+pub const DEPRECATED_KEYS: &[DeprecatedKey] = &[
+    DeprecatedKey {
+        section: "flags",
+        key: "avg_cpu",
+        replacement: Some("hide_avg_cpu"),
+        is_inverted: true,
+    },
+    DeprecatedKey {
+        section: "flags",
+        key: "show_disabled_data",
+        replacement: None,
+        is_inverted: false,
+    },
+];
+
+/// Returns the deprecated option `key` in the table `section`, like `flags`, if it is one.
+pub fn get_deprecated_key(section: &str, key: &str) -> Option<&'static DeprecatedKey> {
+    let section = match section {
+        "theme" => "colors",
+        section => section,
+    };
+
+    DEPRECATED_KEYS
+        .iter()
+        .find(|deprecated| deprecated.section == section && deprecated.key == key)
+}
+
+/// Returns the table name in a line like `[flags]` or `[[row]]`, if it is one.
+fn get_section(line: &str) -> Option<&str> {
+    let line = line.split('#').next().unwrap_or_default().trim();
+    let line = line.strip_prefix('[')?.strip_suffix(']')?;
+    let line = line
+        .strip_prefix('[')
+        .and_then(|line| line.strip_suffix(']'))
+        .unwrap_or(line);
+
+    Some(line.trim())
+}
+
+/// Updates the deprecated options in `config_string`. Only the lines with these options are
+/// changed, so comments and formatting are kept. Returns the new config file, and what was changed.
+pub fn migrate_config(config_string: &str) -> (String, Vec<String>) {
+    let table = match config_string.parse::<toml::Value>() {
+        Ok(toml::Value::Table(table)) => table,
+        _ => return (config_string.to_string(), Vec::new()),
+    };
+
+    let mut migrated = String::with_capacity(config_string.len());
+    let mut changes = Vec::new();
+    let mut section = String::new();
+
+    for line in config_string.split_inclusive('\n') {
+        if let Some(new_section) = get_section(line) {
+            section = new_section.to_string();
+            migrated.push_str(line);
+            continue;
+        }
+
+        let deprecated = line
+            .split_once('=')
+            .and_then(|(key, _)| get_deprecated_key(&section, key.trim().trim_matches('"')));
+        let (deprecated, (key_part, value_part)) = match (deprecated, line.split_once('=')) {
+            (Some(deprecated), Some(parts)) => (deprecated, parts),
+            _ => {
+                migrated.push_str(line);
+                continue;
+            }
+        };
+        let location = format!("\"{}\" in [{}]", deprecated.key, section);
+
+        let replacement = match deprecated.replacement {
+            Some(replacement) => replacement,
+            None => {
+                changes.push(format!(
+                    "Removed {}, which isn't an option any more.",
+                    location
+                ));
+                continue;
+            }
+        };
+
+        // If the new option is also set, it's the one that's used, so the old one can just go.
+        let has_replacement = table
+            .get(&section)
+            .and_then(|section_table| section_table.get(replacement))
+            .is_some();
+        if has_replacement {
+            changes.push(format!(
+                "Removed {}, as \"{}\" is already set.",
+                location, replacement
+            ));
+            continue;
+        }
+
+        let indent = &key_part[..key_part.len() - key_part.trim_start().len()];
+        let line_ending = if line.ends_with("\r\n") {
+            "\r\n"
+        } else if line.ends_with('\n') {
+            "\n"
+        } else {
+            ""
+        };
+        let value = value_part
+            .split('#')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        let value = if deprecated.is_inverted {
+            match value.as_str() {
+                "true" => "false".to_string(),
+                "false" => "true".to_string(),
+                _ => {
+                    // Leave it be, and let the warnings point it out.
+                    migrated.push_str(line);
+                    continue;
+                }
+            }
+        } else {
+            value
+        };
+
+        migrated.push_str(&format!(
+            "{}{} = {}{}",
+            indent, replacement, value, line_ending
+        ));
+        changes.push(format!(
+            "Replaced {} with \"{} = {}\".",
+            location, replacement, value
+        ));
+    }
+
+    (migrated, changes)
+}
+
+/// Reads the config file at `config_path`, without creating it if it doesn't exist.
+fn read_config_file(config_path: &Path) -> anyhow::Result<(Config, String)> {
+    let config_string = fs::read_to_string(config_path).with_context(|| {
+        format!(
+            "Unable to read the config file at {}.",
+            config_path.display()
+        )
+    })?;
+    let config = toml::from_str(&config_string).context("Unable to parse the config file.")?;
+
+    Ok((config, config_string))
+}
+
+/// Reads the config file like bottom does on startup, and returns its warnings.
+fn check_config(matches: &clap::ArgMatches, config_path: &Path) -> anyhow::Result<Vec<String>> {
+    let (mut config, config_string) = read_config_file(config_path)?;
+    let warnings = get_config_warnings(matches, &config_string, &config);
+
+    let (widget_layout, default_widget_id, default_widget_type_option) =
+        get_widget_layout(matches, &config)
+            .context("Found an issue while trying to build the widget layout.")?;
+    build_app(
+        matches,
+        &mut config,
+        &widget_layout,
+        default_widget_id,
+        &default_widget_type_option,
+        Some(config_path.to_path_buf()),
+    )?;
+
+    // The terminal isn't asked for its background or graphics support, as nothing is drawn.
+    let colour_scheme = match get_color_scheme(matches, &config)? {
+        ColourScheme::Auto => ColourScheme::Default,
+        colour_scheme => colour_scheme,
+    };
+    get_graph_renderer(matches, &config)?;
+    Painter::init(widget_layout, &config, colour_scheme, GraphRenderer::Text)?;
+
+    Ok(warnings)
+}
+
+/// Runs a `btm config` subcommand, given the matches of `btm` and of `config`.
+pub fn run_config_command(
+    matches: &clap::ArgMatches, config_matches: &clap::ArgMatches, config_path: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let config_path = config_path.ok_or_else(|| {
+        anyhow!("Unable to find where the config file goes. Give its location with --config.")
+    })?;
+
+    match config_matches.subcommand() {
+        Some(("init", init_matches)) => {
+            if config_path.exists() && !init_matches.is_present("force") {
+                bail!(
+                    "A config file already exists at {}. Use --force to overwrite it.",
+                    config_path.display()
+                );
+            }
+            if let Some(parent_path) = config_path.parent() {
+                fs::create_dir_all(parent_path)?;
+            }
+            fs::File::create(&config_path)?.write_all(CONFIG_TEXT.as_bytes())?;
+            println!("Wrote a default config file to {}.", config_path.display());
+        }
+        Some(("check", _)) => {
+            let warnings = check_config(matches, &config_path)?;
+            for warning in &warnings {
+                eprintln!("Warning: {}", warning);
+            }
+            if warnings.is_empty() {
+                println!("The config file at {} is valid.", config_path.display());
+            } else {
+                println!(
+                    "The config file at {} is valid, with {} warning(s).",
+                    config_path.display(),
+                    warnings.len()
+                );
+            }
+        }
+        Some(("migrate", migrate_matches)) => {
+            let (_, config_string) = read_config_file(&config_path)?;
+            let (migrated, changes) = migrate_config(&config_string);
+            toml::from_str::<Config>(&migrated)
+                .context("The migrated config file couldn't be parsed, so nothing was changed.")?;
+
+            if migrate_matches.is_present("dry_run") {
+                print!("{}", migrated);
+            } else if !changes.is_empty() {
+                let mut backup_path = config_path.clone().into_os_string();
+                backup_path.push(".bak");
+                fs::copy(&config_path, &backup_path)
+                    .context("Unable to back up the config file.")?;
+                fs::write(&config_path, &migrated)
+                    .context("Unable to write the migrated config file.")?;
+            }
+            for change in &changes {
+                eprintln!("{}", change);
+            }
+            if changes.is_empty() {
+                eprintln!(
+                    "The config file at {} is up to date.",
+                    config_path.display()
+                );
+            }
+        }
+        _ => unreachable!("a subcommand is required"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::options::{config_warnings::get_fields, ConfigColours, ConfigFlags};
+
+    #[test]
+    fn test_migrate_config() {
+        let (migrated, changes) = migrate_config(
+            "# My config.\n\
+            [flags]\n\
+            avg_cpu = false # Not needed.\n\
+            \x20 show_disabled_data = true\n\
+            rate = 1000\n\
+            \n\
+            [theme]\n\
+            rx_total_color = \"red\"\n\
+            ram_color = \"blue\"\n",
+        );
+        assert_eq!(
+            migrated,
+            "# My config.\n\
+            [flags]\n\
+            hide_avg_cpu = true\n\
+            rate = 1000\n\
+            \n\
+            [theme]\n\
+            rx_total_color = \"red\"\n\
+            ram_color = \"blue\"\n"
+        );
+        assert_eq!(
+            changes,
+            vec![
+                "Replaced \"avg_cpu\" in [flags] with \"hide_avg_cpu = true\".",
+                "Removed \"show_disabled_data\" in [flags], which isn't an option any more.",
+            ]
+        );
+
+        let (migrated, changes) =
+            migrate_config("[flags]\nhide_avg_cpu = false\navg_cpu = false\n");
+        assert_eq!(migrated, "[flags]\nhide_avg_cpu = false\n");
+        assert_eq!(
+            changes,
+            vec!["Removed \"avg_cpu\" in [flags], as \"hide_avg_cpu\" is already set."]
+        );
+
+        // Options with the same name in other tables are left alone.
+        let config_string = "[[row]]\navg_cpu = true\n";
+        assert_eq!(
+            migrate_config(config_string),
+            (config_string.to_string(), Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_deprecated_keys_are_not_options() {
+        for deprecated in DEPRECATED_KEYS {
+            let fields = match deprecated.section {
+                "flags" => get_fields::<ConfigFlags>(),
+                "colors" => get_fields::<ConfigColours>(),
+                section => panic!("no fields to check for [{}]", section),
+            };
+            assert!(
+                !fields.contains(&deprecated.key),
+                "\"{}\" in [{}] is still an option",
+                deprecated.key,
+                deprecated.section
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_config_has_every_flag() {
+        let missing_flags = get_fields::<ConfigFlags>()
+            .iter()
+            .filter(|flag| !flag.starts_with("search_") && **flag != "no_write")
+            .filter(|flag| !CONFIG_TEXT.contains(&format!("\n#{} = ", flag)))
+            .collect::<Vec<_>>();
+        assert!(
+            missing_flags.is_empty(),
+            "missing flags: {:?}",
+            missing_flags
+        );
+        assert!(toml::from_str::<Config>(CONFIG_TEXT).is_ok());
+    }
+}
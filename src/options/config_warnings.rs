@@ -0,0 +1,284 @@
+//! Checks for mistakes in the config file that don't stop it from being used, like keys that
+//! aren't options and so are ignored, or options that have no effect because of others. These
+//! are shown in a dialog on startup and when the config file is reloaded.
+
+use serde::{
+    de::{self, Visitor},
+    forward_to_deserialize_any, Deserialize, Deserializer,
+};
+
+use super::{
+    config_command::get_deprecated_key,
+    get_app_grouping, get_autohide_time, get_hide_time, get_is_default_tree, get_use_basic_mode,
+    layout_options::{FinalWidget, NamedLayout, Row},
+    Config, ConfigAlert, ConfigColours, ConfigFlags, ConfigHook, ConfigLabels, ConfigTempThreshold,
+    ConfigTempThresholds, IgnoreList, RefreshRates, SavedProcessFilter, WidgetIdEnabled,
+};
+use crate::{
+    app::{
+        data_harvester::{latency::LatencyHost, plugins::PluginConfig},
+        derived_metrics::DerivedMetricConfig,
+        keybindings::get_keybinding_warnings,
+    },
+    utils::gen_util::get_closest_match,
+};
+
+/// The keys of a column in a row, which can't be read from [`RowChildren`](super::RowChildren)
+/// like the other tables, as it's an untagged enum.
+const COLUMN_KEYS: &[&str] = &["ratio", "child", "tabs"];
+
+/// A deserializer that only records the field names of the struct it's asked to deserialize.
+struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+impl<'de, 'a> Deserializer<'de> for FieldNames<'a> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self, _name: &'static str, fields: &'static [&'static str], _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(de::Error::custom("only the fields are needed"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+/// Returns the keys that a struct is read from.
+pub(super) fn get_fields<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+/// Returns the keys that the table at `path` can have, or [`None`] if it can have any, like the
+/// key bindings.
+fn get_known_keys(path: &[&str], table: &toml::value::Table) -> Option<Vec<&'static str>> {
+    Some(match path {
+        [] => {
+            let mut keys = get_fields::<Config>().to_vec();
+            keys.push("theme");
+            keys
+        }
+        ["flags"] => get_fields::<ConfigFlags>().to_vec(),
+        ["flags", "search_case_enabled_widgets"
+        | "search_whole_word_enabled_widgets"
+        | "search_regex_enabled_widgets"] => get_fields::<WidgetIdEnabled>().to_vec(),
+        ["colors" | "theme"] => get_fields::<ConfigColours>().to_vec(),
+        ["layouts"] => get_fields::<NamedLayout>().to_vec(),
+        ["row"] | ["layouts", "row"] => get_fields::<Row>().to_vec(),
+        ["row", "child"] | ["layouts", "row", "child"] => {
+            if table.contains_key("child") {
+                COLUMN_KEYS.to_vec()
+            } else {
+                get_fields::<FinalWidget>().to_vec()
+            }
+        }
+        ["row", "child", "child"] | ["layouts", "row", "child", "child"] => {
+            get_fields::<FinalWidget>().to_vec()
+        }
+        ["disk_filter" | "mount_filter" | "temp_filter" | "net_filter"] => {
+            get_fields::<IgnoreList>().to_vec()
+        }
+        ["hooks"] => get_fields::<ConfigHook>().to_vec(),
+        ["alerts"] => get_fields::<ConfigAlert>().to_vec(),
+        ["process_filters"] => get_fields::<SavedProcessFilter>().to_vec(),
+        ["latency_hosts"] => get_fields::<LatencyHost>().to_vec(),
+        ["plugins"] => get_fields::<PluginConfig>().to_vec(),
+        ["derived_metrics"] => get_fields::<DerivedMetricConfig>().to_vec(),
+        ["refresh_rates"] => get_fields::<RefreshRates>().to_vec(),
+        ["temp_thresholds"] => get_fields::<ConfigTempThresholds>().to_vec(),
+        ["temp_thresholds", "sensors", _] => get_fields::<ConfigTempThreshold>().to_vec(),
+        ["labels"] => get_fields::<ConfigLabels>().to_vec(),
+        _ => return None,
+    })
+}
+
+/// Warns about each key in `table`, and the tables within it, that isn't an option. `path` is the
+/// keys of the tables it's in, and whether each is an array of tables.
+fn check_keys(
+    table: &toml::value::Table, path: &mut Vec<(String, bool)>, warnings: &mut Vec<String>,
+) {
+    let path_keys = path.iter().map(|(key, _)| key.as_str()).collect::<Vec<_>>();
+    let known_keys = get_known_keys(&path_keys, table);
+    let table_name = path_keys.join(".");
+    let section = match path.last() {
+        Some((_, true)) => format!(" in [[{}]]", table_name),
+        Some((_, false)) => format!(" in [{}]", table_name),
+        None => String::new(),
+    };
+
+    for (key, value) in table {
+        if let Some(known_keys) = &known_keys {
+            if !known_keys.contains(&key.as_str()) {
+                let location = format!("\"{}\"{}", key, section);
+                let deprecated = get_deprecated_key(&table_name, key);
+                warnings.push(match deprecated.map(|deprecated| deprecated.replacement) {
+                    Some(Some(replacement)) => format!(
+                        "{} was replaced by \"{}\", so it's ignored. Run \"btm config migrate\" to update it.",
+                        location, replacement
+                    ),
+                    Some(None) => format!(
+                        "{} was removed, so it's ignored. Run \"btm config migrate\" to update it.",
+                        location
+                    ),
+                    None => match get_closest_match(key, known_keys) {
+                        Some(suggestion) => format!(
+                            "{} isn't an option, so it's ignored. Did you mean \"{}\"?",
+                            location, suggestion
+                        ),
+                        None => format!("{} isn't an option, so it's ignored.", location),
+                    },
+                });
+                continue;
+            }
+        }
+
+        match value {
+            toml::Value::Table(child) => {
+                path.push((key.clone(), false));
+                check_keys(child, path, warnings);
+                path.pop();
+            }
+            toml::Value::Array(items) => {
+                path.push((key.clone(), true));
+                for item in items {
+                    if let toml::Value::Table(child) = item {
+                        check_keys(child, path, warnings);
+                    }
+                }
+                path.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Warns about options that have no effect because of other options.
+fn check_conflicts(matches: &clap::ArgMatches, config: &Config, warnings: &mut Vec<String>) {
+    let is_basic = get_use_basic_mode(matches, config);
+
+    if get_hide_time(matches, config) && get_autohide_time(matches, config) {
+        warnings.push("\"autohide_time\" has no effect, as \"hide_time\" is also set.".to_string());
+    }
+    if get_app_grouping(matches, config) && get_is_default_tree(matches, config) {
+        warnings.push("\"tree\" has no effect, as \"group_processes\" is also set.".to_string());
+    }
+    if is_basic && (config.row.is_some() || config.layouts.is_some()) {
+        warnings.push(
+            "the layout in [[row]] and [[layouts]] isn't used, as \"basic\" is set.".to_string(),
+        );
+    }
+    if !is_basic
+        && (matches.is_present("basic_widgets")
+            || config
+                .flags
+                .as_ref()
+                .and_then(|flags| flags.basic_widgets.as_ref())
+                .is_some())
+    {
+        warnings.push("\"basic_widgets\" has no effect, as \"basic\" isn't set.".to_string());
+    }
+}
+
+/// Warns about key bindings that won't work as expected.
+fn check_keybindings(config: &Config, warnings: &mut Vec<String>) {
+    if let Some(keybindings) = &config.keybindings {
+        warnings.extend(get_keybinding_warnings(keybindings));
+    }
+}
+
+/// Returns the warnings about the config file, which is `config_string` read into `config`.
+pub fn get_config_warnings(
+    matches: &clap::ArgMatches, config_string: &str, config: &Config,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if let Ok(toml::Value::Table(table)) = config_string.parse::<toml::Value>() {
+        check_keys(&table, &mut Vec::new(), &mut warnings);
+    }
+    check_conflicts(matches, config, &mut warnings);
+    check_keybindings(config, &mut warnings);
+
+    warnings
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_key_warnings(config_string: &str) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if let toml::Value::Table(table) = config_string.parse::<toml::Value>().unwrap() {
+            check_keys(&table, &mut Vec::new(), &mut warnings);
+        }
+        warnings
+    }
+
+    #[test]
+    fn test_unknown_keys() {
+        assert_eq!(
+            get_key_warnings(
+                r#"
+                [flags]
+                hide_avg_cp = true
+                basic = true
+                avg_cpu = false
+
+                [colours]
+                ram_color = "red"
+
+                [[row]]
+                  [[row.child]]
+                  type = "cpu"
+                  titel = "CPU"
+                  [[row.child]]
+                    [[row.child.child]]
+                    type = "mem"
+                    zzz = 1
+
+                [keybindings]
+                anything = "goes"
+
+                [temp_thresholds.sensors.cpu]
+                warning = 70.0
+                critcal = 90.0
+                "#
+            ),
+            vec![
+                "\"colours\" isn't an option, so it's ignored. Did you mean \"colors\"?",
+                "\"avg_cpu\" in [flags] was replaced by \"hide_avg_cpu\", so it's ignored. Run \"btm config migrate\" to update it.",
+                "\"hide_avg_cp\" in [flags] isn't an option, so it's ignored. Did you mean \"hide_avg_cpu\"?",
+                "\"titel\" in [[row.child]] isn't an option, so it's ignored. Did you mean \"title\"?",
+                "\"zzz\" in [[row.child.child]] isn't an option, so it's ignored.",
+                "\"critcal\" in [temp_thresholds.sensors.cpu] isn't an option, so it's ignored. Did you mean \"critical\"?",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_unknown_keys() {
+        assert!(get_key_warnings(crate::constants::DEFAULT_LAYOUT).is_empty());
+        assert!(get_key_warnings(
+            r##"
+            [theme]
+            ram_color = "#ff0000"
+
+            [[layouts]]
+            name = "small"
+            max_width = 80
+              [[layouts.row]]
+                [[layouts.row.child]]
+                ratio = 1
+                tabs = true
+                child = [{ type = "cpu" }, { type = "mem" }]
+            "##
+        )
+        .is_empty());
+    }
+}
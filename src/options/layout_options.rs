@@ -1,5 +1,5 @@
 use crate::app::layout_manager::*;
-use crate::error::Result;
+use crate::error::{BottomError, Result};
 use serde::{Deserialize, Serialize};
 
 /// Represents a row.  This has a length of some sort (optional) and a vector
@@ -12,6 +12,35 @@ pub struct Row {
 }
 
 impl Row {
+    /// Updates the ratios of this row and its children to match those of a [`BottomRow`]
+    /// that was created from it.
+    pub fn update_ratios(&mut self, bottom_row: &BottomRow) {
+        self.ratio = Some(bottom_row.row_height_ratio);
+
+        if let Some(row_children) = &mut self.child {
+            for (row_child, bottom_col) in row_children.iter_mut().zip(&bottom_row.children) {
+                match row_child {
+                    RowChildren::Widget(widget) => {
+                        widget.ratio = Some(bottom_col.col_width_ratio);
+                    }
+                    RowChildren::Col { ratio, child, .. } => {
+                        *ratio = Some(bottom_col.col_width_ratio);
+
+                        // Process widgets take up two column rows, so only copy these over if
+                        // they still match up one-to-one.
+                        if child.len() == bottom_col.children.len() {
+                            for (widget, bottom_col_row) in
+                                child.iter_mut().zip(&bottom_col.children)
+                            {
+                                widget.ratio = Some(bottom_col_row.col_row_height_ratio);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     pub fn convert_row_to_bottom_row(
         &self, iter_id: &mut u64, total_height_ratio: &mut u32, default_widget_id: &mut u64,
         default_widget_type: &Option<BottomWidgetType>, default_widget_count: &mut u64,
@@ -33,6 +62,8 @@ impl Row {
                         let width_ratio = widget.ratio.unwrap_or(1);
                         total_col_ratio += width_ratio;
                         let widget_type = widget.widget_type.parse::<BottomWidgetType>()?;
+                        widget.check_process_options(&widget_type)?;
+                        widget.check_plugin(&widget_type)?;
 
                         if let Some(default_widget_type_val) = default_widget_type {
                             if *default_widget_type_val == widget_type && *default_widget_count > 0
@@ -74,6 +105,7 @@ impl Row {
                                                 BottomWidget::builder()
                                                     .width_ratio(17)
                                                     .widget_type(BottomWidgetType::Cpu)
+                                                    .title(widget.title.clone())
                                                     .widget_id(cpu_id)
                                                     .flex_grow(true)
                                                     .build(),
@@ -86,6 +118,7 @@ impl Row {
                                                 BottomWidget::builder()
                                                     .width_ratio(17)
                                                     .widget_type(BottomWidgetType::Cpu)
+                                                    .title(widget.title.clone())
                                                     .widget_id(cpu_id)
                                                     .flex_grow(true)
                                                     .build(),
@@ -126,6 +159,8 @@ impl Row {
                                                     .build(),
                                                 BottomWidget::builder()
                                                     .widget_type(BottomWidgetType::Proc)
+                                                    .title(widget.title.clone())
+                                                    .process_options(widget.get_process_options())
                                                     .widget_id(proc_id)
                                                     .width_ratio(2)
                                                     .build(),
@@ -149,23 +184,30 @@ impl Row {
                                 .children(vec![BottomColRow::builder()
                                     .children(vec![BottomWidget::builder()
                                         .widget_type(widget_type)
+                                        .title(widget.title.clone())
+                                        .plugin(widget.plugin.clone())
                                         .widget_id(*iter_id)
                                         .build()])
                                     .build()])
                                 .build(),
                         });
                     }
-                    RowChildren::Col { ratio, child } => {
+                    RowChildren::Col { ratio, child, tabs } => {
                         let col_width_ratio = ratio.unwrap_or(1);
                         total_col_ratio += col_width_ratio;
                         let mut total_col_row_ratio = 0;
                         let mut contains_proc = false;
 
                         let mut col_row_children: Vec<BottomColRow> = Vec::new();
+                        let mut layout_tabs: Vec<LayoutTab> = Vec::new();
 
                         for widget in child {
                             let widget_type = widget.widget_type.parse::<BottomWidgetType>()?;
+                            widget.check_process_options(&widget_type)?;
+                            widget.check_plugin(&widget_type)?;
                             *iter_id += 1;
+                            let widget_id = *iter_id;
+                            let first_col_row = col_row_children.len();
                             let col_row_height_ratio = widget.ratio.unwrap_or(1);
                             total_col_row_ratio += col_row_height_ratio;
 
@@ -210,6 +252,7 @@ impl Row {
                                                     BottomWidget::builder()
                                                         .width_ratio(17)
                                                         .widget_type(BottomWidgetType::Cpu)
+                                                        .title(widget.title.clone())
                                                         .widget_id(cpu_id)
                                                         .flex_grow(true)
                                                         .build(),
@@ -225,6 +268,7 @@ impl Row {
                                                     BottomWidget::builder()
                                                         .width_ratio(17)
                                                         .widget_type(BottomWidgetType::Cpu)
+                                                        .title(widget.title.clone())
                                                         .widget_id(cpu_id)
                                                         .flex_grow(true)
                                                         .build(),
@@ -263,6 +307,8 @@ impl Row {
                                                     .build(),
                                                 BottomWidget::builder()
                                                     .widget_type(BottomWidgetType::Proc)
+                                                    .title(widget.title.clone())
+                                                    .process_options(widget.get_process_options())
                                                     .widget_id(proc_id)
                                                     .width_ratio(2)
                                                     .build(),
@@ -287,12 +333,36 @@ impl Row {
                                     BottomColRow::builder()
                                         .col_row_height_ratio(col_row_height_ratio)
                                         .children(vec![BottomWidget::builder()
-                                            .widget_type(widget_type)
+                                            .widget_type(widget_type.clone())
+                                            .title(widget.title.clone())
+                                            .plugin(widget.plugin.clone())
                                             .widget_id(*iter_id)
                                             .build()])
                                         .build(),
                                 ),
                             }
+
+                            if *tabs == Some(true) {
+                                layout_tabs.push(LayoutTab {
+                                    widget_id,
+                                    widget_type,
+                                    col_rows: first_col_row..col_row_children.len(),
+                                });
+                            }
+                        }
+
+                        if let Some(container_id) = layout_tabs.first().map(|tab| tab.widget_id) {
+                            for tab in &layout_tabs {
+                                for widget in col_row_children[tab.col_rows.clone()]
+                                    .iter_mut()
+                                    .flat_map(|col_row| col_row.children.iter_mut())
+                                {
+                                    widget.tab = Some(TabId {
+                                        container_id,
+                                        tab_widget_id: tab.widget_id,
+                                    });
+                                }
+                            }
                         }
 
                         if contains_proc {
@@ -314,6 +384,7 @@ impl Row {
                                 .total_col_row_ratio(total_col_row_ratio)
                                 .col_width_ratio(col_width_ratio)
                                 .children(col_row_children)
+                                .tabs(layout_tabs)
                                 .build(),
                         );
                     }
@@ -329,6 +400,37 @@ impl Row {
     }
 }
 
+/// The name of the layout in the top-level `[[row]]` section, which is shown on startup.
+pub const DEFAULT_LAYOUT_NAME: &str = "default";
+
+/// A layout that can be switched to while running, in addition to the one in the top-level
+/// `[[row]]` section.
+#[derive(Clone, Deserialize, Debug, Serialize)]
+pub struct NamedLayout {
+    pub name: String,
+    /// If set, this layout is switched to automatically while the terminal is at most this many
+    /// columns wide.
+    pub max_width: Option<u16>,
+    /// If set, this layout is switched to automatically while the terminal is at most this many
+    /// lines tall.
+    pub max_height: Option<u16>,
+    pub row: Option<Vec<Row>>,
+}
+
+impl NamedLayout {
+    /// Whether this layout has any breakpoints, so it's switched to and from automatically.
+    pub fn has_breakpoints(&self) -> bool {
+        self.max_width.is_some() || self.max_height.is_some()
+    }
+
+    /// Whether a terminal of this size is within all of this layout's breakpoints.
+    pub fn fits_breakpoints(&self, width: u16, height: u16) -> bool {
+        self.has_breakpoints()
+            && !matches!(self.max_width, Some(max_width) if width > max_width)
+            && !matches!(self.max_height, Some(max_height) if height > max_height)
+    }
+}
+
 /// Represents a child of a Row - either a Col (column) or a FinalWidget.
 ///
 /// A Col can also have an optional length and children.  We only allow columns
@@ -341,6 +443,8 @@ pub enum RowChildren {
     Col {
         ratio: Option<u32>,
         child: Vec<FinalWidget>,
+        /// Whether to show the widgets as tabs, one at a time, instead of stacking them.
+        tabs: Option<bool>,
     },
 }
 
@@ -351,4 +455,60 @@ pub struct FinalWidget {
     #[serde(rename = "type")]
     pub widget_type: String,
     pub default: Option<bool>,
+    /// A title to show instead of the widget's usual one.
+    pub title: Option<String>,
+    /// For process widgets, the name of the saved process filter to start with.
+    pub filter: Option<String>,
+    /// For process widgets, the column to sort by.
+    pub sort: Option<String>,
+    /// For process widgets, whether to sort in "ascending" or "descending" order.
+    pub sort_order: Option<String>,
+    /// For process widgets, whether to group processes with the same name.
+    pub grouped: Option<bool>,
+    /// For process widgets, whether to show processes as a tree.
+    pub tree: Option<bool>,
+    /// For plugin widgets, the name of the plugin to show.
+    pub plugin: Option<String>,
+}
+
+impl FinalWidget {
+    fn get_process_options(&self) -> ProcLayoutOptions {
+        ProcLayoutOptions {
+            filter: self.filter.clone(),
+            sort: self.sort.clone(),
+            sort_order: self.sort_order.clone(),
+            grouped: self.grouped,
+            tree: self.tree,
+        }
+    }
+
+    /// Only process widgets have process settings, so setting one on any other widget is an
+    /// error.
+    fn check_process_options(&self, widget_type: &BottomWidgetType) -> Result<()> {
+        if *widget_type == BottomWidgetType::Proc
+            || self.get_process_options() == ProcLayoutOptions::default()
+        {
+            Ok(())
+        } else {
+            Err(BottomError::ConfigError(format!(
+                "a {} widget has process widget settings, but only process widgets can have them.",
+                self.widget_type
+            )))
+        }
+    }
+
+    /// Plugin widgets have to say which plugin they show, and no other widget can.
+    fn check_plugin(&self, widget_type: &BottomWidgetType) -> Result<()> {
+        match (widget_type, &self.plugin) {
+            (BottomWidgetType::Plugin, None) => Err(BottomError::ConfigError(
+                "a plugin widget has no \"plugin\" set, which should be the name of a plugin."
+                    .to_string(),
+            )),
+            (BottomWidgetType::Plugin, Some(_)) | (_, None) => Ok(()),
+            (_, Some(_)) => Err(BottomError::ConfigError(format!(
+                "a {} widget has a \"plugin\" set, but only plugin widgets can have one.",
+                self.widget_type
+            ))),
+        }
+    }
 }
@@ -0,0 +1,441 @@
+//! Remote monitoring. An agent, started with `--agent`, collects data like bottom normally does
+//! and serves it over TCP, and bottom started with `--connect` shows that data instead of
+//! collecting its own. Any number of agents can be connected to at once, with one shown at a time.
+//!
+//! The protocol is newline-separated JSON. When a client connects, it sends an [`Auth`] with the
+//! token the agent was started with, and if it matches, the agent sends a [`Hello`], followed by a
+//! [`Data`] for every harvest until the client disconnects. There's no encryption, so the token and
+//! data can be read by anyone on the network, and agents are only meant to listen on loopback and
+//! be reached through an SSH tunnel.
+//!
+//! Hosts that can't run an agent can be collected from over SSH instead, with `--ssh`, which is
+//! handled in [`ssh`].
+
+pub mod ssh;
+
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::{mpsc, Arc, Condvar, Mutex},
+    thread,
+    time::Duration,
+};
+
+use crossterm::event::{KeyEvent, MouseEvent};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{
+        data_farmer::DataCollection, data_harvester::Data, layout_manager::UsedWidgets,
+        AppConfigFields, DataFilters,
+    },
+    create_collection_thread,
+    utils::error::Result,
+    BottomEvent,
+};
+
+/// The version of the protocol, which is bumped whenever [`Data`] changes in a way that older
+/// versions can't read.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// The environment variable the token can be given with instead of `--token`, which keeps it out
+/// of the process list.
+pub const TOKEN_VAR: &str = "BTM_TOKEN";
+
+/// How long to wait for an agent to accept a connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long to wait before trying to connect again after the connection is lost.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How often a client waiting on an agent checks whether bottom is quitting.
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long an agent waits on a client that isn't reading before dropping it.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long an agent waits for a client to send its token.
+const AUTH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The longest line an agent reads from a client, which only ever sends its token.
+const MAX_AUTH_LENGTH: u64 = 4096;
+
+/// The longest line a client reads from an agent. Lines hold every process, so this is generous,
+/// but stops an agent, or anything else listening on the address, from using up all the memory.
+const MAX_LINE_LENGTH: usize = 64 * 1024 * 1024;
+
+/// An agent connected to with `--connect`, or a host collected from with `--ssh`.
+#[derive(Debug, Default)]
+pub struct RemoteHost {
+    /// The address it was connected to, like `server:7777`, or the SSH destination, like
+    /// `user@server`.
+    pub address: String,
+    /// Whether it's collected from over SSH rather than by an agent.
+    pub is_ssh: bool,
+    /// The host name the agent or host gave, once connected.
+    pub hostname: Option<String>,
+    pub is_connected: bool,
+    /// The data of this host while another one is shown. The data of the shown host is kept in
+    /// [`App::data_collection`](crate::app::App::data_collection) instead.
+    pub data_collection: DataCollection,
+}
+
+impl RemoteHost {
+    pub fn new(address: String) -> Self {
+        Self {
+            address,
+            ..Self::default()
+        }
+    }
+
+    pub fn ssh(destination: String) -> Self {
+        Self {
+            address: destination,
+            is_ssh: true,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the host name, or the address if it isn't known yet.
+    pub fn name(&self) -> &str {
+        self.hostname.as_deref().unwrap_or(&self.address)
+    }
+}
+
+/// A change in the connection to an agent.
+#[derive(Debug)]
+pub enum HostStatus {
+    /// Connected, with the host name the agent gave.
+    Connected(String),
+    /// The connection was lost or couldn't be made, and why.
+    Disconnected(String),
+}
+
+/// The first message a client sends to an agent.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct Auth {
+    pub token: String,
+}
+
+/// The first message an agent sends to a client.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct Hello {
+    pub version: u32,
+    pub hostname: String,
+}
+
+/// Runs an agent listening on an address, until bottom is killed. Only clients that send `token`
+/// are served. Everything is collected, as clients can have any layout.
+pub fn run_agent(
+    listen_address: &str, token: String, app_config_fields: &AppConfigFields, filters: DataFilters,
+) -> Result<()> {
+    let listener = TcpListener::bind(listen_address)?;
+    let local_address = listener.local_addr()?;
+    println!("Listening on {}", local_address);
+    if !local_address.ip().is_loopback() {
+        eprintln!(
+            "Warning: the agent isn't encrypted, so the token and data can be read by anyone on \
+            the network. Listen on 127.0.0.1 and connect through an SSH tunnel instead."
+        );
+    }
+
+    let hello = serde_json::to_string(&Hello {
+        version: PROTOCOL_VERSION,
+        hostname: get_hostname(),
+    })?;
+    let clients = Arc::new(Mutex::new(Vec::new()));
+    {
+        let clients = clients.clone();
+        let token = Arc::new(token);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // Each client is checked on its own thread, so one that never sends its token
+                // doesn't hold up the others.
+                let clients = clients.clone();
+                let token = token.clone();
+                let hello = hello.clone();
+                thread::spawn(move || {
+                    if let Ok(stream) = greet_client(stream, &token, &hello) {
+                        clients.lock().unwrap().push(stream);
+                    }
+                });
+            }
+        });
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let (_control_sender, control_receiver) = mpsc::channel();
+    let _collection_thread = create_collection_thread(
+        sender,
+        control_receiver,
+        Arc::new(Mutex::new(false)),
+        Arc::new(Condvar::new()),
+        app_config_fields,
+        filters,
+        UsedWidgets {
+            use_cpu: true,
+            use_mem: true,
+            use_net: true,
+            use_proc: true,
+            use_disk: true,
+            use_temp: true,
+            use_battery: true,
+            use_power: true,
+            use_pressure: true,
+            use_load_avg: true,
+            use_cgroup: true,
+            use_latency: true,
+            use_frequency: true,
+            use_events: true,
+            use_system_info: true,
+            use_users: true,
+            use_top_processes: true,
+            use_plugins: true,
+        },
+    );
+
+    for event in receiver {
+        if let BottomEvent::Update(data) = event {
+            let mut line = serde_json::to_vec(&data)?;
+            line.push(b'\n');
+            // Clients that have disconnected or stopped reading are dropped.
+            clients
+                .lock()
+                .unwrap()
+                .retain(|mut stream| stream.write_all(&line).is_ok());
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the token a client sends, and greets it if it matches. Returns the stream to send data
+/// to, or why the client isn't served.
+fn greet_client(mut stream: TcpStream, token: &str, hello: &str) -> std::io::Result<TcpStream> {
+    stream.set_read_timeout(Some(AUTH_TIMEOUT))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+    let mut line = Vec::new();
+    BufReader::new(&stream)
+        .take(MAX_AUTH_LENGTH)
+        .read_until(b'\n', &mut line)?;
+    let is_authorized = matches!(
+        serde_json::from_slice::<Auth>(&line),
+        Ok(auth) if is_same_token(&auth.token, token)
+    );
+    if !is_authorized {
+        return Err(std::io::Error::new(
+            ErrorKind::PermissionDenied,
+            "the client sent the wrong token",
+        ));
+    }
+
+    writeln!(stream, "{}", hello)?;
+    Ok(stream)
+}
+
+/// Compares tokens in the same time wherever they differ, so the token can't be worked out a
+/// character at a time from how long it takes to be turned away.
+fn is_same_token(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+/// Creates a thread that receives data from the agent at index `host` of
+/// [`App::remote_hosts`](crate::app::App::remote_hosts), in place of the collection thread. If
+/// the connection is lost, the agent is connected to again.
+pub fn create_remote_thread(
+    sender: mpsc::Sender<BottomEvent<KeyEvent, MouseEvent>>, host: usize, address: String,
+    token: String, termination_ctrl_lock: Arc<Mutex<bool>>, termination_ctrl_cvar: Arc<Condvar>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || loop {
+        match receive_data(&sender, host, &address, &token, &termination_ctrl_lock) {
+            Ok(()) => break,
+            Err(err) => {
+                let event = BottomEvent::RemoteStatus(host, HostStatus::Disconnected(err));
+                if sender.send(event).is_err() {
+                    break;
+                }
+            }
+        }
+
+        if let Ok((is_terminated, _wait_timeout_result)) = termination_ctrl_cvar
+            .wait_timeout(termination_ctrl_lock.lock().unwrap(), RECONNECT_INTERVAL)
+        {
+            if *is_terminated {
+                break;
+            }
+        }
+    })
+}
+
+/// Connects to an agent and passes on its data until bottom quits, which returns `Ok`, or the
+/// connection fails, which returns why.
+fn receive_data(
+    sender: &mpsc::Sender<BottomEvent<KeyEvent, MouseEvent>>, host: usize, address: &str,
+    token: &str, termination_ctrl_lock: &Mutex<bool>,
+) -> std::result::Result<(), String> {
+    let mut stream = connect(address).map_err(|err| err.to_string())?;
+    stream
+        .set_read_timeout(Some(READ_TIMEOUT))
+        .map_err(|err| err.to_string())?;
+    let auth = serde_json::to_string(&Auth {
+        token: token.to_string(),
+    })
+    .map_err(|err| err.to_string())?;
+    writeln!(stream, "{}", auth).map_err(|err| err.to_string())?;
+
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::new();
+    let mut is_greeted = false;
+
+    loop {
+        let limit = MAX_LINE_LENGTH.saturating_sub(line.len());
+        if limit == 0 {
+            return Err("the agent sent a line that's too long".to_string());
+        }
+        match (&mut reader)
+            .take(limit as u64)
+            .read_until(b'\n', &mut line)
+        {
+            Ok(0) if is_greeted => return Err("the agent closed the connection".to_string()),
+            Ok(0) => {
+                return Err(
+                    "the agent closed the connection, check that the token is right".to_string(),
+                )
+            }
+            Ok(_) if line.ends_with(b"\n") => {}
+            Ok(_) => continue,
+            Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                if *termination_ctrl_lock.lock().unwrap() {
+                    return Ok(());
+                }
+                continue;
+            }
+            Err(err) => return Err(err.to_string()),
+        }
+
+        let event = if is_greeted {
+            let data = serde_json::from_slice::<Data>(&line)
+                .map_err(|err| format!("the agent sent data that can't be read ({})", err))?;
+            BottomEvent::RemoteUpdate(host, Box::new(data))
+        } else {
+            let hello = check_hello(&line)?;
+            is_greeted = true;
+            BottomEvent::RemoteStatus(host, HostStatus::Connected(hello.hostname))
+        };
+        line.clear();
+
+        if sender.send(event).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+/// Reads the first message from an agent, and checks that its data can be read.
+fn check_hello(line: &[u8]) -> std::result::Result<Hello, String> {
+    let hello: Hello =
+        serde_json::from_slice(line).map_err(|_| "it isn't a bottom agent".to_string())?;
+
+    if hello.version == PROTOCOL_VERSION {
+        Ok(hello)
+    } else {
+        Err(format!(
+            "the agent uses protocol version {}, but this needs version {}",
+            hello.version, PROTOCOL_VERSION
+        ))
+    }
+}
+
+/// Connects to the first address that a host name resolves to which accepts the connection.
+fn connect(address: &str) -> std::io::Result<TcpStream> {
+    let mut last_err = None;
+    for socket_address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&socket_address, CONNECT_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last_err = Some(err),
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| {
+        std::io::Error::new(ErrorKind::NotFound, "the address couldn't be resolved")
+    }))
+}
+
+fn get_hostname() -> String {
+    use sysinfo::{System, SystemExt};
+
+    System::new()
+        .host_name()
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check_hello() {
+        let hello = check_hello(br#"{"version":2,"hostname":"server"}"#).unwrap();
+        assert_eq!(hello.hostname, "server");
+
+        assert!(check_hello(br#"{"version":1,"hostname":"server"}"#).is_err());
+        assert!(check_hello(b"SSH-2.0-OpenSSH_8.9\r\n").is_err());
+    }
+
+    #[test]
+    fn test_is_same_token() {
+        assert!(is_same_token("secret", "secret"));
+        assert!(!is_same_token("secreT", "secret"));
+        assert!(!is_same_token("secret1", "secret"));
+        assert!(!is_same_token("", "secret"));
+    }
+
+    #[test]
+    fn test_greet_client() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let greet = |auth: &str| {
+            let mut client = TcpStream::connect(address).unwrap();
+            writeln!(client, "{}", auth).unwrap();
+            let (stream, _) = listener.accept().unwrap();
+            let result = greet_client(stream, "secret", r#"{"version":2,"hostname":"server"}"#);
+
+            let mut line = String::new();
+            if result.is_ok() {
+                BufReader::new(client).read_line(&mut line).unwrap();
+            }
+            (result.is_ok(), line)
+        };
+
+        let (is_greeted, line) = greet(r#"{"token":"secret"}"#);
+        assert!(is_greeted);
+        assert_eq!(check_hello(line.as_bytes()).unwrap().hostname, "server");
+
+        assert!(!greet(r#"{"token":"wrong"}"#).0);
+        assert!(!greet("secret").0);
+    }
+
+    #[test]
+    fn test_data_round_trip() {
+        let data = Data {
+            load_avg: Some([0.5, 1.0, 1.5]),
+            public_ip: Some("192.0.2.1".parse().unwrap()),
+            ..Data::default()
+        };
+
+        let line = serde_json::to_vec(&data).unwrap();
+        let received: Data = serde_json::from_slice(&line).unwrap();
+        assert_eq!(received.load_avg, data.load_avg);
+        assert_eq!(received.public_ip, data.public_ip);
+        assert!(received.cpu.is_none());
+
+        // Anything missing, like from an agent on another platform, is left out.
+        let received: Data = serde_json::from_slice(br#"{"load_avg":[1.0,2.0,3.0]}"#).unwrap();
+        assert_eq!(received.load_avg, Some([1.0, 2.0, 3.0]));
+    }
+}
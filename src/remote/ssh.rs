@@ -0,0 +1,1037 @@
+//! Collecting from a Linux host over SSH, for hosts that an agent can't be installed on. A small
+//! shell script is run on the host with `ssh`, which prints the files under `/proc` and the output
+//! of a few standard commands on every update. These are parsed into the same [`Data`] that's
+//! collected locally, so nothing but a POSIX shell and coreutils is needed on the host.
+//!
+//! The output is split into sections, each starting with a line like `@bottom:stat`. The `host`
+//! and `passwd` sections are sent once, and the others on every update, ending with `@bottom:end`.
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    process::{Child, Command, Stdio},
+    sync::{mpsc, Arc, Condvar, Mutex},
+    thread,
+};
+
+use crossterm::event::{KeyEvent, MouseEvent};
+use fxhash::FxHashMap;
+
+use super::{HostStatus, READ_TIMEOUT, RECONNECT_INTERVAL};
+use crate::{
+    app::{
+        data_harvester::{
+            cpu::{CpuData, CpuTimes},
+            disks::{DiskHarvest, IoData, IoHarvest},
+            memory::{parse_vmstat, MemHarvest},
+            network::{get_error_data, is_interface_kept, NetworkErrorCounters, NetworkHarvest},
+            processes::ProcessHarvest,
+            system_info::SystemInfoHarvest,
+            temperature::{is_temp_filtered, TempHarvest, TemperatureType},
+            Data,
+        },
+        AppConfigFields, DataFilters, Filter,
+    },
+    BottomEvent, Pid,
+};
+
+/// The script run on the host. `SLEEP_SECONDS` is replaced with the update rate.
+///
+/// Process names and commands can contain newlines, so they're kept from starting a line that
+/// looks like a section: lines of `ps` that don't start with a PID are dropped, and each `stat`
+/// file is joined into one line.
+const SCRIPT: &str = r#"exec 2>/dev/null
+echo @bottom:host
+uname -s
+uname -n
+getconf CLK_TCK
+getconf PAGESIZE
+uname -r
+echo @bottom:passwd
+cat /etc/passwd
+while :; do
+    echo @bottom:uptime; cat /proc/uptime
+    echo @bottom:stat; cat /proc/stat
+    echo @bottom:meminfo; cat /proc/meminfo
+    echo @bottom:vmstat; cat /proc/vmstat
+    echo @bottom:loadavg; cat /proc/loadavg
+    echo @bottom:net; cat /proc/net/dev
+    echo @bottom:diskstats; cat /proc/diskstats
+    echo @bottom:df; df -kP
+    echo @bottom:temp
+    for input in /sys/class/hwmon/hwmon*/temp*_input; do
+        [ -r "$input" ] || continue
+        name=$(cat "${input%/*}/name")
+        [ -r "${input%_input}_label" ] && name="$name: $(cat "${input%_input}_label")"
+        echo "$(cat "$input") $name"
+    done
+    echo @bottom:owners; ls -ln /proc
+    echo @bottom:commands; COLUMNS=4096 ps -eo pid=,args= | grep '^ *[0-9]'
+    echo @bottom:processes
+    for stat in /proc/[0-9]*/stat; do
+        while IFS= read -r line; do printf '%s ' "$line"; done < "$stat" && echo
+    done
+    echo @bottom:end
+    sleep SLEEP_SECONDS
+done
+"#;
+
+/// The line that starts each section of the script's output.
+const SECTION_PREFIX: &str = "@bottom:";
+
+/// Something read from the script's output.
+#[derive(Debug)]
+enum Reading {
+    /// The script has started, on a host with this name.
+    Started(String),
+    Update(Box<Data>),
+}
+
+/// The counters from the last update, which rates are worked out from.
+#[derive(Debug, Default)]
+struct Previous {
+    uptime: f64,
+    average_cpu_times: CpuTimes,
+    cpu_times: Vec<CpuTimes>,
+    total_rx: u64,
+    total_tx: u64,
+    error_counters: FxHashMap<String, NetworkErrorCounters>,
+    process_ticks: FxHashMap<Pid, u64>,
+}
+
+/// Turns the output of [`SCRIPT`] into [`Data`], a line at a time.
+#[derive(Debug)]
+struct SshCollector {
+    temperature_type: TemperatureType,
+    use_current_cpu_total: bool,
+    show_average_cpu: bool,
+    show_cpu_breakdown: bool,
+    filters: DataFilters,
+    section: Option<String>,
+    lines: Vec<String>,
+    sections: HashMap<String, Vec<String>>,
+    ticks_per_second: f64,
+    page_size: u64,
+    #[cfg_attr(not(target_family = "unix"), allow(dead_code))]
+    users: HashMap<u32, String>,
+    /// The host name and kernel version, which are only sent once.
+    system_info: SystemInfoHarvest,
+    previous: Option<Previous>,
+}
+
+impl SshCollector {
+    fn new(app_config_fields: &AppConfigFields, filters: DataFilters) -> Self {
+        Self {
+            temperature_type: app_config_fields.temperature_type.clone(),
+            use_current_cpu_total: app_config_fields.use_current_cpu_total,
+            show_average_cpu: app_config_fields.show_average_cpu,
+            show_cpu_breakdown: app_config_fields.show_cpu_breakdown,
+            filters,
+            section: None,
+            lines: Vec::new(),
+            sections: HashMap::new(),
+            ticks_per_second: 100.0,
+            page_size: 4096,
+            users: HashMap::new(),
+            system_info: SystemInfoHarvest::default(),
+            previous: None,
+        }
+    }
+
+    /// Reads a line of output. Returns an error if the host isn't one that can be collected from.
+    fn read_line(&mut self, line: &str) -> Result<Option<Reading>, String> {
+        let next_section = match line.strip_prefix(SECTION_PREFIX) {
+            Some(next_section) => next_section,
+            None => {
+                if self.section.is_some() {
+                    self.lines.push(line.to_string());
+                }
+                return Ok(None);
+            }
+        };
+
+        let lines = std::mem::take(&mut self.lines);
+        let reading = match self.section.replace(next_section.to_string()).as_deref() {
+            Some("host") => Some(self.read_host(&lines)?),
+            Some("passwd") => {
+                self.users = parse_passwd(&lines);
+                None
+            }
+            Some(section) => {
+                self.sections.insert(section.to_string(), lines);
+                None
+            }
+            None => None,
+        };
+
+        if next_section == "end" {
+            self.section = None;
+            Ok(self.harvest().map(|data| Reading::Update(Box::new(data))))
+        } else {
+            Ok(reading)
+        }
+    }
+
+    fn read_host(&mut self, lines: &[String]) -> Result<Reading, String> {
+        match lines {
+            [os, hostname, ticks_per_second, page_size, rest @ ..] => {
+                if os != "Linux" {
+                    return Err(format!(
+                        "the host runs {}, but only Linux hosts can be collected from over SSH",
+                        os
+                    ));
+                }
+                if let Ok(ticks_per_second) = ticks_per_second.parse::<f64>() {
+                    if ticks_per_second > 0.0 {
+                        self.ticks_per_second = ticks_per_second;
+                    }
+                }
+                if let Ok(page_size) = page_size.parse() {
+                    self.page_size = page_size;
+                }
+                self.system_info = SystemInfoHarvest {
+                    hostname: Some(hostname.clone()),
+                    os_name: Some(os.clone()),
+                    kernel_version: rest.first().cloned(),
+                    ..SystemInfoHarvest::default()
+                };
+                Ok(Reading::Started(hostname.clone()))
+            }
+            _ => Err("the host didn't run the collection script".to_string()),
+        }
+    }
+
+    /// Turns the sections of an update into [`Data`]. Nothing is returned for the first update, as
+    /// usage is worked out from the difference between two.
+    fn harvest(&mut self) -> Option<Data> {
+        let sections = std::mem::take(&mut self.sections);
+        let section = |name: &str| sections.get(name).map(Vec::as_slice).unwrap_or(&[]);
+
+        let uptime = section("uptime")
+            .first()
+            .and_then(|line| line.split_whitespace().next()?.parse::<f64>().ok())?;
+        let (average_cpu_times, cpu_times, boot_time) =
+            parse_stat(section("stat"), self.ticks_per_second)?;
+        let memory = parse_meminfo(section("meminfo"));
+        let (total_rx, total_tx, error_counters) =
+            parse_net_dev(section("net"), &self.filters.net_filter);
+
+        let previous = match self.previous.take() {
+            Some(previous) => previous,
+            None => {
+                self.previous = Some(Previous {
+                    uptime,
+                    average_cpu_times,
+                    cpu_times,
+                    total_rx,
+                    total_tx,
+                    error_counters,
+                    process_ticks: parse_processes(section("processes"))
+                        .map(|process| (process.pid, process.ticks))
+                        .collect(),
+                });
+                return None;
+            }
+        };
+        let elapsed_secs = uptime - previous.uptime;
+        let get_rate = |current: u64, previous: u64| {
+            if elapsed_secs > 0.0 {
+                (current.saturating_sub(previous) as f64 / elapsed_secs) as u64
+            } else {
+                0
+            }
+        };
+
+        let get_cpu_data = |cpu_prefix: &str, cpu_count, current: &CpuTimes, previous| CpuData {
+            cpu_prefix: cpu_prefix.to_string(),
+            cpu_count,
+            cpu_usage: current.usage_since(previous),
+            breakdown: if self.show_cpu_breakdown {
+                Some(current.breakdown_since(previous))
+            } else {
+                None
+            },
+        };
+        let mut cpu = Vec::new();
+        if self.show_average_cpu {
+            cpu.push(get_cpu_data(
+                "AVG",
+                None,
+                &average_cpu_times,
+                &previous.average_cpu_times,
+            ));
+        }
+        for (index, times) in cpu_times.iter().enumerate() {
+            let previous_times = previous.cpu_times.get(index).unwrap_or(times);
+            cpu.push(get_cpu_data("CPU", Some(index), times, previous_times));
+        }
+
+        let mut previous_error_counters = previous.error_counters;
+        let error_data = get_error_data(error_counters, &mut previous_error_counters, elapsed_secs);
+        let network = NetworkHarvest {
+            rx: get_rate(total_rx, previous.total_rx),
+            tx: get_rate(total_tx, previous.total_tx),
+            total_rx,
+            total_tx,
+            errors_per_sec: error_data.errors_per_sec,
+            drops_per_sec: error_data.drops_per_sec,
+            total_errors: error_data.total_errors,
+            total_drops: error_data.total_drops,
+            lossy_interfaces: error_data.lossy_interfaces,
+        };
+
+        // Process usage is a share of the time every CPU spent, like it is locally.
+        let cpu_secs = if self.use_current_cpu_total {
+            average_cpu_times.work - previous.average_cpu_times.work
+        } else {
+            average_cpu_times.total - previous.average_cpu_times.total
+        };
+        let mem_total_in_kib = memory.0.as_ref().map_or(0, |mem| mem.mem_total_in_kib);
+        #[cfg(target_family = "unix")]
+        let owners = parse_owners(section("owners"));
+        let commands = parse_commands(section("commands"));
+        let previous_process_ticks = &previous.process_ticks;
+        let mut process_ticks = FxHashMap::default();
+        let list_of_processes = parse_processes(section("processes"))
+            .map(|process| {
+                process_ticks.insert(process.pid, process.ticks);
+                let cpu_usage_percent = match previous_process_ticks.get(&process.pid) {
+                    Some(previous_ticks) if cpu_secs > 0.0 => {
+                        process.ticks.saturating_sub(*previous_ticks) as f64
+                            / self.ticks_per_second
+                            / cpu_secs
+                            * 100.0
+                    }
+                    _ => 0.0,
+                };
+                let mem_usage_bytes = process.rss_pages * self.page_size;
+
+                #[allow(unused_mut)]
+                let mut harvest = ProcessHarvest {
+                    pid: process.pid,
+                    parent_pid: Some(process.parent_pid),
+                    cpu_usage_percent,
+                    mem_usage_percent: if mem_total_in_kib == 0 {
+                        0.0
+                    } else {
+                        (mem_usage_bytes / 1024) as f64 / mem_total_in_kib as f64 * 100.0
+                    },
+                    mem_usage_bytes,
+                    virtual_bytes: process.virtual_bytes,
+                    command: commands
+                        .get(&process.pid)
+                        .cloned()
+                        .unwrap_or_else(|| process.name.clone()),
+                    name: process.name,
+                    process_state: (get_state_name(process.state).to_string(), process.state),
+                    num_threads: process.num_threads,
+                    last_cpu: process.last_cpu,
+                    start_time: Some(
+                        boot_time + (process.start_ticks as f64 / self.ticks_per_second) as u64,
+                    ),
+                    ..ProcessHarvest::default()
+                };
+
+                #[cfg(target_family = "unix")]
+                if let Some(uid) = owners.get(&process.pid) {
+                    harvest.uid = *uid;
+                    harvest.user = self
+                        .users
+                        .get(uid)
+                        .cloned()
+                        .unwrap_or_else(|| "N/A".to_string())
+                        .into();
+                }
+
+                harvest
+            })
+            .collect();
+
+        self.previous = Some(Previous {
+            uptime,
+            average_cpu_times,
+            cpu_times,
+            total_rx,
+            total_tx,
+            error_counters: previous_error_counters,
+            process_ticks,
+        });
+
+        Some(Data {
+            cpu: Some(cpu),
+            load_avg: parse_loadavg(section("loadavg")),
+            memory: memory.0,
+            swap: memory.1,
+            swap_activity: parse_vmstat(section("vmstat").iter().map(String::as_str)),
+            temperature_sensors: Some(parse_temperatures(
+                section("temp"),
+                &self.temperature_type,
+                &self.filters.temp_filter,
+            )),
+            network: Some(network),
+            list_of_processes: Some(list_of_processes),
+            disks: Some(parse_df(
+                section("df"),
+                &self.filters.disk_filter,
+                &self.filters.mount_filter,
+            )),
+            io: Some(parse_diskstats(section("diskstats"))),
+            system_info: Some(SystemInfoHarvest {
+                uptime_secs: uptime as u64,
+                ..self.system_info.clone()
+            }),
+            ..Data::default()
+        })
+    }
+}
+
+/// A process as read from its `/proc/<pid>/stat` line.
+#[derive(Debug)]
+struct ProcessStat {
+    pid: Pid,
+    name: String,
+    state: char,
+    parent_pid: Pid,
+    /// User and system time, in clock ticks.
+    ticks: u64,
+    num_threads: u64,
+    /// When it started, in clock ticks since boot.
+    start_ticks: u64,
+    virtual_bytes: u64,
+    rss_pages: u64,
+    last_cpu: Option<usize>,
+}
+
+/// Returns the times of all CPUs together and of each CPU, and the boot time in seconds since the
+/// Unix epoch, from `/proc/stat`.
+fn parse_stat(lines: &[String], ticks_per_second: f64) -> Option<(CpuTimes, Vec<CpuTimes>, u64)> {
+    let mut average_cpu_times = None;
+    let mut cpu_times = Vec::new();
+    let mut boot_time = 0;
+
+    for line in lines {
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("cpu") => average_cpu_times = Some(parse_cpu_times(fields, ticks_per_second)),
+            Some(name) if name.starts_with("cpu") => {
+                cpu_times.push(parse_cpu_times(fields, ticks_per_second))
+            }
+            Some("btime") => boot_time = fields.next()?.parse().ok()?,
+            _ => {}
+        }
+    }
+
+    Some((average_cpu_times?, cpu_times, boot_time))
+}
+
+/// Reads the times from a CPU's line of `/proc/stat`, which are in clock ticks.
+fn parse_cpu_times<'a>(fields: impl Iterator<Item = &'a str>, ticks_per_second: f64) -> CpuTimes {
+    let mut times = [0.0; 8];
+    for (time, field) in times.iter_mut().zip(fields) {
+        *time = field.parse::<f64>().unwrap_or(0.0) / ticks_per_second;
+    }
+    let [user, nice, system, idle, io_wait, irq, soft_irq, steal] = times;
+
+    let states = [user + nice, system, io_wait, irq + soft_irq, steal];
+    let work = user + nice + system + irq + soft_irq + steal;
+    CpuTimes {
+        work,
+        total: work + idle + io_wait,
+        states,
+    }
+}
+
+/// Returns memory and swap usage from `/proc/meminfo`, with used memory worked out like it is
+/// locally.
+fn parse_meminfo(lines: &[String]) -> (Option<MemHarvest>, Option<MemHarvest>) {
+    let values: HashMap<&str, u64> = lines
+        .iter()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            Some((key, value.split_whitespace().next()?.parse().ok()?))
+        })
+        .collect();
+    let value = |key: &str| values.get(key).copied().unwrap_or(0);
+
+    let to_harvest = |mem_total_in_kib: u64, mem_used_in_kib: u64| MemHarvest {
+        mem_total_in_kib,
+        mem_used_in_kib,
+        use_percent: if mem_total_in_kib == 0 {
+            None
+        } else {
+            Some(mem_used_in_kib as f64 / mem_total_in_kib as f64 * 100.0)
+        },
+    };
+
+    let memory = values.get("MemTotal").map(|&total| {
+        let mem_free = value("MemFree");
+        let cached = (value("Cached") + value("SReclaimable")).saturating_sub(value("Shmem"));
+        let used_diff = mem_free + cached + value("Buffers");
+        let used = if total >= used_diff {
+            total - used_diff
+        } else {
+            total.saturating_sub(mem_free)
+        };
+        to_harvest(total, used)
+    });
+    let swap = values
+        .get("SwapTotal")
+        .map(|&total| to_harvest(total, total.saturating_sub(value("SwapFree"))));
+
+    (memory, swap)
+}
+
+fn parse_loadavg(lines: &[String]) -> Option<[f32; 3]> {
+    let mut fields = lines.first()?.split_whitespace();
+    Some([
+        fields.next()?.parse().ok()?,
+        fields.next()?.parse().ok()?,
+        fields.next()?.parse().ok()?,
+    ])
+}
+
+/// Returns the total bits received and sent by the kept interfaces, and their error counters,
+/// from `/proc/net/dev`.
+fn parse_net_dev(
+    lines: &[String], filter: &Option<Filter>,
+) -> (u64, u64, FxHashMap<String, NetworkErrorCounters>) {
+    let mut total_rx = 0;
+    let mut total_tx = 0;
+    let mut error_counters = FxHashMap::default();
+
+    for line in lines {
+        let (interface, counters) = match line.split_once(':') {
+            Some((interface, counters)) => (interface.trim(), counters),
+            None => continue,
+        };
+        if !is_interface_kept(interface, filter) {
+            continue;
+        }
+
+        let counters = counters
+            .split_whitespace()
+            .map(|counter| counter.parse::<u64>().unwrap_or(0))
+            .collect::<Vec<_>>();
+        if counters.len() < 12 {
+            continue;
+        }
+
+        total_rx += counters[0] * 8;
+        total_tx += counters[8] * 8;
+        error_counters.insert(
+            interface.to_string(),
+            NetworkErrorCounters {
+                rx_errors: counters[2],
+                tx_errors: counters[10],
+                rx_drops: counters[3],
+                tx_drops: counters[11],
+            },
+        );
+    }
+
+    (total_rx, total_tx, error_counters)
+}
+
+/// Returns the usage of each disk from the output of `df -kP`. Only filesystems on devices are
+/// kept, like only physical partitions are locally.
+fn parse_df(
+    lines: &[String], disk_filter: &Option<Filter>, mount_filter: &Option<Filter>,
+) -> Vec<DiskHarvest> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            if fields.len() < 6 || !fields[0].starts_with("/dev/") {
+                return None;
+            }
+            let name = fields[0].to_string();
+            let mount_point = fields[5..].join(" ");
+            if !is_disk_kept(&name, &mount_point, disk_filter, mount_filter) {
+                return None;
+            }
+
+            let kib = |field: &str| field.parse::<u64>().ok().map(|kib| kib * 1024);
+            Some(DiskHarvest {
+                name,
+                mount_point,
+                fs_type: None,
+                free_space: kib(fields[3]),
+                used_space: kib(fields[2]),
+                total_space: kib(fields[1]),
+                total_inodes: None,
+                free_inodes: None,
+                smart: None,
+                activity: None,
+            })
+        })
+        .collect()
+}
+
+/// Whether a disk is kept by the disk and mount filters. Like locally, a disk matching either
+/// allow list is kept even if the other filter would ignore it.
+fn is_disk_kept(
+    name: &str, mount_point: &str, disk_filter: &Option<Filter>, mount_filter: &Option<Filter>,
+) -> bool {
+    let checks = [(disk_filter, name), (mount_filter, mount_point)];
+    let matches = |is_list_ignored: bool| {
+        checks.iter().any(|(filter, text)| match filter {
+            Some(filter) if filter.is_list_ignored == is_list_ignored => {
+                filter.list.iter().any(|r| r.is_match(text))
+            }
+            _ => false,
+        })
+    };
+
+    matches(false) || !matches(true)
+}
+
+/// Returns the bytes read and written by each block device from `/proc/diskstats`.
+fn parse_diskstats(lines: &[String]) -> IoHarvest {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let sectors = |index: usize| fields.get(index)?.parse::<u64>().ok();
+            let io = IoData {
+                read_bytes: sectors(5)? * 512,
+                write_bytes: sectors(9)? * 512,
+            };
+            Some((fields[2].to_string(), Some(io)))
+        })
+        .collect()
+}
+
+/// Reads the temperature sensors printed as millidegrees Celsius followed by the sensor's name.
+fn parse_temperatures(
+    lines: &[String], temperature_type: &TemperatureType, filter: &Option<Filter>,
+) -> Vec<TempHarvest> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let (millidegrees, name) = line.split_once(' ')?;
+            let celsius = millidegrees.parse::<f32>().ok()? / 1000.0;
+            if !is_temp_filtered(filter, name) {
+                return None;
+            }
+
+            Some(TempHarvest {
+                name: name.to_string(),
+                temperature: temperature_type.convert_from_celsius(celsius),
+            })
+        })
+        .collect()
+}
+
+/// Returns the user name of each uid from `/etc/passwd`.
+fn parse_passwd(lines: &[String]) -> HashMap<u32, String> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+            let name = fields.next()?;
+            let uid = fields.nth(1)?.parse().ok()?;
+            Some((uid, name.to_string()))
+        })
+        .collect()
+}
+
+/// Returns the uid owning each process from the output of `ls -ln /proc`.
+#[cfg(target_family = "unix")]
+fn parse_owners(lines: &[String]) -> FxHashMap<Pid, u32> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            if fields.len() < 9 {
+                return None;
+            }
+            Some((fields.last()?.parse().ok()?, fields[2].parse().ok()?))
+        })
+        .collect()
+}
+
+/// Returns the command of each process from the output of `ps -eo pid=,args=`.
+fn parse_commands(lines: &[String]) -> FxHashMap<Pid, String> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let (pid, command) = line.trim_start().split_once(' ')?;
+            Some((pid.parse().ok()?, command.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Reads each `/proc/<pid>/stat` line, skipping any that can't be read.
+fn parse_processes(lines: &[String]) -> impl Iterator<Item = ProcessStat> + '_ {
+    lines.iter().filter_map(|line| {
+        // The name is in brackets, and can itself contain spaces and brackets.
+        let (pid, rest) = line.split_once(" (")?;
+        let (name, rest) = rest.rsplit_once(") ")?;
+        let fields = rest.split_whitespace().collect::<Vec<_>>();
+        let field = |index: usize| fields.get(index)?.parse::<u64>().ok();
+
+        Some(ProcessStat {
+            pid: pid.parse().ok()?,
+            name: name.to_string(),
+            state: fields.first()?.chars().next()?,
+            parent_pid: fields.get(1)?.parse().ok()?,
+            ticks: field(11)? + field(12)?,
+            num_threads: field(17)?,
+            start_ticks: field(19)?,
+            virtual_bytes: field(20)?,
+            rss_pages: field(21)?,
+            last_cpu: field(36).map(|cpu| cpu as usize),
+        })
+    })
+}
+
+/// Returns the name of a process state, as named locally.
+fn get_state_name(state: char) -> &'static str {
+    match state {
+        'R' => "Runnable",
+        'S' => "Sleeping",
+        'D' => "UninterruptibleDiskSleep",
+        'Z' => "Zombie",
+        'T' => "Stopped",
+        't' => "Tracing",
+        'X' | 'x' => "Dead",
+        'K' => "Wakekill",
+        'W' => "Waking",
+        'P' => "Parked",
+        'I' => "Idle",
+        _ => "Unknown",
+    }
+}
+
+/// Creates a thread that collects from the host at index `host` of
+/// [`App::remote_hosts`](crate::app::App::remote_hosts) over SSH, in place of the collection
+/// thread. If `ssh` exits, it's run again.
+pub fn create_ssh_thread(
+    sender: mpsc::Sender<BottomEvent<KeyEvent, MouseEvent>>, host: usize, destination: String,
+    app_config_fields: &AppConfigFields, filters: DataFilters,
+    termination_ctrl_lock: Arc<Mutex<bool>>, termination_ctrl_cvar: Arc<Condvar>,
+) -> thread::JoinHandle<()> {
+    // Fractions of a second can't be slept for with every `sleep`.
+    let sleep_seconds = (app_config_fields.update_rate_in_milliseconds as f64 / 1000.0).ceil();
+    let script = SCRIPT.replace("SLEEP_SECONDS", &sleep_seconds.max(1.0).to_string());
+    let mut collector = SshCollector::new(app_config_fields, filters);
+
+    thread::spawn(move || loop {
+        collector.previous = None;
+        match collect(
+            &sender,
+            host,
+            &destination,
+            &script,
+            &mut collector,
+            &termination_ctrl_lock,
+        ) {
+            Ok(()) => break,
+            Err(err) => {
+                let event = BottomEvent::RemoteStatus(host, HostStatus::Disconnected(err));
+                if sender.send(event).is_err() {
+                    break;
+                }
+            }
+        }
+
+        if let Ok((is_terminated, _wait_timeout_result)) = termination_ctrl_cvar
+            .wait_timeout(termination_ctrl_lock.lock().unwrap(), RECONNECT_INTERVAL)
+        {
+            if *is_terminated {
+                break;
+            }
+        }
+    })
+}
+
+/// Sends each line read from `output` until it ends. Lines that aren't valid UTF-8, like a
+/// process name in another encoding, are sent with the invalid parts replaced.
+fn send_lines(output: impl Read, line_sender: &mpsc::Sender<String>) {
+    let mut reader = BufReader::new(output);
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        match reader.read_until(b'\n', &mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let line = String::from_utf8_lossy(&buffer);
+                let line = line.trim_end_matches(&['\n', '\r'][..]);
+                if line_sender.send(line.to_string()).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Runs the script over SSH and passes on what it collects until bottom quits, which returns
+/// `Ok`, or `ssh` exits, which returns why.
+fn collect(
+    sender: &mpsc::Sender<BottomEvent<KeyEvent, MouseEvent>>, host: usize, destination: &str,
+    script: &str, collector: &mut SshCollector, termination_ctrl_lock: &Mutex<bool>,
+) -> Result<(), String> {
+    // Batch mode fails rather than asking for a password, which would mess up the terminal.
+    let mut child = Command::new("ssh")
+        .args(["-T", "-o", "BatchMode=yes", "-o", "ConnectTimeout=5"])
+        .args(["-o", "ServerAliveInterval=5", "--", destination, "sh"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("ssh couldn't be run ({})", err))?;
+
+    // The whole script is read by the shell before the loop starts, so stdin is closed here.
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(script.as_bytes());
+    }
+
+    // Reading from a pipe can't time out, so lines are read on another thread, and this one
+    // checks whether bottom is quitting while waiting for them.
+    let (line_sender, line_receiver) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        thread::spawn(move || send_lines(stdout, &line_sender));
+    }
+
+    loop {
+        match line_receiver.recv_timeout(READ_TIMEOUT) {
+            Ok(line) => {
+                let reading = match collector.read_line(&line) {
+                    Ok(reading) => reading,
+                    Err(err) => {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                        return Err(err);
+                    }
+                };
+                let event = match reading {
+                    Some(Reading::Started(hostname)) => {
+                        BottomEvent::RemoteStatus(host, HostStatus::Connected(hostname))
+                    }
+                    Some(Reading::Update(data)) => BottomEvent::RemoteUpdate(host, data),
+                    None => continue,
+                };
+                if sender.send(event).is_err() {
+                    let _ = child.kill();
+                    return Ok(());
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if *termination_ctrl_lock.lock().unwrap() {
+                    let _ = child.kill();
+                    return Ok(());
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    Err(get_exit_reason(&mut child))
+}
+
+/// Returns why `ssh` exited, which is usually the last line it printed.
+fn get_exit_reason(child: &mut Child) -> String {
+    let mut stderr = String::new();
+    if let Some(mut stderr_pipe) = child.stderr.take() {
+        let _ = stderr_pipe.read_to_string(&mut stderr);
+    }
+    let status = child.wait();
+
+    match stderr.lines().rev().find(|line| !line.trim().is_empty()) {
+        Some(line) => line.trim().to_string(),
+        None => match status {
+            Ok(status) => format!("ssh exited ({})", status),
+            Err(err) => err.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn new_collector() -> SshCollector {
+        SshCollector {
+            temperature_type: TemperatureType::Celsius,
+            use_current_cpu_total: false,
+            show_average_cpu: true,
+            show_cpu_breakdown: false,
+            filters: DataFilters {
+                disk_filter: None,
+                mount_filter: None,
+                temp_filter: None,
+                net_filter: None,
+            },
+            section: None,
+            lines: Vec::new(),
+            sections: HashMap::new(),
+            ticks_per_second: 100.0,
+            page_size: 4096,
+            users: HashMap::new(),
+            system_info: SystemInfoHarvest::default(),
+            previous: None,
+        }
+    }
+
+    fn to_lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_processes() {
+        let lines = to_lines(
+            "42 (tmux: server) S 1 42 42 0 -1 4194560 1000 0 0 0 150 50 0 0 20 0 1 0 12345 \
+             10240000 500 18446744073709551615 1 1 0 0 0 0 0 0 0 0 0 0 17 3 0 0 0 0 0\n\
+             43 (broken",
+        );
+        let processes = parse_processes(&lines).collect::<Vec<_>>();
+
+        assert_eq!(processes.len(), 1);
+        let process = &processes[0];
+        assert_eq!(process.pid, 42);
+        assert_eq!(process.name, "tmux: server");
+        assert_eq!(process.state, 'S');
+        assert_eq!(process.parent_pid, 1);
+        assert_eq!(process.ticks, 200);
+        assert_eq!(process.num_threads, 1);
+        assert_eq!(process.start_ticks, 12345);
+        assert_eq!(process.virtual_bytes, 10240000);
+        assert_eq!(process.rss_pages, 500);
+        assert_eq!(process.last_cpu, Some(3));
+    }
+
+    #[test]
+    fn test_parse_meminfo() {
+        let (memory, swap) = parse_meminfo(&to_lines(
+            "MemTotal:       16000000 kB\n\
+             MemFree:         4000000 kB\n\
+             Buffers:         1000000 kB\n\
+             Cached:          3000000 kB\n\
+             Shmem:           1000000 kB\n\
+             SReclaimable:    1000000 kB\n\
+             SwapTotal:       2000000 kB\n\
+             SwapFree:        1500000 kB",
+        ));
+
+        let memory = memory.unwrap();
+        assert_eq!(memory.mem_total_in_kib, 16000000);
+        assert_eq!(memory.mem_used_in_kib, 8000000);
+        assert_eq!(memory.use_percent, Some(50.0));
+        assert_eq!(swap.unwrap().mem_used_in_kib, 500000);
+    }
+
+    #[test]
+    fn test_parse_df() {
+        let lines = to_lines(
+            "Filesystem     1024-blocks     Used Available Capacity Mounted on\n\
+             /dev/sda1        100000000 40000000  60000000      40% /\n\
+             tmpfs              1000000        0   1000000       0% /run\n\
+             /dev/sdb1          2000000  1000000   1000000      50% /mnt/my disk",
+        );
+        let disks = parse_df(&lines, &None, &None);
+
+        assert_eq!(disks.len(), 2);
+        assert_eq!(disks[0].name, "/dev/sda1");
+        assert_eq!(disks[0].total_space, Some(100000000 * 1024));
+        assert_eq!(disks[1].mount_point, "/mnt/my disk");
+
+        let filter = Some(Filter {
+            is_list_ignored: true,
+            list: vec![regex::Regex::new("sdb").unwrap()],
+        });
+        assert_eq!(parse_df(&lines, &filter, &None).len(), 1);
+    }
+
+    #[test]
+    fn test_collect_updates() {
+        let mut collector = new_collector();
+        let update = |uptime: u64, cpu_ticks: u64, process_ticks: u64| {
+            format!(
+                "@bottom:uptime\n{}.00 0.00\n\
+                 @bottom:stat\ncpu  {} 0 0 {} 0 0 0 0 0 0\ncpu0 {} 0 0 {} 0 0 0 0 0 0\n\
+                 btime 1700000000\n\
+                 @bottom:net\n  eth0: {} 10 0 0 0 0 0 0 {} 10 0 0 0 0 0 0\n\
+                 @bottom:owners\ndr-xr-xr-x 9 1000 1000 0 Oct 16 14:40 7\n\
+                 @bottom:commands\n    7 /usr/bin/worker --fast\n\
+                 @bottom:processes\n7 (worker) R 1 7 7 0 -1 0 0 0 0 0 {} 0 0 0 20 0 2 0 500 \
+                 1000 10 0\n\
+                 @bottom:end",
+                uptime,
+                cpu_ticks,
+                cpu_ticks,
+                cpu_ticks,
+                cpu_ticks,
+                uptime * 1000,
+                uptime * 2000,
+                process_ticks,
+            )
+        };
+        let mut read = |text: &str| {
+            text.lines()
+                .filter_map(|line| collector.read_line(line).unwrap())
+                .last()
+        };
+
+        assert!(matches!(
+            read("@bottom:host\nLinux\nserver\n100\n4096\n@bottom:passwd\nme:x:1000:1000::/:/bin/sh"),
+            Some(Reading::Started(hostname)) if hostname == "server"
+        ));
+        assert!(read(&update(10, 500, 100)).is_none());
+
+        let data = match read(&update(12, 600, 150)) {
+            Some(Reading::Update(data)) => data,
+            reading => panic!("expected an update, got {:?}", reading),
+        };
+        let cpu = data.cpu.unwrap();
+        assert_eq!(cpu.len(), 2);
+        assert_eq!(cpu[0].cpu_prefix, "AVG");
+        assert_eq!(cpu[0].cpu_usage, 50.0);
+        assert_eq!(cpu[1].cpu_count, Some(0));
+
+        let network = data.network.unwrap();
+        assert_eq!(network.rx, 8000);
+        assert_eq!(network.tx, 16000);
+
+        let processes = data.list_of_processes.unwrap();
+        assert_eq!(processes.len(), 1);
+        assert_eq!(processes[0].command, "/usr/bin/worker --fast");
+        assert_eq!(processes[0].cpu_usage_percent, 25.0);
+        assert_eq!(processes[0].start_time, Some(1700000005));
+        #[cfg(target_family = "unix")]
+        assert_eq!(processes[0].user, "me");
+    }
+
+    #[test]
+    fn test_send_lines() {
+        let (line_sender, line_receiver) = mpsc::channel();
+        send_lines(
+            &b"@bottom:processes\r\n7 (caf\xe9) S\nlast"[..],
+            &line_sender,
+        );
+        drop(line_sender);
+
+        assert_eq!(
+            line_receiver.iter().collect::<Vec<_>>(),
+            vec!["@bottom:processes", "7 (caf\u{fffd}) S", "last"]
+        );
+    }
+
+    #[test]
+    fn test_read_host() {
+        let mut collector = new_collector();
+        let result = [
+            "@bottom:host",
+            "FreeBSD",
+            "server",
+            "128",
+            "4096",
+            "@bottom:passwd",
+        ]
+        .iter()
+        .map(|line| collector.read_line(line))
+        .last()
+        .unwrap();
+
+        assert!(result.is_err());
+    }
+}
@@ -87,6 +87,12 @@ impl From<fern::InitError> for BottomError {
     }
 }
 
+impl From<serde_json::Error> for BottomError {
+    fn from(err: serde_json::Error) -> Self {
+        BottomError::ConversionError(err.to_string())
+    }
+}
+
 impl From<std::str::Utf8Error> for BottomError {
     fn from(err: std::str::Utf8Error) -> Self {
         BottomError::ConversionError(err.to_string())
@@ -117,6 +117,45 @@ pub fn partial_ordering_rev<T: std::cmp::PartialOrd>(a: T, b: T) -> Ordering {
     partial_ordering(a, b).reverse()
 }
 
+/// Encodes bytes as standard base64, with padding.
+pub fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len() / 3 * 4 + 4);
+    for chunk in bytes.chunks(3) {
+        let value = chunk
+            .iter()
+            .enumerate()
+            .fold(0_u32, |value, (index, byte)| {
+                value | u32::from(*byte) << (16 - 8 * index)
+            });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(char::from(
+                    ALPHABET[(value >> (18 - 6 * index) & 0x3f) as usize],
+                ));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+/// Returns the candidate most like `input`, if any are close enough that `input` is likely a typo
+/// of it. This uses the same measure and threshold as clap's suggestions.
+pub fn get_closest_match<'a>(input: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    const SIMILARITY_THRESHOLD: f64 = 0.8;
+
+    candidates
+        .iter()
+        .map(|candidate| (*candidate, strsim::jaro(input, candidate)))
+        .filter(|(_, similarity)| *similarity > SIMILARITY_THRESHOLD)
+        .max_by(|(_, a), (_, b)| partial_ordering(a, b))
+        .map(|(candidate, _)| candidate)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -138,4 +177,29 @@ mod test {
         y.sort_by(|a, b| sort_partial_fn(true)(a, b));
         assert_eq!(y, vec![16.15, 15.0, 1.0, -1.0, -100.0, -100.0, -100.1]);
     }
+
+    #[test]
+    fn test_get_closest_match() {
+        let candidates = ["hide_avg_cpu", "hide_time", "basic"];
+
+        assert_eq!(
+            get_closest_match("hide_avg_cp", &candidates),
+            Some("hide_avg_cpu")
+        );
+        assert_eq!(
+            get_closest_match("hidetime", &candidates),
+            Some("hide_time")
+        );
+        assert_eq!(get_closest_match("colours", &candidates), None);
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_encode(&[0, 0, 0]), "AAAA");
+    }
 }
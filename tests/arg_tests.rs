@@ -176,3 +176,17 @@ fn test_battery_flag() {
             ));
     }
 }
+
+#[test]
+fn test_agent_needs_token() {
+    btm_command()
+        .env_remove("BTM_TOKEN")
+        .arg("-C")
+        .arg("./tests/empty_config.toml")
+        .arg("--agent")
+        .arg("--listen")
+        .arg("127.0.0.1:0")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("An agent needs a token"));
+}
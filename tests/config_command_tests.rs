@@ -0,0 +1,102 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+mod util;
+use util::*;
+
+// These tests are for the `btm config` subcommands.
+
+#[test]
+fn test_config_check() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/empty_config.toml")
+        .arg("config")
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is valid"));
+}
+
+#[test]
+fn test_config_check_invalid() {
+    btm_command()
+        .arg("config")
+        .arg("check")
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_colour_hex.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid hex colour"));
+}
+
+#[test]
+fn test_config_check_missing() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/missing_config.toml")
+        .arg("config")
+        .arg("check")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unable to read the config file"));
+    assert!(!std::path::Path::new("./tests/invalid_configs/missing_config.toml").exists());
+}
+
+#[test]
+fn test_config_init() {
+    let config_dir = std::env::temp_dir().join(format!("btm_config_init_{}", std::process::id()));
+    let config_path = config_dir.join("bottom.toml");
+
+    btm_command()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("config")
+        .arg("init")
+        .assert()
+        .success();
+    btm_command()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("config")
+        .arg("check")
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+    btm_command()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("config")
+        .arg("init")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--force"));
+    btm_command()
+        .arg("-C")
+        .arg(&config_path)
+        .arg("config")
+        .arg("init")
+        .arg("--force")
+        .assert()
+        .success();
+
+    let _ = std::fs::remove_dir_all(config_dir);
+}
+
+#[test]
+fn test_config_migrate() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/deprecated_config.toml")
+        .arg("config")
+        .arg("migrate")
+        .arg("--dry_run")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hide_avg_cpu = false"))
+        .stdout(predicate::str::contains("avg_cpu = true").not())
+        .stderr(predicate::str::contains("Replaced \"avg_cpu\" in [flags]"))
+        .stderr(predicate::str::contains(
+            "Removed \"show_disabled_data\" in [flags]",
+        ));
+}
@@ -89,7 +89,8 @@ fn test_invalid_colour_name() {
         .arg("./tests/invalid_configs/invalid_colour_name.toml")
         .assert()
         .failure()
-        .stderr(predicate::str::contains("invalid named colour"));
+        .stderr(predicate::str::contains("invalid named colour"))
+        .stderr(predicate::str::contains("Did you mean \"lightblue\"?"));
 }
 
 #[test]
@@ -141,3 +142,45 @@ fn test_invalid_default_widget_count() {
         .failure()
         .stderr(predicate::str::contains("invalid number"));
 }
+
+#[test]
+fn test_duplicate_layout_name() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/duplicate_layout_name.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is used more than once"));
+}
+
+#[test]
+fn test_invalid_basic_widgets() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_basic_widgets.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("can't be shown in basic mode"));
+}
+
+#[test]
+fn test_invalid_graph_renderer() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_graph_renderer.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid graph renderer"));
+}
+
+#[test]
+fn test_invalid_layout_filter() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_layout_filter.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "isn't one of the saved process filters",
+        ));
+}
@@ -1,6 +1,7 @@
 //! Mocks layout management, so we can check if we broke anything.
 
-use bottom::app::layout_manager::{BottomLayout, BottomWidgetType};
+use bottom::app::layout_editor::{AppLayoutEditState, LayoutEntry};
+use bottom::app::layout_manager::{BottomLayout, BottomWidgetType, LayoutSplit, LayoutSplitKind};
 #[cfg(feature = "battery")]
 use bottom::constants::DEFAULT_BATTERY_LAYOUT;
 use bottom::constants::{DEFAULT_LAYOUT, DEFAULT_WIDGET_ID};
@@ -470,3 +471,378 @@ fn test_proc_custom_layout() {
         Some(13)
     );
 }
+
+#[test]
+/// Tests resizing rows by dragging the split between them.
+fn test_resize_row_split() {
+    let rows = toml::from_str::<Config>(DEFAULT_LAYOUT)
+        .unwrap()
+        .row
+        .unwrap();
+    let mut ret_bottom_layout = test_create_layout(&rows, DEFAULT_WIDGET_ID, None, 1, false);
+
+    // Pretend the rows were drawn with heights of 30, 40, and 30.
+    let split = LayoutSplit {
+        kind: LayoutSplitKind::Row { row: 0 },
+        is_vertical: true,
+        sibling_bounds: vec![(0, 30), (30, 40), (70, 30)],
+        cross_bounds: (0, 100),
+    };
+
+    assert!(split.is_hit(50, 29, 0));
+    assert!(split.is_hit(50, 30, 0));
+    assert!(!split.is_hit(50, 31, 0));
+    assert!(!split.is_hit(100, 29, 0));
+
+    // With padding, the lines next to the split also hit it.
+    assert!(split.is_hit(50, 28, 1));
+    assert!(split.is_hit(50, 31, 1));
+    assert!(!split.is_hit(50, 32, 1));
+
+    // Drag the border up by ten lines.
+    assert!(ret_bottom_layout.resize_split(&split, 19));
+    assert_eq!(ret_bottom_layout.rows[0].row_height_ratio, 20);
+    assert_eq!(ret_bottom_layout.rows[1].row_height_ratio, 50);
+    assert_eq!(ret_bottom_layout.rows[2].row_height_ratio, 30);
+    assert_eq!(ret_bottom_layout.total_row_height_ratio, 100);
+
+    // Dragging too far should do nothing.
+    assert!(!ret_bottom_layout.resize_split(&split, 0));
+    assert!(!ret_bottom_layout.resize_split(&split, 69));
+    assert_eq!(ret_bottom_layout.rows[0].row_height_ratio, 20);
+}
+
+#[test]
+/// Tests that resized ratios are copied back into the config layout.
+fn test_resize_col_split_updates_config() {
+    let mut rows = toml::from_str::<Config>(DEFAULT_LAYOUT)
+        .unwrap()
+        .row
+        .unwrap();
+    let mut ret_bottom_layout = test_create_layout(&rows, DEFAULT_WIDGET_ID, None, 1, false);
+
+    let split = LayoutSplit {
+        kind: LayoutSplitKind::Col { row: 1, col: 0 },
+        is_vertical: false,
+        sibling_bounds: vec![(0, 40), (40, 30)],
+        cross_bounds: (30, 70),
+    };
+    assert!(ret_bottom_layout.resize_split(&split, 49));
+    assert_eq!(ret_bottom_layout.rows[1].total_col_ratio, 70);
+
+    for (row, bottom_row) in rows.iter_mut().zip(&ret_bottom_layout.rows) {
+        row.update_ratios(bottom_row);
+    }
+    let serialized = toml::to_string(&Config {
+        row: Some(rows),
+        ..Config::default()
+    })
+    .unwrap();
+    let rows = toml::from_str::<Config>(&serialized).unwrap().row.unwrap();
+    let new_bottom_layout = test_create_layout(&rows, DEFAULT_WIDGET_ID, None, 1, false);
+
+    assert_eq!(new_bottom_layout.rows[1].children[0].col_width_ratio, 50);
+    assert_eq!(new_bottom_layout.rows[1].children[1].col_width_ratio, 20);
+}
+
+#[test]
+/// Tests adding, moving, resizing, and removing widgets in the layout editor.
+fn test_layout_editor_operations() {
+    let rows = toml::from_str::<Config>(
+        r##"
+[[row]]
+    [[row.child]]
+        type="cpu"
+    [[row.child]]
+        type="mem"
+"##,
+    )
+    .unwrap()
+    .row
+    .unwrap();
+
+    let mut edit_state = AppLayoutEditState::default();
+    edit_state.open(rows);
+    assert_eq!(
+        edit_state.get_entries(),
+        vec![
+            LayoutEntry::Row(0),
+            LayoutEntry::RowChild(0, 0),
+            LayoutEntry::RowChild(0, 1)
+        ]
+    );
+
+    // Stack a widget below the CPU widget, then make it a memory widget.
+    edit_state.select_next();
+    edit_state.add_widget_below();
+    assert_eq!(
+        edit_state.get_selected_entry(),
+        Some(LayoutEntry::ColWidget(0, 0, 1))
+    );
+    edit_state.cycle_selected_type(true);
+    edit_state.cycle_selected_type(true);
+    edit_state.change_selected_ratio(true);
+    edit_state.move_selected(true);
+    assert_eq!(
+        edit_state.get_selected_entry(),
+        Some(LayoutEntry::ColWidget(0, 0, 0))
+    );
+
+    // Add a new row and remove the memory widget.
+    edit_state.add_row();
+    assert_eq!(edit_state.get_selected_entry(), Some(LayoutEntry::Row(1)));
+    edit_state.select_previous();
+    edit_state.remove_selected();
+    assert!(edit_state.has_widgets());
+
+    let rows = edit_state.rows.clone();
+    let ret_bottom_layout = test_create_layout(&rows, DEFAULT_WIDGET_ID, None, 1, false);
+    assert_eq!(ret_bottom_layout.rows.len(), 2);
+
+    let col = &ret_bottom_layout.rows[0].children[0];
+    assert_eq!(col.children.len(), 2);
+    assert_eq!(
+        col.children[0].children[0].widget_type,
+        BottomWidgetType::Mem
+    );
+    assert_eq!(col.children[0].col_row_height_ratio, 2);
+    assert_eq!(
+        col.children[1].children[0].widget_type,
+        BottomWidgetType::Cpu
+    );
+    assert_eq!(ret_bottom_layout.rows[0].children.len(), 1);
+    assert_eq!(
+        ret_bottom_layout.rows[1].children[0].children[0].children[0].widget_type,
+        BottomWidgetType::Empty
+    );
+}
+
+#[test]
+/// Tests that removing every widget from the layout editor is caught.
+fn test_layout_editor_remove_all() {
+    let mut edit_state = AppLayoutEditState::default();
+    edit_state.open(
+        toml::from_str::<Config>(DEFAULT_LAYOUT)
+            .unwrap()
+            .row
+            .unwrap(),
+    );
+    while !edit_state.get_entries().is_empty() {
+        edit_state.remove_selected();
+    }
+
+    assert!(edit_state.rows.is_empty());
+    assert!(!edit_state.has_widgets());
+    assert_eq!(edit_state.get_selected_entry(), None);
+}
+
+#[test]
+/// Tests that widgets in a tabbed column are split into tabs, and that moving up or down from one
+/// leaves the column rather than going into another tab.
+fn test_tabbed_column() {
+    let tabbed_layout = r##"
+[[row]]
+    [[row.child]]
+        type="cpu"
+[[row]]
+    [[row.child]]
+        tabs=true
+        [[row.child.child]]
+            type="mem"
+        [[row.child.child]]
+            type="proc"
+        [[row.child.child]]
+            type="temp"
+[[row]]
+    [[row.child]]
+        type="disk"
+"##;
+    let rows = toml::from_str::<Config>(tabbed_layout)
+        .unwrap()
+        .row
+        .unwrap();
+    let ret_bottom_layout = test_create_layout(&rows, DEFAULT_WIDGET_ID, None, 1, false);
+
+    let find_widget = |widget_type: BottomWidgetType| {
+        ret_bottom_layout
+            .rows
+            .iter()
+            .flat_map(|row| &row.children)
+            .flat_map(|col| &col.children)
+            .flat_map(|col_row| &col_row.children)
+            .find(|widget| widget.widget_type == widget_type)
+            .unwrap()
+    };
+    let cpu = find_widget(BottomWidgetType::Cpu);
+    let mem = find_widget(BottomWidgetType::Mem);
+    let proc = find_widget(BottomWidgetType::Proc);
+    let proc_search = find_widget(BottomWidgetType::ProcSearch);
+    let temp = find_widget(BottomWidgetType::Temp);
+    let disk = find_widget(BottomWidgetType::Disk);
+
+    let tabbed_col = &ret_bottom_layout.rows[1].children[0];
+    assert_eq!(
+        tabbed_col
+            .tabs
+            .iter()
+            .map(|tab| (tab.widget_id, tab.col_rows.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (mem.widget_id, 0..1),
+            (proc.widget_id, 1..3),
+            (temp.widget_id, 3..4)
+        ]
+    );
+
+    // Every widget in the column knows which tab it's in.
+    assert_eq!(proc_search.tab.unwrap().container_id, mem.widget_id);
+    assert_eq!(proc_search.tab.unwrap().tab_widget_id, proc.widget_id);
+    assert!(cpu.tab.is_none());
+
+    // Moving up or down leaves the column, unless it's within the same tab.
+    assert_eq!(mem.up_neighbour, Some(cpu.widget_id));
+    assert_eq!(mem.down_neighbour, Some(disk.widget_id));
+    assert_eq!(proc.up_neighbour, Some(cpu.widget_id));
+    assert_eq!(proc.down_neighbour, Some(proc_search.widget_id));
+    assert_eq!(proc_search.down_neighbour, Some(disk.widget_id));
+    assert_eq!(temp.up_neighbour, Some(cpu.widget_id));
+}
+
+#[test]
+/// Tests that titles and process settings set on widgets in the layout are carried over to each
+/// instance.
+fn test_widget_titles_and_process_options() {
+    let titled_layout = r##"
+[[row]]
+    [[row.child]]
+        type="proc"
+        title="My Services"
+        filter="services"
+    [[row.child]]
+        type="proc"
+        title="Everything"
+        sort="cpu%"
+        sort_order="descending"
+        tree=true
+[[row]]
+    [[row.child]]
+        [[row.child.child]]
+            type="cpu"
+            title="Cores"
+        [[row.child.child]]
+            type="mem"
+"##;
+    let rows = toml::from_str::<Config>(titled_layout)
+        .unwrap()
+        .row
+        .unwrap();
+    let ret_bottom_layout = test_create_layout(&rows, DEFAULT_WIDGET_ID, None, 1, false);
+
+    let widgets = ret_bottom_layout
+        .rows
+        .iter()
+        .flat_map(|row| &row.children)
+        .flat_map(|col| &col.children)
+        .flat_map(|col_row| &col_row.children)
+        .filter(|widget| widget.title.is_some() || widget.widget_type == BottomWidgetType::Mem)
+        .map(|widget| {
+            (
+                widget.widget_type.clone(),
+                widget.title.as_deref(),
+                widget.process_options.filter.as_deref(),
+                widget.process_options.sort.as_deref(),
+                widget.process_options.tree,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        widgets,
+        vec![
+            (
+                BottomWidgetType::Proc,
+                Some("My Services"),
+                Some("services"),
+                None,
+                None
+            ),
+            (
+                BottomWidgetType::Proc,
+                Some("Everything"),
+                None,
+                Some("cpu%"),
+                Some(true)
+            ),
+            (BottomWidgetType::Cpu, Some("Cores"), None, None, None),
+            (BottomWidgetType::Mem, None, None, None, None),
+        ]
+    );
+}
+
+#[test]
+/// Tests picking the layout to show from the breakpoints of the layouts.
+fn test_layout_breakpoints() {
+    let config = toml::from_str::<Config>(
+        r##"
+[[layouts]]
+name = "stacked"
+max_width = 99
+[[layouts.row]]
+  [[layouts.row.child]]
+  type="cpu"
+
+[[layouts]]
+name = "short"
+max_width = 150
+max_height = 30
+[[layouts.row]]
+  [[layouts.row.child]]
+  type="mem"
+
+[[layouts]]
+name = "manual"
+[[layouts.row]]
+  [[layouts.row.child]]
+  type="proc"
+"##,
+    )
+    .unwrap();
+
+    // The first layout whose breakpoints all fit is used.
+    assert_eq!(config.get_breakpoint_layout(0, 80, 50), 1);
+    assert_eq!(config.get_breakpoint_layout(0, 80, 20), 1);
+    assert_eq!(config.get_breakpoint_layout(0, 120, 20), 2);
+    assert_eq!(config.get_breakpoint_layout(3, 120, 20), 2);
+
+    // Without a fitting layout, layouts with breakpoints go back to the default one, and others
+    // are kept.
+    assert_eq!(config.get_breakpoint_layout(1, 120, 50), 0);
+    assert_eq!(config.get_breakpoint_layout(2, 200, 20), 0);
+    assert_eq!(config.get_breakpoint_layout(0, 200, 50), 0);
+    assert_eq!(config.get_breakpoint_layout(3, 200, 50), 3);
+}
+
+#[test]
+/// Tests that the neighbours in basic mode skip over summary rows that aren't shown.
+fn test_basic_layout_summary_widgets() {
+    let layout =
+        BottomLayout::init_basic_default(false, &[BottomWidgetType::Cpu, BottomWidgetType::Net]);
+
+    // The CPU row, the network row, the table arrows, and the tables.
+    assert_eq!(layout.rows.len(), 4);
+
+    let cpu = &layout.rows[0].children[0].children[0].children[0];
+    let net = &layout.rows[1].children[0].children[0].children[0];
+    let tables = &layout.rows[2].children[0].children[0].children[0];
+    assert_eq!(net.widget_type, BottomWidgetType::BasicNet);
+    assert_eq!(cpu.down_neighbour, Some(net.widget_id));
+    assert_eq!(net.up_neighbour, Some(cpu.widget_id));
+    assert_eq!(net.left_neighbour, None);
+    assert_eq!(tables.up_neighbour, Some(net.widget_id));
+
+    let layout = BottomLayout::init_basic_default(false, &[]);
+    assert_eq!(layout.rows.len(), 2);
+    assert_eq!(
+        layout.rows[0].children[0].children[0].children[0].up_neighbour,
+        None
+    );
+}